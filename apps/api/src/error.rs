@@ -8,8 +8,34 @@ use axum::{
     response::{IntoResponse, Response},
 };
 use problem_details::ProblemDetails;
+use serde::Serialize;
 use thiserror::Error;
 
+/// A single invalid field in a request
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct FieldError {
+    /// Path of the offending field, e.g. `config.bucket`
+    pub field: String,
+    pub message: String,
+}
+
+impl FieldError {
+    pub fn new(field: impl Into<String>, message: impl Into<String>) -> Self {
+        Self {
+            field: field.into(),
+            message: message.into(),
+        }
+    }
+}
+
+fn join_field_errors(errors: &[FieldError]) -> String {
+    errors
+        .iter()
+        .map(|e| format!("{}: {}", e.field, e.message))
+        .collect::<Vec<_>>()
+        .join("; ")
+}
+
 /// Base API error type with RFC 7807 support
 #[derive(Debug, Error)]
 pub enum ApiError {
@@ -22,6 +48,10 @@ pub enum ApiError {
     #[error("bad request: {message}")]
     BadRequest { code: &'static str, message: String },
 
+    /// One or more request fields are invalid; all of them are reported
+    #[error("validation failed: {}", join_field_errors(errors))]
+    Validation { errors: Vec<FieldError> },
+
     #[error("unauthorized")]
     Unauthorized,
 
@@ -31,6 +61,17 @@ pub enum ApiError {
     #[error("conflict: {message}")]
     Conflict { message: String },
 
+    /// The request is well-formed but the resource isn't in a state to accept it
+    #[error("unprocessable: {message}")]
+    Unprocessable { code: &'static str, message: String },
+
+    #[error("payload too large: limit is {limit_bytes} bytes")]
+    PayloadTooLarge { limit_bytes: u64 },
+
+    /// A backend the request depends on is failing; try again later
+    #[error("service unavailable: {message}")]
+    ServiceUnavailable { message: String },
+
     #[error("internal server error")]
     Internal(#[source] anyhow::Error),
 }
@@ -49,9 +90,13 @@ impl ApiError {
                 _ => "resource.not_found",
             },
             Self::BadRequest { code, .. } => code,
+            Self::Validation { .. } => "validation.failed",
             Self::Unauthorized => "auth.unauthorized",
             Self::Forbidden { .. } => "auth.forbidden",
             Self::Conflict { .. } => "conflict",
+            Self::Unprocessable { code, .. } => code,
+            Self::PayloadTooLarge { .. } => "upload.too_large",
+            Self::ServiceUnavailable { .. } => "service.unavailable",
             Self::Internal(_) => "internal",
         }
     }
@@ -60,10 +105,13 @@ impl ApiError {
     fn title(&self) -> &'static str {
         match self {
             Self::NotFound { .. } => "Resource Not Found",
-            Self::BadRequest { .. } => "Bad Request",
+            Self::BadRequest { .. } | Self::Validation { .. } => "Bad Request",
             Self::Unauthorized => "Unauthorized",
             Self::Forbidden { .. } => "Forbidden",
             Self::Conflict { .. } => "Conflict",
+            Self::Unprocessable { .. } => "Unprocessable Entity",
+            Self::PayloadTooLarge { .. } => "Payload Too Large",
+            Self::ServiceUnavailable { .. } => "Service Unavailable",
             Self::Internal(_) => "Internal Server Error",
         }
     }
@@ -84,6 +132,11 @@ impl ApiError {
         }
     }
 
+    /// Create a validation error listing every invalid field
+    pub fn validation(errors: Vec<FieldError>) -> Self {
+        Self::Validation { errors }
+    }
+
     /// Create a conflict error with message
     pub fn conflict(message: impl Into<String>) -> Self {
         Self::Conflict {
@@ -91,6 +144,34 @@ impl ApiError {
         }
     }
 
+    /// Create an unprocessable entity error with code and message
+    pub fn unprocessable(code: &'static str, message: impl Into<String>) -> Self {
+        Self::Unprocessable {
+            code,
+            message: message.into(),
+        }
+    }
+
+    /// Create a payload too large error for a byte limit
+    pub fn payload_too_large(limit_bytes: u64) -> Self {
+        Self::PayloadTooLarge { limit_bytes }
+    }
+
+    /// Create a bad request error for a malformed ID of the given entity type
+    pub fn invalid_id(entity: &str, err: &glyph_domain::IdParseError) -> Self {
+        Self::BadRequest {
+            code: "validation.invalid_id",
+            message: format!("invalid {entity} id: {err}"),
+        }
+    }
+
+    /// Create a service unavailable error with message
+    pub fn service_unavailable(message: impl Into<String>) -> Self {
+        Self::ServiceUnavailable {
+            message: message.into(),
+        }
+    }
+
     /// Create a forbidden error with message
     pub fn forbidden(message: impl Into<String>) -> Self {
         Self::Forbidden {
@@ -103,10 +184,13 @@ impl IntoResponse for ApiError {
     fn into_response(self) -> Response {
         let status = match &self {
             ApiError::NotFound { .. } => StatusCode::NOT_FOUND,
-            ApiError::BadRequest { .. } => StatusCode::BAD_REQUEST,
+            ApiError::BadRequest { .. } | ApiError::Validation { .. } => StatusCode::BAD_REQUEST,
             ApiError::Unauthorized => StatusCode::UNAUTHORIZED,
             ApiError::Forbidden { .. } => StatusCode::FORBIDDEN,
             ApiError::Conflict { .. } => StatusCode::CONFLICT,
+            ApiError::Unprocessable { .. } => StatusCode::UNPROCESSABLE_ENTITY,
+            ApiError::PayloadTooLarge { .. } => StatusCode::PAYLOAD_TOO_LARGE,
+            ApiError::ServiceUnavailable { .. } => StatusCode::SERVICE_UNAVAILABLE,
             ApiError::Internal(_) => StatusCode::INTERNAL_SERVER_ERROR,
         };
 
@@ -114,21 +198,31 @@ impl IntoResponse for ApiError {
         let type_uri = format!("https://api.glyph.app/errors/{error_code}");
 
         // Build RFC 7807 Problem Details response
-        ProblemDetails::from_status_code(status)
+        let problem = ProblemDetails::from_status_code(status)
             .with_type(http::Uri::try_from(type_uri.as_str()).unwrap_or_default())
             .with_title(self.title())
-            .with_detail(self.to_string())
-            .into_response()
+            .with_detail(self.to_string());
+
+        match self {
+            // Field errors go in an `errors` extension member so clients can map them to inputs
+            ApiError::Validation { errors } => problem
+                .with_extensions(serde_json::json!({ "errors": errors }))
+                .into_response(),
+            _ => problem.into_response(),
+        }
     }
 }
 
 // Conversion from domain errors
 impl From<glyph_domain::IdParseError> for ApiError {
+    /// Prefer [`ApiError::invalid_id`] where the entity type is known; this
+    /// only has the expected prefix to go on
     fn from(err: glyph_domain::IdParseError) -> Self {
-        ApiError::BadRequest {
-            code: "id.parse_error",
-            message: err.to_string(),
-        }
+        let entity = match &err {
+            glyph_domain::IdParseError::WrongPrefix { expected, .. } => expected,
+            _ => "resource",
+        };
+        ApiError::invalid_id(entity, &err)
     }
 }
 
@@ -155,10 +249,37 @@ mod tests {
         assert_eq!(err.error_code(), "validation.email");
     }
 
+    #[test]
+    fn test_validation_error_lists_every_field() {
+        let err = ApiError::validation(vec![
+            FieldError::new("config.bucket", "is required"),
+            FieldError::new("config.region", "is required"),
+        ]);
+        assert_eq!(err.error_code(), "validation.failed");
+        assert_eq!(
+            err.to_string(),
+            "validation failed: config.bucket: is required; config.region: is required"
+        );
+        assert_eq!(err.into_response().status(), StatusCode::BAD_REQUEST);
+    }
+
+    #[test]
+    fn test_payload_too_large_status() {
+        let response = ApiError::payload_too_large(1024).into_response();
+        assert_eq!(response.status(), StatusCode::PAYLOAD_TOO_LARGE);
+    }
+
     #[test]
     fn test_id_parse_error_conversion() {
         let id_err = glyph_domain::IdParseError::MissingPrefix;
         let api_err: ApiError = id_err.into();
-        assert_eq!(api_err.error_code(), "id.parse_error");
+        assert_eq!(api_err.error_code(), "validation.invalid_id");
+        assert_eq!(api_err.into_response().status(), StatusCode::BAD_REQUEST);
+
+        let wrong: ApiError = "user_01961a8e-7d3a-7f1c-9b2e-4a5c6d7e8f90"
+            .parse::<glyph_domain::TeamId>()
+            .unwrap_err()
+            .into();
+        assert!(wrong.to_string().contains("invalid team id"));
     }
 }
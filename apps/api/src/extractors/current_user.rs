@@ -9,8 +9,12 @@ use std::sync::Arc;
 
 use axum::{extract::FromRequestParts, http::request::Parts};
 use axum_extra::extract::cookie::CookieJar;
-use glyph_auth::{validate_jwt, Auth0Config, Claims, JwksCache, ACCESS_TOKEN_COOKIE};
-use glyph_domain::UserId;
+use glyph_auth::{
+    effective_roles, validate_jwt, Auth0Config, Claims, JwksCache, RevocationStore,
+    ACCESS_TOKEN_COOKIE, ORG_CLAIM,
+};
+use glyph_db::PgOrganizationRepository;
+use glyph_domain::{OrgId, UserId};
 
 use crate::error::ApiError;
 
@@ -21,6 +25,8 @@ pub struct AuthState {
     pub jwks_cache: Arc<JwksCache>,
     /// Auth0 configuration
     pub auth0_config: Arc<Auth0Config>,
+    /// Revoked access token IDs
+    pub revocations: Arc<dyn RevocationStore>,
 }
 
 /// Marker extension indicating development mode is enabled.
@@ -29,6 +35,8 @@ pub struct AuthState {
 pub struct DevMode {
     /// The mock user ID to use in dev mode
     pub mock_user_id: UserId,
+    /// Organization the mock user belongs to
+    pub org_id: OrgId,
 }
 
 /// Authenticated user context extracted from JWT.
@@ -52,22 +60,56 @@ pub struct CurrentUser {
     pub email_verified: bool,
     /// User's display name
     pub name: Option<String>,
-    /// User's roles from Auth0
+    /// User's roles, mapped from the configured Auth0 roles claim
     pub roles: Vec<String>,
+    /// Organization whose projects, teams and data the user can see
+    pub org_id: OrgId,
 }
 
 impl CurrentUser {
     /// Create CurrentUser from validated JWT claims.
-    fn from_claims(claims: Claims) -> Self {
-        Self {
+    ///
+    /// Roles are read from `roles_claim`; roles Glyph doesn't know are dropped.
+    /// The organization comes from [`ORG_CLAIM`], either as an `org_` ID or a
+    /// bare UUID. Tokens without it are rejected unless `single_tenant` puts
+    /// them in [`OrgId::DEFAULT`]; tokens with an unreadable one are rejected.
+    fn from_claims(
+        claims: Claims,
+        roles_claim: &str,
+        single_tenant: bool,
+    ) -> Result<Self, ApiError> {
+        let org_id = match claims.extra.get(ORG_CLAIM) {
+            None if single_tenant => OrgId::DEFAULT,
+            None => {
+                tracing::debug!("Token has no organization claim");
+                return Err(ApiError::Unauthorized);
+            }
+            Some(value) => value
+                .as_str()
+                .and_then(|s| {
+                    s.parse::<OrgId>()
+                        .ok()
+                        .or_else(|| uuid::Uuid::parse_str(s).ok().map(OrgId::from_uuid))
+                })
+                .ok_or_else(|| {
+                    tracing::debug!(claim = %value, "Unreadable organization claim");
+                    ApiError::Unauthorized
+                })?,
+        };
+        let roles = effective_roles(&claims, roles_claim)
+            .into_iter()
+            .map(|role| role.as_str().to_string())
+            .collect();
+        Ok(Self {
             // Placeholder - real user lookup will be added in Phase 4
             user_id: UserId::new(),
             auth0_id: claims.sub,
             email: claims.email,
             email_verified: claims.email_verified.unwrap_or(false),
             name: claims.name,
-            roles: claims.roles.unwrap_or_default(),
-        }
+            roles,
+            org_id,
+        })
     }
 
     /// Check if user has a specific role.
@@ -100,6 +142,7 @@ where
                 email_verified: true,
                 name: Some("Development User".to_string()),
                 roles: vec!["admin".to_string(), "annotator".to_string()],
+                org_id: dev_mode.org_id,
             });
         }
 
@@ -122,19 +165,51 @@ where
             .ok_or(ApiError::Unauthorized)?;
 
         // Validate JWT and extract claims
-        let claims = validate_jwt(&token, &auth_state.jwks_cache, &auth_state.auth0_config)
-            .await
-            .map_err(|e| {
-                tracing::debug!(error = %e, "JWT validation failed");
-                ApiError::Unauthorized
-            })?;
-
-        Ok(CurrentUser::from_claims(claims))
+        let claims = validate_jwt(
+            &token,
+            &auth_state.jwks_cache,
+            &auth_state.auth0_config,
+            auth_state.revocations.as_ref(),
+        )
+        .await
+        .map_err(|e| {
+            tracing::debug!(error = %e, "JWT validation failed");
+            ApiError::Unauthorized
+        })?;
+
+        let user = CurrentUser::from_claims(
+            claims,
+            &auth_state.auth0_config.roles_claim,
+            auth_state.auth0_config.single_tenant,
+        )?;
+
+        // A claim naming an organization that doesn't exist can't see anything
+        if user.org_id != OrgId::DEFAULT {
+            let pool = parts
+                .extensions
+                .get::<sqlx::PgPool>()
+                .ok_or_else(|| ApiError::Internal(anyhow::anyhow!("Database pool not configured")))?
+                .clone();
+            let exists = PgOrganizationRepository::new(pool)
+                .exists(user.org_id)
+                .await
+                .map_err(|e| ApiError::Internal(e.into()))?;
+            if !exists {
+                tracing::debug!(org_id = %user.org_id, "Token names an unknown organization");
+                return Err(ApiError::Unauthorized);
+            }
+        }
+
+        Ok(user)
     }
 }
 
 #[cfg(test)]
 mod tests {
+    use std::collections::HashMap;
+
+    use glyph_auth::DEFAULT_ROLES_CLAIM;
+
     use super::*;
 
     fn test_claims() -> Claims {
@@ -144,17 +219,19 @@ mod tests {
             aud: glyph_auth::Audience::Single("api://glyph".to_string()),
             exp: 9999999999,
             iat: 0,
+            jti: None,
             email: Some("user@example.com".to_string()),
             email_verified: Some(true),
             name: Some("Test User".to_string()),
             picture: None,
             roles: Some(vec!["annotator".to_string(), "admin".to_string()]),
+            extra: HashMap::new(),
         }
     }
 
     #[test]
     fn from_claims_extracts_fields() {
-        let user = CurrentUser::from_claims(test_claims());
+        let user = CurrentUser::from_claims(test_claims(), DEFAULT_ROLES_CLAIM, true).unwrap();
         assert_eq!(user.auth0_id, "auth0|123");
         assert_eq!(user.email, Some("user@example.com".to_string()));
         assert!(user.email_verified);
@@ -164,7 +241,7 @@ mod tests {
 
     #[test]
     fn has_role_checks_correctly() {
-        let user = CurrentUser::from_claims(test_claims());
+        let user = CurrentUser::from_claims(test_claims(), DEFAULT_ROLES_CLAIM, true).unwrap();
         assert!(user.has_role("annotator"));
         assert!(user.has_role("admin"));
         assert!(!user.has_role("superuser"));
@@ -172,9 +249,64 @@ mod tests {
 
     #[test]
     fn has_any_role_checks_correctly() {
-        let user = CurrentUser::from_claims(test_claims());
+        let user = CurrentUser::from_claims(test_claims(), DEFAULT_ROLES_CLAIM, true).unwrap();
         assert!(user.has_any_role(&["annotator", "reviewer"]));
         assert!(user.has_any_role(&["superuser", "admin"]));
         assert!(!user.has_any_role(&["superuser", "reviewer"]));
     }
+
+    #[test]
+    fn namespaced_roles_claim_drives_has_role() {
+        let mut claims = test_claims();
+        claims.roles = None;
+        claims.extra.insert(
+            "https://tenant.example/roles".to_string(),
+            serde_json::json!(["Team-Lead", "finance", "reviewer"]),
+        );
+
+        let user = CurrentUser::from_claims(claims, "https://tenant.example/roles", true).unwrap();
+        assert_eq!(user.roles, vec!["team_leader", "reviewer"]);
+        assert!(user.has_role("reviewer"));
+        assert!(!user.has_role("finance"));
+        assert!(!user.has_role("admin"));
+    }
+
+    #[test]
+    fn org_claim_sets_org_and_defaults_only_when_single_tenant() {
+        let user = CurrentUser::from_claims(test_claims(), DEFAULT_ROLES_CLAIM, true).unwrap();
+        assert_eq!(user.org_id, OrgId::DEFAULT);
+
+        assert!(matches!(
+            CurrentUser::from_claims(test_claims(), DEFAULT_ROLES_CLAIM, false),
+            Err(ApiError::Unauthorized)
+        ));
+
+        let org = OrgId::new();
+        let mut claims = test_claims();
+        claims
+            .extra
+            .insert(ORG_CLAIM.to_string(), serde_json::json!(org.to_string()));
+        let user = CurrentUser::from_claims(claims, DEFAULT_ROLES_CLAIM, true).unwrap();
+        assert_eq!(user.org_id, org);
+
+        let mut claims = test_claims();
+        claims.extra.insert(
+            ORG_CLAIM.to_string(),
+            serde_json::json!(org.as_uuid().to_string()),
+        );
+        let user = CurrentUser::from_claims(claims, DEFAULT_ROLES_CLAIM, true).unwrap();
+        assert_eq!(user.org_id, org);
+    }
+
+    #[test]
+    fn unreadable_org_claim_is_rejected() {
+        let mut claims = test_claims();
+        claims
+            .extra
+            .insert(ORG_CLAIM.to_string(), serde_json::json!("not-an-org"));
+        assert!(matches!(
+            CurrentUser::from_claims(claims, DEFAULT_ROLES_CLAIM, true),
+            Err(ApiError::Unauthorized)
+        ));
+    }
 }
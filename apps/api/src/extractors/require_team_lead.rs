@@ -47,9 +47,7 @@ where
 
         let team_id: TeamId = team_id_str
             .parse()
-            .map_err(|e: glyph_domain::IdParseError| {
-                ApiError::bad_request("team.id.invalid", e.to_string())
-            })?;
+            .map_err(|e| ApiError::invalid_id("team", &e))?;
 
         // Admins bypass team leadership check
         if user.has_role("admin") {
@@ -10,10 +10,12 @@ pub mod error;
 pub mod extractors;
 pub mod middleware;
 pub mod openapi;
+pub mod pagination;
 pub mod routes;
 pub mod services;
 pub mod ws;
 
-pub use error::ApiError;
+pub use error::{ApiError, FieldError};
 pub use openapi::ApiDoc;
+pub use pagination::PageNav;
 pub use ws::QueueUpdateHub;
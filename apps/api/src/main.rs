@@ -1,23 +1,32 @@
 //! Glyph Server - Main entry point
 
+use std::future::IntoFuture;
 use std::net::SocketAddr;
 use std::sync::Arc;
+use std::time::Duration;
 
 use anyhow::Result;
 use axum::{Extension, Router};
 use sqlx::postgres::PgPoolOptions;
-use tower_http::cors::CorsLayer;
 use tower_http::trace::TraceLayer;
 use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
-use utoipa::OpenApi;
+use utoipa::{Modify, OpenApi};
 use utoipa_swagger_ui::SwaggerUi;
 
 use glyph_api::{
     extractors::{AuthState as ExtractorAuthState, CurrentUser, DevMode},
-    routes, ApiDoc,
+    middleware::{audit_middleware, AuditConfig, CorsConfig},
+    openapi::ProtectedPaths,
+    routes,
+    services::SchemaValidationService,
+    ApiDoc, QueueUpdateHub,
 };
-use glyph_auth::{Auth0Client, Auth0Config, JwksCache};
-use glyph_domain::UserId;
+use glyph_auth::{
+    Auth0Client, Auth0Config, InMemoryRevocationStore, JwksCache, RedisRevocationStore,
+    RevocationStore,
+};
+use glyph_common::redact::RedactingFields;
+use glyph_domain::{OrgId, UserId};
 
 #[tokio::main]
 async fn main() -> Result<()> {
@@ -27,7 +36,7 @@ async fn main() -> Result<()> {
             tracing_subscriber::EnvFilter::try_from_default_env()
                 .unwrap_or_else(|_| "glyph_api=debug,tower_http=debug,audit=info".into()),
         )
-        .with(tracing_subscriber::fmt::layer())
+        .with(tracing_subscriber::fmt::layer().fmt_fields(RedactingFields::default()))
         .init();
 
     // Load environment variables
@@ -45,19 +54,36 @@ async fn main() -> Result<()> {
     tracing::info!("Connected to database");
 
     // Initialize authentication (optional - skip if Auth0 not configured)
-    let auth_state = init_auth().await;
+    let auth_state = init_auth().await?;
 
     // Build OpenAPI spec with route paths
     let mut openapi = ApiDoc::openapi();
-    openapi.paths = routes::openapi_paths();
+    openapi.merge(routes::openapi_docs());
+    ProtectedPaths {
+        optional: auth_state.is_none(),
+    }
+    .modify(&mut openapi);
+
+    // WebSocket broadcast hub (also used to close sockets on shutdown)
+    let hub = Arc::new(QueueUpdateHub::new());
 
     // Build the application
     let mut app = Router::new()
         .merge(routes::api_routes())
+        .merge(routes::queue_ws_routes(hub.clone()))
         .merge(SwaggerUi::new("/swagger-ui").url("/api-docs/openapi.json", openapi))
         .layer(Extension(pool.clone()))
+        .layer(Extension(hub.clone()))
+        // One time source for presence and cooldowns
+        .layer(Extension(glyph_workflow_engine::system_clock()))
+        // Compiled output schemas, shared by every submission
+        .layer(Extension(Arc::new(SchemaValidationService::new())))
+        .layer(axum::middleware::from_fn_with_state(
+            AuditConfig::from_env(),
+            audit_middleware,
+        ))
         .layer(TraceLayer::new_for_http())
-        .layer(CorsLayer::permissive());
+        .layer(CorsConfig::from_env().layer(auth_state.is_none()));
 
     // Add auth routes if configured
     if let Some(state) = auth_state {
@@ -67,6 +93,7 @@ async fn main() -> Result<()> {
         let extractor_state = ExtractorAuthState {
             jwks_cache: state.jwks_cache.clone(),
             auth0_config: state.auth0_config.clone(),
+            revocations: state.revocations.clone(),
         };
 
         app = app
@@ -80,6 +107,7 @@ async fn main() -> Result<()> {
         let dev_user_id = ensure_dev_user_exists(&pool).await?;
         let dev_mode = DevMode {
             mock_user_id: dev_user_id,
+            org_id: OrgId::DEFAULT,
         };
 
         // Add dev auth routes
@@ -94,12 +122,78 @@ async fn main() -> Result<()> {
     tracing::info!("Starting Glyph server on {}", addr);
     tracing::info!("Swagger UI available at http://localhost:3000/swagger-ui/");
 
+    let drain_timeout = Duration::from_secs(
+        std::env::var("SHUTDOWN_DRAIN_TIMEOUT_SECS")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(DEFAULT_DRAIN_TIMEOUT_SECS),
+    );
+
+    let (shutdown_tx, mut shutdown_rx) = tokio::sync::watch::channel(false);
+    let signal_hub = hub.clone();
+    tokio::spawn(async move {
+        shutdown_signal().await;
+        tracing::info!("Shutdown signal received - draining connections");
+        signal_hub.shutdown();
+        let _ = shutdown_tx.send(true);
+    });
+
     let listener = tokio::net::TcpListener::bind(addr).await?;
-    axum::serve(listener, app).await?;
+    let mut graceful_rx = shutdown_rx.clone();
+    let server = axum::serve(listener, app)
+        .with_graceful_shutdown(async move {
+            let _ = graceful_rx.wait_for(|stop| *stop).await;
+        })
+        .into_future();
+
+    // Stop waiting for in-flight requests once the drain timeout elapses
+    let drain_deadline = async move {
+        let _ = shutdown_rx.wait_for(|stop| *stop).await;
+        tokio::time::sleep(drain_timeout).await;
+    };
 
+    tokio::select! {
+        result = server => result?,
+        () = drain_deadline => {
+            tracing::warn!(
+                timeout_secs = drain_timeout.as_secs(),
+                "Drain timeout elapsed - dropping remaining connections"
+            );
+        }
+    }
+
+    tracing::info!("Server stopped");
     Ok(())
 }
 
+/// Default time allowed for in-flight requests to finish after a shutdown signal
+const DEFAULT_DRAIN_TIMEOUT_SECS: u64 = 30;
+
+/// Resolve when the process receives ctrl-c or SIGTERM
+async fn shutdown_signal() {
+    let ctrl_c = async {
+        tokio::signal::ctrl_c()
+            .await
+            .expect("Failed to install ctrl-c handler");
+    };
+
+    #[cfg(unix)]
+    let terminate = async {
+        tokio::signal::unix::signal(tokio::signal::unix::SignalKind::terminate())
+            .expect("Failed to install SIGTERM handler")
+            .recv()
+            .await;
+    };
+
+    #[cfg(not(unix))]
+    let terminate = std::future::pending::<()>();
+
+    tokio::select! {
+        () = ctrl_c => {},
+        () = terminate => {},
+    }
+}
+
 /// Development auth routes (used when Auth0 is not configured).
 fn dev_auth_routes() -> Router {
     use axum::{routing::get, Json};
@@ -134,6 +228,7 @@ fn dev_auth_routes() -> Router {
     struct QualityProfile {
         overall_score: Option<f64>,
         accuracy_score: Option<f64>,
+        gold_accuracy_score: Option<f64>,
         consistency_score: Option<f64>,
         speed_percentile: Option<f64>,
         total_annotations: i32,
@@ -169,6 +264,7 @@ fn dev_auth_routes() -> Router {
             quality_profile: QualityProfile {
                 overall_score: None,
                 accuracy_score: None,
+                gold_accuracy_score: None,
                 consistency_score: None,
                 speed_percentile: None,
                 total_annotations: 0,
@@ -185,75 +281,114 @@ fn dev_auth_routes() -> Router {
 
 /// Ensure a dev user exists in the database for development mode.
 /// Returns the user ID of the dev user.
+///
+/// Uses a single upsert so that several API instances starting at once
+/// all resolve to the same canonical dev user instead of racing on insert.
 async fn ensure_dev_user_exists(pool: &sqlx::PgPool) -> Result<UserId> {
     const DEV_USER_EMAIL: &str = "dev@localhost";
 
-    // Check if dev user already exists
-    let existing: Option<(uuid::Uuid,)> =
-        sqlx::query_as("SELECT user_id FROM users WHERE email = $1")
-            .bind(DEV_USER_EMAIL)
-            .fetch_optional(pool)
-            .await?;
-
-    if let Some((uuid,)) = existing {
-        let user_id = UserId::from_uuid(uuid);
-        tracing::info!(user_id = %user_id, "Using existing dev user");
-        return Ok(user_id);
-    }
-
-    // Create dev user
-    let user_id = UserId::new();
-    sqlx::query(
+    // The no-op update makes RETURNING yield the existing row on conflict
+    let (uuid,): (uuid::Uuid,) = sqlx::query_as(
         r#"
         INSERT INTO users (user_id, email, display_name, auth0_id, global_role, status)
         VALUES ($1, $2, $3, $4, 'admin', 'active')
+        ON CONFLICT (email) DO UPDATE SET email = EXCLUDED.email
+        RETURNING user_id
         "#,
     )
-    .bind(user_id.as_uuid())
+    .bind(UserId::new().as_uuid())
     .bind(DEV_USER_EMAIL)
     .bind("Development User")
     .bind("dev|mock-user")
-    .execute(pool)
+    .fetch_one(pool)
     .await?;
 
-    tracing::info!(user_id = %user_id, "Created dev user");
+    let user_id = UserId::from_uuid(uuid);
+    tracing::info!(user_id = %user_id, "Using dev user");
     Ok(user_id)
 }
 
 /// Initialize authentication state from environment.
 /// Returns None if Auth0 is not configured.
-async fn init_auth() -> Option<routes::AuthState> {
+///
+/// Once Auth0 is configured, failing to set it up is an error rather than a
+/// fallback to development mode, which would let every request in as admin.
+async fn init_auth() -> Result<Option<routes::AuthState>> {
     // Try to load Auth0 config
     let config = match Auth0Config::from_env() {
         Ok(c) => Arc::new(c),
         Err(e) => {
             tracing::debug!("Auth0 config not available: {}", e);
-            return None;
+            return Ok(None);
         }
     };
 
     // Initialize JWKS cache
-    let jwks_cache = Arc::new(JwksCache::new(config.jwks_url()));
+    let jwks_cache = Arc::new(JwksCache::with_issuers(config.trusted_issuers()));
 
     // Attempt initial JWKS fetch
     if let Err(e) = jwks_cache.refresh().await {
         tracing::warn!("Initial JWKS fetch failed (will retry on demand): {}", e);
     }
 
-    // Initialize Auth0 client
-    let auth0_client = match Auth0Client::new((*config).clone()).await {
-        Ok(c) => Arc::new(c),
-        Err(e) => {
-            tracing::warn!("Failed to initialize Auth0 client: {}", e);
-            return None;
+    // Revoked tokens must be visible to every instance, so use Redis when available
+    let revocations: Arc<dyn RevocationStore> = match std::env::var("REDIS_URL") {
+        Ok(url) => {
+            let redis_config = glyph_db::RedisConfig {
+                url,
+                ..Default::default()
+            };
+            let pool = glyph_db::create_redis_pool(&redis_config).map_err(|e| {
+                anyhow::anyhow!("Failed to create Redis pool for token revocation: {e}")
+            })?;
+            Arc::new(RedisRevocationStore::new(pool))
+        }
+        Err(_) => {
+            tracing::warn!("REDIS_URL not set - token revocation is local to this instance");
+            Arc::new(InMemoryRevocationStore::new())
         }
     };
 
+    // Initialize Auth0 client
+    let auth0_client = Arc::new(
+        Auth0Client::new((*config).clone())
+            .await
+            .map_err(|e| anyhow::anyhow!("Failed to initialize Auth0 client: {e}"))?,
+    );
+
     tracing::info!(domain = %config.domain, "Auth0 initialized");
 
-    Some(routes::AuthState {
+    Ok(Some(routes::AuthState {
         jwks_cache,
         auth0_config: config,
         auth0_client,
-    })
+        revocations,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    #[ignore = "requires DATABASE_URL pointing at a migrated database"]
+    async fn test_ensure_dev_user_exists_concurrent() {
+        let url = std::env::var("DATABASE_URL").expect("DATABASE_URL must be set");
+        let pool = PgPoolOptions::new()
+            .max_connections(4)
+            .connect(&url)
+            .await
+            .unwrap();
+
+        let (a, b) = tokio::join!(ensure_dev_user_exists(&pool), ensure_dev_user_exists(&pool));
+        let (a, b) = (a.unwrap(), b.unwrap());
+        assert_eq!(a, b);
+
+        let (canonical,): (uuid::Uuid,) =
+            sqlx::query_as("SELECT user_id FROM users WHERE email = 'dev@localhost'")
+                .fetch_one(&pool)
+                .await
+                .unwrap();
+        assert_eq!(a, UserId::from_uuid(canonical));
+    }
 }
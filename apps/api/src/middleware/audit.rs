@@ -1,9 +1,17 @@
 //! Audit context extraction for authentication events.
 //!
 //! Provides helpers to extract client metadata from requests
-//! for inclusion in audit events.
+//! for inclusion in audit events, and a middleware that records the
+//! HTTP outcome (status and duration) of each audited request.
 
-use axum::http::{HeaderMap, Request};
+use std::time::Instant;
+
+use axum::{
+    extract::{Request as AxumRequest, State},
+    http::{HeaderMap, Method, Request},
+    middleware::Next,
+    response::Response,
+};
 
 /// Audit context extracted from a request.
 #[derive(Debug, Clone)]
@@ -14,6 +22,10 @@ pub struct AuditContext {
     pub user_agent: Option<String>,
     /// Request ID for correlation
     pub request_id: String,
+    /// Final HTTP status code (set once the response is produced)
+    pub status: Option<u16>,
+    /// Total request duration in milliseconds (set once the response is produced)
+    pub duration_ms: Option<u64>,
 }
 
 impl AuditContext {
@@ -41,9 +53,17 @@ impl AuditContext {
             ip_address,
             user_agent,
             request_id,
+            status: None,
+            duration_ms: None,
         }
     }
 
+    /// Whether the recorded outcome was a success (2xx/3xx).
+    #[must_use]
+    pub fn succeeded(&self) -> bool {
+        self.status.is_some_and(|s| s < 400)
+    }
+
     /// Extract audit context from a request.
     #[must_use]
     pub fn from_request<B>(req: &Request<B>) -> Self {
@@ -60,6 +80,92 @@ pub fn audit_context<B>(req: &Request<B>) -> (Option<String>, Option<String>, St
     (ctx.ip_address, ctx.user_agent, ctx.request_id)
 }
 
+/// Settings for the request audit middleware.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct AuditConfig {
+    /// Also audit read-only requests (GET/HEAD/OPTIONS). Off by default.
+    pub include_reads: bool,
+}
+
+impl AuditConfig {
+    /// Load settings from the environment (`AUDIT_INCLUDE_READS=true`).
+    #[must_use]
+    pub fn from_env() -> Self {
+        Self {
+            include_reads: std::env::var("AUDIT_INCLUDE_READS")
+                .is_ok_and(|v| v.eq_ignore_ascii_case("true") || v == "1"),
+        }
+    }
+
+    /// Whether requests with this method should be audited.
+    #[must_use]
+    pub fn should_audit(&self, method: &Method) -> bool {
+        self.include_reads || !matches!(*method, Method::GET | Method::HEAD | Method::OPTIONS)
+    }
+}
+
+/// Audit record emitted for each completed request.
+#[derive(Debug, Clone)]
+pub struct RequestAuditEvent {
+    /// HTTP method
+    pub method: Method,
+    /// Request path
+    pub path: String,
+    /// Client metadata plus the recorded outcome
+    pub context: AuditContext,
+}
+
+/// Emit a request audit event on the `audit` tracing target.
+fn emit_request_audit(event: &RequestAuditEvent) {
+    tracing::info!(
+        target: "audit",
+        method = %event.method,
+        path = %event.path,
+        status = ?event.context.status,
+        duration_ms = ?event.context.duration_ms,
+        success = event.context.succeeded(),
+        request_id = %event.context.request_id,
+        ip_address = ?event.context.ip_address,
+        user_agent = ?event.context.user_agent,
+        "request_completed"
+    );
+}
+
+/// Middleware recording the final status code and duration of audited requests.
+///
+/// The [`AuditContext`] is inserted into request extensions for handlers, and
+/// the completed [`RequestAuditEvent`] is attached to the response extensions
+/// after being emitted.
+pub async fn audit_middleware(
+    State(config): State<AuditConfig>,
+    mut request: AxumRequest,
+    next: Next,
+) -> Response {
+    if !config.should_audit(request.method()) {
+        return next.run(request).await;
+    }
+
+    let started = Instant::now();
+    let mut context = AuditContext::from_request(&request);
+    let method = request.method().clone();
+    let path = request.uri().path().to_string();
+    request.extensions_mut().insert(context.clone());
+
+    let mut response = next.run(request).await;
+
+    context.status = Some(response.status().as_u16());
+    context.duration_ms = Some(u64::try_from(started.elapsed().as_millis()).unwrap_or(u64::MAX));
+
+    let event = RequestAuditEvent {
+        method,
+        path,
+        context,
+    };
+    emit_request_audit(&event);
+    response.extensions_mut().insert(event);
+    response
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -121,4 +227,81 @@ mod tests {
         // Should be a valid UUID
         assert!(uuid::Uuid::parse_str(&ctx.request_id).is_ok());
     }
+
+    mod middleware {
+        use super::super::*;
+        use axum::{
+            body::Body,
+            http::StatusCode,
+            routing::{get, put},
+            Router,
+        };
+        use tower::ServiceExt;
+
+        fn app(config: AuditConfig) -> Router {
+            Router::new()
+                .route(
+                    "/things",
+                    get(|| async { "ok" })
+                        .put(|| async { (StatusCode::UNPROCESSABLE_ENTITY, "bad") }),
+                )
+                .route(
+                    "/broken",
+                    put(|| async { StatusCode::INTERNAL_SERVER_ERROR }),
+                )
+                .layer(axum::middleware::from_fn_with_state(
+                    config,
+                    audit_middleware,
+                ))
+        }
+
+        async fn send(config: AuditConfig, method: Method, uri: &str) -> Response {
+            app(config)
+                .oneshot(
+                    Request::builder()
+                        .method(method)
+                        .uri(uri)
+                        .body(Body::empty())
+                        .unwrap(),
+                )
+                .await
+                .unwrap()
+        }
+
+        #[tokio::test]
+        async fn failed_put_records_error_status() {
+            let response = send(AuditConfig::default(), Method::PUT, "/things").await;
+            let event = response
+                .extensions()
+                .get::<RequestAuditEvent>()
+                .expect("PUT should be audited");
+
+            assert_eq!(event.method, Method::PUT);
+            assert_eq!(event.path, "/things");
+            assert_eq!(event.context.status, Some(422));
+            assert!(event.context.duration_ms.is_some());
+            assert!(!event.context.succeeded());
+
+            let response = send(AuditConfig::default(), Method::PUT, "/broken").await;
+            let event = response.extensions().get::<RequestAuditEvent>().unwrap();
+            assert_eq!(event.context.status, Some(500));
+        }
+
+        #[tokio::test]
+        async fn reads_excluded_by_default() {
+            let response = send(AuditConfig::default(), Method::GET, "/things").await;
+            assert!(response.extensions().get::<RequestAuditEvent>().is_none());
+        }
+
+        #[tokio::test]
+        async fn reads_included_when_configured() {
+            let config = AuditConfig {
+                include_reads: true,
+            };
+            let response = send(config, Method::GET, "/things").await;
+            let event = response.extensions().get::<RequestAuditEvent>().unwrap();
+            assert_eq!(event.context.status, Some(200));
+            assert!(event.context.succeeded());
+        }
+    }
 }
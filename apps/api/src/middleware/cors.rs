@@ -0,0 +1,135 @@
+//! CORS configuration
+//!
+//! Allowed origins are read from `CORS_ALLOWED_ORIGINS` (comma-separated).
+//! Auth cookies are sent with credentialed requests, which browsers only
+//! permit for explicitly listed origins, never a wildcard.
+
+use axum::http::HeaderValue;
+use tower_http::cors::{AllowHeaders, AllowMethods, AllowOrigin, CorsLayer};
+
+/// Environment variable holding the allowed origin list
+pub const CORS_ALLOWED_ORIGINS_ENV: &str = "CORS_ALLOWED_ORIGINS";
+
+/// CORS settings for the API server
+#[derive(Debug, Clone, Default)]
+pub struct CorsConfig {
+    /// Origins allowed to make credentialed cross-origin requests
+    pub allowed_origins: Vec<String>,
+}
+
+impl CorsConfig {
+    /// Load CORS settings from the environment
+    #[must_use]
+    pub fn from_env() -> Self {
+        std::env::var(CORS_ALLOWED_ORIGINS_ENV)
+            .map(|v| Self::from_list(&v))
+            .unwrap_or_default()
+    }
+
+    /// Parse a comma-separated origin list, ignoring blanks and trailing slashes
+    #[must_use]
+    pub fn from_list(list: &str) -> Self {
+        let allowed_origins = list
+            .split(',')
+            .map(|o| o.trim().trim_end_matches('/'))
+            .filter(|o| !o.is_empty())
+            .map(String::from)
+            .collect();
+        Self { allowed_origins }
+    }
+
+    /// Build the CORS layer.
+    ///
+    /// With no configured origins, development mode falls back to permissive
+    /// CORS; otherwise cross-origin requests are not allowed at all.
+    #[must_use]
+    pub fn layer(&self, dev_mode: bool) -> CorsLayer {
+        if self.allowed_origins.is_empty() {
+            if dev_mode {
+                return CorsLayer::permissive();
+            }
+            tracing::warn!(
+                "{} not set - cross-origin requests will be rejected",
+                CORS_ALLOWED_ORIGINS_ENV
+            );
+            return CorsLayer::new();
+        }
+
+        let origins: Vec<HeaderValue> = self
+            .allowed_origins
+            .iter()
+            .filter_map(|o| match HeaderValue::from_str(o) {
+                Ok(v) => Some(v),
+                Err(_) => {
+                    tracing::warn!(origin = %o, "Ignoring invalid CORS origin");
+                    None
+                }
+            })
+            .collect();
+
+        CorsLayer::new()
+            .allow_origin(AllowOrigin::list(origins))
+            .allow_methods(AllowMethods::mirror_request())
+            .allow_headers(AllowHeaders::mirror_request())
+            .allow_credentials(true)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{body::Body, http::Request, routing::get, Router};
+    use tower::ServiceExt;
+
+    fn app(config: &CorsConfig) -> Router {
+        Router::new()
+            .route("/", get(|| async { "ok" }))
+            .layer(config.layer(false))
+    }
+
+    async fn allow_origin_for(config: &CorsConfig, origin: &str) -> Option<HeaderValue> {
+        let response = app(config)
+            .oneshot(
+                Request::builder()
+                    .uri("/")
+                    .header("origin", origin)
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        response
+            .headers()
+            .get("access-control-allow-origin")
+            .cloned()
+    }
+
+    #[test]
+    fn parses_origin_list() {
+        let config = CorsConfig::from_list(" https://app.glyph.dev/, ,http://localhost:5173");
+        assert_eq!(
+            config.allowed_origins,
+            vec!["https://app.glyph.dev", "http://localhost:5173"]
+        );
+    }
+
+    #[tokio::test]
+    async fn allowed_origin_passes() {
+        let config = CorsConfig::from_list("https://app.glyph.dev");
+        let header = allow_origin_for(&config, "https://app.glyph.dev").await;
+        assert_eq!(header.unwrap(), "https://app.glyph.dev");
+    }
+
+    #[tokio::test]
+    async fn disallowed_origin_is_rejected() {
+        let config = CorsConfig::from_list("https://app.glyph.dev");
+        let header = allow_origin_for(&config, "https://evil.example.com").await;
+        assert!(header.is_none());
+    }
+
+    #[tokio::test]
+    async fn no_origins_outside_dev_mode_allows_nothing() {
+        let header = allow_origin_for(&CorsConfig::default(), "https://app.glyph.dev").await;
+        assert!(header.is_none());
+    }
+}
@@ -2,8 +2,10 @@
 
 pub mod audit;
 pub mod auth;
+pub mod cors;
 pub mod tracing;
 
-pub use audit::{audit_context, AuditContext};
+pub use audit::{audit_context, audit_middleware, AuditConfig, AuditContext, RequestAuditEvent};
 pub use auth::*;
+pub use cors::CorsConfig;
 pub use tracing::*;
@@ -2,7 +2,15 @@
 //!
 //! Configures the OpenAPI document for the Glyph API.
 
-use utoipa::OpenApi;
+use utoipa::openapi::path::Operation;
+use utoipa::openapi::security::{HttpAuthScheme, HttpBuilder, SecurityRequirement, SecurityScheme};
+use utoipa::{Modify, OpenApi};
+
+/// Name of the bearer token security scheme
+pub const BEARER_AUTH: &str = "bearer_auth";
+
+/// Paths served without a bearer token
+pub const PUBLIC_PATHS: &[&str] = &["/api/v1/config"];
 
 /// API documentation configuration
 #[derive(OpenApi)]
@@ -23,14 +31,132 @@ use utoipa::OpenApi;
     servers(
         (url = "/api/v1", description = "API v1")
     ),
+    modifiers(&SecurityAddon),
     tags(
         (name = "health", description = "Health check endpoints"),
         (name = "users", description = "User management"),
         (name = "teams", description = "Team management"),
         (name = "projects", description = "Project management"),
+        (name = "project-types", description = "Project type and schema management"),
+        (name = "data-sources", description = "Project data sources and uploads"),
         (name = "tasks", description = "Task management"),
+        (name = "queue", description = "Annotator task queue"),
+        (name = "drafts", description = "Annotation drafts"),
         (name = "annotations", description = "Annotation management"),
+        (name = "reviews", description = "Annotation reviews"),
+        (name = "skills", description = "Skill types and certifications"),
+        (name = "skip-reasons", description = "Task skip reasons"),
+        (name = "config", description = "Client configuration"),
         (name = "workflows", description = "Workflow management")
     )
 )]
 pub struct ApiDoc;
+
+/// Declares the bearer JWT security scheme
+pub struct SecurityAddon;
+
+impl Modify for SecurityAddon {
+    fn modify(&self, openapi: &mut utoipa::openapi::OpenApi) {
+        let components = openapi.components.get_or_insert_with(Default::default);
+        components.add_security_scheme(
+            BEARER_AUTH,
+            SecurityScheme::Http(
+                HttpBuilder::new()
+                    .scheme(HttpAuthScheme::Bearer)
+                    .bearer_format("JWT")
+                    .description(Some("Auth0 access token"))
+                    .build(),
+            ),
+        );
+    }
+}
+
+/// Requires the bearer scheme on every path outside [`PUBLIC_PATHS`].
+///
+/// Apply after merging route paths into the document. With `optional` set
+/// (development mode, where a mock user is injected) the token may be omitted.
+pub struct ProtectedPaths {
+    pub optional: bool,
+}
+
+impl Modify for ProtectedPaths {
+    fn modify(&self, openapi: &mut utoipa::openapi::OpenApi) {
+        let mut requirements = vec![SecurityRequirement::new(BEARER_AUTH, Vec::<String>::new())];
+        if self.optional {
+            requirements.push(SecurityRequirement::default());
+        }
+
+        for (path, item) in &mut openapi.paths.paths {
+            if PUBLIC_PATHS.contains(&path.as_str()) {
+                continue;
+            }
+            for operation in [
+                &mut item.get,
+                &mut item.put,
+                &mut item.post,
+                &mut item.delete,
+                &mut item.options,
+                &mut item.head,
+                &mut item.patch,
+                &mut item.trace,
+            ]
+            .into_iter()
+            .flatten()
+            {
+                require(operation, &requirements);
+            }
+        }
+    }
+}
+
+fn require(operation: &mut Operation, requirements: &[SecurityRequirement]) {
+    if operation.security.is_none() {
+        operation.security = Some(requirements.to_vec());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn documented(optional: bool) -> utoipa::openapi::OpenApi {
+        let mut openapi = ApiDoc::openapi();
+        openapi.merge(crate::routes::openapi_docs());
+        ProtectedPaths { optional }.modify(&mut openapi);
+        openapi
+    }
+
+    #[test]
+    fn test_bearer_security_scheme_declared() {
+        let openapi = ApiDoc::openapi();
+        let components = openapi.components.expect("components");
+        assert!(components.security_schemes.contains_key(BEARER_AUTH));
+
+        let json = serde_json::to_value(documented(false)).unwrap();
+        assert_eq!(
+            json["components"]["securitySchemes"][BEARER_AUTH]["scheme"],
+            "bearer"
+        );
+    }
+
+    #[test]
+    fn test_protected_paths_require_token() {
+        let openapi = documented(false);
+        let paths = &openapi.paths.paths;
+
+        let project = paths["/api/v1/projects/{project_id}"].get.as_ref().unwrap();
+        assert_eq!(project.security.as_ref().map(Vec::len), Some(1));
+
+        let config = paths["/api/v1/config"].get.as_ref().unwrap();
+        assert!(config.security.is_none());
+    }
+
+    #[test]
+    fn test_dev_mode_makes_token_optional() {
+        let openapi = documented(true);
+        let json = serde_json::to_value(&openapi).unwrap();
+        let security = &json["paths"]["/api/v1/projects/{project_id}"]["get"]["security"];
+        assert_eq!(security.as_array().map(Vec::len), Some(2));
+        assert_eq!(security[1], serde_json::json!({}));
+    }
+}
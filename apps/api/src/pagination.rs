@@ -0,0 +1,205 @@
+//! Page-size policies and navigation metadata for paginated list responses
+
+use glyph_db::Pagination;
+use serde::Serialize;
+use utoipa::ToSchema;
+
+/// Page size used when a list request doesn't specify one
+pub const DEFAULT_PAGE_SIZE: i32 = 20;
+
+/// Largest page size a list request may ask for
+pub const MAX_PAGE_SIZE: i32 = 100;
+
+/// Resources served by paginated list endpoints
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PageResource {
+    Projects,
+    ProjectTypes,
+    Teams,
+    Users,
+    Tasks,
+    Queue,
+    AuditLog,
+}
+
+/// Default and maximum page size for a resource.
+///
+/// List handlers clamp requested limits through this rather than passing
+/// them to the database as given.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PaginationPolicy {
+    pub default_limit: i32,
+    pub max_limit: i32,
+}
+
+impl PaginationPolicy {
+    /// The policy for a resource
+    #[must_use]
+    pub const fn for_resource(resource: PageResource) -> Self {
+        match resource {
+            // Audit entries are small and usually read in bulk
+            PageResource::AuditLog => Self {
+                default_limit: 50,
+                max_limit: 200,
+            },
+            PageResource::Projects
+            | PageResource::ProjectTypes
+            | PageResource::Teams
+            | PageResource::Users
+            | PageResource::Tasks
+            | PageResource::Queue => Self {
+                default_limit: DEFAULT_PAGE_SIZE,
+                max_limit: MAX_PAGE_SIZE,
+            },
+        }
+    }
+
+    /// Page size for a request, defaulted and clamped to `1..=max_limit`
+    #[must_use]
+    pub fn per_page(&self, requested: Option<i32>) -> i32 {
+        requested
+            .unwrap_or(self.default_limit)
+            .clamp(1, self.max_limit)
+    }
+
+    /// [`Self::per_page`] for endpoints that take an `i64` limit
+    #[must_use]
+    pub fn limit(&self, requested: Option<i64>) -> i64 {
+        requested
+            .unwrap_or(i64::from(self.default_limit))
+            .clamp(1, i64::from(self.max_limit))
+    }
+
+    /// Repository pagination for a limit/offset request
+    #[must_use]
+    pub fn pagination(&self, limit: Option<i64>, offset: Option<i64>) -> Pagination {
+        Pagination {
+            limit: self.limit(limit),
+            offset: offset.unwrap_or(0).max(0),
+            ..Default::default()
+        }
+    }
+
+    /// Clamp pagination a client supplied directly
+    #[must_use]
+    pub fn clamp(&self, pagination: Pagination) -> Pagination {
+        Pagination {
+            limit: self.limit(Some(pagination.limit)),
+            offset: pagination.offset.max(0),
+            ..pagination
+        }
+    }
+}
+
+/// Ready-made navigation for offset-paginated responses.
+///
+/// Flattened into list responses so clients don't compute offsets themselves.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, ToSchema)]
+pub struct PageNav {
+    /// Whether items exist after this page
+    pub has_next: bool,
+    /// Whether items exist before this page
+    pub has_prev: bool,
+    /// Offset of the next page, if any
+    pub next_offset: Option<i64>,
+    /// Offset of the previous page, if any
+    pub prev_offset: Option<i64>,
+}
+
+impl PageNav {
+    /// Derive navigation from a page's total, limit, and offset
+    #[must_use]
+    pub fn new(total: i64, limit: i64, offset: i64) -> Self {
+        let limit = limit.max(1);
+        let offset = offset.max(0);
+        let has_next = offset + limit < total;
+        let has_prev = offset > 0;
+        Self {
+            has_next,
+            has_prev,
+            next_offset: has_next.then_some(offset + limit),
+            prev_offset: has_prev.then(|| (offset - limit).max(0)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn oversized_limits_are_clamped_for_every_resource() {
+        for resource in [
+            PageResource::Projects,
+            PageResource::ProjectTypes,
+            PageResource::Teams,
+            PageResource::Users,
+            PageResource::Tasks,
+            PageResource::Queue,
+            PageResource::AuditLog,
+        ] {
+            let policy = PaginationPolicy::for_resource(resource);
+            let max = i64::from(policy.max_limit);
+            assert_eq!(policy.limit(Some(100_000)), max, "{resource:?}");
+            assert_eq!(policy.per_page(Some(100_000)), policy.max_limit);
+            assert_eq!(policy.limit(Some(0)), 1);
+            assert_eq!(policy.limit(None), i64::from(policy.default_limit));
+
+            let page = policy.pagination(Some(100_000), Some(-5));
+            assert_eq!((page.limit, page.offset), (max, 0));
+            let direct = policy.clamp(Pagination {
+                limit: 100_000,
+                ..Default::default()
+            });
+            assert_eq!(direct.limit, max);
+        }
+    }
+
+    #[test]
+    fn first_page() {
+        let nav = PageNav::new(50, 20, 0);
+        assert!(nav.has_next);
+        assert!(!nav.has_prev);
+        assert_eq!(nav.next_offset, Some(20));
+        assert_eq!(nav.prev_offset, None);
+    }
+
+    #[test]
+    fn middle_page() {
+        let nav = PageNav::new(50, 20, 20);
+        assert!(nav.has_next);
+        assert!(nav.has_prev);
+        assert_eq!(nav.next_offset, Some(40));
+        assert_eq!(nav.prev_offset, Some(0));
+    }
+
+    #[test]
+    fn last_page_exactly_full() {
+        let nav = PageNav::new(40, 20, 20);
+        assert!(!nav.has_next);
+        assert!(nav.has_prev);
+        assert_eq!(nav.next_offset, None);
+    }
+
+    #[test]
+    fn last_partial_page() {
+        let nav = PageNav::new(45, 20, 40);
+        assert!(!nav.has_next);
+        assert!(nav.has_prev);
+        assert_eq!(nav.prev_offset, Some(20));
+    }
+
+    #[test]
+    fn unaligned_offset_clamps_prev_to_zero() {
+        let nav = PageNav::new(45, 20, 5);
+        assert_eq!(nav.prev_offset, Some(0));
+        assert_eq!(nav.next_offset, Some(25));
+    }
+
+    #[test]
+    fn empty_result() {
+        let nav = PageNav::new(0, 20, 0);
+        assert!(!nav.has_next);
+        assert!(!nav.has_prev);
+    }
+}
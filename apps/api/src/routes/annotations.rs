@@ -1,10 +1,57 @@
 //! Annotation endpoints
 
-use axum::{extract::Path, routing::get, Json, Router};
+use axum::{extract::Path, routing::get, Extension, Json, Router};
+use serde::Serialize;
+use sqlx::PgPool;
+use utoipa::ToSchema;
 use uuid::Uuid;
 
+use glyph_db::{
+    AnnotationRepository, FindTaskError, PgAnnotationRepository, PgProjectRepository,
+    PgTaskRepository, ProjectRepository, TaskRepository,
+};
+use glyph_domain::{AnnotationId, AnnotationVersion, TaskId};
+
+use crate::extractors::CurrentUser;
+use crate::services::PermissionService;
 use crate::ApiError;
 
+// =============================================================================
+// Response Types
+// =============================================================================
+
+/// A single saved version of an annotation
+#[derive(Debug, Serialize, ToSchema)]
+pub struct AnnotationVersionResponse {
+    pub version: i32,
+    pub user_id: String,
+    pub data: serde_json::Value,
+    pub saved_at: String,
+}
+
+impl From<AnnotationVersion> for AnnotationVersionResponse {
+    fn from(version: AnnotationVersion) -> Self {
+        Self {
+            version: version.version,
+            user_id: version.user_id.to_string(),
+            data: version.data,
+            saved_at: version.saved_at.to_rfc3339(),
+        }
+    }
+}
+
+/// Edit history of an annotation, oldest version first
+#[derive(Debug, Serialize, ToSchema)]
+pub struct AnnotationHistoryResponse {
+    pub annotation_id: String,
+    pub task_id: String,
+    pub versions: Vec<AnnotationVersionResponse>,
+}
+
+// =============================================================================
+// Route Handlers
+// =============================================================================
+
 async fn get_annotation(
     Path(annotation_id): Path<Uuid>,
 ) -> Result<Json<serde_json::Value>, ApiError> {
@@ -33,8 +80,221 @@ async fn list_annotations() -> Result<Json<serde_json::Value>, ApiError> {
     })))
 }
 
+/// Get the edit history of an annotation on a task
+#[utoipa::path(
+    get,
+    path = "/api/v1/tasks/{task_id}/annotations/{annotation_id}/history",
+    params(
+        ("task_id" = Uuid, Path, description = "Task ID"),
+        ("annotation_id" = Uuid, Path, description = "Annotation ID"),
+    ),
+    responses(
+        (status = 200, description = "Annotation history", body = AnnotationHistoryResponse),
+        (status = 403, description = "Not a member of the task's project"),
+        (status = 404, description = "Annotation not found on this task"),
+    ),
+    tag = "annotations"
+)]
+async fn get_annotation_history(
+    Path((task_id, annotation_id)): Path<(Uuid, Uuid)>,
+    current_user: CurrentUser,
+    Extension(pool): Extension<PgPool>,
+) -> Result<Json<AnnotationHistoryResponse>, ApiError> {
+    let task_id = TaskId::from_uuid(task_id);
+    let annotation_id = AnnotationId::from_uuid(annotation_id);
+
+    // Tasks outside the caller's organization look the same as missing ones
+    let task = PgTaskRepository::new(pool.clone())
+        .find_by_id(&task_id)
+        .await
+        .map_err(|e| match e {
+            FindTaskError::NotFound(id) => ApiError::not_found("task", id.to_string()),
+            FindTaskError::Database(e) => ApiError::Internal(e.into()),
+        })?
+        .ok_or_else(|| ApiError::not_found("task", task_id.to_string()))?;
+    let project = PgProjectRepository::new(pool.clone())
+        .in_org(current_user.org_id)
+        .find_by_id(&task.project_id)
+        .await
+        .map_err(|e| ApiError::Internal(anyhow::anyhow!("{}", e)))?
+        .ok_or_else(|| ApiError::not_found("task", task_id.to_string()))?;
+
+    let is_member = PermissionService::new(pool.clone())
+        .is_project_member(&current_user, &project)
+        .await
+        .map_err(|e| ApiError::Internal(e.into()))?;
+    if !is_member {
+        return Err(ApiError::forbidden(
+            "Only members of the project can read its annotation history",
+        ));
+    }
+
+    let versions = PgAnnotationRepository::new(pool)
+        .history(&annotation_id)
+        .await
+        .map_err(|e| ApiError::Internal(e.into()))?;
+
+    // Every annotation has at least its initial version
+    if versions.is_empty() || versions.iter().any(|v| v.task_id != task_id) {
+        return Err(ApiError::not_found("annotation", annotation_id.to_string()));
+    }
+
+    Ok(Json(AnnotationHistoryResponse {
+        annotation_id: annotation_id.to_string(),
+        task_id: task_id.to_string(),
+        versions: versions.into_iter().map(Into::into).collect(),
+    }))
+}
+
+// =============================================================================
+// Router
+// =============================================================================
+
+/// OpenAPI paths and schemas served by this module
+#[derive(utoipa::OpenApi)]
+#[openapi(
+    paths(get_annotation_history),
+    components(schemas(AnnotationVersionResponse, AnnotationHistoryResponse))
+)]
+pub(super) struct ApiPaths;
+
+/// Annotation routes nested under /tasks/{task_id}/annotations
+pub fn task_routes() -> Router {
+    Router::new().route("/{annotation_id}/history", get(get_annotation_history))
+}
+
 pub fn routes() -> Router {
     Router::new()
         .route("/", get(list_annotations).post(create_annotation))
         .route("/{annotation_id}", get(get_annotation))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use glyph_domain::{OrgId, UserId};
+
+    fn current_user(user_id: Uuid, roles: &[&str]) -> CurrentUser {
+        CurrentUser {
+            user_id: UserId::from_uuid(user_id),
+            auth0_id: format!("test|{user_id}"),
+            email: None,
+            email_verified: true,
+            name: None,
+            roles: roles.iter().map(ToString::to_string).collect(),
+            org_id: OrgId::DEFAULT,
+        }
+    }
+
+    async fn seed_user(pool: &PgPool) -> Uuid {
+        let user_id = Uuid::new_v4();
+        sqlx::query(
+            r#"
+            INSERT INTO users (user_id, email, display_name, auth0_id, global_role, status)
+            VALUES ($1, $2, 'History', $3, 'user', 'active')
+            "#,
+        )
+        .bind(user_id)
+        .bind(format!("{user_id}@history.test"))
+        .bind(format!("test|{user_id}"))
+        .execute(pool)
+        .await
+        .unwrap();
+        user_id
+    }
+
+    /// Seed a project with one task, an assignment for `annotator` and two
+    /// saved versions of their annotation. Returns `(project_id, task_id, annotation_id)`.
+    async fn seed_history(pool: &PgPool, annotator: Uuid) -> (Uuid, Uuid, Uuid) {
+        let project = PgProjectRepository::new(pool.clone())
+            .create_minimal("History", None, &UserId::from_uuid(annotator))
+            .await
+            .unwrap();
+        let project_id = *project.project_id.as_uuid();
+        let task_id: Uuid = sqlx::query_scalar(
+            "INSERT INTO tasks (project_id, input_data) VALUES ($1, '{}') RETURNING task_id",
+        )
+        .bind(project_id)
+        .fetch_one(pool)
+        .await
+        .unwrap();
+        sqlx::query(
+            "INSERT INTO task_assignments (task_id, project_id, step_id, user_id) VALUES ($1, $2, 'annotate', $3)",
+        )
+        .bind(task_id)
+        .bind(project_id)
+        .bind(annotator)
+        .execute(pool)
+        .await
+        .unwrap();
+
+        let annotation_id = Uuid::new_v4();
+        for version in 1..=2 {
+            sqlx::query(
+                r#"
+                INSERT INTO annotation_versions (annotation_id, version, task_id, user_id, data)
+                VALUES ($1, $2, $3, $4, jsonb_build_object('label', $2))
+                "#,
+            )
+            .bind(annotation_id)
+            .bind(version)
+            .bind(task_id)
+            .bind(annotator)
+            .execute(pool)
+            .await
+            .unwrap();
+        }
+        (project_id, task_id, annotation_id)
+    }
+
+    #[tokio::test]
+    #[ignore = "requires DATABASE_URL pointing at a migrated database"]
+    async fn test_history_is_limited_to_project_members_in_the_org() {
+        let url = std::env::var("DATABASE_URL").expect("DATABASE_URL must be set");
+        let pool = PgPool::connect(&url).await.unwrap();
+        let annotator = seed_user(&pool).await;
+        let outsider = seed_user(&pool).await;
+        let (project_id, task_id, annotation_id) = seed_history(&pool, annotator).await;
+
+        let Json(history) = get_annotation_history(
+            Path((task_id, annotation_id)),
+            current_user(annotator, &[]),
+            Extension(pool.clone()),
+        )
+        .await
+        .unwrap();
+        let versions: Vec<i32> = history.versions.iter().map(|v| v.version).collect();
+        assert_eq!(versions, [1, 2]);
+
+        let result = get_annotation_history(
+            Path((task_id, annotation_id)),
+            current_user(outsider, &[]),
+            Extension(pool.clone()),
+        )
+        .await;
+        assert!(matches!(result, Err(ApiError::Forbidden { .. })));
+
+        // Once the project belongs to another organization, even its own
+        // annotator can't find the task from the default one
+        let other_org = OrgId::new();
+        sqlx::query("INSERT INTO organizations (org_id, name, slug) VALUES ($1, $2, $2)")
+            .bind(other_org.as_uuid())
+            .bind(other_org.to_string())
+            .execute(&pool)
+            .await
+            .unwrap();
+        sqlx::query("UPDATE projects SET org_id = $2 WHERE project_id = $1")
+            .bind(project_id)
+            .bind(other_org.as_uuid())
+            .execute(&pool)
+            .await
+            .unwrap();
+        let result = get_annotation_history(
+            Path((task_id, annotation_id)),
+            current_user(annotator, &["admin"]),
+            Extension(pool),
+        )
+        .await;
+        assert!(matches!(result, Err(ApiError::NotFound { .. })));
+    }
+}
@@ -17,13 +17,15 @@ use axum::{
     Json, Router,
 };
 use axum_extra::extract::CookieJar;
+use glyph_common::redact::Secret;
 use serde::{Deserialize, Serialize};
 use tracing::{debug, info, warn};
 
 use glyph_auth::{
     clear_auth_cookies, clear_pkce_cookie, cookie_time, emit_audit_event, parse_pkce_cookie,
-    set_auth_cookies, set_pkce_cookie, AuditEvent, AuditEventType, Auth0Client, Auth0Config,
-    Cookie, JwksCache, SameSite, PKCE_STATE_COOKIE,
+    set_auth_cookies, set_pkce_cookie, validate_jwt, verify_state, AuditEvent, AuditEventType,
+    Auth0Client, Auth0Config, AuthError, Cookie, JwksCache, RevocationStore, SameSite,
+    ACCESS_TOKEN_COOKIE, PKCE_STATE_COOKIE,
 };
 
 use crate::extractors::CurrentUser;
@@ -36,6 +38,7 @@ pub struct AuthState {
     pub jwks_cache: Arc<JwksCache>,
     pub auth0_config: Arc<Auth0Config>,
     pub auth0_client: Arc<Auth0Client>,
+    pub revocations: Arc<dyn RevocationStore>,
 }
 
 /// Query parameters for login endpoint.
@@ -125,7 +128,9 @@ async fn login(
         updated_jar = updated_jar.add(cookie);
     }
 
-    debug!(url = %auth_data.url, "redirecting to Auth0");
+    // The query string carries state and nonce, so only the endpoint is logged
+    let authorize_endpoint = auth_data.url.split('?').next().unwrap_or_default();
+    debug!(url = %authorize_endpoint, "redirecting to Auth0");
 
     (updated_jar, Redirect::to(&auth_data.url))
 }
@@ -177,8 +182,8 @@ async fn callback(
         })?;
 
     // Verify CSRF state
-    if query.state != csrf_token {
-        warn!(expected = %csrf_token, got = %query.state, "CSRF state mismatch");
+    if verify_state(&csrf_token, &query.state).is_err() {
+        warn!(expected = %Secret(&csrf_token), got = %Secret(&query.state), "CSRF state mismatch");
         emit_audit_event(
             AuditEvent::new(
                 AuditEventType::LoginFailed,
@@ -201,6 +206,16 @@ async fn callback(
         .exchange_code(&query.code, &pkce_verifier, &nonce)
         .await
         .map_err(|e| {
+            let (failure, code, detail) = match e {
+                AuthError::InvalidNonce => {
+                    ("nonce_mismatch", "auth.invalid_nonce", "Nonce mismatch")
+                }
+                _ => (
+                    "token_exchange_failed",
+                    "auth.token_exchange_failed",
+                    "Failed to exchange code",
+                ),
+            };
             warn!(error = %e, "token exchange failed");
             emit_audit_event(
                 AuditEvent::new(
@@ -208,11 +223,11 @@ async fn callback(
                     &audit_ctx.request_id,
                     "/api/auth/callback",
                 )
-                .with_failure("token_exchange_failed")
+                .with_failure(failure)
                 .with_ip(audit_ctx.ip_address.clone().unwrap_or_default())
                 .with_user_agent(audit_ctx.user_agent.clone().unwrap_or_default()),
             );
-            ApiError::bad_request("auth.token_exchange_failed", "Failed to exchange code")
+            ApiError::bad_request(code, detail)
         })?;
 
     info!("token exchange successful");
@@ -254,11 +269,42 @@ async fn callback(
     Ok((updated_jar, Redirect::to(&redirect_to)))
 }
 
+/// Revoke an access token for the rest of its lifetime.
+///
+/// Invalid or already-expired tokens need no revocation; failures are logged
+/// rather than blocking logout.
+async fn revoke_access_token(auth: &AuthState, token: &str) {
+    let claims = match validate_jwt(
+        token,
+        &auth.jwks_cache,
+        &auth.auth0_config,
+        auth.revocations.as_ref(),
+    )
+    .await
+    {
+        Ok(claims) => claims,
+        Err(e) => {
+            debug!(error = %e, "not revoking invalid access token");
+            return;
+        }
+    };
+    let Some(jti) = claims.jti else {
+        warn!("access token has no jti; it stays valid until expiry");
+        return;
+    };
+
+    let remaining = claims.exp - chrono::Utc::now().timestamp();
+    let ttl = std::time::Duration::from_secs(u64::try_from(remaining).unwrap_or(0));
+    if let Err(e) = auth.revocations.revoke(&jti, ttl).await {
+        warn!(error = %e, "failed to revoke access token");
+    }
+}
+
 /// Logout endpoint.
 ///
 /// POST /api/auth/logout
 ///
-/// Clears cookies and returns Auth0 logout URL.
+/// Revokes the current access token, clears cookies, and returns Auth0 logout URL.
 async fn logout(
     State(auth): State<AuthState>,
     headers: HeaderMap,
@@ -278,6 +324,11 @@ async fn logout(
         .with_user_agent(audit_ctx.user_agent.unwrap_or_default()),
     );
 
+    // Revoke the access token so a copy of it stops working too
+    if let Some(token) = jar.get(ACCESS_TOKEN_COOKIE) {
+        revoke_access_token(&auth, token.value()).await;
+    }
+
     // Clear auth cookies
     let (access_cookie, refresh_cookie) = clear_auth_cookies();
 
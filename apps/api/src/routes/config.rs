@@ -0,0 +1,163 @@
+//! Client configuration endpoint
+//!
+//! Exposes the runtime settings the frontend needs to adapt its UI (auth,
+//! upload and pagination limits, data source types) without a redeploy.
+//! Only values that are safe to show any visitor belong here.
+
+use axum::{routing::get, Extension, Json, Router};
+use glyph_domain::{DataSourceConfig, DataSourceType};
+use serde::Serialize;
+use utoipa::ToSchema;
+
+use crate::extractors::AuthState;
+use crate::pagination::{DEFAULT_PAGE_SIZE, MAX_PAGE_SIZE};
+
+/// Data source types the server can create
+const DATA_SOURCE_TYPES: [DataSourceType; 5] = [
+    DataSourceType::FileUpload,
+    DataSourceType::S3,
+    DataSourceType::Gcs,
+    DataSourceType::AzureBlob,
+    DataSourceType::Api,
+];
+
+// =============================================================================
+// Response Types
+// =============================================================================
+
+/// Non-sensitive runtime configuration for the frontend
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ClientConfigResponse {
+    /// Whether Auth0 login is enabled; `false` in development mode
+    pub auth_enabled: bool,
+    /// Default upload size limit for new file upload data sources, in MB
+    pub max_upload_mb: i32,
+    /// Page size used when a list request doesn't specify one
+    pub default_page_size: i32,
+    /// Largest page size a list request may ask for
+    pub max_page_size: i32,
+    /// Data source types that can be created
+    pub data_source_types: Vec<&'static str>,
+}
+
+impl ClientConfigResponse {
+    fn new(auth_enabled: bool) -> Self {
+        let max_upload_mb = match DataSourceConfig::default() {
+            DataSourceConfig::FileUpload {
+                max_file_size_mb, ..
+            } => max_file_size_mb,
+            _ => 0,
+        };
+
+        Self {
+            auth_enabled,
+            max_upload_mb,
+            default_page_size: DEFAULT_PAGE_SIZE,
+            max_page_size: MAX_PAGE_SIZE,
+            data_source_types: DATA_SOURCE_TYPES
+                .iter()
+                .map(DataSourceType::as_str)
+                .collect(),
+        }
+    }
+}
+
+// =============================================================================
+// Route Handlers
+// =============================================================================
+
+/// Get client configuration
+///
+/// Public: the login page needs it before anyone is signed in.
+#[utoipa::path(
+    get,
+    path = "/api/v1/config",
+    responses(
+        (status = 200, description = "Client configuration", body = ClientConfigResponse),
+    ),
+    tag = "config"
+)]
+async fn get_config(auth: Option<Extension<AuthState>>) -> Json<ClientConfigResponse> {
+    Json(ClientConfigResponse::new(auth.is_some()))
+}
+
+// =============================================================================
+// Router
+// =============================================================================
+
+/// OpenAPI paths and schemas served by this module
+#[derive(utoipa::OpenApi)]
+#[openapi(paths(get_config), components(schemas(ClientConfigResponse)))]
+pub(super) struct ApiPaths;
+
+pub fn routes() -> Router {
+    Router::new().route("/config", get(get_config))
+}
+
+#[cfg(test)]
+mod tests {
+    use axum::{
+        body::Body,
+        http::{Request, StatusCode},
+    };
+    use glyph_domain::{OrgId, UserId};
+    use tower::ServiceExt;
+
+    use super::*;
+    use crate::extractors::DevMode;
+
+    async fn fetch_config(app: Router) -> serde_json::Value {
+        let response = app
+            .oneshot(Request::get("/config").body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        serde_json::from_slice(&bytes).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_config_in_dev_mode() {
+        let app = routes().layer(Extension(DevMode {
+            mock_user_id: UserId::new(),
+            org_id: OrgId::DEFAULT,
+        }));
+
+        let config = fetch_config(app).await;
+        assert_eq!(
+            config,
+            serde_json::json!({
+                "auth_enabled": false,
+                "max_upload_mb": 100,
+                "default_page_size": 20,
+                "max_page_size": 100,
+                "data_source_types": ["file_upload", "s3", "gcs", "azure_blob", "api"],
+            })
+        );
+    }
+
+    #[test]
+    fn test_config_exposes_only_whitelisted_fields() {
+        let value = serde_json::to_value(ClientConfigResponse::new(true)).unwrap();
+        let mut keys: Vec<&str> = value
+            .as_object()
+            .unwrap()
+            .keys()
+            .map(String::as_str)
+            .collect();
+        keys.sort_unstable();
+        assert_eq!(
+            keys,
+            [
+                "auth_enabled",
+                "data_source_types",
+                "default_page_size",
+                "max_page_size",
+                "max_upload_mb",
+            ]
+        );
+        assert_eq!(value["auth_enabled"], true);
+    }
+}
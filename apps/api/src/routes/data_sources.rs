@@ -3,23 +3,56 @@
 //! Nested under /projects/{project_id}/data-sources
 
 use axum::{
-    extract::{Path, Query},
-    http::StatusCode,
+    extract::{DefaultBodyLimit, Multipart, Path, Query},
+    http::{HeaderMap, StatusCode},
     routing::{get, post, put},
     Extension, Json, Router,
 };
 use serde::{Deserialize, Serialize};
-use sqlx::PgPool;
+use sqlx::{PgPool, Postgres, Transaction};
 use utoipa::ToSchema;
 
-use glyph_db::{DataSourceRepository, PgDataSourceRepository};
+use glyph_common::{content_type, StorageError, StorageService, StoredObject};
+use glyph_db::{
+    CreateTaskError, DataSourceRepository, NewTask, PgDataSourceRepository, PgTaskRepository,
+};
 use glyph_domain::{
-    CreateDataSource, DataSource, DataSourceConfig, DataSourceFilter, DataSourceId, DataSourceType,
-    ProjectId, UpdateDataSource, ValidationMode,
+    ApiAuthType, CreateDataSource, DataSource, DataSourceConfig, DataSourceFilter, DataSourceId,
+    DataSourceType, ProjectId, UpdateDataSource, ValidationMode, DEFAULT_CSV_DELIMITER,
 };
 
-use crate::error::ApiError;
+use super::project_types::SchemaAmbiguityResponse;
+use crate::error::{ApiError, FieldError};
 use crate::extractors::CurrentUser;
+use crate::services::storage_service::{
+    ensure_bucket_allowed, storage_for, S3_ALLOWED_BUCKETS_ENV,
+};
+use crate::services::upload_service::{import_upload, RecordSink};
+use crate::services::{
+    preview_sample, CsvOptions, ImportFormat, SampleError, SamplePreview, SchemaValidationService,
+    UploadLimits,
+};
+
+/// Items previewed when `n` isn't given
+const DEFAULT_PREVIEW_ITEMS: usize = 5;
+
+/// Most items a preview may ask for
+const MAX_PREVIEW_ITEMS: usize = 50;
+
+/// Most bytes read from a source when fetching a preview sample
+const MAX_PREVIEW_BYTES: usize = 1024 * 1024;
+
+/// Files of a storage-backed source searched for one that can be previewed
+const PREVIEW_SCAN_FILES: usize = 100;
+
+/// How long to wait for a source to return a preview sample
+const PREVIEW_FETCH_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(10);
+
+/// Files listed per page when no limit is given
+const DEFAULT_FILE_PAGE: usize = 100;
+
+/// Most files listed per page
+const MAX_FILE_PAGE: usize = 1000;
 
 /// Data source list query parameters
 #[derive(Debug, Deserialize)]
@@ -89,6 +122,15 @@ pub struct UpdateDataSourceRequest {
     pub is_active: Option<bool>,
 }
 
+/// Data source update query parameters
+#[derive(Debug, Default, Deserialize)]
+pub struct UpdateDataSourceQuery {
+    /// Run a connection test against the updated config and reject the
+    /// update if it fails
+    #[serde(default)]
+    pub validate: bool,
+}
+
 /// Request to update credentials
 #[derive(Debug, Deserialize, ToSchema)]
 pub struct UpdateCredentialsRequest {
@@ -104,14 +146,41 @@ pub struct TestConnectionResponse {
     pub sample_files: Option<Vec<String>>,
 }
 
+/// File list query parameters
+#[derive(Debug, Deserialize)]
+pub struct ListFilesQuery {
+    /// Only files whose path starts with this
+    pub prefix: Option<String>,
+    /// Only files whose path sorts after this; the last path of the previous page
+    pub after: Option<String>,
+    /// Page size (default 100, max 1000)
+    pub limit: Option<usize>,
+}
+
 /// File list response
 #[derive(Debug, Serialize, ToSchema)]
 pub struct FileListResponse {
     pub files: Vec<FileInfoResponse>,
+    /// Files on this page
     pub total: i64,
+    /// More files follow; pass the last path as `after` to fetch them
     pub has_more: bool,
 }
 
+/// Result of importing an uploaded file
+#[derive(Debug, Serialize, ToSchema)]
+pub struct UploadResponse {
+    pub file_name: String,
+    pub content_type: String,
+    pub bytes: u64,
+    /// Tasks created from the file
+    pub imported: u64,
+    /// Lines that could not be parsed
+    pub skipped: u64,
+    /// The first unparseable lines, with line numbers
+    pub errors: Vec<RecordErrorResponse>,
+}
+
 #[derive(Debug, Serialize, ToSchema)]
 pub struct FileInfoResponse {
     pub path: String,
@@ -120,6 +189,92 @@ pub struct FileInfoResponse {
     pub content_type: Option<String>,
 }
 
+/// Preview query parameters
+#[derive(Debug, Deserialize)]
+pub struct PreviewItemsQuery {
+    /// Items to parse (default 5, max 50)
+    pub n: Option<usize>,
+}
+
+/// Parsed sample records and the schema inferred from them
+#[derive(Debug, Serialize, ToSchema)]
+pub struct DataSourcePreviewResponse {
+    pub records: Vec<serde_json::Value>,
+    /// Items that couldn't be parsed
+    pub errors: Vec<RecordErrorResponse>,
+    pub schema: serde_json::Value,
+    pub ambiguities: Vec<SchemaAmbiguityResponse>,
+}
+
+/// An item in a file or sample that couldn't be parsed
+#[derive(Debug, Serialize, ToSchema)]
+pub struct RecordErrorResponse {
+    /// 1-based line of the item
+    pub item: usize,
+    pub message: String,
+}
+
+impl From<SampleError> for RecordErrorResponse {
+    fn from(e: SampleError) -> Self {
+        Self {
+            item: e.item,
+            message: e.message,
+        }
+    }
+}
+
+impl From<SamplePreview> for DataSourcePreviewResponse {
+    fn from(preview: SamplePreview) -> Self {
+        let inferred = SchemaValidationService::new().infer_schema(&preview.records);
+        Self {
+            records: preview.records,
+            errors: preview
+                .errors
+                .into_iter()
+                .map(RecordErrorResponse::from)
+                .collect(),
+            schema: inferred.schema,
+            ambiguities: inferred
+                .ambiguities
+                .into_iter()
+                .map(SchemaAmbiguityResponse::from)
+                .collect(),
+        }
+    }
+}
+
+/// OpenAPI paths and schemas served by this module
+#[derive(utoipa::OpenApi)]
+#[openapi(
+    paths(
+        list_data_sources,
+        get_data_source,
+        create_data_source,
+        update_data_source,
+        delete_data_source,
+        test_connection,
+        list_files,
+        update_credentials,
+        trigger_sync,
+        upload_file,
+        preview_items
+    ),
+    components(schemas(
+        DataSourceListResponse,
+        DataSourceResponse,
+        CreateDataSourceRequest,
+        UpdateDataSourceRequest,
+        UpdateCredentialsRequest,
+        TestConnectionResponse,
+        FileListResponse,
+        UploadResponse,
+        FileInfoResponse,
+        DataSourcePreviewResponse,
+        RecordErrorResponse
+    ))
+)]
+pub(super) struct ApiPaths;
+
 pub fn routes() -> Router {
     Router::new()
         .route("/", get(list_data_sources).post(create_data_source))
@@ -133,6 +288,12 @@ pub fn routes() -> Router {
         .route("/{data_source_id}/files", get(list_files))
         .route("/{data_source_id}/credentials", put(update_credentials))
         .route("/{data_source_id}/sync", post(trigger_sync))
+        .route("/{data_source_id}/preview", get(preview_items))
+        .route(
+            "/{data_source_id}/upload",
+            // The upload handler enforces the data source's own limit while streaming
+            post(upload_file).layer(DefaultBodyLimit::disable()),
+        )
 }
 
 /// List data sources for a project
@@ -154,7 +315,7 @@ async fn list_data_sources(
     Path(project_id): Path<String>,
     Query(params): Query<ListDataSourcesQuery>,
     Extension(pool): Extension<PgPool>,
-    _current_user: CurrentUser,
+    current_user: CurrentUser,
 ) -> Result<Json<DataSourceListResponse>, ApiError> {
     let project_id_parsed: ProjectId = project_id
         .parse()
@@ -168,7 +329,7 @@ async fn list_data_sources(
         is_active: params.is_active,
     };
 
-    let repo = PgDataSourceRepository::new(pool);
+    let repo = PgDataSourceRepository::new(pool).in_org(current_user.org_id);
     let items = repo.list(&filter).await.map_err(|e| {
         tracing::error!("Failed to list data sources: {:?}", e);
         ApiError::Internal(anyhow::anyhow!("{}", e))
@@ -199,7 +360,7 @@ async fn list_data_sources(
 async fn get_data_source(
     Path((project_id, data_source_id)): Path<(String, String)>,
     Extension(pool): Extension<PgPool>,
-    _current_user: CurrentUser,
+    current_user: CurrentUser,
 ) -> Result<Json<DataSourceResponse>, ApiError> {
     let _project_id: ProjectId = project_id
         .parse()
@@ -208,7 +369,7 @@ async fn get_data_source(
         .parse()
         .map_err(|_| ApiError::not_found("data_source", &data_source_id))?;
 
-    let repo = PgDataSourceRepository::new(pool);
+    let repo = PgDataSourceRepository::new(pool).in_org(current_user.org_id);
     let data_source = repo
         .find_by_id(&id)
         .await
@@ -239,7 +400,7 @@ async fn get_data_source(
 async fn create_data_source(
     Path(project_id): Path<String>,
     Extension(pool): Extension<PgPool>,
-    _current_user: CurrentUser,
+    current_user: CurrentUser,
     Json(req): Json<CreateDataSourceRequest>,
 ) -> Result<(StatusCode, Json<DataSourceResponse>), ApiError> {
     let project_id_parsed: ProjectId = project_id
@@ -264,6 +425,7 @@ async fn create_data_source(
 
     // Parse config based on source type
     let config = parse_config(source_type, &req.config)?;
+    ensure_bucket_allowed(current_user.org_id, &config)?;
 
     // Parse validation mode
     let validation_mode = req
@@ -277,7 +439,7 @@ async fn create_data_source(
         validation_mode,
     };
 
-    let repo = PgDataSourceRepository::new(pool);
+    let repo = PgDataSourceRepository::new(pool).in_org(current_user.org_id);
     let data_source = repo
         .create(&project_id_parsed, &create)
         .await
@@ -308,18 +470,21 @@ async fn create_data_source(
     params(
         ("project_id" = String, Path, description = "Project ID"),
         ("data_source_id" = String, Path, description = "Data Source ID"),
+        ("validate" = Option<bool>, Query, description = "Reject the update if the connection test fails"),
     ),
     request_body = UpdateDataSourceRequest,
     responses(
         (status = 200, description = "Data source updated", body = DataSourceResponse),
+        (status = 400, description = "Invalid config, or connection test failed"),
         (status = 404, description = "Data source not found"),
     ),
     tag = "data-sources"
 )]
 async fn update_data_source(
     Path((project_id, data_source_id)): Path<(String, String)>,
+    Query(query): Query<UpdateDataSourceQuery>,
     Extension(pool): Extension<PgPool>,
-    _current_user: CurrentUser,
+    current_user: CurrentUser,
     Json(req): Json<UpdateDataSourceRequest>,
 ) -> Result<Json<DataSourceResponse>, ApiError> {
     let _project_id: ProjectId = project_id
@@ -329,10 +494,9 @@ async fn update_data_source(
         .parse()
         .map_err(|_| ApiError::not_found("data_source", &data_source_id))?;
 
-    // Parse config if provided - need to get current source type first
-    let config = if let Some(config_value) = req.config {
-        // Get current data source to know the type
-        let repo = PgDataSourceRepository::new(pool.clone());
+    // The current source type is needed to parse a new config or to revalidate
+    let config = if req.config.is_some() || query.validate {
+        let repo = PgDataSourceRepository::new(pool.clone()).in_org(current_user.org_id);
         let current = repo
             .find_by_id(&id)
             .await
@@ -342,7 +506,26 @@ async fn update_data_source(
             })?
             .ok_or_else(|| ApiError::not_found("data_source", &data_source_id))?;
 
-        Some(parse_config(current.source_type, &config_value)?)
+        let config = req
+            .config
+            .as_ref()
+            .map(|value| parse_config(current.source_type, value))
+            .transpose()?;
+        if let Some(config) = &config {
+            ensure_bucket_allowed(current_user.org_id, config)?;
+        }
+
+        // Check the config the source will have after the update, so a
+        // working source isn't broken by an edit
+        if query.validate {
+            let mut candidate = current;
+            if let Some(config) = &config {
+                candidate.config = config.clone();
+            }
+            revalidate_connection(&candidate).await?;
+        }
+
+        config
     } else {
         None
     };
@@ -358,7 +541,7 @@ async fn update_data_source(
         is_active: req.is_active,
     };
 
-    let repo = PgDataSourceRepository::new(pool);
+    let repo = PgDataSourceRepository::new(pool).in_org(current_user.org_id);
     let data_source = repo.update(&id, &update).await.map_err(|e| match e {
         glyph_db::UpdateDataSourceError::NotFound(_) => {
             ApiError::not_found("data_source", &data_source_id)
@@ -393,7 +576,7 @@ async fn update_data_source(
 async fn delete_data_source(
     Path((project_id, data_source_id)): Path<(String, String)>,
     Extension(pool): Extension<PgPool>,
-    _current_user: CurrentUser,
+    current_user: CurrentUser,
 ) -> Result<StatusCode, ApiError> {
     let _project_id: ProjectId = project_id
         .parse()
@@ -402,7 +585,7 @@ async fn delete_data_source(
         .parse()
         .map_err(|_| ApiError::not_found("data_source", &data_source_id))?;
 
-    let repo = PgDataSourceRepository::new(pool);
+    let repo = PgDataSourceRepository::new(pool).in_org(current_user.org_id);
     repo.delete(&id).await.map_err(|e| match e {
         glyph_db::DeleteDataSourceError::NotFound(_) => {
             ApiError::not_found("data_source", &data_source_id)
@@ -417,6 +600,9 @@ async fn delete_data_source(
 }
 
 /// Test connection to a data source
+///
+/// File upload and S3 sources are tested by listing a few of their files.
+/// Other source types can't be tested yet.
 #[utoipa::path(
     post,
     path = "/api/v1/projects/{project_id}/data-sources/{data_source_id}/test",
@@ -426,6 +612,7 @@ async fn delete_data_source(
     ),
     responses(
         (status = 200, description = "Connection test result", body = TestConnectionResponse),
+        (status = 400, description = "Connection tests aren't supported for this source type"),
         (status = 404, description = "Data source not found"),
     ),
     tag = "data-sources"
@@ -433,7 +620,7 @@ async fn delete_data_source(
 async fn test_connection(
     Path((project_id, data_source_id)): Path<(String, String)>,
     Extension(pool): Extension<PgPool>,
-    _current_user: CurrentUser,
+    current_user: CurrentUser,
 ) -> Result<Json<TestConnectionResponse>, ApiError> {
     let _project_id: ProjectId = project_id
         .parse()
@@ -443,7 +630,7 @@ async fn test_connection(
         .map_err(|_| ApiError::not_found("data_source", &data_source_id))?;
 
     // Verify data source exists
-    let repo = PgDataSourceRepository::new(pool);
+    let repo = PgDataSourceRepository::new(pool).in_org(current_user.org_id);
     let data_source = repo
         .find_by_id(&id)
         .await
@@ -453,24 +640,7 @@ async fn test_connection(
         })?
         .ok_or_else(|| ApiError::not_found("data_source", &data_source_id))?;
 
-    // Test based on source type
-    let (success, message) = match data_source.source_type {
-        DataSourceType::FileUpload => (true, "File upload source is ready".to_string()),
-        DataSourceType::S3 => (false, "S3 connection test not implemented yet".to_string()),
-        DataSourceType::Gcs => (false, "GCS connection test not implemented yet".to_string()),
-        DataSourceType::AzureBlob => (
-            false,
-            "Azure Blob connection test not implemented yet".to_string(),
-        ),
-        DataSourceType::Api => (false, "API connection test not implemented yet".to_string()),
-    };
-
-    Ok(Json(TestConnectionResponse {
-        success,
-        message,
-        latency_ms: None,
-        sample_files: None,
-    }))
+    Ok(Json(check_connection(&data_source).await?))
 }
 
 /// List files in a data source
@@ -480,17 +650,22 @@ async fn test_connection(
     params(
         ("project_id" = String, Path, description = "Project ID"),
         ("data_source_id" = String, Path, description = "Data Source ID"),
+        ("prefix" = Option<String>, Query, description = "Only files whose path starts with this"),
+        ("after" = Option<String>, Query, description = "Only files whose path sorts after this"),
+        ("limit" = Option<usize>, Query, description = "Page size (default 100, max 1000)"),
     ),
     responses(
         (status = 200, description = "File list", body = FileListResponse),
+        (status = 400, description = "Source type has no file storage"),
         (status = 404, description = "Data source not found"),
     ),
     tag = "data-sources"
 )]
 async fn list_files(
     Path((project_id, data_source_id)): Path<(String, String)>,
+    Query(query): Query<ListFilesQuery>,
     Extension(pool): Extension<PgPool>,
-    _current_user: CurrentUser,
+    current_user: CurrentUser,
 ) -> Result<Json<FileListResponse>, ApiError> {
     let _project_id: ProjectId = project_id
         .parse()
@@ -499,9 +674,8 @@ async fn list_files(
         .parse()
         .map_err(|_| ApiError::not_found("data_source", &data_source_id))?;
 
-    // Verify data source exists
-    let repo = PgDataSourceRepository::new(pool);
-    let _data_source = repo
+    let repo = PgDataSourceRepository::new(pool).in_org(current_user.org_id);
+    let data_source = repo
         .find_by_id(&id)
         .await
         .map_err(|e| {
@@ -510,15 +684,41 @@ async fn list_files(
         })?
         .ok_or_else(|| ApiError::not_found("data_source", &data_source_id))?;
 
-    // Placeholder - will use StorageService to list files
+    let limit = query
+        .limit
+        .unwrap_or(DEFAULT_FILE_PAGE)
+        .clamp(1, MAX_FILE_PAGE);
+    let storage = storage_for(&data_source)?;
+    let page = storage
+        .list(
+            query.prefix.as_deref().unwrap_or_default(),
+            query.after.as_deref(),
+            limit,
+        )
+        .await?;
+    let mut files = Vec::new();
+    for object in page.objects {
+        let content_type = file_content_type(storage.as_ref(), &object).await?;
+        files.push(FileInfoResponse {
+            path: object.key,
+            size_bytes: object.size_bytes,
+            modified_at: object.modified_at.map(|t| t.to_rfc3339()),
+            content_type: Some(content_type.to_string()),
+        });
+    }
+
     Ok(Json(FileListResponse {
-        files: vec![],
-        total: 0,
-        has_more: false,
+        total: files.len() as i64,
+        files,
+        has_more: page.has_more,
     }))
 }
 
 /// Update credentials for a data source
+///
+/// Per-source credentials aren't stored yet: S3 sources are read with the
+/// server's credentials, limited to buckets granted in `S3_ALLOWED_BUCKETS`,
+/// so this always responds 400 rather than pretending to save them.
 #[utoipa::path(
     put,
     path = "/api/v1/projects/{project_id}/data-sources/{data_source_id}/credentials",
@@ -528,7 +728,7 @@ async fn list_files(
     ),
     request_body = UpdateCredentialsRequest,
     responses(
-        (status = 204, description = "Credentials updated"),
+        (status = 400, description = "Per-source credentials are not supported"),
         (status = 404, description = "Data source not found"),
     ),
     tag = "data-sources"
@@ -536,7 +736,7 @@ async fn list_files(
 async fn update_credentials(
     Path((project_id, data_source_id)): Path<(String, String)>,
     Extension(pool): Extension<PgPool>,
-    _current_user: CurrentUser,
+    current_user: CurrentUser,
     Json(_req): Json<UpdateCredentialsRequest>,
 ) -> Result<StatusCode, ApiError> {
     let _project_id: ProjectId = project_id
@@ -547,7 +747,7 @@ async fn update_credentials(
         .map_err(|_| ApiError::not_found("data_source", &data_source_id))?;
 
     // Verify data source exists
-    let repo = PgDataSourceRepository::new(pool);
+    let repo = PgDataSourceRepository::new(pool).in_org(current_user.org_id);
     let _data_source = repo
         .find_by_id(&id)
         .await
@@ -557,8 +757,13 @@ async fn update_credentials(
         })?
         .ok_or_else(|| ApiError::not_found("data_source", &data_source_id))?;
 
-    // Placeholder - will encrypt and store credentials
-    Ok(StatusCode::NO_CONTENT)
+    Err(ApiError::bad_request(
+        "data_source.credentials_unsupported",
+        format!(
+            "Per-source credentials are not supported; S3 buckets are read with the \
+             server's credentials once listed in {S3_ALLOWED_BUCKETS_ENV}"
+        ),
+    ))
 }
 
 /// Trigger a sync for a data source
@@ -579,7 +784,7 @@ async fn update_credentials(
 async fn trigger_sync(
     Path((project_id, data_source_id)): Path<(String, String)>,
     Extension(pool): Extension<PgPool>,
-    _current_user: CurrentUser,
+    current_user: CurrentUser,
 ) -> Result<StatusCode, ApiError> {
     let _project_id: ProjectId = project_id
         .parse()
@@ -589,7 +794,7 @@ async fn trigger_sync(
         .map_err(|_| ApiError::not_found("data_source", &data_source_id))?;
 
     // Verify data source exists
-    let repo = PgDataSourceRepository::new(pool);
+    let repo = PgDataSourceRepository::new(pool).in_org(current_user.org_id);
     let _data_source = repo
         .find_by_id(&id)
         .await
@@ -606,148 +811,445 @@ async fn trigger_sync(
     ))
 }
 
+/// Upload a file to a file upload data source, importing each record as a task
+///
+/// Expects `multipart/form-data` with the file in a `file` field. The body is
+/// streamed: uploads over the data source's `max_file_size_mb` are rejected
+/// with 413 as soon as they pass it. The file is streamed into the data
+/// source's storage under its file name, replacing any earlier upload of
+/// the same name. Tasks are created in one transaction that commits only
+/// once the file is stored, so a failed upload imports nothing.
+#[utoipa::path(
+    post,
+    path = "/api/v1/projects/{project_id}/data-sources/{data_source_id}/upload",
+    params(
+        ("project_id" = String, Path, description = "Project ID"),
+        ("data_source_id" = String, Path, description = "Data Source ID"),
+    ),
+    responses(
+        (status = 201, description = "File imported", body = UploadResponse),
+        (status = 400, description = "Not a file upload source, disallowed extension, or malformed body"),
+        (status = 404, description = "Data source not found"),
+        (status = 413, description = "File exceeds the data source's size limit"),
+    ),
+    tag = "data-sources"
+)]
+async fn upload_file(
+    Path((project_id, data_source_id)): Path<(String, String)>,
+    Extension(pool): Extension<PgPool>,
+    current_user: CurrentUser,
+    headers: HeaderMap,
+    multipart: Multipart,
+) -> Result<(StatusCode, Json<UploadResponse>), ApiError> {
+    let project_id: ProjectId = project_id
+        .parse()
+        .map_err(|_| ApiError::not_found("project", &project_id))?;
+    let id: DataSourceId = data_source_id
+        .parse()
+        .map_err(|_| ApiError::not_found("data_source", &data_source_id))?;
+
+    let data_source = PgDataSourceRepository::new(pool.clone())
+        .in_org(current_user.org_id)
+        .find_by_id(&id)
+        .await
+        .map_err(|e| {
+            tracing::error!("Failed to find data source: {:?}", e);
+            ApiError::Internal(anyhow::anyhow!("{}", e))
+        })?
+        .filter(|ds| ds.project_id == project_id)
+        .ok_or_else(|| ApiError::not_found("data_source", &data_source_id))?;
+
+    let limits = UploadLimits::from_config(&data_source.config).ok_or_else(|| {
+        ApiError::bad_request(
+            "upload.not_file_upload",
+            "Files can only be uploaded to file upload data sources",
+        )
+    })?;
+    limits.check_content_length(&headers)?;
+
+    let storage = storage_for(&data_source)?;
+    let mut sink = TaskImportSink {
+        repo: PgTaskRepository::new(pool.clone()),
+        tx: pool
+            .begin()
+            .await
+            .map_err(|e| ApiError::Internal(e.into()))?,
+        project_id,
+        metadata: serde_json::json!({ "data_source_id": id.to_string() }),
+    };
+    // Dropping the sink on error rolls back every task it created
+    let summary = import_upload(multipart, &limits, storage.as_ref(), &mut sink).await?;
+    sink.tx
+        .commit()
+        .await
+        .map_err(|e| ApiError::Internal(e.into()))?;
+
+    tracing::info!(
+        data_source_id = %id,
+        file_name = %summary.file_name,
+        imported = summary.records,
+        skipped = summary.skipped,
+        "Imported uploaded file"
+    );
+
+    Ok((
+        StatusCode::CREATED,
+        Json(UploadResponse {
+            file_name: summary.file_name,
+            content_type: summary.content_type,
+            bytes: summary.bytes,
+            imported: summary.records,
+            skipped: summary.skipped,
+            errors: summary
+                .errors
+                .into_iter()
+                .map(RecordErrorResponse::from)
+                .collect(),
+        }),
+    ))
+}
+
+/// Preview parsed sample records from a data source
+///
+/// API sources are fetched and parsed as JSON, JSON Lines, or CSV by content
+/// type. Bucket sources are previewed from the start of their first file in
+/// one of those formats, read through the source's storage. File upload
+/// sources are previewed from the records already imported from them. Items
+/// that fail to parse are reported alongside the records rather than failing
+/// the request.
+#[utoipa::path(
+    get,
+    path = "/api/v1/projects/{project_id}/data-sources/{data_source_id}/preview",
+    params(
+        ("project_id" = String, Path, description = "Project ID"),
+        ("data_source_id" = String, Path, description = "Data Source ID"),
+        ("n" = Option<usize>, Query, description = "Items to parse (default 5, max 50)"),
+    ),
+    responses(
+        (status = 200, description = "Sample records and inferred schema", body = DataSourcePreviewResponse),
+        (status = 400, description = "Source can't be previewed or couldn't be fetched"),
+        (status = 404, description = "Data source not found"),
+    ),
+    tag = "data-sources"
+)]
+async fn preview_items(
+    Path((project_id, data_source_id)): Path<(String, String)>,
+    Query(query): Query<PreviewItemsQuery>,
+    Extension(pool): Extension<PgPool>,
+    current_user: CurrentUser,
+) -> Result<Json<DataSourcePreviewResponse>, ApiError> {
+    let project_id: ProjectId = project_id
+        .parse()
+        .map_err(|_| ApiError::not_found("project", &project_id))?;
+    let id: DataSourceId = data_source_id
+        .parse()
+        .map_err(|_| ApiError::not_found("data_source", &data_source_id))?;
+    let limit = query
+        .n
+        .unwrap_or(DEFAULT_PREVIEW_ITEMS)
+        .clamp(1, MAX_PREVIEW_ITEMS);
+
+    let data_source = PgDataSourceRepository::new(pool.clone())
+        .in_org(current_user.org_id)
+        .find_by_id(&id)
+        .await
+        .map_err(|e| {
+            tracing::error!("Failed to find data source: {:?}", e);
+            ApiError::Internal(anyhow::anyhow!("{}", e))
+        })?
+        .filter(|ds| ds.project_id == project_id)
+        .ok_or_else(|| ApiError::not_found("data_source", &data_source_id))?;
+
+    let preview = match &data_source.config {
+        DataSourceConfig::Api {
+            endpoint, headers, ..
+        } => {
+            let (format, bytes) = fetch_sample(endpoint, headers).await?;
+            preview_sample(format, &bytes, limit)?
+        }
+        DataSourceConfig::FileUpload { .. } => SamplePreview {
+            records: imported_records(&pool, &project_id, &id, limit).await?,
+            errors: Vec::new(),
+        },
+        _ => {
+            let storage = storage_for(&data_source)?;
+            let (format, bytes) = sample_stored_file(storage.as_ref()).await?;
+            preview_sample(format, &bytes, limit)?
+        }
+    };
+
+    Ok(Json(DataSourcePreviewResponse::from(preview)))
+}
+
+/// Fetch the start of an API source's response body
+async fn fetch_sample(
+    endpoint: &str,
+    headers: &std::collections::HashMap<String, String>,
+) -> Result<(ImportFormat, Vec<u8>), ApiError> {
+    let fetch_failed =
+        |message: String| ApiError::bad_request("data_source.preview_fetch_failed", message);
+
+    let client = reqwest::Client::builder()
+        .timeout(PREVIEW_FETCH_TIMEOUT)
+        .build()
+        .map_err(|e| ApiError::Internal(e.into()))?;
+    let mut request = client.get(endpoint);
+    for (name, value) in headers {
+        request = request.header(name, value);
+    }
+    let mut response = request
+        .send()
+        .await
+        .and_then(reqwest::Response::error_for_status)
+        .map_err(|e| fetch_failed(format!("Couldn't fetch {endpoint}: {e}")))?;
+
+    let content_type = response
+        .headers()
+        .get(reqwest::header::CONTENT_TYPE)
+        .and_then(|v| v.to_str().ok())
+        .map(str::to_string);
+    let format =
+        ImportFormat::detect(content_type.as_deref(), endpoint).unwrap_or(ImportFormat::Json);
+
+    let mut bytes = Vec::new();
+    while let Some(chunk) = response
+        .chunk()
+        .await
+        .map_err(|e| fetch_failed(format!("Couldn't read {endpoint}: {e}")))?
+    {
+        bytes.extend_from_slice(&chunk);
+        if bytes.len() >= MAX_PREVIEW_BYTES {
+            bytes.truncate(MAX_PREVIEW_BYTES);
+            break;
+        }
+    }
+
+    Ok((format, bytes))
+}
+
+/// Read the start of the first JSON, JSON Lines or CSV file among the first
+/// [`PREVIEW_SCAN_FILES`] files of a storage-backed source
+async fn sample_stored_file(
+    storage: &dyn StorageService,
+) -> Result<(ImportFormat, Vec<u8>), ApiError> {
+    let page = storage.list("", None, PREVIEW_SCAN_FILES).await?;
+    let Some((object, format)) = page
+        .objects
+        .iter()
+        .find_map(|o| ImportFormat::detect(None, &o.key).map(|format| (o, format)))
+    else {
+        return Err(ApiError::bad_request(
+            "data_source.preview_no_files",
+            "The source has no JSON, JSON Lines or CSV files to preview",
+        ));
+    };
+    let bytes = match object.size_bytes.min(MAX_PREVIEW_BYTES as u64) {
+        0 => Vec::new(),
+        len => storage.get_range(&object.key, 0..len).await?,
+    };
+    Ok((format, bytes))
+}
+
+/// The most recent records imported from a file upload source
+async fn imported_records(
+    pool: &PgPool,
+    project_id: &ProjectId,
+    id: &DataSourceId,
+    limit: usize,
+) -> Result<Vec<serde_json::Value>, ApiError> {
+    sqlx::query_scalar(
+        r#"
+        SELECT input_data
+        FROM tasks
+        WHERE project_id = $1 AND metadata->>'data_source_id' = $2
+        ORDER BY created_at DESC
+        LIMIT $3
+        "#,
+    )
+    .bind(project_id.as_uuid())
+    .bind(id.to_string())
+    .bind(i64::try_from(limit).unwrap_or(i64::MAX))
+    .fetch_all(pool)
+    .await
+    .map_err(|e| ApiError::Internal(e.into()))
+}
+
+/// Creates a task per uploaded record, all in one transaction
+struct TaskImportSink {
+    repo: PgTaskRepository,
+    tx: Transaction<'static, Postgres>,
+    project_id: ProjectId,
+    metadata: serde_json::Value,
+}
+
+impl RecordSink for TaskImportSink {
+    async fn accept(&mut self, records: Vec<serde_json::Value>) -> Result<(), ApiError> {
+        for input_data in records {
+            let task = NewTask {
+                project_id: self.project_id,
+                input_data,
+                priority: None,
+                metadata: Some(self.metadata.clone()),
+                gold_output: None,
+            };
+            self.repo
+                .create_in(&mut self.tx, &task)
+                .await
+                .map_err(|e| match e {
+                    CreateTaskError::ProjectNotFound(id) => {
+                        ApiError::not_found("project", id.to_string())
+                    }
+                    CreateTaskError::Database(e) => ApiError::Internal(e.into()),
+                })?;
+        }
+        Ok(())
+    }
+}
+
 // =============================================================================
 // Helper functions
 // =============================================================================
 
+/// Files listed by a connection test
+const CONNECTION_SAMPLE_FILES: usize = 5;
+
+/// Test connectivity for a data source by listing a few of its files.
+///
+/// Fails with 400 for source types without a storage backend, which can't
+/// be tested yet.
+async fn check_connection(data_source: &DataSource) -> Result<TestConnectionResponse, ApiError> {
+    let storage = match data_source.source_type {
+        DataSourceType::FileUpload | DataSourceType::S3 => storage_for(data_source)?,
+        DataSourceType::Gcs | DataSourceType::AzureBlob | DataSourceType::Api => {
+            return Err(ApiError::bad_request(
+                "data_source.connection_test_unsupported",
+                format!(
+                    "Connection tests aren't supported for {} data sources yet",
+                    data_source.source_type.as_str()
+                ),
+            ));
+        }
+    };
+    Ok(probe_storage(storage.as_ref()).await)
+}
+
+/// A listed file's content type. Only files without a recognised extension
+/// have their leading bytes read; the rest of the object is never fetched.
+async fn file_content_type(
+    storage: &dyn StorageService,
+    object: &StoredObject,
+) -> Result<&'static str, StorageError> {
+    if let Some(content_type) = content_type::from_extension(&object.key) {
+        return Ok(content_type);
+    }
+    if object.size_bytes == 0 {
+        return Ok(content_type::OCTET_STREAM);
+    }
+    let head = storage
+        .get_range(
+            &object.key,
+            0..object.size_bytes.min(content_type::SNIFF_BYTES),
+        )
+        .await?;
+    Ok(content_type::detect_content_type(&object.key, &head))
+}
+
+/// List the first few objects in `storage`, reporting how long it took
+async fn probe_storage(storage: &dyn StorageService) -> TestConnectionResponse {
+    let started = std::time::Instant::now();
+    let result = storage.list("", None, CONNECTION_SAMPLE_FILES).await;
+    let latency_ms = i64::try_from(started.elapsed().as_millis()).unwrap_or(i64::MAX);
+
+    match result {
+        Ok(page) => TestConnectionResponse {
+            success: true,
+            message: "Connected".to_string(),
+            latency_ms: Some(latency_ms),
+            sample_files: Some(page.objects.into_iter().map(|o| o.key).collect()),
+        },
+        Err(e) => TestConnectionResponse {
+            success: false,
+            message: e.to_string(),
+            latency_ms: Some(latency_ms),
+            sample_files: None,
+        },
+    }
+}
+
+/// Reject a configuration that fails its connection test
+async fn revalidate_connection(data_source: &DataSource) -> Result<(), ApiError> {
+    let result = check_connection(data_source).await?;
+    if result.success {
+        Ok(())
+    } else {
+        Err(ApiError::bad_request(
+            "data_source.connection_failed",
+            format!(
+                "Connection test failed; data source not updated: {}",
+                result.message
+            ),
+        ))
+    }
+}
+
 /// Parse config JSON into appropriate DataSourceConfig variant
+///
+/// Every missing or invalid field is collected so the client can fix them all
+/// at once; any problem fails the whole config with a validation error.
 fn parse_config(
     source_type: DataSourceType,
     config: &serde_json::Value,
 ) -> Result<DataSourceConfig, ApiError> {
-    match source_type {
+    let mut fields = ConfigFields::new(config);
+
+    let parsed = match source_type {
         DataSourceType::FileUpload => {
-            let allowed_extensions = config
-                .get("allowed_extensions")
-                .and_then(|v| v.as_array())
-                .map(|arr| {
-                    arr.iter()
-                        .filter_map(|v| v.as_str().map(String::from))
-                        .collect()
-                })
-                .unwrap_or_else(|| {
+            let allowed_extensions =
+                fields.string_list("allowed_extensions").unwrap_or_else(|| {
                     vec!["json".to_string(), "jsonl".to_string(), "csv".to_string()]
                 });
+            let max_file_size_mb = fields.positive_int("max_file_size_mb").unwrap_or(100);
+            let csv_delimiter = fields
+                .optional_str("csv_delimiter")
+                .unwrap_or_else(|| DEFAULT_CSV_DELIMITER.to_string());
+            if CsvOptions::delimiter_byte(&csv_delimiter).is_none() {
+                fields.invalid("csv_delimiter", "Must be a single ASCII character");
+            }
 
-            let max_file_size_mb = config
-                .get("max_file_size_mb")
-                .and_then(|v| v.as_i64())
-                .unwrap_or(100) as i32;
-
-            Ok(DataSourceConfig::FileUpload {
+            DataSourceConfig::FileUpload {
                 allowed_extensions,
                 max_file_size_mb,
-            })
-        }
-        DataSourceType::S3 => {
-            let bucket = config
-                .get("bucket")
-                .and_then(|v| v.as_str())
-                .ok_or_else(|| {
-                    ApiError::bad_request("validation.missing_field", "S3 bucket is required")
-                })?
-                .to_string();
-
-            let region = config
-                .get("region")
-                .and_then(|v| v.as_str())
-                .ok_or_else(|| {
-                    ApiError::bad_request("validation.missing_field", "S3 region is required")
-                })?
-                .to_string();
-
-            let prefix = config
-                .get("prefix")
-                .and_then(|v| v.as_str())
-                .map(String::from);
-            let use_iam_role = config
-                .get("use_iam_role")
-                .and_then(|v| v.as_bool())
-                .unwrap_or(false);
-
-            Ok(DataSourceConfig::S3 {
-                bucket,
-                region,
-                prefix,
-                use_iam_role,
-            })
-        }
-        DataSourceType::Gcs => {
-            let bucket = config
-                .get("bucket")
-                .and_then(|v| v.as_str())
-                .ok_or_else(|| {
-                    ApiError::bad_request("validation.missing_field", "GCS bucket is required")
-                })?
-                .to_string();
-
-            let prefix = config
-                .get("prefix")
-                .and_then(|v| v.as_str())
-                .map(String::from);
-            let use_workload_identity = config
-                .get("use_workload_identity")
-                .and_then(|v| v.as_bool())
-                .unwrap_or(false);
-
-            Ok(DataSourceConfig::Gcs {
-                bucket,
-                prefix,
-                use_workload_identity,
-            })
-        }
-        DataSourceType::AzureBlob => {
-            let container = config
-                .get("container")
-                .and_then(|v| v.as_str())
-                .ok_or_else(|| {
-                    ApiError::bad_request(
-                        "validation.missing_field",
-                        "Azure Blob container is required",
-                    )
-                })?
-                .to_string();
-
-            let account = config
-                .get("account")
-                .and_then(|v| v.as_str())
-                .ok_or_else(|| {
-                    ApiError::bad_request(
-                        "validation.missing_field",
-                        "Azure storage account is required",
-                    )
-                })?
-                .to_string();
-
-            let prefix = config
-                .get("prefix")
-                .and_then(|v| v.as_str())
-                .map(String::from);
-            let use_managed_identity = config
-                .get("use_managed_identity")
-                .and_then(|v| v.as_bool())
-                .unwrap_or(false);
-
-            Ok(DataSourceConfig::AzureBlob {
-                container,
-                account,
-                prefix,
-                use_managed_identity,
-            })
+                csv_delimiter,
+                csv_coerce_types: fields.bool_or("csv_coerce_types", false),
+            }
         }
+        DataSourceType::S3 => DataSourceConfig::S3 {
+            bucket: fields.required_str("bucket", "S3 bucket is required"),
+            region: fields.required_str("region", "S3 region is required"),
+            prefix: fields.optional_str("prefix"),
+            use_iam_role: fields.bool_or("use_iam_role", false),
+        },
+        DataSourceType::Gcs => DataSourceConfig::Gcs {
+            bucket: fields.required_str("bucket", "GCS bucket is required"),
+            prefix: fields.optional_str("prefix"),
+            use_workload_identity: fields.bool_or("use_workload_identity", false),
+        },
+        DataSourceType::AzureBlob => DataSourceConfig::AzureBlob {
+            container: fields.required_str("container", "Azure Blob container is required"),
+            account: fields.required_str("account", "Azure storage account is required"),
+            prefix: fields.optional_str("prefix"),
+            use_managed_identity: fields.bool_or("use_managed_identity", false),
+        },
         DataSourceType::Api => {
-            let endpoint = config
-                .get("endpoint")
-                .and_then(|v| v.as_str())
-                .ok_or_else(|| {
-                    ApiError::bad_request("validation.missing_field", "API endpoint is required")
-                })?
-                .to_string();
-
-            let auth_type = config
-                .get("auth_type")
-                .and_then(|v| serde_json::from_value(v.clone()).ok())
-                .unwrap_or_default();
+            let endpoint = fields.required_str("endpoint", "API endpoint is required");
+
+            let auth_type = match config.get("auth_type") {
+                None | Some(serde_json::Value::Null) => ApiAuthType::default(),
+                Some(v) => serde_json::from_value(v.clone()).unwrap_or_else(|_| {
+                    fields.invalid("auth_type", "Unknown API auth type");
+                    ApiAuthType::default()
+                }),
+            };
 
             let headers = config
                 .get("headers")
@@ -759,17 +1261,301 @@ fn parse_config(
                 })
                 .unwrap_or_default();
 
-            let polling_interval_seconds = config
-                .get("polling_interval_seconds")
-                .and_then(|v| v.as_i64())
-                .map(|v| v as i32);
+            let polling_interval_seconds = fields.positive_int("polling_interval_seconds");
 
-            Ok(DataSourceConfig::Api {
+            DataSourceConfig::Api {
                 endpoint,
                 auth_type,
                 headers,
                 polling_interval_seconds,
-            })
+            }
+        }
+    };
+
+    fields.finish().map(|()| parsed)
+}
+
+/// Reads fields out of a data source config, collecting every problem
+struct ConfigFields<'a> {
+    config: &'a serde_json::Value,
+    errors: Vec<FieldError>,
+}
+
+impl<'a> ConfigFields<'a> {
+    fn new(config: &'a serde_json::Value) -> Self {
+        Self {
+            config,
+            errors: Vec::new(),
         }
     }
+
+    fn invalid(&mut self, field: &str, message: &str) {
+        self.errors
+            .push(FieldError::new(format!("config.{field}"), message));
+    }
+
+    fn present(&self, field: &str) -> Option<&'a serde_json::Value> {
+        self.config.get(field).filter(|v| !v.is_null())
+    }
+
+    /// A non-empty string; records `missing` and returns "" when absent
+    fn required_str(&mut self, field: &str, missing: &str) -> String {
+        match self.present(field) {
+            Some(serde_json::Value::String(s)) if !s.trim().is_empty() => s.clone(),
+            Some(serde_json::Value::String(_)) | None => {
+                self.invalid(field, missing);
+                String::new()
+            }
+            Some(_) => {
+                self.invalid(field, "Must be a string");
+                String::new()
+            }
+        }
+    }
+
+    fn optional_str(&mut self, field: &str) -> Option<String> {
+        match self.present(field)? {
+            serde_json::Value::String(s) => Some(s.clone()),
+            _ => {
+                self.invalid(field, "Must be a string");
+                None
+            }
+        }
+    }
+
+    fn bool_or(&mut self, field: &str, default: bool) -> bool {
+        match self.present(field) {
+            None => default,
+            Some(serde_json::Value::Bool(b)) => *b,
+            Some(_) => {
+                self.invalid(field, "Must be true or false");
+                default
+            }
+        }
+    }
+
+    fn positive_int(&mut self, field: &str) -> Option<i32> {
+        let value = self.present(field)?;
+        match value.as_i64().and_then(|v| i32::try_from(v).ok()) {
+            Some(v) if v > 0 => Some(v),
+            _ => {
+                self.invalid(field, "Must be a positive integer");
+                None
+            }
+        }
+    }
+
+    fn string_list(&mut self, field: &str) -> Option<Vec<String>> {
+        let value = self.present(field)?;
+        let items: Option<Vec<String>> = value
+            .as_array()
+            .and_then(|arr| arr.iter().map(|v| v.as_str().map(String::from)).collect());
+        if items.is_none() {
+            self.invalid(field, "Must be a list of strings");
+        }
+        items
+    }
+
+    fn finish(self) -> Result<(), ApiError> {
+        if self.errors.is_empty() {
+            Ok(())
+        } else {
+            Err(ApiError::validation(self.errors))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_jsonl_preview_reports_bad_lines_and_infers_schema() {
+        let sample = b"{\"text\": \"a\", \"score\": 1}\nnot json\n\n{\"text\": \"b\", \"score\": 2}\n{\"text\": \"c\"}";
+
+        let response = DataSourcePreviewResponse::from(
+            preview_sample(ImportFormat::JsonLines, sample, 3).unwrap(),
+        );
+
+        assert_eq!(
+            response.records,
+            vec![
+                serde_json::json!({"text": "a", "score": 1}),
+                serde_json::json!({"text": "b", "score": 2}),
+            ]
+        );
+        assert_eq!(response.errors.len(), 1);
+        assert_eq!(response.errors[0].item, 2);
+        assert_eq!(
+            response.schema["properties"]["text"]["type"],
+            serde_json::json!("string")
+        );
+        let mut required: Vec<&str> = response.schema["required"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .filter_map(serde_json::Value::as_str)
+            .collect();
+        required.sort_unstable();
+        assert_eq!(required, ["score", "text"]);
+    }
+
+    fn field_errors(result: Result<DataSourceConfig, ApiError>) -> Vec<FieldError> {
+        match result {
+            Err(ApiError::Validation { errors }) => errors,
+            other => panic!("expected validation error, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_s3_config_reports_every_missing_field() {
+        let errors = field_errors(parse_config(
+            DataSourceType::S3,
+            &serde_json::json!({ "prefix": "raw/" }),
+        ));
+
+        assert_eq!(
+            errors,
+            vec![
+                FieldError::new("config.bucket", "S3 bucket is required"),
+                FieldError::new("config.region", "S3 region is required"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_invalid_and_missing_fields_are_reported_together() {
+        let errors = field_errors(parse_config(
+            DataSourceType::AzureBlob,
+            &serde_json::json!({ "account": 42, "use_managed_identity": "yes" }),
+        ));
+
+        let fields: Vec<_> = errors.iter().map(|e| e.field.as_str()).collect();
+        assert_eq!(
+            fields,
+            vec![
+                "config.container",
+                "config.account",
+                "config.use_managed_identity"
+            ]
+        );
+    }
+
+    fn data_source(source_type: DataSourceType, config: serde_json::Value) -> DataSource {
+        DataSource {
+            data_source_id: DataSourceId::new(),
+            project_id: ProjectId::new(),
+            org_id: glyph_domain::OrgId::DEFAULT,
+            name: "Source".to_string(),
+            source_type,
+            config: parse_config(source_type, &config).unwrap(),
+            validation_mode: ValidationMode::Strict,
+            last_sync_at: None,
+            item_count: 0,
+            error_count: 0,
+            is_active: true,
+            created_at: chrono::Utc::now(),
+            updated_at: chrono::Utc::now(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_files_without_extensions_are_sniffed_from_their_head() {
+        let root = std::env::temp_dir().join(format!("glyph-sniff-{}", uuid::Uuid::new_v4()));
+        let storage = glyph_common::LocalStorage::new(&root);
+        let mut png = vec![
+            0x89, b'P', b'N', b'G', 0x0D, 0x0A, 0x1A, 0x0A, 0, 0, 0, 0x0D,
+        ];
+        png.resize(64 * 1024, 0);
+        storage.put("scan", png).await.unwrap();
+        storage.put("empty", Vec::new()).await.unwrap();
+        storage.put("rows.csv", b"a,b".to_vec()).await.unwrap();
+
+        let page = storage.list("", None, 10).await.unwrap();
+        let mut types = Vec::new();
+        for object in &page.objects {
+            types.push(file_content_type(&storage, object).await.unwrap());
+        }
+        std::fs::remove_dir_all(&root).unwrap();
+
+        assert_eq!(types, ["application/octet-stream", "text/csv", "image/png"]);
+    }
+
+    #[tokio::test]
+    async fn test_stored_json_larger_than_the_sample_still_previews() {
+        let root = std::env::temp_dir().join(format!("glyph-preview-{}", uuid::Uuid::new_v4()));
+        let storage = glyph_common::LocalStorage::new(&root);
+        let items: Vec<serde_json::Value> = (0..50_000)
+            .map(|i| serde_json::json!({ "id": i, "text": "some text to annotate" }))
+            .collect();
+        let json = serde_json::to_vec(&items).unwrap();
+        assert!(json.len() > MAX_PREVIEW_BYTES);
+        storage
+            .put("a-readme.txt", b"notes".to_vec())
+            .await
+            .unwrap();
+        storage.put("b-items.json", json).await.unwrap();
+
+        let (format, bytes) = sample_stored_file(&storage).await.unwrap();
+        std::fs::remove_dir_all(&root).unwrap();
+
+        assert_eq!(format, ImportFormat::Json);
+        assert_eq!(bytes.len(), MAX_PREVIEW_BYTES);
+        let preview = preview_sample(format, &bytes, 3).unwrap();
+        assert_eq!(preview.records, items[..3]);
+        assert!(preview.errors.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_probe_reports_storage_failures() {
+        // A file where the storage root should be can't be listed
+        let root = std::env::temp_dir().join(format!("glyph-probe-{}", uuid::Uuid::new_v4()));
+        std::fs::write(&root, b"not a directory").unwrap();
+        let result = probe_storage(&glyph_common::LocalStorage::new(&root)).await;
+        std::fs::remove_file(&root).unwrap();
+
+        assert!(!result.success);
+        assert!(result.latency_ms.is_some());
+        assert!(result.sample_files.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_unsupported_source_types_fail_revalidation() {
+        let source = data_source(
+            DataSourceType::Gcs,
+            serde_json::json!({ "bucket": "b", "project_id": "p" }),
+        );
+        let err = revalidate_connection(&source).await.unwrap_err();
+        assert!(matches!(
+            err,
+            ApiError::BadRequest {
+                code: "data_source.connection_test_unsupported",
+                ..
+            }
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_passing_revalidation_allows_update() {
+        let source = data_source(DataSourceType::FileUpload, serde_json::json!({}));
+        let result = check_connection(&source).await.unwrap();
+        assert!(result.success);
+        assert_eq!(result.sample_files, Some(Vec::new()));
+        assert!(revalidate_connection(&source).await.is_ok());
+    }
+
+    #[test]
+    fn test_valid_config_parses() {
+        let config = parse_config(
+            DataSourceType::S3,
+            &serde_json::json!({ "bucket": "tasks", "region": "us-east-1" }),
+        )
+        .unwrap();
+
+        assert!(matches!(
+            config,
+            DataSourceConfig::S3 { ref bucket, ref region, prefix: None, use_iam_role: false }
+                if bucket == "tasks" && region == "us-east-1"
+        ));
+    }
 }
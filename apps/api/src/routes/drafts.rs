@@ -1,16 +1,24 @@
 //! Draft management endpoints for auto-saved annotation work.
 //!
 //! Drafts allow annotators to save work in progress automatically.
-//! Only one draft exists per (task_id, user_id) pair.
-
-use axum::{extract::Path, http::StatusCode, routing::post, Extension, Json, Router};
+//! A draft belongs to the caller's active assignment on a step, so only one
+//! exists per (task_id, step_id, user_id).
+
+use axum::{
+    extract::{Path, Query},
+    http::StatusCode,
+    routing::post,
+    Extension, Json, Router,
+};
 use serde::{Deserialize, Serialize};
 use sqlx::PgPool;
 use utoipa::ToSchema;
 use uuid::Uuid;
 
-use glyph_domain::{Draft, TaskId, UserId};
+use glyph_db::PgDraftRepository;
+use glyph_domain::{Draft, TaskId};
 
+use crate::extractors::CurrentUser;
 use crate::ApiError;
 
 // =============================================================================
@@ -20,15 +28,27 @@ use crate::ApiError;
 /// Request to save or update a draft.
 #[derive(Debug, Deserialize, ToSchema)]
 pub struct SaveDraftRequest {
+    /// Workflow step the work is for; defaults to the caller's most recent
+    /// active assignment on the task
+    #[serde(default)]
+    pub step_id: Option<String>,
     /// Annotation data in progress
     pub data: serde_json::Value,
 }
 
+/// Narrows a draft lookup to one step.
+#[derive(Debug, Deserialize)]
+pub struct DraftQuery {
+    /// Workflow step; without it the most recently saved draft is used
+    pub step_id: Option<String>,
+}
+
 /// Draft response.
 #[derive(Debug, Serialize, ToSchema)]
 pub struct DraftResponse {
     pub draft_id: String,
     pub task_id: String,
+    pub step_id: String,
     pub user_id: String,
     pub data: serde_json::Value,
     pub version: i32,
@@ -41,6 +61,7 @@ impl From<Draft> for DraftResponse {
         Self {
             draft_id: draft.draft_id.to_string(),
             task_id: draft.task_id.to_string(),
+            step_id: draft.step_id,
             user_id: draft.user_id.to_string(),
             data: draft.data,
             version: draft.version,
@@ -54,9 +75,9 @@ impl From<Draft> for DraftResponse {
 // Route Handlers
 // =============================================================================
 
-/// Save or update a draft for a task.
+/// Save or update a draft for a task step.
 /// Upserts: creates if none exists, updates if exists.
-/// Only one draft per (task_id, user_id) pair.
+/// The caller must hold an active assignment on the task.
 #[utoipa::path(
     post,
     path = "/api/v1/tasks/{task_id}/drafts",
@@ -64,35 +85,44 @@ impl From<Draft> for DraftResponse {
     responses(
         (status = 200, description = "Draft saved", body = DraftResponse),
         (status = 201, description = "Draft created", body = DraftResponse),
-        (status = 404, description = "Task not found"),
+        (status = 403, description = "No active assignment on this task"),
     ),
     tag = "drafts"
 )]
 async fn save_draft(
     Path(task_id): Path<Uuid>,
-    Extension(_pool): Extension<PgPool>,
+    current_user: CurrentUser,
+    Extension(pool): Extension<PgPool>,
     Json(req): Json<SaveDraftRequest>,
 ) -> Result<(StatusCode, Json<DraftResponse>), ApiError> {
-    // TODO: Get current user from auth context
-    let user_id = UserId::new(); // Placeholder
     let task_id = TaskId::from_uuid(task_id);
 
-    // TODO: Implement actual database upsert
-    // For now, return a mock response
-    let draft = Draft::new(task_id, user_id, req.data);
-
-    // In real implementation:
-    // 1. Check if draft exists for (task_id, user_id)
-    // 2. If exists: update data and version, return 200
-    // 3. If not: create new draft, return 201
-
-    Ok((StatusCode::CREATED, Json(DraftResponse::from(draft))))
+    let draft = PgDraftRepository::new(pool)
+        .save(
+            &task_id,
+            req.step_id.as_deref(),
+            &current_user.user_id,
+            &req.data,
+        )
+        .await
+        .map_err(|e| ApiError::Internal(e.into()))?
+        .ok_or_else(|| {
+            ApiError::forbidden("Drafts can only be saved on a task you are assigned to")
+        })?;
+
+    let status = if draft.version == 1 {
+        StatusCode::CREATED
+    } else {
+        StatusCode::OK
+    };
+    Ok((status, Json(DraftResponse::from(draft))))
 }
 
 /// Get the current user's draft for a task.
 #[utoipa::path(
     get,
     path = "/api/v1/tasks/{task_id}/drafts",
+    params(("step_id" = Option<String>, Query, description = "Workflow step")),
     responses(
         (status = 200, description = "Draft found", body = DraftResponse),
         (status = 404, description = "No draft found"),
@@ -101,18 +131,19 @@ async fn save_draft(
 )]
 async fn get_draft(
     Path(task_id): Path<Uuid>,
-    Extension(_pool): Extension<PgPool>,
+    Query(query): Query<DraftQuery>,
+    current_user: CurrentUser,
+    Extension(pool): Extension<PgPool>,
 ) -> Result<Json<DraftResponse>, ApiError> {
-    // TODO: Get current user from auth context
-    let _user_id = UserId::new(); // Placeholder
     let task_id = TaskId::from_uuid(task_id);
 
-    // TODO: Implement actual database lookup
-    // For now, return 404 (no draft)
-    Err(ApiError::NotFound {
-        resource_type: "draft",
-        id: task_id.to_string(),
-    })
+    let draft = PgDraftRepository::new(pool)
+        .find(&task_id, &current_user.user_id, query.step_id.as_deref())
+        .await
+        .map_err(|e| ApiError::Internal(e.into()))?
+        .ok_or_else(|| ApiError::not_found("draft", task_id.to_string()))?;
+
+    Ok(Json(DraftResponse::from(draft)))
 }
 
 /// Delete the current user's draft for a task.
@@ -120,6 +151,7 @@ async fn get_draft(
 #[utoipa::path(
     delete,
     path = "/api/v1/tasks/{task_id}/drafts",
+    params(("step_id" = Option<String>, Query, description = "Workflow step")),
     responses(
         (status = 204, description = "Draft deleted"),
         (status = 404, description = "No draft found"),
@@ -128,14 +160,20 @@ async fn get_draft(
 )]
 async fn delete_draft(
     Path(task_id): Path<Uuid>,
-    Extension(_pool): Extension<PgPool>,
+    Query(query): Query<DraftQuery>,
+    current_user: CurrentUser,
+    Extension(pool): Extension<PgPool>,
 ) -> Result<StatusCode, ApiError> {
-    // TODO: Get current user from auth context
-    let _user_id = UserId::new(); // Placeholder
-    let _task_id = TaskId::from_uuid(task_id);
+    let task_id = TaskId::from_uuid(task_id);
+
+    let deleted = PgDraftRepository::new(pool)
+        .delete(&task_id, &current_user.user_id, query.step_id.as_deref())
+        .await
+        .map_err(|e| ApiError::Internal(e.into()))?;
+    if deleted == 0 {
+        return Err(ApiError::not_found("draft", task_id.to_string()));
+    }
 
-    // TODO: Implement actual database delete
-    // For now, return success
     Ok(StatusCode::NO_CONTENT)
 }
 
@@ -143,6 +181,14 @@ async fn delete_draft(
 // Router
 // =============================================================================
 
+/// OpenAPI paths and schemas served by this module
+#[derive(utoipa::OpenApi)]
+#[openapi(
+    paths(save_draft, get_draft, delete_draft),
+    components(schemas(SaveDraftRequest, DraftResponse))
+)]
+pub(super) struct ApiPaths;
+
 /// Draft routes nested under /tasks/{task_id}/drafts
 pub fn routes() -> Router {
     Router::new().route("/", post(save_draft).get(get_draft).delete(delete_draft))
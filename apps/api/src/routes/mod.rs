@@ -2,6 +2,7 @@
 
 mod annotations;
 pub mod auth;
+mod config;
 mod data_sources;
 mod drafts;
 mod health;
@@ -16,10 +17,14 @@ mod teams;
 mod users;
 mod workflows;
 
-use axum::Router;
+use std::sync::Arc;
+
+use axum::{routing::get, Router};
 
 pub use auth::AuthState;
 
+use crate::ws::QueueUpdateHub;
+
 /// Build the API router with all routes
 pub fn api_routes() -> Router {
     Router::new()
@@ -30,6 +35,7 @@ pub fn api_routes() -> Router {
 /// API v1 routes
 fn api_v1_routes() -> Router {
     Router::new()
+        .merge(config::routes())
         .nest("/users", users::routes())
         .nest("/users/{user_id}/skills", skills::user_skill_routes())
         .nest("/skills/types", skills::skill_type_routes())
@@ -38,6 +44,7 @@ fn api_v1_routes() -> Router {
         .nest("/tasks/{task_id}/drafts", drafts::routes())
         .nest("/tasks/{task_id}/skip", skip_reasons::task_skip_route())
         .nest("/tasks/{task_id}/reviews", reviews::routes())
+        .nest("/tasks/{task_id}/annotations", annotations::task_routes())
         .nest("/queue", queue::routes_without_ws())
         .nest("/annotations", annotations::routes())
         .nest("/projects", projects::routes())
@@ -54,19 +61,84 @@ fn api_v1_routes() -> Router {
         .nest("/workflows", workflows::routes())
 }
 
+/// Build the queue WebSocket route bound to a broadcast hub
+pub fn queue_ws_routes(hub: Arc<QueueUpdateHub>) -> Router {
+    Router::new()
+        .route("/api/v1/queue/ws", get(queue::queue_websocket))
+        .with_state(hub)
+}
+
 /// Build auth router with state
 pub fn auth_routes(state: AuthState) -> Router<()> {
     auth::routes().with_state(state)
 }
 
+/// OpenAPI paths and schemas from every route module
+pub fn openapi_docs() -> utoipa::openapi::OpenApi {
+    use utoipa::OpenApi;
+
+    let mut docs = users::ApiPaths::openapi();
+    for module in [
+        annotations::ApiPaths::openapi(),
+        config::ApiPaths::openapi(),
+        data_sources::ApiPaths::openapi(),
+        drafts::ApiPaths::openapi(),
+        project_types::ApiPaths::openapi(),
+        projects::ApiPaths::openapi(),
+        queue::ApiPaths::openapi(),
+        reviews::ApiPaths::openapi(),
+        skills::ApiPaths::openapi(),
+        skip_reasons::ApiPaths::openapi(),
+        tasks::ApiPaths::openapi(),
+        teams::ApiPaths::openapi(),
+    ] {
+        docs.merge(module);
+    }
+    docs
+}
+
 /// Get all route paths for OpenAPI documentation
 pub fn openapi_paths() -> utoipa::openapi::Paths {
-    use utoipa::OpenApi;
+    openapi_docs().paths
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_openapi_includes_every_module() {
+        let paths = openapi_paths();
+        for path in [
+            "/users",
+            "/api/v1/projects/{project_id}",
+            "/teams",
+            "/api/v1/queue",
+            "/api/v1/project-types",
+            "/api/v1/config",
+        ] {
+            assert!(paths.paths.contains_key(path), "missing {path}");
+        }
 
-    // Collect paths from all route modules
-    #[derive(OpenApi)]
-    #[openapi(paths(users::list_users, users::get_user, users::create_user,))]
-    struct UserPaths;
+        let project = &paths.paths["/api/v1/projects/{project_id}"];
+        assert!(project.get.is_some());
+        assert!(project.put.is_some());
+        assert!(project.delete.is_some());
+    }
 
-    UserPaths::openapi().paths
+    #[test]
+    fn test_openapi_registers_module_schemas() {
+        let docs = openapi_docs();
+        let schemas = &docs.components.expect("components").schemas;
+        for schema in [
+            "ClientConfigResponse",
+            "ProjectDetailResponse",
+            "QueueItem",
+            "TeamDetailResponse",
+            "DataSourceResponse",
+            "PageNav",
+        ] {
+            assert!(schemas.contains_key(schema), "missing {schema}");
+        }
+    }
 }
@@ -13,12 +13,13 @@ use utoipa::ToSchema;
 use glyph_db::{PgProjectTypeRepository, ProjectTypeRepository};
 use glyph_domain::{
     CreateProjectType, DifficultyLevel, ProficiencyLevel, ProjectType, ProjectTypeFilter,
-    ProjectTypeId, SkillRequirement, UpdateProjectType,
+    ProjectTypeId, SchemaAmbiguity, SkillRequirement, UpdateProjectType,
 };
 
 use crate::error::ApiError;
 use crate::extractors::CurrentUser;
-use crate::services::SchemaValidationService;
+use crate::pagination::{PageNav, PageResource, PaginationPolicy};
+use crate::services::{AnnotationBounds, SchemaValidationService};
 
 /// Project type list query parameters
 #[derive(Debug, Deserialize)]
@@ -36,6 +37,8 @@ pub struct ProjectTypeListResponse {
     pub total: i64,
     pub limit: i64,
     pub offset: i64,
+    #[serde(flatten)]
+    pub nav: PageNav,
 }
 
 /// Project type response
@@ -46,9 +49,17 @@ pub struct ProjectTypeResponse {
     pub description: Option<String>,
     pub input_schema: serde_json::Value,
     pub output_schema: serde_json::Value,
+    /// Incremented whenever either schema changes
+    pub schema_version: i32,
     pub estimated_duration_seconds: Option<i32>,
+    /// `estimated_duration_seconds`, or a default for the difficulty level
+    pub effective_duration_seconds: Option<i32>,
+    /// Whether `effective_duration_seconds` was inferred from difficulty
+    pub duration_inferred: bool,
     pub difficulty_level: Option<String>,
     pub skill_requirements: Vec<SkillRequirementResponse>,
+    /// Whether spans within one annotation field may overlap
+    pub allow_overlapping_spans: bool,
     pub is_system: bool,
     pub created_by: Option<String>,
     pub created_at: String,
@@ -58,19 +69,24 @@ pub struct ProjectTypeResponse {
 
 impl From<ProjectType> for ProjectTypeResponse {
     fn from(pt: ProjectType) -> Self {
+        let effective_duration = pt.effective_duration();
         Self {
             project_type_id: pt.project_type_id.to_string(),
             name: pt.name,
             description: pt.description,
             input_schema: pt.input_schema,
             output_schema: pt.output_schema,
+            schema_version: pt.schema_version,
             estimated_duration_seconds: pt.estimated_duration_seconds,
+            effective_duration_seconds: effective_duration.map(|d| d.seconds),
+            duration_inferred: effective_duration.is_some_and(|d| d.inferred),
             difficulty_level: pt.difficulty_level.map(format_difficulty),
             skill_requirements: pt
                 .skill_requirements
                 .into_iter()
                 .map(SkillRequirementResponse::from)
                 .collect(),
+            allow_overlapping_spans: pt.allow_overlapping_spans,
             is_system: pt.is_system,
             created_by: pt.created_by.map(|u| u.to_string()),
             created_at: pt.created_at.to_rfc3339(),
@@ -110,6 +126,15 @@ pub struct CreateProjectTypeRequest {
     pub estimated_duration_seconds: Option<i32>,
     pub difficulty_level: Option<String>,
     pub skill_requirements: Option<Vec<SkillRequirementRequest>>,
+    /// Whether spans within one annotation field may overlap (default true)
+    pub allow_overlapping_spans: Option<bool>,
+}
+
+/// Request to clone a project type
+#[derive(Debug, Default, Deserialize, ToSchema)]
+pub struct CloneProjectTypeRequest {
+    /// Name for the clone; defaults to "<source name> (copy)"
+    pub name: Option<String>,
 }
 
 /// Request to update a project type
@@ -121,6 +146,7 @@ pub struct UpdateProjectTypeRequest {
     pub output_schema: Option<serde_json::Value>,
     pub estimated_duration_seconds: Option<i32>,
     pub difficulty_level: Option<String>,
+    pub allow_overlapping_spans: Option<bool>,
 }
 
 /// Skill requirement in request
@@ -137,6 +163,14 @@ pub struct SkillRequirementRequest {
 pub struct ValidateSchemaRequest {
     pub schema: serde_json::Value,
     pub sample_data: serde_json::Value,
+    /// Image width in pixels, for checking bounding-box fields
+    pub image_width: Option<f64>,
+    /// Image height in pixels, for checking bounding-box fields
+    pub image_height: Option<f64>,
+    /// Document length in characters, for checking span fields
+    pub doc_length: Option<usize>,
+    /// Whether spans may overlap (default true)
+    pub allow_overlapping_spans: Option<bool>,
 }
 
 /// Request to infer schema from samples
@@ -174,6 +208,51 @@ pub struct SchemaAmbiguityResponse {
     pub suggested: String,
 }
 
+impl From<SchemaAmbiguity> for SchemaAmbiguityResponse {
+    fn from(a: SchemaAmbiguity) -> Self {
+        Self {
+            path: a.path,
+            description: a.description,
+            options: a.options,
+            suggested: a.suggested,
+        }
+    }
+}
+
+/// OpenAPI paths and schemas served by this module
+#[derive(utoipa::OpenApi)]
+#[openapi(
+    paths(
+        list_project_types,
+        get_project_type,
+        create_project_type,
+        clone_project_type,
+        update_project_type,
+        delete_project_type,
+        validate_schema,
+        infer_schema,
+        add_skill_requirement,
+        remove_skill_requirement
+    ),
+    components(schemas(
+        PageNav,
+        ProjectTypeListResponse,
+        ProjectTypeResponse,
+        SkillRequirementResponse,
+        CreateProjectTypeRequest,
+        CloneProjectTypeRequest,
+        UpdateProjectTypeRequest,
+        SkillRequirementRequest,
+        ValidateSchemaRequest,
+        InferSchemaRequest,
+        ValidationResponse,
+        ValidationErrorResponse,
+        InferSchemaResponse,
+        SchemaAmbiguityResponse
+    ))
+)]
+pub(super) struct ApiPaths;
+
 pub fn routes() -> Router {
     Router::new()
         .route("/", get(list_project_types).post(create_project_type))
@@ -183,6 +262,7 @@ pub fn routes() -> Router {
                 .put(update_project_type)
                 .delete(delete_project_type),
         )
+        .route("/{project_type_id}/clone", post(clone_project_type))
         .route("/{project_type_id}/validate-schema", post(validate_schema))
         .route("/infer-schema", post(infer_schema))
         .route("/{project_type_id}/skills", post(add_skill_requirement))
@@ -209,11 +289,11 @@ pub fn routes() -> Router {
 )]
 async fn list_project_types(
     Query(params): Query<ListProjectTypesQuery>,
-    _current_user: CurrentUser,
+    current_user: CurrentUser,
     Extension(pool): Extension<PgPool>,
 ) -> Result<Json<ProjectTypeListResponse>, ApiError> {
-    let limit = params.limit.unwrap_or(20);
-    let offset = params.offset.unwrap_or(0);
+    let limit = PaginationPolicy::for_resource(PageResource::ProjectTypes).limit(params.limit);
+    let offset = params.offset.unwrap_or(0).max(0);
 
     let filter = ProjectTypeFilter {
         is_system: params.is_system,
@@ -223,7 +303,7 @@ async fn list_project_types(
         offset: Some(offset),
     };
 
-    let repo = PgProjectTypeRepository::new(pool);
+    let repo = PgProjectTypeRepository::new(pool).in_org(current_user.org_id);
     let items = repo.list(&filter).await.map_err(|e| {
         tracing::error!("Failed to list project types: {:?}", e);
         ApiError::Internal(anyhow::anyhow!("{}", e))
@@ -236,6 +316,7 @@ async fn list_project_types(
         total,
         limit,
         offset,
+        nav: PageNav::new(total, limit, offset),
     }))
 }
 
@@ -254,14 +335,14 @@ async fn list_project_types(
 )]
 async fn get_project_type(
     Path(project_type_id): Path<String>,
-    _current_user: CurrentUser,
+    current_user: CurrentUser,
     Extension(pool): Extension<PgPool>,
 ) -> Result<Json<ProjectTypeResponse>, ApiError> {
     let id: ProjectTypeId = project_type_id
         .parse()
         .map_err(|_| ApiError::not_found("project_type", &project_type_id))?;
 
-    let repo = PgProjectTypeRepository::new(pool);
+    let repo = PgProjectTypeRepository::new(pool).in_org(current_user.org_id);
     let project_type = repo
         .find_by_id(&id)
         .await
@@ -324,10 +405,11 @@ async fn create_project_type(
                 })
                 .collect()
         }),
+        allow_overlapping_spans: req.allow_overlapping_spans,
         is_system: Some(false),
     };
 
-    let repo = PgProjectTypeRepository::new(pool);
+    let repo = PgProjectTypeRepository::new(pool).in_org(current_user.org_id);
     let project_type = repo
         .create(&create, Some(&current_user.user_id))
         .await
@@ -348,6 +430,71 @@ async fn create_project_type(
     ))
 }
 
+/// Clone a project type
+///
+/// Copies the schemas, skill requirements, and other settings into a new
+/// non-system type owned by the caller. System types can be cloned.
+#[utoipa::path(
+    post,
+    path = "/api/v1/project-types/{project_type_id}/clone",
+    params(
+        ("project_type_id" = String, Path, description = "Project Type ID to clone"),
+    ),
+    request_body = CloneProjectTypeRequest,
+    responses(
+        (status = 201, description = "Project type cloned", body = ProjectTypeResponse),
+        (status = 400, description = "Name is empty or already taken"),
+        (status = 404, description = "Project type not found"),
+    ),
+    tag = "project-types"
+)]
+async fn clone_project_type(
+    Path(project_type_id): Path<String>,
+    current_user: CurrentUser,
+    Extension(pool): Extension<PgPool>,
+    Json(req): Json<CloneProjectTypeRequest>,
+) -> Result<(StatusCode, Json<ProjectTypeResponse>), ApiError> {
+    let id: ProjectTypeId = project_type_id
+        .parse()
+        .map_err(|_| ApiError::not_found("project_type", &project_type_id))?;
+
+    if req.name.as_ref().is_some_and(|name| name.trim().is_empty()) {
+        return Err(ApiError::bad_request(
+            "validation.name_required",
+            "Project type name is required",
+        ));
+    }
+
+    let repo = PgProjectTypeRepository::new(pool).in_org(current_user.org_id);
+    let source = repo
+        .find_by_id(&id)
+        .await
+        .map_err(|e| {
+            tracing::error!("Failed to find project type {}: {:?}", project_type_id, e);
+            ApiError::Internal(anyhow::anyhow!("{}", e))
+        })?
+        .ok_or_else(|| ApiError::not_found("project_type", &project_type_id))?;
+
+    let project_type = repo
+        .create(&clone_of(&source, req.name), Some(&current_user.user_id))
+        .await
+        .map_err(|e| match e {
+            glyph_db::CreateProjectTypeError::NameExists(name) => ApiError::bad_request(
+                "validation.name_exists",
+                format!("Project type name already exists: {}", name),
+            ),
+            glyph_db::CreateProjectTypeError::Database(e) => {
+                tracing::error!("Failed to clone project type: {:?}", e);
+                ApiError::Internal(anyhow::anyhow!("{}", e))
+            }
+        })?;
+
+    Ok((
+        StatusCode::CREATED,
+        Json(ProjectTypeResponse::from(project_type)),
+    ))
+}
+
 /// Update a project type
 #[utoipa::path(
     put,
@@ -364,7 +511,7 @@ async fn create_project_type(
 )]
 async fn update_project_type(
     Path(project_type_id): Path<String>,
-    _current_user: CurrentUser,
+    current_user: CurrentUser,
     Extension(pool): Extension<PgPool>,
     Json(req): Json<UpdateProjectTypeRequest>,
 ) -> Result<Json<ProjectTypeResponse>, ApiError> {
@@ -387,9 +534,10 @@ async fn update_project_type(
         output_schema: req.output_schema,
         estimated_duration_seconds: req.estimated_duration_seconds,
         difficulty_level: req.difficulty_level.and_then(|s| parse_difficulty(&s)),
+        allow_overlapping_spans: req.allow_overlapping_spans,
     };
 
-    let repo = PgProjectTypeRepository::new(pool);
+    let repo = PgProjectTypeRepository::new(pool).in_org(current_user.org_id);
     let project_type = repo.update(&id, &update).await.map_err(|e| match e {
         glyph_db::UpdateProjectTypeError::NotFound(_) => {
             ApiError::not_found("project_type", &project_type_id)
@@ -419,14 +567,14 @@ async fn update_project_type(
 )]
 async fn delete_project_type(
     Path(project_type_id): Path<String>,
-    _current_user: CurrentUser,
+    current_user: CurrentUser,
     Extension(pool): Extension<PgPool>,
 ) -> Result<StatusCode, ApiError> {
     let id: ProjectTypeId = project_type_id
         .parse()
         .map_err(|_| ApiError::not_found("project_type", &project_type_id))?;
 
-    let repo = PgProjectTypeRepository::new(pool);
+    let repo = PgProjectTypeRepository::new(pool).in_org(current_user.org_id);
     repo.delete(&id).await.map_err(|e| match e {
         glyph_db::DeleteProjectTypeError::NotFound(_) => {
             ApiError::not_found("project_type", &project_type_id)
@@ -463,8 +611,14 @@ async fn validate_schema(
 ) -> Result<Json<ValidationResponse>, ApiError> {
     let service = SchemaValidationService::new();
 
+    let bounds = AnnotationBounds {
+        image_size: req.image_width.zip(req.image_height),
+        doc_len: req.doc_length,
+        ..AnnotationBounds::default()
+    }
+    .with_overlapping_spans(req.allow_overlapping_spans.unwrap_or(true));
     let result = service
-        .validate(&req.schema, &req.sample_data)
+        .validate_output(&req.schema, &req.sample_data, bounds)
         .await
         .map_err(|e| ApiError::bad_request("schema.invalid", e.to_string()))?;
 
@@ -511,12 +665,7 @@ async fn infer_schema(
         ambiguities: result
             .ambiguities
             .into_iter()
-            .map(|a| SchemaAmbiguityResponse {
-                path: a.path,
-                description: a.description,
-                options: a.options,
-                suggested: a.suggested,
-            })
+            .map(SchemaAmbiguityResponse::from)
             .collect(),
     }))
 }
@@ -538,7 +687,7 @@ async fn infer_schema(
 async fn add_skill_requirement(
     Path(project_type_id): Path<String>,
     Extension(pool): Extension<PgPool>,
-    _current_user: CurrentUser,
+    current_user: CurrentUser,
     Json(req): Json<SkillRequirementRequest>,
 ) -> Result<StatusCode, ApiError> {
     let id: ProjectTypeId = project_type_id
@@ -552,7 +701,7 @@ async fn add_skill_requirement(
         weight: req.weight.unwrap_or(1.0),
     };
 
-    let repo = PgProjectTypeRepository::new(pool);
+    let repo = PgProjectTypeRepository::new(pool).in_org(current_user.org_id);
     repo.add_skill_requirement(&id, &requirement)
         .await
         .map_err(|e| match e {
@@ -585,13 +734,13 @@ async fn add_skill_requirement(
 async fn remove_skill_requirement(
     Path((project_type_id, skill_id)): Path<(String, String)>,
     Extension(pool): Extension<PgPool>,
-    _current_user: CurrentUser,
+    current_user: CurrentUser,
 ) -> Result<StatusCode, ApiError> {
     let id: ProjectTypeId = project_type_id
         .parse()
         .map_err(|_| ApiError::not_found("project_type", &project_type_id))?;
 
-    let repo = PgProjectTypeRepository::new(pool);
+    let repo = PgProjectTypeRepository::new(pool).in_org(current_user.org_id);
     repo.remove_skill_requirement(&id, &skill_id)
         .await
         .map_err(|e| match e {
@@ -637,7 +786,7 @@ fn format_difficulty(level: DifficultyLevel) -> String {
     }
 }
 
-fn parse_difficulty(s: &str) -> Option<DifficultyLevel> {
+pub(crate) fn parse_difficulty(s: &str) -> Option<DifficultyLevel> {
     match s.to_lowercase().as_str() {
         "easy" => Some(DifficultyLevel::Easy),
         "medium" => Some(DifficultyLevel::Medium),
@@ -656,6 +805,21 @@ fn format_proficiency(level: ProficiencyLevel) -> String {
     }
 }
 
+/// A new, non-system project type copying everything from `source`
+fn clone_of(source: &ProjectType, name: Option<String>) -> CreateProjectType {
+    CreateProjectType {
+        name: name.unwrap_or_else(|| format!("{} (copy)", source.name)),
+        description: source.description.clone(),
+        input_schema: Some(source.input_schema.clone()),
+        output_schema: Some(source.output_schema.clone()),
+        estimated_duration_seconds: source.estimated_duration_seconds,
+        difficulty_level: source.difficulty_level,
+        skill_requirements: Some(source.skill_requirements.clone()),
+        allow_overlapping_spans: Some(source.allow_overlapping_spans),
+        is_system: Some(false),
+    }
+}
+
 fn parse_proficiency(s: &str) -> ProficiencyLevel {
     match s.to_lowercase().as_str() {
         "novice" => ProficiencyLevel::Novice,
@@ -665,3 +829,106 @@ fn parse_proficiency(s: &str) -> ProficiencyLevel {
         _ => ProficiencyLevel::Intermediate,
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+    use glyph_domain::OrgId;
+
+    fn system_type() -> ProjectType {
+        ProjectType {
+            project_type_id: ProjectTypeId::new(),
+            name: "NER".to_string(),
+            description: Some("Named entities".to_string()),
+            input_schema: serde_json::json!({"type": "object", "required": ["text"]}),
+            output_schema: serde_json::json!({"type": "object", "required": ["entities"]}),
+            schema_version: 1,
+            estimated_duration_seconds: Some(120),
+            difficulty_level: Some(DifficultyLevel::Medium),
+            skill_requirements: vec![SkillRequirement {
+                skill_id: "ner".to_string(),
+                min_proficiency: ProficiencyLevel::Advanced,
+                is_required: true,
+                weight: 2.0,
+            }],
+            allow_overlapping_spans: false,
+            is_system: true,
+            created_by: None,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+            org_id: OrgId::DEFAULT,
+        }
+    }
+
+    #[test]
+    fn test_clone_copies_settings_into_user_type() {
+        let source = system_type();
+        let clone = clone_of(&source, None);
+
+        assert_eq!(clone.name, "NER (copy)");
+        assert_eq!(clone.is_system, Some(false));
+        assert_eq!(clone.allow_overlapping_spans, Some(false));
+        assert_eq!(clone.input_schema.as_ref(), Some(&source.input_schema));
+        assert_eq!(clone.output_schema.as_ref(), Some(&source.output_schema));
+        let requirements = clone.skill_requirements.unwrap();
+        assert_eq!(requirements.len(), 1);
+        assert_eq!(requirements[0].skill_id, "ner");
+        assert_eq!(requirements[0].min_proficiency, ProficiencyLevel::Advanced);
+
+        assert_eq!(clone_of(&source, Some("NER v2".to_string())).name, "NER v2");
+    }
+
+    #[tokio::test]
+    #[ignore = "requires DATABASE_URL pointing at a migrated database"]
+    async fn test_clone_has_independent_schemas() {
+        let url = std::env::var("DATABASE_URL").expect("DATABASE_URL must be set");
+        let repo = PgProjectTypeRepository::new(PgPool::connect(&url).await.unwrap());
+        let mut source = system_type();
+        source.name = format!("Clone Source {}", uuid::Uuid::new_v4());
+        let source = repo
+            .create(&clone_of(&source, Some(source.name.clone())), None)
+            .await
+            .unwrap();
+
+        let clone = repo
+            .create(
+                &clone_of(&source, Some(format!("{} v2", source.name))),
+                None,
+            )
+            .await
+            .unwrap();
+        assert_ne!(clone.project_type_id, source.project_type_id);
+
+        let edited = serde_json::json!({"type": "object", "required": ["spans"]});
+        repo.update(
+            &clone.project_type_id,
+            &UpdateProjectType {
+                name: None,
+                description: None,
+                input_schema: None,
+                output_schema: Some(edited.clone()),
+                estimated_duration_seconds: None,
+                difficulty_level: None,
+                allow_overlapping_spans: None,
+            },
+        )
+        .await
+        .unwrap();
+
+        let source_after = repo
+            .find_by_id(&source.project_type_id)
+            .await
+            .unwrap()
+            .unwrap();
+        let clone_after = repo
+            .find_by_id(&clone.project_type_id)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(source_after.output_schema, source.output_schema);
+        assert_eq!(clone_after.output_schema, edited);
+        assert_eq!(clone_after.skill_requirements.len(), 1);
+        assert!(!clone_after.is_system);
+    }
+}
@@ -1,20 +1,43 @@
 //! Project CRUD endpoints
 
+use std::collections::HashMap;
+use std::sync::Arc;
+
 use axum::{
     extract::{Path, Query},
-    http::StatusCode,
-    routing::{get, post},
+    http::{header, StatusCode},
+    response::{IntoResponse, Response},
+    routing::{get, post, put},
     Extension, Json, Router,
 };
 use serde::{Deserialize, Serialize};
 use sqlx::PgPool;
 use utoipa::ToSchema;
 
-use glyph_db::{ExtendedProjectUpdate, Pagination, PgProjectRepository, ProjectRepository};
-use glyph_domain::{Project, ProjectId, ProjectStatus, ProjectTypeId, TeamId};
+use glyph_common::resolve_public_url;
+use glyph_db::{
+    live_task_total, AuditAction, AuditActorType, AuditEvent, AuditReader, AuditRecord,
+    AuditWriter, DataSourceReadiness, DataSourceRepository, ExtendedProjectUpdate, GoalSnapshot,
+    PgAnnotationRepository, PgAssignmentRepository, PgDataSourceRepository, PgGoalRepository,
+    PgProjectRepository, PgProjectTypeRepository, PgTaskRepository, PgTeamRepository,
+    PgWebhookRepository, PgWorkflowRepository, ProjectRepository, ProjectTypeRepository,
+    RejectionStats, TeamRepository, UserDailyThroughput, WebhookConfig,
+};
+use glyph_domain::{
+    GoalType, Project, ProjectId, ProjectSettings, ProjectStatus, ProjectTypeId, TaskStatus,
+    TeamId, UserId, WorkflowId,
+};
+use glyph_quality::export::{AnnotationExportService, ExportError};
 
 use crate::error::ApiError;
 use crate::extractors::CurrentUser;
+use crate::pagination::{PageNav, PageResource, PaginationPolicy};
+use crate::services::project_config_service::{
+    export_project_config, import_project_config, BundledProject, BundledProjectType,
+    BundledWorkflow, ImportedConfig, ProjectConfigBundle,
+};
+use crate::services::storage_service::project_storage;
+use crate::services::PermissionService;
 
 /// Project-level settings (API response type)
 #[derive(Debug, Clone, Default, Serialize, Deserialize, ToSchema)]
@@ -27,6 +50,80 @@ pub struct ProjectSettingsResponse {
     pub auto_complete_enabled: bool,
 }
 
+/// Upper bound on `max_assignments_per_user`
+const MAX_ASSIGNMENTS_PER_USER: i32 = 10_000;
+
+/// Upper bound on `assignment_timeout_hours` (90 days)
+const MAX_ASSIGNMENT_TIMEOUT_HOURS: i32 = 24 * 90;
+
+impl ProjectSettingsResponse {
+    /// Check that numeric settings are within range.
+    ///
+    /// Returns a 400 naming the first offending field.
+    pub fn validate(&self) -> Result<(), ApiError> {
+        if let Some(threshold) = self.quality_threshold {
+            if !(0.0..=1.0).contains(&threshold) {
+                return Err(ApiError::bad_request(
+                    "validation.settings.quality_threshold",
+                    format!(
+                        "settings.quality_threshold must be between 0.0 and 1.0, got {threshold}"
+                    ),
+                ));
+            }
+        }
+
+        if let Some(max) = self.max_assignments_per_user {
+            if !(0..=MAX_ASSIGNMENTS_PER_USER).contains(&max) {
+                return Err(ApiError::bad_request(
+                    "validation.settings.max_assignments_per_user",
+                    format!(
+                        "settings.max_assignments_per_user must be between 0 and {MAX_ASSIGNMENTS_PER_USER}, got {max}"
+                    ),
+                ));
+            }
+        }
+
+        if let Some(hours) = self.assignment_timeout_hours {
+            if !(0..=MAX_ASSIGNMENT_TIMEOUT_HOURS).contains(&hours) {
+                return Err(ApiError::bad_request(
+                    "validation.settings.assignment_timeout_hours",
+                    format!(
+                        "settings.assignment_timeout_hours must be between 0 and {MAX_ASSIGNMENT_TIMEOUT_HOURS}, got {hours}"
+                    ),
+                ));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl From<ProjectSettings> for ProjectSettingsResponse {
+    fn from(s: ProjectSettings) -> Self {
+        Self {
+            allow_self_review: s.allow_self_review,
+            require_all_fields: s.require_all_fields,
+            max_assignments_per_user: s.max_assignments_per_user,
+            assignment_timeout_hours: s.assignment_timeout_hours,
+            quality_threshold: s.quality_threshold,
+            auto_complete_enabled: s.auto_complete_enabled,
+        }
+    }
+}
+
+impl From<ProjectSettingsResponse> for ProjectSettings {
+    fn from(s: ProjectSettingsResponse) -> Self {
+        Self {
+            allow_self_review: s.allow_self_review,
+            require_all_fields: s.require_all_fields,
+            max_assignments_per_user: s.max_assignments_per_user,
+            assignment_timeout_hours: s.assignment_timeout_hours,
+            quality_threshold: s.quality_threshold,
+            auto_complete_enabled: s.auto_complete_enabled,
+        }
+    }
+}
+
 /// Project list query parameters
 #[derive(Debug, Deserialize)]
 pub struct ListProjectsQuery {
@@ -47,6 +144,8 @@ pub struct ProjectListResponse {
     pub total: i64,
     pub limit: i64,
     pub offset: i64,
+    #[serde(flatten)]
+    pub nav: PageNav,
 }
 
 /// Project summary for list responses
@@ -67,8 +166,20 @@ pub struct ProjectSummaryResponse {
     pub created_by: String,
 }
 
-impl From<Project> for ProjectSummaryResponse {
-    fn from(p: Project) -> Self {
+impl ProjectSummaryResponse {
+    /// Build a summary from a project and its per-status task counts.
+    ///
+    /// Deleted and cancelled tasks don't count toward the total.
+    fn new(p: Project, counts: &HashMap<TaskStatus, i64>) -> Self {
+        let task_count = live_task_total(counts);
+        let completed_task_count = counts.get(&TaskStatus::Completed).copied().unwrap_or(0);
+        #[allow(clippy::cast_precision_loss)]
+        let completion_percentage = if task_count > 0 {
+            completed_task_count as f64 / task_count as f64 * 100.0
+        } else {
+            0.0
+        };
+
         Self {
             project_id: p.project_id.to_string(),
             name: p.name,
@@ -76,9 +187,9 @@ impl From<Project> for ProjectSummaryResponse {
             status: format!("{:?}", p.status).to_lowercase(),
             project_type_name: None, // Would need join to get this
             team_name: None,         // Would need join to get this
-            task_count: 0,           // Would need aggregation
-            completed_task_count: 0, // Would need aggregation
-            completion_percentage: 0.0,
+            task_count,
+            completed_task_count,
+            completion_percentage,
             tags: p.tags,
             deadline: p.deadline.map(|d| d.to_rfc3339()),
             created_at: p.created_at.to_rfc3339(),
@@ -127,14 +238,7 @@ impl From<Project> for ProjectDetailResponse {
             workflow_id: p.workflow_id.map(|id| id.to_string()),
             layout_id: p.layout_id,
             team_id: p.team_id.map(|id| id.to_string()),
-            settings: ProjectSettingsResponse {
-                allow_self_review: p.settings.allow_self_review,
-                require_all_fields: p.settings.require_all_fields,
-                max_assignments_per_user: p.settings.max_assignments_per_user,
-                assignment_timeout_hours: p.settings.assignment_timeout_hours,
-                quality_threshold: p.settings.quality_threshold,
-                auto_complete_enabled: p.settings.auto_complete_enabled,
-            },
+            settings: p.settings.into(),
             tags: p.tags,
             documentation: p.documentation,
             deadline: p.deadline.map(|d| d.to_rfc3339()),
@@ -257,6 +361,360 @@ pub struct ActivationValidationResponse {
     pub checks: Vec<ActivationCheck>,
 }
 
+// =============================================================================
+// Webhook Types
+// =============================================================================
+
+/// Request to configure a project's completion webhook
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct WebhookConfigRequest {
+    /// Public https endpoint that receives deliveries
+    pub url: String,
+    /// Shared secret used to sign deliveries (`X-Glyph-Signature`)
+    pub secret: String,
+    pub is_active: Option<bool>,
+}
+
+/// A project's webhook configuration. The secret is never returned.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct WebhookConfigResponse {
+    pub project_id: String,
+    pub url: String,
+    pub is_active: bool,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+impl From<WebhookConfig> for WebhookConfigResponse {
+    fn from(config: WebhookConfig) -> Self {
+        Self {
+            project_id: config.project_id.to_string(),
+            url: config.url,
+            is_active: config.is_active,
+            created_at: config.created_at.to_rfc3339(),
+            updated_at: config.updated_at.to_rfc3339(),
+        }
+    }
+}
+
+/// Audit history query parameters
+#[derive(Debug, Deserialize)]
+pub struct AuditQuery {
+    pub limit: Option<i64>,
+    pub offset: Option<i64>,
+}
+
+/// A single audit log entry
+#[derive(Debug, Serialize, ToSchema)]
+pub struct AuditEntryResponse {
+    pub audit_id: String,
+    /// create, update, or delete
+    pub action: String,
+    pub actor_id: String,
+    pub actor_type: String,
+    /// Field-level `{ "field": { "old": .., "new": .. } }` diff, for updates
+    pub changes: Option<serde_json::Value>,
+    pub occurred_at: String,
+}
+
+impl From<AuditRecord> for AuditEntryResponse {
+    fn from(record: AuditRecord) -> Self {
+        let label = |value: serde_json::Value| value.as_str().unwrap_or_default().to_string();
+        Self {
+            audit_id: record.audit_id.to_string(),
+            action: label(serde_json::to_value(record.action).unwrap_or_default()),
+            actor_id: record.actor_id,
+            actor_type: label(serde_json::to_value(record.actor_type).unwrap_or_default()),
+            changes: record.changes,
+            occurred_at: record.occurred_at.to_rfc3339(),
+        }
+    }
+}
+
+/// A project's audit history, newest first
+#[derive(Debug, Serialize, ToSchema)]
+pub struct AuditListResponse {
+    pub items: Vec<AuditEntryResponse>,
+    pub total: i64,
+    pub limit: i64,
+    pub offset: i64,
+    #[serde(flatten)]
+    pub nav: PageNav,
+}
+
+/// Rejection stats query parameters
+#[derive(Debug, Deserialize)]
+pub struct RejectionStatsQuery {
+    /// Only count rejections at or after this time
+    pub from: Option<chrono::DateTime<chrono::Utc>>,
+    /// Only count rejections before this time
+    pub to: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+/// Number of rejections given for one reason
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ReasonCount {
+    /// Reason type, e.g. "unclear_instructions"
+    pub reason: String,
+    pub count: i64,
+}
+
+/// Number of rejections made by one user
+#[derive(Debug, Serialize, ToSchema)]
+pub struct UserRejectionCount {
+    pub user_id: String,
+    pub count: i64,
+}
+
+/// A project's rejections grouped by reason and by rejecting user
+#[derive(Debug, Serialize, ToSchema)]
+pub struct RejectionStatsResponse {
+    pub project_id: String,
+    pub total: i64,
+    /// Most common reasons first
+    pub by_reason: Vec<ReasonCount>,
+    /// Users with the most rejections first
+    pub by_user: Vec<UserRejectionCount>,
+}
+
+/// Annotation export query parameters
+#[derive(Debug, Deserialize)]
+pub struct AnnotationExportQuery {
+    /// Only export annotations completed or changed after this time; pass
+    /// the previous export's `checkpoint` for an incremental export
+    pub since: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+/// A Parquet export written to the project's storage
+#[derive(Debug, Serialize, ToSchema)]
+pub struct AnnotationExportResponse {
+    /// Storage key of the Parquet file
+    pub key: String,
+    /// Where to download the file from
+    pub download_url: String,
+    pub rows: usize,
+    /// Pass as `since` to export only what changed after this export
+    pub checkpoint: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+/// Throughput query parameters
+#[derive(Debug, Deserialize)]
+pub struct ThroughputQuery {
+    /// Grouping key; defaults to `user`, the only one supported
+    pub group_by: Option<String>,
+    pub from: chrono::NaiveDate,
+    pub to: chrono::NaiveDate,
+}
+
+/// Submissions on one day
+#[derive(Debug, Serialize, ToSchema)]
+pub struct DailyCount {
+    pub date: chrono::NaiveDate,
+    pub count: i64,
+}
+
+/// One user's submissions per day
+#[derive(Debug, Serialize, ToSchema)]
+pub struct UserThroughput {
+    pub user_id: String,
+    pub total: i64,
+    /// Every day in the range, oldest first; idle days count zero
+    pub days: Vec<DailyCount>,
+}
+
+impl From<UserDailyThroughput> for UserThroughput {
+    fn from(throughput: UserDailyThroughput) -> Self {
+        Self {
+            user_id: throughput.user_id.to_string(),
+            total: throughput.total,
+            days: throughput
+                .days
+                .into_iter()
+                .map(|(date, count)| DailyCount { date, count })
+                .collect(),
+        }
+    }
+}
+
+/// A project's daily submission counts per user
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ThroughputResponse {
+    pub project_id: String,
+    pub from: chrono::NaiveDate,
+    pub to: chrono::NaiveDate,
+    /// Users with the most submissions first
+    pub users: Vec<UserThroughput>,
+}
+
+impl RejectionStatsResponse {
+    fn new(project_id: &ProjectId, stats: RejectionStats) -> Self {
+        Self {
+            project_id: project_id.to_string(),
+            total: stats.total,
+            by_reason: stats
+                .by_reason
+                .into_iter()
+                .map(|(reason, count)| ReasonCount { reason, count })
+                .collect(),
+            by_user: stats
+                .by_user
+                .into_iter()
+                .map(|(user_id, count)| UserRejectionCount {
+                    user_id: user_id.to_string(),
+                    count,
+                })
+                .collect(),
+        }
+    }
+}
+
+/// Progress of one project goal
+#[derive(Debug, Serialize, ToSchema)]
+pub struct GoalProgressResponse {
+    pub goal_id: String,
+    pub name: String,
+    /// Goal type, e.g. "volume" or "manual"
+    pub goal_type: String,
+    pub target_value: f64,
+    pub current_value: f64,
+    /// Fraction complete, 0.0 to 1.0 (clamped)
+    pub percent_complete: f64,
+    pub is_complete: bool,
+    pub deadline: Option<chrono::DateTime<chrono::Utc>>,
+    /// Completion state set by hand; only present for manual goals
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub manually_completed: Option<bool>,
+    /// Whether the goal's completion action has run
+    pub completion_action_fired: bool,
+    pub completion_action_fired_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+impl From<GoalSnapshot> for GoalProgressResponse {
+    fn from(goal: GoalSnapshot) -> Self {
+        let is_manual = goal.goal_type == GoalType::Manual;
+        let ratio = if goal.target_value > 0.0 {
+            goal.current_value / goal.target_value
+        } else {
+            1.0
+        };
+        // Manual goals are complete when someone says so, whatever the value
+        let is_complete = if is_manual {
+            goal.manually_completed
+        } else {
+            ratio >= 1.0
+        };
+        let percent_complete = if is_manual && goal.manually_completed {
+            1.0
+        } else {
+            ratio.clamp(0.0, 1.0)
+        };
+
+        Self {
+            goal_id: goal.goal_id.to_string(),
+            name: goal.name,
+            goal_type: format!("{:?}", goal.goal_type).to_lowercase(),
+            target_value: goal.target_value,
+            current_value: goal.current_value,
+            percent_complete,
+            is_complete,
+            deadline: goal.deadline,
+            manually_completed: is_manual.then_some(goal.manually_completed),
+            completion_action_fired: goal.completion_action_fired_at.is_some(),
+            completion_action_fired_at: goal.completion_action_fired_at,
+        }
+    }
+}
+
+/// Progress of every goal in a project
+#[derive(Debug, Serialize, ToSchema)]
+pub struct GoalProgressListResponse {
+    pub project_id: String,
+    pub goals: Vec<GoalProgressResponse>,
+}
+
+impl WebhookConfigRequest {
+    /// Check the URL is https and resolves only to public addresses, and the
+    /// secret is non-empty
+    async fn validate(&self) -> Result<(), ApiError> {
+        if let Err(e) = resolve_public_url(&self.url).await {
+            return Err(ApiError::bad_request(
+                "validation.invalid_webhook_url",
+                format!("Webhook URL is not allowed: {e}"),
+            ));
+        }
+        if self.secret.trim().is_empty() {
+            return Err(ApiError::bad_request(
+                "validation.webhook_secret_required",
+                "Webhook secret must not be empty",
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// OpenAPI paths and schemas served by this module
+#[derive(utoipa::OpenApi)]
+#[openapi(
+    paths(
+        list_projects,
+        get_project,
+        create_project,
+        update_project,
+        delete_project,
+        update_status,
+        activate_project,
+        validate_project_activation,
+        clone_project,
+        export_project_config_handler,
+        import_project_config_handler,
+        export_annotations,
+        download_annotation_export,
+        set_webhook,
+        get_webhook,
+        delete_webhook,
+        get_project_audit,
+        get_rejection_stats,
+        get_throughput,
+        list_goal_progress,
+        complete_goal,
+        uncomplete_goal
+    ),
+    components(schemas(
+        PageNav,
+        ProjectSettingsResponse,
+        ProjectListResponse,
+        ProjectSummaryResponse,
+        ProjectDetailResponse,
+        CreateProjectRequest,
+        UpdateProjectRequest,
+        UpdateStatusRequest,
+        StatusUpdateResponse,
+        TransitionInfo,
+        CloneProjectRequest,
+        ProjectConfigBundle,
+        BundledProject,
+        BundledProjectType,
+        BundledWorkflow,
+        ImportedConfig,
+        AnnotationExportResponse,
+        ActivationCheck,
+        ActivationValidationResponse,
+        WebhookConfigRequest,
+        WebhookConfigResponse,
+        AuditEntryResponse,
+        AuditListResponse,
+        ReasonCount,
+        UserRejectionCount,
+        RejectionStatsResponse,
+        DailyCount,
+        UserThroughput,
+        ThroughputResponse,
+        GoalProgressResponse,
+        GoalProgressListResponse
+    ))
+)]
+pub(super) struct ApiPaths;
+
 pub fn routes() -> Router {
     Router::new()
         .route("/", get(list_projects).post(create_project))
@@ -271,6 +729,32 @@ pub fn routes() -> Router {
             get(validate_project_activation),
         )
         .route("/{project_id}/clone", post(clone_project))
+        .route(
+            "/{project_id}/export-config",
+            get(export_project_config_handler),
+        )
+        .route("/import-config", post(import_project_config_handler))
+        .route("/{project_id}/exports", post(export_annotations))
+        .route(
+            "/{project_id}/exports/{file_name}",
+            get(download_annotation_export),
+        )
+        .route(
+            "/{project_id}/webhook",
+            put(set_webhook).get(get_webhook).delete(delete_webhook),
+        )
+        .route("/{project_id}/audit", get(get_project_audit))
+        .route("/{project_id}/rejections/stats", get(get_rejection_stats))
+        .route("/{project_id}/throughput", get(get_throughput))
+        .route("/{project_id}/goals", get(list_goal_progress))
+        .route(
+            "/{project_id}/goals/{goal_id}/complete",
+            post(complete_goal),
+        )
+        .route(
+            "/{project_id}/goals/{goal_id}/uncomplete",
+            post(uncomplete_goal),
+        )
 }
 
 /// List projects with filtering
@@ -291,31 +775,43 @@ pub fn routes() -> Router {
 )]
 async fn list_projects(
     Query(params): Query<ListProjectsQuery>,
-    _current_user: CurrentUser,
+    current_user: CurrentUser,
     Extension(pool): Extension<PgPool>,
 ) -> Result<Json<ProjectListResponse>, ApiError> {
-    let pagination = Pagination {
-        limit: params.limit.unwrap_or(20),
-        offset: params.offset.unwrap_or(0),
-        sort_by: None,
-        sort_order: Default::default(),
-    };
+    let pagination = PaginationPolicy::for_resource(PageResource::Projects)
+        .pagination(params.limit, params.offset);
 
-    let repo = PgProjectRepository::new(pool);
+    let task_repo = PgTaskRepository::new(pool.clone());
+    let repo = PgProjectRepository::new(pool).in_org(current_user.org_id);
     let page = repo.list(pagination).await.map_err(|e| {
         tracing::error!("Failed to list projects: {:?}", e);
         ApiError::Internal(anyhow::anyhow!("{}", e))
     })?;
 
+    let project_ids: Vec<ProjectId> = page
+        .items
+        .iter()
+        .map(|project| project.project_id)
+        .collect();
+    let counts = task_repo
+        .count_by_status_for_projects(&project_ids)
+        .await
+        .map_err(|e| ApiError::Internal(e.into()))?;
+    let items = page
+        .items
+        .into_iter()
+        .map(|project| {
+            let project_counts = &counts[&project.project_id];
+            ProjectSummaryResponse::new(project, project_counts)
+        })
+        .collect();
+
     Ok(Json(ProjectListResponse {
-        items: page
-            .items
-            .into_iter()
-            .map(ProjectSummaryResponse::from)
-            .collect(),
+        items,
         total: page.total,
         limit: page.limit,
         offset: page.offset,
+        nav: PageNav::new(page.total, page.limit, page.offset),
     }))
 }
 
@@ -334,14 +830,14 @@ async fn list_projects(
 )]
 async fn get_project(
     Path(project_id): Path<String>,
-    _current_user: CurrentUser,
+    current_user: CurrentUser,
     Extension(pool): Extension<PgPool>,
 ) -> Result<Json<ProjectDetailResponse>, ApiError> {
     let id: ProjectId = project_id
         .parse()
         .map_err(|_| ApiError::not_found("project", &project_id))?;
 
-    let repo = PgProjectRepository::new(pool);
+    let repo = PgProjectRepository::new(pool).in_org(current_user.org_id);
     let project = repo
         .find_by_id(&id)
         .await
@@ -377,8 +873,22 @@ async fn create_project(
             "Project name is required",
         ));
     }
-
-    let repo = PgProjectRepository::new(pool.clone());
+    if let Some(settings) = &req.settings {
+        settings.validate()?;
+    }
+    let project_type_id = req
+        .project_type_id
+        .and_then(|s| s.parse::<ProjectTypeId>().ok());
+    let team_id = req.team_id.and_then(|s| s.parse::<TeamId>().ok());
+    ensure_references_in_org(
+        &pool,
+        &current_user,
+        project_type_id.as_ref(),
+        team_id.as_ref(),
+    )
+    .await?;
+
+    let repo = PgProjectRepository::new(pool.clone()).in_org(current_user.org_id);
     let project = repo
         .create_minimal(&req.name, req.description.as_deref(), &current_user.user_id)
         .await
@@ -388,17 +898,16 @@ async fn create_project(
         })?;
 
     // If additional fields provided, update them
-    if req.project_type_id.is_some()
-        || req.team_id.is_some()
+    if project_type_id.is_some()
+        || team_id.is_some()
         || req.tags.is_some()
         || req.documentation.is_some()
         || req.deadline.is_some()
+        || req.settings.is_some()
     {
         let update = ExtendedProjectUpdate {
-            project_type_id: req
-                .project_type_id
-                .and_then(|s| s.parse::<ProjectTypeId>().ok()),
-            team_id: req.team_id.and_then(|s| s.parse::<TeamId>().ok()),
+            project_type_id,
+            team_id,
             tags: req.tags,
             documentation: req.documentation,
             deadline: req.deadline.and_then(|s| {
@@ -407,10 +916,11 @@ async fn create_project(
                     .map(|dt| dt.with_timezone(&chrono::Utc))
             }),
             deadline_action: req.deadline_action.and_then(|s| parse_deadline_action(&s)),
+            settings: req.settings.map(Into::into),
             ..Default::default()
         };
 
-        let repo = PgProjectRepository::new(pool);
+        let repo = PgProjectRepository::new(pool.clone()).in_org(current_user.org_id);
         let updated = repo
             .update_extended(&project.project_id, &update)
             .await
@@ -419,6 +929,7 @@ async fn create_project(
                 ApiError::Internal(anyhow::anyhow!("{}", e))
             })?;
 
+        record_project_audit(&pool, &current_user.user_id, Some(&project), &updated).await;
         return Ok((
             StatusCode::CREATED,
             Json(ProjectDetailResponse::from(updated)),
@@ -441,13 +952,14 @@ async fn create_project(
     request_body = UpdateProjectRequest,
     responses(
         (status = 200, description = "Project updated", body = ProjectDetailResponse),
+        (status = 400, description = "Invalid settings, or unknown project type or team"),
         (status = 404, description = "Project not found"),
     ),
     tag = "projects"
 )]
 async fn update_project(
     Path(project_id): Path<String>,
-    _current_user: CurrentUser,
+    current_user: CurrentUser,
     Extension(pool): Extension<PgPool>,
     Json(req): Json<UpdateProjectRequest>,
 ) -> Result<Json<ProjectDetailResponse>, ApiError> {
@@ -455,13 +967,26 @@ async fn update_project(
         .parse()
         .map_err(|_| ApiError::not_found("project", &project_id))?;
 
+    if let Some(settings) = &req.settings {
+        settings.validate()?;
+    }
+    let project_type_id = req
+        .project_type_id
+        .and_then(|s| s.parse::<ProjectTypeId>().ok());
+    let team_id = req.team_id.and_then(|s| s.parse::<TeamId>().ok());
+    ensure_references_in_org(
+        &pool,
+        &current_user,
+        project_type_id.as_ref(),
+        team_id.as_ref(),
+    )
+    .await?;
+
     let update = ExtendedProjectUpdate {
         name: req.name,
         description: req.description,
-        project_type_id: req
-            .project_type_id
-            .and_then(|s| s.parse::<ProjectTypeId>().ok()),
-        team_id: req.team_id.and_then(|s| s.parse::<TeamId>().ok()),
+        project_type_id,
+        team_id,
         tags: req.tags,
         documentation: req.documentation,
         deadline: req.deadline.and_then(|s| {
@@ -470,10 +995,13 @@ async fn update_project(
                 .map(|dt| dt.with_timezone(&chrono::Utc))
         }),
         deadline_action: req.deadline_action.and_then(|s| parse_deadline_action(&s)),
+        settings: req.settings.map(Into::into),
         ..Default::default()
     };
 
-    let repo = PgProjectRepository::new(pool);
+    let repo = PgProjectRepository::new(pool.clone()).in_org(current_user.org_id);
+    // Snapshot before the update so the audit entry can record a diff
+    let before = repo.find_by_id(&id).await.ok().flatten();
     let project = repo
         .update_extended(&id, &update)
         .await
@@ -487,6 +1015,7 @@ async fn update_project(
             }
         })?;
 
+    record_project_audit(&pool, &current_user.user_id, before.as_ref(), &project).await;
     Ok(Json(ProjectDetailResponse::from(project)))
 }
 
@@ -505,14 +1034,14 @@ async fn update_project(
 )]
 async fn delete_project(
     Path(project_id): Path<String>,
-    _current_user: CurrentUser,
+    current_user: CurrentUser,
     Extension(pool): Extension<PgPool>,
 ) -> Result<StatusCode, ApiError> {
     let id: ProjectId = project_id
         .parse()
         .map_err(|_| ApiError::not_found("project", &project_id))?;
 
-    let repo = PgProjectRepository::new(pool);
+    let repo = PgProjectRepository::new(pool).in_org(current_user.org_id);
     repo.soft_delete(&id).await.map_err(|e| match e {
         glyph_db::UpdateProjectError::NotFound(_) => ApiError::not_found("project", &project_id),
         glyph_db::UpdateProjectError::Database(e) => {
@@ -541,7 +1070,7 @@ async fn delete_project(
 )]
 async fn update_status(
     Path(project_id): Path<String>,
-    _current_user: CurrentUser,
+    current_user: CurrentUser,
     Extension(pool): Extension<PgPool>,
     Json(req): Json<UpdateStatusRequest>,
 ) -> Result<Json<StatusUpdateResponse>, ApiError> {
@@ -557,7 +1086,7 @@ async fn update_status(
         )
     })?;
 
-    let repo = PgProjectRepository::new(pool);
+    let repo = PgProjectRepository::new(pool).in_org(current_user.org_id);
 
     // Get current project to validate transition
     let current = repo
@@ -615,21 +1144,21 @@ async fn update_status(
     ),
     responses(
         (status = 200, description = "Project activated", body = ProjectDetailResponse),
-        (status = 400, description = "Activation validation failed"),
+        (status = 422, description = "An activation check is blocking"),
         (status = 404, description = "Project not found"),
     ),
     tag = "projects"
 )]
 async fn activate_project(
     Path(project_id): Path<String>,
-    _current_user: CurrentUser,
+    current_user: CurrentUser,
     Extension(pool): Extension<PgPool>,
 ) -> Result<Json<ProjectDetailResponse>, ApiError> {
     let id: ProjectId = project_id
         .parse()
         .map_err(|_| ApiError::not_found("project", &project_id))?;
 
-    let repo = PgProjectRepository::new(pool);
+    let repo = PgProjectRepository::new(pool.clone()).in_org(current_user.org_id);
 
     // Get current project
     let current = repo
@@ -641,28 +1170,56 @@ async fn activate_project(
         })?
         .ok_or_else(|| ApiError::not_found("project", &project_id))?;
 
-    // Check activation readiness
-    let (can_activate, errors) = check_activation_readiness(&current);
-    if !can_activate {
-        return Err(ApiError::bad_request(
-            "validation.activation_failed",
-            format!("Cannot activate project: {}", errors.join(", ")),
+    // Run the same checks the validate-activation endpoint reports
+    let readiness = PgDataSourceRepository::new(pool.clone())
+        .in_org(current_user.org_id)
+        .readiness(&id)
+        .await
+        .map_err(|e| ApiError::Internal(e.into()))?;
+    let default_workflow = PgWorkflowRepository::new(pool.clone())
+        .find_default()
+        .await
+        .map_err(|e| ApiError::Internal(e.into()))?;
+    let blockers: Vec<String> =
+        build_activation_checks(&current, &readiness, default_workflow.as_ref())
+            .into_iter()
+            .filter(|c| c.severity == "blocker")
+            .map(|c| c.message)
+            .collect();
+    if !blockers.is_empty() {
+        return Err(ApiError::unprocessable(
+            "project.activation_blocked",
+            format!("Cannot activate project: {}", blockers.join("; ")),
         ));
     }
 
+    // Projects without a workflow get the default, if one is set
+    let workflow_id = match default_workflow {
+        Some((default_id, _)) if current.workflow_id.is_none() => Some(default_id),
+        _ => None,
+    };
+
     // Update status to active
-    let update = glyph_db::ProjectUpdate {
+    let update = ExtendedProjectUpdate {
         status: Some(ProjectStatus::Active),
+        workflow_id,
         ..Default::default()
     };
 
-    let updated = repo.update(&id, &update).await.map_err(|e| match e {
-        glyph_db::UpdateProjectError::NotFound(_) => ApiError::not_found("project", &project_id),
-        glyph_db::UpdateProjectError::Database(e) => {
-            tracing::error!("Failed to activate project: {:?}", e);
-            ApiError::Internal(anyhow::anyhow!("{}", e))
-        }
-    })?;
+    let updated = repo
+        .update_extended(&id, &update)
+        .await
+        .map_err(|e| match e {
+            glyph_db::UpdateProjectError::NotFound(_) => {
+                ApiError::not_found("project", &project_id)
+            }
+            glyph_db::UpdateProjectError::Database(e) => {
+                tracing::error!("Failed to activate project: {:?}", e);
+                ApiError::Internal(anyhow::anyhow!("{}", e))
+            }
+        })?;
+
+    record_project_audit(&pool, &current_user.user_id, Some(&current), &updated).await;
 
     Ok(Json(ProjectDetailResponse::from(updated)))
 }
@@ -682,14 +1239,14 @@ async fn activate_project(
 )]
 async fn validate_project_activation(
     Path(project_id): Path<String>,
-    _current_user: CurrentUser,
+    current_user: CurrentUser,
     Extension(pool): Extension<PgPool>,
 ) -> Result<Json<ActivationValidationResponse>, ApiError> {
     let id: ProjectId = project_id
         .parse()
         .map_err(|_| ApiError::not_found("project", &project_id))?;
 
-    let repo = PgProjectRepository::new(pool);
+    let repo = PgProjectRepository::new(pool.clone()).in_org(current_user.org_id);
 
     let project = repo
         .find_by_id(&id)
@@ -700,7 +1257,17 @@ async fn validate_project_activation(
         })?
         .ok_or_else(|| ApiError::not_found("project", &project_id))?;
 
-    let checks = build_activation_checks(&project);
+    let readiness = PgDataSourceRepository::new(pool.clone())
+        .in_org(current_user.org_id)
+        .readiness(&id)
+        .await
+        .map_err(|e| ApiError::Internal(e.into()))?;
+    let default_workflow = PgWorkflowRepository::new(pool)
+        .find_default()
+        .await
+        .map_err(|e| ApiError::Internal(e.into()))?;
+
+    let checks = build_activation_checks(&project, &readiness, default_workflow.as_ref());
     let has_blockers = checks.iter().any(|c| c.severity == "blocker");
 
     Ok(Json(ActivationValidationResponse {
@@ -709,8 +1276,15 @@ async fn validate_project_activation(
     }))
 }
 
-/// Build detailed activation checks for a project
-fn build_activation_checks(project: &Project) -> Vec<ActivationCheck> {
+/// Build detailed activation checks for a project.
+///
+/// `default_workflow` is the ID and name of the workflow activation assigns
+/// when the project has none.
+fn build_activation_checks(
+    project: &Project,
+    readiness: &DataSourceReadiness,
+    default_workflow: Option<&(WorkflowId, String)>,
+) -> Vec<ActivationCheck> {
     let mut checks = Vec::new();
 
     // Status check
@@ -732,24 +1306,7 @@ fn build_activation_checks(project: &Project) -> Vec<ActivationCheck> {
         });
     }
 
-    // Workflow check
-    if project.workflow_id.is_some() {
-        checks.push(ActivationCheck {
-            id: "has_workflow".to_string(),
-            category: "workflow".to_string(),
-            severity: "passed".to_string(),
-            message: "Workflow is configured".to_string(),
-            fix_action: None,
-        });
-    } else {
-        checks.push(ActivationCheck {
-            id: "has_workflow".to_string(),
-            category: "workflow".to_string(),
-            severity: "warning".to_string(),
-            message: "No workflow configured (using default)".to_string(),
-            fix_action: Some("workflow".to_string()),
-        });
-    }
+    checks.push(workflow_check(project, default_workflow));
 
     // Layout check
     if project.layout_id.is_some() {
@@ -789,18 +1346,81 @@ fn build_activation_checks(project: &Project) -> Vec<ActivationCheck> {
         });
     }
 
-    // Data source placeholder (would need to check data sources table)
-    checks.push(ActivationCheck {
-        id: "has_data_source".to_string(),
-        category: "data_source".to_string(),
-        severity: "warning".to_string(),
-        message: "Data source configuration pending".to_string(),
-        fix_action: Some("data-source".to_string()),
-    });
+    checks.push(data_source_check(readiness));
 
     checks
 }
 
+/// Activation check for the project's workflow. Without one it is only a
+/// warning: activation assigns the default workflow, or leaves it unset.
+fn workflow_check(
+    project: &Project,
+    default_workflow: Option<&(WorkflowId, String)>,
+) -> ActivationCheck {
+    let (severity, message, fix_action) = match (&project.workflow_id, default_workflow) {
+        (Some(_), _) => ("passed", "Workflow is configured".to_string(), None),
+        (None, Some((_, name))) => (
+            "warning",
+            format!("No workflow configured; activation will assign the default workflow '{name}'"),
+            Some("workflow".to_string()),
+        ),
+        (None, None) => (
+            "warning",
+            "No workflow configured and no default workflow is set".to_string(),
+            Some("workflow".to_string()),
+        ),
+    };
+    ActivationCheck {
+        id: "has_workflow".to_string(),
+        category: "workflow".to_string(),
+        severity: severity.to_string(),
+        message,
+        fix_action,
+    }
+}
+
+/// Data source check: passes once an active source has items (or tasks were
+/// imported directly), warns while sources are empty, and blocks when the
+/// project has nothing to annotate
+fn data_source_check(readiness: &DataSourceReadiness) -> ActivationCheck {
+    let (severity, message, fix_action) = if readiness.active_with_items > 0 {
+        (
+            "passed",
+            format!(
+                "{} active data source(s) with items",
+                readiness.active_with_items
+            ),
+            None,
+        )
+    } else if readiness.source_count > 0 {
+        (
+            "warning",
+            "Data sources have no items yet".to_string(),
+            Some("data-source"),
+        )
+    } else if readiness.task_count > 0 {
+        (
+            "passed",
+            format!("{} task(s) imported", readiness.task_count),
+            None,
+        )
+    } else {
+        (
+            "blocker",
+            "No data sources or tasks; add a data source or import tasks".to_string(),
+            Some("data-source"),
+        )
+    };
+
+    ActivationCheck {
+        id: "has_data_source".to_string(),
+        category: "data_source".to_string(),
+        severity: severity.to_string(),
+        message,
+        fix_action: fix_action.map(str::to_string),
+    }
+}
+
 /// Clone a project
 #[utoipa::path(
     post,
@@ -825,7 +1445,7 @@ async fn clone_project(
         .parse()
         .map_err(|_| ApiError::not_found("project", &project_id))?;
 
-    let repo = PgProjectRepository::new(pool);
+    let repo = PgProjectRepository::new(pool).in_org(current_user.org_id);
 
     // Get source project
     let source = repo
@@ -885,18 +1505,805 @@ async fn clone_project(
     ))
 }
 
-// =============================================================================
-// Helper functions
-// =============================================================================
+/// Export a project's configuration as a portable bundle
+#[utoipa::path(
+    get,
+    path = "/api/v1/projects/{project_id}/export-config",
+    params(
+        ("project_id" = String, Path, description = "Project ID"),
+    ),
+    responses(
+        (status = 200, description = "Project settings, project type, workflow and layout", body = ProjectConfigBundle),
+        (status = 403, description = "Not an admin or leader of the project's team"),
+        (status = 404, description = "Project not found"),
+    ),
+    tag = "projects"
+)]
+async fn export_project_config_handler(
+    Path(project_id): Path<String>,
+    current_user: CurrentUser,
+    Extension(pool): Extension<PgPool>,
+) -> Result<Json<ProjectConfigBundle>, ApiError> {
+    let id: ProjectId = project_id
+        .parse()
+        .map_err(|_| ApiError::not_found("project", &project_id))?;
 
-fn parse_project_status(s: &str) -> Option<ProjectStatus> {
-    match s.to_lowercase().as_str() {
-        "draft" => Some(ProjectStatus::Draft),
-        "active" => Some(ProjectStatus::Active),
-        "paused" => Some(ProjectStatus::Paused),
-        "completed" => Some(ProjectStatus::Completed),
-        "archived" => Some(ProjectStatus::Archived),
-        _ => None,
+    let project = PgProjectRepository::new(pool.clone())
+        .in_org(current_user.org_id)
+        .find_by_id(&id)
+        .await
+        .map_err(|e| ApiError::Internal(anyhow::anyhow!("{}", e)))?
+        .ok_or_else(|| ApiError::not_found("project", &project_id))?;
+
+    let allowed = PermissionService::new(pool.clone())
+        .is_admin_or_team_leader(&current_user, project.team_id.as_ref())
+        .await
+        .map_err(|e| ApiError::Internal(e.into()))?;
+    if !allowed {
+        return Err(ApiError::forbidden(
+            "Only admins and team leaders can export a project's configuration",
+        ));
+    }
+
+    Ok(Json(
+        export_project_config(&pool, current_user.org_id, &id).await?,
+    ))
+}
+
+/// Prefix of the storage keys annotation exports are written under
+const ANNOTATION_EXPORT_PREFIX: &str = "exports/";
+
+/// Find a project whose annotations the user may export: admins and leaders
+/// of the project's team only
+async fn find_exportable_project(
+    pool: &PgPool,
+    current_user: &CurrentUser,
+    project_id: &str,
+) -> Result<ProjectId, ApiError> {
+    let id: ProjectId = project_id
+        .parse()
+        .map_err(|_| ApiError::not_found("project", project_id))?;
+
+    let project = PgProjectRepository::new(pool.clone())
+        .in_org(current_user.org_id)
+        .find_by_id(&id)
+        .await
+        .map_err(|e| ApiError::Internal(anyhow::anyhow!("{}", e)))?
+        .ok_or_else(|| ApiError::not_found("project", project_id))?;
+
+    let allowed = PermissionService::new(pool.clone())
+        .is_admin_or_team_leader(current_user, project.team_id.as_ref())
+        .await
+        .map_err(|e| ApiError::Internal(e.into()))?;
+    if !allowed {
+        return Err(ApiError::forbidden(
+            "Only admins and team leaders can export a project's annotations",
+        ));
+    }
+
+    Ok(id)
+}
+
+/// Export a project's completed annotations as Parquet
+///
+/// The file is written to the project's storage under
+/// `exports/annotations-<timestamp>.parquet` and can be fetched from the
+/// returned `download_url`.
+#[utoipa::path(
+    post,
+    path = "/api/v1/projects/{project_id}/exports",
+    params(
+        ("project_id" = String, Path, description = "Project ID"),
+        ("since" = Option<String>, Query, description = "Only annotations completed or changed after this time"),
+    ),
+    responses(
+        (status = 201, description = "Export written", body = AnnotationExportResponse),
+        (status = 403, description = "Not an admin or leader of the project's team"),
+        (status = 404, description = "Project not found"),
+        (status = 422, description = "An annotation doesn't match the project's output schema"),
+    ),
+    tag = "projects"
+)]
+async fn export_annotations(
+    Path(project_id): Path<String>,
+    Query(query): Query<AnnotationExportQuery>,
+    current_user: CurrentUser,
+    Extension(pool): Extension<PgPool>,
+) -> Result<(StatusCode, Json<AnnotationExportResponse>), ApiError> {
+    let id = find_exportable_project(&pool, &current_user, &project_id).await?;
+
+    let exporter = AnnotationExportService::new(
+        Arc::new(PgAnnotationRepository::new(pool.clone())),
+        Arc::new(PgProjectRepository::new(pool.clone()).in_org(current_user.org_id)),
+        Arc::new(PgProjectTypeRepository::new(pool).in_org(current_user.org_id)),
+    );
+    let file_name = format!(
+        "annotations-{}.parquet",
+        chrono::Utc::now().format("%Y%m%dT%H%M%S%.3fZ")
+    );
+    let key = format!("{ANNOTATION_EXPORT_PREFIX}{file_name}");
+    let summary = exporter
+        .export_parquet_to_storage(
+            *id.as_uuid(),
+            query.since,
+            project_storage(&id).as_ref(),
+            &key,
+        )
+        .await
+        .map_err(|e| match e {
+            ExportError::ProjectNotFound(_) => ApiError::not_found("project", &project_id),
+            e @ ExportError::TypeMismatch { .. } => {
+                ApiError::unprocessable("export.type_mismatch", e.to_string())
+            }
+            e => ApiError::Internal(e.into()),
+        })?;
+
+    Ok((
+        StatusCode::CREATED,
+        Json(AnnotationExportResponse {
+            key,
+            download_url: format!("/api/v1/projects/{id}/exports/{file_name}"),
+            rows: summary.rows,
+            checkpoint: summary.checkpoint,
+        }),
+    ))
+}
+
+/// Download a Parquet annotation export
+#[utoipa::path(
+    get,
+    path = "/api/v1/projects/{project_id}/exports/{file_name}",
+    params(
+        ("project_id" = String, Path, description = "Project ID"),
+        ("file_name" = String, Path, description = "File name from the export's download_url"),
+    ),
+    responses(
+        (status = 200, description = "Parquet file", content_type = "application/vnd.apache.parquet"),
+        (status = 403, description = "Not an admin or leader of the project's team"),
+        (status = 404, description = "Project or export not found"),
+    ),
+    tag = "projects"
+)]
+async fn download_annotation_export(
+    Path((project_id, file_name)): Path<(String, String)>,
+    current_user: CurrentUser,
+    Extension(pool): Extension<PgPool>,
+) -> Result<Response, ApiError> {
+    let id = find_exportable_project(&pool, &current_user, &project_id).await?;
+
+    // Only plain export file names; anything else in storage stays private
+    if !file_name.ends_with(".parquet") || file_name.contains(['/', '\\', '"']) {
+        return Err(ApiError::not_found("export", &file_name));
+    }
+    let bytes = project_storage(&id)
+        .get(&format!("{ANNOTATION_EXPORT_PREFIX}{file_name}"))
+        .await?;
+
+    Ok((
+        [
+            (
+                header::CONTENT_TYPE,
+                "application/vnd.apache.parquet".to_string(),
+            ),
+            (
+                header::CONTENT_DISPOSITION,
+                format!("attachment; filename=\"{file_name}\""),
+            ),
+        ],
+        bytes,
+    )
+        .into_response())
+}
+
+/// Create a project from an exported configuration bundle
+#[utoipa::path(
+    post,
+    path = "/api/v1/projects/import-config",
+    request_body = ProjectConfigBundle,
+    responses(
+        (status = 201, description = "Project and its configuration created", body = ImportedConfig),
+        (status = 400, description = "Unsupported bundle version or invalid workflow"),
+    ),
+    tag = "projects"
+)]
+async fn import_project_config_handler(
+    current_user: CurrentUser,
+    Extension(pool): Extension<PgPool>,
+    Json(bundle): Json<ProjectConfigBundle>,
+) -> Result<(StatusCode, Json<ImportedConfig>), ApiError> {
+    ProjectSettingsResponse::from(bundle.project.settings.clone()).validate()?;
+    let imported =
+        import_project_config(&pool, current_user.org_id, &bundle, &current_user.user_id).await?;
+    Ok((StatusCode::CREATED, Json(imported)))
+}
+
+/// Configure the webhook notified when a project's tasks complete
+#[utoipa::path(
+    put,
+    path = "/api/v1/projects/{project_id}/webhook",
+    params(
+        ("project_id" = String, Path, description = "Project ID"),
+    ),
+    request_body = WebhookConfigRequest,
+    responses(
+        (status = 200, description = "Webhook configured", body = WebhookConfigResponse),
+        (status = 400, description = "Invalid URL or secret"),
+        (status = 403, description = "Not an admin or leader of the project's team"),
+        (status = 404, description = "Project not found"),
+    ),
+    tag = "projects"
+)]
+async fn set_webhook(
+    Path(project_id): Path<String>,
+    current_user: CurrentUser,
+    Extension(pool): Extension<PgPool>,
+    Json(req): Json<WebhookConfigRequest>,
+) -> Result<Json<WebhookConfigResponse>, ApiError> {
+    let id = authorize_webhook_admin(&pool, &current_user, &project_id).await?;
+    req.validate().await?;
+
+    let config = PgWebhookRepository::new(pool)
+        .upsert_config(&id, &req.url, &req.secret, req.is_active.unwrap_or(true))
+        .await
+        .map_err(|e| {
+            tracing::error!("Failed to save webhook for project {}: {:?}", project_id, e);
+            ApiError::Internal(anyhow::anyhow!("{}", e))
+        })?;
+
+    Ok(Json(WebhookConfigResponse::from(config)))
+}
+
+/// Get a project's webhook configuration
+#[utoipa::path(
+    get,
+    path = "/api/v1/projects/{project_id}/webhook",
+    params(
+        ("project_id" = String, Path, description = "Project ID"),
+    ),
+    responses(
+        (status = 200, description = "Webhook configuration", body = WebhookConfigResponse),
+        (status = 403, description = "Not an admin or leader of the project's team"),
+        (status = 404, description = "No webhook configured"),
+    ),
+    tag = "projects"
+)]
+async fn get_webhook(
+    Path(project_id): Path<String>,
+    current_user: CurrentUser,
+    Extension(pool): Extension<PgPool>,
+) -> Result<Json<WebhookConfigResponse>, ApiError> {
+    let id = authorize_webhook_admin(&pool, &current_user, &project_id).await?;
+
+    let config = PgWebhookRepository::new(pool)
+        .find_config(&id)
+        .await
+        .map_err(|e| ApiError::Internal(anyhow::anyhow!("{}", e)))?
+        .ok_or_else(|| ApiError::not_found("webhook", &project_id))?;
+
+    Ok(Json(WebhookConfigResponse::from(config)))
+}
+
+/// Remove a project's webhook
+#[utoipa::path(
+    delete,
+    path = "/api/v1/projects/{project_id}/webhook",
+    params(
+        ("project_id" = String, Path, description = "Project ID"),
+    ),
+    responses(
+        (status = 204, description = "Webhook removed"),
+        (status = 403, description = "Not an admin or leader of the project's team"),
+        (status = 404, description = "No webhook configured"),
+    ),
+    tag = "projects"
+)]
+async fn delete_webhook(
+    Path(project_id): Path<String>,
+    current_user: CurrentUser,
+    Extension(pool): Extension<PgPool>,
+) -> Result<StatusCode, ApiError> {
+    let id = authorize_webhook_admin(&pool, &current_user, &project_id).await?;
+
+    let deleted = PgWebhookRepository::new(pool)
+        .delete_config(&id)
+        .await
+        .map_err(|e| ApiError::Internal(anyhow::anyhow!("{}", e)))?;
+    if !deleted {
+        return Err(ApiError::not_found("webhook", &project_id));
+    }
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Resolve a project in the caller's organization whose webhook they may
+/// manage: deliveries carry final annotations, so only admins and leaders of
+/// the project's team can see or change where they go
+async fn authorize_webhook_admin(
+    pool: &PgPool,
+    current_user: &CurrentUser,
+    project_id: &str,
+) -> Result<ProjectId, ApiError> {
+    let id: ProjectId = project_id
+        .parse()
+        .map_err(|_| ApiError::not_found("project", project_id))?;
+    let project = PgProjectRepository::new(pool.clone())
+        .in_org(current_user.org_id)
+        .find_by_id(&id)
+        .await
+        .map_err(|e| ApiError::Internal(anyhow::anyhow!("{}", e)))?
+        .ok_or_else(|| ApiError::not_found("project", project_id))?;
+
+    let allowed = PermissionService::new(pool.clone())
+        .is_admin_or_team_leader(current_user, project.team_id.as_ref())
+        .await
+        .map_err(|e| ApiError::Internal(e.into()))?;
+    if !allowed {
+        return Err(ApiError::forbidden(
+            "Only admins and team leaders can manage a project's webhook",
+        ));
+    }
+    Ok(id)
+}
+
+/// Get a project's audit history
+#[utoipa::path(
+    get,
+    path = "/api/v1/projects/{project_id}/audit",
+    params(
+        ("project_id" = String, Path, description = "Project ID"),
+        ("limit" = Option<i64>, Query, description = "Page size (default: 50, max: 200)"),
+        ("offset" = Option<i64>, Query, description = "Page offset"),
+    ),
+    responses(
+        (status = 200, description = "Audit entries, newest first", body = AuditListResponse),
+        (status = 403, description = "Not an admin or leader of the project's team"),
+        (status = 404, description = "Project not found"),
+    ),
+    tag = "projects"
+)]
+async fn get_project_audit(
+    Path(project_id): Path<String>,
+    Query(query): Query<AuditQuery>,
+    current_user: CurrentUser,
+    Extension(pool): Extension<PgPool>,
+) -> Result<Json<AuditListResponse>, ApiError> {
+    let id: ProjectId = project_id
+        .parse()
+        .map_err(|_| ApiError::not_found("project", &project_id))?;
+
+    let project = PgProjectRepository::new(pool.clone())
+        .in_org(current_user.org_id)
+        .find_by_id(&id)
+        .await
+        .map_err(|e| ApiError::Internal(anyhow::anyhow!("{}", e)))?
+        .ok_or_else(|| ApiError::not_found("project", &project_id))?;
+
+    let allowed = PermissionService::new(pool.clone())
+        .is_admin_or_team_leader(&current_user, project.team_id.as_ref())
+        .await
+        .map_err(|e| ApiError::Internal(e.into()))?;
+    if !allowed {
+        return Err(ApiError::forbidden(
+            "Only admins and team leaders can view a project's audit history",
+        ));
+    }
+
+    let limit = PaginationPolicy::for_resource(PageResource::AuditLog).limit(query.limit);
+    let offset = query.offset.unwrap_or(0).max(0);
+    let (records, total) = AuditReader::new(pool)
+        .list_for_entity(PROJECT_ENTITY, &id.to_string(), limit, offset)
+        .await
+        .map_err(|e| ApiError::Internal(e.into()))?;
+
+    Ok(Json(AuditListResponse {
+        items: records.into_iter().map(AuditEntryResponse::from).collect(),
+        total,
+        limit,
+        offset,
+        nav: PageNav::new(total, limit, offset),
+    }))
+}
+
+/// Mark a manual goal complete
+#[utoipa::path(
+    post,
+    path = "/api/v1/projects/{project_id}/goals/{goal_id}/complete",
+    params(
+        ("project_id" = String, Path, description = "Project ID"),
+        ("goal_id" = String, Path, description = "Goal ID"),
+    ),
+    responses(
+        (status = 200, description = "Goal marked complete", body = GoalProgressResponse),
+        (status = 400, description = "Goal is not a manual goal"),
+        (status = 403, description = "Not an admin or leader of the project's team"),
+        (status = 404, description = "Project or goal not found"),
+    ),
+    tag = "projects"
+)]
+async fn complete_goal(
+    Path((project_id, goal_id)): Path<(String, String)>,
+    current_user: CurrentUser,
+    Extension(pool): Extension<PgPool>,
+) -> Result<Json<GoalProgressResponse>, ApiError> {
+    set_manual_goal_completion(&pool, &current_user, &project_id, &goal_id, true)
+        .await
+        .map(Json)
+}
+
+/// Revert a manual goal to incomplete
+#[utoipa::path(
+    post,
+    path = "/api/v1/projects/{project_id}/goals/{goal_id}/uncomplete",
+    params(
+        ("project_id" = String, Path, description = "Project ID"),
+        ("goal_id" = String, Path, description = "Goal ID"),
+    ),
+    responses(
+        (status = 200, description = "Goal marked incomplete", body = GoalProgressResponse),
+        (status = 400, description = "Goal is not a manual goal"),
+        (status = 403, description = "Not an admin or leader of the project's team"),
+        (status = 404, description = "Project or goal not found"),
+    ),
+    tag = "projects"
+)]
+async fn uncomplete_goal(
+    Path((project_id, goal_id)): Path<(String, String)>,
+    current_user: CurrentUser,
+    Extension(pool): Extension<PgPool>,
+) -> Result<Json<GoalProgressResponse>, ApiError> {
+    set_manual_goal_completion(&pool, &current_user, &project_id, &goal_id, false)
+        .await
+        .map(Json)
+}
+
+// =============================================================================
+// Helper functions
+// =============================================================================
+
+/// Audit `entity_type` for projects
+const PROJECT_ENTITY: &str = "project";
+
+/// Audit `entity_type` for goals
+const GOAL_ENTITY: &str = "goal";
+
+/// Reject completion changes on goals whose progress isn't set by hand
+fn ensure_manual_goal(goal: &GoalSnapshot) -> Result<(), ApiError> {
+    if goal.goal_type == GoalType::Manual {
+        return Ok(());
+    }
+    Err(ApiError::bad_request(
+        "goal.not_manual",
+        format!(
+            "Only manual goals can be completed by hand; this is a {} goal",
+            format!("{:?}", goal.goal_type).to_lowercase()
+        ),
+    ))
+}
+
+/// Shared body of the complete/uncomplete endpoints.
+///
+/// Setting the state a goal is already in succeeds without an audit entry.
+async fn set_manual_goal_completion(
+    pool: &PgPool,
+    current_user: &CurrentUser,
+    project_id: &str,
+    goal_id: &str,
+    completed: bool,
+) -> Result<GoalProgressResponse, ApiError> {
+    let id: ProjectId = project_id
+        .parse()
+        .map_err(|_| ApiError::not_found("project", project_id))?;
+    let goal_uuid: uuid::Uuid = goal_id
+        .parse()
+        .map_err(|_| ApiError::not_found("goal", goal_id))?;
+
+    let project = PgProjectRepository::new(pool.clone())
+        .in_org(current_user.org_id)
+        .find_by_id(&id)
+        .await
+        .map_err(|e| ApiError::Internal(anyhow::anyhow!("{}", e)))?
+        .ok_or_else(|| ApiError::not_found("project", project_id))?;
+
+    let allowed = PermissionService::new(pool.clone())
+        .is_admin_or_team_leader(current_user, project.team_id.as_ref())
+        .await
+        .map_err(|e| ApiError::Internal(e.into()))?;
+    if !allowed {
+        return Err(ApiError::forbidden(
+            "Only admins and team leaders can complete a project's goals",
+        ));
+    }
+
+    let repo = PgGoalRepository::new(pool.clone());
+    let goal = repo
+        .find(&id, goal_uuid)
+        .await
+        .map_err(|e| ApiError::Internal(e.into()))?
+        .ok_or_else(|| ApiError::not_found("goal", goal_id))?;
+    ensure_manual_goal(&goal)?;
+
+    if goal.manually_completed == completed {
+        return Ok(goal.into());
+    }
+
+    let updated = repo
+        .set_manually_completed(&id, goal_uuid, completed)
+        .await
+        .map_err(|e| ApiError::Internal(e.into()))?
+        .ok_or_else(|| ApiError::not_found("goal", goal_id))?;
+
+    AuditWriter::new(pool.clone())
+        .record_best_effort(AuditEvent {
+            entity_type: GOAL_ENTITY,
+            entity_id: goal_uuid.to_string(),
+            action: AuditAction::Update,
+            actor_id: current_user.user_id.to_string(),
+            actor_type: AuditActorType::User,
+            data_snapshot: serde_json::json!({
+                "project_id": id.to_string(),
+                "name": updated.name,
+                "manually_completed": completed,
+            }),
+            changes: Some(serde_json::json!({
+                "manually_completed": { "old": !completed, "new": completed },
+            })),
+            request_id: None,
+        })
+        .await;
+
+    Ok(updated.into())
+}
+
+/// Count a project's rejections by reason and by user
+#[utoipa::path(
+    get,
+    path = "/api/v1/projects/{project_id}/rejections/stats",
+    params(
+        ("project_id" = String, Path, description = "Project ID"),
+        ("from" = Option<String>, Query, description = "Start of the window (RFC 3339, inclusive)"),
+        ("to" = Option<String>, Query, description = "End of the window (RFC 3339, exclusive)"),
+    ),
+    responses(
+        (status = 200, description = "Rejection counts", body = RejectionStatsResponse),
+        (status = 400, description = "`from` is not before `to`"),
+        (status = 403, description = "Not an admin or leader of the project's team"),
+        (status = 404, description = "Project not found"),
+    ),
+    tag = "projects"
+)]
+async fn get_rejection_stats(
+    Path(project_id): Path<String>,
+    Query(query): Query<RejectionStatsQuery>,
+    current_user: CurrentUser,
+    Extension(pool): Extension<PgPool>,
+) -> Result<Json<RejectionStatsResponse>, ApiError> {
+    let id: ProjectId = project_id
+        .parse()
+        .map_err(|_| ApiError::not_found("project", &project_id))?;
+
+    if let (Some(from), Some(to)) = (query.from, query.to) {
+        if from >= to {
+            return Err(ApiError::bad_request(
+                "validation.invalid_range",
+                "`from` must be before `to`",
+            ));
+        }
+    }
+
+    let project = PgProjectRepository::new(pool.clone())
+        .in_org(current_user.org_id)
+        .find_by_id(&id)
+        .await
+        .map_err(|e| ApiError::Internal(anyhow::anyhow!("{}", e)))?
+        .ok_or_else(|| ApiError::not_found("project", &project_id))?;
+
+    let allowed = PermissionService::new(pool.clone())
+        .is_admin_or_team_leader(&current_user, project.team_id.as_ref())
+        .await
+        .map_err(|e| ApiError::Internal(e.into()))?;
+    if !allowed {
+        return Err(ApiError::forbidden(
+            "Only admins and team leaders can view a project's rejection stats",
+        ));
+    }
+
+    let stats = PgAssignmentRepository::new(pool)
+        .rejection_stats(&id, query.from, query.to)
+        .await
+        .map_err(|e| ApiError::Internal(e.into()))?;
+
+    Ok(Json(RejectionStatsResponse::new(&id, stats)))
+}
+
+/// Longest range a throughput request may cover, in days
+const MAX_THROUGHPUT_DAYS: i64 = 366;
+
+/// Count each user's submissions per day over a date range
+#[utoipa::path(
+    get,
+    path = "/api/v1/projects/{project_id}/throughput",
+    params(
+        ("project_id" = String, Path, description = "Project ID"),
+        ("group_by" = Option<String>, Query, description = "Grouping; only `user` is supported"),
+        ("from" = String, Query, description = "First day (YYYY-MM-DD, UTC, inclusive)"),
+        ("to" = String, Query, description = "Last day (YYYY-MM-DD, UTC, inclusive)"),
+    ),
+    responses(
+        (status = 200, description = "Daily submission counts per user", body = ThroughputResponse),
+        (status = 400, description = "Unsupported grouping or invalid range"),
+        (status = 403, description = "Not an admin or leader of the project's team"),
+        (status = 404, description = "Project not found"),
+    ),
+    tag = "projects"
+)]
+async fn get_throughput(
+    Path(project_id): Path<String>,
+    Query(query): Query<ThroughputQuery>,
+    current_user: CurrentUser,
+    Extension(pool): Extension<PgPool>,
+) -> Result<Json<ThroughputResponse>, ApiError> {
+    let id: ProjectId = project_id
+        .parse()
+        .map_err(|_| ApiError::not_found("project", &project_id))?;
+
+    if let Some(group_by) = query.group_by.as_deref().filter(|g| *g != "user") {
+        return Err(ApiError::bad_request(
+            "validation.invalid_group_by",
+            format!("Unsupported group_by '{group_by}'; expected 'user'"),
+        ));
+    }
+    if query.from > query.to {
+        return Err(ApiError::bad_request(
+            "validation.invalid_range",
+            "`from` must not be after `to`",
+        ));
+    }
+    if (query.to - query.from).num_days() >= MAX_THROUGHPUT_DAYS {
+        return Err(ApiError::bad_request(
+            "validation.invalid_range",
+            format!("Range may cover at most {MAX_THROUGHPUT_DAYS} days"),
+        ));
+    }
+
+    let project = PgProjectRepository::new(pool.clone())
+        .in_org(current_user.org_id)
+        .find_by_id(&id)
+        .await
+        .map_err(|e| ApiError::Internal(anyhow::anyhow!("{}", e)))?
+        .ok_or_else(|| ApiError::not_found("project", &project_id))?;
+
+    let allowed = PermissionService::new(pool.clone())
+        .is_admin_or_team_leader(&current_user, project.team_id.as_ref())
+        .await
+        .map_err(|e| ApiError::Internal(e.into()))?;
+    if !allowed {
+        return Err(ApiError::forbidden(
+            "Only admins and team leaders can view a project's throughput",
+        ));
+    }
+
+    let users = PgAssignmentRepository::new(pool)
+        .daily_throughput(&id, query.from, query.to)
+        .await
+        .map_err(|e| ApiError::Internal(e.into()))?;
+
+    Ok(Json(ThroughputResponse {
+        project_id: id.to_string(),
+        from: query.from,
+        to: query.to,
+        users: users.into_iter().map(UserThroughput::from).collect(),
+    }))
+}
+
+/// Snapshot the progress of a project's goals
+#[utoipa::path(
+    get,
+    path = "/api/v1/projects/{project_id}/goals",
+    params(("project_id" = String, Path, description = "Project ID")),
+    responses(
+        (status = 200, description = "Goal progress", body = GoalProgressListResponse),
+        (status = 404, description = "Project not found"),
+    ),
+    tag = "projects"
+)]
+async fn list_goal_progress(
+    Path(project_id): Path<String>,
+    current_user: CurrentUser,
+    Extension(pool): Extension<PgPool>,
+) -> Result<Json<GoalProgressListResponse>, ApiError> {
+    let id: ProjectId = project_id
+        .parse()
+        .map_err(|_| ApiError::not_found("project", &project_id))?;
+
+    PgProjectRepository::new(pool.clone())
+        .in_org(current_user.org_id)
+        .find_by_id(&id)
+        .await
+        .map_err(|e| ApiError::Internal(anyhow::anyhow!("{}", e)))?
+        .ok_or_else(|| ApiError::not_found("project", &project_id))?;
+
+    let goals = PgGoalRepository::new(pool)
+        .list_for_project(&id)
+        .await
+        .map_err(|e| ApiError::Internal(e.into()))?;
+
+    Ok(Json(GoalProgressListResponse {
+        project_id: id.to_string(),
+        goals: goals.into_iter().map(Into::into).collect(),
+    }))
+}
+
+/// Record a project create (no `before`) or update in the audit log.
+///
+/// Best effort: a failed write is logged and doesn't fail the request.
+async fn record_project_audit(
+    pool: &PgPool,
+    actor: &UserId,
+    before: Option<&Project>,
+    after: &Project,
+) {
+    let snapshot = serde_json::to_value(after).unwrap_or_default();
+    let (action, changes) = match before {
+        None => (AuditAction::Create, None),
+        Some(before) => {
+            let old = serde_json::to_value(before).unwrap_or_default();
+            (
+                AuditAction::Update,
+                AuditWriter::compute_changes(&old, &snapshot),
+            )
+        }
+    };
+
+    AuditWriter::new(pool.clone())
+        .record_best_effort(AuditEvent {
+            entity_type: PROJECT_ENTITY,
+            entity_id: after.project_id.to_string(),
+            action,
+            actor_id: actor.to_string(),
+            actor_type: AuditActorType::User,
+            data_snapshot: snapshot,
+            changes,
+            request_id: None,
+        })
+        .await;
+}
+
+/// Reject a project type or team the caller's organization can't see
+async fn ensure_references_in_org(
+    pool: &PgPool,
+    current_user: &CurrentUser,
+    project_type_id: Option<&ProjectTypeId>,
+    team_id: Option<&TeamId>,
+) -> Result<(), ApiError> {
+    if let Some(id) = project_type_id {
+        PgProjectTypeRepository::new(pool.clone())
+            .in_org(current_user.org_id)
+            .find_by_id(id)
+            .await
+            .map_err(|e| ApiError::Internal(anyhow::anyhow!("{}", e)))?
+            .ok_or_else(|| {
+                ApiError::bad_request("project.project_type.not_found", "Project type not found")
+            })?;
+    }
+    if let Some(id) = team_id {
+        PgTeamRepository::new(pool.clone())
+            .in_org(current_user.org_id)
+            .find_by_id(id)
+            .await
+            .map_err(|e| ApiError::Internal(anyhow::anyhow!("{}", e)))?
+            .ok_or_else(|| ApiError::bad_request("project.team.not_found", "Team not found"))?;
+    }
+    Ok(())
+}
+
+fn parse_project_status(s: &str) -> Option<ProjectStatus> {
+    match s.to_lowercase().as_str() {
+        "draft" => Some(ProjectStatus::Draft),
+        "active" => Some(ProjectStatus::Active),
+        "paused" => Some(ProjectStatus::Paused),
+        "completed" => Some(ProjectStatus::Completed),
+        "archived" => Some(ProjectStatus::Archived),
+        _ => None,
     }
 }
 
@@ -908,3 +2315,759 @@ fn parse_deadline_action(s: &str) -> Option<glyph_domain::DeadlineAction> {
         _ => None,
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn field_code(settings: &ProjectSettingsResponse) -> Option<&'static str> {
+        match settings.validate() {
+            Ok(()) => None,
+            Err(ApiError::BadRequest { code, .. }) => Some(code),
+            Err(other) => panic!("unexpected error: {other:?}"),
+        }
+    }
+
+    fn readiness(
+        source_count: i64,
+        active_with_items: i64,
+        task_count: i64,
+    ) -> DataSourceReadiness {
+        DataSourceReadiness {
+            source_count,
+            active_with_items,
+            task_count,
+        }
+    }
+
+    #[test]
+    fn test_data_source_check_passes_with_items() {
+        let check = data_source_check(&readiness(2, 1, 0));
+        assert_eq!(check.severity, "passed");
+        assert_eq!(check.fix_action, None);
+    }
+
+    #[test]
+    fn test_data_source_check_warns_on_empty_sources() {
+        // An inactive source with items doesn't count
+        let check = data_source_check(&readiness(1, 0, 0));
+        assert_eq!(check.severity, "warning");
+        assert_eq!(check.fix_action.as_deref(), Some("data-source"));
+    }
+
+    #[test]
+    fn test_data_source_check_blocks_without_sources_or_tasks() {
+        let check = data_source_check(&readiness(0, 0, 0));
+        assert_eq!(check.severity, "blocker");
+        assert_eq!(check.id, "has_data_source");
+    }
+
+    #[test]
+    fn test_data_source_check_accepts_imported_tasks() {
+        let check = data_source_check(&readiness(0, 0, 25));
+        assert_eq!(check.severity, "passed");
+        assert!(check.message.contains("25"));
+    }
+
+    fn goal(goal_type: GoalType, target: f64, current: f64) -> GoalSnapshot {
+        GoalSnapshot {
+            goal_id: uuid::Uuid::new_v4(),
+            name: "Goal".to_string(),
+            goal_type,
+            target_value: target,
+            current_value: current,
+            deadline: None,
+            manually_completed: false,
+            completion_action_fired_at: None,
+        }
+    }
+
+    #[test]
+    fn test_volume_goal_progress() {
+        let progress = GoalProgressResponse::from(goal(GoalType::Volume, 200.0, 50.0));
+        assert_eq!(progress.goal_type, "volume");
+        assert!((progress.percent_complete - 0.25).abs() < f64::EPSILON);
+        assert!(!progress.is_complete);
+        assert!(!progress.completion_action_fired);
+        assert_eq!(progress.manually_completed, None);
+
+        let mut done = goal(GoalType::Volume, 200.0, 260.0);
+        done.completion_action_fired_at = Some(chrono::Utc::now());
+        let progress = GoalProgressResponse::from(done);
+        assert!((progress.percent_complete - 1.0).abs() < f64::EPSILON);
+        assert!(progress.is_complete);
+        assert!(progress.completion_action_fired);
+    }
+
+    #[test]
+    fn test_manual_goal_uses_manual_state() {
+        let mut manual = goal(GoalType::Manual, 1.0, 0.0);
+        manual.manually_completed = true;
+        let progress = GoalProgressResponse::from(manual);
+        assert_eq!(progress.manually_completed, Some(true));
+        assert!(progress.is_complete);
+        assert!((progress.percent_complete - 1.0).abs() < f64::EPSILON);
+
+        // A manual goal isn't complete until marked, even at its target value
+        let progress = GoalProgressResponse::from(goal(GoalType::Manual, 1.0, 1.0));
+        assert_eq!(progress.manually_completed, Some(false));
+        assert!(!progress.is_complete);
+    }
+
+    #[test]
+    fn test_only_manual_goals_complete_by_hand() {
+        assert!(ensure_manual_goal(&goal(GoalType::Manual, 1.0, 0.0)).is_ok());
+
+        let Err(ApiError::BadRequest { code, message }) =
+            ensure_manual_goal(&goal(GoalType::Volume, 100.0, 0.0))
+        else {
+            panic!("expected bad request");
+        };
+        assert_eq!(code, "goal.not_manual");
+        assert!(message.contains("volume"));
+    }
+
+    #[test]
+    fn test_manual_goal_complete_and_uncomplete_transitions() {
+        let mut manual = goal(GoalType::Manual, 1.0, 0.0);
+        assert!(!GoalProgressResponse::from(manual.clone()).is_complete);
+
+        manual.manually_completed = true;
+        assert!(GoalProgressResponse::from(manual.clone()).is_complete);
+
+        manual.manually_completed = false;
+        let progress = GoalProgressResponse::from(manual);
+        assert!(!progress.is_complete);
+        assert!(progress.percent_complete.abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn test_default_settings_are_valid() {
+        assert_eq!(field_code(&ProjectSettingsResponse::default()), None);
+    }
+
+    #[test]
+    fn test_quality_threshold_bounds() {
+        for ok in [0.0, 0.5, 1.0] {
+            let s = ProjectSettingsResponse {
+                quality_threshold: Some(ok),
+                ..Default::default()
+            };
+            assert_eq!(field_code(&s), None, "{ok} should be valid");
+        }
+        for bad in [2.0, -0.1, f64::NAN] {
+            let s = ProjectSettingsResponse {
+                quality_threshold: Some(bad),
+                ..Default::default()
+            };
+            assert_eq!(
+                field_code(&s),
+                Some("validation.settings.quality_threshold"),
+                "{bad} should be rejected"
+            );
+        }
+    }
+
+    #[test]
+    fn test_negative_integers_rejected() {
+        let s = ProjectSettingsResponse {
+            max_assignments_per_user: Some(-1),
+            ..Default::default()
+        };
+        assert_eq!(
+            field_code(&s),
+            Some("validation.settings.max_assignments_per_user")
+        );
+
+        let s = ProjectSettingsResponse {
+            assignment_timeout_hours: Some(-24),
+            ..Default::default()
+        };
+        assert_eq!(
+            field_code(&s),
+            Some("validation.settings.assignment_timeout_hours")
+        );
+    }
+
+    #[test]
+    fn test_integer_caps() {
+        let s = ProjectSettingsResponse {
+            max_assignments_per_user: Some(MAX_ASSIGNMENTS_PER_USER),
+            assignment_timeout_hours: Some(MAX_ASSIGNMENT_TIMEOUT_HOURS),
+            ..Default::default()
+        };
+        assert_eq!(field_code(&s), None);
+
+        let s = ProjectSettingsResponse {
+            assignment_timeout_hours: Some(MAX_ASSIGNMENT_TIMEOUT_HOURS + 1),
+            ..Default::default()
+        };
+        assert_eq!(
+            field_code(&s),
+            Some("validation.settings.assignment_timeout_hours")
+        );
+    }
+
+    #[test]
+    fn test_error_message_names_field() {
+        let s = ProjectSettingsResponse {
+            quality_threshold: Some(2.0),
+            ..Default::default()
+        };
+        let Err(ApiError::BadRequest { message, .. }) = s.validate() else {
+            panic!("expected bad request");
+        };
+        assert!(message.contains("quality_threshold"));
+    }
+
+    #[tokio::test]
+    #[ignore = "requires DATABASE_URL pointing at a migrated database"]
+    async fn test_create_and_update_produce_two_audit_entries() {
+        let url = std::env::var("DATABASE_URL").expect("DATABASE_URL must be set");
+        let pool = PgPool::connect(&url).await.unwrap();
+
+        let user_id = UserId::new();
+        sqlx::query(
+            r#"
+            INSERT INTO users (user_id, email, display_name, auth0_id, global_role, status)
+            VALUES ($1, $2, 'Audit Test', $3, 'admin', 'active')
+            "#,
+        )
+        .bind(user_id.as_uuid())
+        .bind(format!("{user_id}@audit.test"))
+        .bind(format!("test|{user_id}"))
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        // The repository records the create itself
+        let repo = PgProjectRepository::new(pool.clone());
+        let created = repo
+            .create_minimal("Audited", None, &user_id)
+            .await
+            .unwrap();
+
+        let update = ExtendedProjectUpdate {
+            name: Some("Audited (renamed)".to_string()),
+            ..Default::default()
+        };
+        let updated = repo
+            .update_extended(&created.project_id, &update)
+            .await
+            .unwrap();
+        record_project_audit(&pool, &user_id, Some(&created), &updated).await;
+
+        let (entries, total) = AuditReader::new(pool)
+            .list_for_entity(PROJECT_ENTITY, &created.project_id.to_string(), 50, 0)
+            .await
+            .unwrap();
+
+        assert_eq!(total, 2);
+        assert_eq!(entries[0].action, AuditAction::Update);
+        assert_eq!(entries[1].action, AuditAction::Create);
+        assert_eq!(entries[0].actor_id, user_id.to_string());
+        let changes = entries[0].changes.as_ref().unwrap();
+        assert_eq!(changes["name"]["old"], "Audited");
+        assert_eq!(changes["name"]["new"], "Audited (renamed)");
+    }
+
+    #[tokio::test]
+    #[ignore = "requires DATABASE_URL pointing at a migrated database"]
+    async fn test_rejection_stats_group_seeded_rejections() {
+        use glyph_db::{AssignmentRepository, RejectAssignment};
+        use glyph_domain::AssignmentId;
+
+        let url = std::env::var("DATABASE_URL").expect("DATABASE_URL must be set");
+        let pool = PgPool::connect(&url).await.unwrap();
+
+        let mut users = Vec::new();
+        for name in ["Rejecter A", "Rejecter B"] {
+            let user_id = UserId::new();
+            sqlx::query(
+                r#"
+                INSERT INTO users (user_id, email, display_name, auth0_id, global_role, status)
+                VALUES ($1, $2, $3, $4, 'user', 'active')
+                "#,
+            )
+            .bind(user_id.as_uuid())
+            .bind(format!("{user_id}@rejections.test"))
+            .bind(name)
+            .bind(format!("test|{user_id}"))
+            .execute(&pool)
+            .await
+            .unwrap();
+            users.push(user_id);
+        }
+
+        let project = PgProjectRepository::new(pool.clone())
+            .create_minimal("Rejections", None, &users[0])
+            .await
+            .unwrap();
+        let repo = PgAssignmentRepository::new(pool.clone());
+
+        let seeded = [
+            (
+                &users[0],
+                serde_json::json!({ "type": "unclear_instructions" }),
+            ),
+            (
+                &users[1],
+                serde_json::json!({ "type": "unclear_instructions" }),
+            ),
+            (
+                &users[1],
+                serde_json::json!({ "type": "unclear_instructions" }),
+            ),
+            (
+                &users[1],
+                serde_json::json!({ "type": "other", "details": "duplicate" }),
+            ),
+        ];
+        for (user_id, reason) in seeded {
+            let task_id: uuid::Uuid = sqlx::query_scalar(
+                "INSERT INTO tasks (project_id, priority, input_data) VALUES ($1, 5, '{}') RETURNING task_id",
+            )
+            .bind(project.project_id.as_uuid())
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+            let assignment_id: uuid::Uuid = sqlx::query_scalar(
+                r#"
+                INSERT INTO task_assignments (task_id, project_id, step_id, user_id)
+                VALUES ($1, $2, 'annotate', $3)
+                RETURNING assignment_id
+                "#,
+            )
+            .bind(task_id)
+            .bind(project.project_id.as_uuid())
+            .bind(user_id.as_uuid())
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+            repo.reject(&RejectAssignment {
+                assignment_id: AssignmentId::from_uuid(assignment_id),
+                reason,
+            })
+            .await
+            .unwrap();
+        }
+
+        let stats = repo
+            .rejection_stats(&project.project_id, None, None)
+            .await
+            .unwrap();
+        assert_eq!(stats.total, 4);
+        assert_eq!(
+            stats.by_reason,
+            vec![
+                ("unclear_instructions".to_string(), 3),
+                ("other".to_string(), 1)
+            ]
+        );
+        assert_eq!(
+            stats.by_user,
+            vec![(users[1].clone(), 3), (users[0].clone(), 1)]
+        );
+
+        // A window ending before the rejections excludes them all
+        let before = chrono::Utc::now() - chrono::Duration::hours(1);
+        let stats = repo
+            .rejection_stats(&project.project_id, None, Some(before))
+            .await
+            .unwrap();
+        assert_eq!(stats.total, 0);
+        assert!(stats.by_reason.is_empty());
+    }
+
+    #[tokio::test]
+    #[ignore = "requires DATABASE_URL pointing at a migrated database"]
+    async fn test_daily_throughput_two_users_two_days() {
+        let url = std::env::var("DATABASE_URL").expect("DATABASE_URL must be set");
+        let pool = PgPool::connect(&url).await.unwrap();
+
+        let mut users = Vec::new();
+        for name in ["Annotator A", "Annotator B"] {
+            let user_id = UserId::new();
+            sqlx::query(
+                r#"
+                INSERT INTO users (user_id, email, display_name, auth0_id, global_role, status)
+                VALUES ($1, $2, $3, $4, 'user', 'active')
+                "#,
+            )
+            .bind(user_id.as_uuid())
+            .bind(format!("{user_id}@throughput.test"))
+            .bind(name)
+            .bind(format!("test|{user_id}"))
+            .execute(&pool)
+            .await
+            .unwrap();
+            users.push(user_id);
+        }
+
+        let project = PgProjectRepository::new(pool.clone())
+            .create_minimal("Throughput", None, &users[0])
+            .await
+            .unwrap();
+
+        let day = |d| chrono::NaiveDate::from_ymd_opt(2026, 3, d).unwrap();
+        let at = |d, h| day(d).and_hms_opt(h, 0, 0).unwrap().and_utc();
+        // A submits twice on day 1 and not on day 2; B submits on both days
+        let seeded = [
+            (&users[0], at(1, 9)),
+            (&users[0], at(1, 23)),
+            (&users[1], at(1, 12)),
+            (&users[1], at(2, 0)),
+            (&users[1], at(2, 18)),
+        ];
+        for (user_id, submitted_at) in seeded {
+            let task_id: uuid::Uuid = sqlx::query_scalar(
+                "INSERT INTO tasks (project_id, priority, input_data) VALUES ($1, 5, '{}') RETURNING task_id",
+            )
+            .bind(project.project_id.as_uuid())
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+            sqlx::query(
+                r#"
+                INSERT INTO task_assignments (task_id, project_id, step_id, user_id, status, submitted_at)
+                VALUES ($1, $2, 'annotate', $3, 'submitted', $4)
+                "#,
+            )
+            .bind(task_id)
+            .bind(project.project_id.as_uuid())
+            .bind(user_id.as_uuid())
+            .bind(submitted_at)
+            .execute(&pool)
+            .await
+            .unwrap();
+        }
+
+        let throughput = PgAssignmentRepository::new(pool.clone())
+            .daily_throughput(&project.project_id, day(1), day(2))
+            .await
+            .unwrap();
+
+        assert_eq!(throughput.len(), 2);
+        assert_eq!(throughput[0].user_id, users[1]);
+        assert_eq!(throughput[0].days, vec![(day(1), 1), (day(2), 2)]);
+        assert_eq!(throughput[1].user_id, users[0]);
+        assert_eq!(throughput[1].days, vec![(day(1), 2), (day(2), 0)]);
+    }
+
+    #[tokio::test]
+    #[ignore = "requires DATABASE_URL pointing at a migrated database"]
+    async fn test_project_config_round_trips_through_export_and_import() {
+        use glyph_db::{
+            LayoutDefinition, PgLayoutRepository, PgProjectTypeRepository, ProjectTypeRepository,
+        };
+        use glyph_domain::{CreateProjectType, ProficiencyLevel, SkillRequirement};
+
+        let url = std::env::var("DATABASE_URL").expect("DATABASE_URL must be set");
+        let pool = PgPool::connect(&url).await.unwrap();
+
+        let user_id = UserId::new();
+        sqlx::query(
+            r#"
+            INSERT INTO users (user_id, email, display_name, auth0_id, global_role, status)
+            VALUES ($1, $2, 'Exporter', $3, 'user', 'active')
+            "#,
+        )
+        .bind(user_id.as_uuid())
+        .bind(format!("{user_id}@config.test"))
+        .bind(format!("test|{user_id}"))
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        let project_type = PgProjectTypeRepository::new(pool.clone())
+            .create(
+                &CreateProjectType {
+                    name: format!("Config NER {user_id}"),
+                    description: None,
+                    input_schema: None,
+                    output_schema: Some(serde_json::json!({ "type": "object" })),
+                    estimated_duration_seconds: Some(90),
+                    difficulty_level: None,
+                    skill_requirements: Some(vec![SkillRequirement {
+                        skill_id: "ner".to_string(),
+                        min_proficiency: ProficiencyLevel::Advanced,
+                        is_required: true,
+                        weight: 2.0,
+                    }]),
+                    allow_overlapping_spans: Some(false),
+                    is_system: None,
+                },
+                Some(&user_id),
+            )
+            .await
+            .unwrap();
+        let workflow_id = PgWorkflowRepository::new(pool.clone())
+            .create_from_definition(
+                &crate::services::project_config_service::single_step_workflow("Config flow"),
+            )
+            .await
+            .unwrap();
+        let layout_id = PgLayoutRepository::new(pool.clone())
+            .create_from_definition(
+                &LayoutDefinition {
+                    name: "Config layout".to_string(),
+                    description: None,
+                    version: "1.0.0".to_string(),
+                    template_format: "nunjucks".to_string(),
+                    content: "<p>{{ text }}</p>".to_string(),
+                    input_schema: None,
+                    output_schema: None,
+                    settings: serde_json::json!({}),
+                    allowed_components: vec!["TextBlock".to_string()],
+                    shortcuts: serde_json::json!({}),
+                },
+                Some(&project_type.project_type_id),
+                &user_id,
+            )
+            .await
+            .unwrap();
+
+        let repo = PgProjectRepository::new(pool.clone());
+        let source = repo
+            .create_minimal("Config source", Some("Exported"), &user_id)
+            .await
+            .unwrap();
+        repo.update_extended(
+            &source.project_id,
+            &ExtendedProjectUpdate {
+                project_type_id: Some(project_type.project_type_id.clone()),
+                tags: Some(vec!["ner".to_string()]),
+                settings: Some(ProjectSettings {
+                    quality_threshold: Some(0.8),
+                    ..Default::default()
+                }),
+                workflow_id: Some(workflow_id.clone()),
+                layout_id: Some(layout_id.to_string()),
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
+
+        let exported =
+            export_project_config(&pool, glyph_domain::OrgId::DEFAULT, &source.project_id)
+                .await
+                .unwrap();
+        assert!(exported.workflow.is_some() && exported.layout.is_some());
+
+        let imported =
+            import_project_config(&pool, glyph_domain::OrgId::DEFAULT, &exported, &user_id)
+                .await
+                .unwrap();
+        assert_ne!(imported.project_id, source.project_id.to_string());
+        assert_ne!(
+            imported.project_type_id,
+            Some(project_type.project_type_id.to_string())
+        );
+        assert_ne!(imported.workflow_id, Some(workflow_id.to_string()));
+        assert_ne!(imported.layout_id, Some(layout_id.to_string()));
+
+        let copy: ProjectId = imported.project_id.parse().unwrap();
+        let reexported = export_project_config(&pool, glyph_domain::OrgId::DEFAULT, &copy)
+            .await
+            .unwrap();
+        assert_eq!(reexported.project, exported.project);
+        assert_eq!(reexported.workflow, exported.workflow);
+        assert_eq!(reexported.layout, exported.layout);
+
+        // The source type still exists, so the copy is renamed
+        let (original_type, copied_type) = (
+            exported.project_type.unwrap(),
+            reexported.project_type.unwrap(),
+        );
+        assert_eq!(
+            copied_type.name,
+            format!("{} (imported)", original_type.name)
+        );
+        assert_eq!(
+            BundledProjectType {
+                name: original_type.name.clone(),
+                ..copied_type
+            },
+            original_type
+        );
+    }
+
+    #[tokio::test]
+    #[ignore = "requires DATABASE_URL pointing at a migrated database"]
+    async fn test_activation_assigns_default_workflow() {
+        let url = std::env::var("DATABASE_URL").expect("DATABASE_URL must be set");
+        let pool = PgPool::connect(&url).await.unwrap();
+
+        let user_id = UserId::new();
+        sqlx::query(
+            r#"
+            INSERT INTO users (user_id, email, display_name, auth0_id, global_role, status)
+            VALUES ($1, $2, 'Activation Test', $3, 'admin', 'active')
+            "#,
+        )
+        .bind(user_id.as_uuid())
+        .bind(format!("{user_id}@activation.test"))
+        .bind(format!("test|{user_id}"))
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        let workflow_name = format!("default-{user_id}");
+        let workflow_id: WorkflowId = sqlx::query_scalar::<_, uuid::Uuid>(
+            "INSERT INTO workflows (name, entry_step_id) VALUES ($1, 'annotate') RETURNING workflow_id",
+        )
+        .bind(&workflow_name)
+        .fetch_one(&pool)
+        .await
+        .map(WorkflowId::from_uuid)
+        .unwrap();
+        assert!(PgWorkflowRepository::new(pool.clone())
+            .set_default(&workflow_id)
+            .await
+            .unwrap());
+
+        let project = PgProjectRepository::new(pool.clone())
+            .create_minimal("Needs Workflow", None, &user_id)
+            .await
+            .unwrap();
+        assert!(project.workflow_id.is_none());
+        sqlx::query("UPDATE projects SET layout_id = 'default' WHERE project_id = $1")
+            .bind(project.project_id.as_uuid())
+            .execute(&pool)
+            .await
+            .unwrap();
+
+        let admin = || CurrentUser {
+            user_id: user_id.clone(),
+            auth0_id: format!("test|{user_id}"),
+            email: None,
+            email_verified: true,
+            name: None,
+            roles: vec!["admin".to_string()],
+            org_id: glyph_domain::OrgId::DEFAULT,
+        };
+        let activate = || {
+            activate_project(
+                Path(project.project_id.to_string()),
+                admin(),
+                Extension(pool.clone()),
+            )
+        };
+
+        let Json(validation) = validate_project_activation(
+            Path(project.project_id.to_string()),
+            admin(),
+            Extension(pool.clone()),
+        )
+        .await
+        .unwrap();
+        let workflow_check = validation
+            .checks
+            .iter()
+            .find(|c| c.id == "has_workflow")
+            .unwrap();
+        assert_eq!(workflow_check.severity, "warning");
+        assert!(workflow_check.message.contains(&workflow_name));
+
+        // Nothing to annotate yet, so the data source check blocks
+        assert!(matches!(
+            activate().await,
+            Err(ApiError::Unprocessable {
+                code: "project.activation_blocked",
+                ..
+            })
+        ));
+
+        sqlx::query("INSERT INTO tasks (project_id, input_data) VALUES ($1, '{}')")
+            .bind(project.project_id.as_uuid())
+            .execute(&pool)
+            .await
+            .unwrap();
+        let Json(activated) = activate().await.unwrap();
+
+        assert_eq!(activated.status, "active");
+        assert_eq!(activated.workflow_id, Some(workflow_id.to_string()));
+    }
+
+    #[tokio::test]
+    #[ignore = "requires DATABASE_URL pointing at a migrated database"]
+    async fn test_annotation_export_can_be_downloaded() {
+        let url = std::env::var("DATABASE_URL").expect("DATABASE_URL must be set");
+        let pool = PgPool::connect(&url).await.unwrap();
+
+        let user_id = UserId::new();
+        sqlx::query(
+            r#"
+            INSERT INTO users (user_id, email, display_name, auth0_id, global_role, status)
+            VALUES ($1, $2, 'Export Test', $3, 'admin', 'active')
+            "#,
+        )
+        .bind(user_id.as_uuid())
+        .bind(format!("{user_id}@export.test"))
+        .bind(format!("test|{user_id}"))
+        .execute(&pool)
+        .await
+        .unwrap();
+        let project = PgProjectRepository::new(pool.clone())
+            .create_minimal("Exported", None, &user_id)
+            .await
+            .unwrap();
+        let admin = || CurrentUser {
+            user_id: user_id.clone(),
+            auth0_id: format!("test|{user_id}"),
+            email: None,
+            email_verified: true,
+            name: None,
+            roles: vec!["admin".to_string()],
+            org_id: glyph_domain::OrgId::DEFAULT,
+        };
+
+        let (status, Json(export)) = export_annotations(
+            Path(project.project_id.to_string()),
+            Query(AnnotationExportQuery { since: None }),
+            admin(),
+            Extension(pool.clone()),
+        )
+        .await
+        .unwrap();
+        assert_eq!(status, StatusCode::CREATED);
+        let file_name = export.download_url.rsplit('/').next().unwrap().to_string();
+        assert_eq!(
+            export.download_url,
+            format!(
+                "/api/v1/projects/{}/exports/{file_name}",
+                project.project_id
+            )
+        );
+
+        let response = download_annotation_export(
+            Path((project.project_id.to_string(), file_name)),
+            admin(),
+            Extension(pool.clone()),
+        )
+        .await
+        .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        assert!(body.starts_with(b"PAR1"));
+
+        // Only export files can be fetched this way
+        assert!(matches!(
+            download_annotation_export(
+                Path((project.project_id.to_string(), "secrets.json".to_string())),
+                admin(),
+                Extension(pool),
+            )
+            .await,
+            Err(ApiError::NotFound { .. })
+        ));
+
+        project_storage(&project.project_id)
+            .delete(&export.key)
+            .await
+            .unwrap();
+    }
+}
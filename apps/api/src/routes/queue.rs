@@ -3,26 +3,35 @@
 //! Provides endpoints for viewing assigned tasks, queue statistics,
 //! and user presence on projects.
 
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
 
 use axum::{
+    body::Body,
     extract::{
-        ws::{Message, WebSocket, WebSocketUpgrade},
+        ws::{close_code, CloseFrame, Message, WebSocket, WebSocketUpgrade},
         Path, Query, State,
     },
-    http::StatusCode,
-    response::IntoResponse,
+    http::{header, StatusCode},
+    response::{IntoResponse, Response},
     routing::get,
     Extension, Json, Router,
 };
 use chrono::{DateTime, Utc};
+use futures::{SinkExt, Stream, StreamExt};
+use glyph_db::{proficiency_score_sql, PgProjectRepository, ProficiencyDecay, ProjectRepository};
+use glyph_domain::{EstimatedDuration, ProjectId};
+use glyph_workflow_engine::assignment::TaskCooldown;
+use glyph_workflow_engine::Clock;
 use serde::{Deserialize, Serialize};
 use sqlx::PgPool;
 use utoipa::ToSchema;
 use uuid::Uuid;
 
 use crate::extractors::CurrentUser;
+use crate::pagination::{PageResource, PaginationPolicy};
+use crate::routes::project_types::parse_difficulty;
+use crate::services::PermissionService;
 use crate::ws::{ClientMessage, QueueEvent, QueueUpdateHub};
 use crate::ApiError;
 
@@ -58,12 +67,19 @@ pub struct QueueFilters {
 /// Sort options for queue listing
 #[derive(Debug, Deserialize, Default)]
 pub struct QueueSort {
-    /// Sort field: priority, age, project
+    /// Sort field: priority, age, project, skill_match, urgency
     pub by: Option<String>,
     /// Sort order: asc, desc
     pub order: Option<String>,
 }
 
+impl QueueSort {
+    /// Whether this is the default priority ordering, the only one cursors support
+    fn is_default(&self) -> bool {
+        matches!(self.by.as_deref(), None | Some("priority"))
+    }
+}
+
 /// Combined query parameters for queue listing
 #[derive(Debug, Deserialize, Default)]
 pub struct QueueQuery {
@@ -73,6 +89,10 @@ pub struct QueueQuery {
     pub sort: QueueSort,
     pub page: Option<i32>,
     pub per_page: Option<i32>,
+    /// Opaque cursor from a previous response's `next_cursor`.
+    /// When present, keyset pagination is used and `page` is ignored; only the
+    /// default priority ordering can be combined with a cursor.
+    pub cursor: Option<String>,
 }
 
 /// Queue statistics per project
@@ -82,6 +102,8 @@ pub struct ProjectQueueStats {
     pub project_name: String,
     pub pending: i64,
     pub in_progress: i64,
+    /// Workflow steps in this project currently past their SLA deadline
+    pub overdue_steps: i64,
 }
 
 /// Overall queue statistics
@@ -89,6 +111,7 @@ pub struct ProjectQueueStats {
 pub struct QueueStats {
     pub total_pending: i64,
     pub total_in_progress: i64,
+    pub total_overdue_steps: i64,
     pub by_project: Vec<ProjectQueueStats>,
 }
 
@@ -100,6 +123,55 @@ pub struct QueueListResponse {
     pub page: i32,
     pub per_page: i32,
     pub total_pages: i32,
+    /// Cursor for fetching the next items in priority order (None when exhausted).
+    /// In cursor mode `page` is 0 and `total_pages` is not meaningful.
+    pub next_cursor: Option<String>,
+}
+
+/// Keyset position in the default queue ordering
+/// (`priority DESC, assigned_at ASC, assignment_id ASC`).
+///
+/// Encoded as `priority:assigned_at_micros:assignment_id` so that
+/// assignments arriving mid-scroll never shift later pages.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct QueueCursor {
+    pub priority: i32,
+    pub assigned_at: DateTime<Utc>,
+    pub assignment_id: Uuid,
+}
+
+impl QueueCursor {
+    /// Build a cursor pointing at the given queue item
+    pub fn from_item(item: &QueueItem) -> Self {
+        Self {
+            priority: item.priority,
+            assigned_at: item.assigned_at,
+            assignment_id: item.assignment_id,
+        }
+    }
+
+    /// Encode as an opaque, URL-safe string
+    pub fn encode(&self) -> String {
+        format!(
+            "{}:{}:{}",
+            self.priority,
+            self.assigned_at.timestamp_micros(),
+            self.assignment_id.simple()
+        )
+    }
+
+    /// Decode a cursor previously produced by [`QueueCursor::encode`]
+    pub fn decode(s: &str) -> Option<Self> {
+        let mut parts = s.splitn(3, ':');
+        let priority = parts.next()?.parse().ok()?;
+        let micros: i64 = parts.next()?.parse().ok()?;
+        let assignment_id = parts.next()?.parse().ok()?;
+        Some(Self {
+            priority,
+            assigned_at: DateTime::from_timestamp_micros(micros)?,
+            assignment_id,
+        })
+    }
 }
 
 /// User presence information
@@ -118,6 +190,22 @@ pub struct PresenceResponse {
     pub active_users: Vec<UserPresence>,
 }
 
+/// Query parameters for the queue CSV export
+#[derive(Debug, Deserialize)]
+pub struct QueueExportQuery {
+    pub project_id: Uuid,
+}
+
+/// Column header of the queue CSV export
+pub const QUEUE_EXPORT_HEADER: [&str; 6] = [
+    "assignment_id",
+    "task_id",
+    "user",
+    "status",
+    "priority",
+    "time_in_queue",
+];
+
 // =============================================================================
 // Database Row Types
 // =============================================================================
@@ -134,6 +222,8 @@ struct QueueRow {
     priority: i32,
     assigned_at: DateTime<Utc>,
     time_in_queue_seconds: Option<i64>,
+    estimated_duration_seconds: Option<i32>,
+    difficulty_level: Option<String>,
 }
 
 #[derive(sqlx::FromRow)]
@@ -142,6 +232,17 @@ struct StatsRow {
     project_name: String,
     pending: i64,
     in_progress: i64,
+    overdue_steps: i64,
+}
+
+#[derive(sqlx::FromRow)]
+struct QueueExportRow {
+    assignment_id: Uuid,
+    task_id: Uuid,
+    user_name: String,
+    status: String,
+    priority: i32,
+    time_in_queue_seconds: Option<i64>,
 }
 
 #[derive(sqlx::FromRow)]
@@ -164,13 +265,15 @@ struct PresenceRow {
         ("project_id" = Option<Uuid>, Query, description = "Filter by project"),
         ("step_type" = Option<String>, Query, description = "Filter by step type"),
         ("status" = Option<String>, Query, description = "Filter by status"),
-        ("by" = Option<String>, Query, description = "Sort by: priority, age, project"),
+        ("by" = Option<String>, Query, description = "Sort by: priority, age, project, skill_match, urgency"),
         ("order" = Option<String>, Query, description = "Sort order: asc, desc"),
         ("page" = Option<i32>, Query, description = "Page number"),
         ("per_page" = Option<i32>, Query, description = "Items per page"),
+        ("cursor" = Option<String>, Query, description = "Cursor from a previous next_cursor (enables keyset pagination; priority ordering only)"),
     ),
     responses(
         (status = 200, description = "Queue items", body = QueueListResponse),
+        (status = 400, description = "Invalid cursor, or a cursor combined with a non-priority sort"),
         (status = 401, description = "Unauthorized"),
     ),
     tag = "queue"
@@ -181,43 +284,25 @@ async fn get_queue(
     Extension(pool): Extension<PgPool>,
 ) -> Result<Json<QueueListResponse>, ApiError> {
     let user_id = current_user.user_id;
+    let per_page = PaginationPolicy::for_resource(PageResource::Queue).per_page(query.per_page);
+
+    if let Some(ref raw) = query.cursor {
+        if !query.sort.is_default() {
+            return Err(ApiError::bad_request(
+                "queue.cursor.unsupported_sort",
+                "Cursors are only supported with the default priority ordering",
+            ));
+        }
+        let cursor = QueueCursor::decode(raw)
+            .ok_or_else(|| ApiError::bad_request("queue.cursor.invalid", "Invalid queue cursor"))?;
+        return get_queue_after_cursor(&pool, &user_id, &query.filters, cursor, per_page).await;
+    }
+
     let page = query.page.unwrap_or(1).max(1);
-    let per_page = query.per_page.unwrap_or(20).clamp(1, 100);
     let offset = ((page - 1) * per_page) as i64;
     let limit = per_page as i64;
 
-    // Build dynamic sort clause
-    let order_by = match (query.sort.by.as_deref(), query.sort.order.as_deref()) {
-        (Some("age"), Some("asc")) => "ta.assigned_at ASC",
-        (Some("age"), _) => "ta.assigned_at DESC",
-        (Some("project"), Some("desc")) => "p.name DESC, t.priority DESC",
-        (Some("project"), _) => "p.name ASC, t.priority DESC",
-        _ => "t.priority DESC, ta.assigned_at ASC", // default: priority
-    };
-
-    // Build WHERE clauses for filters
-    let mut conditions = vec![
-        "ta.user_id = $1",
-        "ta.status IN ('assigned', 'accepted', 'in_progress')",
-    ];
-
-    let project_filter = query.filters.project_id;
-    let step_filter = query.filters.step_type.clone();
-    let status_filter = query.filters.status.clone();
-
-    if project_filter.is_some() {
-        conditions.push("ta.project_id = $4");
-    }
-    if step_filter.is_some() {
-        conditions.push("ta.step_id = $5");
-    }
-    if let Some(ref s) = status_filter {
-        if !s.is_empty() {
-            conditions.push("ta.status = $6::assignment_status");
-        }
-    }
-
-    let where_clause = conditions.join(" AND ");
+    let order_by = queue_order_by(query.sort.by.as_deref(), query.sort.order.as_deref());
 
     // Query with dynamic ordering (using format! for ORDER BY since it can't be parameterized)
     let query_str = format!(
@@ -232,111 +317,270 @@ async fn get_queue(
             ta.status::text,
             t.priority,
             ta.assigned_at,
-            EXTRACT(EPOCH FROM (NOW() - ta.assigned_at))::bigint as time_in_queue_seconds
+            EXTRACT(EPOCH FROM (NOW() - ta.assigned_at))::bigint as time_in_queue_seconds,
+            pt.estimated_duration_seconds,
+            pt.difficulty_level
         FROM task_assignments ta
         JOIN tasks t ON ta.task_id = t.task_id
         JOIN projects p ON ta.project_id = p.project_id
-        WHERE {}
+        LEFT JOIN project_types pt ON p.project_type_id = pt.project_type_id
+        WHERE ta.user_id = $1
+          AND ta.status IN ('assigned', 'accepted', 'in_progress')
+          AND {}
         ORDER BY {}
         LIMIT $2 OFFSET $3
         "#,
-        where_clause, order_by
+        queue_filter_sql(4),
+        order_by
     );
 
-    // Execute query with conditional bindings
-    let rows: Vec<QueueRow> = if let Some(proj_id) = project_filter {
-        if let Some(ref step) = step_filter {
-            if let Some(ref status) = status_filter {
-                sqlx::query_as(&query_str)
-                    .bind(user_id.as_uuid())
-                    .bind(limit)
-                    .bind(offset)
-                    .bind(proj_id)
-                    .bind(step)
-                    .bind(status)
-                    .fetch_all(&pool)
-                    .await
-            } else {
-                sqlx::query_as(&query_str)
-                    .bind(user_id.as_uuid())
-                    .bind(limit)
-                    .bind(offset)
-                    .bind(proj_id)
-                    .bind(step)
-                    .fetch_all(&pool)
-                    .await
-            }
-        } else if let Some(ref status) = status_filter {
-            // Adjust query for missing step filter
-            let adjusted_query = query_str
-                .replace("$5", "$6")
-                .replace("$6::assignment_status", "$5::assignment_status");
-            sqlx::query_as(&adjusted_query)
-                .bind(user_id.as_uuid())
-                .bind(limit)
-                .bind(offset)
-                .bind(proj_id)
-                .bind(status)
-                .fetch_all(&pool)
-                .await
-        } else {
-            sqlx::query_as(&query_str)
-                .bind(user_id.as_uuid())
-                .bind(limit)
-                .bind(offset)
-                .bind(proj_id)
-                .fetch_all(&pool)
-                .await
-        }
+    let rows: Vec<QueueRow> = sqlx::query_as(&query_str)
+        .bind(user_id.as_uuid())
+        .bind(limit)
+        .bind(offset)
+        .bind(query.filters.project_id)
+        .bind(&query.filters.step_type)
+        .bind(status_filter(&query.filters))
+        .fetch_all(&pool)
+        .await
+        .map_err(|e| ApiError::Internal(e.into()))?;
+
+    let total = count_queue(&pool, &user_id, &query.filters).await?;
+
+    let items: Vec<QueueItem> = rows.into_iter().map(QueueItem::from).collect();
+
+    let total_pages = ((total as f64) / (per_page as f64)).ceil() as i32;
+
+    // Cursors only make sense for the default priority ordering
+    let next_cursor = if query.sort.is_default() {
+        next_cursor_for(&items, per_page)
     } else {
-        // No project filter - simpler query
-        let simple_query = format!(
-            r#"
-            SELECT
-                ta.assignment_id,
-                ta.task_id,
-                ta.project_id,
-                p.name as project_name,
-                ta.step_id,
-                ta.step_id as step_type,
-                ta.status::text,
-                t.priority,
-                ta.assigned_at,
-                EXTRACT(EPOCH FROM (NOW() - ta.assigned_at))::bigint as time_in_queue_seconds
-            FROM task_assignments ta
-            JOIN tasks t ON ta.task_id = t.task_id
-            JOIN projects p ON ta.project_id = p.project_id
-            WHERE ta.user_id = $1 AND ta.status IN ('assigned', 'accepted', 'in_progress')
-            ORDER BY {}
-            LIMIT $2 OFFSET $3
-            "#,
-            order_by
-        );
-        sqlx::query_as(&simple_query)
-            .bind(user_id.as_uuid())
-            .bind(limit)
-            .bind(offset)
-            .fetch_all(&pool)
-            .await
+        None
+    };
+
+    Ok(Json(QueueListResponse {
+        items,
+        total,
+        page,
+        per_page,
+        total_pages,
+        next_cursor,
+    }))
+}
+
+/// How well the user's skills fit a task, from 0 to 1.
+///
+/// The weighted mean, over the skills the project type requires, of the
+/// user's proficiency in each: novice 0.25 up to expert (or no level) 1.0,
+/// decayed for inactivity since the user last submitted work needing the
+/// skill (or was certified), and 0 for skills they lack or whose
+/// certification has hard-expired. Tasks without skill requirements score 0,
+/// so skill-specific work comes first.
+fn skill_match_score(decay: &ProficiencyDecay) -> String {
+    let proficiency = proficiency_score_sql("us.proficiency_level");
+    let decay = decay.sql_factor(
+        r#"GREATEST(us.certified_at, (
+                SELECT MAX(a.submitted_at)
+                FROM annotations a
+                JOIN projects ap ON ap.project_id = a.project_id
+                JOIN project_type_skill_requirements ar
+                    ON ar.project_type_id = ap.project_type_id
+                WHERE a.user_id = ta.user_id AND ar.skill_id = r.skill_id
+            ))"#,
+    );
+    format!(
+        r#"
+    COALESCE((
+        SELECT SUM(COALESCE(r.weight, 1.0) * CASE
+                   WHEN us.user_id IS NULL THEN 0
+                   ELSE {proficiency} * {decay}
+               END) / NULLIF(SUM(COALESCE(r.weight, 1.0)), 0)
+        FROM project_type_skill_requirements r
+        LEFT JOIN user_skills_with_status us
+            ON us.skill_id = r.skill_id
+           AND us.user_id = ta.user_id
+           AND us.status <> 'hard_expired'
+        WHERE r.project_type_id = p.project_type_id
+    ), 0)"#
+    )
+}
+
+/// Priority a task gets at or past its project's deadline, on top of its base
+const URGENCY_MAX_BOOST: i32 = 100;
+
+/// How far ahead of a deadline the urgency boost starts ramping up
+const URGENCY_WINDOW_SECONDS: i64 = 7 * 24 * 3600;
+
+/// A task's base priority plus a deadline-proximity boost.
+///
+/// The boost grows linearly from 0 a week out to [`URGENCY_MAX_BOOST`] at the
+/// deadline and stays there once it passes. Projects without a deadline keep
+/// their base priority.
+fn urgency_score() -> String {
+    format!(
+        r#"(t.priority + CASE
+        WHEN p.deadline IS NULL THEN 0
+        ELSE {URGENCY_MAX_BOOST} * GREATEST(0, LEAST(1,
+            1 - EXTRACT(EPOCH FROM (p.deadline - NOW())) / {URGENCY_WINDOW_SECONDS}))
+    END)"#
+    )
+}
+
+/// ORDER BY clause for a queue sort; unknown fields sort by priority
+fn queue_order_by(by: Option<&str>, order: Option<&str>) -> String {
+    match (by, order) {
+        (Some("age"), Some("asc")) => "ta.assigned_at ASC".to_string(),
+        (Some("age"), _) => "ta.assigned_at DESC".to_string(),
+        (Some("project"), Some("desc")) => "p.name DESC, t.priority DESC".to_string(),
+        (Some("project"), _) => "p.name ASC, t.priority DESC".to_string(),
+        (Some("skill_match"), Some("asc")) => {
+            format!(
+                "{} ASC, t.priority DESC, ta.assigned_at ASC",
+                skill_match_score(&ProficiencyDecay::default())
+            )
+        }
+        (Some("skill_match"), _) => {
+            format!(
+                "{} DESC, t.priority DESC, ta.assigned_at ASC",
+                skill_match_score(&ProficiencyDecay::default())
+            )
+        }
+        (Some("urgency"), Some("asc")) => {
+            format!("{} ASC, ta.assigned_at ASC", urgency_score())
+        }
+        (Some("urgency"), _) => format!("{} DESC, ta.assigned_at ASC", urgency_score()),
+        _ => DEFAULT_QUEUE_ORDER.to_string(),
     }
-    .map_err(|e| ApiError::Internal(e.into()))?;
+}
+
+/// Default queue ordering; the trailing assignment id makes it total, so
+/// page boundaries and cursors agree on ties
+const DEFAULT_QUEUE_ORDER: &str = "t.priority DESC, ta.assigned_at ASC, ta.assignment_id ASC";
+
+/// Optional queue filter predicates, binding project, step and status at
+/// `$first`, `$first + 1` and `$first + 2` (NULL disables a filter)
+fn queue_filter_sql(first: usize) -> String {
+    let (project, step, status) = (first, first + 1, first + 2);
+    format!(
+        "(${project}::uuid IS NULL OR ta.project_id = ${project}) \
+         AND (${step}::text IS NULL OR ta.step_id = ${step}) \
+         AND (${status}::text IS NULL OR ta.status = ${status}::assignment_status)"
+    )
+}
+
+/// Status filter to bind, treating an empty string as no filter
+fn status_filter(filters: &QueueFilters) -> Option<&str> {
+    filters.status.as_deref().filter(|s| !s.is_empty())
+}
 
-    // Get total count
-    let total: i64 = sqlx::query_scalar(
+/// Number of open assignments in the user's queue matching the filters
+async fn count_queue(
+    pool: &PgPool,
+    user_id: &glyph_domain::UserId,
+    filters: &QueueFilters,
+) -> Result<i64, ApiError> {
+    sqlx::query_scalar(&format!(
         r#"
         SELECT COUNT(*)
-        FROM task_assignments
-        WHERE user_id = $1 AND status IN ('assigned', 'accepted', 'in_progress')
+        FROM task_assignments ta
+        WHERE ta.user_id = $1
+          AND ta.status IN ('assigned', 'accepted', 'in_progress')
+          AND {}
         "#,
-    )
+        queue_filter_sql(2)
+    ))
+    .bind(user_id.as_uuid())
+    .bind(filters.project_id)
+    .bind(&filters.step_type)
+    .bind(status_filter(filters))
+    .fetch_one(pool)
+    .await
+    .map_err(|e| ApiError::Internal(e.into()))
+}
+
+/// Fetch queue items strictly after a cursor in the default priority ordering
+async fn get_queue_after_cursor(
+    pool: &PgPool,
+    user_id: &glyph_domain::UserId,
+    filters: &QueueFilters,
+    cursor: QueueCursor,
+    per_page: i32,
+) -> Result<Json<QueueListResponse>, ApiError> {
+    let rows: Vec<QueueRow> = sqlx::query_as(&format!(
+        r#"
+        SELECT
+            ta.assignment_id,
+            ta.task_id,
+            ta.project_id,
+            p.name as project_name,
+            ta.step_id,
+            ta.step_id as step_type,
+            ta.status::text,
+            t.priority,
+            ta.assigned_at,
+            EXTRACT(EPOCH FROM (NOW() - ta.assigned_at))::bigint as time_in_queue_seconds,
+            pt.estimated_duration_seconds,
+            pt.difficulty_level
+        FROM task_assignments ta
+        JOIN tasks t ON ta.task_id = t.task_id
+        JOIN projects p ON ta.project_id = p.project_id
+        LEFT JOIN project_types pt ON p.project_type_id = pt.project_type_id
+        WHERE ta.user_id = $1
+          AND ta.status IN ('assigned', 'accepted', 'in_progress')
+          AND {}
+          AND (
+              t.priority < $3
+              OR (t.priority = $3 AND (ta.assigned_at, ta.assignment_id) > ($4, $5))
+          )
+        ORDER BY {DEFAULT_QUEUE_ORDER}
+        LIMIT $2
+        "#,
+        queue_filter_sql(6)
+    ))
     .bind(user_id.as_uuid())
-    .fetch_one(&pool)
+    .bind(per_page as i64)
+    .bind(cursor.priority)
+    .bind(cursor.assigned_at)
+    .bind(cursor.assignment_id)
+    .bind(filters.project_id)
+    .bind(&filters.step_type)
+    .bind(status_filter(filters))
+    .fetch_all(pool)
     .await
     .map_err(|e| ApiError::Internal(e.into()))?;
 
-    let items: Vec<QueueItem> = rows
-        .into_iter()
-        .map(|r| QueueItem {
+    let total = count_queue(pool, user_id, filters).await?;
+
+    let items: Vec<QueueItem> = rows.into_iter().map(QueueItem::from).collect();
+    let next_cursor = next_cursor_for(&items, per_page);
+
+    Ok(Json(QueueListResponse {
+        items,
+        total,
+        page: 0,
+        per_page,
+        total_pages: 0,
+        next_cursor,
+    }))
+}
+
+/// Cursor for the item after the last one, if the page was full
+fn next_cursor_for(items: &[QueueItem], per_page: i32) -> Option<String> {
+    if items.len() < per_page as usize {
+        return None;
+    }
+    items
+        .last()
+        .map(|item| QueueCursor::from_item(item).encode())
+}
+
+impl From<QueueRow> for QueueItem {
+    fn from(r: QueueRow) -> Self {
+        let estimated_duration = EstimatedDuration::resolve(
+            r.estimated_duration_seconds,
+            r.difficulty_level.as_deref().and_then(parse_difficulty),
+        );
+        Self {
             assignment_id: r.assignment_id,
             task_id: r.task_id,
             project_id: r.project_id,
@@ -347,20 +591,11 @@ async fn get_queue(
             priority: r.priority,
             assigned_at: r.assigned_at,
             time_in_queue_seconds: r.time_in_queue_seconds.unwrap_or(0),
-            estimated_duration_minutes: None,
+            // Rounded up so short tasks don't show as zero minutes
+            estimated_duration_minutes: estimated_duration.map(|d| (d.seconds + 59) / 60),
             input_data_preview: None,
-        })
-        .collect();
-
-    let total_pages = ((total as f64) / (per_page as f64)).ceil() as i32;
-
-    Ok(Json(QueueListResponse {
-        items,
-        total,
-        page,
-        per_page,
-        total_pages,
-    }))
+        }
+    }
 }
 
 /// Get queue statistics for current user
@@ -381,15 +616,36 @@ async fn get_queue_stats(
 
     let rows: Vec<StatsRow> = sqlx::query_as(
         r#"
+        WITH overdue AS (
+            -- Steps flagged overdue that haven't since moved on
+            SELECT t.project_id, COUNT(*) AS overdue_steps
+            FROM workflow_events o
+            JOIN tasks t ON t.task_id = o.stream_id
+            WHERE o.event_type = 'step_overdue'
+              AND NOT EXISTS (
+                  SELECT 1 FROM workflow_events l
+                  WHERE l.stream_id = o.stream_id
+                    AND l.version > o.version
+                    AND (
+                        l.event_type IN ('workflow_completed', 'workflow_failed')
+                        OR (l.event_type IN ('step_activated', 'step_completed',
+                                             'step_failed', 'step_skipped')
+                            AND l.event_data->>'step_id' = o.event_data->>'step_id')
+                    )
+              )
+            GROUP BY t.project_id
+        )
         SELECT
             ta.project_id,
             p.name as project_name,
             COUNT(*) FILTER (WHERE ta.status = 'assigned') as pending,
-            COUNT(*) FILTER (WHERE ta.status IN ('accepted', 'in_progress')) as in_progress
+            COUNT(*) FILTER (WHERE ta.status IN ('accepted', 'in_progress')) as in_progress,
+            COALESCE(od.overdue_steps, 0) as overdue_steps
         FROM task_assignments ta
         JOIN projects p ON ta.project_id = p.project_id
+        LEFT JOIN overdue od ON od.project_id = ta.project_id
         WHERE ta.user_id = $1 AND ta.status IN ('assigned', 'accepted', 'in_progress')
-        GROUP BY ta.project_id, p.name
+        GROUP BY ta.project_id, p.name, od.overdue_steps
         "#,
     )
     .bind(user_id.as_uuid())
@@ -399,6 +655,7 @@ async fn get_queue_stats(
 
     let total_pending: i64 = rows.iter().map(|r| r.pending).sum();
     let total_in_progress: i64 = rows.iter().map(|r| r.in_progress).sum();
+    let total_overdue_steps: i64 = rows.iter().map(|r| r.overdue_steps).sum();
 
     let by_project: Vec<ProjectQueueStats> = rows
         .into_iter()
@@ -407,16 +664,26 @@ async fn get_queue_stats(
             project_name: r.project_name,
             pending: r.pending,
             in_progress: r.in_progress,
+            overdue_steps: r.overdue_steps,
         })
         .collect();
 
     Ok(Json(QueueStats {
         total_pending,
         total_in_progress,
+        total_overdue_steps,
         by_project,
     }))
 }
 
+/// How recently a user must have been seen to count as present
+pub const PRESENCE_WINDOW: chrono::Duration = chrono::Duration::minutes(5);
+
+/// Oldest `last_seen_at` that still counts as present
+fn presence_cutoff(clock: &dyn Clock) -> DateTime<Utc> {
+    clock.now() - PRESENCE_WINDOW
+}
+
 /// Get active users on a project
 #[utoipa::path(
     get,
@@ -427,15 +694,22 @@ async fn get_queue_stats(
     responses(
         (status = 200, description = "Active users", body = PresenceResponse),
         (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Not part of the project"),
     ),
     tag = "queue"
 )]
 async fn get_presence(
-    _current_user: CurrentUser,
+    current_user: CurrentUser,
     Path(project_id): Path<Uuid>,
     Extension(pool): Extension<PgPool>,
+    Extension(clock): Extension<Arc<dyn Clock>>,
 ) -> Result<Json<PresenceResponse>, ApiError> {
-    // Get users active in last 5 minutes
+    if !can_watch_project(&pool, &current_user, project_id).await? {
+        return Err(ApiError::forbidden(
+            "Only members of a project can see who is working on it",
+        ));
+    }
+
     let rows: Vec<PresenceRow> = sqlx::query_as(
         r#"
         SELECT
@@ -446,11 +720,12 @@ async fn get_presence(
         FROM user_presence up
         JOIN users u ON up.user_id = u.user_id
         WHERE up.project_id = $1
-          AND up.last_seen_at > NOW() - INTERVAL '5 minutes'
+          AND up.last_seen_at > $2
         ORDER BY up.last_seen_at DESC
         "#,
     )
     .bind(project_id)
+    .bind(presence_cutoff(clock.as_ref()))
     .fetch_all(&pool)
     .await
     .map_err(|e| ApiError::Internal(e.into()))?;
@@ -471,15 +746,36 @@ async fn get_presence(
     }))
 }
 
+/// Whether the user takes part in a project in their organization, and so
+/// may see who else is working on it
+async fn can_watch_project(
+    pool: &PgPool,
+    user: &CurrentUser,
+    project_id: Uuid,
+) -> Result<bool, ApiError> {
+    let project = PgProjectRepository::new(pool.clone())
+        .in_org(user.org_id)
+        .find_by_id(&ProjectId::from_uuid(project_id))
+        .await
+        .map_err(|e| ApiError::Internal(anyhow::anyhow!("{}", e)))?;
+    let Some(project) = project else {
+        return Ok(false);
+    };
+    PermissionService::new(pool.clone())
+        .is_project_member(user, &project)
+        .await
+        .map_err(|e| ApiError::Internal(e.into()))
+}
+
 /// WebSocket endpoint for real-time queue updates
 pub async fn queue_websocket(
     ws: WebSocketUpgrade,
     current_user: CurrentUser,
     State(hub): State<Arc<QueueUpdateHub>>,
     Extension(pool): Extension<PgPool>,
+    Extension(clock): Extension<Arc<dyn Clock>>,
 ) -> impl IntoResponse {
-    let user_id = *current_user.user_id.as_uuid();
-    ws.on_upgrade(move |socket| handle_socket(socket, hub, pool, user_id))
+    ws.on_upgrade(move |socket| handle_socket(socket, hub, pool, clock, current_user))
 }
 
 /// Handle a WebSocket connection
@@ -487,17 +783,37 @@ async fn handle_socket(
     mut socket: WebSocket,
     hub: Arc<QueueUpdateHub>,
     pool: PgPool,
-    user_id: Uuid,
+    clock: Arc<dyn Clock>,
+    current_user: CurrentUser,
 ) {
+    let user_id = *current_user.user_id.as_uuid();
     // Subscribe to user's queue updates
     let mut user_rx = hub.subscribe_user(user_id).await;
+    let mut shutdown_rx = hub.shutdown_receiver();
 
     // Track subscribed projects for presence
     let mut subscribed_projects: HashMap<Uuid, tokio::sync::broadcast::Receiver<QueueEvent>> =
         HashMap::new();
+    // Projects this connection counts the user as online in
+    let mut active_projects: HashSet<Uuid> = HashSet::new();
+    // Projects the user was found to take part in, checked once per connection
+    let mut watchable_projects: HashSet<Uuid> = HashSet::new();
 
     loop {
+        if *shutdown_rx.borrow_and_update() {
+            let _ = socket
+                .send(Message::Close(Some(CloseFrame {
+                    code: close_code::AWAY,
+                    reason: "server shutting down".into(),
+                })))
+                .await;
+            break;
+        }
+
         tokio::select! {
+            // Wake up so the shutdown check above can close the socket
+            _ = shutdown_rx.changed() => {}
+
             // Forward hub events to WebSocket
             Ok(event) = user_rx.recv() => {
                 let msg = serde_json::to_string(&event).unwrap_or_default();
@@ -509,34 +825,59 @@ async fn handle_socket(
             // Handle incoming messages from client
             Some(msg) = socket.recv() => {
                 match msg {
-                    Ok(Message::Text(text)) => {
-                        if let Ok(client_msg) = serde_json::from_str::<ClientMessage>(&text) {
-                            match client_msg {
-                                ClientMessage::Ping { timestamp } => {
-                                    let pong = QueueEvent::Pong { timestamp };
-                                    let msg = serde_json::to_string(&pong).unwrap_or_default();
-                                    if socket.send(Message::Text(msg.into())).await.is_err() {
-                                        break;
-                                    }
-                                }
-                                ClientMessage::SubscribeProject { project_id } => {
-                                    if !subscribed_projects.contains_key(&project_id) {
-                                        let rx = hub.subscribe_project(project_id).await;
-                                        subscribed_projects.insert(project_id, rx);
-                                    }
-                                }
-                                ClientMessage::UnsubscribeProject { project_id } => {
-                                    subscribed_projects.remove(&project_id);
-                                    hub.cleanup_project(project_id).await;
+                    Ok(Message::Text(text)) => match ClientMessage::parse(&text) {
+                        Err(error) => {
+                            if send_event(&mut socket, &error).await.is_err() {
+                                break;
+                            }
+                        }
+                        Ok(ClientMessage::Ping { timestamp }) => {
+                            let pong = QueueEvent::Pong { timestamp };
+                            if send_event(&mut socket, &pong).await.is_err() {
+                                break;
+                            }
+                        }
+                        Ok(ClientMessage::SubscribeProject { project_id }) => {
+                            if !check_watchable(&pool, &current_user, project_id, &mut watchable_projects).await {
+                                let error = QueueEvent::project_forbidden(project_id);
+                                if send_event(&mut socket, &error).await.is_err() {
+                                    break;
                                 }
-                                ClientMessage::Activity { project_id } => {
-                                    // Update presence
-                                    if let Some(pid) = project_id {
-                                        let _ = update_user_presence(&pool, user_id, pid).await;
+                            } else if !subscribed_projects.contains_key(&project_id) {
+                                let rx = hub.subscribe_project(project_id).await;
+                                subscribed_projects.insert(project_id, rx);
+                            }
+                        }
+                        Ok(ClientMessage::UnsubscribeProject { project_id }) => {
+                            subscribed_projects.remove(&project_id);
+                            hub.cleanup_project(project_id).await;
+                        }
+                        Ok(ClientMessage::Activity { project_id }) => {
+                            if let Some(pid) = project_id {
+                                if !check_watchable(&pool, &current_user, pid, &mut watchable_projects).await {
+                                    let error = QueueEvent::project_forbidden(pid);
+                                    if send_event(&mut socket, &error).await.is_err() {
+                                        break;
                                     }
+                                    continue;
                                 }
+                                record_activity(
+                                    &hub,
+                                    &pool,
+                                    clock.as_ref(),
+                                    user_id,
+                                    pid,
+                                    &mut active_projects,
+                                )
+                                .await;
                             }
                         }
+                    },
+                    Ok(Message::Binary(_)) => {
+                        let error = QueueEvent::binary_frame_rejected();
+                        if send_event(&mut socket, &error).await.is_err() {
+                            break;
+                        }
                     }
                     Ok(Message::Ping(data)) => {
                         if socket.send(Message::Pong(data)).await.is_err() {
@@ -563,28 +904,106 @@ async fn handle_socket(
     }
 
     // Cleanup on disconnect
+    leave_projects(&hub, user_id, active_projects).await;
     hub.cleanup_user(user_id).await;
     for project_id in subscribed_projects.keys() {
         hub.cleanup_project(*project_id).await;
     }
 }
 
-/// Update user presence for a project
+/// Send an event to the client as a JSON text frame
+async fn send_event(socket: &mut WebSocket, event: &QueueEvent) -> Result<(), axum::Error> {
+    let msg = serde_json::to_string(event).unwrap_or_default();
+    socket.send(Message::Text(msg.into())).await
+}
+
+/// [`can_watch_project`], remembering projects that pass for the connection.
+///
+/// Lookup failures are logged and treated as no access.
+async fn check_watchable(
+    pool: &PgPool,
+    user: &CurrentUser,
+    project_id: Uuid,
+    watchable: &mut HashSet<Uuid>,
+) -> bool {
+    if watchable.contains(&project_id) {
+        return true;
+    }
+    match can_watch_project(pool, user, project_id).await {
+        Ok(true) => {
+            watchable.insert(project_id);
+            true
+        }
+        Ok(false) => false,
+        Err(e) => {
+            tracing::warn!(error = ?e, user_id = %user.user_id, %project_id, "Failed to check project access");
+            false
+        }
+    }
+}
+
+/// Release a closing connection's presence, telling each project's
+/// subscribers the user went offline if it was their last connection there
+async fn leave_projects(hub: &QueueUpdateHub, user_id: Uuid, active_projects: HashSet<Uuid>) {
+    for project_id in active_projects {
+        if hub.leave_presence(project_id, user_id).await {
+            let offline = QueueEvent::PresenceChanged {
+                project_id,
+                user_id,
+                online: false,
+            };
+            hub.broadcast_to_project(project_id, offline).await;
+        }
+    }
+}
+
+/// Record a user's activity in a project.
+///
+/// Refreshes their presence row and, the first time this connection sees
+/// them active in the project, counts the connection towards their presence;
+/// if it is their only one, tells the project's subscribers they're online.
+async fn record_activity(
+    hub: &QueueUpdateHub,
+    pool: &PgPool,
+    clock: &dyn Clock,
+    user_id: Uuid,
+    project_id: Uuid,
+    active_projects: &mut HashSet<Uuid>,
+) {
+    if let Err(e) = update_user_presence(pool, clock.now(), user_id, project_id).await {
+        tracing::warn!(error = %e, %user_id, %project_id, "Failed to update presence");
+    }
+
+    if active_projects.insert(project_id) && hub.join_presence(project_id, user_id).await {
+        let online = QueueEvent::PresenceChanged {
+            project_id,
+            user_id,
+            online: true,
+        };
+        hub.broadcast_to_project(project_id, online).await;
+    }
+}
+
+/// Update user presence for a project.
+///
+/// `seen_at` comes from the same clock [`get_presence`] compares against.
 async fn update_user_presence(
     pool: &PgPool,
+    seen_at: DateTime<Utc>,
     user_id: Uuid,
     project_id: Uuid,
 ) -> Result<(), sqlx::Error> {
     sqlx::query(
         r#"
         INSERT INTO user_presence (user_id, project_id, last_seen_at)
-        VALUES ($1, $2, NOW())
+        VALUES ($1, $2, $3)
         ON CONFLICT (user_id, project_id)
-        DO UPDATE SET last_seen_at = NOW()
+        DO UPDATE SET last_seen_at = EXCLUDED.last_seen_at
         "#,
     )
     .bind(user_id)
     .bind(project_id)
+    .bind(seen_at)
     .execute(pool)
     .await?;
     Ok(())
@@ -594,6 +1013,9 @@ async fn update_user_presence(
 // Accept/Reject Endpoints
 // =============================================================================
 
+/// How long a rejected task is kept out of the queue
+pub const REJECT_COOLDOWN: chrono::Duration = chrono::Duration::minutes(2);
+
 /// Request to reject an assignment
 #[derive(Debug, Deserialize, ToSchema)]
 pub struct RejectRequest {
@@ -628,6 +1050,7 @@ async fn accept_task(
     current_user: CurrentUser,
     Path(assignment_id): Path<Uuid>,
     Extension(pool): Extension<PgPool>,
+    hub: Option<Extension<Arc<QueueUpdateHub>>>,
 ) -> Result<Json<AcceptResponse>, ApiError> {
     use glyph_db::{AssignmentRepository, PgAssignmentRepository};
     use glyph_domain::{AssignmentId, AssignmentStatus};
@@ -663,6 +1086,14 @@ async fn accept_task(
         .await
         .map_err(|e| ApiError::Internal(e.into()))?;
 
+    notify_assignment_status(
+        hub.as_deref(),
+        &current_user,
+        assignment_id,
+        AssignmentStatus::Accepted,
+    )
+    .await;
+
     Ok(Json(AcceptResponse {
         assignment_id,
         task_id: *assignment.task_id.as_uuid(),
@@ -690,13 +1121,15 @@ async fn reject_task(
     current_user: CurrentUser,
     Path(assignment_id): Path<Uuid>,
     Extension(pool): Extension<PgPool>,
+    Extension(clock): Extension<Arc<dyn Clock>>,
+    hub: Option<Extension<Arc<QueueUpdateHub>>>,
     Json(req): Json<RejectRequest>,
 ) -> Result<StatusCode, ApiError> {
     use glyph_db::{
         AssignmentRepository, PgAssignmentRepository, PgTaskRepository, RejectAssignment,
         TaskRepository,
     };
-    use glyph_domain::AssignmentId;
+    use glyph_domain::{AssignmentId, AssignmentStatus};
 
     let assignment_repo = PgAssignmentRepository::new(pool.clone());
     let task_repo = PgTaskRepository::new(pool);
@@ -727,16 +1160,151 @@ async fn reject_task(
         .await
         .map_err(|e| ApiError::Internal(e.into()))?;
 
-    // 3. Set task cooldown (2 minutes default)
-    let cooldown_until = chrono::Utc::now() + chrono::Duration::minutes(2);
+    // 3. Set task cooldown
+    let cooldown_until = TaskCooldown::new(REJECT_COOLDOWN).with_clock(clock).until();
     task_repo
         .set_cooldown(&assignment.task_id, cooldown_until)
         .await
         .map_err(|e| ApiError::Internal(e.into()))?;
 
+    notify_assignment_status(
+        hub.as_deref(),
+        &current_user,
+        assignment_id,
+        AssignmentStatus::Rejected,
+    )
+    .await;
+
     Ok(StatusCode::NO_CONTENT)
 }
 
+/// Most assignments one bulk reject may name
+pub const MAX_BULK_REJECT: usize = 100;
+
+/// Request to reject several assignments for the same reason
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct BulkRejectRequest {
+    pub assignment_ids: Vec<Uuid>,
+    pub reason: glyph_domain::RejectReason,
+}
+
+/// Outcome for one assignment in a bulk reject
+#[derive(Debug, Serialize, ToSchema)]
+pub struct BulkRejectResult {
+    pub assignment_id: Uuid,
+    /// `rejected`, or `not_found`, `not_owned` or `not_active` if skipped
+    pub status: String,
+}
+
+/// Response after a bulk reject
+#[derive(Debug, Serialize, ToSchema)]
+pub struct BulkRejectResponse {
+    /// How many assignments were rejected
+    pub rejected: usize,
+    /// One entry per requested assignment, in request order
+    pub results: Vec<BulkRejectResult>,
+}
+
+/// Reject several of the current user's assignments at once
+///
+/// All rejections are applied in one transaction and each task goes on the
+/// same cooldown as a single reject. Assignments that don't exist, belong to
+/// another user, or are no longer active are skipped rather than failing the
+/// request.
+#[utoipa::path(
+    post,
+    path = "/api/v1/queue/reject-bulk",
+    request_body = BulkRejectRequest,
+    responses(
+        (status = 200, description = "Per-assignment results", body = BulkRejectResponse),
+        (status = 400, description = "No assignments, or too many"),
+        (status = 401, description = "Unauthorized"),
+    ),
+    tag = "queue"
+)]
+async fn reject_bulk(
+    current_user: CurrentUser,
+    Extension(pool): Extension<PgPool>,
+    Extension(clock): Extension<Arc<dyn Clock>>,
+    hub: Option<Extension<Arc<QueueUpdateHub>>>,
+    Json(req): Json<BulkRejectRequest>,
+) -> Result<Json<BulkRejectResponse>, ApiError> {
+    use glyph_db::{BulkRejectOutcome, PgAssignmentRepository};
+    use glyph_domain::{AssignmentId, AssignmentStatus};
+
+    if req.assignment_ids.is_empty() || req.assignment_ids.len() > MAX_BULK_REJECT {
+        return Err(ApiError::bad_request(
+            "queue.bulk_reject_size",
+            format!("Provide between 1 and {MAX_BULK_REJECT} assignment IDs"),
+        ));
+    }
+
+    let mut ids: Vec<AssignmentId> = Vec::with_capacity(req.assignment_ids.len());
+    for id in &req.assignment_ids {
+        let id = AssignmentId::from_uuid(*id);
+        if !ids.contains(&id) {
+            ids.push(id);
+        }
+    }
+
+    let outcomes = PgAssignmentRepository::new(pool)
+        .reject_many_for_user(
+            &current_user.user_id,
+            &ids,
+            &serde_json::to_value(&req.reason).unwrap_or_default(),
+            TaskCooldown::new(REJECT_COOLDOWN).with_clock(clock).until(),
+        )
+        .await
+        .map_err(|e| ApiError::Internal(e.into()))?;
+
+    let mut results = Vec::with_capacity(outcomes.len());
+    for (id, outcome) in outcomes {
+        let assignment_id = *id.as_uuid();
+        let status = match outcome {
+            BulkRejectOutcome::Rejected => {
+                notify_assignment_status(
+                    hub.as_deref(),
+                    &current_user,
+                    assignment_id,
+                    AssignmentStatus::Rejected,
+                )
+                .await;
+                "rejected"
+            }
+            BulkRejectOutcome::NotFound => "not_found",
+            BulkRejectOutcome::NotOwned => "not_owned",
+            BulkRejectOutcome::NotActive => "not_active",
+        };
+        results.push(BulkRejectResult {
+            assignment_id,
+            status: status.to_string(),
+        });
+    }
+
+    Ok(Json(BulkRejectResponse {
+        rejected: results.iter().filter(|r| r.status == "rejected").count(),
+        results,
+    }))
+}
+
+/// Tell the user's other connections that an assignment changed status.
+///
+/// The hub is absent when the router runs without WebSocket support.
+async fn notify_assignment_status(
+    hub: Option<&Arc<QueueUpdateHub>>,
+    user: &CurrentUser,
+    assignment_id: Uuid,
+    status: glyph_domain::AssignmentStatus,
+) {
+    if let Some(hub) = hub {
+        let event = QueueEvent::AssignmentStatusChanged {
+            assignment_id,
+            status,
+        };
+        hub.broadcast_to_user(*user.user_id.as_uuid(), event).await;
+    }
+}
+
 /// Request to claim a task from the pool
 #[derive(Debug, Deserialize, ToSchema)]
 pub struct ClaimRequest {
@@ -759,6 +1327,7 @@ pub struct ClaimRequest {
 async fn claim_from_pool(
     current_user: CurrentUser,
     Extension(pool): Extension<PgPool>,
+    Extension(clock): Extension<Arc<dyn Clock>>,
     Json(req): Json<ClaimRequest>,
 ) -> Result<Json<AcceptResponse>, ApiError> {
     use glyph_domain::AssignmentId;
@@ -771,18 +1340,22 @@ async fn claim_from_pool(
         .await
         .map_err(|e| ApiError::Internal(e.into()))?;
 
-    // 1. Lock the task if available
+    // 1. Lock the task if available to the caller's organization
     let task: Option<TaskClaimRow> = sqlx::query_as(
         r#"
-        SELECT task_id, project_id
-        FROM tasks
-        WHERE task_id = $1
-          AND status = 'pending'
-          AND (cooldown_until IS NULL OR cooldown_until < NOW())
-        FOR UPDATE SKIP LOCKED
+        SELECT t.task_id, t.project_id
+        FROM tasks t
+        JOIN projects p ON p.project_id = t.project_id
+        WHERE t.task_id = $1
+          AND p.org_id = $2
+          AND t.status = 'pending'
+          AND (t.cooldown_until IS NULL OR t.cooldown_until < $3)
+        FOR UPDATE OF t SKIP LOCKED
         "#,
     )
     .bind(req.task_id)
+    .bind(current_user.org_id.as_uuid())
+    .bind(clock.now())
     .fetch_optional(&mut *tx)
     .await
     .map_err(|e| ApiError::Internal(e.into()))?;
@@ -849,18 +1422,343 @@ struct TaskClaimRow {
 }
 
 // =============================================================================
-// Router
+// Preview
 // =============================================================================
 
+/// Longest string kept in a preview, in characters
+const PREVIEW_MAX_STRING_CHARS: usize = 500;
+
+/// Most array items kept in a preview
+const PREVIEW_MAX_ARRAY_ITEMS: usize = 20;
+
+/// Deepest nesting kept in a preview; deeper values are dropped
+const PREVIEW_MAX_DEPTH: usize = 6;
+
+/// A task's input, trimmed for a look before accepting
+#[derive(Debug, Serialize, ToSchema)]
+pub struct QueuePreviewResponse {
+    pub assignment_id: Uuid,
+    pub task_id: Uuid,
+    pub project_id: Uuid,
+    pub step_id: String,
+    /// `input_data` without internal (`_`-prefixed) keys, truncated if large
+    pub input_data_preview: serde_json::Value,
+    /// Whether any part of `input_data_preview` was cut short
+    pub truncated: bool,
+    /// Layout the task will be annotated in, if the project has one
+    pub layout_id: Option<String>,
+}
+
+#[derive(sqlx::FromRow)]
+struct PreviewRow {
+    input_data: serde_json::Value,
+    layout_id: Option<String>,
+}
+
+/// Preview a queued task's input before accepting it
+#[utoipa::path(
+    get,
+    path = "/api/v1/queue/{assignment_id}/preview",
+    params(
+        ("assignment_id" = Uuid, Path, description = "Assignment ID"),
+    ),
+    responses(
+        (status = 200, description = "Task preview", body = QueuePreviewResponse),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Assignment belongs to another user"),
+        (status = 404, description = "Assignment not found"),
+    ),
+    tag = "queue"
+)]
+async fn preview_task(
+    current_user: CurrentUser,
+    Path(assignment_id): Path<Uuid>,
+    Extension(pool): Extension<PgPool>,
+) -> Result<Json<QueuePreviewResponse>, ApiError> {
+    use glyph_db::{AssignmentRepository, PgAssignmentRepository};
+    use glyph_domain::AssignmentId;
+
+    let assignment = PgAssignmentRepository::new(pool.clone())
+        .find_by_id(&AssignmentId::from_uuid(assignment_id))
+        .await
+        .map_err(|e| ApiError::Internal(e.into()))?
+        .ok_or_else(|| ApiError::NotFound {
+            resource_type: "assignment",
+            id: assignment_id.to_string(),
+        })?;
+
+    if assignment.user_id != current_user.user_id {
+        return Err(ApiError::Forbidden {
+            message: "Assignment belongs to another user".to_string(),
+        });
+    }
+
+    let row: PreviewRow = sqlx::query_as(
+        r#"
+        SELECT t.input_data, p.layout_id
+        FROM tasks t
+        JOIN projects p ON p.project_id = t.project_id
+        WHERE t.task_id = $1 AND p.org_id = $2
+        "#,
+    )
+    .bind(assignment.task_id.as_uuid())
+    .bind(current_user.org_id.as_uuid())
+    .fetch_optional(&pool)
+    .await
+    .map_err(|e| ApiError::Internal(e.into()))?
+    .ok_or_else(|| ApiError::NotFound {
+        resource_type: "task",
+        id: assignment.task_id.to_string(),
+    })?;
+
+    let (input_data_preview, truncated) = preview_input(&row.input_data);
+    Ok(Json(QueuePreviewResponse {
+        assignment_id,
+        task_id: *assignment.task_id.as_uuid(),
+        project_id: *assignment.project_id.as_uuid(),
+        step_id: assignment.step_id,
+        input_data_preview,
+        truncated,
+        layout_id: row.layout_id,
+    }))
+}
+
+/// Sanitize and trim task input for a preview, returning whether anything was cut
+fn preview_input(input: &serde_json::Value) -> (serde_json::Value, bool) {
+    let mut truncated = false;
+    let preview = preview_value(input, 0, &mut truncated);
+    (preview, truncated)
+}
+
+fn preview_value(
+    value: &serde_json::Value,
+    depth: usize,
+    truncated: &mut bool,
+) -> serde_json::Value {
+    use serde_json::Value;
+
+    match value {
+        Value::String(s) if s.chars().count() > PREVIEW_MAX_STRING_CHARS => {
+            *truncated = true;
+            Value::String(s.chars().take(PREVIEW_MAX_STRING_CHARS).collect())
+        }
+        Value::Array(_) | Value::Object(_) if depth >= PREVIEW_MAX_DEPTH => {
+            *truncated = true;
+            Value::Null
+        }
+        Value::Array(items) => {
+            if items.len() > PREVIEW_MAX_ARRAY_ITEMS {
+                *truncated = true;
+            }
+            Value::Array(
+                items
+                    .iter()
+                    .take(PREVIEW_MAX_ARRAY_ITEMS)
+                    .map(|item| preview_value(item, depth + 1, truncated))
+                    .collect(),
+            )
+        }
+        Value::Object(fields) => Value::Object(
+            fields
+                .iter()
+                .filter(|(key, _)| !key.starts_with('_'))
+                .map(|(key, field)| (key.clone(), preview_value(field, depth + 1, truncated)))
+                .collect(),
+        ),
+        other => other.clone(),
+    }
+}
+
+// =============================================================================
+// Export
+// =============================================================================
+
+/// Rows buffered between the database cursor and the response body
+const EXPORT_CHANNEL_CAPACITY: usize = 64;
+
+/// Export a project's active queue as CSV
+///
+/// Rows are streamed from the database as the response is written, so large
+/// queues are never held in memory. `time_in_queue` is in seconds.
+#[utoipa::path(
+    get,
+    path = "/api/v1/queue/export",
+    params(
+        ("project_id" = Uuid, Query, description = "Project to export"),
+    ),
+    responses(
+        (status = 200, description = "Queue CSV", content_type = "text/csv", body = String),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Not an admin or leader of the project's team"),
+        (status = 404, description = "Project not found"),
+    ),
+    tag = "queue"
+)]
+async fn export_queue(
+    current_user: CurrentUser,
+    Query(query): Query<QueueExportQuery>,
+    Extension(pool): Extension<PgPool>,
+) -> Result<Response, ApiError> {
+    let project_id = query.project_id.to_string();
+    let project = PgProjectRepository::new(pool.clone())
+        .in_org(current_user.org_id)
+        .find_by_id(&ProjectId::from_uuid(query.project_id))
+        .await
+        .map_err(|e| ApiError::Internal(anyhow::anyhow!("{}", e)))?
+        .ok_or_else(|| ApiError::not_found("project", &project_id))?;
+
+    let allowed = PermissionService::new(pool.clone())
+        .is_admin_or_team_leader(&current_user, project.team_id.as_ref())
+        .await
+        .map_err(|e| ApiError::Internal(e.into()))?;
+    if !allowed {
+        return Err(ApiError::forbidden(
+            "Only admins and team leaders can export a project's queue",
+        ));
+    }
+
+    let rows = stream_export_rows(pool, query.project_id);
+    let body = Body::from_stream(queue_export_csv(rows));
+
+    Ok((
+        [
+            (header::CONTENT_TYPE, "text/csv; charset=utf-8".to_string()),
+            (
+                header::CONTENT_DISPOSITION,
+                format!("attachment; filename=\"queue-{project_id}.csv\""),
+            ),
+        ],
+        body,
+    )
+        .into_response())
+}
+
+/// Stream a project's active assignments, highest priority first.
+///
+/// The query runs on a spawned task that feeds a bounded channel, so the
+/// cursor only advances as fast as the client reads.
+fn stream_export_rows(
+    pool: PgPool,
+    project_id: Uuid,
+) -> futures::channel::mpsc::Receiver<Result<QueueExportRow, sqlx::Error>> {
+    let (mut tx, rx) = futures::channel::mpsc::channel(EXPORT_CHANNEL_CAPACITY);
+
+    tokio::spawn(async move {
+        let mut rows = sqlx::query_as::<_, QueueExportRow>(
+            r#"
+            SELECT
+                ta.assignment_id,
+                ta.task_id,
+                u.display_name as user_name,
+                ta.status::text,
+                t.priority,
+                EXTRACT(EPOCH FROM (NOW() - ta.assigned_at))::bigint as time_in_queue_seconds
+            FROM task_assignments ta
+            JOIN tasks t ON ta.project_id = t.project_id AND ta.task_id = t.task_id
+            JOIN users u ON ta.user_id = u.user_id
+            WHERE ta.project_id = $1
+              AND ta.status IN ('assigned', 'accepted', 'in_progress')
+            ORDER BY t.priority DESC, ta.assigned_at ASC, ta.assignment_id ASC
+            "#,
+        )
+        .bind(project_id)
+        .fetch(&pool);
+
+        while let Some(row) = rows.next().await {
+            let failed = row.is_err();
+            // The client hung up; stop reading
+            if tx.send(row).await.is_err() || failed {
+                break;
+            }
+        }
+    });
+
+    rx
+}
+
+/// Render export rows as CSV chunks: the header line, then one line per row.
+///
+/// A database error ends the body early, which aborts the download rather
+/// than handing the client a silently truncated file.
+fn queue_export_csv<S>(rows: S) -> impl Stream<Item = Result<Vec<u8>, axum::BoxError>>
+where
+    S: Stream<Item = Result<QueueExportRow, sqlx::Error>>,
+{
+    let header = futures::stream::once(async { csv_line(QUEUE_EXPORT_HEADER) });
+    let lines = rows.map(|row| {
+        let row = row?;
+        csv_line([
+            row.assignment_id.to_string(),
+            row.task_id.to_string(),
+            row.user_name,
+            row.status,
+            row.priority.to_string(),
+            row.time_in_queue_seconds.unwrap_or(0).to_string(),
+        ])
+    });
+    header.chain(lines)
+}
+
+/// Encode one CSV record, quoting fields as needed
+fn csv_line<I, T>(fields: I) -> Result<Vec<u8>, axum::BoxError>
+where
+    I: IntoIterator<Item = T>,
+    T: AsRef<[u8]>,
+{
+    let mut writer = csv::Writer::from_writer(Vec::new());
+    writer.write_record(fields)?;
+    writer.into_inner().map_err(|e| e.into_error().into())
+}
+
+// =============================================================================
+// Router
+// =============================================================================
+
+/// OpenAPI paths and schemas served by this module
+#[derive(utoipa::OpenApi)]
+#[openapi(
+    paths(
+        get_queue,
+        get_queue_stats,
+        get_presence,
+        accept_task,
+        reject_task,
+        reject_bulk,
+        claim_from_pool,
+        preview_task,
+        export_queue
+    ),
+    components(schemas(
+        QueueEvent,
+        QueueItem,
+        ProjectQueueStats,
+        QueueStats,
+        QueueListResponse,
+        UserPresence,
+        PresenceResponse,
+        RejectRequest,
+        AcceptResponse,
+        BulkRejectRequest,
+        BulkRejectResult,
+        BulkRejectResponse,
+        ClaimRequest,
+        QueuePreviewResponse
+    ))
+)]
+pub(super) struct ApiPaths;
+
 /// Queue routes (require WebSocket hub state)
 pub fn routes() -> Router<Arc<QueueUpdateHub>> {
     Router::new()
         .route("/", get(get_queue))
         .route("/stats", get(get_queue_stats))
+        .route("/export", get(export_queue))
         .route("/presence/{project_id}", get(get_presence))
         .route("/ws", get(queue_websocket))
         .route("/{assignment_id}/accept", axum::routing::post(accept_task))
         .route("/{assignment_id}/reject", axum::routing::post(reject_task))
+        .route("/reject-bulk", axum::routing::post(reject_bulk))
+        .route("/{assignment_id}/preview", get(preview_task))
         .route("/claim", axum::routing::post(claim_from_pool))
 }
 
@@ -869,8 +1767,871 @@ pub fn routes_without_ws() -> Router {
     Router::new()
         .route("/", get(get_queue))
         .route("/stats", get(get_queue_stats))
+        .route("/export", get(export_queue))
         .route("/presence/{project_id}", get(get_presence))
         .route("/{assignment_id}/accept", axum::routing::post(accept_task))
         .route("/{assignment_id}/reject", axum::routing::post(reject_task))
+        .route("/reject-bulk", axum::routing::post(reject_bulk))
+        .route("/{assignment_id}/preview", get(preview_task))
         .route("/claim", axum::routing::post(claim_from_pool))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+
+    #[test]
+    fn test_cursor_roundtrip() {
+        let cursor = QueueCursor {
+            priority: -5,
+            assigned_at: DateTime::from_timestamp_micros(1_700_000_000_123_456).unwrap(),
+            assignment_id: Uuid::new_v4(),
+        };
+        assert_eq!(QueueCursor::decode(&cursor.encode()), Some(cursor));
+    }
+
+    #[test]
+    fn test_cursor_decode_rejects_garbage() {
+        assert_eq!(QueueCursor::decode(""), None);
+        assert_eq!(QueueCursor::decode("10:abc:def"), None);
+        assert_eq!(QueueCursor::decode("10:1700000000"), None);
+    }
+
+    #[tokio::test]
+    async fn test_cursor_with_non_default_sort_is_rejected() {
+        let pool = sqlx::postgres::PgPoolOptions::new()
+            .acquire_timeout(std::time::Duration::from_millis(50))
+            .connect_lazy("postgres://glyph@127.0.0.1:1/glyph")
+            .unwrap();
+        let cursor = QueueCursor {
+            priority: 0,
+            assigned_at: Utc::now(),
+            assignment_id: Uuid::new_v4(),
+        };
+
+        for by in ["age", "project", "skill_match", "urgency"] {
+            let query = QueueQuery {
+                sort: QueueSort {
+                    by: Some(by.to_string()),
+                    order: None,
+                },
+                cursor: Some(cursor.encode()),
+                ..Default::default()
+            };
+            let result = get_queue(
+                current_user(Uuid::new_v4()),
+                Query(query),
+                Extension(pool.clone()),
+            )
+            .await;
+            assert!(
+                matches!(
+                    result,
+                    Err(ApiError::BadRequest {
+                        code: "queue.cursor.unsupported_sort",
+                        ..
+                    })
+                ),
+                "{by}"
+            );
+        }
+    }
+
+    fn queue_row(
+        estimated_duration_seconds: Option<i32>,
+        difficulty_level: Option<&str>,
+    ) -> QueueRow {
+        QueueRow {
+            assignment_id: Uuid::from_u128(1),
+            task_id: Uuid::from_u128(2),
+            project_id: Uuid::from_u128(3),
+            project_name: "Sentiment".to_string(),
+            step_id: "annotate".to_string(),
+            step_type: None,
+            status: "assigned".to_string(),
+            priority: 0,
+            assigned_at: Utc::now(),
+            time_in_queue_seconds: Some(0),
+            estimated_duration_seconds,
+            difficulty_level: difficulty_level.map(String::from),
+        }
+    }
+
+    #[test]
+    fn test_queue_item_duration_follows_project_type() {
+        let minutes = |row| QueueItem::from(row).estimated_duration_minutes;
+        assert_eq!(minutes(queue_row(Some(90), Some("expert"))), Some(2));
+        assert_eq!(minutes(queue_row(None, Some("hard"))), Some(5));
+        assert_eq!(minutes(queue_row(None, Some("easy"))), Some(1));
+        assert_eq!(minutes(queue_row(None, None)), None);
+    }
+
+    fn export_row(user_name: &str) -> QueueExportRow {
+        QueueExportRow {
+            assignment_id: Uuid::from_u128(1),
+            task_id: Uuid::from_u128(2),
+            user_name: user_name.to_string(),
+            status: "assigned".to_string(),
+            priority: 7,
+            time_in_queue_seconds: Some(90),
+        }
+    }
+
+    async fn render(rows: Vec<Result<QueueExportRow, sqlx::Error>>) -> String {
+        let chunks: Vec<Vec<u8>> = queue_export_csv(futures::stream::iter(rows))
+            .map(|chunk| chunk.unwrap())
+            .collect()
+            .await;
+        String::from_utf8(chunks.concat()).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_export_writes_header_then_rows() {
+        let csv = render(vec![Ok(export_row("Ada"))]).await;
+        assert_eq!(
+            csv,
+            "assignment_id,task_id,user,status,priority,time_in_queue\n\
+             00000000-0000-0000-0000-000000000001,00000000-0000-0000-0000-000000000002,Ada,assigned,7,90\n"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_export_quotes_fields_with_commas() {
+        let csv = render(vec![Ok(export_row("Lovelace, Ada"))]).await;
+        assert!(csv.contains(",\"Lovelace, Ada\",assigned,"));
+    }
+
+    #[tokio::test]
+    async fn test_export_stops_on_database_error() {
+        let chunks: Vec<_> =
+            queue_export_csv(futures::stream::iter(vec![Err(sqlx::Error::RowNotFound)]))
+                .collect()
+                .await;
+        assert_eq!(chunks.len(), 2);
+        assert!(chunks[0].is_ok());
+        assert!(chunks[1].is_err());
+    }
+
+    #[tokio::test]
+    async fn test_activity_broadcasts_presence_once_per_connection() {
+        // Presence rows can't be written without a database; the broadcast
+        // must still go out.
+        let pool = sqlx::postgres::PgPoolOptions::new()
+            .acquire_timeout(std::time::Duration::from_millis(50))
+            .connect_lazy("postgres://glyph@127.0.0.1:1/glyph")
+            .unwrap();
+        let hub = QueueUpdateHub::new();
+        let clock = glyph_workflow_engine::MockClock::default();
+        let project_id = Uuid::new_v4();
+        let user_id = Uuid::new_v4();
+        let mut observer = hub.subscribe_project(project_id).await;
+        let mut active = HashSet::new();
+
+        record_activity(&hub, &pool, &clock, user_id, project_id, &mut active).await;
+        match observer.try_recv().unwrap() {
+            QueueEvent::PresenceChanged {
+                project_id: p,
+                user_id: u,
+                online,
+            } => {
+                assert_eq!((p, u, online), (project_id, user_id, true));
+            }
+            other => panic!("expected presence event, got {other:?}"),
+        }
+
+        // Further activity only refreshes the presence row
+        record_activity(&hub, &pool, &clock, user_id, project_id, &mut active).await;
+        assert!(observer.try_recv().is_err());
+        assert!(active.contains(&project_id));
+    }
+
+    #[tokio::test]
+    async fn test_offline_only_after_last_connection_closes() {
+        let pool = sqlx::postgres::PgPoolOptions::new()
+            .acquire_timeout(std::time::Duration::from_millis(50))
+            .connect_lazy("postgres://glyph@127.0.0.1:1/glyph")
+            .unwrap();
+        let hub = QueueUpdateHub::new();
+        let clock = glyph_workflow_engine::MockClock::default();
+        let project_id = Uuid::new_v4();
+        let user_id = Uuid::new_v4();
+        let mut observer = hub.subscribe_project(project_id).await;
+        let (mut first_tab, mut second_tab) = (HashSet::new(), HashSet::new());
+
+        record_activity(&hub, &pool, &clock, user_id, project_id, &mut first_tab).await;
+        record_activity(&hub, &pool, &clock, user_id, project_id, &mut second_tab).await;
+        assert!(matches!(
+            observer.try_recv().unwrap(),
+            QueueEvent::PresenceChanged { online: true, .. }
+        ));
+        assert!(observer.try_recv().is_err(), "second tab is not news");
+
+        leave_projects(&hub, user_id, first_tab).await;
+        assert!(
+            observer.try_recv().is_err(),
+            "still online in the second tab"
+        );
+
+        leave_projects(&hub, user_id, second_tab).await;
+        assert!(matches!(
+            observer.try_recv().unwrap(),
+            QueueEvent::PresenceChanged { online: false, .. }
+        ));
+    }
+
+    fn current_user(user_id: Uuid) -> CurrentUser {
+        CurrentUser {
+            user_id: glyph_domain::UserId::from_uuid(user_id),
+            auth0_id: format!("test|{user_id}"),
+            email: None,
+            email_verified: true,
+            name: None,
+            roles: vec![],
+            org_id: glyph_domain::OrgId::DEFAULT,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_status_change_reaches_only_the_owner() {
+        let hub = Arc::new(QueueUpdateHub::new());
+        let owner = Uuid::new_v4();
+        let other = Uuid::new_v4();
+        let mut owner_rx = hub.subscribe_user(owner).await;
+        let mut other_rx = hub.subscribe_user(other).await;
+        let assignment_id = Uuid::new_v4();
+
+        notify_assignment_status(
+            Some(&hub),
+            &current_user(owner),
+            assignment_id,
+            glyph_domain::AssignmentStatus::Rejected,
+        )
+        .await;
+
+        let event = serde_json::to_value(owner_rx.try_recv().unwrap()).unwrap();
+        assert_eq!(
+            event,
+            serde_json::json!({
+                "type": "assignment_status_changed",
+                "assignment_id": assignment_id,
+                "status": "rejected",
+            })
+        );
+        assert!(other_rx.try_recv().is_err());
+    }
+
+    #[tokio::test]
+    #[ignore = "requires DATABASE_URL pointing at a migrated database"]
+    async fn test_accept_publishes_accepted_status() {
+        let url = std::env::var("DATABASE_URL").expect("DATABASE_URL must be set");
+        let pool = PgPool::connect(&url).await.unwrap();
+        let (user_id, _project_id, _task_id, assignment_id) =
+            seed_assignment(&pool, "Queue Accept").await;
+
+        let hub = Arc::new(QueueUpdateHub::new());
+        let mut rx = hub.subscribe_user(user_id).await;
+
+        accept_task(
+            current_user(user_id),
+            Path(assignment_id),
+            Extension(pool),
+            Some(Extension(hub.clone())),
+        )
+        .await
+        .unwrap();
+
+        match rx.try_recv().unwrap() {
+            QueueEvent::AssignmentStatusChanged {
+                assignment_id: id,
+                status,
+            } => {
+                assert_eq!(id, assignment_id);
+                assert_eq!(status, glyph_domain::AssignmentStatus::Accepted);
+            }
+            other => panic!("expected status change, got {other:?}"),
+        }
+    }
+
+    /// Seed a user, project, task, and an `assigned` assignment.
+    ///
+    /// Returns `(user_id, project_id, task_id, assignment_id)`.
+    async fn seed_assignment(pool: &PgPool, display_name: &str) -> (Uuid, Uuid, Uuid, Uuid) {
+        let user_id = Uuid::new_v4();
+        sqlx::query(
+            r#"
+            INSERT INTO users (user_id, email, display_name, auth0_id, global_role, status)
+            VALUES ($1, $2, $3, $4, 'admin', 'active')
+            "#,
+        )
+        .bind(user_id)
+        .bind(format!("{user_id}@queue.test"))
+        .bind(display_name)
+        .bind(format!("test|{user_id}"))
+        .execute(pool)
+        .await
+        .unwrap();
+
+        let project = PgProjectRepository::new(pool.clone())
+            .create_minimal("Queue", None, &glyph_domain::UserId::from_uuid(user_id))
+            .await
+            .unwrap();
+        let project_id = *project.project_id.as_uuid();
+
+        let task_id: Uuid = sqlx::query_scalar(
+            "INSERT INTO tasks (project_id, priority, input_data) VALUES ($1, 5, '{}') RETURNING task_id",
+        )
+        .bind(project_id)
+        .fetch_one(pool)
+        .await
+        .unwrap();
+        let assignment_id: Uuid = sqlx::query_scalar(
+            r#"
+            INSERT INTO task_assignments (task_id, project_id, step_id, user_id)
+            VALUES ($1, $2, 'annotate', $3)
+            RETURNING assignment_id
+            "#,
+        )
+        .bind(task_id)
+        .bind(project_id)
+        .bind(user_id)
+        .fetch_one(pool)
+        .await
+        .unwrap();
+
+        (user_id, project_id, task_id, assignment_id)
+    }
+
+    /// Seed another `assigned` assignment for an existing user and project
+    async fn seed_extra_assignment(pool: &PgPool, user_id: Uuid, project_id: Uuid) -> Uuid {
+        let task_id: Uuid = sqlx::query_scalar(
+            "INSERT INTO tasks (project_id, priority, input_data) VALUES ($1, 5, '{}') RETURNING task_id",
+        )
+        .bind(project_id)
+        .fetch_one(pool)
+        .await
+        .unwrap();
+        sqlx::query_scalar(
+            r#"
+            INSERT INTO task_assignments (task_id, project_id, step_id, user_id)
+            VALUES ($1, $2, 'annotate', $3)
+            RETURNING assignment_id
+            "#,
+        )
+        .bind(task_id)
+        .bind(project_id)
+        .bind(user_id)
+        .fetch_one(pool)
+        .await
+        .unwrap()
+    }
+
+    #[tokio::test]
+    #[ignore = "requires DATABASE_URL pointing at a migrated database"]
+    async fn test_bulk_reject_rejects_three_and_skips_foreign() {
+        let url = std::env::var("DATABASE_URL").expect("DATABASE_URL must be set");
+        let pool = PgPool::connect(&url).await.unwrap();
+        let (user_id, project_id, _task_id, first) =
+            seed_assignment(&pool, "Queue Bulk Reject").await;
+        let second = seed_extra_assignment(&pool, user_id, project_id).await;
+        let third = seed_extra_assignment(&pool, user_id, project_id).await;
+        let (_other, _, _, foreign) = seed_assignment(&pool, "Queue Bulk Other").await;
+
+        // Frozen at whole microseconds so it survives the database round trip
+        let clock = glyph_workflow_engine::MockClock::new(
+            DateTime::from_timestamp_micros(Utc::now().timestamp_micros()).unwrap(),
+        );
+        let Json(response) = reject_bulk(
+            current_user(user_id),
+            Extension(pool.clone()),
+            Extension(Arc::new(clock.clone()) as Arc<dyn Clock>),
+            None,
+            Json(BulkRejectRequest {
+                assignment_ids: vec![first, second, third, foreign],
+                reason: glyph_domain::RejectReason::UnclearInstructions,
+            }),
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(response.rejected, 3);
+        let statuses: Vec<&str> = response.results.iter().map(|r| r.status.as_str()).collect();
+        assert_eq!(statuses, ["rejected", "rejected", "rejected", "not_owned"]);
+
+        let cooled: i64 = sqlx::query_scalar(
+            r#"
+            SELECT COUNT(*) FROM tasks t
+            JOIN task_assignments ta ON ta.task_id = t.task_id
+            WHERE ta.assignment_id = ANY($1) AND t.cooldown_until = $2
+            "#,
+        )
+        .bind(vec![first, second, third])
+        .bind(clock.now() + REJECT_COOLDOWN)
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+        assert_eq!(cooled, 3);
+
+        let foreign_status: String = sqlx::query_scalar(
+            "SELECT status::text FROM task_assignments WHERE assignment_id = $1",
+        )
+        .bind(foreign)
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+        assert_eq!(foreign_status, "assigned");
+    }
+
+    #[tokio::test]
+    #[ignore = "requires DATABASE_URL pointing at a migrated database"]
+    async fn test_cursor_pages_filtered_queue_without_duplicates_or_skips() {
+        let url = std::env::var("DATABASE_URL").expect("DATABASE_URL must be set");
+        let pool = PgPool::connect(&url).await.unwrap();
+        let (user_id, project_id, _task_id, first) = seed_assignment(&pool, "Queue Cursor").await;
+        let mut expected = vec![first];
+        for _ in 0..4 {
+            expected.push(seed_extra_assignment(&pool, user_id, project_id).await);
+        }
+        // Every assignment ties on priority and assigned_at, so only the id orders them
+        sqlx::query("UPDATE task_assignments SET assigned_at = NOW() WHERE user_id = $1")
+            .bind(user_id)
+            .execute(&pool)
+            .await
+            .unwrap();
+        expected.sort();
+
+        // Work in another project must not count towards the filtered total
+        let other_project = PgProjectRepository::new(pool.clone())
+            .create_minimal(
+                "Queue Cursor Other",
+                None,
+                &glyph_domain::UserId::from_uuid(user_id),
+            )
+            .await
+            .unwrap();
+        seed_extra_assignment(&pool, user_id, *other_project.project_id.as_uuid()).await;
+
+        let query = |cursor: Option<String>| QueueQuery {
+            filters: QueueFilters {
+                project_id: Some(project_id),
+                ..Default::default()
+            },
+            per_page: Some(2),
+            cursor,
+            ..Default::default()
+        };
+        let Json(page) = get_queue(
+            current_user(user_id),
+            Query(query(None)),
+            Extension(pool.clone()),
+        )
+        .await
+        .unwrap();
+        assert_eq!(page.total, 5);
+        let mut seen: Vec<Uuid> = page.items.iter().map(|i| i.assignment_id).collect();
+        let mut cursor = page.next_cursor;
+
+        // A higher-priority assignment arriving mid-scroll must not shift later pages
+        let urgent = seed_extra_assignment(&pool, user_id, project_id).await;
+        sqlx::query(
+            "UPDATE tasks SET priority = 50 FROM task_assignments ta \
+             WHERE ta.task_id = tasks.task_id AND ta.assignment_id = $1",
+        )
+        .bind(urgent)
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        while let Some(next) = cursor {
+            let Json(page) = get_queue(
+                current_user(user_id),
+                Query(query(Some(next))),
+                Extension(pool.clone()),
+            )
+            .await
+            .unwrap();
+            assert_eq!(page.total, 6);
+            seen.extend(page.items.iter().map(|i| i.assignment_id));
+            cursor = page.next_cursor;
+        }
+
+        assert_eq!(seen, expected);
+    }
+
+    #[tokio::test]
+    #[ignore = "requires DATABASE_URL pointing at a migrated database"]
+    async fn test_presence_is_only_visible_to_project_members() {
+        let url = std::env::var("DATABASE_URL").expect("DATABASE_URL must be set");
+        let pool = PgPool::connect(&url).await.unwrap();
+        let (member, project_id, _, _) = seed_assignment(&pool, "Presence Member").await;
+        let (outsider, _, _, _) = seed_assignment(&pool, "Presence Outsider").await;
+        let clock: Arc<dyn Clock> = Arc::new(glyph_workflow_engine::MockClock::default());
+
+        let presence = |user_id| {
+            get_presence(
+                current_user(user_id),
+                Path(project_id),
+                Extension(pool.clone()),
+                Extension(clock.clone()),
+            )
+        };
+        assert!(presence(member).await.is_ok());
+        assert!(matches!(
+            presence(outsider).await,
+            Err(ApiError::Forbidden { .. })
+        ));
+
+        let mut watchable = HashSet::new();
+        assert!(!check_watchable(&pool, &current_user(outsider), project_id, &mut watchable).await);
+        assert!(check_watchable(&pool, &current_user(member), project_id, &mut watchable).await);
+        assert!(watchable.contains(&project_id));
+    }
+
+    #[tokio::test]
+    #[ignore = "requires DATABASE_URL pointing at a migrated database"]
+    async fn test_export_includes_seeded_assignment() {
+        let url = std::env::var("DATABASE_URL").expect("DATABASE_URL must be set");
+        let pool = PgPool::connect(&url).await.unwrap();
+
+        let (_user_id, project_id, task_id, assignment_id) =
+            seed_assignment(&pool, "Queue Export").await;
+
+        let rows = stream_export_rows(pool, project_id);
+        let chunks: Vec<Vec<u8>> = queue_export_csv(rows)
+            .map(|chunk| chunk.unwrap())
+            .collect()
+            .await;
+        let csv = String::from_utf8(chunks.concat()).unwrap();
+        let mut lines = csv.lines();
+
+        assert_eq!(
+            lines.next(),
+            Some("assignment_id,task_id,user,status,priority,time_in_queue")
+        );
+        let row = lines.next().unwrap();
+        assert!(row.starts_with(&format!(
+            "{assignment_id},{task_id},Queue Export,assigned,5,"
+        )));
+        assert_eq!(lines.next(), None);
+    }
+
+    #[test]
+    fn test_preview_truncates_large_input() {
+        let long_text = "x".repeat(PREVIEW_MAX_STRING_CHARS + 100);
+        let input = serde_json::json!({
+            "text": long_text,
+            "tokens": (0..50).collect::<Vec<_>>(),
+            "source": "upload",
+            "_gold_expected": { "label": "cat" },
+        });
+
+        let (preview, truncated) = preview_input(&input);
+        assert!(truncated);
+        assert_eq!(
+            preview["text"].as_str().unwrap().chars().count(),
+            PREVIEW_MAX_STRING_CHARS
+        );
+        assert_eq!(
+            preview["tokens"].as_array().unwrap().len(),
+            PREVIEW_MAX_ARRAY_ITEMS
+        );
+        assert_eq!(preview["source"], "upload");
+        assert!(preview.get("_gold_expected").is_none());
+
+        let (small, truncated) = preview_input(&serde_json::json!({"text": "short"}));
+        assert!(!truncated);
+        assert_eq!(small, serde_json::json!({"text": "short"}));
+    }
+
+    #[tokio::test]
+    #[ignore = "requires DATABASE_URL pointing at a migrated database"]
+    async fn test_preview_of_another_users_assignment_is_forbidden() {
+        let url = std::env::var("DATABASE_URL").expect("DATABASE_URL must be set");
+        let pool = PgPool::connect(&url).await.unwrap();
+        let (_user_id, _project_id, _task_id, assignment_id) =
+            seed_assignment(&pool, "Queue Preview").await;
+
+        let result = preview_task(
+            current_user(Uuid::new_v4()),
+            Path(assignment_id),
+            Extension(pool),
+        )
+        .await;
+        assert!(matches!(result, Err(ApiError::Forbidden { .. })));
+    }
+
+    /// Seed an organization other than the default one
+    async fn seed_org(pool: &PgPool) -> glyph_domain::OrgId {
+        let org_id = glyph_domain::OrgId::new();
+        sqlx::query("INSERT INTO organizations (org_id, name, slug) VALUES ($1, $2, $2)")
+            .bind(org_id.as_uuid())
+            .bind(org_id.to_string())
+            .execute(pool)
+            .await
+            .unwrap();
+        org_id
+    }
+
+    #[tokio::test]
+    #[ignore = "requires DATABASE_URL pointing at a migrated database"]
+    async fn test_cannot_claim_or_preview_another_orgs_task() {
+        let url = std::env::var("DATABASE_URL").expect("DATABASE_URL must be set");
+        let pool = PgPool::connect(&url).await.unwrap();
+        let (user_id, project_id, _task_id, assignment_id) =
+            seed_assignment(&pool, "Queue Other Org").await;
+
+        // Move the seeded project into another organization
+        let other_org = seed_org(&pool).await;
+        sqlx::query("UPDATE projects SET org_id = $2 WHERE project_id = $1")
+            .bind(project_id)
+            .bind(other_org.as_uuid())
+            .execute(&pool)
+            .await
+            .unwrap();
+        let pending_task: Uuid = sqlx::query_scalar(
+            "INSERT INTO tasks (project_id, priority, input_data) VALUES ($1, 5, '{\"secret\": 1}') RETURNING task_id",
+        )
+        .bind(project_id)
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+
+        let claim = claim_from_pool(
+            current_user(user_id),
+            Extension(pool.clone()),
+            Extension(glyph_workflow_engine::system_clock()),
+            Json(ClaimRequest {
+                task_id: pending_task,
+                step_id: "annotate".to_string(),
+            }),
+        )
+        .await;
+        assert!(matches!(claim, Err(ApiError::Conflict { .. })));
+        let claimed: i64 =
+            sqlx::query_scalar("SELECT COUNT(*) FROM task_assignments WHERE task_id = $1")
+                .bind(pending_task)
+                .fetch_one(&pool)
+                .await
+                .unwrap();
+        assert_eq!(claimed, 0);
+
+        let preview =
+            preview_task(current_user(user_id), Path(assignment_id), Extension(pool)).await;
+        assert!(matches!(preview, Err(ApiError::NotFound { .. })));
+    }
+
+    #[test]
+    fn test_skill_match_order_falls_back_to_priority() {
+        let order = queue_order_by(Some("skill_match"), None);
+        assert!(order.contains("project_type_skill_requirements"));
+        assert!(order.ends_with("DESC, t.priority DESC, ta.assigned_at ASC"));
+        assert_eq!(
+            queue_order_by(Some("unknown"), None),
+            "t.priority DESC, ta.assigned_at ASC, ta.assignment_id ASC"
+        );
+    }
+
+    /// Give the project a type requiring a new skill, which the user was
+    /// certified in at `level` the given number of days ago
+    async fn require_skill(
+        pool: &PgPool,
+        user_id: Uuid,
+        project_id: Uuid,
+        level: &str,
+        certified_days_ago: i32,
+    ) {
+        let skill_id = format!("skill-{}", Uuid::new_v4().simple());
+        sqlx::query("INSERT INTO skill_types (skill_id, name) VALUES ($1, $1)")
+            .bind(&skill_id)
+            .execute(pool)
+            .await
+            .unwrap();
+        let project_type_id: Uuid = sqlx::query_scalar(
+            "INSERT INTO project_types (name) VALUES ($1) RETURNING project_type_id",
+        )
+        .bind(format!("Skill Match {skill_id}"))
+        .fetch_one(pool)
+        .await
+        .unwrap();
+        sqlx::query(
+            "INSERT INTO project_type_skill_requirements (project_type_id, skill_id) VALUES ($1, $2)",
+        )
+        .bind(project_type_id)
+        .bind(&skill_id)
+        .execute(pool)
+        .await
+        .unwrap();
+        sqlx::query("UPDATE projects SET project_type_id = $2 WHERE project_id = $1")
+            .bind(project_id)
+            .bind(project_type_id)
+            .execute(pool)
+            .await
+            .unwrap();
+        sqlx::query(
+            r#"
+            INSERT INTO user_skills (user_id, skill_id, proficiency_level, certified_at)
+            VALUES ($1, $2, $3, NOW() - make_interval(days => $4))
+            "#,
+        )
+        .bind(user_id)
+        .bind(&skill_id)
+        .bind(level)
+        .bind(certified_days_ago)
+        .execute(pool)
+        .await
+        .unwrap();
+    }
+
+    #[tokio::test]
+    #[ignore = "requires DATABASE_URL pointing at a migrated database"]
+    async fn test_skill_match_sorts_better_matched_task_first() {
+        let url = std::env::var("DATABASE_URL").expect("DATABASE_URL must be set");
+        let pool = PgPool::connect(&url).await.unwrap();
+        let (user_id, matched_project, _task_id, matched) =
+            seed_assignment(&pool, "Queue Skill Match").await;
+
+        // The matched project requires a skill the user holds at expert
+        require_skill(&pool, user_id, matched_project, "expert", 0).await;
+
+        // A higher-priority task on a project with no skill requirements
+        let unmatched_project = *PgProjectRepository::new(pool.clone())
+            .create_minimal("Queue", None, &glyph_domain::UserId::from_uuid(user_id))
+            .await
+            .unwrap()
+            .project_id
+            .as_uuid();
+        let unmatched = seed_extra_assignment(&pool, user_id, unmatched_project).await;
+        sqlx::query(
+            r#"
+            UPDATE tasks SET priority = 9
+            WHERE task_id = (SELECT task_id FROM task_assignments WHERE assignment_id = $1)
+            "#,
+        )
+        .bind(unmatched)
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        let Json(response) = get_queue(
+            current_user(user_id),
+            Query(QueueQuery {
+                sort: QueueSort {
+                    by: Some("skill_match".to_string()),
+                    order: None,
+                },
+                ..Default::default()
+            }),
+            Extension(pool),
+        )
+        .await
+        .unwrap();
+
+        let order: Vec<Uuid> = response.items.iter().map(|i| i.assignment_id).collect();
+        assert_eq!(order, [matched, unmatched]);
+    }
+
+    #[tokio::test]
+    #[ignore = "requires DATABASE_URL pointing at a migrated database"]
+    async fn test_skill_match_decays_idle_proficiency() {
+        let url = std::env::var("DATABASE_URL").expect("DATABASE_URL must be set");
+        let pool = PgPool::connect(&url).await.unwrap();
+        let (user_id, idle_project, _task_id, idle) =
+            seed_assignment(&pool, "Queue Skill Decay").await;
+        let recent_project = *PgProjectRepository::new(pool.clone())
+            .create_minimal("Queue", None, &glyph_domain::UserId::from_uuid(user_id))
+            .await
+            .unwrap()
+            .project_id
+            .as_uuid();
+        let recent = seed_extra_assignment(&pool, user_id, recent_project).await;
+
+        // An expert untouched for two years ranks below a fresh novice
+        require_skill(&pool, user_id, idle_project, "expert", 730).await;
+        require_skill(&pool, user_id, recent_project, "novice", 0).await;
+
+        let Json(response) = get_queue(
+            current_user(user_id),
+            Query(QueueQuery {
+                sort: QueueSort {
+                    by: Some("skill_match".to_string()),
+                    order: None,
+                },
+                ..Default::default()
+            }),
+            Extension(pool),
+        )
+        .await
+        .unwrap();
+
+        let order: Vec<Uuid> = response.items.iter().map(|i| i.assignment_id).collect();
+        assert_eq!(order, [recent, idle]);
+    }
+
+    #[test]
+    fn test_urgency_order_boosts_by_deadline() {
+        let order = queue_order_by(Some("urgency"), None);
+        assert!(order.starts_with("(t.priority + CASE"));
+        assert!(order.contains("WHEN p.deadline IS NULL THEN 0"));
+        assert!(order.ends_with("DESC, ta.assigned_at ASC"));
+    }
+
+    #[tokio::test]
+    #[ignore = "requires DATABASE_URL pointing at a migrated database"]
+    async fn test_urgency_sorts_near_deadline_task_first() {
+        let url = std::env::var("DATABASE_URL").expect("DATABASE_URL must be set");
+        let pool = PgPool::connect(&url).await.unwrap();
+        let (user_id, far_project, _task_id, far) = seed_assignment(&pool, "Queue Urgency").await;
+        let near_project = *PgProjectRepository::new(pool.clone())
+            .create_minimal("Queue", None, &glyph_domain::UserId::from_uuid(user_id))
+            .await
+            .unwrap()
+            .project_id
+            .as_uuid();
+        let near = seed_extra_assignment(&pool, user_id, near_project).await;
+
+        // Same base priority; only the deadlines differ
+        for (project_id, deadline) in [
+            (far_project, Utc::now() + Duration::days(30)),
+            (near_project, Utc::now() + Duration::hours(12)),
+        ] {
+            sqlx::query("UPDATE projects SET deadline = $2 WHERE project_id = $1")
+                .bind(project_id)
+                .bind(deadline)
+                .execute(&pool)
+                .await
+                .unwrap();
+            sqlx::query("UPDATE tasks SET priority = 5 WHERE project_id = $1")
+                .bind(project_id)
+                .execute(&pool)
+                .await
+                .unwrap();
+        }
+
+        let Json(response) = get_queue(
+            current_user(user_id),
+            Query(QueueQuery {
+                sort: QueueSort {
+                    by: Some("urgency".to_string()),
+                    order: None,
+                },
+                ..Default::default()
+            }),
+            Extension(pool),
+        )
+        .await
+        .unwrap();
+
+        let order: Vec<Uuid> = response.items.iter().map(|i| i.assignment_id).collect();
+        assert_eq!(order, [near, far]);
+    }
+
+    #[test]
+    fn test_presence_cutoff_follows_clock() {
+        let clock = glyph_workflow_engine::MockClock::default();
+        let start = clock.now();
+        assert_eq!(presence_cutoff(&clock), start - PRESENCE_WINDOW);
+
+        clock.advance(Duration::minutes(10));
+        assert_eq!(presence_cutoff(&clock), start + Duration::minutes(5));
+    }
+}
@@ -216,6 +216,20 @@ async fn add_comment(
 // Router
 // =============================================================================
 
+/// OpenAPI paths and schemas served by this module
+#[derive(utoipa::OpenApi)]
+#[openapi(
+    paths(submit_review, list_reviews, add_comment),
+    components(schemas(
+        SubmitReviewRequest,
+        AddCommentRequest,
+        ReviewResponse,
+        ReviewCommentResponse,
+        ReviewListResponse
+    ))
+)]
+pub(super) struct ApiPaths;
+
 /// Review routes nested under /tasks/{task_id}/reviews
 pub fn routes() -> Router {
     Router::new()
@@ -407,6 +407,28 @@ pub async fn list_user_skills(
 // Routes
 // =============================================================================
 
+/// OpenAPI paths and schemas served by this module
+#[derive(utoipa::OpenApi)]
+#[openapi(
+    paths(
+        create_skill_type,
+        list_skill_types,
+        get_skill_type,
+        update_skill_type,
+        certify_user_skill,
+        revoke_user_skill,
+        list_user_skills
+    ),
+    components(schemas(
+        CreateSkillTypeRequest,
+        UpdateSkillTypeRequest,
+        SkillTypeResponse,
+        CertifySkillRequest,
+        UserSkillResponse
+    ))
+)]
+pub(super) struct ApiPaths;
+
 /// Skill type routes (/api/v1/skills/types)
 pub fn skill_type_routes() -> axum::Router {
     use axum::routing::get;
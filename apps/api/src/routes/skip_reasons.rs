@@ -231,6 +231,25 @@ async fn skip_task(
 // Routers
 // =============================================================================
 
+/// OpenAPI paths and schemas served by this module
+#[derive(utoipa::OpenApi)]
+#[openapi(
+    paths(
+        list_skip_reasons,
+        create_skip_reason,
+        deactivate_skip_reason,
+        skip_task
+    ),
+    components(schemas(
+        CreateSkipReasonRequest,
+        SkipTaskRequest,
+        SkipReasonResponse,
+        SkipReasonListResponse,
+        TaskSkipResponse
+    ))
+)]
+pub(super) struct ApiPaths;
+
 /// Project skip reason routes (/projects/{project_id}/skip-reasons)
 pub fn project_routes() -> Router {
     Router::new()
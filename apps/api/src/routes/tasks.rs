@@ -1,9 +1,11 @@
-//! Task CRUD endpoints
+//! Task CRUD and workflow progress endpoints
+
+use std::sync::Arc;
 
 use axum::{
     extract::{Path, Query},
     http::StatusCode,
-    routing::get,
+    routing::{get, post},
     Extension, Json, Router,
 };
 use serde::{Deserialize, Serialize};
@@ -11,9 +13,18 @@ use sqlx::PgPool;
 use utoipa::ToSchema;
 use uuid::Uuid;
 
-use glyph_db::{NewTask, Pagination, PgTaskRepository, TaskRepository, TaskUpdate as DbTaskUpdate};
-use glyph_domain::{ProjectId, Task, TaskId, TaskStatus};
+use glyph_db::{
+    AnnotationRepository, AuditAction, AuditActorType, AuditEvent, AuditWriter, FindTaskError,
+    NewTask, Pagination, PgAnnotationRepository, PgProjectRepository, PgTaskRepository,
+    ProjectRepository, TaskRepository, TaskUpdate as DbTaskUpdate,
+};
+use glyph_domain::{Project, ProjectId, Task, TaskId, TaskStatus, UserId};
+use glyph_workflow_engine::events::StateRebuilder;
+use glyph_workflow_engine::{EventStore, PgEventStore, StoredEvent, WorkflowStateManager};
 
+use crate::extractors::{CurrentUser, RequireAdmin};
+use crate::pagination::{PageResource, PaginationPolicy};
+use crate::services::PermissionService;
 use crate::ApiError;
 
 // =============================================================================
@@ -26,6 +37,9 @@ pub struct CreateTaskRequest {
     pub input_data: serde_json::Value,
     pub priority: Option<i32>,
     pub metadata: Option<serde_json::Value>,
+    /// Known answer; marks the task as a gold task for quality checks.
+    /// Never returned in task responses.
+    pub gold_output: Option<serde_json::Value>,
 }
 
 /// Request to update a task
@@ -86,6 +100,91 @@ pub struct TaskListResponse {
     pub total_pages: i32,
 }
 
+/// Status of a single step in a task's workflow
+#[derive(Debug, Serialize, ToSchema)]
+pub struct WorkflowStepStatus {
+    pub step_id: String,
+    pub status: String,
+    /// Full step state (timestamps, result, skip reason, ...)
+    pub state: serde_json::Value,
+}
+
+/// One event in a task's workflow history
+#[derive(Debug, Serialize, ToSchema)]
+pub struct WorkflowTimelineEntry {
+    /// Position in the task's event stream
+    pub sequence: u64,
+    pub event_type: String,
+    pub step_id: Option<String>,
+    /// User who caused the event; null for system events
+    pub actor: Option<String>,
+    pub occurred_at: String,
+    pub details: serde_json::Value,
+}
+
+/// Task workflow progress response
+#[derive(Debug, Serialize, ToSchema)]
+pub struct TaskWorkflowResponse {
+    pub task_id: String,
+    /// Current step ID (null once the workflow is complete)
+    pub current_step: Option<String>,
+    pub is_complete: bool,
+    /// Steps the workflow has reached, in the order they were first reached
+    pub steps: Vec<WorkflowStepStatus>,
+    /// Workflow events in stream order
+    pub timeline: Vec<WorkflowTimelineEntry>,
+}
+
+/// Result of clearing a task's cooldown
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ClearCooldownResponse {
+    pub task_id: String,
+    /// When the cleared cooldown would have ended; null if there was none
+    pub cleared_cooldown_until: Option<String>,
+}
+
+/// Query parameters for comparing two annotators' submissions
+#[derive(Debug, Deserialize)]
+pub struct AnnotationDiffQuery {
+    pub user_a: Uuid,
+    pub user_b: Uuid,
+}
+
+/// How a field differs between two submissions
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum FieldChange {
+    /// Only user B's submission has the field
+    Added,
+    /// Only user A's submission has the field
+    Removed,
+    /// Both have the field with different values
+    Changed,
+}
+
+/// One differing field between two submissions
+#[derive(Debug, Serialize, ToSchema)]
+pub struct FieldDiff {
+    pub field: String,
+    pub change: FieldChange,
+    /// User A's value; null when the field was added
+    pub a: Option<serde_json::Value>,
+    /// User B's value; null when the field was removed
+    pub b: Option<serde_json::Value>,
+}
+
+/// Field-by-field diff of two annotators' submissions on a task
+#[derive(Debug, Serialize, ToSchema)]
+pub struct AnnotationDiffResponse {
+    pub task_id: String,
+    pub user_a: String,
+    pub user_b: String,
+    pub annotation_a: String,
+    pub annotation_b: String,
+    /// Differing fields, sorted by name; empty when the submissions agree
+    pub fields: Vec<FieldDiff>,
+}
+
 // =============================================================================
 // Route Handlers
 // =============================================================================
@@ -104,16 +203,20 @@ pub struct TaskListResponse {
 )]
 async fn create_task(
     Path(project_id): Path<Uuid>,
+    current_user: CurrentUser,
     Extension(pool): Extension<PgPool>,
     Json(req): Json<CreateTaskRequest>,
 ) -> Result<(StatusCode, Json<TaskResponse>), ApiError> {
+    let project_id = ProjectId::from_uuid(project_id);
+    ensure_project_in_org(&pool, &current_user, &project_id).await?;
     let repo = PgTaskRepository::new(pool);
 
     let new_task = NewTask {
-        project_id: ProjectId::from_uuid(project_id),
+        project_id,
         input_data: req.input_data,
         priority: req.priority,
         metadata: req.metadata,
+        gold_output: req.gold_output,
     };
 
     let task = repo.create(&new_task).await.map_err(|e| match e {
@@ -139,18 +242,22 @@ async fn create_task(
     ),
     responses(
         (status = 200, description = "Task list", body = TaskListResponse),
+        (status = 404, description = "Project not found"),
     ),
     tag = "tasks"
 )]
 async fn list_project_tasks(
     Path(project_id): Path<Uuid>,
     Query(query): Query<ListTasksQuery>,
+    current_user: CurrentUser,
     Extension(pool): Extension<PgPool>,
 ) -> Result<Json<TaskListResponse>, ApiError> {
+    let project_id = ProjectId::from_uuid(project_id);
+    ensure_project_in_org(&pool, &current_user, &project_id).await?;
     let repo = PgTaskRepository::new(pool);
 
     let page = query.page.unwrap_or(1).max(1);
-    let per_page = query.per_page.unwrap_or(20).clamp(1, 100);
+    let per_page = PaginationPolicy::for_resource(PageResource::Tasks).per_page(query.per_page);
     let offset = ((page - 1) * per_page) as i64;
 
     let pagination = Pagination {
@@ -160,8 +267,6 @@ async fn list_project_tasks(
         sort_order: glyph_db::SortOrder::Desc,
     };
 
-    let project_id = ProjectId::from_uuid(project_id);
-
     let result = if let Some(status_str) = &query.status {
         let status = parse_task_status(status_str);
         repo.list_by_project_with_status(&project_id, status, pagination)
@@ -199,25 +304,10 @@ async fn list_project_tasks(
 )]
 async fn get_task(
     Path(task_id): Path<Uuid>,
+    current_user: CurrentUser,
     Extension(pool): Extension<PgPool>,
 ) -> Result<Json<TaskResponse>, ApiError> {
-    let repo = PgTaskRepository::new(pool);
-
-    let task_id = TaskId::from_uuid(task_id);
-    let task = repo
-        .find_by_id(&task_id)
-        .await
-        .map_err(|e| match e {
-            glyph_db::FindTaskError::NotFound(id) => ApiError::NotFound {
-                resource_type: "task",
-                id: id.to_string(),
-            },
-            glyph_db::FindTaskError::Database(e) => ApiError::Internal(e.into()),
-        })?
-        .ok_or_else(|| ApiError::NotFound {
-            resource_type: "task",
-            id: task_id.to_string(),
-        })?;
+    let task = find_task_in_org(&pool, &current_user, &TaskId::from_uuid(task_id)).await?;
 
     Ok(Json(TaskResponse::from(task)))
 }
@@ -238,12 +328,14 @@ async fn get_task(
 )]
 async fn update_task(
     Path(task_id): Path<Uuid>,
+    current_user: CurrentUser,
     Extension(pool): Extension<PgPool>,
     Json(req): Json<UpdateTaskRequest>,
 ) -> Result<Json<TaskResponse>, ApiError> {
+    let task_id = TaskId::from_uuid(task_id);
+    find_task_in_org(&pool, &current_user, &task_id).await?;
     let repo = PgTaskRepository::new(pool);
 
-    let task_id = TaskId::from_uuid(task_id);
     let update = DbTaskUpdate {
         status: req.status.as_deref().map(parse_task_status),
         priority: req.priority,
@@ -280,11 +372,13 @@ async fn update_task(
 )]
 async fn delete_task(
     Path(task_id): Path<Uuid>,
+    current_user: CurrentUser,
     Extension(pool): Extension<PgPool>,
 ) -> Result<StatusCode, ApiError> {
+    let task_id = TaskId::from_uuid(task_id);
+    find_task_in_org(&pool, &current_user, &task_id).await?;
     let repo = PgTaskRepository::new(pool);
 
-    let task_id = TaskId::from_uuid(task_id);
     repo.soft_delete(&task_id).await.map_err(|e| match e {
         glyph_db::UpdateTaskError::NotFound(id) => ApiError::NotFound {
             resource_type: "task",
@@ -300,6 +394,168 @@ async fn delete_task(
     Ok(StatusCode::NO_CONTENT)
 }
 
+/// Clear a task's cooldown (admin only)
+///
+/// Makes a task that was rejected recently claimable again right away.
+#[utoipa::path(
+    post,
+    path = "/api/v1/tasks/{task_id}/clear-cooldown",
+    params(
+        ("task_id" = Uuid, Path, description = "Task ID"),
+    ),
+    responses(
+        (status = 200, description = "Cooldown cleared", body = ClearCooldownResponse),
+        (status = 403, description = "Requires admin role"),
+        (status = 404, description = "Task not found"),
+    ),
+    tag = "tasks"
+)]
+async fn clear_task_cooldown(
+    Path(task_id): Path<Uuid>,
+    Extension(pool): Extension<PgPool>,
+    RequireAdmin(admin): RequireAdmin,
+) -> Result<Json<ClearCooldownResponse>, ApiError> {
+    let task_id = TaskId::from_uuid(task_id);
+    find_task_in_org(&pool, &admin, &task_id).await?;
+    let repo = PgTaskRepository::new(pool.clone());
+
+    let cooldown_until = repo.get_cooldown(&task_id).await.map_err(|e| match e {
+        FindTaskError::NotFound(id) => ApiError::not_found("task", id.to_string()),
+        FindTaskError::Database(e) => ApiError::Internal(e.into()),
+    })?;
+    repo.clear_cooldown(&task_id).await.map_err(|e| match e {
+        glyph_db::UpdateTaskError::NotFound(id) => ApiError::not_found("task", id.to_string()),
+        glyph_db::UpdateTaskError::InvalidStatusTransition => ApiError::BadRequest {
+            code: "task.invalid_status_transition",
+            message: "Invalid status transition".to_string(),
+        },
+        glyph_db::UpdateTaskError::Database(e) => ApiError::Internal(e.into()),
+    })?;
+
+    if let Some(until) = cooldown_until {
+        AuditWriter::new(pool)
+            .record_best_effort(AuditEvent {
+                entity_type: "task",
+                entity_id: task_id.to_string(),
+                action: AuditAction::Update,
+                actor_id: admin.user_id.to_string(),
+                actor_type: AuditActorType::User,
+                data_snapshot: serde_json::json!({ "cooldown_until": null }),
+                changes: Some(serde_json::json!({
+                    "cooldown_until": { "old": until.to_rfc3339(), "new": null },
+                })),
+                request_id: None,
+            })
+            .await;
+    }
+
+    Ok(Json(ClearCooldownResponse {
+        task_id: task_id.to_string(),
+        cleared_cooldown_until: cooldown_until.map(|t| t.to_rfc3339()),
+    }))
+}
+
+/// Get a task's workflow progress and event timeline
+#[utoipa::path(
+    get,
+    path = "/api/v1/tasks/{task_id}/workflow",
+    params(
+        ("task_id" = Uuid, Path, description = "Task ID"),
+    ),
+    responses(
+        (status = 200, description = "Workflow progress", body = TaskWorkflowResponse),
+        (status = 404, description = "Task not found or no workflow started"),
+    ),
+    tag = "tasks"
+)]
+async fn get_task_workflow(
+    Path(task_id): Path<Uuid>,
+    current_user: CurrentUser,
+    Extension(pool): Extension<PgPool>,
+) -> Result<Json<TaskWorkflowResponse>, ApiError> {
+    let task_id = TaskId::from_uuid(task_id);
+    find_task_in_org(&pool, &current_user, &task_id).await?;
+
+    // Workflow streams are keyed by task ID
+    let store = Arc::new(PgEventStore::new(pool));
+    let events = store
+        .load_events(*task_id.as_uuid(), 0)
+        .await
+        .map_err(|e| ApiError::Internal(e.into()))?;
+    let state = StateRebuilder::new(store)
+        .replay_events(&events)
+        .map_err(|e| ApiError::Internal(e.into()))?
+        .ok_or_else(|| ApiError::not_found("workflow", task_id.to_string()))?;
+
+    Ok(Json(TaskWorkflowResponse {
+        task_id: task_id.to_string(),
+        current_step: state.current_step().map(str::to_string),
+        is_complete: state.is_complete(),
+        steps: step_statuses(&events, &state),
+        timeline: timeline(&events),
+    }))
+}
+
+/// Compare two annotators' submissions on a task
+#[utoipa::path(
+    get,
+    path = "/api/v1/tasks/{task_id}/diff",
+    params(
+        ("task_id" = Uuid, Path, description = "Task ID"),
+        ("user_a" = Uuid, Query, description = "First annotator"),
+        ("user_b" = Uuid, Query, description = "Second annotator"),
+    ),
+    responses(
+        (status = 200, description = "Submission diff", body = AnnotationDiffResponse),
+        (status = 403, description = "Caller is not a reviewer, adjudicator or team leader"),
+        (status = 404, description = "Task not found, or either user has no submission on it"),
+    ),
+    tag = "tasks"
+)]
+async fn get_annotation_diff(
+    Path(task_id): Path<Uuid>,
+    Query(query): Query<AnnotationDiffQuery>,
+    current_user: CurrentUser,
+    Extension(pool): Extension<PgPool>,
+) -> Result<Json<AnnotationDiffResponse>, ApiError> {
+    let task_id = TaskId::from_uuid(task_id);
+    let task = find_task_in_org(&pool, &current_user, &task_id).await?;
+    let project = ensure_project_in_org(&pool, &current_user, &task.project_id).await?;
+    let allowed = PermissionService::new(pool.clone())
+        .can_review_task(&current_user, &project, &task_id)
+        .await
+        .map_err(|e| ApiError::Internal(e.into()))?;
+    if !allowed {
+        return Err(ApiError::forbidden(
+            "Only reviewers, adjudicators and team leaders can compare submissions",
+        ));
+    }
+    let repo = PgAnnotationRepository::new(pool);
+
+    let mut submissions = Vec::with_capacity(2);
+    for user_id in [query.user_a, query.user_b] {
+        let user_id = UserId::from_uuid(user_id);
+        let submission = repo
+            .find_latest_submission(&task_id, &user_id)
+            .await
+            .map_err(|e| ApiError::Internal(e.into()))?
+            .ok_or_else(|| ApiError::not_found("submission", format!("{task_id}:{user_id}")))?;
+        submissions.push(submission);
+    }
+    let [a, b]: [_; 2] = submissions
+        .try_into()
+        .map_err(|_| ApiError::Internal(anyhow::anyhow!("expected two submissions")))?;
+
+    Ok(Json(AnnotationDiffResponse {
+        task_id: task_id.to_string(),
+        user_a: a.user_id.to_string(),
+        user_b: b.user_id.to_string(),
+        annotation_a: a.annotation_id.to_string(),
+        annotation_b: b.annotation_id.to_string(),
+        fields: diff_fields(&a.data, &b.data),
+    }))
+}
+
 /// List all tasks (global)
 async fn list_tasks(
     Query(query): Query<ListTasksQuery>,
@@ -307,7 +563,7 @@ async fn list_tasks(
 ) -> Result<Json<serde_json::Value>, ApiError> {
     // For now, return an empty list - full implementation would need a list_all method
     let page = query.page.unwrap_or(1);
-    let per_page = query.per_page.unwrap_or(20);
+    let per_page = PaginationPolicy::for_resource(PageResource::Tasks).per_page(query.per_page);
 
     Ok(Json(serde_json::json!({
         "items": [],
@@ -322,12 +578,46 @@ async fn list_tasks(
 // Router
 // =============================================================================
 
+/// OpenAPI paths and schemas served by this module
+#[derive(utoipa::OpenApi)]
+#[openapi(
+    paths(
+        create_task,
+        list_project_tasks,
+        get_task,
+        update_task,
+        delete_task,
+        get_task_workflow,
+        get_annotation_diff,
+        clear_task_cooldown
+    ),
+    components(schemas(
+        CreateTaskRequest,
+        UpdateTaskRequest,
+        TaskResponse,
+        TaskListResponse,
+        WorkflowStepStatus,
+        WorkflowTimelineEntry,
+        TaskWorkflowResponse,
+        FieldChange,
+        FieldDiff,
+        AnnotationDiffResponse,
+        ClearCooldownResponse
+    ))
+)]
+pub(super) struct ApiPaths;
+
 /// Global task routes (/tasks)
 pub fn routes() -> Router {
-    Router::new().route("/", get(list_tasks)).route(
-        "/{task_id}",
-        get(get_task).patch(update_task).delete(delete_task),
-    )
+    Router::new()
+        .route("/", get(list_tasks))
+        .route(
+            "/{task_id}",
+            get(get_task).patch(update_task).delete(delete_task),
+        )
+        .route("/{task_id}/workflow", get(get_task_workflow))
+        .route("/{task_id}/diff", get(get_annotation_diff))
+        .route("/{task_id}/clear-cooldown", post(clear_task_cooldown))
 }
 
 /// Project-scoped task routes (/projects/{project_id}/tasks)
@@ -339,6 +629,44 @@ pub fn project_routes() -> Router {
 // Helpers
 // =============================================================================
 
+/// Load a project in the caller's organization; 404 for any other
+async fn ensure_project_in_org(
+    pool: &PgPool,
+    current_user: &CurrentUser,
+    project_id: &ProjectId,
+) -> Result<Project, ApiError> {
+    PgProjectRepository::new(pool.clone())
+        .in_org(current_user.org_id)
+        .find_by_id(project_id)
+        .await
+        .map_err(|e| ApiError::Internal(anyhow::anyhow!("{}", e)))?
+        .ok_or_else(|| ApiError::not_found("project", project_id.to_string()))
+}
+
+/// Load a task whose project is in the caller's organization; tasks of other
+/// organizations are reported as missing
+async fn find_task_in_org(
+    pool: &PgPool,
+    current_user: &CurrentUser,
+    task_id: &TaskId,
+) -> Result<Task, ApiError> {
+    let task = PgTaskRepository::new(pool.clone())
+        .find_by_id(task_id)
+        .await
+        .map_err(|e| match e {
+            FindTaskError::NotFound(id) => ApiError::not_found("task", id.to_string()),
+            FindTaskError::Database(e) => ApiError::Internal(e.into()),
+        })?
+        .ok_or_else(|| ApiError::not_found("task", task_id.to_string()))?;
+    ensure_project_in_org(pool, current_user, &task.project_id)
+        .await
+        .map_err(|e| match e {
+            ApiError::NotFound { .. } => ApiError::not_found("task", task_id.to_string()),
+            e => e,
+        })?;
+    Ok(task)
+}
+
 fn parse_task_status(s: &str) -> TaskStatus {
     match s.to_lowercase().as_str() {
         "pending" => TaskStatus::Pending,
@@ -353,3 +681,287 @@ fn parse_task_status(s: &str) -> TaskStatus {
         _ => TaskStatus::Pending,
     }
 }
+
+/// Diff two submissions' top-level fields using the audit change machinery
+fn diff_fields(a: &serde_json::Value, b: &serde_json::Value) -> Vec<FieldDiff> {
+    let Some(serde_json::Value::Object(changes)) = AuditWriter::compute_changes(a, b) else {
+        return Vec::new();
+    };
+
+    // compute_changes sorts keys and reports absent fields as null, so
+    // presence is read from the submissions themselves
+    changes
+        .into_iter()
+        .map(|(field, _)| {
+            let a = a.get(&field).cloned();
+            let b = b.get(&field).cloned();
+            let change = match (&a, &b) {
+                (None, _) => FieldChange::Added,
+                (_, None) => FieldChange::Removed,
+                _ => FieldChange::Changed,
+            };
+            FieldDiff {
+                field,
+                change,
+                a,
+                b,
+            }
+        })
+        .collect()
+}
+
+/// Build the timeline from stream events, ordered by stream version
+fn timeline(events: &[StoredEvent]) -> Vec<WorkflowTimelineEntry> {
+    let mut ordered: Vec<&StoredEvent> = events.iter().collect();
+    ordered.sort_by_key(|e| e.version);
+
+    ordered
+        .into_iter()
+        .map(|e| WorkflowTimelineEntry {
+            sequence: e.version,
+            event_type: e.event.event_type().to_string(),
+            step_id: e.event.step_id().map(str::to_string),
+            actor: e
+                .metadata
+                .get("user_id")
+                .and_then(|v| v.as_str())
+                .map(str::to_string),
+            occurred_at: e.occurred_at.to_rfc3339(),
+            details: serde_json::to_value(&e.event).unwrap_or_default(),
+        })
+        .collect()
+}
+
+/// Step states in the order each step first appears in the stream
+fn step_statuses(events: &[StoredEvent], state: &WorkflowStateManager) -> Vec<WorkflowStepStatus> {
+    let mut seen: Vec<&str> = Vec::new();
+    for step_id in events.iter().filter_map(|e| e.event.step_id()) {
+        if !seen.contains(&step_id) {
+            seen.push(step_id);
+        }
+    }
+
+    seen.into_iter()
+        .filter_map(|step_id| {
+            let step_state = state.all_step_states().get(step_id)?;
+            Some(WorkflowStepStatus {
+                step_id: step_id.to_string(),
+                status: step_state.status_name().to_string(),
+                state: serde_json::to_value(step_state).unwrap_or_default(),
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{Duration, Utc};
+    use glyph_workflow_engine::WorkflowEvent;
+
+    #[test]
+    fn test_diff_marks_added_removed_and_changed_fields() {
+        let a = serde_json::json!({
+            "label": "cat",
+            "confidence": 0.9,
+            "notes": "blurry",
+            "boxes": [[0, 0, 10, 10]],
+        });
+        let b = serde_json::json!({
+            "label": "dog",
+            "confidence": 0.9,
+            "boxes": [[0, 0, 10, 10]],
+            "occluded": true,
+        });
+
+        let fields = diff_fields(&a, &b);
+        let summary: Vec<_> = fields
+            .iter()
+            .map(|f| (f.field.as_str(), f.change))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("label", FieldChange::Changed),
+                ("notes", FieldChange::Removed),
+                ("occluded", FieldChange::Added),
+            ]
+        );
+
+        assert_eq!(fields[0].a, Some(serde_json::json!("cat")));
+        assert_eq!(fields[0].b, Some(serde_json::json!("dog")));
+        assert_eq!(fields[1].b, None);
+        assert_eq!(fields[2].a, None);
+    }
+
+    #[test]
+    fn test_diff_keeps_explicit_null_distinct_from_missing() {
+        let a = serde_json::json!({ "comment": null });
+        let b = serde_json::json!({});
+
+        let fields = diff_fields(&a, &b);
+        assert_eq!(fields.len(), 1);
+        assert_eq!(fields[0].change, FieldChange::Removed);
+        assert_eq!(fields[0].a, Some(serde_json::Value::Null));
+
+        assert!(diff_fields(&a, &a).is_empty());
+    }
+
+    fn stored(version: u64, event: WorkflowEvent, metadata: serde_json::Value) -> StoredEvent {
+        StoredEvent::new(Uuid::nil(), "workflow", version, event, metadata)
+    }
+
+    #[test]
+    fn test_timeline_follows_event_sequence() {
+        let start = Utc::now();
+        let annotator = Uuid::new_v4();
+        let events = vec![
+            stored(
+                3,
+                WorkflowEvent::StepCompleted {
+                    step_id: "annotate".to_string(),
+                    result: glyph_workflow_engine::StepResult::submitted(vec![], None),
+                    completed_at: start + Duration::minutes(5),
+                },
+                serde_json::json!({ "user_id": annotator }),
+            ),
+            stored(
+                1,
+                WorkflowEvent::WorkflowStarted {
+                    workflow_id: Uuid::new_v4(),
+                    config_version: "1.0".to_string(),
+                    started_at: start,
+                },
+                serde_json::json!({}),
+            ),
+            stored(
+                2,
+                WorkflowEvent::StepActivated {
+                    step_id: "annotate".to_string(),
+                    assigned_to: vec![],
+                    sla_deadline: None,
+                    activated_at: start,
+                },
+                serde_json::json!({}),
+            ),
+        ];
+
+        let entries = timeline(&events);
+        let sequence: Vec<u64> = entries.iter().map(|e| e.sequence).collect();
+        assert_eq!(sequence, vec![1, 2, 3]);
+        assert_eq!(entries[0].event_type, "workflow_started");
+        assert_eq!(entries[1].step_id.as_deref(), Some("annotate"));
+        assert_eq!(entries[2].actor, Some(annotator.to_string()));
+        assert_eq!(entries[0].actor, None);
+    }
+
+    fn current_user(user_id: Uuid, roles: &[&str]) -> CurrentUser {
+        CurrentUser {
+            user_id: UserId::from_uuid(user_id),
+            auth0_id: format!("test|{user_id}"),
+            email: None,
+            email_verified: true,
+            name: None,
+            roles: roles.iter().map(ToString::to_string).collect(),
+            org_id: glyph_domain::OrgId::DEFAULT,
+        }
+    }
+
+    #[tokio::test]
+    #[ignore = "requires DATABASE_URL pointing at a migrated database"]
+    async fn test_diff_is_limited_to_reviewers_and_adjudicators() {
+        let url = std::env::var("DATABASE_URL").expect("DATABASE_URL must be set");
+        let pool = PgPool::connect(&url).await.unwrap();
+
+        let mut users = Vec::new();
+        for _ in 0..3 {
+            let user_id = Uuid::new_v4();
+            sqlx::query(
+                r#"
+                INSERT INTO users (user_id, email, display_name, auth0_id, global_role, status)
+                VALUES ($1, $2, 'Diff', $3, 'user', 'active')
+                "#,
+            )
+            .bind(user_id)
+            .bind(format!("{user_id}@diff.test"))
+            .bind(format!("test|{user_id}"))
+            .execute(&pool)
+            .await
+            .unwrap();
+            users.push(user_id);
+        }
+        let (annotator, adjudicator, outsider) = (users[0], users[1], users[2]);
+
+        let workflow_id: Uuid = sqlx::query_scalar(
+            r#"
+            INSERT INTO workflows (name, entry_step_id, steps)
+            VALUES ('Diff flow', 'annotate', '[
+                {"step_id": "annotate", "step_type": "annotation"},
+                {"step_id": "adjudicate", "step_type": "adjudication"}
+            ]')
+            RETURNING workflow_id
+            "#,
+        )
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+        let project = PgProjectRepository::new(pool.clone())
+            .create_minimal("Diff", None, &UserId::from_uuid(annotator))
+            .await
+            .unwrap();
+        let project_id = *project.project_id.as_uuid();
+        sqlx::query("UPDATE projects SET workflow_id = $2 WHERE project_id = $1")
+            .bind(project_id)
+            .bind(workflow_id)
+            .execute(&pool)
+            .await
+            .unwrap();
+        let task_id: Uuid = sqlx::query_scalar(
+            "INSERT INTO tasks (project_id, input_data) VALUES ($1, '{}') RETURNING task_id",
+        )
+        .bind(project_id)
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+        for (user_id, step_id) in [(annotator, "annotate"), (adjudicator, "adjudicate")] {
+            sqlx::query(
+                "INSERT INTO task_assignments (task_id, project_id, step_id, user_id) VALUES ($1, $2, $3, $4)",
+            )
+            .bind(task_id)
+            .bind(project_id)
+            .bind(step_id)
+            .bind(user_id)
+            .execute(&pool)
+            .await
+            .unwrap();
+        }
+
+        let diff = |user: CurrentUser| {
+            get_annotation_diff(
+                Path(task_id),
+                Query(AnnotationDiffQuery {
+                    user_a: annotator,
+                    user_b: adjudicator,
+                }),
+                user,
+                Extension(pool.clone()),
+            )
+        };
+        for denied in [current_user(annotator, &[]), current_user(outsider, &[])] {
+            assert!(matches!(
+                diff(denied).await,
+                Err(ApiError::Forbidden { .. })
+            ));
+        }
+        // Allowed callers get past the gate to the (missing) submissions
+        for allowed in [
+            current_user(adjudicator, &[]),
+            current_user(outsider, &["reviewer"]),
+        ] {
+            assert!(matches!(
+                diff(allowed).await,
+                Err(ApiError::NotFound { .. })
+            ));
+        }
+    }
+}
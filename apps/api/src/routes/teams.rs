@@ -6,8 +6,9 @@ use axum::{
     Extension, Json,
 };
 use glyph_db::{
-    NewTeam, Pagination, PgTeamRepository, PgUserRepository, TeamMembershipError,
-    TeamMembershipWithUser, TeamRepository, TeamTreeNode, TeamUpdate, UserRepository,
+    BulkMembershipError, MoveTeamError, NewTeam, NewTeamMember, Pagination, PgTeamRepository,
+    PgUserRepository, TeamMembershipError, TeamMembershipWithUser, TeamRepository, TeamTreeNode,
+    TeamUpdate, UserRepository,
 };
 use glyph_domain::{TeamId, TeamRole, UserId};
 use serde::{Deserialize, Serialize};
@@ -16,8 +17,15 @@ use utoipa::ToSchema;
 
 use crate::error::ApiError;
 use crate::extractors::{CurrentUser, RequireAdmin};
+use crate::pagination::{PageNav, PageResource, PaginationPolicy};
 use crate::services::PermissionService;
 
+/// Most specializations a team may list
+pub const MAX_SPECIALIZATIONS: usize = 20;
+
+/// Longest specialization accepted, in characters
+pub const MAX_SPECIALIZATION_LEN: usize = 64;
+
 // =============================================================================
 // Response Types
 // =============================================================================
@@ -29,6 +37,8 @@ pub struct TeamListResponse {
     pub total: i64,
     pub limit: i64,
     pub offset: i64,
+    #[serde(flatten)]
+    pub nav: PageNav,
 }
 
 /// Summary view of a team for list responses
@@ -125,6 +135,20 @@ pub struct TeamMemberListResponse {
     pub offset: i64,
 }
 
+/// Result of a bulk membership change for one user
+#[derive(Debug, Serialize, ToSchema)]
+pub struct BulkMemberResultResponse {
+    pub user_id: String,
+    /// "added", "removed", "already_member", or "not_a_member"
+    pub outcome: String,
+}
+
+/// Bulk membership change response
+#[derive(Debug, Serialize, ToSchema)]
+pub struct BulkMembersResponse {
+    pub results: Vec<BulkMemberResultResponse>,
+}
+
 // =============================================================================
 // Request Types
 // =============================================================================
@@ -151,6 +175,12 @@ pub struct UpdateTeamRequest {
     pub specializations: Option<Vec<String>>,
 }
 
+/// Move team request; a null parent makes the team a root team
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct MoveTeamRequest {
+    pub new_parent_id: Option<String>,
+}
+
 /// Query parameters for listing teams
 #[derive(Debug, Deserialize)]
 pub struct ListTeamsParams {
@@ -169,6 +199,16 @@ pub struct AddMemberRequest {
     pub allocation_percentage: Option<i32>,
 }
 
+/// Bulk membership change; applied atomically
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct BulkMembersRequest {
+    #[serde(default)]
+    pub add: Vec<AddMemberRequest>,
+    /// User IDs to remove
+    #[serde(default)]
+    pub remove: Vec<String>,
+}
+
 /// Update team member request
 #[derive(Debug, Deserialize, ToSchema)]
 pub struct UpdateMemberRequest {
@@ -195,18 +235,14 @@ pub struct UpdateMemberRequest {
     )
 )]
 pub async fn list_teams(
-    _user: CurrentUser,
+    current_user: CurrentUser,
     Query(params): Query<ListTeamsParams>,
     Extension(pool): Extension<PgPool>,
 ) -> Result<Json<TeamListResponse>, ApiError> {
-    let pagination = Pagination {
-        limit: params.limit.unwrap_or(20),
-        offset: params.offset.unwrap_or(0),
-        sort_by: None,
-        sort_order: Default::default(),
-    };
+    let pagination =
+        PaginationPolicy::for_resource(PageResource::Teams).pagination(params.limit, params.offset);
 
-    let repo = PgTeamRepository::new(pool);
+    let repo = PgTeamRepository::new(pool).in_org(current_user.org_id);
     let page = if params.root_only.unwrap_or(false) {
         repo.list_root_teams(pagination).await
     } else {
@@ -235,6 +271,7 @@ pub async fn list_teams(
         total: page.total,
         limit: page.limit,
         offset: page.offset,
+        nav: PageNav::new(page.total, page.limit, page.offset),
     }))
 }
 
@@ -250,13 +287,15 @@ pub async fn list_teams(
     )
 )]
 pub async fn get_team(
-    _user: CurrentUser,
+    current_user: CurrentUser,
     Path(team_id): Path<String>,
     Extension(pool): Extension<PgPool>,
 ) -> Result<Json<TeamDetailResponse>, ApiError> {
-    let id: TeamId = team_id.parse()?;
+    let id: TeamId = team_id
+        .parse()
+        .map_err(|e| ApiError::invalid_id("team", &e))?;
 
-    let repo = PgTeamRepository::new(pool);
+    let repo = PgTeamRepository::new(pool).in_org(current_user.org_id);
     let team = repo
         .find_by_id(&id)
         .await
@@ -323,12 +362,14 @@ pub async fn get_team(
     )
 )]
 pub async fn get_team_tree(
-    _user: CurrentUser,
+    current_user: CurrentUser,
     Path(team_id): Path<String>,
     Extension(pool): Extension<PgPool>,
 ) -> Result<Json<TeamTreeResponse>, ApiError> {
-    let id: TeamId = team_id.parse()?;
-    let repo = PgTeamRepository::new(pool);
+    let id: TeamId = team_id
+        .parse()
+        .map_err(|e| ApiError::invalid_id("team", &e))?;
+    let repo = PgTeamRepository::new(pool).in_org(current_user.org_id);
     let tree = repo
         .get_team_tree(&id)
         .await
@@ -351,15 +392,17 @@ pub async fn get_team_tree(
     )
 )]
 pub async fn create_team(
-    RequireAdmin(_admin): RequireAdmin,
+    RequireAdmin(admin): RequireAdmin,
     Extension(pool): Extension<PgPool>,
     Json(body): Json<CreateTeamRequest>,
 ) -> Result<(StatusCode, Json<TeamDetailResponse>), ApiError> {
-    let repo = PgTeamRepository::new(pool);
+    let repo = PgTeamRepository::new(pool).in_org(admin.org_id);
 
     // Validate parent exists if provided
     let parent_team_id = if let Some(ref parent_id_str) = body.parent_team_id {
-        let parent_id: TeamId = parent_id_str.parse()?;
+        let parent_id: TeamId = parent_id_str
+            .parse()
+            .map_err(|e| ApiError::invalid_id("team", &e))?;
         let _ = repo
             .find_by_id(&parent_id)
             .await
@@ -372,15 +415,19 @@ pub async fn create_team(
         None
     };
 
-    let initial_leader_id: Option<UserId> =
-        body.initial_leader_id.map(|s| s.parse()).transpose()?;
+    let initial_leader_id: Option<UserId> = body
+        .initial_leader_id
+        .map(|s| s.parse())
+        .transpose()
+        .map_err(|e| ApiError::invalid_id("user", &e))?;
+    let specializations = normalize_specializations(body.specializations)?;
 
     let new_team = NewTeam {
         name: body.name,
         description: body.description,
         parent_team_id,
         capacity: body.capacity,
-        specializations: body.specializations,
+        specializations,
         initial_leader_id: initial_leader_id.clone(),
     };
 
@@ -419,6 +466,7 @@ pub async fn create_team(
     request_body = UpdateTeamRequest,
     responses(
         (status = 200, description = "Team updated", body = TeamDetailResponse),
+        (status = 400, description = "Invalid request"),
         (status = 404, description = "Team not found"),
         (status = 403, description = "Requires team leadership or admin")
     )
@@ -429,7 +477,13 @@ pub async fn update_team(
     Extension(pool): Extension<PgPool>,
     Json(body): Json<UpdateTeamRequest>,
 ) -> Result<Json<TeamDetailResponse>, ApiError> {
-    let id: TeamId = team_id.parse()?;
+    let id: TeamId = team_id
+        .parse()
+        .map_err(|e| ApiError::invalid_id("team", &e))?;
+    let specializations = body
+        .specializations
+        .map(normalize_specializations)
+        .transpose()?;
 
     // Check permission: admin or team leader
     if !current_user.has_role("admin") {
@@ -450,10 +504,10 @@ pub async fn update_team(
         description: body.description,
         status: body.status.and_then(|s| parse_team_status_opt(&s)),
         capacity: body.capacity,
-        specializations: body.specializations,
+        specializations,
     };
 
-    let repo = PgTeamRepository::new(pool);
+    let repo = PgTeamRepository::new(pool).in_org(current_user.org_id);
     let team = repo.update(&id, &update).await.map_err(|e| match e {
         glyph_db::UpdateTeamError::NotFound(id) => ApiError::not_found("team", id.to_string()),
         glyph_db::UpdateTeamError::Database(e) => ApiError::Internal(anyhow::anyhow!("{}", e)),
@@ -488,12 +542,14 @@ pub async fn update_team(
     )
 )]
 pub async fn delete_team(
-    RequireAdmin(_admin): RequireAdmin,
+    RequireAdmin(admin): RequireAdmin,
     Path(team_id): Path<String>,
     Extension(pool): Extension<PgPool>,
 ) -> Result<StatusCode, ApiError> {
-    let id: TeamId = team_id.parse()?;
-    let repo = PgTeamRepository::new(pool);
+    let id: TeamId = team_id
+        .parse()
+        .map_err(|e| ApiError::invalid_id("team", &e))?;
+    let repo = PgTeamRepository::new(pool).in_org(admin.org_id);
     repo.soft_delete(&id).await.map_err(|e| match e {
         glyph_db::UpdateTeamError::NotFound(id) => ApiError::not_found("team", id.to_string()),
         glyph_db::UpdateTeamError::Database(e) => ApiError::Internal(anyhow::anyhow!("{}", e)),
@@ -501,6 +557,107 @@ pub async fn delete_team(
     Ok(StatusCode::NO_CONTENT)
 }
 
+/// Trim, lowercase, and dedupe specializations, dropping blank entries.
+///
+/// Rejects lists longer than [`MAX_SPECIALIZATIONS`] or entries longer than
+/// [`MAX_SPECIALIZATION_LEN`].
+pub fn normalize_specializations(raw: Vec<String>) -> Result<Vec<String>, ApiError> {
+    let mut normalized: Vec<String> = Vec::with_capacity(raw.len());
+    for entry in raw {
+        let entry = entry.trim().to_lowercase();
+        if entry.is_empty() || normalized.contains(&entry) {
+            continue;
+        }
+        if entry.chars().count() > MAX_SPECIALIZATION_LEN {
+            return Err(ApiError::bad_request(
+                "team.specializations.too_long",
+                format!("Specializations must be at most {MAX_SPECIALIZATION_LEN} characters"),
+            ));
+        }
+        normalized.push(entry);
+    }
+
+    if normalized.len() > MAX_SPECIALIZATIONS {
+        return Err(ApiError::bad_request(
+            "team.specializations.too_many",
+            format!("A team may have at most {MAX_SPECIALIZATIONS} specializations"),
+        ));
+    }
+    Ok(normalized)
+}
+
+/// Move a team, with its sub-teams, under a new parent
+#[utoipa::path(
+    post,
+    path = "/teams/{team_id}/move",
+    tag = "teams",
+    params(("team_id" = String, Path, description = "Team ID")),
+    request_body = MoveTeamRequest,
+    responses(
+        (status = 200, description = "Team moved", body = TeamDetailResponse),
+        (status = 400, description = "Parent not found or move would create a cycle"),
+        (status = 403, description = "Admin only"),
+        (status = 404, description = "Team not found")
+    )
+)]
+pub async fn move_team(
+    RequireAdmin(admin): RequireAdmin,
+    Path(team_id): Path<String>,
+    Extension(pool): Extension<PgPool>,
+    Json(body): Json<MoveTeamRequest>,
+) -> Result<Json<TeamDetailResponse>, ApiError> {
+    let id: TeamId = team_id
+        .parse()
+        .map_err(|e| ApiError::invalid_id("team", &e))?;
+    let new_parent_id: Option<TeamId> = body
+        .new_parent_id
+        .map(|s| s.parse())
+        .transpose()
+        .map_err(|e| ApiError::invalid_id("team", &e))?;
+
+    let repo = PgTeamRepository::new(pool).in_org(admin.org_id);
+    let team = repo
+        .move_team(&id, new_parent_id.as_ref())
+        .await
+        .map_err(|e| match e {
+            MoveTeamError::NotFound(id) => ApiError::not_found("team", id.to_string()),
+            MoveTeamError::ParentNotFound(_) => {
+                ApiError::bad_request("team.parent.not_found", "Parent team not found")
+            }
+            MoveTeamError::Cycle { .. } => ApiError::bad_request(
+                "team.move.cycle",
+                "A team cannot be moved under itself or one of its sub-teams",
+            ),
+            MoveTeamError::Database(e) => ApiError::Internal(anyhow::anyhow!("{}", e)),
+        })?;
+
+    Ok(Json(TeamDetailResponse {
+        team_id: team.team_id.to_string(),
+        name: team.name,
+        description: team.description,
+        status: format!("{:?}", team.status).to_lowercase(),
+        parent_team_id: team.parent_team_id.map(|id| id.to_string()),
+        capacity: team.capacity,
+        specializations: team.specializations,
+        member_count: 0,
+        leader_count: 0,
+        sub_teams: vec![],
+        created_at: team.created_at.to_rfc3339(),
+        updated_at: team.updated_at.to_rfc3339(),
+    }))
+}
+
+fn parse_member_role(role: Option<&str>) -> Result<TeamRole, ApiError> {
+    match role {
+        Some("leader") => Ok(TeamRole::Leader),
+        Some("member") | None => Ok(TeamRole::Member),
+        Some(r) => Err(ApiError::bad_request(
+            "team.role.invalid",
+            format!("Invalid role: {}. Must be 'leader' or 'member'", r),
+        )),
+    }
+}
+
 fn parse_team_status_opt(s: &str) -> Option<glyph_domain::TeamStatus> {
     match s.to_lowercase().as_str() {
         "active" => Some(glyph_domain::TeamStatus::Active),
@@ -529,14 +686,17 @@ fn parse_team_status_opt(s: &str) -> Option<glyph_domain::TeamStatus> {
     )
 )]
 pub async fn list_team_members(
-    _user: CurrentUser,
+    current_user: CurrentUser,
     Path(team_id): Path<String>,
     Query(pagination): Query<Pagination>,
     Extension(pool): Extension<PgPool>,
 ) -> Result<Json<TeamMemberListResponse>, ApiError> {
-    let id: TeamId = team_id.parse()?;
+    let id: TeamId = team_id
+        .parse()
+        .map_err(|e| ApiError::invalid_id("team", &e))?;
+    let pagination = PaginationPolicy::for_resource(PageResource::Teams).clamp(pagination);
 
-    let repo = PgTeamRepository::new(pool);
+    let repo = PgTeamRepository::new(pool).in_org(current_user.org_id);
 
     // Verify team exists
     let _ = repo
@@ -583,7 +743,9 @@ pub async fn add_team_member(
     Extension(pool): Extension<PgPool>,
     Json(body): Json<AddMemberRequest>,
 ) -> Result<(StatusCode, Json<TeamMemberResponse>), ApiError> {
-    let id: TeamId = team_id.parse()?;
+    let id: TeamId = team_id
+        .parse()
+        .map_err(|e| ApiError::invalid_id("team", &e))?;
 
     // Check permission: admin or team leader (with cascade)
     if !current_user.has_role("admin") {
@@ -599,7 +761,10 @@ pub async fn add_team_member(
         }
     }
 
-    let member_user_id: UserId = body.user_id.parse()?;
+    let member_user_id: UserId = body
+        .user_id
+        .parse()
+        .map_err(|e| ApiError::invalid_id("user", &e))?;
 
     let user_repo = PgUserRepository::new(pool.clone());
     // Verify user exists
@@ -609,18 +774,9 @@ pub async fn add_team_member(
         .map_err(|e| ApiError::Internal(anyhow::anyhow!("{}", e)))?
         .ok_or_else(|| ApiError::not_found("user", body.user_id.clone()))?;
 
-    let role = match body.role.as_deref() {
-        Some("leader") => TeamRole::Leader,
-        Some("member") | None => TeamRole::Member,
-        Some(r) => {
-            return Err(ApiError::bad_request(
-                "team.role.invalid",
-                format!("Invalid role: {}. Must be 'leader' or 'member'", r),
-            ))
-        }
-    };
+    let role = parse_member_role(body.role.as_deref())?;
 
-    let repo = PgTeamRepository::new(pool);
+    let repo = PgTeamRepository::new(pool).in_org(current_user.org_id);
     let membership = repo
         .add_member(&id, &member_user_id, role, body.allocation_percentage)
         .await
@@ -649,6 +805,97 @@ pub async fn add_team_member(
     ))
 }
 
+/// Add and remove several team members at once
+#[utoipa::path(
+    post,
+    path = "/teams/{team_id}/members/bulk",
+    tag = "teams",
+    params(("team_id" = String, Path, description = "Team ID")),
+    request_body = BulkMembersRequest,
+    responses(
+        (status = 200, description = "Changes applied", body = BulkMembersResponse),
+        (status = 400, description = "Invalid request, last leader removed, or capacity exceeded"),
+        (status = 403, description = "Requires team leadership"),
+        (status = 404, description = "Team or user not found")
+    )
+)]
+pub async fn bulk_update_team_members(
+    current_user: CurrentUser,
+    Path(team_id): Path<String>,
+    Extension(pool): Extension<PgPool>,
+    Json(body): Json<BulkMembersRequest>,
+) -> Result<Json<BulkMembersResponse>, ApiError> {
+    let id: TeamId = team_id
+        .parse()
+        .map_err(|e| ApiError::invalid_id("team", &e))?;
+
+    // Check permission: admin or team leader (with cascade)
+    if !current_user.has_role("admin") {
+        let permission_service = PermissionService::new(pool.clone());
+        let is_leader = permission_service
+            .check_team_leadership_cascade(&current_user.user_id, &id)
+            .await
+            .map_err(|e| ApiError::Internal(anyhow::anyhow!("{}", e)))?;
+        if !is_leader {
+            return Err(ApiError::Forbidden {
+                message: format!("Requires team lead or admin role for team {}", id),
+            });
+        }
+    }
+
+    let add = body
+        .add
+        .iter()
+        .map(|member| {
+            Ok(NewTeamMember {
+                user_id: member
+                    .user_id
+                    .parse()
+                    .map_err(|e| ApiError::invalid_id("user", &e))?,
+                role: parse_member_role(member.role.as_deref())?,
+                allocation: member.allocation_percentage,
+            })
+        })
+        .collect::<Result<Vec<_>, ApiError>>()?;
+    let remove = body
+        .remove
+        .iter()
+        .map(|user_id| user_id.parse())
+        .collect::<Result<Vec<UserId>, _>>()?;
+
+    let repo = PgTeamRepository::new(pool).in_org(current_user.org_id);
+    let results = repo
+        .bulk_update_members(&id, &add, &remove)
+        .await
+        .map_err(|e| match e {
+            BulkMembershipError::TeamNotFound(id) => ApiError::not_found("team", id.to_string()),
+            BulkMembershipError::UserNotFound(id) => ApiError::not_found("user", id.to_string()),
+            BulkMembershipError::DuplicateUser(id) => ApiError::bad_request(
+                "team.members.duplicate",
+                format!("User {id} is listed more than once"),
+            ),
+            BulkMembershipError::LastLeader => ApiError::bad_request(
+                "team.last_leader",
+                "Cannot remove the last leader. Promote another member to leader first.",
+            ),
+            BulkMembershipError::CapacityExceeded { capacity, .. } => ApiError::bad_request(
+                "team.capacity_exceeded",
+                format!("Team capacity of {capacity} members would be exceeded"),
+            ),
+            BulkMembershipError::Database(e) => ApiError::Internal(anyhow::anyhow!("{}", e)),
+        })?;
+
+    Ok(Json(BulkMembersResponse {
+        results: results
+            .into_iter()
+            .map(|r| BulkMemberResultResponse {
+                user_id: r.user_id.to_string(),
+                outcome: r.outcome.as_str().to_string(),
+            })
+            .collect(),
+    }))
+}
+
 /// Remove a member from a team
 #[utoipa::path(
     delete,
@@ -670,8 +917,12 @@ pub async fn remove_team_member(
     Path((team_id, user_id)): Path<(String, String)>,
     Extension(pool): Extension<PgPool>,
 ) -> Result<StatusCode, ApiError> {
-    let id: TeamId = team_id.parse()?;
-    let member_user_id: UserId = user_id.parse()?;
+    let id: TeamId = team_id
+        .parse()
+        .map_err(|e| ApiError::invalid_id("team", &e))?;
+    let member_user_id: UserId = user_id
+        .parse()
+        .map_err(|e| ApiError::invalid_id("user", &e))?;
 
     // Check permission
     if !current_user.has_role("admin") {
@@ -687,7 +938,7 @@ pub async fn remove_team_member(
         }
     }
 
-    let repo = PgTeamRepository::new(pool);
+    let repo = PgTeamRepository::new(pool).in_org(current_user.org_id);
 
     // Prevent removing last leader
     let members = repo
@@ -753,8 +1004,12 @@ pub async fn update_team_member(
     Extension(pool): Extension<PgPool>,
     Json(body): Json<UpdateMemberRequest>,
 ) -> Result<Json<TeamMemberResponse>, ApiError> {
-    let id: TeamId = team_id.parse()?;
-    let member_user_id: UserId = user_id.parse()?;
+    let id: TeamId = team_id
+        .parse()
+        .map_err(|e| ApiError::invalid_id("team", &e))?;
+    let member_user_id: UserId = user_id
+        .parse()
+        .map_err(|e| ApiError::invalid_id("user", &e))?;
 
     // Check permission
     if !current_user.has_role("admin") {
@@ -783,7 +1038,7 @@ pub async fn update_team_member(
         })
         .transpose()?;
 
-    let repo = PgTeamRepository::new(pool.clone());
+    let repo = PgTeamRepository::new(pool.clone()).in_org(current_user.org_id);
 
     // If demoting from leader, check not last leader
     if new_role == Some(TeamRole::Member) {
@@ -849,9 +1104,46 @@ pub async fn update_team_member(
 // Routes
 // =============================================================================
 
+/// OpenAPI paths and schemas served by this module
+#[derive(utoipa::OpenApi)]
+#[openapi(
+    paths(
+        list_teams,
+        get_team,
+        get_team_tree,
+        create_team,
+        update_team,
+        delete_team,
+        move_team,
+        list_team_members,
+        add_team_member,
+        bulk_update_team_members,
+        remove_team_member,
+        update_team_member
+    ),
+    components(schemas(
+        TeamListResponse,
+        TeamSummary,
+        TeamDetailResponse,
+        TeamTreeResponse,
+        TeamTreeNodeResponse,
+        TeamMemberResponse,
+        TeamMemberListResponse,
+        BulkMemberResultResponse,
+        BulkMembersResponse,
+        CreateTeamRequest,
+        UpdateTeamRequest,
+        MoveTeamRequest,
+        AddMemberRequest,
+        BulkMembersRequest,
+        UpdateMemberRequest
+    ))
+)]
+pub(super) struct ApiPaths;
+
 /// Build team routes
 pub fn routes() -> axum::Router {
-    use axum::routing::get;
+    use axum::routing::{get, post};
 
     axum::Router::new()
         .route("/", get(list_teams).post(create_team))
@@ -860,10 +1152,12 @@ pub fn routes() -> axum::Router {
             get(get_team).patch(update_team).delete(delete_team),
         )
         .route("/{team_id}/tree", get(get_team_tree))
+        .route("/{team_id}/move", post(move_team))
         .route(
             "/{team_id}/members",
             get(list_team_members).post(add_team_member),
         )
+        .route("/{team_id}/members/bulk", post(bulk_update_team_members))
         .route(
             "/{team_id}/members/{user_id}",
             get(list_team_members)
@@ -871,3 +1165,97 @@ pub fn routes() -> axum::Router {
                 .delete(remove_team_member),
         )
 }
+
+#[cfg(test)]
+mod tests {
+    use axum::response::IntoResponse;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_malformed_team_id_is_bad_request() {
+        // Never connects: the id is rejected before any query runs
+        let pool = sqlx::postgres::PgPoolOptions::new()
+            .connect_lazy("postgres://glyph@127.0.0.1:1/glyph")
+            .unwrap();
+        let current_user = CurrentUser {
+            user_id: UserId::new(),
+            auth0_id: "test|teams".to_string(),
+            email: None,
+            email_verified: true,
+            name: None,
+            roles: vec!["admin".to_string()],
+            org_id: glyph_domain::OrgId::DEFAULT,
+        };
+
+        let err = add_team_member(
+            current_user,
+            Path("not-a-team-id".to_string()),
+            Extension(pool),
+            Json(AddMemberRequest {
+                user_id: UserId::new().to_string(),
+                role: None,
+                allocation_percentage: None,
+            }),
+        )
+        .await
+        .unwrap_err();
+
+        assert!(matches!(
+            err,
+            ApiError::BadRequest {
+                code: "validation.invalid_id",
+                ..
+            }
+        ));
+        assert!(err.to_string().contains("invalid team id"));
+        assert_eq!(err.into_response().status(), StatusCode::BAD_REQUEST);
+    }
+
+    #[test]
+    fn test_specializations_are_normalized() {
+        let normalized = normalize_specializations(vec![
+            "  NLP ".to_string(),
+            "nlp".to_string(),
+            String::new(),
+            "   ".to_string(),
+            "Computer Vision".to_string(),
+            "computer vision ".to_string(),
+        ])
+        .unwrap();
+        assert_eq!(normalized, vec!["nlp", "computer vision"]);
+    }
+
+    #[test]
+    fn test_overlong_specialization_rejected() {
+        let err = normalize_specializations(vec!["x".repeat(MAX_SPECIALIZATION_LEN + 1)]);
+        assert!(matches!(
+            err,
+            Err(ApiError::BadRequest {
+                code: "team.specializations.too_long",
+                ..
+            })
+        ));
+
+        let at_limit = normalize_specializations(vec!["x".repeat(MAX_SPECIALIZATION_LEN)]);
+        assert!(at_limit.is_ok());
+    }
+
+    #[test]
+    fn test_too_many_specializations_rejected() {
+        let raw = (0..=MAX_SPECIALIZATIONS)
+            .map(|i| format!("skill-{i}"))
+            .collect();
+        assert!(matches!(
+            normalize_specializations(raw),
+            Err(ApiError::BadRequest {
+                code: "team.specializations.too_many",
+                ..
+            })
+        ));
+
+        // Duplicates collapse before the count is checked
+        let raw = vec!["nlp".to_string(); MAX_SPECIALIZATIONS + 5];
+        assert_eq!(normalize_specializations(raw).unwrap(), vec!["nlp"]);
+    }
+}
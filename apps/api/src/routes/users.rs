@@ -5,7 +5,11 @@ use axum::{
     http::StatusCode,
     Extension, Json,
 };
-use glyph_db::{NewUser, Pagination, PgUserRepository, UserRepository, UserUpdate};
+use glyph_common::redact::Email;
+use glyph_db::{
+    NewUser, Pagination, PgQualityProfileRepository, PgUserRepository, QualityProfileRepository,
+    UserRepository, UserUpdate,
+};
 use glyph_domain::{ContactInfo, GlobalRole, QualityProfile, User, UserId};
 use serde::{Deserialize, Serialize};
 use sqlx::PgPool;
@@ -13,6 +17,7 @@ use utoipa::ToSchema;
 
 use crate::error::ApiError;
 use crate::extractors::{CurrentUser, RequireAdmin};
+use crate::pagination::{PageResource, PaginationPolicy};
 
 /// User list response with pagination
 #[derive(Debug, Serialize, ToSchema)]
@@ -69,6 +74,7 @@ pub struct UserDetailResponse {
 pub struct QualityProfileResponse {
     pub overall_score: Option<f64>,
     pub accuracy_score: Option<f64>,
+    pub gold_accuracy_score: Option<f64>,
     pub consistency_score: Option<f64>,
     pub speed_percentile: Option<f64>,
     pub total_annotations: i64,
@@ -81,6 +87,7 @@ impl From<QualityProfile> for QualityProfileResponse {
         Self {
             overall_score: q.overall_score,
             accuracy_score: q.accuracy_score,
+            gold_accuracy_score: q.gold_accuracy_score,
             consistency_score: q.consistency_score,
             speed_percentile: q.speed_percentile,
             total_annotations: q.total_annotations,
@@ -153,6 +160,7 @@ pub async fn list_users(
     Query(pagination): Query<Pagination>,
     Extension(pool): Extension<PgPool>,
 ) -> Result<Json<UserListResponse>, ApiError> {
+    let pagination = PaginationPolicy::for_resource(PageResource::Users).clamp(pagination);
     let repo = PgUserRepository::new(pool);
     let page = repo
         .list(pagination)
@@ -188,8 +196,8 @@ pub async fn get_user(
 ) -> Result<Json<UserDetailResponse>, ApiError> {
     let id: UserId = user_id.parse()?;
 
-    let repo = PgUserRepository::new(pool);
-    let user = repo
+    let repo = PgUserRepository::new(pool.clone());
+    let mut user = repo
         .find_by_id(&id)
         .await
         .map_err(|e| {
@@ -198,6 +206,14 @@ pub async fn get_user(
         })?
         .ok_or_else(|| ApiError::not_found("user", user_id.clone()))?;
 
+    // Profiles are computed by the worker; users without one get null scores
+    let profile_repo = PgQualityProfileRepository::new(pool);
+    user.quality_profile = profile_repo
+        .find_by_user(&id)
+        .await
+        .map_err(|e| ApiError::Internal(anyhow::anyhow!("{}", e)))?
+        .unwrap_or_default();
+
     Ok(Json(UserDetailResponse::from(user)))
 }
 
@@ -214,6 +230,7 @@ pub async fn get_user(
         (status = 403, description = "Admin only")
     )
 )]
+#[tracing::instrument(skip_all, fields(email = %Email(&body.email)))]
 pub async fn create_user(
     RequireAdmin(_admin): RequireAdmin,
     Extension(pool): Extension<PgPool>,
@@ -239,6 +256,7 @@ pub async fn create_user(
     let repo = PgUserRepository::new(pool);
     let user = repo.create(&new_user).await.map_err(|e| match e {
         glyph_db::CreateUserError::EmailExists(email) => {
+            tracing::info!(email = %Email(&email), "user creation rejected: email exists");
             ApiError::conflict(format!("Email already exists: {}", email))
         }
         glyph_db::CreateUserError::Database(e) => ApiError::Internal(anyhow::anyhow!("{}", e)),
@@ -262,6 +280,7 @@ pub async fn create_user(
         (status = 403, description = "Can only update own profile unless admin")
     )
 )]
+#[tracing::instrument(skip_all, fields(user_id = %user_id))]
 pub async fn update_user(
     current_user: CurrentUser,
     Path(user_id): Path<String>,
@@ -340,6 +359,21 @@ fn parse_global_role(s: &str) -> Option<GlobalRole> {
     }
 }
 
+/// OpenAPI paths and schemas served by this module
+#[derive(utoipa::OpenApi)]
+#[openapi(
+    paths(list_users, get_user, create_user, update_user, delete_user),
+    components(schemas(
+        UserListResponse,
+        UserSummary,
+        UserDetailResponse,
+        QualityProfileResponse,
+        CreateUserRequest,
+        UpdateUserRequest
+    ))
+)]
+pub(super) struct ApiPaths;
+
 /// Build user routes
 pub fn routes() -> axum::Router {
     use axum::routing::get;
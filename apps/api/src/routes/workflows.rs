@@ -5,11 +5,24 @@
 use axum::{
     extract::{Path, State},
     routing::{get, post},
-    Json, Router,
+    Extension, Json, Router,
 };
 use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+
+use sqlx::PgPool;
 use uuid::Uuid;
 
+use glyph_db::{PgDraftRepository, PgWorkflowRepository};
+use glyph_domain::{StepType, TaskId, WorkflowId};
+use glyph_workflow_engine::{
+    step_durations, summarize_step_durations, PgEventStore, StepDurationStats, SubmissionKind,
+    SubmissionValidator, PROGRESS_SAVED,
+};
+
+use crate::error::FieldError;
+use crate::extractors::{CurrentUser, RequireAdmin};
+use crate::services::{OutputSchemaValidator, SchemaValidationService};
 use crate::ApiError;
 
 // =============================================================================
@@ -52,6 +65,9 @@ pub struct SubmitAnnotationRequest {
     pub workflow_id: Uuid,
     /// Annotation data
     pub data: serde_json::Value,
+    /// `partial` saves progress as a draft without advancing the step
+    #[serde(default)]
+    pub kind: SubmissionKind,
 }
 
 /// Response for task workflow state
@@ -79,6 +95,35 @@ pub enum ProcessResultResponse {
     Failed { error: String, recoverable: bool },
 }
 
+/// Duration percentiles for one step
+#[derive(Debug, Serialize)]
+pub struct StepMetrics {
+    pub step_id: String,
+    /// Completed activations measured
+    pub count: usize,
+    pub p50_ms: i64,
+    pub p95_ms: i64,
+}
+
+impl From<StepDurationStats> for StepMetrics {
+    fn from(stats: StepDurationStats) -> Self {
+        Self {
+            step_id: stats.step_id,
+            count: stats.count,
+            p50_ms: stats.p50_ms,
+            p95_ms: stats.p95_ms,
+        }
+    }
+}
+
+/// Response for workflow step metrics
+#[derive(Debug, Serialize)]
+pub struct StepMetricsResponse {
+    pub workflow_id: Uuid,
+    /// One entry per step with at least one completed activation
+    pub steps: Vec<StepMetrics>,
+}
+
 // =============================================================================
 // Handlers
 // =============================================================================
@@ -118,6 +163,52 @@ async fn create_workflow(
     })))
 }
 
+/// Task streams whose events are loaded at a time when measuring step durations
+const STEP_METRICS_STREAM_PAGE: i64 = 500;
+
+/// Step duration percentiles across every task the caller's organization ran
+/// on a workflow
+///
+/// Durations run from activation to completion; steps still active, failed
+/// or skipped are not counted. Workflows none of the organization's projects
+/// use are not found.
+async fn get_step_metrics(
+    Path(workflow_id): Path<Uuid>,
+    Extension(pool): Extension<PgPool>,
+    RequireAdmin(admin): RequireAdmin,
+) -> Result<Json<StepMetricsResponse>, ApiError> {
+    let in_org = PgWorkflowRepository::new(pool.clone())
+        .is_used_in_org(&WorkflowId::from_uuid(workflow_id), admin.org_id)
+        .await
+        .map_err(|e| ApiError::Internal(e.into()))?;
+    if !in_org {
+        return Err(ApiError::not_found("workflow", workflow_id.to_string()));
+    }
+
+    let store = PgEventStore::new(pool);
+    let org_id = Some(admin.org_id.into_uuid());
+    let mut durations = Vec::new();
+    let mut after = None;
+    loop {
+        let page = store
+            .load_step_events_for_workflow(workflow_id, org_id, after, STEP_METRICS_STREAM_PAGE)
+            .await
+            .map_err(|e| ApiError::Internal(e.into()))?;
+        durations.extend(step_durations(&page.events));
+        match page.next_after {
+            Some(next) => after = Some(next),
+            None => break,
+        }
+    }
+
+    let steps = summarize_step_durations(&durations)
+        .into_iter()
+        .map(StepMetrics::from)
+        .collect();
+
+    Ok(Json(StepMetricsResponse { workflow_id, steps }))
+}
+
 /// Start a workflow for a task
 async fn start_task_workflow(
     Path(task_id): Path<Uuid>,
@@ -132,11 +223,86 @@ async fn start_task_workflow(
     })))
 }
 
+/// Reject a final submission to an annotation step that doesn't match the
+/// output schema of the task's project type, listing every violation
+async fn check_submission(
+    pool: &PgPool,
+    schemas: Arc<SchemaValidationService>,
+    task_id: Uuid,
+    request: &SubmitAnnotationRequest,
+) -> Result<(), ApiError> {
+    let workflow_id = WorkflowId::from_uuid(request.workflow_id);
+    let definition = PgWorkflowRepository::new(pool.clone())
+        .find_definition(&workflow_id)
+        .await
+        .map_err(|e| ApiError::Internal(e.into()))?
+        .ok_or_else(|| ApiError::not_found("workflow", workflow_id.to_string()))?;
+    let is_annotation_step = definition
+        .steps
+        .as_array()
+        .into_iter()
+        .flatten()
+        .find(|step| step.get("step_id").and_then(|id| id.as_str()) == Some(&request.step_id))
+        .and_then(|step| step.get("step_type"))
+        .and_then(|step_type| serde_json::from_value::<StepType>(step_type.clone()).ok())
+        == Some(StepType::Annotation);
+    if !is_annotation_step {
+        return Ok(());
+    }
+
+    let violations = OutputSchemaValidator::new(pool.clone(), schemas)
+        .validate(task_id, &request.step_id, &request.data)
+        .await
+        .map_err(|e| ApiError::Internal(anyhow::anyhow!(e)))?;
+    if violations.is_empty() {
+        return Ok(());
+    }
+    Err(ApiError::validation(
+        violations
+            .into_iter()
+            .map(|violation| {
+                FieldError::new(
+                    format!("data{}", violation.path.replace('/', ".")),
+                    violation.message,
+                )
+            })
+            .collect(),
+    ))
+}
+
 /// Submit an annotation for the current step
+///
+/// Partial submissions are kept as the caller's draft on the step and never
+/// advance the workflow. Final submissions to annotation steps must match the
+/// project type's output schema.
 async fn submit_annotation(
     Path(task_id): Path<Uuid>,
+    current_user: CurrentUser,
+    Extension(pool): Extension<PgPool>,
+    Extension(schemas): Extension<Arc<SchemaValidationService>>,
     Json(request): Json<SubmitAnnotationRequest>,
 ) -> Result<Json<ProcessResultResponse>, ApiError> {
+    if request.kind == SubmissionKind::Partial {
+        PgDraftRepository::new(pool)
+            .save(
+                &TaskId::from_uuid(task_id),
+                Some(&request.step_id),
+                &current_user.user_id,
+                &request.data,
+            )
+            .await
+            .map_err(|e| ApiError::Internal(e.into()))?
+            .ok_or_else(|| {
+                ApiError::forbidden("Progress can only be saved on a step you are assigned to")
+            })?;
+        return Ok(Json(ProcessResultResponse::Waiting {
+            step_id: request.step_id,
+            reason: PROGRESS_SAVED.to_string(),
+        }));
+    }
+
+    check_submission(&pool, schemas, task_id, &request).await?;
+
     // Placeholder
     Ok(Json(ProcessResultResponse::Waiting {
         step_id: request.step_id,
@@ -183,9 +349,260 @@ pub fn routes() -> Router {
         // Workflow configuration endpoints
         .route("/", get(list_workflows).post(create_workflow))
         .route("/{workflow_id}", get(get_workflow))
+        .route("/{workflow_id}/step-metrics", get(get_step_metrics))
         // Task workflow operation endpoints
         .route("/tasks/{task_id}/start", post(start_task_workflow))
         .route("/tasks/{task_id}/submit", post(submit_annotation))
         .route("/tasks/{task_id}/state", get(get_task_workflow_state))
         .route("/tasks/{task_id}/advance", post(advance_task_workflow))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{Duration, Utc};
+    use glyph_db::{PgProjectRepository, PgProjectTypeRepository, ProjectTypeRepository};
+    use glyph_domain::{CreateProjectType, OrgId, UserId};
+    use glyph_workflow_engine::{StepResult, WorkflowEvent};
+
+    #[tokio::test]
+    #[ignore = "requires DATABASE_URL pointing at a migrated database"]
+    async fn test_submission_missing_required_field_is_rejected() {
+        let url = std::env::var("DATABASE_URL").expect("DATABASE_URL must be set");
+        let pool = PgPool::connect(&url).await.unwrap();
+
+        let user_id = UserId::new();
+        sqlx::query(
+            r#"
+            INSERT INTO users (user_id, email, display_name, auth0_id, global_role, status)
+            VALUES ($1, $2, 'Submitter', $3, 'user', 'active')
+            "#,
+        )
+        .bind(user_id.as_uuid())
+        .bind(format!("{user_id}@submit.test"))
+        .bind(format!("test|{user_id}"))
+        .execute(&pool)
+        .await
+        .unwrap();
+        let project_type = PgProjectTypeRepository::new(pool.clone())
+            .create(
+                &CreateProjectType {
+                    name: format!("Submit labels {user_id}"),
+                    description: None,
+                    input_schema: None,
+                    output_schema: Some(serde_json::json!({
+                        "type": "object",
+                        "properties": {"label": {"type": "string"}},
+                        "required": ["label"]
+                    })),
+                    estimated_duration_seconds: None,
+                    difficulty_level: None,
+                    skill_requirements: None,
+                    allow_overlapping_spans: None,
+                    is_system: None,
+                },
+                Some(&user_id),
+            )
+            .await
+            .unwrap();
+        let project = PgProjectRepository::new(pool.clone())
+            .create_minimal("Submit labels", None, &user_id)
+            .await
+            .unwrap();
+        sqlx::query("UPDATE projects SET project_type_id = $2 WHERE project_id = $1")
+            .bind(project.project_id.as_uuid())
+            .bind(project_type.project_type_id.as_uuid())
+            .execute(&pool)
+            .await
+            .unwrap();
+        let task_id: Uuid = sqlx::query_scalar(
+            "INSERT INTO tasks (project_id, input_data) VALUES ($1, '{}') RETURNING task_id",
+        )
+        .bind(project.project_id.as_uuid())
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+        let workflow_id = PgWorkflowRepository::new(pool.clone())
+            .create_from_definition(
+                &crate::services::project_config_service::single_step_workflow("Submit flow"),
+            )
+            .await
+            .unwrap();
+
+        let current_user = || CurrentUser {
+            user_id: user_id.clone(),
+            auth0_id: format!("test|{user_id}"),
+            email: None,
+            email_verified: true,
+            name: None,
+            roles: vec![],
+            org_id: glyph_domain::OrgId::DEFAULT,
+        };
+        let schemas = Arc::new(SchemaValidationService::new());
+        let submit = |data: serde_json::Value, kind: SubmissionKind| {
+            submit_annotation(
+                Path(task_id),
+                current_user(),
+                Extension(pool.clone()),
+                Extension(schemas.clone()),
+                Json(SubmitAnnotationRequest {
+                    step_id: "annotate".to_string(),
+                    workflow_id: *workflow_id.as_uuid(),
+                    data,
+                    kind,
+                }),
+            )
+        };
+
+        let Err(ApiError::Validation { errors }) =
+            submit(serde_json::json!({"note": "?"}), SubmissionKind::Final).await
+        else {
+            panic!("expected the missing label to be rejected");
+        };
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].field, "data");
+        assert!(errors[0].message.contains("label"));
+
+        assert!(
+            submit(serde_json::json!({"label": "cat"}), SubmissionKind::Final)
+                .await
+                .is_ok()
+        );
+
+        // Progress is kept against an assignment, and this user has none
+        assert!(matches!(
+            submit(serde_json::json!({"note": "?"}), SubmissionKind::Partial).await,
+            Err(ApiError::Forbidden { .. })
+        ));
+    }
+
+    #[tokio::test]
+    #[ignore = "requires DATABASE_URL pointing at a migrated database"]
+    async fn test_step_metrics_are_scoped_to_the_callers_org() {
+        let url = std::env::var("DATABASE_URL").expect("DATABASE_URL must be set");
+        let pool = PgPool::connect(&url).await.unwrap();
+
+        let user_id = UserId::new();
+        sqlx::query(
+            r#"
+            INSERT INTO users (user_id, email, display_name, auth0_id, global_role, status)
+            VALUES ($1, $2, 'Metrics', $3, 'admin', 'active')
+            "#,
+        )
+        .bind(user_id.as_uuid())
+        .bind(format!("{user_id}@metrics.test"))
+        .bind(format!("test|{user_id}"))
+        .execute(&pool)
+        .await
+        .unwrap();
+        let workflow_id = PgWorkflowRepository::new(pool.clone())
+            .create_from_definition(
+                &crate::services::project_config_service::single_step_workflow("Metrics scope"),
+            )
+            .await
+            .unwrap();
+        let new_org = || async {
+            let org_id = OrgId::new();
+            sqlx::query("INSERT INTO organizations (org_id, name, slug) VALUES ($1, $2, $2)")
+                .bind(org_id.as_uuid())
+                .bind(org_id.to_string())
+                .execute(&pool)
+                .await
+                .unwrap();
+            org_id
+        };
+
+        // One task per org on the shared workflow, with different annotate times
+        let (org_a, org_b) = (new_org().await, new_org().await);
+        for (org_id, seconds) in [(org_a, 10), (org_b, 1000)] {
+            let project = PgProjectRepository::new(pool.clone())
+                .in_org(org_id)
+                .create_minimal("Metrics scope", None, &user_id)
+                .await
+                .unwrap();
+            sqlx::query("UPDATE projects SET workflow_id = $2 WHERE project_id = $1")
+                .bind(project.project_id.as_uuid())
+                .bind(workflow_id.as_uuid())
+                .execute(&pool)
+                .await
+                .unwrap();
+            let task_id: Uuid = sqlx::query_scalar(
+                "INSERT INTO tasks (project_id, input_data) VALUES ($1, '{}') RETURNING task_id",
+            )
+            .bind(project.project_id.as_uuid())
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+            let started_at = Utc::now();
+            let events = [
+                WorkflowEvent::WorkflowStarted {
+                    workflow_id: *workflow_id.as_uuid(),
+                    config_version: "1".to_string(),
+                    started_at,
+                },
+                WorkflowEvent::StepActivated {
+                    step_id: "annotate".to_string(),
+                    assigned_to: vec![],
+                    sla_deadline: None,
+                    activated_at: started_at,
+                },
+                WorkflowEvent::StepCompleted {
+                    step_id: "annotate".to_string(),
+                    result: StepResult::approved(),
+                    completed_at: started_at + Duration::seconds(seconds),
+                },
+            ];
+            // Written directly so they land in a partition the migrations create
+            for (version, event) in (1_i64..).zip(events) {
+                sqlx::query(
+                    r#"
+                    INSERT INTO workflow_events
+                        (event_id, stream_id, stream_type, version, event_type, event_data, occurred_at)
+                    VALUES ($1, $2, 'task', $3, $4, $5, '2026-03-15')
+                    "#,
+                )
+                .bind(Uuid::new_v4())
+                .bind(task_id)
+                .bind(version)
+                .bind(event.event_type())
+                .bind(serde_json::to_value(&event).unwrap())
+                .execute(&pool)
+                .await
+                .unwrap();
+            }
+        }
+
+        let admin = |org_id| {
+            RequireAdmin(CurrentUser {
+                user_id,
+                auth0_id: format!("test|{user_id}"),
+                email: None,
+                email_verified: true,
+                name: None,
+                roles: vec!["admin".to_string()],
+                org_id,
+            })
+        };
+        let metrics = |org_id| {
+            get_step_metrics(
+                Path(*workflow_id.as_uuid()),
+                Extension(pool.clone()),
+                admin(org_id),
+            )
+        };
+
+        let Json(response) = metrics(org_a).await.unwrap();
+        assert_eq!(response.steps.len(), 1);
+        assert_eq!(response.steps[0].count, 1);
+        assert_eq!(response.steps[0].p50_ms, 10_000);
+
+        let Json(response) = metrics(org_b).await.unwrap();
+        assert_eq!(response.steps[0].count, 1);
+        assert_eq!(response.steps[0].p50_ms, 1_000_000);
+
+        assert!(matches!(
+            metrics(new_org().await).await,
+            Err(ApiError::NotFound { .. })
+        ));
+    }
+}
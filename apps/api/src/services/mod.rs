@@ -1,7 +1,17 @@
 //! Business logic services
 
 pub mod permission_service;
+pub mod project_config_service;
 pub mod schema_service;
+pub mod storage_service;
+pub mod upload_service;
 
 pub use permission_service::PermissionService;
-pub use schema_service::{SchemaError, SchemaValidationService};
+pub use schema_service::{
+    submission_violations, AnnotationBounds, OutputSchemaValidator, SchemaError,
+    SchemaValidationService,
+};
+pub use upload_service::{
+    preview_sample, CsvHeaderError, CsvOptions, ImportFormat, SampleError, SamplePreview,
+    UploadLimits, UploadSummary,
+};
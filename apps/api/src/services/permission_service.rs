@@ -1,6 +1,6 @@
 //! Permission checking service with team hierarchy support.
 
-use glyph_domain::{TeamId, UserId};
+use glyph_domain::{Project, TaskId, TeamId, UserId};
 use sqlx::PgPool;
 
 use crate::extractors::CurrentUser;
@@ -80,6 +80,108 @@ impl PermissionService {
         Ok(result)
     }
 
+    /// Check if user is an admin or leads the given team (or any of its
+    /// parent teams). Used for project-level oversight like audit history.
+    pub async fn is_admin_or_team_leader(
+        &self,
+        user: &CurrentUser,
+        team_id: Option<&TeamId>,
+    ) -> Result<bool, sqlx::Error> {
+        if user.has_role("admin") {
+            return Ok(true);
+        }
+        match team_id {
+            Some(team_id) => {
+                self.check_team_leadership_cascade(&user.user_id, team_id)
+                    .await
+            }
+            None => Ok(false),
+        }
+    }
+
+    /// Check if user takes part in a project: an admin, a member of the
+    /// project's team or a team above it, or anyone with work assigned on it.
+    pub async fn is_project_member(
+        &self,
+        user: &CurrentUser,
+        project: &Project,
+    ) -> Result<bool, sqlx::Error> {
+        if user.has_role("admin") {
+            return Ok(true);
+        }
+        sqlx::query_scalar::<_, bool>(
+            r#"
+            WITH RECURSIVE parent_teams AS (
+                SELECT team_id, parent_team_id
+                FROM teams
+                WHERE team_id = $2 AND status != 'deleted'
+
+                UNION ALL
+
+                SELECT t.team_id, t.parent_team_id
+                FROM teams t
+                JOIN parent_teams pt ON t.team_id = pt.parent_team_id
+                WHERE t.status != 'deleted'
+            )
+            SELECT EXISTS(
+                SELECT 1
+                FROM team_memberships tm
+                JOIN parent_teams pt ON tm.team_id = pt.team_id
+                WHERE tm.user_id = $3
+            ) OR EXISTS(
+                SELECT 1 FROM task_assignments
+                WHERE project_id = $1 AND user_id = $3
+            )
+            "#,
+        )
+        .bind(project.project_id.as_uuid())
+        .bind(project.team_id.as_ref().map(TeamId::as_uuid))
+        .bind(user.user_id.as_uuid())
+        .fetch_one(&self.pool)
+        .await
+    }
+
+    /// Check if user may compare annotators' submissions on a task: an
+    /// admin, a reviewer, a leader of the project's team or a team above it,
+    /// or someone assigned to a review or adjudication step of the task.
+    pub async fn can_review_task(
+        &self,
+        user: &CurrentUser,
+        project: &Project,
+        task_id: &TaskId,
+    ) -> Result<bool, sqlx::Error> {
+        if user.has_any_role(&["admin", "reviewer"]) {
+            return Ok(true);
+        }
+        if let Some(team_id) = &project.team_id {
+            if self
+                .check_team_leadership_cascade(&user.user_id, team_id)
+                .await?
+            {
+                return Ok(true);
+            }
+        }
+        sqlx::query_scalar::<_, bool>(
+            r#"
+            SELECT EXISTS(
+                SELECT 1
+                FROM task_assignments ta
+                JOIN projects p ON p.project_id = ta.project_id
+                JOIN workflows w ON w.workflow_id = p.workflow_id
+                CROSS JOIN LATERAL jsonb_array_elements(w.steps) AS step
+                WHERE ta.task_id = $1
+                  AND ta.user_id = $2
+                  AND step->>'step_id' = ta.step_id
+                  AND step->>'step_type' IN ('review', 'adjudication')
+            )
+            "#,
+        )
+        .bind(task_id.as_uuid())
+        .bind(user.user_id.as_uuid())
+        .fetch_one(&self.pool)
+        .await
+    }
+
     /// Check if user can certify skills (either admin or has skill:certifier role).
     pub fn can_certify_skills(&self, user: &CurrentUser) -> bool {
         user.has_any_role(&["admin", "skill:certifier"])
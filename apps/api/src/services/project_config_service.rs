@@ -0,0 +1,700 @@
+//! Portable project configuration bundles
+//!
+//! A bundle carries everything needed to set up a project the same way on
+//! another instance: the project's settings, its project type (schemas and
+//! skill requirements), its workflow as engine workflow YAML and its current
+//! layout. Tasks,
+//! annotations, team and deadline are environment-specific and left out.
+//!
+//! Importing creates fresh copies with new IDs. A project type whose name is
+//! already taken gets an " (imported)" suffix rather than reusing the existing
+//! one, so an import never changes configuration other projects depend on.
+
+use serde::{Deserialize, Serialize};
+use sqlx::{Acquire, PgPool, Postgres, Transaction};
+use utoipa::ToSchema;
+
+use glyph_db::{
+    CreateProjectTypeError, ExtendedProjectUpdate, LayoutDefinition, PgLayoutRepository,
+    PgProjectRepository, PgProjectTypeRepository, PgWorkflowRepository, ProjectRepository,
+    ProjectTypeRepository, WorkflowDefinition,
+};
+use glyph_domain::enums::{
+    AssignmentMode, CompletionCriteriaType, LoadBalancingStrategy, StepType, TimeoutAction,
+};
+use glyph_domain::workflow::{
+    AssignmentConfig, CompletionCriteria, StepUiConfig, TimeoutConfig, WorkflowHooks,
+};
+use glyph_domain::{
+    CreateProjectType, DifficultyLevel, OrgId, ProjectId, ProjectSettings, ProjectTypeId,
+    SkillRequirement, UserId, WorkflowId, WorkflowStep,
+};
+use glyph_workflow_engine::config::{
+    StepSettingsConfig, TransitionConditionConfig, WorkflowSettingsConfig,
+};
+use glyph_workflow_engine::{parse_workflow, StepConfig, TransitionConfig, WorkflowConfig};
+
+use crate::error::ApiError;
+
+/// Bundle format written by this version; imports of other versions are refused
+pub const CONFIG_BUNDLE_VERSION: u32 = 1;
+
+/// Suffixes tried, in order, when an imported project type's name is taken
+const MAX_NAME_ATTEMPTS: u32 = 10;
+
+/// A project's configuration, portable between instances
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct ProjectConfigBundle {
+    pub format_version: u32,
+    pub project: BundledProject,
+    pub project_type: Option<BundledProjectType>,
+    pub workflow: Option<BundledWorkflow>,
+    /// The layout and its current version
+    #[schema(value_type = Option<Object>)]
+    pub layout: Option<LayoutDefinition>,
+}
+
+/// The project's own settings
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct BundledProject {
+    pub name: String,
+    pub description: Option<String>,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    pub documentation: Option<String>,
+    #[schema(value_type = Object)]
+    #[serde(default)]
+    pub settings: ProjectSettings,
+}
+
+/// The project type, with its schemas and skill requirements
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct BundledProjectType {
+    pub name: String,
+    pub description: Option<String>,
+    #[schema(value_type = Object)]
+    pub input_schema: serde_json::Value,
+    #[schema(value_type = Object)]
+    pub output_schema: serde_json::Value,
+    pub estimated_duration_seconds: Option<i32>,
+    #[schema(value_type = Option<String>)]
+    pub difficulty_level: Option<DifficultyLevel>,
+    #[schema(value_type = Vec<Object>)]
+    #[serde(default)]
+    pub skill_requirements: Vec<SkillRequirement>,
+    pub allow_overlapping_spans: bool,
+}
+
+/// The workflow, as engine workflow YAML
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct BundledWorkflow {
+    pub yaml: String,
+}
+
+/// IDs created by an import
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct ImportedConfig {
+    pub project_id: String,
+    pub project_type_id: Option<String>,
+    pub workflow_id: Option<String>,
+    pub layout_id: Option<String>,
+}
+
+/// Render a stored workflow as engine workflow YAML, the format
+/// [`parse_workflow`] reads.
+///
+/// Stored rows keep the engine's step settings alongside the domain step
+/// fields; rows created before that are exported from the domain fields
+/// alone. Exit steps without a transition to `_complete` get one. Workflow
+/// hooks have no YAML form and are not exported.
+pub fn workflow_to_yaml(definition: &WorkflowDefinition) -> Result<String, ApiError> {
+    let config = workflow_config(definition)
+        .map_err(|e| ApiError::Internal(anyhow::anyhow!("stored workflow: {e}")))?;
+    serde_yml::to_string(&config).map_err(|e| ApiError::Internal(e.into()))
+}
+
+/// Parse and validate a bundled workflow's engine YAML into a workflow row
+pub fn workflow_from_yaml(yaml: &str) -> Result<WorkflowDefinition, ApiError> {
+    let config = parse_workflow(yaml)
+        .map_err(|e| ApiError::bad_request("config.invalid_workflow", e.to_string()))?;
+    workflow_definition(&config).map_err(|e| ApiError::Internal(e.into()))
+}
+
+/// A workflow with a single annotation step, for tests that need one stored
+#[cfg(test)]
+pub(crate) fn single_step_workflow(name: &str) -> WorkflowDefinition {
+    workflow_from_yaml(&format!(
+        r#"
+version: "1.0"
+name: {name}
+workflow_type: single
+steps:
+  - id: annotate
+    name: Annotate
+    step_type: annotation
+transitions:
+  - from: annotate
+    to: _complete
+"#
+    ))
+    .unwrap()
+}
+
+fn workflow_config(definition: &WorkflowDefinition) -> Result<WorkflowConfig, serde_json::Error> {
+    let mut steps = Vec::new();
+    for value in as_array(&definition.steps) {
+        let step: WorkflowStep = serde_json::from_value(value.clone())?;
+        let mut settings: StepSettingsConfig = match value.get("settings") {
+            Some(settings) => serde_json::from_value(settings.clone())?,
+            None => StepSettingsConfig::default(),
+        };
+        // The domain fields are what the rest of the system reads, so they win
+        if let Some(timeout) = &step.timeout {
+            settings.timeout_minutes = u32::try_from((timeout.duration_seconds + 59) / 60).ok();
+        }
+        if !step.assignment.required_skills.is_empty() {
+            settings.required_skills = Some(step.assignment.required_skills.clone());
+        }
+        if let Some(count) = step.completion_criteria.required_count {
+            settings.min_annotators = u32::try_from(count).ok();
+        }
+        if let Some(threshold) = step
+            .consensus
+            .as_ref()
+            .map(|c| c.threshold)
+            .or(step.completion_criteria.min_agreement)
+        {
+            settings.threshold = Some(threshold);
+        }
+        if let Some(ui) = &step.ui {
+            settings.show_previous = Some(ui.show_previous);
+        }
+        steps.push(StepConfig {
+            id: step.step_id,
+            name: step.name,
+            step_type: step.step_type,
+            settings,
+            ref_name: None,
+            overrides: None,
+        });
+    }
+
+    let mut transitions = Vec::new();
+    for value in as_array(&definition.transitions) {
+        let stored: StoredTransition = serde_json::from_value(value.clone())?;
+        let condition = stored
+            .condition
+            .filter(|c| c.condition_type != "always")
+            .map(|c| TransitionConditionConfig {
+                condition_type: c.condition_type,
+                expression: c.expression,
+                threshold: c.threshold,
+            });
+        transitions.push(TransitionConfig {
+            from: stored.from_step_id,
+            to: stored.to_step_id,
+            condition,
+        });
+    }
+    for exit in as_array(&definition.exit_step_ids)
+        .iter()
+        .filter_map(serde_json::Value::as_str)
+    {
+        if !transitions
+            .iter()
+            .any(|t| t.from == exit && t.to == "_complete")
+        {
+            transitions.push(TransitionConfig {
+                from: exit.to_string(),
+                to: "_complete".to_string(),
+                condition: None,
+            });
+        }
+    }
+
+    let mut settings: WorkflowSettingsConfig =
+        serde_json::from_value(definition.settings.clone()).unwrap_or_default();
+    if let Some(max_retries) = definition
+        .settings
+        .get("max_retries")
+        .and_then(|v| v.as_u64())
+    {
+        settings.max_retries = u32::try_from(max_retries).ok();
+    }
+    if let Some(seconds) = definition
+        .settings
+        .get("default_timeout_seconds")
+        .and_then(|v| v.as_u64())
+    {
+        settings.default_timeout_minutes = u32::try_from(seconds.div_ceil(60)).ok();
+    }
+
+    Ok(WorkflowConfig {
+        version: "1.0".to_string(),
+        name: definition.name.clone(),
+        workflow_type: serde_json::from_value(serde_json::Value::String(
+            definition.workflow_type.clone(),
+        ))?,
+        settings,
+        entry_step: Some(definition.entry_step_id.clone()),
+        steps,
+        transitions,
+        step_library: Vec::new(),
+    })
+}
+
+fn workflow_definition(config: &WorkflowConfig) -> Result<WorkflowDefinition, serde_json::Error> {
+    let mut steps = Vec::new();
+    for step in &config.steps {
+        let settings = &step.settings;
+        let criteria_type = match step.step_type {
+            StepType::Annotation => CompletionCriteriaType::AnnotationCount,
+            StepType::Review | StepType::Adjudication => CompletionCriteriaType::ReviewDecision,
+            StepType::AutoProcess | StepType::Conditional | StepType::SubWorkflow => {
+                CompletionCriteriaType::Auto
+            }
+        };
+        let domain = WorkflowStep {
+            step_id: step.id.clone(),
+            name: step.name.clone(),
+            step_type: step.step_type,
+            completion_criteria: CompletionCriteria {
+                criteria_type,
+                required_count: settings.min_annotators.and_then(|n| i32::try_from(n).ok()),
+                min_agreement: settings.threshold,
+            },
+            assignment: AssignmentConfig {
+                mode: AssignmentMode::Auto,
+                load_balancing: LoadBalancingStrategy::LeastLoaded,
+                required_skills: settings.required_skills.clone().unwrap_or_default(),
+                exclude_previous_annotators: false,
+            },
+            consensus: None,
+            timeout: settings.timeout_minutes.map(|minutes| TimeoutConfig {
+                duration_seconds: i64::from(minutes) * 60,
+                action: TimeoutAction::Retry,
+                max_retries: None,
+            }),
+            ui: settings.show_previous.map(|show_previous| StepUiConfig {
+                layout_override: None,
+                show_previous,
+                show_context: false,
+                editable_fields: None,
+            }),
+        };
+        let mut value = serde_json::to_value(&domain)?;
+        value["settings"] = serde_json::to_value(settings)?;
+        steps.push(value);
+    }
+
+    let transitions = config
+        .transitions
+        .iter()
+        .map(|t| {
+            let condition = t.condition.as_ref();
+            serde_json::to_value(StoredTransition {
+                from_step_id: t.from.clone(),
+                to_step_id: t.to.clone(),
+                condition: Some(StoredCondition {
+                    condition_type: condition
+                        .map_or_else(|| "always".to_string(), |c| c.condition_type.clone()),
+                    expression: condition.and_then(|c| c.expression.clone()),
+                    threshold: condition.and_then(|c| c.threshold),
+                }),
+            })
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let mut exit_step_ids: Vec<&str> = Vec::new();
+    for transition in config.transitions.iter().filter(|t| t.to == "_complete") {
+        if !exit_step_ids.contains(&transition.from.as_str()) {
+            exit_step_ids.push(&transition.from);
+        }
+    }
+
+    let mut settings = serde_json::to_value(&config.settings)?;
+    settings["allow_parallel_steps"] = false.into();
+    settings["max_retries"] = config.settings.max_retries.unwrap_or(0).into();
+    settings["default_timeout_seconds"] = config
+        .settings
+        .default_timeout_minutes
+        .map(|minutes| u64::from(minutes) * 60)
+        .into();
+
+    let workflow_type = serde_json::to_value(config.workflow_type)?;
+    Ok(WorkflowDefinition {
+        name: config.name.clone(),
+        workflow_type: workflow_type.as_str().unwrap_or_default().to_string(),
+        entry_step_id: config.entry_step_id().unwrap_or_default().to_string(),
+        exit_step_ids: serde_json::to_value(exit_step_ids)?,
+        steps: serde_json::Value::Array(steps),
+        transitions: serde_json::Value::Array(transitions),
+        settings,
+        hooks: serde_json::to_value(WorkflowHooks::default())?,
+    })
+}
+
+fn as_array(value: &serde_json::Value) -> &[serde_json::Value] {
+    value.as_array().map_or(&[], Vec::as_slice)
+}
+
+/// A transition as stored in a workflow row. The condition type is kept as
+/// text because the engine accepts more kinds than the domain enum names.
+#[derive(Debug, Serialize, Deserialize)]
+struct StoredTransition {
+    from_step_id: String,
+    to_step_id: String,
+    #[serde(default)]
+    condition: Option<StoredCondition>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct StoredCondition {
+    condition_type: String,
+    #[serde(default)]
+    expression: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    threshold: Option<f64>,
+}
+
+/// Build a bundle from the configuration of one of an organization's projects
+pub async fn export_project_config(
+    pool: &PgPool,
+    org_id: OrgId,
+    project_id: &ProjectId,
+) -> Result<ProjectConfigBundle, ApiError> {
+    let project = PgProjectRepository::new(pool.clone())
+        .in_org(org_id)
+        .find_by_id(project_id)
+        .await
+        .map_err(|e| ApiError::Internal(anyhow::anyhow!("{}", e)))?
+        .ok_or_else(|| ApiError::not_found("project", project_id.to_string()))?;
+
+    let project_type = match &project.project_type_id {
+        Some(id) => PgProjectTypeRepository::new(pool.clone())
+            .in_org(org_id)
+            .find_by_id(id)
+            .await
+            .map_err(|e| ApiError::Internal(anyhow::anyhow!("{}", e)))?
+            .map(|pt| BundledProjectType {
+                name: pt.name,
+                description: pt.description,
+                input_schema: pt.input_schema,
+                output_schema: pt.output_schema,
+                estimated_duration_seconds: pt.estimated_duration_seconds,
+                difficulty_level: pt.difficulty_level,
+                skill_requirements: pt.skill_requirements,
+                allow_overlapping_spans: pt.allow_overlapping_spans,
+            }),
+        None => None,
+    };
+
+    let workflow = match &project.workflow_id {
+        Some(id) => PgWorkflowRepository::new(pool.clone())
+            .find_definition(id)
+            .await
+            .map_err(|e| ApiError::Internal(e.into()))?
+            .map(|definition| workflow_to_yaml(&definition))
+            .transpose()?
+            .map(|yaml| BundledWorkflow { yaml }),
+        None => None,
+    };
+
+    // Layout IDs are free text; only ones naming a stored layout are exported
+    let layout = match project
+        .layout_id
+        .as_deref()
+        .and_then(|id| id.parse::<uuid::Uuid>().ok())
+    {
+        Some(id) => PgLayoutRepository::new(pool.clone())
+            .find_current_definition(id)
+            .await
+            .map_err(|e| ApiError::Internal(e.into()))?,
+        None => None,
+    };
+
+    Ok(ProjectConfigBundle {
+        format_version: CONFIG_BUNDLE_VERSION,
+        project: BundledProject {
+            name: project.name,
+            description: project.description,
+            tags: project.tags,
+            documentation: project.documentation,
+            settings: project.settings,
+        },
+        project_type,
+        workflow,
+        layout,
+    })
+}
+
+/// Create a new project, and new copies of its project type, workflow and
+/// layout, from a bundle, in an organization.
+///
+/// Everything is created in one transaction, so a failure part way leaves
+/// nothing behind.
+pub async fn import_project_config(
+    pool: &PgPool,
+    org_id: OrgId,
+    bundle: &ProjectConfigBundle,
+    created_by: &UserId,
+) -> Result<ImportedConfig, ApiError> {
+    if bundle.format_version != CONFIG_BUNDLE_VERSION {
+        return Err(ApiError::bad_request(
+            "config.unsupported_version",
+            format!(
+                "Bundle format {} is not supported; expected {CONFIG_BUNDLE_VERSION}",
+                bundle.format_version
+            ),
+        ));
+    }
+    if bundle.project.name.trim().is_empty() {
+        return Err(ApiError::bad_request(
+            "validation.name_required",
+            "Project name is required",
+        ));
+    }
+    // Parse up front so a bad workflow fails before anything is written
+    let workflow = bundle
+        .workflow
+        .as_ref()
+        .map(|w| workflow_from_yaml(&w.yaml))
+        .transpose()?;
+
+    let internal = |e: sqlx::Error| ApiError::Internal(e.into());
+    let mut tx = pool.begin().await.map_err(internal)?;
+
+    let project_type_id = match &bundle.project_type {
+        Some(project_type) => {
+            Some(import_project_type(pool, &mut tx, org_id, project_type, created_by).await?)
+        }
+        None => None,
+    };
+
+    let workflow_id = match &workflow {
+        Some(definition) => Some(
+            PgWorkflowRepository::new(pool.clone())
+                .create_from_definition_in(&mut tx, definition)
+                .await
+                .map_err(internal)?,
+        ),
+        None => None,
+    };
+
+    let layout_id = match &bundle.layout {
+        Some(layout) => Some(
+            PgLayoutRepository::new(pool.clone())
+                .create_from_definition_in(&mut tx, layout, project_type_id.as_ref(), created_by)
+                .await
+                .map_err(internal)?
+                .to_string(),
+        ),
+        None => None,
+    };
+
+    let repo = PgProjectRepository::new(pool.clone()).in_org(org_id);
+    let project = repo
+        .create_minimal_in(
+            &mut tx,
+            &bundle.project.name,
+            bundle.project.description.as_deref(),
+            created_by,
+        )
+        .await
+        .map_err(|e| ApiError::Internal(anyhow::anyhow!("{}", e)))?;
+    let update = ExtendedProjectUpdate {
+        project_type_id: project_type_id.clone(),
+        tags: Some(bundle.project.tags.clone()),
+        documentation: bundle.project.documentation.clone(),
+        settings: Some(bundle.project.settings.clone()),
+        workflow_id: workflow_id.clone(),
+        layout_id: layout_id.clone(),
+        ..Default::default()
+    };
+    repo.update_extended_in(&mut tx, &project.project_id, &update)
+        .await
+        .map_err(|e| ApiError::Internal(anyhow::anyhow!("{}", e)))?;
+
+    tx.commit().await.map_err(internal)?;
+
+    Ok(ImportedConfig {
+        project_id: project.project_id.to_string(),
+        project_type_id: project_type_id.as_ref().map(ToString::to_string),
+        workflow_id: workflow_id.as_ref().map(WorkflowId::to_string),
+        layout_id,
+    })
+}
+
+/// Create the bundled project type under the first free name. Each attempt
+/// runs in a savepoint so a taken name doesn't abort the import.
+async fn import_project_type(
+    pool: &PgPool,
+    tx: &mut Transaction<'_, Postgres>,
+    org_id: OrgId,
+    project_type: &BundledProjectType,
+    created_by: &UserId,
+) -> Result<ProjectTypeId, ApiError> {
+    let internal = |e: sqlx::Error| ApiError::Internal(e.into());
+    let repo = PgProjectTypeRepository::new(pool.clone()).in_org(org_id);
+    for attempt in 0..MAX_NAME_ATTEMPTS {
+        let input = CreateProjectType {
+            name: imported_name(&project_type.name, attempt),
+            description: project_type.description.clone(),
+            input_schema: Some(project_type.input_schema.clone()),
+            output_schema: Some(project_type.output_schema.clone()),
+            estimated_duration_seconds: project_type.estimated_duration_seconds,
+            difficulty_level: project_type.difficulty_level,
+            skill_requirements: Some(project_type.skill_requirements.clone()),
+            allow_overlapping_spans: Some(project_type.allow_overlapping_spans),
+            is_system: Some(false),
+        };
+        let mut savepoint = tx.begin().await.map_err(internal)?;
+        match repo
+            .create_in(&mut savepoint, &input, Some(created_by))
+            .await
+        {
+            Ok(created) => {
+                savepoint.commit().await.map_err(internal)?;
+                return Ok(created.project_type_id);
+            }
+            Err(CreateProjectTypeError::NameExists(_)) => {}
+            Err(e) => return Err(ApiError::Internal(anyhow::anyhow!("{}", e))),
+        }
+    }
+    Err(ApiError::conflict(format!(
+        "Project type '{}' already exists under every fallback name",
+        project_type.name
+    )))
+}
+
+/// `name`, then `name (imported)`, `name (imported 2)`, ...
+fn imported_name(name: &str, attempt: u32) -> String {
+    match attempt {
+        0 => name.to_string(),
+        1 => format!("{name} (imported)"),
+        n => format!("{name} (imported {n})"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const REVIEW_FLOW: &str = r#"
+version: "1.0"
+name: Review flow
+workflow_type: custom
+settings:
+  max_retries: 2
+  default_timeout_minutes: 30
+steps:
+  - id: annotate
+    name: Annotate
+    step_type: annotation
+    settings:
+      min_annotators: 2
+      required_skills: [ner]
+  - id: route
+    name: Route
+    step_type: conditional
+    settings:
+      condition: "annotation.label == 'hard'"
+  - id: review
+    name: Review
+    step_type: review
+    settings:
+      timeout_minutes: 45
+      show_previous: true
+transitions:
+  - from: annotate
+    to: route
+  - from: route
+    to: review
+    condition:
+      type: expression
+      expression: "annotation.label == 'hard'"
+  - from: route
+    to: _complete
+  - from: review
+    to: _complete
+    condition:
+      type: on_approved
+"#;
+
+    fn as_json(config: &WorkflowConfig) -> serde_json::Value {
+        serde_json::to_value(config).unwrap()
+    }
+
+    #[test]
+    fn test_workflow_yaml_round_trips() {
+        let definition = workflow_from_yaml(REVIEW_FLOW).unwrap();
+        assert_eq!(definition.entry_step_id, "annotate");
+        assert_eq!(
+            definition.exit_step_ids,
+            serde_json::json!(["route", "review"])
+        );
+        // The stored steps are readable as domain steps
+        let steps: Vec<WorkflowStep> = serde_json::from_value(definition.steps.clone()).unwrap();
+        assert_eq!(steps[0].assignment.required_skills, vec!["ner"]);
+        assert_eq!(steps[2].timeout.as_ref().unwrap().duration_seconds, 45 * 60);
+
+        let exported = workflow_to_yaml(&definition).unwrap();
+        let mut original = parse_workflow(REVIEW_FLOW).unwrap();
+        original.entry_step = Some("annotate".to_string());
+        assert_eq!(
+            as_json(&parse_workflow(&exported).unwrap()),
+            as_json(&original)
+        );
+        assert_eq!(workflow_from_yaml(&exported).unwrap(), definition);
+    }
+
+    #[test]
+    fn test_workflows_stored_without_engine_settings_export_valid_yaml() {
+        let definition = WorkflowDefinition {
+            name: "Single".to_string(),
+            workflow_type: "single".to_string(),
+            entry_step_id: "annotate".to_string(),
+            exit_step_ids: serde_json::json!(["annotate"]),
+            steps: serde_json::json!([{
+                "step_id": "annotate",
+                "name": "Annotate",
+                "step_type": "annotation",
+                "completion_criteria": { "criteria_type": "annotation_count", "required_count": 1, "min_agreement": null },
+                "assignment": { "mode": "auto", "load_balancing": "least_loaded", "required_skills": [], "exclude_previous_annotators": false },
+                "consensus": null,
+                "timeout": { "duration_seconds": 3600, "action": "retry", "max_retries": null },
+                "ui": null
+            }]),
+            transitions: serde_json::json!([]),
+            settings: serde_json::json!({ "max_retries": 3 }),
+            hooks: serde_json::json!({}),
+        };
+        let config = parse_workflow(&workflow_to_yaml(&definition).unwrap()).unwrap();
+        assert_eq!(config.steps[0].settings.timeout_minutes, Some(60));
+        assert_eq!(config.steps[0].settings.min_annotators, Some(1));
+        assert_eq!(config.settings.max_retries, Some(3));
+        assert_eq!(config.transitions[0].to, "_complete");
+    }
+
+    #[test]
+    fn test_invalid_workflow_yaml_is_rejected() {
+        // A step with no way out never completes
+        let dead_end = REVIEW_FLOW.replace("  - from: review\n    to: _complete", "");
+        for yaml in ["steps: [", dead_end.as_str()] {
+            assert!(matches!(
+                workflow_from_yaml(yaml),
+                Err(ApiError::BadRequest {
+                    code: "config.invalid_workflow",
+                    ..
+                })
+            ));
+        }
+    }
+
+    #[test]
+    fn test_imported_names_fall_back_with_suffixes() {
+        assert_eq!(imported_name("NER", 0), "NER");
+        assert_eq!(imported_name("NER", 1), "NER (imported)");
+        assert_eq!(imported_name("NER", 2), "NER (imported 2)");
+    }
+}
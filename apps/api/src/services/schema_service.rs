@@ -3,15 +3,81 @@
 //! Provides centralized schema compilation, caching, and validation
 //! for task input and annotation output data.
 
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::hash::{Hash, Hasher};
 use std::sync::Arc;
 
+use async_trait::async_trait;
 use jsonschema::Validator;
+use sqlx::PgPool;
 use thiserror::Error;
 use tokio::sync::RwLock;
+use uuid::Uuid;
+
+use glyph_db::PgProjectTypeRepository;
+use glyph_domain::{
+    check_span_overlap, unique_span_ids, BoundingBox, Polygon, Relation, SchemaAmbiguity,
+    SchemaInferenceResult, Span, TaskId, ValidationError, ValidationResult,
+};
+use glyph_workflow_engine::SubmissionValidator;
+
+/// Schema `format` marking a field as a [`BoundingBox`] annotation value
+pub const BOUNDING_BOX_FORMAT: &str = "bounding_box";
+
+/// Schema `format` marking a field as a [`Polygon`] annotation value
+pub const POLYGON_FORMAT: &str = "polygon";
+
+/// Schema `format` marking a field as a text [`Span`] annotation value
+pub const SPAN_FORMAT: &str = "span";
+
+/// Schema `format` marking a field as a [`Relation`] between spans
+pub const RELATION_FORMAT: &str = "relation";
+
+/// Schema `format` marking the input field that span offsets index into
+pub const DOCUMENT_FORMAT: &str = "document";
+
+/// Input field spans index into when no field has the document format
+pub const DEFAULT_DOCUMENT_FIELD: &str = "text";
+
+/// What annotation values are checked against, when known
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct AnnotationBounds {
+    /// Image (width, height) in pixels, for bounding boxes
+    pub image_size: Option<(f64, f64)>,
+    /// Document length in characters, for spans
+    pub doc_len: Option<usize>,
+    /// Reject spans in one array field that overlap each other
+    pub forbid_overlapping_spans: bool,
+}
 
-use glyph_domain::{SchemaAmbiguity, SchemaInferenceResult, ValidationError, ValidationResult};
+impl AnnotationBounds {
+    /// Bounds for annotations on an image
+    #[must_use]
+    pub const fn image(width: f64, height: f64) -> Self {
+        Self {
+            image_size: Some((width, height)),
+            doc_len: None,
+            forbid_overlapping_spans: false,
+        }
+    }
+
+    /// Bounds for annotations on a document of `doc_len` characters
+    #[must_use]
+    pub const fn document(doc_len: usize) -> Self {
+        Self {
+            image_size: None,
+            doc_len: Some(doc_len),
+            forbid_overlapping_spans: false,
+        }
+    }
+
+    /// These bounds, rejecting overlapping spans unless `allow` is set
+    #[must_use]
+    pub const fn with_overlapping_spans(mut self, allow: bool) -> Self {
+        self.forbid_overlapping_spans = !allow;
+        self
+    }
+}
 
 /// Errors that can occur during schema operations
 #[derive(Debug, Error)]
@@ -74,10 +140,30 @@ impl SchemaValidationService {
         &self,
         schema: &serde_json::Value,
         data: &serde_json::Value,
+    ) -> Result<ValidationResult, SchemaError> {
+        self.validate_output(schema, data, AnnotationBounds::default())
+            .await
+    }
+
+    /// Validate annotation output against a JSON Schema.
+    ///
+    /// Fields whose schema has `"format": "bounding_box"` are additionally
+    /// checked as [`BoundingBox`] values: dimensions must be non-negative and,
+    /// when the image size is known, the box must lie within it.
+    /// Fields with `"format": "polygon"` must be simple [`Polygon`]s. Fields
+    /// with `"format": "span"` must be ordered, non-negative [`Span`]s and,
+    /// when the document length is known, end within it; arrays of spans may
+    /// also be required not to overlap. Fields with `"format": "relation"`
+    /// must be [`Relation`]s between spans whose `id`s appear in `data`.
+    pub async fn validate_output(
+        &self,
+        schema: &serde_json::Value,
+        data: &serde_json::Value,
+        bounds: AnnotationBounds,
     ) -> Result<ValidationResult, SchemaError> {
         let validator = self.compile(schema).await?;
 
-        let errors: Vec<ValidationError> = validator
+        let mut errors: Vec<ValidationError> = validator
             .iter_errors(data)
             .map(|e| ValidationError {
                 path: e.instance_path.to_string(),
@@ -86,6 +172,26 @@ impl SchemaValidationService {
             })
             .collect();
 
+        // Only check geometry once the shape is known to be right
+        if errors.is_empty() {
+            let mut spans = Vec::new();
+            collect_spans(schema, data, "", &mut spans);
+            let (span_paths, spans): (Vec<String>, Vec<Span>) = spans.into_iter().unzip();
+            let span_ids = unique_span_ids(&spans).unwrap_or_else(|duplicate| {
+                errors.push(ValidationError {
+                    path: span_paths[duplicate.second].clone(),
+                    message: duplicate.to_string(),
+                    keyword: Some("uniqueId".to_string()),
+                });
+                spans.iter().filter_map(|span| span.id.clone()).collect()
+            });
+            let context = GeometryContext {
+                bounds: &bounds,
+                span_ids: &span_ids,
+            };
+            collect_geometry_errors(schema, data, "", &context, &mut errors);
+        }
+
         Ok(ValidationResult {
             is_valid: errors.is_empty(),
             errors,
@@ -284,6 +390,90 @@ impl Default for SchemaValidationService {
     }
 }
 
+/// Validates annotation submissions against the output schema of the task's
+/// project type, for [`glyph_workflow_engine::WorkflowOrchestrator`].
+///
+/// Tasks whose project has no project type accept any payload.
+pub struct OutputSchemaValidator {
+    repo: PgProjectTypeRepository,
+    schemas: Arc<SchemaValidationService>,
+}
+
+impl OutputSchemaValidator {
+    /// Create a validator sharing `schemas`' compiled validator cache
+    pub fn new(pool: PgPool, schemas: Arc<SchemaValidationService>) -> Self {
+        Self {
+            repo: PgProjectTypeRepository::new(pool),
+            schemas,
+        }
+    }
+}
+
+/// Length in characters of the document a task's spans index into.
+///
+/// The document is the input field whose schema has `"format": "document"`,
+/// or the `text` field when none does. Returns `None` when that field isn't a
+/// string, so spans are only checked for ordering.
+pub fn document_len(
+    input_schema: &serde_json::Value,
+    input_data: &serde_json::Value,
+) -> Option<usize> {
+    let field = input_schema
+        .get("properties")
+        .and_then(|p| p.as_object())
+        .and_then(|properties| {
+            properties.iter().find_map(|(key, schema)| {
+                (schema.get("format").and_then(|f| f.as_str()) == Some(DOCUMENT_FORMAT))
+                    .then_some(key.as_str())
+            })
+        })
+        .unwrap_or(DEFAULT_DOCUMENT_FIELD);
+    input_data
+        .get(field)
+        .and_then(|document| document.as_str())
+        .map(|document| document.chars().count())
+}
+
+/// Schema violations in an annotation submission
+pub async fn submission_violations(
+    schemas: &SchemaValidationService,
+    output_schema: &serde_json::Value,
+    submission: &serde_json::Value,
+    bounds: AnnotationBounds,
+) -> Result<Vec<ValidationError>, SchemaError> {
+    Ok(schemas
+        .validate_output(output_schema, submission, bounds)
+        .await?
+        .errors)
+}
+
+#[async_trait]
+impl SubmissionValidator for OutputSchemaValidator {
+    async fn validate(
+        &self,
+        task_id: Uuid,
+        _step_id: &str,
+        submission: &serde_json::Value,
+    ) -> Result<Vec<ValidationError>, String> {
+        let schema = self
+            .repo
+            .find_output_schema_for_task(&TaskId::from_uuid(task_id))
+            .await
+            .map_err(|e| e.to_string())?;
+        let Some(schema) = schema else {
+            return Ok(Vec::new());
+        };
+        let bounds = AnnotationBounds {
+            doc_len: document_len(&schema.input_schema, &schema.input_data),
+            ..AnnotationBounds::default()
+        }
+        .with_overlapping_spans(schema.allow_overlapping_spans);
+        submission_violations(&self.schemas, &schema.output_schema, submission, bounds)
+            .await
+            .map_err(|e| e.to_string())
+    }
+}
+
 /// Get the JSON type name for a value
 fn json_type(value: &serde_json::Value) -> &'static str {
     match value {
@@ -302,10 +492,229 @@ fn json_type(value: &serde_json::Value) -> &'static str {
     }
 }
 
+/// What annotation values in one submission are checked against
+struct GeometryContext<'a> {
+    bounds: &'a AnnotationBounds,
+    /// `id`s of every span value in the submission
+    span_ids: &'a HashSet<String>,
+}
+
+/// Walk `data` alongside its schema, collecting each well-formed span value
+/// with its path
+fn collect_spans(
+    schema: &serde_json::Value,
+    data: &serde_json::Value,
+    path: &str,
+    spans: &mut Vec<(String, Span)>,
+) {
+    if schema.get("format").and_then(|f| f.as_str()) == Some(SPAN_FORMAT) {
+        if let Ok(span) = serde_json::from_value::<Span>(data.clone()) {
+            spans.push((path.to_string(), span));
+        }
+        return;
+    }
+    if let (Some(properties), Some(object)) = (
+        schema.get("properties").and_then(|p| p.as_object()),
+        data.as_object(),
+    ) {
+        for (key, field_schema) in properties {
+            if let Some(value) = object.get(key) {
+                collect_spans(field_schema, value, &format!("{path}/{key}"), spans);
+            }
+        }
+    }
+    if let (Some(items), Some(array)) = (schema.get("items"), data.as_array()) {
+        for (i, value) in array.iter().enumerate() {
+            collect_spans(items, value, &format!("{path}/{i}"), spans);
+        }
+    }
+}
+
+/// Walk `data` alongside its schema, validating bounding-box, polygon, span
+/// and relation fields
+fn collect_geometry_errors(
+    schema: &serde_json::Value,
+    data: &serde_json::Value,
+    path: &str,
+    context: &GeometryContext<'_>,
+    errors: &mut Vec<ValidationError>,
+) {
+    let bounds = context.bounds;
+    let (image_width, image_height) = bounds.image_size.unwrap_or((f64::INFINITY, f64::INFINITY));
+    let format = schema.get("format").and_then(|f| f.as_str());
+    let result = match format {
+        Some(BOUNDING_BOX_FORMAT) => Some(
+            serde_json::from_value::<BoundingBox>(data.clone())
+                .map_err(|e| e.to_string())
+                .and_then(|bbox| {
+                    bbox.validate(image_width, image_height)
+                        .map_err(|e| e.to_string())
+                }),
+        ),
+        Some(POLYGON_FORMAT) => Some(
+            serde_json::from_value::<Polygon>(data.clone())
+                .map_err(|e| e.to_string())
+                .and_then(|polygon| polygon.validate().map_err(|e| e.to_string())),
+        ),
+        Some(SPAN_FORMAT) => Some(
+            serde_json::from_value::<Span>(data.clone())
+                .map_err(|e| e.to_string())
+                .and_then(|span| {
+                    span.validate(bounds.doc_len.unwrap_or(usize::MAX))
+                        .map_err(|e| e.to_string())
+                }),
+        ),
+        Some(RELATION_FORMAT) => Some(
+            serde_json::from_value::<Relation>(data.clone())
+                .map_err(|e| e.to_string())
+                .and_then(|relation| {
+                    relation
+                        .validate(context.span_ids)
+                        .map_err(|e| e.to_string())
+                }),
+        ),
+        _ => None,
+    };
+    if let Some(result) = result {
+        if let Err(message) = result {
+            errors.push(ValidationError {
+                path: path.to_string(),
+                message,
+                keyword: Some("format".to_string()),
+            });
+        }
+        return;
+    }
+
+    if let (Some(properties), Some(object)) = (
+        schema.get("properties").and_then(|p| p.as_object()),
+        data.as_object(),
+    ) {
+        for (key, field_schema) in properties {
+            if let Some(value) = object.get(key) {
+                let field_path = format!("{path}/{key}");
+                collect_geometry_errors(field_schema, value, &field_path, context, errors);
+            }
+        }
+    }
+
+    if let (Some(items), Some(array)) = (schema.get("items"), data.as_array()) {
+        for (i, value) in array.iter().enumerate() {
+            let item_path = format!("{path}/{i}");
+            collect_geometry_errors(items, value, &item_path, context, errors);
+        }
+        if bounds.forbid_overlapping_spans
+            && items.get("format").and_then(|f| f.as_str()) == Some(SPAN_FORMAT)
+        {
+            collect_span_overlap_error(array, path, errors);
+        }
+    }
+}
+
+/// Report the first pair of overlapping spans in an array of span values.
+///
+/// Arrays with unparseable spans are skipped; those are reported per item.
+fn collect_span_overlap_error(
+    array: &[serde_json::Value],
+    path: &str,
+    errors: &mut Vec<ValidationError>,
+) {
+    let spans: Result<Vec<Span>, _> = array
+        .iter()
+        .map(|value| serde_json::from_value::<Span>(value.clone()))
+        .collect();
+    if let Ok(spans) = spans {
+        if let Err(overlap) = check_span_overlap(&spans) {
+            errors.push(ValidationError {
+                path: path.to_string(),
+                message: overlap.to_string(),
+                keyword: Some("overlap".to_string()),
+            });
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[tokio::test]
+    async fn test_submission_missing_required_field_has_violation() {
+        let service = SchemaValidationService::new();
+        let output_schema = serde_json::json!({
+            "type": "object",
+            "properties": {
+                "label": {"type": "string"},
+                "confidence": {"type": "number"}
+            },
+            "required": ["label"]
+        });
+
+        let violations = submission_violations(
+            &service,
+            &output_schema,
+            &serde_json::json!({"confidence": 0.8}),
+            AnnotationBounds::default(),
+        )
+        .await
+        .unwrap();
+        assert_eq!(violations.len(), 1);
+        assert!(violations[0].message.contains("label"));
+
+        let violations = submission_violations(
+            &service,
+            &output_schema,
+            &serde_json::json!({"label": "cat", "confidence": 0.8}),
+            AnnotationBounds::default(),
+        )
+        .await
+        .unwrap();
+        assert!(violations.is_empty());
+    }
+
+    #[test]
+    fn test_document_len_uses_document_field_or_text() {
+        let data = serde_json::json!({"body": "héllo world", "text": "hi"});
+        let schema = serde_json::json!({
+            "type": "object",
+            "properties": {"body": {"type": "string", "format": "document"}}
+        });
+        assert_eq!(document_len(&schema, &data), Some(11));
+        assert_eq!(document_len(&serde_json::json!({}), &data), Some(2));
+        assert_eq!(
+            document_len(&serde_json::json!({}), &serde_json::json!({"text": 3})),
+            None
+        );
+    }
+
+    #[tokio::test]
+    async fn test_submission_spans_are_bounded_by_task_document() {
+        let service = SchemaValidationService::new();
+        let output_schema = serde_json::json!({
+            "type": "object",
+            "properties": {
+                "entities": {"type": "array", "items": {"type": "object", "format": "span"}}
+            }
+        });
+        let input = serde_json::json!({"text": "Hello world"});
+        let bounds = AnnotationBounds {
+            doc_len: document_len(&serde_json::json!({}), &input),
+            ..AnnotationBounds::default()
+        };
+
+        let violations = submission_violations(
+            &service,
+            &output_schema,
+            &serde_json::json!({"entities": [{"start": 6, "end": 11}, {"start": 6, "end": 12}]}),
+            bounds,
+        )
+        .await
+        .unwrap();
+        assert_eq!(violations.len(), 1);
+        assert_eq!(violations[0].path, "/entities/1");
+        assert!(violations[0].message.contains("past the end"));
+    }
+
     #[tokio::test]
     async fn test_validate_valid_data() {
         let service = SchemaValidationService::new();
@@ -375,4 +784,233 @@ mod tests {
         assert!(props.get("name").is_some());
         assert!(props.get("age").is_some());
     }
+
+    fn bounding_box_schema() -> serde_json::Value {
+        serde_json::json!({
+            "type": "object",
+            "properties": {
+                "boxes": {
+                    "type": "array",
+                    "items": {
+                        "type": "object",
+                        "format": "bounding_box",
+                        "required": ["x", "y", "w", "h"]
+                    }
+                }
+            }
+        })
+    }
+
+    #[tokio::test]
+    async fn test_validate_output_accepts_boxes_within_image() {
+        let service = SchemaValidationService::new();
+        let data = serde_json::json!({
+            "boxes": [{"x": 0, "y": 0, "w": 640, "h": 480}, {"x": 10.5, "y": 20, "w": 5, "h": 5}]
+        });
+
+        let result = service
+            .validate_output(
+                &bounding_box_schema(),
+                &data,
+                AnnotationBounds::image(640.0, 480.0),
+            )
+            .await
+            .unwrap();
+        assert!(result.is_valid);
+    }
+
+    #[tokio::test]
+    async fn test_validate_output_rejects_out_of_bounds_box() {
+        let service = SchemaValidationService::new();
+        let data = serde_json::json!({
+            "boxes": [{"x": 0, "y": 0, "w": 10, "h": 10}, {"x": 600, "y": 0, "w": 100, "h": 10}]
+        });
+
+        let result = service
+            .validate_output(
+                &bounding_box_schema(),
+                &data,
+                AnnotationBounds::image(640.0, 480.0),
+            )
+            .await
+            .unwrap();
+        assert!(!result.is_valid);
+        assert_eq!(result.errors.len(), 1);
+        assert_eq!(result.errors[0].path, "/boxes/1");
+    }
+
+    #[tokio::test]
+    async fn test_validate_rejects_negative_box_without_image_size() {
+        let service = SchemaValidationService::new();
+        let data = serde_json::json!({"boxes": [{"x": 0, "y": 0, "w": -1, "h": 10}]});
+
+        let result = service
+            .validate(&bounding_box_schema(), &data)
+            .await
+            .unwrap();
+        assert!(!result.is_valid);
+        assert_eq!(result.errors[0].path, "/boxes/0");
+    }
+
+    #[tokio::test]
+    async fn test_validate_rejects_self_intersecting_polygon() {
+        let service = SchemaValidationService::new();
+        let schema = serde_json::json!({
+            "type": "object",
+            "properties": {
+                "mask": {"type": "object", "format": "polygon", "required": ["points"]}
+            }
+        });
+        let data = serde_json::json!({
+            "mask": {"points": [[0, 0], [10, 10], [10, 0], [0, 10]]}
+        });
+
+        let result = service.validate(&schema, &data).await.unwrap();
+        assert!(!result.is_valid);
+        assert_eq!(result.errors[0].path, "/mask");
+        assert!(result.errors[0].message.contains("self-intersecting"));
+    }
+
+    fn span_schema() -> serde_json::Value {
+        serde_json::json!({
+            "type": "object",
+            "properties": {
+                "entities": {
+                    "type": "array",
+                    "items": {"type": "object", "format": "span", "required": ["start", "end"]}
+                }
+            }
+        })
+    }
+
+    #[tokio::test]
+    async fn test_validate_output_rejects_inverted_and_out_of_bounds_spans() {
+        let service = SchemaValidationService::new();
+        let data = serde_json::json!({
+            "entities": [
+                {"start": 0, "end": 5},
+                {"start": 9, "end": 4},
+                {"start": 6, "end": 40}
+            ]
+        });
+
+        let result = service
+            .validate_output(&span_schema(), &data, AnnotationBounds::document(11))
+            .await
+            .unwrap();
+        let paths: Vec<&str> = result.errors.iter().map(|e| e.path.as_str()).collect();
+        assert_eq!(paths, ["/entities/1", "/entities/2"]);
+        assert!(result.errors[0].message.contains("after its end"));
+        assert!(result.errors[1].message.contains("past the end"));
+    }
+
+    #[tokio::test]
+    async fn test_overlapping_spans_follow_project_type_policy() {
+        let service = SchemaValidationService::new();
+        let data = serde_json::json!({
+            "entities": [
+                {"start": 0, "end": 5},
+                {"start": 6, "end": 11},
+                {"start": 3, "end": 7}
+            ]
+        });
+
+        let allowed = AnnotationBounds::document(11).with_overlapping_spans(true);
+        let result = service
+            .validate_output(&span_schema(), &data, allowed)
+            .await
+            .unwrap();
+        assert!(result.is_valid);
+
+        let strict = AnnotationBounds::document(11).with_overlapping_spans(false);
+        let result = service
+            .validate_output(&span_schema(), &data, strict)
+            .await
+            .unwrap();
+        assert_eq!(result.errors.len(), 1);
+        assert_eq!(result.errors[0].path, "/entities");
+        assert_eq!(result.errors[0].keyword.as_deref(), Some("overlap"));
+        assert!(result.errors[0]
+            .message
+            .contains("span 0 (0..5) overlaps span 2 (3..7)"));
+    }
+
+    fn relation_schema() -> serde_json::Value {
+        serde_json::json!({
+            "type": "object",
+            "properties": {
+                "entities": {
+                    "type": "array",
+                    "items": {"type": "object", "format": "span", "required": ["id", "start", "end"]}
+                },
+                "relations": {
+                    "type": "array",
+                    "items": {
+                        "type": "object",
+                        "format": "relation",
+                        "required": ["from_span_id", "to_span_id", "label"]
+                    }
+                }
+            }
+        })
+    }
+
+    #[tokio::test]
+    async fn test_relation_between_spans_in_annotation_is_valid() {
+        let service = SchemaValidationService::new();
+        let data = serde_json::json!({
+            "entities": [
+                {"id": "person", "start": 0, "end": 5},
+                {"id": "org", "start": 15, "end": 22}
+            ],
+            "relations": [{"from_span_id": "person", "to_span_id": "org", "label": "works_for"}]
+        });
+
+        let result = service.validate(&relation_schema(), &data).await.unwrap();
+        assert!(result.is_valid, "{:?}", result.errors);
+    }
+
+    #[tokio::test]
+    async fn test_duplicate_span_ids_are_rejected() {
+        let service = SchemaValidationService::new();
+        let data = serde_json::json!({
+            "entities": [
+                {"start": 0, "end": 5, "id": "person"},
+                {"start": 6, "end": 9, "id": "person"}
+            ],
+            "relations": [{"from_span_id": "person", "to_span_id": "person", "label": "same_as"}]
+        });
+
+        let result = service.validate(&relation_schema(), &data).await.unwrap();
+        assert_eq!(result.errors.len(), 1);
+        assert_eq!(result.errors[0].path, "/entities/1");
+        assert!(result.errors[0].message.contains("reuses id \"person\""));
+    }
+
+    #[tokio::test]
+    async fn test_relation_with_dangling_span_reference_is_rejected() {
+        let service = SchemaValidationService::new();
+        let data = serde_json::json!({
+            "entities": [{"id": "person", "start": 0, "end": 5}],
+            "relations": [
+                {"from_span_id": "person", "to_span_id": "person", "label": "same_as"},
+                {"from_span_id": "person", "to_span_id": "org", "label": "works_for"}
+            ]
+        });
+
+        let result = service.validate(&relation_schema(), &data).await.unwrap();
+        assert_eq!(result.errors.len(), 1);
+        assert_eq!(result.errors[0].path, "/relations/1");
+        assert!(result.errors[0].message.contains("\"org\""));
+    }
+
+    #[tokio::test]
+    async fn test_validate_rejects_negative_span_without_document() {
+        let service = SchemaValidationService::new();
+        let data = serde_json::json!({"entities": [{"start": -2, "end": 3}]});
+
+        let result = service.validate(&span_schema(), &data).await.unwrap();
+        assert!(!result.is_valid);
+        assert_eq!(result.errors[0].path, "/entities/0");
+    }
 }
@@ -0,0 +1,232 @@
+//! Storage backends for data sources
+//!
+//! Picks the [`StorageService`] that holds a data source's files from its
+//! config. File upload sources are kept on local disk under `STORAGE_ROOT`
+//! (default `./data`); S3 sources read their bucket directly, behind a
+//! circuit breaker shared by every source using the same bucket. Project
+//! exports are kept on local disk alongside uploads.
+//!
+//! S3 sources are read with the server's own credentials, so a source may
+//! only name a bucket the operator has granted its organization in
+//! `S3_ALLOWED_BUCKETS`; otherwise any tenant could read any bucket the
+//! server can reach.
+
+use std::collections::{HashMap, HashSet};
+use std::path::PathBuf;
+use std::sync::{Arc, LazyLock, Mutex, PoisonError};
+use std::time::Duration;
+
+use glyph_common::{
+    CircuitBreaker, CircuitBreakingStorage, LocalStorage, S3Credentials, S3Storage, StorageError,
+    StorageService,
+};
+use glyph_domain::{DataSource, DataSourceConfig, OrgId, ProjectId};
+
+use crate::error::ApiError;
+
+/// Root directory for locally stored files when `STORAGE_ROOT` isn't set
+pub const DEFAULT_STORAGE_ROOT: &str = "./data";
+
+/// Consecutive S3 failures before calls to a bucket are short-circuited
+const S3_FAILURE_THRESHOLD: u32 = 5;
+
+/// How long a failing bucket is short-circuited before it is probed again
+const S3_COOLDOWN: Duration = Duration::from_secs(30);
+
+/// Breakers by `region/bucket`, kept for the life of the process
+static S3_BREAKERS: LazyLock<Mutex<HashMap<String, Arc<CircuitBreaker>>>> =
+    LazyLock::new(Mutex::default);
+
+fn s3_breaker(bucket: &str, region: &str) -> Arc<CircuitBreaker> {
+    let mut breakers = S3_BREAKERS.lock().unwrap_or_else(PoisonError::into_inner);
+    Arc::clone(
+        breakers
+            .entry(format!("{region}/{bucket}"))
+            .or_insert_with(|| Arc::new(CircuitBreaker::new(S3_FAILURE_THRESHOLD, S3_COOLDOWN))),
+    )
+}
+
+/// Environment variable listing the S3 buckets data sources may use
+pub const S3_ALLOWED_BUCKETS_ENV: &str = "S3_ALLOWED_BUCKETS";
+
+static ALLOWED_BUCKETS: LazyLock<BucketAllowlist> = LazyLock::new(BucketAllowlist::from_env);
+
+/// S3 buckets organizations may read with the server's credentials
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct BucketAllowlist {
+    /// Buckets any organization may use, for single-tenant installs
+    shared: HashSet<String>,
+    /// Buckets granted to one organization
+    per_org: HashSet<(OrgId, String)>,
+}
+
+impl BucketAllowlist {
+    /// Load the allowlist from the environment; unset allows nothing
+    #[must_use]
+    pub fn from_env() -> Self {
+        std::env::var(S3_ALLOWED_BUCKETS_ENV)
+            .map(|v| Self::from_list(&v))
+            .unwrap_or_default()
+    }
+
+    /// Parse a comma-separated list of `bucket` (any organization) or
+    /// `org_<uuid>/bucket` (that organization only) entries. Entries naming
+    /// an invalid organization are ignored.
+    #[must_use]
+    pub fn from_list(list: &str) -> Self {
+        let mut allowlist = Self::default();
+        for entry in list.split(',').map(str::trim).filter(|e| !e.is_empty()) {
+            match entry.split_once('/') {
+                Some((org, bucket)) => match org.parse::<OrgId>() {
+                    Ok(org_id) => {
+                        allowlist.per_org.insert((org_id, bucket.to_string()));
+                    }
+                    Err(e) => {
+                        tracing::warn!(entry, error = %e, "Ignoring {S3_ALLOWED_BUCKETS_ENV} entry");
+                    }
+                },
+                None => {
+                    allowlist.shared.insert(entry.to_string());
+                }
+            }
+        }
+        allowlist
+    }
+
+    #[must_use]
+    pub fn allows(&self, org_id: OrgId, bucket: &str) -> bool {
+        self.shared.contains(bucket) || self.per_org.contains(&(org_id, bucket.to_string()))
+    }
+}
+
+/// Reject configs naming a bucket that `org_id` hasn't been granted
+pub fn ensure_bucket_allowed(org_id: OrgId, config: &DataSourceConfig) -> Result<(), ApiError> {
+    check_bucket(&ALLOWED_BUCKETS, org_id, config)
+}
+
+fn check_bucket(
+    allowlist: &BucketAllowlist,
+    org_id: OrgId,
+    config: &DataSourceConfig,
+) -> Result<(), ApiError> {
+    match config {
+        DataSourceConfig::S3 { bucket, .. } if !allowlist.allows(org_id, bucket) => {
+            Err(ApiError::bad_request(
+                "data_source.bucket_not_allowed",
+                format!(
+                    "S3 bucket '{bucket}' is not enabled for this organization; \
+                     ask an operator to add it to {S3_ALLOWED_BUCKETS_ENV}"
+                ),
+            ))
+        }
+        _ => Ok(()),
+    }
+}
+
+/// Directory holding locally stored files
+pub fn storage_root() -> PathBuf {
+    std::env::var("STORAGE_ROOT")
+        .unwrap_or_else(|_| DEFAULT_STORAGE_ROOT.to_string())
+        .into()
+}
+
+/// The storage backend holding a data source's files
+pub fn storage_for(data_source: &DataSource) -> Result<Arc<dyn StorageService>, ApiError> {
+    match &data_source.config {
+        DataSourceConfig::FileUpload { .. } => Ok(Arc::new(LocalStorage::new(
+            storage_root()
+                .join("data-sources")
+                .join(data_source.data_source_id.to_string()),
+        ))),
+        DataSourceConfig::S3 {
+            bucket,
+            region,
+            prefix,
+            use_iam_role,
+        } => {
+            ensure_bucket_allowed(data_source.org_id, &data_source.config)?;
+            let credentials = if *use_iam_role {
+                S3Credentials::IamRole
+            } else {
+                S3Credentials::AccessKeys
+            };
+            Ok(Arc::new(CircuitBreakingStorage::new(
+                S3Storage::new(bucket, region, prefix.as_deref(), credentials)
+                    .map_err(ApiError::from)?,
+                s3_breaker(bucket, region),
+            )))
+        }
+        _ => Err(ApiError::bad_request(
+            "data_source.storage_unsupported",
+            format!(
+                "{} data sources don't support file storage yet",
+                data_source.source_type.as_str()
+            ),
+        )),
+    }
+}
+
+/// The storage backend holding a project's exports
+pub fn project_storage(project_id: &ProjectId) -> Arc<dyn StorageService> {
+    Arc::new(LocalStorage::new(
+        storage_root().join("projects").join(project_id.to_string()),
+    ))
+}
+
+impl From<StorageError> for ApiError {
+    fn from(err: StorageError) -> Self {
+        match err {
+            StorageError::NotFound(key) => ApiError::not_found("file", key),
+            StorageError::AccessDenied(key) => {
+                ApiError::forbidden(format!("Storage access denied for '{key}'"))
+            }
+            StorageError::Other(message) => ApiError::Internal(anyhow::anyhow!(message)),
+            StorageError::Unavailable(message) => ApiError::service_unavailable(message),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn s3(bucket: &str) -> DataSourceConfig {
+        DataSourceConfig::S3 {
+            bucket: bucket.to_string(),
+            region: "us-east-1".to_string(),
+            prefix: None,
+            use_iam_role: true,
+        }
+    }
+
+    #[test]
+    fn test_buckets_must_be_granted_to_the_org() {
+        let tenant = OrgId::new();
+        let other = OrgId::new();
+        let allowlist =
+            BucketAllowlist::from_list(&format!(" shared-data , {tenant}/tenant-data,bad/x,"));
+
+        assert!(allowlist.allows(tenant, "shared-data"));
+        assert!(allowlist.allows(other, "shared-data"));
+        assert!(allowlist.allows(tenant, "tenant-data"));
+        assert!(!allowlist.allows(other, "tenant-data"));
+        assert!(!allowlist.allows(tenant, "x"));
+
+        assert!(check_bucket(&allowlist, tenant, &s3("tenant-data")).is_ok());
+        assert!(matches!(
+            check_bucket(&allowlist, other, &s3("tenant-data")),
+            Err(ApiError::BadRequest {
+                code: "data_source.bucket_not_allowed",
+                ..
+            })
+        ));
+        // Nothing is allowed until the operator configures it
+        assert!(check_bucket(&BucketAllowlist::default(), tenant, &s3("shared-data")).is_err());
+        assert!(check_bucket(
+            &BucketAllowlist::default(),
+            tenant,
+            &DataSourceConfig::default()
+        )
+        .is_ok());
+    }
+}
@@ -0,0 +1,1071 @@
+//! Streaming file upload service
+//!
+//! Reads multipart uploads chunk by chunk, enforcing the data source's size
+//! and extension limits as bytes arrive, and hands parsed records to a sink
+//! in batches. JSON Lines are parsed line by line and CSV record by record,
+//! so quoted fields may span lines; a plain JSON array has to be buffered
+//! whole. The raw file is written to storage as it
+//! arrives and only stored once the whole upload has been read, so callers
+//! that import records transactionally can commit after the file is safe.
+
+use std::future::Future;
+
+use axum::extract::Multipart;
+use axum::http::{header, HeaderMap};
+
+use glyph_common::{detect_content_type, ObjectWriter, StorageService};
+use glyph_domain::DataSourceConfig;
+use thiserror::Error;
+
+use crate::error::ApiError;
+
+/// Multipart field carrying the uploaded file
+pub const UPLOAD_FIELD: &str = "file";
+
+/// Records handed to the sink at a time
+pub const IMPORT_BATCH_SIZE: usize = 100;
+
+/// Slack over the file limit for multipart boundaries and part headers
+const MULTIPART_OVERHEAD_BYTES: u64 = 16 * 1024;
+
+/// Most malformed lines an upload reports individually
+const MAX_REPORTED_ERRORS: usize = 100;
+
+/// Size and type limits for a file upload data source
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UploadLimits {
+    pub max_bytes: u64,
+    /// Lowercase extensions without the leading dot
+    pub allowed_extensions: Vec<String>,
+    /// How CSV files are read
+    pub csv: CsvOptions,
+}
+
+impl UploadLimits {
+    /// Limits for a data source, or `None` if it doesn't accept uploads
+    pub fn from_config(config: &DataSourceConfig) -> Option<Self> {
+        match config {
+            DataSourceConfig::FileUpload {
+                allowed_extensions,
+                max_file_size_mb,
+                csv_delimiter,
+                csv_coerce_types,
+            } => Some(Self {
+                max_bytes: u64::try_from(*max_file_size_mb).unwrap_or(0) * 1024 * 1024,
+                allowed_extensions: allowed_extensions
+                    .iter()
+                    .map(|ext| ext.trim_start_matches('.').to_ascii_lowercase())
+                    .collect(),
+                csv: CsvOptions {
+                    delimiter: CsvOptions::delimiter_byte(csv_delimiter).unwrap_or(b','),
+                    coerce_types: *csv_coerce_types,
+                },
+            }),
+            _ => None,
+        }
+    }
+
+    /// Reject a request whose declared length can't fit within the limit.
+    ///
+    /// Requests without `Content-Length` are checked as they stream instead.
+    pub fn check_content_length(&self, headers: &HeaderMap) -> Result<(), ApiError> {
+        let declared = headers
+            .get(header::CONTENT_LENGTH)
+            .and_then(|v| v.to_str().ok())
+            .and_then(|v| v.parse::<u64>().ok());
+
+        match declared {
+            Some(len) if len > self.max_bytes.saturating_add(MULTIPART_OVERHEAD_BYTES) => {
+                Err(ApiError::payload_too_large(self.max_bytes))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Import format for a file name, if its extension is allowed
+    pub fn format_for(&self, file_name: &str) -> Result<ImportFormat, ApiError> {
+        let extension = file_name
+            .rsplit_once('.')
+            .map(|(_, ext)| ext.to_ascii_lowercase())
+            .unwrap_or_default();
+
+        if !self.allowed_extensions.contains(&extension) {
+            return Err(ApiError::bad_request(
+                "upload.extension_not_allowed",
+                format!(
+                    "'{file_name}' is not an allowed file type (allowed: {})",
+                    self.allowed_extensions.join(", ")
+                ),
+            ));
+        }
+
+        ImportFormat::from_extension(&extension, self.csv).ok_or_else(|| {
+            ApiError::bad_request(
+                "upload.unsupported_format",
+                format!("Files with extension '.{extension}' cannot be imported"),
+            )
+        })
+    }
+}
+
+/// How CSV rows become records
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CsvOptions {
+    pub delimiter: u8,
+    /// Turn numeric and boolean fields into JSON numbers and booleans
+    pub coerce_types: bool,
+}
+
+impl Default for CsvOptions {
+    fn default() -> Self {
+        Self {
+            delimiter: b',',
+            coerce_types: false,
+        }
+    }
+}
+
+impl CsvOptions {
+    /// A configured delimiter as a byte, if it's a single ASCII character
+    pub fn delimiter_byte(delimiter: &str) -> Option<u8> {
+        match delimiter.as_bytes() {
+            [b] if b.is_ascii() => Some(*b),
+            _ => None,
+        }
+    }
+}
+
+/// How an uploaded file is split into records
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ImportFormat {
+    /// One JSON value per line
+    JsonLines,
+    /// Header row, then one record per row (RFC 4180)
+    Csv(CsvOptions),
+    /// A single JSON array (or one object)
+    Json,
+}
+
+impl ImportFormat {
+    fn from_extension(extension: &str, csv: CsvOptions) -> Option<Self> {
+        match extension {
+            "jsonl" | "ndjson" => Some(Self::JsonLines),
+            "csv" => Some(Self::Csv(csv)),
+            "json" => Some(Self::Json),
+            _ => None,
+        }
+    }
+
+    /// Format of fetched content, from its media type or else the path's extension
+    pub fn detect(content_type: Option<&str>, path: &str) -> Option<Self> {
+        let media_type = content_type
+            .and_then(|ct| ct.split(';').next())
+            .map(|ct| ct.trim().to_ascii_lowercase());
+        match media_type.as_deref() {
+            Some("application/x-ndjson" | "application/jsonl" | "application/x-jsonlines") => {
+                return Some(Self::JsonLines)
+            }
+            Some("text/csv") => return Some(Self::Csv(CsvOptions::default())),
+            Some("application/json") => return Some(Self::Json),
+            _ => {}
+        }
+        let path = path.split(['?', '#']).next().unwrap_or_default();
+        path.rsplit_once('.').and_then(|(_, ext)| {
+            Self::from_extension(&ext.to_ascii_lowercase(), CsvOptions::default())
+        })
+    }
+}
+
+/// Receives parsed records as the upload streams in
+pub(crate) trait RecordSink: Send {
+    /// Persist a batch of records
+    fn accept(
+        &mut self,
+        records: Vec<serde_json::Value>,
+    ) -> impl Future<Output = Result<(), ApiError>> + Send;
+}
+
+/// Outcome of a streamed upload
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct UploadSummary {
+    pub file_name: String,
+    /// Detected from the file name, or else its leading bytes
+    pub content_type: String,
+    pub bytes: u64,
+    /// Records handed to the sink
+    pub records: u64,
+    /// Lines that couldn't be parsed
+    pub skipped: u64,
+    /// The first malformed lines, with line numbers
+    pub errors: Vec<SampleError>,
+}
+
+/// Stream the upload's file field into `sink`, storing the file in
+/// `storage` under [`upload_key`] as it arrives.
+///
+/// Fails with 413 as soon as the file exceeds `limits.max_bytes`. The file
+/// is only stored when the upload succeeds; records already handed to the
+/// sink are the sink's to discard.
+pub(crate) async fn import_upload<S: RecordSink>(
+    mut multipart: Multipart,
+    limits: &UploadLimits,
+    storage: &dyn StorageService,
+    sink: &mut S,
+) -> Result<UploadSummary, ApiError> {
+    while let Some(mut field) = next_field(&mut multipart).await? {
+        if field.name() != Some(UPLOAD_FIELD) {
+            continue;
+        }
+
+        let file_name = field.file_name().unwrap_or_default().to_string();
+        let format = limits.format_for(&file_name)?;
+        let mut writer = storage.writer(&upload_key(&file_name)).await?;
+        let mut summary = UploadSummary {
+            file_name,
+            ..UploadSummary::default()
+        };
+
+        match stream_field(
+            &mut field,
+            format,
+            limits,
+            writer.as_mut(),
+            sink,
+            &mut summary,
+        )
+        .await
+        {
+            Ok(()) => writer.finish().await?,
+            Err(e) => {
+                if let Err(abort) = writer.abort().await {
+                    tracing::warn!("Failed to discard partial upload: {}", abort);
+                }
+                return Err(e);
+            }
+        }
+        return Ok(summary);
+    }
+
+    Err(ApiError::bad_request(
+        "upload.missing_file",
+        format!("Multipart body has no '{UPLOAD_FIELD}' field"),
+    ))
+}
+
+async fn stream_field<S: RecordSink>(
+    field: &mut axum::extract::multipart::Field<'_>,
+    format: ImportFormat,
+    limits: &UploadLimits,
+    writer: &mut dyn ObjectWriter,
+    sink: &mut S,
+    summary: &mut UploadSummary,
+) -> Result<(), ApiError> {
+    let mut parser = RecordParser::new(format);
+    while let Some(chunk) = field
+        .chunk()
+        .await
+        .map_err(|e| ApiError::bad_request("upload.malformed", e.body_text()))?
+    {
+        if summary.bytes == 0 {
+            summary.content_type = detect_content_type(&summary.file_name, &chunk).to_string();
+        }
+        summary.bytes += chunk.len() as u64;
+        if summary.bytes > limits.max_bytes {
+            return Err(ApiError::payload_too_large(limits.max_bytes));
+        }
+
+        writer.write(&chunk).await?;
+        parser.push(&chunk)?;
+        while parser.ready() >= IMPORT_BATCH_SIZE {
+            summary.records += flush(&mut parser, sink, IMPORT_BATCH_SIZE).await?;
+        }
+    }
+
+    if summary.content_type.is_empty() {
+        summary.content_type = detect_content_type(&summary.file_name, &[]).to_string();
+    }
+    parser.finish()?;
+    summary.records += flush(&mut parser, sink, usize::MAX).await?;
+    summary.skipped = parser.skipped;
+    summary.errors = std::mem::take(&mut parser.errors);
+    Ok(())
+}
+
+/// Storage key for an uploaded file: its name without any client path
+pub fn upload_key(file_name: &str) -> String {
+    std::path::Path::new(file_name)
+        .file_name()
+        .and_then(|name| name.to_str())
+        .filter(|name| !name.is_empty())
+        .unwrap_or("upload")
+        .to_string()
+}
+
+async fn next_field(
+    multipart: &mut Multipart,
+) -> Result<Option<axum::extract::multipart::Field<'_>>, ApiError> {
+    multipart
+        .next_field()
+        .await
+        .map_err(|e| ApiError::bad_request("upload.malformed", e.body_text()))
+}
+
+async fn flush<S: RecordSink>(
+    parser: &mut RecordParser,
+    sink: &mut S,
+    max: usize,
+) -> Result<u64, ApiError> {
+    let batch = parser.take(max);
+    if batch.is_empty() {
+        return Ok(0);
+    }
+    let count = batch.len() as u64;
+    sink.accept(batch).await?;
+    Ok(count)
+}
+
+// =============================================================================
+// Record parsing
+// =============================================================================
+
+/// Incremental parser turning byte chunks into records
+struct RecordParser {
+    format: ImportFormat,
+    /// Bytes of an incomplete trailing line or CSV record (or the whole
+    /// file for JSON)
+    pending: Vec<u8>,
+    /// Lines consumed so far
+    line: usize,
+    csv: CsvRows,
+    /// Stop after this many items, parsed or not
+    limit: usize,
+    records: Vec<serde_json::Value>,
+    skipped: u64,
+    errors: Vec<SampleError>,
+}
+
+impl RecordParser {
+    fn new(format: ImportFormat) -> Self {
+        let csv = match format {
+            ImportFormat::Csv(options) => options,
+            ImportFormat::JsonLines | ImportFormat::Json => CsvOptions::default(),
+        };
+        Self {
+            format,
+            pending: Vec::new(),
+            line: 0,
+            csv: CsvRows::new(csv),
+            limit: usize::MAX,
+            records: Vec::new(),
+            skipped: 0,
+            errors: Vec::new(),
+        }
+    }
+
+    /// Parse whatever complete lines or records `chunk` finishes.
+    ///
+    /// Fails if the CSV header row is unusable.
+    fn push(&mut self, chunk: &[u8]) -> Result<(), CsvHeaderError> {
+        self.pending.extend_from_slice(chunk);
+        let end = match self.format {
+            ImportFormat::Json => return Ok(()),
+            ImportFormat::JsonLines => self.pending.iter().rposition(|&b| b == b'\n'),
+            ImportFormat::Csv(_) => self.csv.last_record_end(&self.pending),
+        };
+        let Some(end) = end else {
+            return Ok(());
+        };
+
+        let rest = self.pending.split_off(end + 1);
+        let complete = std::mem::replace(&mut self.pending, rest);
+        match self.format {
+            ImportFormat::Csv(_) => {
+                self.csv.scanned = self.pending.len();
+                self.parse_csv(&complete)
+            }
+            ImportFormat::JsonLines | ImportFormat::Json => {
+                // The split yields an empty tail after the final newline; it isn't a line
+                let mut lines: Vec<&[u8]> = complete.split(|&b| b == b'\n').collect();
+                lines.pop();
+                for line in lines {
+                    self.parse_line(line);
+                }
+                Ok(())
+            }
+        }
+    }
+
+    fn finish(&mut self) -> Result<(), CsvHeaderError> {
+        let remaining = std::mem::take(&mut self.pending);
+        match self.format {
+            ImportFormat::Json => self.parse_document(&remaining),
+            ImportFormat::JsonLines => self.parse_line(&remaining),
+            ImportFormat::Csv(_) => return self.parse_csv(&remaining),
+        }
+        Ok(())
+    }
+
+    fn ready(&self) -> usize {
+        self.records.len()
+    }
+
+    fn take(&mut self, max: usize) -> Vec<serde_json::Value> {
+        let count = self.records.len().min(max);
+        self.records.drain(..count).collect()
+    }
+
+    fn full(&self) -> bool {
+        self.records.len() + self.errors.len() >= self.limit
+    }
+
+    fn parse_line(&mut self, line: &[u8]) {
+        self.line += 1;
+        let line = line.strip_suffix(b"\r").unwrap_or(line);
+        if self.full() || line.iter().all(u8::is_ascii_whitespace) {
+            return;
+        }
+        match serde_json::from_slice(line) {
+            Ok(record) => self.records.push(record),
+            Err(e) => self.reject(e.to_string()),
+        }
+    }
+
+    /// Parse complete CSV records. The first is the header row.
+    fn parse_csv(&mut self, bytes: &[u8]) -> Result<(), CsvHeaderError> {
+        let first_line = self.line;
+        let mut reader = csv::ReaderBuilder::new()
+            .has_headers(false)
+            .flexible(true)
+            .delimiter(self.csv.options.delimiter)
+            .from_reader(bytes);
+        let mut record = csv::ByteRecord::new();
+
+        while !self.full() {
+            match reader.read_byte_record(&mut record) {
+                Ok(true) => {}
+                Ok(false) => break,
+                Err(e) => {
+                    self.reject(e.to_string());
+                    break;
+                }
+            }
+            self.line = first_line + record.position().map_or(1, |p| p.line() as usize);
+            if record.len() == 1 && record[0].iter().all(u8::is_ascii_whitespace) {
+                continue;
+            }
+            if self.csv.header.is_none() {
+                self.csv.header = Some(csv_header(&record)?);
+                continue;
+            }
+            match self.csv.parse(&record) {
+                Ok(record) => self.records.push(record),
+                Err(message) => self.reject(message),
+            }
+        }
+
+        self.line = first_line + bytes.iter().filter(|&&b| b == b'\n').count();
+        Ok(())
+    }
+
+    fn parse_document(&mut self, bytes: &[u8]) {
+        if bytes.iter().all(u8::is_ascii_whitespace) {
+            return;
+        }
+        self.line = 1;
+        match serde_json::from_slice(bytes) {
+            Ok(serde_json::Value::Array(items)) => self.records.extend(items),
+            Ok(record) => self.records.push(record),
+            Err(e) => self.reject(e.to_string()),
+        }
+    }
+
+    fn reject(&mut self, message: String) {
+        self.skipped += 1;
+        if self.errors.len() < MAX_REPORTED_ERRORS {
+            self.errors.push(SampleError {
+                item: self.line,
+                message,
+            });
+        }
+    }
+}
+
+// =============================================================================
+// Sample previews
+// =============================================================================
+
+/// A sample item that couldn't be parsed
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SampleError {
+    /// 1-based line of the item, or its position in a JSON array
+    pub item: usize,
+    pub message: String,
+}
+
+/// Records parsed from the start of a sample file
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SamplePreview {
+    pub records: Vec<serde_json::Value>,
+    pub errors: Vec<SampleError>,
+}
+
+/// Parse up to `limit` items from the start of a sample.
+///
+/// Unlike an import, items that fail to parse are reported rather than
+/// counted, and count toward the limit. A CSV header row isn't an item. A
+/// JSON array is read item by item, so a sample cut off part way through a
+/// large array still previews the items before the cut.
+pub fn preview_sample(
+    format: ImportFormat,
+    bytes: &[u8],
+    limit: usize,
+) -> Result<SamplePreview, CsvHeaderError> {
+    if format == ImportFormat::Json {
+        return Ok(preview_json(bytes, limit));
+    }
+
+    let mut parser = RecordParser::new(format);
+    parser.limit = limit;
+    parser.push(bytes)?;
+    parser.finish()?;
+    Ok(SamplePreview {
+        records: parser.records,
+        errors: parser.errors,
+    })
+}
+
+/// Preview a JSON document: the leading items of an array, or a single value
+fn preview_json(bytes: &[u8], limit: usize) -> SamplePreview {
+    let mut preview = SamplePreview::default();
+    let start = bytes
+        .iter()
+        .position(|b| !b.is_ascii_whitespace())
+        .unwrap_or(bytes.len());
+    if bytes.get(start) != Some(&b'[') {
+        match serde_json::from_slice(bytes) {
+            Ok(record) if limit > 0 => preview.records.push(record),
+            Ok(_) => {}
+            Err(e) => preview.errors.push(SampleError {
+                item: 1,
+                message: e.to_string(),
+            }),
+        }
+        return preview;
+    }
+
+    let mut pos = start + 1;
+    let skip_whitespace = |pos: &mut usize| {
+        while bytes.get(*pos).is_some_and(u8::is_ascii_whitespace) {
+            *pos += 1;
+        }
+    };
+    while preview.records.len() + preview.errors.len() < limit {
+        skip_whitespace(&mut pos);
+        if matches!(bytes.get(pos), None | Some(b']')) {
+            break;
+        }
+        let item = preview.records.len() + preview.errors.len() + 1;
+        let mut values =
+            serde_json::Deserializer::from_slice(&bytes[pos..]).into_iter::<serde_json::Value>();
+        match values.next() {
+            Some(Ok(record)) => preview.records.push(record),
+            // The sample ends part way through this item
+            Some(Err(e)) if e.is_eof() => break,
+            Some(Err(e)) => {
+                // Nothing after a syntax error can be trusted
+                preview.errors.push(SampleError {
+                    item,
+                    message: e.to_string(),
+                });
+                break;
+            }
+            None => break,
+        }
+        pos += values.byte_offset();
+        skip_whitespace(&mut pos);
+        match bytes.get(pos) {
+            Some(b',') => pos += 1,
+            Some(b']') | None => break,
+            Some(_) => {
+                preview.errors.push(SampleError {
+                    item: item + 1,
+                    message: "expected ',' or ']' after an array item".to_string(),
+                });
+                break;
+            }
+        }
+    }
+    preview
+}
+
+// =============================================================================
+// CSV
+// =============================================================================
+
+/// A CSV header row that can't key records
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+#[error("invalid CSV header: {0}")]
+pub struct CsvHeaderError(String);
+
+impl From<CsvHeaderError> for ApiError {
+    fn from(err: CsvHeaderError) -> Self {
+        ApiError::bad_request("upload.invalid_csv_header", err.to_string())
+    }
+}
+
+/// Where the record-boundary scan is within the current field
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum QuoteState {
+    FieldStart,
+    Unquoted,
+    Quoted,
+    /// A quote inside a quoted field: either its end or half of an escaped `""`
+    QuoteInQuoted,
+}
+
+/// Turns CSV records into JSON objects keyed by the header row
+struct CsvRows {
+    options: CsvOptions,
+    header: Option<Vec<String>>,
+    /// Pending bytes already scanned for record boundaries
+    scanned: usize,
+    quote: QuoteState,
+}
+
+impl CsvRows {
+    fn new(options: CsvOptions) -> Self {
+        Self {
+            options,
+            header: None,
+            scanned: 0,
+            quote: QuoteState::FieldStart,
+        }
+    }
+
+    /// Index of the last newline in `pending` that ends a record rather
+    /// than falling inside a quoted field
+    fn last_record_end(&mut self, pending: &[u8]) -> Option<usize> {
+        let mut end = None;
+        for (i, &b) in pending.iter().enumerate().skip(self.scanned) {
+            self.quote = match (self.quote, b) {
+                (QuoteState::FieldStart | QuoteState::QuoteInQuoted, b'"') => QuoteState::Quoted,
+                (QuoteState::Quoted, b'"') => QuoteState::QuoteInQuoted,
+                (QuoteState::Quoted, _) => QuoteState::Quoted,
+                (_, b'\n') => {
+                    end = Some(i);
+                    QuoteState::FieldStart
+                }
+                (_, b) if b == self.options.delimiter => QuoteState::FieldStart,
+                _ => QuoteState::Unquoted,
+            };
+        }
+        self.scanned = pending.len();
+        end
+    }
+
+    /// The object for a data row.
+    ///
+    /// Rows whose field count differs from the header's are malformed.
+    fn parse(&self, record: &csv::ByteRecord) -> Result<serde_json::Value, String> {
+        let header = self.header.as_deref().unwrap_or_default();
+        if record.len() != header.len() {
+            return Err(format!(
+                "expected {} fields, found {}",
+                header.len(),
+                record.len()
+            ));
+        }
+
+        let coerce = self.options.coerce_types;
+        let mut object = serde_json::Map::with_capacity(header.len());
+        for (name, field) in header.iter().zip(record) {
+            let field = std::str::from_utf8(field)
+                .map_err(|_| format!("field '{name}' is not valid UTF-8"))?
+                .to_string();
+            let value = if coerce {
+                coerce_field(field)
+            } else {
+                serde_json::Value::String(field)
+            };
+            object.insert(name.clone(), value);
+        }
+        Ok(serde_json::Value::Object(object))
+    }
+}
+
+/// Column names from the header row: UTF-8, non-blank and distinct
+fn csv_header(record: &csv::ByteRecord) -> Result<Vec<String>, CsvHeaderError> {
+    let mut names: Vec<String> = Vec::with_capacity(record.len());
+    for (index, field) in record.iter().enumerate() {
+        let column = index + 1;
+        let name = std::str::from_utf8(field)
+            .map_err(|_| CsvHeaderError(format!("column {column} is not valid UTF-8")))?;
+        let name = if index == 0 {
+            name.trim_start_matches('\u{feff}')
+        } else {
+            name
+        };
+        if name.trim().is_empty() {
+            return Err(CsvHeaderError(format!("column {column} has no name")));
+        }
+        if names.iter().any(|existing| existing == name) {
+            return Err(CsvHeaderError(format!("column '{name}' appears twice")));
+        }
+        names.push(name.to_string());
+    }
+    Ok(names)
+}
+
+/// A CSV field as a JSON boolean or number where it reads as one.
+///
+/// Numbers with leading zeros, like ZIP codes, stay strings.
+fn coerce_field(field: String) -> serde_json::Value {
+    match field.as_str() {
+        "true" | "TRUE" | "True" => return serde_json::Value::Bool(true),
+        "false" | "FALSE" | "False" => return serde_json::Value::Bool(false),
+        _ => {}
+    }
+
+    let digits = field.strip_prefix('-').unwrap_or(&field);
+    let leading_zero = digits.len() > 1 && digits.starts_with('0') && !digits.starts_with("0.");
+    if !leading_zero {
+        if let Ok(n) = field.parse::<i64>() {
+            return serde_json::Value::from(n);
+        }
+        if let Some(n) = field
+            .parse::<f64>()
+            .ok()
+            .filter(|n| n.is_finite())
+            .and_then(serde_json::Number::from_f64)
+        {
+            return serde_json::Value::Number(n);
+        }
+    }
+    serde_json::Value::String(field)
+}
+
+#[cfg(test)]
+mod tests {
+    use axum::{
+        body::Body,
+        extract::DefaultBodyLimit,
+        http::{Request, StatusCode},
+        routing::post,
+        Router,
+    };
+    use glyph_common::LocalStorage;
+    use tower::ServiceExt;
+
+    use super::*;
+
+    const BOUNDARY: &str = "glyph-test-boundary";
+
+    struct TempDir(std::path::PathBuf);
+
+    impl TempDir {
+        fn new() -> Self {
+            Self(std::env::temp_dir().join(format!("glyph-upload-{}", uuid::Uuid::new_v4())))
+        }
+    }
+
+    impl Drop for TempDir {
+        fn drop(&mut self) {
+            let _ = std::fs::remove_dir_all(&self.0);
+        }
+    }
+
+    impl RecordSink for Vec<serde_json::Value> {
+        async fn accept(&mut self, records: Vec<serde_json::Value>) -> Result<(), ApiError> {
+            self.extend(records);
+            Ok(())
+        }
+    }
+
+    fn limits(max_bytes: u64) -> UploadLimits {
+        UploadLimits {
+            max_bytes,
+            allowed_extensions: vec!["jsonl".to_string(), "csv".to_string()],
+            csv: CsvOptions::default(),
+        }
+    }
+
+    fn app(limits: UploadLimits, root: &std::path::Path) -> Router {
+        let storage = std::sync::Arc::new(LocalStorage::new(root));
+        Router::new().route(
+            "/upload",
+            post(move |headers: HeaderMap, multipart: Multipart| async move {
+                limits.check_content_length(&headers)?;
+                let mut records = Vec::new();
+                let summary =
+                    import_upload(multipart, &limits, storage.as_ref(), &mut records).await?;
+                Ok::<_, ApiError>(format!("{} {}", summary.records, summary.skipped))
+            })
+            .layer(DefaultBodyLimit::disable()),
+        )
+    }
+
+    fn multipart_body(file_name: &str, contents: &[u8]) -> Vec<u8> {
+        let mut body = format!(
+            "--{BOUNDARY}\r\nContent-Disposition: form-data; name=\"{UPLOAD_FIELD}\"; \
+             filename=\"{file_name}\"\r\nContent-Type: application/octet-stream\r\n\r\n"
+        )
+        .into_bytes();
+        body.extend_from_slice(contents);
+        body.extend_from_slice(format!("\r\n--{BOUNDARY}--\r\n").as_bytes());
+        body
+    }
+
+    async fn post_upload(
+        limits: UploadLimits,
+        body: Vec<u8>,
+        content_length: bool,
+    ) -> (StatusCode, String) {
+        post_upload_to(&TempDir::new().0, limits, body, content_length).await
+    }
+
+    async fn post_upload_to(
+        root: &std::path::Path,
+        limits: UploadLimits,
+        body: Vec<u8>,
+        content_length: bool,
+    ) -> (StatusCode, String) {
+        let mut request = Request::builder().method("POST").uri("/upload").header(
+            header::CONTENT_TYPE,
+            format!("multipart/form-data; boundary={BOUNDARY}"),
+        );
+        if content_length {
+            request = request.header(header::CONTENT_LENGTH, body.len());
+        }
+
+        let response = app(limits, root)
+            .oneshot(request.body(Body::from(body)).unwrap())
+            .await
+            .unwrap();
+        let status = response.status();
+        let bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        (status, String::from_utf8_lossy(&bytes).into_owned())
+    }
+
+    #[tokio::test]
+    async fn test_over_limit_upload_is_rejected_with_413() {
+        let contents = "{\"text\": \"hello\"}\n".repeat(2000);
+        let body = multipart_body("tasks.jsonl", contents.as_bytes());
+
+        // No Content-Length: rejected while streaming
+        let (status, _) = post_upload(limits(1024), body.clone(), false).await;
+        assert_eq!(status, StatusCode::PAYLOAD_TOO_LARGE);
+
+        // Declared length: rejected before reading the body
+        let (status, _) = post_upload(limits(1024), body, true).await;
+        assert_eq!(status, StatusCode::PAYLOAD_TOO_LARGE);
+    }
+
+    #[tokio::test]
+    async fn test_within_limit_upload_is_imported_and_stored() {
+        let dir = TempDir::new();
+        let contents = b"{\"text\": \"a\"}\nnot json\n{\"text\": \"b\"}";
+        let (status, body) = post_upload_to(
+            &dir.0,
+            limits(1024),
+            multipart_body("../exports/tasks.jsonl", contents),
+            true,
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body, "2 1");
+        assert_eq!(std::fs::read(dir.0.join("tasks.jsonl")).unwrap(), contents);
+    }
+
+    #[tokio::test]
+    async fn test_rejected_upload_is_not_stored() {
+        let dir = TempDir::new();
+        let contents = "{\"text\": \"hello\"}\n".repeat(2000);
+        let (status, _) = post_upload_to(
+            &dir.0,
+            limits(1024),
+            multipart_body("tasks.jsonl", contents.as_bytes()),
+            false,
+        )
+        .await;
+        assert_eq!(status, StatusCode::PAYLOAD_TOO_LARGE);
+        assert_eq!(std::fs::read_dir(&dir.0).unwrap().count(), 0);
+    }
+
+    #[test]
+    fn test_upload_key_drops_client_paths() {
+        assert_eq!(upload_key("tasks.jsonl"), "tasks.jsonl");
+        assert_eq!(upload_key("exports/2024/tasks.csv"), "tasks.csv");
+        assert_eq!(upload_key("../../etc/passwd"), "passwd");
+        assert_eq!(upload_key(".."), "upload");
+        assert_eq!(upload_key(""), "upload");
+    }
+
+    #[tokio::test]
+    async fn test_disallowed_extension_is_rejected() {
+        let (status, _) = post_upload(limits(1024), multipart_body("tasks.exe", b"{}"), true).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+    }
+
+    #[test]
+    fn test_parser_handles_lines_split_across_chunks() {
+        let mut parser = RecordParser::new(ImportFormat::Csv(CsvOptions::default()));
+        parser.push(b"text,label\nhel").unwrap();
+        parser.push(b"lo,\"a, b\"\r\nbye,c").unwrap();
+        parser.finish().unwrap();
+
+        assert_eq!(
+            parser.take(usize::MAX),
+            vec![
+                serde_json::json!({"text": "hello", "label": "a, b"}),
+                serde_json::json!({"text": "bye", "label": "c"}),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_limits_from_file_upload_config() {
+        let config = DataSourceConfig::FileUpload {
+            allowed_extensions: vec![".JSONL".to_string(), "csv".to_string()],
+            max_file_size_mb: 2,
+            csv_delimiter: ";".to_string(),
+            csv_coerce_types: true,
+        };
+        let limits = UploadLimits::from_config(&config).unwrap();
+        assert_eq!(limits.max_bytes, 2 * 1024 * 1024);
+        assert_eq!(limits.allowed_extensions, vec!["jsonl", "csv"]);
+        assert_eq!(
+            limits.format_for("Tasks.JSONL").unwrap(),
+            ImportFormat::JsonLines
+        );
+    }
+
+    #[test]
+    fn test_csv_with_quoted_fields_and_coercion() {
+        let options = CsvOptions {
+            delimiter: b';',
+            coerce_types: true,
+        };
+        let mut parser = RecordParser::new(ImportFormat::Csv(options));
+        parser
+            .push(b"text;count;flag;zip\n\"hello; world\";3;true;02134\n")
+            .unwrap();
+        parser
+            .push(b"\"say \"\"hi\"\"\";1.5;false;90210\nshort;1\n")
+            .unwrap();
+        parser.finish().unwrap();
+
+        assert_eq!(
+            parser.take(usize::MAX),
+            vec![
+                serde_json::json!({"text": "hello; world", "count": 3, "flag": true, "zip": "02134"}),
+                serde_json::json!({"text": "say \"hi\"", "count": 1.5, "flag": false, "zip": 90210}),
+            ]
+        );
+        assert_eq!(parser.skipped, 1);
+        assert_eq!(
+            parser.errors,
+            vec![SampleError {
+                item: 4,
+                message: "expected 4 fields, found 2".to_string(),
+            }]
+        );
+    }
+
+    #[test]
+    fn test_csv_without_coercion_keeps_strings() {
+        let preview = preview_sample(
+            ImportFormat::Csv(CsvOptions::default()),
+            b"id,ok\n7,true\n",
+            5,
+        )
+        .unwrap();
+        assert_eq!(
+            preview.records,
+            vec![serde_json::json!({"id": "7", "ok": "true"})]
+        );
+    }
+
+    #[test]
+    fn test_json_array_preview_reads_leading_items_of_a_cut_off_sample() {
+        let sample = br#"[ {"id": 1}, 2 , "three", {"id": 4, "text": "cut of"#;
+        let preview = preview_sample(ImportFormat::Json, sample, 10).unwrap();
+        assert_eq!(
+            preview.records,
+            vec![
+                serde_json::json!({"id": 1}),
+                serde_json::json!(2),
+                serde_json::json!("three"),
+            ]
+        );
+        assert!(preview.errors.is_empty());
+
+        let preview = preview_sample(ImportFormat::Json, sample, 2).unwrap();
+        assert_eq!(preview.records.len(), 2);
+    }
+
+    #[test]
+    fn test_json_preview_reports_malformed_items() {
+        let preview = preview_sample(ImportFormat::Json, b"[1, tru, 3]", 5).unwrap();
+        assert_eq!(preview.records, vec![serde_json::json!(1)]);
+        assert_eq!(preview.errors.len(), 1);
+        assert_eq!(preview.errors[0].item, 2);
+
+        let preview = preview_sample(ImportFormat::Json, br#" {"id": 1} "#, 5).unwrap();
+        assert_eq!(preview.records, vec![serde_json::json!({"id": 1})]);
+        assert!(preview_sample(ImportFormat::Json, b"[]", 5)
+            .unwrap()
+            .records
+            .is_empty());
+    }
+
+    #[test]
+    fn test_csv_quoted_fields_may_span_lines_and_chunks() {
+        let mut parser = RecordParser::new(ImportFormat::Csv(CsvOptions::default()));
+        parser.push(b"text,label\n\"first line\nsec").unwrap();
+        parser
+            .push(b"ond \"\"line\"\"\",a\r\n\"x,\ny\",b\nbad\n")
+            .unwrap();
+        parser.finish().unwrap();
+
+        assert_eq!(
+            parser.take(usize::MAX),
+            vec![
+                serde_json::json!({"text": "first line\nsecond \"line\"", "label": "a"}),
+                serde_json::json!({"text": "x,\ny", "label": "b"}),
+            ]
+        );
+        assert_eq!(
+            parser.errors,
+            vec![SampleError {
+                item: 6,
+                message: "expected 2 fields, found 1".to_string(),
+            }]
+        );
+    }
+
+    #[test]
+    fn test_unusable_csv_header_is_an_error() {
+        let csv = ImportFormat::Csv(CsvOptions::default());
+        for (sample, message) in [
+            (&b"id,,label\n1,2,3\n"[..], "column 2 has no name"),
+            (b"id,label,id\n1,2,3\n", "column 'id' appears twice"),
+        ] {
+            let err = preview_sample(csv, sample, 5).unwrap_err();
+            assert_eq!(err.to_string(), format!("invalid CSV header: {message}"));
+        }
+    }
+
+    #[tokio::test]
+    async fn test_upload_with_unusable_csv_header_is_rejected() {
+        let (status, body) = post_upload(
+            limits(1024),
+            multipart_body("tasks.csv", b"a,a\n1,2\n"),
+            true,
+        )
+        .await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert!(body.contains("upload.invalid_csv_header"), "{body}");
+    }
+}
@@ -1,5 +1,20 @@
 //! WebSocket event types for real-time queue updates
+//!
+//! # Client protocol
+//!
+//! Clients connect to `/api/v1/queue/ws` and exchange JSON text frames, each
+//! an object tagged by `type`:
+//!
+//! - Client to server: a [`ClientMessage`], e.g.
+//!   `{"type": "subscribe_project", "project_id": "..."}`.
+//! - Server to client: a [`QueueEvent`], e.g. `{"type": "pong", "timestamp": 1}`.
+//!
+//! A frame the server can't handle (not JSON, an unknown `type`, missing or
+//! mistyped fields, or any binary frame) gets a [`QueueEvent::Error`] reply
+//! and the connection stays open. WebSocket ping frames are answered with
+//! pongs; a close frame ends the session.
 
+use glyph_domain::AssignmentStatus;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use utoipa::ToSchema;
@@ -34,6 +49,16 @@ pub enum QueueEvent {
         new_status: String,
     },
 
+    /// One of the user's assignments changed status through the API.
+    ///
+    /// Sent to the assignment owner's channel so their other open tabs can
+    /// update, e.g. `{"type": "assignment_status_changed",
+    /// "assignment_id": "...", "status": "accepted"}`.
+    AssignmentStatusChanged {
+        assignment_id: Uuid,
+        status: AssignmentStatus,
+    },
+
     /// Queue counts changed
     QueueCountChanged {
         total: i64,
@@ -52,14 +77,65 @@ pub enum QueueEvent {
         action: String,
     },
 
+    /// A user came online in, or left, a project.
+    ///
+    /// Sent to project subscribers on a user's first activity in the project
+    /// across their open connections, and again when the last of those
+    /// connections closes.
+    PresenceChanged {
+        project_id: Uuid,
+        user_id: Uuid,
+        online: bool,
+    },
+
     /// Heartbeat/ping response
     Pong { timestamp: i64 },
 
-    /// Error event
+    /// Error event, e.g. in reply to a malformed client message
     Error { code: String, message: String },
 }
 
+/// Error code for a client frame that isn't a well-formed [`ClientMessage`]
+pub const INVALID_MESSAGE_CODE: &str = "ws.invalid_message";
+
+/// Error code for a client message with an unrecognised `type`
+pub const UNKNOWN_MESSAGE_CODE: &str = "ws.unknown_message";
+
+/// Error code for a binary frame; the protocol is text-only
+pub const BINARY_FRAME_CODE: &str = "ws.binary_unsupported";
+
+/// Error code for subscribing to, or reporting activity in, a project the
+/// user doesn't take part in
+pub const PROJECT_FORBIDDEN_CODE: &str = "ws.project_forbidden";
+
+impl QueueEvent {
+    fn error(code: &str, message: impl Into<String>) -> Self {
+        Self::Error {
+            code: code.to_string(),
+            message: message.into(),
+        }
+    }
+
+    /// Reply to a binary frame
+    pub fn binary_frame_rejected() -> Self {
+        Self::error(
+            BINARY_FRAME_CODE,
+            "Binary frames are not supported; send JSON text frames",
+        )
+    }
+
+    /// Reply to a message about a project the user can't see
+    pub fn project_forbidden(project_id: Uuid) -> Self {
+        Self::error(
+            PROJECT_FORBIDDEN_CODE,
+            format!("You don't have access to project {project_id}"),
+        )
+    }
+}
+
 /// Client-to-server messages
+///
+/// Sent as JSON text frames tagged by `type`; see the module docs.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(tag = "type", rename_all = "snake_case")]
 pub enum ClientMessage {
@@ -75,3 +151,113 @@ pub enum ClientMessage {
     /// Report activity (updates presence)
     Activity { project_id: Option<Uuid> },
 }
+
+impl ClientMessage {
+    /// Message `type` tags the server understands
+    pub const TYPES: [&'static str; 4] = [
+        "ping",
+        "subscribe_project",
+        "unsubscribe_project",
+        "activity",
+    ];
+
+    /// Parse a text frame, or describe why it can't be handled.
+    pub fn parse(text: &str) -> Result<Self, QueueEvent> {
+        let value: serde_json::Value = serde_json::from_str(text).map_err(|e| {
+            QueueEvent::error(
+                INVALID_MESSAGE_CODE,
+                format!("Message is not valid JSON: {e}"),
+            )
+        })?;
+
+        let Some(kind) = value.get("type").and_then(serde_json::Value::as_str) else {
+            return Err(QueueEvent::error(
+                INVALID_MESSAGE_CODE,
+                "Message must be a JSON object with a string \"type\"",
+            ));
+        };
+        if !Self::TYPES.contains(&kind) {
+            return Err(QueueEvent::error(
+                UNKNOWN_MESSAGE_CODE,
+                format!(
+                    "Unknown message type '{kind}'; expected one of: {}",
+                    Self::TYPES.join(", ")
+                ),
+            ));
+        }
+
+        Self::deserialize(&value).map_err(|e| {
+            QueueEvent::error(
+                INVALID_MESSAGE_CODE,
+                format!("Invalid '{kind}' message: {e}"),
+            )
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn error_code(event: &QueueEvent) -> &str {
+        match event {
+            QueueEvent::Error { code, .. } => code,
+            other => panic!("expected error event, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_parses_known_messages() {
+        assert!(matches!(
+            ClientMessage::parse(r#"{"type": "ping", "timestamp": 42}"#),
+            Ok(ClientMessage::Ping { timestamp: 42 })
+        ));
+        assert!(matches!(
+            ClientMessage::parse(r#"{"type": "activity", "project_id": null}"#),
+            Ok(ClientMessage::Activity { project_id: None })
+        ));
+    }
+
+    #[test]
+    fn test_garbage_yields_error_event() {
+        let event = ClientMessage::parse("not json at all").unwrap_err();
+        assert_eq!(error_code(&event), INVALID_MESSAGE_CODE);
+
+        let json = serde_json::to_value(&event).unwrap();
+        assert_eq!(json["type"], "error");
+        assert_eq!(json["code"], "ws.invalid_message");
+        assert!(json["message"].as_str().unwrap().contains("not valid JSON"));
+    }
+
+    #[test]
+    fn test_unknown_and_malformed_messages_are_distinguished() {
+        let event = ClientMessage::parse(r#"{"type": "launch_rockets"}"#).unwrap_err();
+        assert_eq!(error_code(&event), UNKNOWN_MESSAGE_CODE);
+
+        let event = ClientMessage::parse(r#"{"type": "ping"}"#).unwrap_err();
+        assert_eq!(error_code(&event), INVALID_MESSAGE_CODE);
+
+        let event = ClientMessage::parse(r#"["ping"]"#).unwrap_err();
+        assert_eq!(error_code(&event), INVALID_MESSAGE_CODE);
+    }
+
+    #[test]
+    fn test_type_list_matches_enum() {
+        let samples = [
+            serde_json::json!({"type": "ping", "timestamp": 0}),
+            serde_json::json!({"type": "subscribe_project", "project_id": Uuid::nil()}),
+            serde_json::json!({"type": "unsubscribe_project", "project_id": Uuid::nil()}),
+            serde_json::json!({"type": "activity", "project_id": null}),
+        ];
+        for (sample, kind) in samples.iter().zip(ClientMessage::TYPES) {
+            let parsed: ClientMessage = serde_json::from_value(sample.clone()).unwrap();
+            assert_eq!(serde_json::to_value(parsed).unwrap()["type"], kind);
+        }
+    }
+
+    #[test]
+    fn test_binary_frame_error() {
+        let event = QueueEvent::binary_frame_rejected();
+        assert_eq!(error_code(&event), BINARY_FRAME_CODE);
+    }
+}
@@ -4,7 +4,7 @@
 
 use std::collections::HashMap;
 use std::sync::Arc;
-use tokio::sync::{broadcast, RwLock};
+use tokio::sync::{broadcast, watch, RwLock};
 use uuid::Uuid;
 
 use super::events::QueueEvent;
@@ -18,6 +18,10 @@ pub struct QueueUpdateHub {
     user_channels: Arc<RwLock<HashMap<Uuid, broadcast::Sender<QueueEvent>>>>,
     /// Per-project broadcast channels for presence updates
     project_channels: Arc<RwLock<HashMap<Uuid, broadcast::Sender<QueueEvent>>>>,
+    /// Open connections a user is active through, per `(project_id, user_id)`
+    presence: Arc<RwLock<HashMap<(Uuid, Uuid), usize>>>,
+    /// Flipped to true when the server begins shutting down
+    shutdown: watch::Sender<bool>,
 }
 
 impl Default for QueueUpdateHub {
@@ -32,9 +36,28 @@ impl QueueUpdateHub {
         Self {
             user_channels: Arc::new(RwLock::new(HashMap::new())),
             project_channels: Arc::new(RwLock::new(HashMap::new())),
+            presence: Arc::new(RwLock::new(HashMap::new())),
+            shutdown: watch::Sender::new(false),
         }
     }
 
+    /// Watch for server shutdown
+    ///
+    /// Connection handlers should close their socket once the value becomes true.
+    pub fn shutdown_receiver(&self) -> watch::Receiver<bool> {
+        self.shutdown.subscribe()
+    }
+
+    /// Signal all connected clients that the server is shutting down
+    pub fn shutdown(&self) {
+        self.shutdown.send_replace(true);
+    }
+
+    /// Whether shutdown has been signalled
+    pub fn is_shutting_down(&self) -> bool {
+        *self.shutdown.borrow()
+    }
+
     /// Subscribe to queue updates for a specific user
     ///
     /// Creates a new channel if one doesn't exist. Returns a receiver that
@@ -121,6 +144,34 @@ impl QueueUpdateHub {
         }
     }
 
+    /// Count another connection through which a user is active in a project.
+    ///
+    /// Returns true if it is their first, i.e. they just came online.
+    pub async fn join_presence(&self, project_id: Uuid, user_id: Uuid) -> bool {
+        let mut presence = self.presence.write().await;
+        let connections = presence.entry((project_id, user_id)).or_insert(0);
+        *connections += 1;
+        *connections == 1
+    }
+
+    /// Stop counting a connection joined with [`Self::join_presence`].
+    ///
+    /// Returns true if it was their last, i.e. they just went offline.
+    pub async fn leave_presence(&self, project_id: Uuid, user_id: Uuid) -> bool {
+        let mut presence = self.presence.write().await;
+        match presence.get_mut(&(project_id, user_id)) {
+            Some(connections) if *connections > 1 => {
+                *connections -= 1;
+                false
+            }
+            Some(_) => {
+                presence.remove(&(project_id, user_id));
+                true
+            }
+            None => false,
+        }
+    }
+
     /// Get count of active user subscriptions
     pub async fn user_subscription_count(&self) -> usize {
         self.user_channels.read().await.len()
@@ -162,6 +213,20 @@ mod tests {
         }
     }
 
+    #[tokio::test]
+    async fn test_presence_counts_connections() {
+        let hub = QueueUpdateHub::new();
+        let project_id = Uuid::new_v4();
+        let user_id = Uuid::new_v4();
+
+        assert!(hub.join_presence(project_id, user_id).await);
+        assert!(!hub.join_presence(project_id, user_id).await);
+
+        assert!(!hub.leave_presence(project_id, user_id).await);
+        assert!(hub.leave_presence(project_id, user_id).await);
+        assert!(!hub.leave_presence(project_id, user_id).await);
+    }
+
     #[tokio::test]
     async fn test_cleanup_removes_empty_channel() {
         let hub = QueueUpdateHub::new();
@@ -176,4 +241,17 @@ mod tests {
 
         assert_eq!(hub.user_subscription_count().await, 0);
     }
+
+    #[tokio::test]
+    async fn test_shutdown_notifies_receivers() {
+        let hub = QueueUpdateHub::new();
+        let mut rx = hub.shutdown_receiver();
+        assert!(!hub.is_shutting_down());
+
+        hub.shutdown();
+
+        rx.changed().await.unwrap();
+        assert!(*rx.borrow());
+        assert!(hub.is_shutting_down());
+    }
 }
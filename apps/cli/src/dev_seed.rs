@@ -0,0 +1,436 @@
+//! Sample data for local development
+//!
+//! `glyph dev seed` creates a team, a few users, a project type with a schema,
+//! a single-step workflow, an active project and a batch of pending tasks.
+//! Every row has a fixed ID, so running it again inserts only what's missing
+//! and prints the same IDs.
+
+use glyph_domain::{
+    AssignmentConfig, AssignmentMode, CompletionCriteria, CompletionCriteriaType,
+    LoadBalancingStrategy, ProjectId, ProjectTypeId, StepType, TaskId, TeamId, UserId, WorkflowId,
+    WorkflowStep,
+};
+use serde_json::json;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+/// Tasks created when `--tasks` isn't given
+pub const DEFAULT_TASK_COUNT: u32 = 20;
+
+/// High bits shared by every seeded ID ("glyphdev"), so they're easy to spot
+const SEED_ID_PREFIX: u128 = 0x676c_7970_6864_6576;
+
+/// A fixed ID for the `n`th seeded row of a kind
+fn seed_uuid(kind: u16, n: u32) -> Uuid {
+    Uuid::from_u128((SEED_ID_PREFIX << 64) | (u128::from(kind) << 32) | u128::from(n))
+}
+
+/// A seeded user and their role in the sample team
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SeedUser {
+    pub user_id: UserId,
+    pub email: &'static str,
+    pub display_name: &'static str,
+    pub global_role: &'static str,
+    pub team_role: &'static str,
+}
+
+/// A seeded task's input
+#[derive(Debug, Clone, PartialEq)]
+pub struct SeedTask {
+    pub task_id: TaskId,
+    pub priority: i32,
+    pub input_data: serde_json::Value,
+}
+
+/// Everything `glyph dev seed` creates
+#[derive(Debug, Clone, PartialEq)]
+pub struct SeedPlan {
+    pub team_id: TeamId,
+    pub team_name: &'static str,
+    pub users: Vec<SeedUser>,
+    pub project_type_id: ProjectTypeId,
+    pub project_type_name: &'static str,
+    pub input_schema: serde_json::Value,
+    pub output_schema: serde_json::Value,
+    pub workflow_id: WorkflowId,
+    pub workflow_name: &'static str,
+    /// Serialized `Vec<WorkflowStep>`
+    pub steps: serde_json::Value,
+    pub project_id: ProjectId,
+    pub project_name: &'static str,
+    pub tasks: Vec<SeedTask>,
+}
+
+/// Sample texts cycled through for task inputs
+const SAMPLE_TEXTS: [&str; 4] = [
+    "The delivery arrived two days late but the support team was helpful.",
+    "Absolutely love the new dashboard, it saves me an hour a day.",
+    "The app crashes whenever I try to export a report.",
+    "Pricing is fair for what you get.",
+];
+
+impl SeedPlan {
+    /// The plan for `task_count` tasks
+    pub fn new(task_count: u32) -> Self {
+        let users = [
+            ("admin@glyph.dev", "Dev Admin", "admin", "leader"),
+            ("annotator1@glyph.dev", "Annotator One", "user", "member"),
+            ("annotator2@glyph.dev", "Annotator Two", "user", "member"),
+            ("reviewer@glyph.dev", "Reviewer", "user", "member"),
+        ]
+        .into_iter()
+        .zip(0..)
+        .map(
+            |((email, display_name, global_role, team_role), n)| SeedUser {
+                user_id: UserId::from_uuid(seed_uuid(2, n)),
+                email,
+                display_name,
+                global_role,
+                team_role,
+            },
+        )
+        .collect();
+
+        let tasks = (0..task_count)
+            .zip(SAMPLE_TEXTS.iter().cycle())
+            .map(|(n, text)| SeedTask {
+                task_id: TaskId::from_uuid(seed_uuid(6, n)),
+                priority: i32::try_from(n % 3).unwrap_or(0),
+                input_data: json!({ "text": text, "source": format!("seed-{n}") }),
+            })
+            .collect();
+
+        let steps = vec![WorkflowStep {
+            step_id: "annotate".to_string(),
+            name: "Annotate".to_string(),
+            step_type: StepType::Annotation,
+            completion_criteria: CompletionCriteria {
+                criteria_type: CompletionCriteriaType::AnnotationCount,
+                required_count: Some(1),
+                min_agreement: None,
+            },
+            assignment: AssignmentConfig {
+                mode: AssignmentMode::Auto,
+                load_balancing: LoadBalancingStrategy::LeastLoaded,
+                required_skills: vec![],
+                exclude_previous_annotators: false,
+            },
+            consensus: None,
+            timeout: None,
+            ui: None,
+        }];
+
+        Self {
+            team_id: TeamId::from_uuid(seed_uuid(1, 0)),
+            team_name: "Dev Team",
+            users,
+            project_type_id: ProjectTypeId::from_uuid(seed_uuid(3, 0)),
+            project_type_name: "Dev Sentiment",
+            input_schema: json!({
+                "type": "object",
+                "required": ["text"],
+                "properties": { "text": { "type": "string" } }
+            }),
+            output_schema: json!({
+                "type": "object",
+                "required": ["sentiment"],
+                "properties": {
+                    "sentiment": {
+                        "type": "string",
+                        "enum": ["positive", "neutral", "negative"]
+                    },
+                    "notes": { "type": "string" }
+                }
+            }),
+            workflow_id: WorkflowId::from_uuid(seed_uuid(4, 0)),
+            workflow_name: "Dev Single Annotation",
+            steps: serde_json::to_value(steps).expect("workflow steps serialize to JSON"),
+            project_id: ProjectId::from_uuid(seed_uuid(5, 0)),
+            project_name: "Dev Sentiment Project",
+            tasks,
+        }
+    }
+
+    /// Print what the plan creates, one ID per line
+    pub fn print(&self) {
+        println!("team          {}  {}", self.team_id, self.team_name);
+        for user in &self.users {
+            println!(
+                "user          {}  {} ({}, team {})",
+                user.user_id, user.email, user.global_role, user.team_role
+            );
+        }
+        println!(
+            "project type  {}  {}",
+            self.project_type_id, self.project_type_name
+        );
+        println!("workflow      {}  {}", self.workflow_id, self.workflow_name);
+        println!("project       {}  {}", self.project_id, self.project_name);
+        match (self.tasks.first(), self.tasks.last()) {
+            (Some(first), Some(last)) => println!(
+                "tasks         {} .. {}  ({} tasks)",
+                first.task_id,
+                last.task_id,
+                self.tasks.len()
+            ),
+            _ => println!("tasks         none"),
+        }
+    }
+
+    /// Insert whatever part of the plan is missing, in one transaction.
+    ///
+    /// Returns the number of rows inserted; `0` means everything already existed.
+    pub async fn apply(&self, pool: &PgPool) -> Result<u64, sqlx::Error> {
+        let mut tx = pool.begin().await?;
+        let mut inserted = 0;
+
+        // A seed email may already belong to a user with another ID; reuse
+        // that user rather than pointing memberships at one that isn't there
+        let mut user_ids = Vec::with_capacity(self.users.len());
+        for user in &self.users {
+            let (user_id, created): (Uuid, bool) = sqlx::query_as(
+                r#"
+                INSERT INTO users (user_id, email, display_name, auth0_id, global_role)
+                VALUES ($1, $2, $3, $4, $5)
+                ON CONFLICT (email) DO UPDATE SET email = EXCLUDED.email
+                RETURNING user_id, (xmax = 0)
+                "#,
+            )
+            .bind(user.user_id.as_uuid())
+            .bind(user.email)
+            .bind(user.display_name)
+            .bind(format!("dev|{}", user.user_id))
+            .bind(user.global_role)
+            .fetch_one(&mut *tx)
+            .await?;
+            inserted += u64::from(created);
+            user_ids.push(user_id);
+        }
+
+        inserted += sqlx::query(
+            "INSERT INTO teams (team_id, name, description) VALUES ($1, $2, $3) ON CONFLICT DO NOTHING",
+        )
+        .bind(self.team_id.as_uuid())
+        .bind(self.team_name)
+        .bind("Sample team created by `glyph dev seed`")
+        .execute(&mut *tx)
+        .await?
+        .rows_affected();
+
+        for (user, user_id) in self.users.iter().zip(&user_ids) {
+            inserted += sqlx::query(
+                r#"
+                INSERT INTO team_memberships (team_id, user_id, role)
+                VALUES ($1, $2, $3::team_role)
+                ON CONFLICT DO NOTHING
+                "#,
+            )
+            .bind(self.team_id.as_uuid())
+            .bind(user_id)
+            .bind(user.team_role)
+            .execute(&mut *tx)
+            .await?
+            .rows_affected();
+        }
+
+        let created_by = user_ids[0];
+        inserted += sqlx::query(
+            r#"
+            INSERT INTO project_types (project_type_id, name, description, input_schema, output_schema, created_by)
+            VALUES ($1, $2, $3, $4, $5, $6)
+            ON CONFLICT DO NOTHING
+            "#,
+        )
+        .bind(self.project_type_id.as_uuid())
+        .bind(self.project_type_name)
+        .bind("Label a short text as positive, neutral or negative")
+        .bind(&self.input_schema)
+        .bind(&self.output_schema)
+        .bind(created_by)
+        .execute(&mut *tx)
+        .await?
+        .rows_affected();
+
+        sqlx::query(
+            r#"
+            INSERT INTO project_type_schema_versions (project_type_id, version, input_schema, output_schema)
+            VALUES ($1, 1, $2, $3)
+            ON CONFLICT DO NOTHING
+            "#,
+        )
+        .bind(self.project_type_id.as_uuid())
+        .bind(&self.input_schema)
+        .bind(&self.output_schema)
+        .execute(&mut *tx)
+        .await?;
+
+        inserted += sqlx::query(
+            r#"
+            INSERT INTO workflows (workflow_id, name, workflow_type, entry_step_id, exit_step_ids, steps)
+            VALUES ($1, $2, 'single', 'annotate', '["annotate"]', $3)
+            ON CONFLICT DO NOTHING
+            "#,
+        )
+        .bind(self.workflow_id.as_uuid())
+        .bind(self.workflow_name)
+        .bind(&self.steps)
+        .execute(&mut *tx)
+        .await?
+        .rows_affected();
+
+        inserted += sqlx::query(
+            r#"
+            INSERT INTO projects (project_id, name, description, status, workflow_id,
+                                  project_type_id, team_id, created_by)
+            VALUES ($1, $2, $3, 'active', $4, $5, $6, $7)
+            ON CONFLICT DO NOTHING
+            "#,
+        )
+        .bind(self.project_id.as_uuid())
+        .bind(self.project_name)
+        .bind("Sample project created by `glyph dev seed`")
+        .bind(self.workflow_id.as_uuid())
+        .bind(self.project_type_id.as_uuid())
+        .bind(self.team_id.as_uuid())
+        .bind(created_by)
+        .execute(&mut *tx)
+        .await?
+        .rows_affected();
+
+        for task in &self.tasks {
+            inserted += sqlx::query(
+                r#"
+                INSERT INTO tasks (task_id, project_id, priority, input_data)
+                VALUES ($1, $2, $3, $4)
+                ON CONFLICT DO NOTHING
+                "#,
+            )
+            .bind(task.task_id.as_uuid())
+            .bind(self.project_id.as_uuid())
+            .bind(task.priority)
+            .bind(&task.input_data)
+            .execute(&mut *tx)
+            .await?
+            .rows_affected();
+        }
+
+        tx.commit().await?;
+        Ok(inserted)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn plan_is_stable_across_runs() {
+        assert_eq!(SeedPlan::new(5), SeedPlan::new(5));
+    }
+
+    #[test]
+    fn plan_covers_every_entity() {
+        let plan = SeedPlan::new(DEFAULT_TASK_COUNT);
+
+        assert_eq!(plan.users.len(), 4);
+        assert_eq!(
+            plan.users
+                .iter()
+                .filter(|u| u.team_role == "leader")
+                .count(),
+            1
+        );
+        assert_eq!(plan.tasks.len(), DEFAULT_TASK_COUNT as usize);
+        let steps: Vec<WorkflowStep> = serde_json::from_value(plan.steps.clone()).unwrap();
+        assert_eq!(steps.len(), 1);
+        assert_eq!(steps[0].step_id, "annotate");
+
+        // Every seeded ID is distinct
+        let mut ids: Vec<Uuid> = plan.users.iter().map(|u| *u.user_id.as_uuid()).collect();
+        ids.extend(plan.tasks.iter().map(|t| *t.task_id.as_uuid()));
+        ids.extend([
+            *plan.team_id.as_uuid(),
+            *plan.project_type_id.as_uuid(),
+            *plan.workflow_id.as_uuid(),
+            *plan.project_id.as_uuid(),
+        ]);
+        let count = ids.len();
+        ids.sort();
+        ids.dedup();
+        assert_eq!(ids.len(), count);
+    }
+
+    #[test]
+    fn task_inputs_satisfy_the_input_schema() {
+        let plan = SeedPlan::new(8);
+        for task in &plan.tasks {
+            assert!(task.input_data["text"].is_string());
+            assert!((-100..=100).contains(&task.priority));
+        }
+        assert_eq!(plan.input_schema["required"], json!(["text"]));
+    }
+
+    #[tokio::test]
+    #[ignore = "requires DATABASE_URL pointing at a migrated database"]
+    async fn apply_is_idempotent() {
+        let url = std::env::var("DATABASE_URL").expect("DATABASE_URL must be set");
+        let pool = PgPool::connect(&url).await.unwrap();
+        let plan = SeedPlan::new(3);
+
+        plan.apply(&pool).await.unwrap();
+        assert_eq!(plan.apply(&pool).await.unwrap(), 0);
+
+        let tasks: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM tasks WHERE project_id = $1")
+            .bind(plan.project_id.as_uuid())
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+        assert!(tasks >= 3);
+    }
+
+    #[tokio::test]
+    #[ignore = "requires DATABASE_URL pointing at a migrated database"]
+    async fn apply_reuses_users_that_already_hold_a_seed_email() {
+        let url = std::env::var("DATABASE_URL").expect("DATABASE_URL must be set");
+        let pool = PgPool::connect(&url).await.unwrap();
+        let plan = SeedPlan::new(1);
+        let leader = plan.users.iter().find(|u| u.team_role == "leader").unwrap();
+
+        // Someone already signed up with the leader's email under another ID
+        let existing: Uuid = match sqlx::query_scalar(
+            r#"
+            INSERT INTO users (user_id, email, display_name, auth0_id, global_role)
+            VALUES ($1, $2, 'Existing', $3, 'user')
+            ON CONFLICT (email) DO NOTHING
+            RETURNING user_id
+            "#,
+        )
+        .bind(Uuid::new_v4())
+        .bind(leader.email)
+        .bind(format!("test|{}", Uuid::new_v4()))
+        .fetch_optional(&pool)
+        .await
+        .unwrap()
+        {
+            Some(id) => id,
+            None => sqlx::query_scalar("SELECT user_id FROM users WHERE email = $1")
+                .bind(leader.email)
+                .fetch_one(&pool)
+                .await
+                .unwrap(),
+        };
+
+        plan.apply(&pool).await.unwrap();
+
+        let role: String = sqlx::query_scalar(
+            "SELECT role::text FROM team_memberships WHERE team_id = $1 AND user_id = $2",
+        )
+        .bind(plan.team_id.as_uuid())
+        .bind(existing)
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+        assert_eq!(role, "leader");
+    }
+}
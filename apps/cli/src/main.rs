@@ -2,7 +2,20 @@
 //!
 //! Administrative command-line tool for Glyph.
 
+mod dev_seed;
+mod user_import;
+
+use std::path::PathBuf;
+use std::sync::Arc;
+
 use clap::{Parser, Subcommand};
+use glyph_db::{
+    create_pool, DatabaseConfig, DeadLetter, DeadLetterStatus, PgDeadLetterRepository,
+    PgUserRepository,
+};
+use glyph_workflow_engine::events::{ReplayVerification, StateRebuilder};
+use glyph_workflow_engine::PgEventStore;
+use uuid::Uuid;
 
 #[derive(Parser)]
 #[command(name = "glyph")]
@@ -24,6 +37,21 @@ enum Commands {
         #[command(subcommand)]
         action: ProjectCommands,
     },
+    /// Workflow maintenance commands
+    Workflow {
+        #[command(subcommand)]
+        action: WorkflowCommands,
+    },
+    /// Background job commands
+    Jobs {
+        #[command(subcommand)]
+        action: JobCommands,
+    },
+    /// Local development helpers
+    Dev {
+        #[command(subcommand)]
+        action: DevCommands,
+    },
 }
 
 #[derive(Subcommand)]
@@ -35,6 +63,11 @@ enum UserCommands {
         #[arg(short, long)]
         email: String,
     },
+    /// Import users from a CSV file with columns email,display_name,global_role
+    Import {
+        /// Path to the CSV file
+        file: PathBuf,
+    },
 }
 
 #[derive(Subcommand)]
@@ -43,6 +76,56 @@ enum ProjectCommands {
     List,
 }
 
+#[derive(Subcommand)]
+enum WorkflowCommands {
+    /// Check that a task's latest snapshot matches a full replay of its events
+    Verify {
+        /// Task whose workflow stream to check
+        task_id: Uuid,
+    },
+}
+
+#[derive(Subcommand)]
+enum JobCommands {
+    /// Inspect and requeue jobs that failed all retries
+    Dlq {
+        #[command(subcommand)]
+        action: DlqCommands,
+    },
+}
+
+#[derive(Subcommand)]
+enum DlqCommands {
+    /// List dead-lettered jobs, newest first
+    List {
+        /// Only show jobs in this state (dead, requeued, retried)
+        #[arg(long)]
+        status: Option<String>,
+        /// Maximum number of jobs to show
+        #[arg(long, default_value_t = 50)]
+        limit: i64,
+    },
+    /// Requeue a dead job for the worker to retry
+    Retry {
+        /// Dead-letter ID from `glyph jobs dlq list`
+        id: Uuid,
+    },
+}
+
+#[derive(Subcommand)]
+enum DevCommands {
+    /// Create a sample team, users, project type, workflow, project and tasks.
+    /// Safe to re-run: existing rows are left alone.
+    Seed {
+        /// Number of tasks to create
+        #[arg(long, default_value_t = dev_seed::DEFAULT_TASK_COUNT)]
+        tasks: u32,
+        /// Print the IDs that would be created without touching the database
+        #[arg(long)]
+        dry_run: bool,
+    },
+}
+
 #[tokio::main]
 async fn main() {
     let cli = Cli::parse();
@@ -55,11 +138,154 @@ async fn main() {
             UserCommands::Create { email } => {
                 println!("Creating user with email: {email} (not implemented)");
             }
+            UserCommands::Import { file } => {
+                let input = match std::fs::File::open(&file) {
+                    Ok(f) => f,
+                    Err(e) => {
+                        eprintln!("Failed to open {}: {e}", file.display());
+                        std::process::exit(1);
+                    }
+                };
+                let repo = PgUserRepository::new(connect().await);
+                let summary = user_import::import_users(&repo, input).await;
+                summary.print();
+            }
         },
         Commands::Project { action } => match action {
             ProjectCommands::List => {
                 println!("Listing projects... (not implemented)");
             }
         },
+        Commands::Workflow { action } => match action {
+            WorkflowCommands::Verify { task_id } => {
+                let store = Arc::new(PgEventStore::new(connect().await));
+                let report = match StateRebuilder::new(store).verify(task_id).await {
+                    Ok(report) => report,
+                    Err(e) => {
+                        eprintln!("Failed to verify workflow for task {task_id}: {e}");
+                        std::process::exit(1);
+                    }
+                };
+                print_verification(&report);
+                if !report.is_consistent() {
+                    std::process::exit(1);
+                }
+            }
+        },
+        Commands::Jobs { action } => match action {
+            JobCommands::Dlq { action } => run_dlq(action).await,
+        },
+        Commands::Dev { action } => match action {
+            DevCommands::Seed { tasks, dry_run } => {
+                let plan = dev_seed::SeedPlan::new(tasks);
+                if dry_run {
+                    println!("Dry run; nothing written");
+                    plan.print();
+                    return;
+                }
+                match plan.apply(&connect().await).await {
+                    Ok(0) => println!("Seed data already present"),
+                    Ok(inserted) => println!("Inserted {inserted} rows"),
+                    Err(e) => {
+                        eprintln!("Failed to seed database: {e}");
+                        std::process::exit(1);
+                    }
+                }
+                plan.print();
+            }
+        },
+    }
+}
+
+async fn run_dlq(action: DlqCommands) {
+    match action {
+        DlqCommands::List { status, limit } => {
+            let status = match status.as_deref().map(DeadLetterStatus::from_str) {
+                None => None,
+                Some(Some(status)) => Some(status),
+                Some(None) => {
+                    eprintln!("Unknown status; expected dead, requeued, or retried");
+                    std::process::exit(1);
+                }
+            };
+            let repo = PgDeadLetterRepository::new(connect().await);
+            match repo.list(status, limit).await {
+                Ok(jobs) if jobs.is_empty() => println!("Dead-letter queue is empty"),
+                Ok(jobs) => jobs.iter().for_each(print_dead_letter),
+                Err(e) => {
+                    eprintln!("Failed to list dead-lettered jobs: {e}");
+                    std::process::exit(1);
+                }
+            }
+        }
+        DlqCommands::Retry { id } => {
+            let repo = PgDeadLetterRepository::new(connect().await);
+            match repo.requeue(id).await {
+                Ok(true) => println!("Requeued {id}; the worker will retry it shortly"),
+                Ok(false) => {
+                    eprintln!("No dead job with ID {id} (it may already be requeued)");
+                    std::process::exit(1);
+                }
+                Err(e) => {
+                    eprintln!("Failed to requeue {id}: {e}");
+                    std::process::exit(1);
+                }
+            }
+        }
+    }
+}
+
+fn print_dead_letter(job: &DeadLetter) {
+    println!(
+        "{}  {:<8}  {:<16}  attempts={}  {}",
+        job.dlq_id,
+        job.status.as_str(),
+        job.job_type,
+        job.attempts,
+        job.created_at.to_rfc3339()
+    );
+    println!("    error:   {}", job.last_error);
+    println!("    payload: {}", job.payload);
+}
+
+fn print_verification(report: &ReplayVerification) {
+    let Some(version) = report.snapshot_version else {
+        println!(
+            "Task {}: no snapshot to verify ({} events)",
+            report.stream_id, report.events_replayed
+        );
+        return;
+    };
+    if report.is_consistent() {
+        println!(
+            "Task {}: snapshot v{version} matches full replay of {} events",
+            report.stream_id, report.events_replayed
+        );
+        return;
+    }
+    println!(
+        "Task {}: snapshot v{version} differs from full replay of {} events",
+        report.stream_id, report.events_replayed
+    );
+    for mismatch in &report.mismatches {
+        println!(
+            "  {}: snapshot={} events={}",
+            mismatch.field, mismatch.from_snapshot, mismatch.from_events
+        );
+    }
+}
+
+/// Connect to the database from `DATABASE_URL`, exiting on failure
+async fn connect() -> sqlx::PgPool {
+    let config = DatabaseConfig {
+        url: std::env::var("DATABASE_URL").unwrap_or_else(|_| DatabaseConfig::default().url),
+        ..Default::default()
+    };
+    match create_pool(&config).await {
+        Ok(pool) => pool,
+        Err(e) => {
+            eprintln!("Failed to connect to database: {e}");
+            std::process::exit(1);
+        }
     }
 }
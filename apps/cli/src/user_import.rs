@@ -0,0 +1,208 @@
+//! Bulk user import from CSV
+//!
+//! Expects a header row with `email,display_name,global_role`. Rows are
+//! validated up front; duplicates are skipped rather than aborting the run.
+
+use std::io::Read;
+
+use glyph_db::{CreateUserError, NewUser, UserRepository};
+use glyph_domain::GlobalRole;
+use serde::Deserialize;
+
+/// A raw CSV row
+#[derive(Debug, Deserialize)]
+struct ImportRow {
+    email: String,
+    display_name: String,
+    #[serde(default)]
+    global_role: Option<String>,
+}
+
+/// Outcome for a single CSV row (line numbers are 1-based, header is line 1)
+#[derive(Debug, PartialEq)]
+pub enum RowOutcome {
+    Created { line: usize, email: String },
+    Skipped { line: usize, email: String },
+    Failed { line: usize, reason: String },
+}
+
+/// Summary of an import run
+#[derive(Debug, Default)]
+pub struct ImportSummary {
+    pub outcomes: Vec<RowOutcome>,
+}
+
+impl ImportSummary {
+    pub fn created(&self) -> usize {
+        self.count(|o| matches!(o, RowOutcome::Created { .. }))
+    }
+
+    pub fn skipped(&self) -> usize {
+        self.count(|o| matches!(o, RowOutcome::Skipped { .. }))
+    }
+
+    pub fn failed(&self) -> usize {
+        self.count(|o| matches!(o, RowOutcome::Failed { .. }))
+    }
+
+    fn count(&self, f: impl Fn(&RowOutcome) -> bool) -> usize {
+        self.outcomes.iter().filter(|o| f(o)).count()
+    }
+
+    /// Print a human-readable summary to stdout
+    pub fn print(&self) {
+        for outcome in &self.outcomes {
+            match outcome {
+                RowOutcome::Created { .. } => {}
+                RowOutcome::Skipped { line, email } => {
+                    println!("  line {line}: skipped {email} (email already exists)");
+                }
+                RowOutcome::Failed { line, reason } => {
+                    println!("  line {line}: failed ({reason})");
+                }
+            }
+        }
+        println!(
+            "Created: {}, skipped: {}, failed: {}",
+            self.created(),
+            self.skipped(),
+            self.failed()
+        );
+    }
+}
+
+/// Parse a global role, defaulting to `user` when blank
+pub fn parse_role(role: Option<&str>) -> Result<GlobalRole, String> {
+    match role.map(str::trim).unwrap_or("").to_lowercase().as_str() {
+        "" | "user" => Ok(GlobalRole::User),
+        "admin" => Ok(GlobalRole::Admin),
+        other => Err(format!(
+            "invalid global_role '{other}' (expected admin or user)"
+        )),
+    }
+}
+
+/// Parse and validate CSV input into users to create.
+///
+/// Invalid rows are returned as failures alongside the valid ones.
+pub fn parse_users(input: impl Read) -> (Vec<(usize, NewUser)>, Vec<RowOutcome>) {
+    let mut reader = csv::ReaderBuilder::new()
+        .trim(csv::Trim::All)
+        .from_reader(input);
+    let mut users = Vec::new();
+    let mut failures = Vec::new();
+
+    for (idx, record) in reader.deserialize::<ImportRow>().enumerate() {
+        let line = idx + 2;
+        let row = match record {
+            Ok(row) => row,
+            Err(e) => {
+                failures.push(RowOutcome::Failed {
+                    line,
+                    reason: e.to_string(),
+                });
+                continue;
+            }
+        };
+
+        if !row.email.contains('@') {
+            failures.push(RowOutcome::Failed {
+                line,
+                reason: format!("invalid email '{}'", row.email),
+            });
+            continue;
+        }
+        if row.display_name.is_empty() {
+            failures.push(RowOutcome::Failed {
+                line,
+                reason: "display_name is required".to_string(),
+            });
+            continue;
+        }
+        let global_role = match parse_role(row.global_role.as_deref()) {
+            Ok(role) => role,
+            Err(reason) => {
+                failures.push(RowOutcome::Failed { line, reason });
+                continue;
+            }
+        };
+
+        users.push((
+            line,
+            NewUser {
+                email: row.email,
+                display_name: row.display_name,
+                global_role: Some(global_role),
+                ..Default::default()
+            },
+        ));
+    }
+
+    (users, failures)
+}
+
+/// Import users from CSV, creating each one and continuing past duplicates
+pub async fn import_users(repo: &impl UserRepository, input: impl Read) -> ImportSummary {
+    let (users, mut outcomes) = parse_users(input);
+
+    for (line, user) in users {
+        let outcome = match repo.create(&user).await {
+            Ok(_) => RowOutcome::Created {
+                line,
+                email: user.email,
+            },
+            Err(CreateUserError::EmailExists(email)) => RowOutcome::Skipped { line, email },
+            Err(CreateUserError::Database(e)) => RowOutcome::Failed {
+                line,
+                reason: e.to_string(),
+            },
+        };
+        outcomes.push(outcome);
+    }
+
+    outcomes.sort_by_key(|o| match o {
+        RowOutcome::Created { line, .. }
+        | RowOutcome::Skipped { line, .. }
+        | RowOutcome::Failed { line, .. } => *line,
+    });
+    ImportSummary { outcomes }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_valid_rows() {
+        let csv = "email,display_name,global_role\n\
+                   a@example.com,Alice,admin\n\
+                   b@example.com,Bob,\n";
+        let (users, failures) = parse_users(csv.as_bytes());
+        assert!(failures.is_empty());
+        assert_eq!(users.len(), 2);
+        assert_eq!(users[0].0, 2);
+        assert_eq!(users[0].1.global_role, Some(GlobalRole::Admin));
+        assert_eq!(users[1].1.global_role, Some(GlobalRole::User));
+    }
+
+    #[test]
+    fn invalid_rows_fail_without_stopping() {
+        let csv = "email,display_name,global_role\n\
+                   not-an-email,Alice,user\n\
+                   c@example.com,Carol,superuser\n\
+                   d@example.com,Dan,user\n";
+        let (users, failures) = parse_users(csv.as_bytes());
+        assert_eq!(users.len(), 1);
+        assert_eq!(users[0].1.email, "d@example.com");
+        assert_eq!(failures.len(), 2);
+        assert!(matches!(failures[0], RowOutcome::Failed { line: 2, .. }));
+        assert!(matches!(failures[1], RowOutcome::Failed { line: 3, .. }));
+    }
+
+    #[test]
+    fn role_parsing_is_case_insensitive() {
+        assert_eq!(parse_role(Some(" Admin ")), Ok(GlobalRole::Admin));
+        assert_eq!(parse_role(None), Ok(GlobalRole::User));
+        assert!(parse_role(Some("annotator")).is_err());
+    }
+}
@@ -10,6 +10,7 @@ import { api, ApiError } from "./client";
 export interface Draft {
   draft_id: string;
   task_id: string;
+  step_id: string;
   user_id: string;
   data: Record<string, unknown>;
   version: number;
@@ -19,8 +19,12 @@ export interface ProjectType {
   description: string | null;
   input_schema: Record<string, unknown>;
   output_schema: Record<string, unknown>;
+  schema_version: number;
   estimated_duration_seconds: number | null;
+  effective_duration_seconds: number | null;
+  duration_inferred: boolean;
   difficulty_level: string | null;
+  allow_overlapping_spans: boolean;
   is_system: boolean;
   skill_requirements: SkillRequirement[];
   created_by: string | null;
@@ -33,6 +37,10 @@ export interface ProjectTypeListResponse {
   total: number;
   limit: number;
   offset: number;
+  has_next: boolean;
+  has_prev: boolean;
+  next_offset: number | null;
+  prev_offset: number | null;
 }
 
 export interface CreateProjectTypeRequest {
@@ -43,6 +51,7 @@ export interface CreateProjectTypeRequest {
   estimated_duration_seconds?: number;
   difficulty_level?: string;
   skill_requirements?: Omit<SkillRequirement, 'skill_id'>[];
+  allow_overlapping_spans?: boolean;
 }
 
 export interface UpdateProjectTypeRequest {
@@ -52,6 +61,7 @@ export interface UpdateProjectTypeRequest {
   output_schema?: Record<string, unknown>;
   estimated_duration_seconds?: number;
   difficulty_level?: string;
+  allow_overlapping_spans?: boolean;
 }
 
 export interface ProjectTypeFilter {
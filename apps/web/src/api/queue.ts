@@ -32,11 +32,13 @@ export interface ProjectQueueStats {
   project_name: string;
   pending: number;
   in_progress: number;
+  overdue_steps: number;
 }
 
 export interface QueueStats {
   total_pending: number;
   total_in_progress: number;
+  total_overdue_steps: number;
   by_project: ProjectQueueStats[];
 }
 
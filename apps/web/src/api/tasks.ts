@@ -30,6 +30,7 @@ export interface TaskWithLayout {
 export interface Draft {
   draft_id: string;
   task_id: string;
+  step_id: string;
   user_id: string;
   data: Record<string, unknown>;
   version: number;
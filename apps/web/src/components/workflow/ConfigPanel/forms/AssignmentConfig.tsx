@@ -74,6 +74,12 @@ const ASSIGNMENT_MODES = [
     label: "Least Loaded",
     description: "Assign to annotator with fewest pending tasks",
   },
+  {
+    value: "skill_weighted",
+    label: "Skill Weighted",
+    description:
+      "Assign to the best weighted skill match, then fewest pending tasks",
+  },
 ];
 
 // =============================================================================
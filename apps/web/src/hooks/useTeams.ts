@@ -18,6 +18,10 @@ export interface TeamListResponse {
   total: number;
   limit: number;
   offset: number;
+  has_next: boolean;
+  has_prev: boolean;
+  next_offset: number | null;
+  prev_offset: number | null;
 }
 
 export interface TeamDetailResponse {
@@ -12,6 +12,7 @@ export interface ContactInfo {
 export interface QualityProfileResponse {
   overall_score: number | null;
   accuracy_score: number | null;
+  gold_accuracy_score: number | null;
   consistency_score: number | null;
   speed_percentile: number | null;
   total_annotations: number;
@@ -41,6 +41,7 @@ export function useQueueWebSocket(currentProjectId?: string) {
           break;
 
         case "task_status_changed":
+        case "assignment_status_changed":
           queryClient.invalidateQueries({ queryKey: ["queue"] });
           break;
 
@@ -58,6 +59,14 @@ export function useQueueWebSocket(currentProjectId?: string) {
           }
           break;
 
+        case "presence_changed":
+          if (typeof event.project_id === "string") {
+            queryClient.invalidateQueries({
+              queryKey: ["queue", "presence", event.project_id],
+            });
+          }
+          break;
+
         default:
           console.debug("[Queue] Unknown event type:", event.type);
       }
@@ -0,0 +1,365 @@
+//! Assignment job
+//!
+//! Consumes `AssignmentRequest` messages from NATS and creates the
+//! assignment. A failure is handled by its [`AssignmentDisposition`]:
+//! transient errors are retried with backoff, requests nobody can take yet
+//! are republished after a delay, requests with nothing left to do are
+//! dropped, and the rest are recorded in the dead-letter queue.
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use futures::StreamExt;
+use glyph_common::{with_backoff_if, RetryPolicy};
+use glyph_db::{
+    AssignmentRepository, PgAssignmentRepository, PgUserRepository, UserRepository,
+    DEAD_LETTER_ASSIGNMENT,
+};
+use glyph_domain::{AssignmentRequest, TaskAssignment, ASSIGNMENT_SUBJECT};
+use glyph_workflow_engine::assignment::{
+    AssignmentConfig, AssignmentDisposition, AssignmentEngine, AssignmentError,
+};
+use sqlx::PgPool;
+
+use super::dead_letters::DeadLetterSink;
+
+/// How long a request nobody can take yet waits before it is republished
+pub const REQUEUE_DELAY: Duration = Duration::from_secs(60);
+
+/// Creates assignments for requests
+#[async_trait]
+pub trait Assigner: Send + Sync {
+    async fn assign(&self, request: &AssignmentRequest) -> Result<TaskAssignment, AssignmentError>;
+}
+
+#[async_trait]
+impl<A, U> Assigner for AssignmentEngine<A, U>
+where
+    A: AssignmentRepository,
+    U: UserRepository,
+{
+    async fn assign(&self, request: &AssignmentRequest) -> Result<TaskAssignment, AssignmentError> {
+        self.assign_task_with_project(
+            request.task_id,
+            request.project_id,
+            &request.step_id,
+            request.user_id,
+        )
+        .await
+    }
+}
+
+/// The assignment engine backed by Postgres
+pub fn pg_assigner(pool: &PgPool) -> impl Assigner {
+    AssignmentEngine::new(
+        Arc::new(PgAssignmentRepository::new(pool.clone())),
+        Arc::new(PgUserRepository::new(pool.clone())),
+        AssignmentConfig::default(),
+    )
+}
+
+/// What became of an assignment request
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Outcome {
+    Assigned,
+    RequeueLater,
+    Dropped,
+    DeadLettered,
+}
+
+/// Decode an assignment request message payload
+pub fn decode(payload: &[u8]) -> Result<AssignmentRequest, serde_json::Error> {
+    serde_json::from_slice(payload)
+}
+
+/// Consume assignment requests until the subscription closes.
+///
+/// Each request is handled on its own task so a slow retry doesn't hold up
+/// the rest of the queue. Malformed messages are logged and dropped.
+pub async fn run(
+    client: async_nats::Client,
+    pool: PgPool,
+    dead_letters: Arc<dyn DeadLetterSink>,
+) -> Result<(), async_nats::SubscribeError> {
+    let assigner: Arc<dyn Assigner> = Arc::new(pg_assigner(&pool));
+    let mut subscriber = client.subscribe(ASSIGNMENT_SUBJECT).await?;
+
+    while let Some(message) = subscriber.next().await {
+        let request = match decode(&message.payload) {
+            Ok(request) => request,
+            Err(e) => {
+                tracing::warn!(error = %e, "Dropping malformed assignment request");
+                continue;
+            }
+        };
+
+        let assigner = Arc::clone(&assigner);
+        let dead_letters = Arc::clone(&dead_letters);
+        let client = client.clone();
+        tokio::spawn(async move {
+            let outcome = process(
+                assigner.as_ref(),
+                dead_letters.as_ref(),
+                &request,
+                &RetryPolicy::default(),
+            )
+            .await;
+            if outcome == Outcome::RequeueLater {
+                tokio::time::sleep(REQUEUE_DELAY).await;
+                if let Err(e) = client
+                    .publish(ASSIGNMENT_SUBJECT, message.payload.clone())
+                    .await
+                {
+                    tracing::error!(error = %e, task_id = %request.task_id, "Failed to requeue assignment request");
+                }
+            }
+        });
+    }
+
+    Ok(())
+}
+
+/// Assign a request, retrying transient failures, and handle whatever
+/// failure is left by its disposition
+pub async fn process(
+    assigner: &dyn Assigner,
+    dead_letters: &dyn DeadLetterSink,
+    request: &AssignmentRequest,
+    policy: &RetryPolicy,
+) -> Outcome {
+    let (result, attempts) = assign_with_retry(assigner, request, policy).await;
+    let Err(error) = result else {
+        return Outcome::Assigned;
+    };
+
+    match error.disposition() {
+        AssignmentDisposition::RequeueLater => {
+            tracing::info!(task_id = %request.task_id, %error, "Assignment deferred");
+            Outcome::RequeueLater
+        }
+        AssignmentDisposition::Drop => {
+            tracing::info!(task_id = %request.task_id, %error, "Dropping assignment request");
+            Outcome::Dropped
+        }
+        // Retries ran out, or the failure needs an operator
+        AssignmentDisposition::Retry | AssignmentDisposition::DeadLetter => {
+            tracing::error!(task_id = %request.task_id, %error, attempts, "Giving up on assignment request");
+            let payload = match serde_json::to_value(request) {
+                Ok(payload) => payload,
+                Err(e) => {
+                    tracing::error!(error = %e, "Failed to serialize assignment request for dead-letter queue");
+                    return Outcome::DeadLettered;
+                }
+            };
+            let attempts = i32::try_from(attempts).unwrap_or(i32::MAX);
+            if let Err(e) = dead_letters
+                .dead_letter(
+                    DEAD_LETTER_ASSIGNMENT,
+                    &payload,
+                    &error.to_string(),
+                    attempts,
+                )
+                .await
+            {
+                tracing::error!(error = %e, "Failed to record assignment request in dead-letter queue");
+            }
+            Outcome::DeadLettered
+        }
+    }
+}
+
+/// Re-run a dead-lettered assignment request.
+///
+/// A request that no longer has anything to do counts as done; any other
+/// failure returns its message and the attempts made.
+pub async fn retry_dead_letter(
+    assigner: &dyn Assigner,
+    request: &AssignmentRequest,
+    policy: &RetryPolicy,
+) -> Result<(), (String, u32)> {
+    match assign_with_retry(assigner, request, policy).await {
+        (Ok(_), _) => Ok(()),
+        (Err(error), _) if error.disposition() == AssignmentDisposition::Drop => Ok(()),
+        (Err(error), attempts) => Err((error.to_string(), attempts)),
+    }
+}
+
+/// Assign, retrying only errors classified as retryable. Also returns the
+/// number of attempts made.
+async fn assign_with_retry(
+    assigner: &dyn Assigner,
+    request: &AssignmentRequest,
+    policy: &RetryPolicy,
+) -> (Result<TaskAssignment, AssignmentError>, u32) {
+    let mut attempts = 0;
+    let result = with_backoff_if(policy, AssignmentError::is_retryable, || {
+        attempts += 1;
+        assigner.assign(request)
+    })
+    .await;
+    (result, attempts)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::VecDeque;
+    use std::sync::Mutex;
+
+    use chrono::Utc;
+    use glyph_domain::{AssignmentId, AssignmentStatus, ProjectId, TaskId, UserId};
+    use sqlx::types::Uuid;
+
+    use super::*;
+    use crate::jobs::dead_letters::tests::CapturingDeadLetters;
+
+    /// Fails with each scripted error in turn, then succeeds
+    struct ScriptedAssigner {
+        errors: Mutex<VecDeque<AssignmentError>>,
+        calls: Mutex<u32>,
+    }
+
+    impl ScriptedAssigner {
+        fn new(errors: impl IntoIterator<Item = AssignmentError>) -> Self {
+            Self {
+                errors: Mutex::new(errors.into_iter().collect()),
+                calls: Mutex::new(0),
+            }
+        }
+    }
+
+    #[async_trait]
+    impl Assigner for ScriptedAssigner {
+        async fn assign(
+            &self,
+            request: &AssignmentRequest,
+        ) -> Result<TaskAssignment, AssignmentError> {
+            *self.calls.lock().unwrap() += 1;
+            let scripted = self.errors.lock().unwrap().pop_front();
+            if let Some(error) = scripted {
+                return Err(error);
+            }
+            Ok(TaskAssignment {
+                assignment_id: AssignmentId::new(),
+                task_id: request.task_id,
+                project_id: request.project_id,
+                step_id: request.step_id.clone(),
+                user_id: request.user_id,
+                status: AssignmentStatus::Assigned,
+                assigned_at: Utc::now(),
+                accepted_at: None,
+                submitted_at: None,
+                time_spent_ms: None,
+                metadata: serde_json::json!({}),
+            })
+        }
+    }
+
+    fn request() -> AssignmentRequest {
+        AssignmentRequest {
+            task_id: TaskId::new(),
+            project_id: ProjectId::new(),
+            step_id: "annotate".to_string(),
+            user_id: UserId::new(),
+        }
+    }
+
+    fn fast_policy() -> RetryPolicy {
+        RetryPolicy {
+            max_attempts: 3,
+            base_delay: Duration::from_millis(1),
+            factor: 2.0,
+            max_delay: Duration::from_millis(5),
+            jitter: 0.0,
+        }
+    }
+
+    fn db_error() -> AssignmentError {
+        AssignmentError::DatabaseError("connection reset".to_string())
+    }
+
+    #[tokio::test]
+    async fn test_transient_errors_are_retried() {
+        let assigner = ScriptedAssigner::new([db_error(), db_error()]);
+        let dead_letters = CapturingDeadLetters::default();
+
+        let outcome = process(&assigner, &dead_letters, &request(), &fast_policy()).await;
+
+        assert_eq!(outcome, Outcome::Assigned);
+        assert_eq!(*assigner.calls.lock().unwrap(), 3);
+        assert!(dead_letters.recorded.lock().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_exhausted_retries_land_in_dead_letter_queue() {
+        let assigner = ScriptedAssigner::new([db_error(), db_error(), db_error()]);
+        let dead_letters = CapturingDeadLetters::default();
+        let request = request();
+
+        let outcome = process(&assigner, &dead_letters, &request, &fast_policy()).await;
+
+        assert_eq!(outcome, Outcome::DeadLettered);
+        let recorded = dead_letters.recorded.lock().unwrap();
+        let (job_type, payload, _, attempts) = &recorded[0];
+        assert_eq!(job_type, DEAD_LETTER_ASSIGNMENT);
+        assert_eq!(payload, &serde_json::to_value(&request).unwrap());
+        assert_eq!(*attempts, 3);
+    }
+
+    #[tokio::test]
+    async fn test_permanent_errors_are_not_retried() {
+        let id = Uuid::nil();
+        let cases = [
+            (AssignmentError::NoEligibleUsers, Outcome::RequeueLater),
+            (
+                AssignmentError::AssignmentLimitReached(id),
+                Outcome::RequeueLater,
+            ),
+            (AssignmentError::DuplicateAssignment, Outcome::Dropped),
+            (AssignmentError::UserNotEligible(id), Outcome::Dropped),
+            (
+                AssignmentError::InvalidStatusTransition,
+                Outcome::DeadLettered,
+            ),
+        ];
+
+        for (error, expected) in cases {
+            let message = error.to_string();
+            let assigner = ScriptedAssigner::new([error]);
+            let dead_letters = CapturingDeadLetters::default();
+
+            let outcome = process(&assigner, &dead_letters, &request(), &fast_policy()).await;
+
+            assert_eq!(outcome, expected, "{message}");
+            assert_eq!(*assigner.calls.lock().unwrap(), 1, "{message}");
+            assert_eq!(
+                dead_letters.recorded.lock().unwrap().len(),
+                usize::from(expected == Outcome::DeadLettered),
+                "{message}"
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn test_dead_letter_retry_treats_nothing_left_as_done() {
+        let assigner = ScriptedAssigner::new([AssignmentError::DuplicateAssignment]);
+        assert_eq!(
+            retry_dead_letter(&assigner, &request(), &fast_policy()).await,
+            Ok(())
+        );
+
+        let assigner = ScriptedAssigner::new([AssignmentError::InvalidStatusTransition]);
+        assert_eq!(
+            retry_dead_letter(&assigner, &request(), &fast_policy()).await,
+            Err((AssignmentError::InvalidStatusTransition.to_string(), 1))
+        );
+    }
+
+    #[test]
+    fn test_decode_round_trips() {
+        let request = request();
+        let payload = serde_json::to_vec(&request).unwrap();
+        assert_eq!(decode(&payload).unwrap(), request);
+        assert!(decode(b"not json").is_err());
+    }
+}
@@ -0,0 +1,198 @@
+//! Dead-letter queue job
+//!
+//! Jobs that fail all their retries are recorded through a [`DeadLetterSink`]
+//! rather than dropped. This job re-runs dead letters an operator requeued
+//! with `glyph jobs dlq retry`, returning them to the queue if they fail again.
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use glyph_common::RetryPolicy;
+use glyph_db::{
+    DeadLetter, PgDeadLetterRepository, PgWebhookRepository, DEAD_LETTER_ASSIGNMENT,
+    DEAD_LETTER_NOTIFICATION, DEAD_LETTER_WEBHOOK_DELIVERY,
+};
+use sqlx::types::Uuid;
+use sqlx::PgPool;
+
+use super::assignments;
+use crate::notifier::{default_retry_policy, send_with_retry_counted, Notifier};
+
+/// Default interval between polls for requeued jobs
+pub const DEFAULT_DEAD_LETTER_INTERVAL: Duration = Duration::from_secs(30);
+
+/// Requeued jobs retried per poll
+const BATCH_SIZE: usize = 20;
+
+/// Why a retry failed, how many attempts it made, and the narrowed payload
+/// to keep if only part of the job is left
+type RetryFailure = (String, i32, Option<serde_json::Value>);
+
+/// Records jobs that exhausted their retries
+#[async_trait]
+pub trait DeadLetterSink: Send + Sync {
+    async fn dead_letter(
+        &self,
+        job_type: &str,
+        payload: &serde_json::Value,
+        error: &str,
+        attempts: i32,
+    ) -> Result<(), sqlx::Error>;
+}
+
+#[async_trait]
+impl DeadLetterSink for PgDeadLetterRepository {
+    async fn dead_letter(
+        &self,
+        job_type: &str,
+        payload: &serde_json::Value,
+        error: &str,
+        attempts: i32,
+    ) -> Result<(), sqlx::Error> {
+        let dlq_id = self.record(job_type, payload, error, attempts).await?;
+        tracing::warn!(%dlq_id, job_type, attempts, error, "Job moved to dead-letter queue");
+        Ok(())
+    }
+}
+
+/// Retry one batch of requeued jobs, returning how many succeeded.
+///
+/// Each job is claimed and marked in its own transaction, so it only leaves
+/// the requeued state once its retry's outcome is recorded.
+pub async fn run_once(pool: &PgPool, notifier: &dyn Notifier) -> Result<usize, sqlx::Error> {
+    let dead_letters = PgDeadLetterRepository::new(pool.clone());
+    let mut retried = 0;
+
+    for _ in 0..BATCH_SIZE {
+        let mut tx = pool.begin().await?;
+        let Some(job) = dead_letters.claim_requeued(&mut tx).await? else {
+            break;
+        };
+        match retry(pool, notifier, &job).await? {
+            Ok(()) => {
+                dead_letters.mark_retried(&mut tx, job.dlq_id).await?;
+                retried += 1;
+            }
+            Err((error, attempts, payload)) => {
+                tracing::warn!(dlq_id = %job.dlq_id, job_type = %job.job_type, %error, "Requeued job failed again");
+                dead_letters
+                    .mark_dead(&mut tx, job.dlq_id, &error, attempts, payload.as_ref())
+                    .await?;
+            }
+        }
+        tx.commit().await?;
+    }
+
+    Ok(retried)
+}
+
+/// Run the dead-letter retry job forever at the given interval.
+///
+/// Failures are logged and retried on the next tick.
+pub async fn run(pool: PgPool, notifier: Arc<dyn Notifier>, interval: Duration) {
+    let mut ticker = tokio::time::interval(interval);
+    loop {
+        ticker.tick().await;
+        match run_once(&pool, notifier.as_ref()).await {
+            Ok(0) => {}
+            Ok(count) => tracing::info!(jobs = count, "Retried dead-lettered jobs"),
+            Err(e) => tracing::error!(error = %e, "Dead-letter retry pass failed"),
+        }
+    }
+}
+
+/// Re-run a dead-lettered job. The inner error carries the failure message,
+/// the attempts the retry made and, when only part of the job is left to do,
+/// the payload to keep for the next retry.
+async fn retry(
+    pool: &PgPool,
+    notifier: &dyn Notifier,
+    job: &DeadLetter,
+) -> Result<Result<(), RetryFailure>, sqlx::Error> {
+    match job.job_type.as_str() {
+        DEAD_LETTER_NOTIFICATION => {
+            let notification = match serde_json::from_value(job.payload.clone()) {
+                Ok(notification) => notification,
+                Err(e) => return Ok(Err((format!("malformed payload: {e}"), 0, None))),
+            };
+            Ok(
+                send_with_retry_counted(notifier, &notification, &default_retry_policy())
+                    .await
+                    .map_err(|failure| {
+                        (
+                            failure.error.to_string(),
+                            i32::try_from(failure.attempts).unwrap_or(i32::MAX),
+                            serde_json::to_value(&failure.undelivered).ok(),
+                        )
+                    }),
+            )
+        }
+        DEAD_LETTER_WEBHOOK_DELIVERY => {
+            let delivery_id = job
+                .payload
+                .get("delivery_id")
+                .and_then(|v| v.as_str())
+                .and_then(|v| Uuid::parse_str(v).ok());
+            let Some(delivery_id) = delivery_id else {
+                return Ok(Err(("payload has no delivery_id".to_string(), 0, None)));
+            };
+            // The webhook job makes the actual attempts; a delivery that is
+            // no longer failed has nothing to requeue.
+            PgWebhookRepository::new(pool.clone())
+                .requeue(delivery_id)
+                .await?;
+            Ok(Ok(()))
+        }
+        DEAD_LETTER_ASSIGNMENT => {
+            let request = match serde_json::from_value(job.payload.clone()) {
+                Ok(request) => request,
+                Err(e) => return Ok(Err((format!("malformed payload: {e}"), 0, None))),
+            };
+            let assigner = assignments::pg_assigner(pool);
+            Ok(
+                assignments::retry_dead_letter(&assigner, &request, &RetryPolicy::default())
+                    .await
+                    .map_err(|(error, attempts)| {
+                        (error, i32::try_from(attempts).unwrap_or(i32::MAX), None)
+                    }),
+            )
+        }
+        other => Ok(Err((format!("unknown job type '{other}'"), 0, None))),
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use std::sync::Mutex;
+
+    use super::*;
+
+    /// A recorded dead letter: job type, payload, error, attempts
+    pub type Recorded = (String, serde_json::Value, String, i32);
+
+    /// In-memory dead-letter sink
+    #[derive(Default)]
+    pub struct CapturingDeadLetters {
+        pub recorded: Mutex<Vec<Recorded>>,
+    }
+
+    #[async_trait]
+    impl DeadLetterSink for CapturingDeadLetters {
+        async fn dead_letter(
+            &self,
+            job_type: &str,
+            payload: &serde_json::Value,
+            error: &str,
+            attempts: i32,
+        ) -> Result<(), sqlx::Error> {
+            self.recorded.lock().unwrap().push((
+                job_type.to_string(),
+                payload.clone(),
+                error.to_string(),
+                attempts,
+            ));
+            Ok(())
+        }
+    }
+}
@@ -0,0 +1,306 @@
+//! Project deadline enforcement job
+//!
+//! Scans active projects past their deadline and performs the configured
+//! deadline action once per deadline. The action's notification is queued
+//! for the notification consumer through the event outbox.
+
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use glyph_db::{
+    enqueue_notification, DeadlineCandidate, ExtendedProjectUpdate, PgProjectRepository,
+    UpdateProjectError,
+};
+use glyph_domain::{DeadlineAction, Notification, NotificationKind, ProjectStatus, UserId};
+use sqlx::PgPool;
+
+/// Default interval between deadline scans
+pub const DEFAULT_DEADLINE_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Decide which action, if any, is due for a project at `now`.
+///
+/// Only active projects act, and only once per deadline: if the action
+/// already fired after the current deadline was set, nothing is due.
+pub fn select_action(candidate: &DeadlineCandidate, now: DateTime<Utc>) -> Option<DeadlineAction> {
+    if candidate.status != ProjectStatus::Active || candidate.deadline > now {
+        return None;
+    }
+    if candidate
+        .deadline_action_fired_at
+        .is_some_and(|fired| fired >= candidate.deadline)
+    {
+        return None;
+    }
+    candidate.deadline_action
+}
+
+/// Run one deadline scan, returning the number of actions performed
+pub async fn run_once(pool: &PgPool) -> Result<usize, sqlx::Error> {
+    let repo = PgProjectRepository::new(pool.clone());
+    let now = Utc::now();
+    let mut performed = 0;
+
+    for candidate in repo.list_deadline_candidates(now).await? {
+        let Some(action) = select_action(&candidate, now) else {
+            continue;
+        };
+        match perform(pool, &repo, &candidate, action, now).await {
+            Ok(true) => performed += 1,
+            Ok(false) => {}
+            Err(e) => tracing::error!(
+                project_id = %candidate.project_id,
+                action = ?action,
+                error = %e,
+                "Failed to perform project deadline action"
+            ),
+        }
+    }
+
+    Ok(performed)
+}
+
+/// Claim a project's deadline action, perform it and queue its notification
+/// in one transaction.
+///
+/// A concurrent worker that already claimed it makes this return `false`; a
+/// failure rolls the claim back so the next scan retries.
+async fn perform(
+    pool: &PgPool,
+    repo: &PgProjectRepository,
+    candidate: &DeadlineCandidate,
+    action: DeadlineAction,
+    now: DateTime<Utc>,
+) -> Result<bool, sqlx::Error> {
+    let mut tx = pool.begin().await?;
+    if !repo
+        .claim_deadline_action(&mut tx, &candidate.project_id, now)
+        .await?
+    {
+        return Ok(false);
+    }
+
+    let recipients = match action {
+        DeadlineAction::Notify => vec![candidate.created_by],
+        DeadlineAction::Pause => {
+            let update = ExtendedProjectUpdate {
+                status: Some(ProjectStatus::Paused),
+                ..Default::default()
+            };
+            repo.update_extended_in(&mut tx, &candidate.project_id, &update)
+                .await
+                .map_err(|e| match e {
+                    UpdateProjectError::NotFound(_) => sqlx::Error::RowNotFound,
+                    UpdateProjectError::Database(e) => e,
+                })?;
+            vec![candidate.created_by]
+        }
+        DeadlineAction::Escalate => repo.escalation_recipients(&candidate.project_id).await?,
+    };
+
+    enqueue_notification(
+        &mut *tx,
+        *candidate.project_id.as_uuid(),
+        &deadline_notification(candidate, action, recipients),
+    )
+    .await?;
+    tx.commit().await?;
+    Ok(true)
+}
+
+/// Run the deadline job forever at the given interval.
+///
+/// Failures are logged and retried on the next tick.
+pub async fn run(pool: PgPool, interval: Duration) {
+    let mut ticker = tokio::time::interval(interval);
+    loop {
+        ticker.tick().await;
+        match run_once(&pool).await {
+            Ok(0) => {}
+            Ok(count) => tracing::info!(actions = count, "Performed project deadline actions"),
+            Err(e) => tracing::error!(error = %e, "Project deadline scan failed"),
+        }
+    }
+}
+
+/// The notification sent when a project's deadline action fires
+pub fn deadline_notification(
+    candidate: &DeadlineCandidate,
+    action: DeadlineAction,
+    recipients: Vec<UserId>,
+) -> Notification {
+    let project_id = candidate.project_id;
+    let project_name = candidate.name.clone();
+    let deadline = candidate.deadline;
+    let kind = match action {
+        DeadlineAction::Notify | DeadlineAction::Pause => NotificationKind::DeadlineReached {
+            project_id,
+            project_name,
+            deadline,
+        },
+        DeadlineAction::Escalate => NotificationKind::DeadlineEscalated {
+            project_id,
+            project_name,
+            deadline,
+        },
+    };
+    Notification { kind, recipients }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration as ChronoDuration;
+    use glyph_domain::ProjectId;
+
+    fn candidate(action: Option<DeadlineAction>) -> DeadlineCandidate {
+        DeadlineCandidate {
+            project_id: ProjectId::new(),
+            name: "Overdue".to_string(),
+            status: ProjectStatus::Active,
+            team_id: None,
+            deadline: Utc::now() - ChronoDuration::hours(1),
+            deadline_action: action,
+            deadline_action_fired_at: None,
+            created_by: UserId::new(),
+        }
+    }
+
+    #[test]
+    fn test_notify_selected_past_deadline() {
+        let c = candidate(Some(DeadlineAction::Notify));
+        assert_eq!(select_action(&c, Utc::now()), Some(DeadlineAction::Notify));
+    }
+
+    #[test]
+    fn test_pause_selected_past_deadline() {
+        let c = candidate(Some(DeadlineAction::Pause));
+        assert_eq!(select_action(&c, Utc::now()), Some(DeadlineAction::Pause));
+    }
+
+    #[test]
+    fn test_escalate_selected_past_deadline() {
+        let c = candidate(Some(DeadlineAction::Escalate));
+        assert_eq!(
+            select_action(&c, Utc::now()),
+            Some(DeadlineAction::Escalate)
+        );
+    }
+
+    #[test]
+    fn test_nothing_before_deadline() {
+        let mut c = candidate(Some(DeadlineAction::Pause));
+        c.deadline = Utc::now() + ChronoDuration::hours(1);
+        assert_eq!(select_action(&c, Utc::now()), None);
+    }
+
+    #[test]
+    fn test_no_action_configured() {
+        assert_eq!(select_action(&candidate(None), Utc::now()), None);
+    }
+
+    #[test]
+    fn test_inactive_project_skipped() {
+        let mut c = candidate(Some(DeadlineAction::Notify));
+        c.status = ProjectStatus::Paused;
+        assert_eq!(select_action(&c, Utc::now()), None);
+    }
+
+    #[test]
+    fn test_does_not_fire_twice() {
+        let mut c = candidate(Some(DeadlineAction::Notify));
+        c.deadline_action_fired_at = Some(c.deadline + ChronoDuration::minutes(1));
+        assert_eq!(select_action(&c, Utc::now()), None);
+    }
+
+    #[test]
+    fn test_escalation_notifies_as_escalated() {
+        let c = candidate(Some(DeadlineAction::Escalate));
+        let leads = vec![UserId::new(), UserId::new()];
+        let notification = deadline_notification(&c, DeadlineAction::Escalate, leads.clone());
+        assert!(matches!(
+            notification.kind,
+            NotificationKind::DeadlineEscalated { project_id, .. } if project_id == c.project_id
+        ));
+        assert_eq!(notification.recipients, leads);
+
+        let notification = deadline_notification(&c, DeadlineAction::Pause, vec![c.created_by]);
+        assert!(matches!(
+            notification.kind,
+            NotificationKind::DeadlineReached { .. }
+        ));
+    }
+
+    #[test]
+    fn test_rearms_when_deadline_moves() {
+        let mut c = candidate(Some(DeadlineAction::Notify));
+        c.deadline_action_fired_at = Some(c.deadline - ChronoDuration::days(1));
+        assert_eq!(select_action(&c, Utc::now()), Some(DeadlineAction::Notify));
+    }
+
+    #[tokio::test]
+    #[ignore = "requires DATABASE_URL pointing at a migrated database"]
+    async fn test_pause_commits_with_its_claim_and_notification() {
+        let url = std::env::var("DATABASE_URL").expect("DATABASE_URL must be set");
+        let pool = PgPool::connect(&url).await.unwrap();
+
+        let user_id = UserId::new();
+        sqlx::query(
+            r#"
+            INSERT INTO users (user_id, email, display_name, auth0_id, global_role, status)
+            VALUES ($1, $2, 'Deadline Owner', $3, 'user', 'active')
+            "#,
+        )
+        .bind(user_id.as_uuid())
+        .bind(format!("{user_id}@deadlines.test"))
+        .bind(format!("test|{user_id}"))
+        .execute(&pool)
+        .await
+        .unwrap();
+        let project_id: sqlx::types::Uuid = sqlx::query_scalar(
+            r#"
+            WITH pt AS (
+                INSERT INTO project_types (name) VALUES ($1) RETURNING project_type_id
+            )
+            INSERT INTO projects (name, project_type_id, created_by, status, deadline, deadline_action)
+            SELECT 'Overdue', project_type_id, $2, 'active', NOW() - INTERVAL '1 hour', 'pause'
+            FROM pt
+            RETURNING project_id
+            "#,
+        )
+        .bind(format!("Deadlines {}", sqlx::types::Uuid::new_v4()))
+        .bind(user_id.as_uuid())
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+
+        run_once(&pool).await.unwrap();
+
+        let status: String =
+            sqlx::query_scalar("SELECT status::text FROM projects WHERE project_id = $1")
+                .bind(project_id)
+                .fetch_one(&pool)
+                .await
+                .unwrap();
+        assert_eq!(status, "paused");
+        let queued: Vec<sqlx::types::Json<Notification>> = sqlx::query_scalar(
+            "SELECT payload FROM event_outbox WHERE stream_id = $1 AND subject = $2",
+        )
+        .bind(project_id)
+        .bind(glyph_domain::NOTIFICATION_SUBJECT)
+        .fetch_all(&pool)
+        .await
+        .unwrap();
+        assert_eq!(queued.len(), 1);
+        assert_eq!(queued[0].recipients, vec![user_id]);
+
+        // Already claimed for this deadline
+        run_once(&pool).await.unwrap();
+        let count: i64 =
+            sqlx::query_scalar("SELECT COUNT(*) FROM event_outbox WHERE stream_id = $1")
+                .bind(project_id)
+                .fetch_one(&pool)
+                .await
+                .unwrap();
+        assert_eq!(count, 1);
+    }
+}
@@ -0,0 +1,11 @@
+//! Background jobs run by the worker
+
+pub mod assignments;
+pub mod dead_letters;
+pub mod deadlines;
+pub mod notifications;
+pub mod outbox;
+pub mod quality;
+pub mod skills;
+pub mod sla;
+pub mod webhooks;
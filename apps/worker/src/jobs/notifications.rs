@@ -0,0 +1,172 @@
+//! Notification delivery job
+//!
+//! Consumes `Notification` messages from NATS and delivers them through the
+//! configured notifier, retrying transient failures. Notifications that still
+//! fail are recorded in the dead-letter queue.
+
+use std::sync::Arc;
+
+use futures::StreamExt;
+use glyph_common::RetryPolicy;
+use glyph_db::DEAD_LETTER_NOTIFICATION;
+use glyph_domain::{Notification, NOTIFICATION_SUBJECT};
+
+use super::dead_letters::DeadLetterSink;
+use crate::notifier::{default_retry_policy, send_with_retry_counted, DeliveryFailure, Notifier};
+
+/// Decode a notification message payload
+pub fn decode(payload: &[u8]) -> Result<Notification, serde_json::Error> {
+    serde_json::from_slice(payload)
+}
+
+/// Consume notifications until the subscription closes.
+///
+/// Each message is delivered on its own task so a slow retry doesn't hold
+/// up the rest of the queue. Malformed messages are logged and dropped.
+pub async fn run(
+    client: async_nats::Client,
+    notifier: Arc<dyn Notifier>,
+    dead_letters: Arc<dyn DeadLetterSink>,
+) -> Result<(), async_nats::SubscribeError> {
+    let mut subscriber = client.subscribe(NOTIFICATION_SUBJECT).await?;
+
+    while let Some(message) = subscriber.next().await {
+        let notification = match decode(&message.payload) {
+            Ok(notification) => notification,
+            Err(e) => {
+                tracing::warn!(error = %e, "Dropping malformed notification message");
+                continue;
+            }
+        };
+
+        let notifier = Arc::clone(&notifier);
+        let dead_letters = Arc::clone(&dead_letters);
+        tokio::spawn(async move {
+            deliver(
+                notifier.as_ref(),
+                dead_letters.as_ref(),
+                &notification,
+                &default_retry_policy(),
+            )
+            .await;
+        });
+    }
+
+    Ok(())
+}
+
+/// Deliver a notification, dead-lettering it for the recipients still
+/// unreached once retries run out
+pub async fn deliver(
+    notifier: &dyn Notifier,
+    dead_letters: &dyn DeadLetterSink,
+    notification: &Notification,
+    policy: &RetryPolicy,
+) {
+    let Err(failure) = send_with_retry_counted(notifier, notification, policy).await else {
+        return;
+    };
+    let DeliveryFailure {
+        error,
+        attempts,
+        undelivered,
+    } = failure;
+
+    tracing::error!(
+        error = %error,
+        attempts,
+        title = %notification.kind.title(),
+        undelivered = undelivered.recipients.len(),
+        "Giving up on notification delivery"
+    );
+
+    // Only the recipients still waiting, so a DLQ retry doesn't repeat the rest
+    let payload = match serde_json::to_value(&undelivered) {
+        Ok(payload) => payload,
+        Err(e) => {
+            tracing::error!(error = %e, "Failed to serialize notification for dead-letter queue");
+            return;
+        }
+    };
+    let attempts = i32::try_from(attempts).unwrap_or(i32::MAX);
+    if let Err(e) = dead_letters
+        .dead_letter(
+            DEAD_LETTER_NOTIFICATION,
+            &payload,
+            &error.to_string(),
+            attempts,
+        )
+        .await
+    {
+        tracing::error!(error = %e, "Failed to record notification in dead-letter queue");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::jobs::dead_letters::tests::CapturingDeadLetters;
+    use crate::notifier::send_with_retry;
+    use crate::notifier::tests::{fast_policy, rejection_notification, CapturingNotifier};
+    use crate::notifier::NotifyError;
+
+    #[tokio::test]
+    async fn test_published_message_is_delivered() {
+        let notification = rejection_notification();
+        let payload = serde_json::to_vec(&notification).unwrap();
+
+        let decoded = decode(&payload).unwrap();
+        let notifier = CapturingNotifier::default();
+        send_with_retry(&notifier, &decoded, &fast_policy())
+            .await
+            .unwrap();
+
+        let sent = notifier.sent.lock().unwrap();
+        assert_eq!(sent.len(), 1);
+        assert_eq!(sent[0].1, notification.recipients);
+    }
+
+    #[tokio::test]
+    async fn test_exhausted_retries_land_in_dead_letter_queue() {
+        let notification = rejection_notification();
+        let notifier = CapturingNotifier::failing_with(
+            (0..1000)
+                .map(|_| NotifyError::Delivery("connection refused".to_string()))
+                .collect(),
+        );
+        let dead_letters = CapturingDeadLetters::default();
+        deliver(&notifier, &dead_letters, &notification, &fast_policy()).await;
+
+        assert!(notifier.sent.lock().unwrap().is_empty());
+        let recorded = dead_letters.recorded.lock().unwrap();
+        assert_eq!(recorded.len(), 1);
+        let (job_type, payload, error, attempts) = &recorded[0];
+        assert_eq!(job_type, DEAD_LETTER_NOTIFICATION);
+        assert_eq!(payload, &serde_json::to_value(&notification).unwrap());
+        assert_eq!(error, "delivery failed: connection refused");
+        assert_eq!(*attempts, 5);
+    }
+
+    #[tokio::test]
+    async fn test_delivered_notification_is_not_dead_lettered() {
+        let notifier = CapturingNotifier::default();
+        let dead_letters = CapturingDeadLetters::default();
+
+        deliver(
+            &notifier,
+            &dead_letters,
+            &rejection_notification(),
+            &fast_policy(),
+        )
+        .await;
+
+        assert_eq!(notifier.sent.lock().unwrap().len(), 1);
+        assert!(dead_letters.recorded.lock().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_malformed_payload_is_rejected() {
+        assert!(decode(b"{\"kind\": 42}").is_err());
+    }
+}
@@ -0,0 +1,70 @@
+//! Event outbox relay job
+//!
+//! Publishes workflow events and queued notifications from the transactional
+//! outbox to NATS and marks them sent. A row is only marked sent after the
+//! server has acknowledged the publish, so events may be delivered more than
+//! once but never dropped.
+
+use std::time::Duration;
+
+use async_trait::async_trait;
+use glyph_workflow_engine::events::DEFAULT_RELAY_BATCH;
+use glyph_workflow_engine::{relay_once, OutboxError, OutboxPublisher, PgEventStore};
+use sqlx::PgPool;
+
+/// Default interval between outbox polls
+pub const DEFAULT_OUTBOX_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Publishes outbox rows to NATS
+pub struct NatsOutboxPublisher {
+    client: async_nats::Client,
+}
+
+impl NatsOutboxPublisher {
+    pub fn new(client: async_nats::Client) -> Self {
+        Self { client }
+    }
+}
+
+#[async_trait]
+impl OutboxPublisher for NatsOutboxPublisher {
+    async fn publish(&self, subject: &str, payload: Vec<u8>) -> Result<(), OutboxError> {
+        self.client
+            .publish(subject.to_string(), payload.into())
+            .await
+            .map_err(|e| OutboxError::Publish(e.to_string()))?;
+        // Publishing only buffers; flush so the row isn't marked sent early
+        self.client
+            .flush()
+            .await
+            .map_err(|e| OutboxError::Publish(e.to_string()))
+    }
+}
+
+/// Run the relay forever at the given interval.
+///
+/// Full batches are followed immediately by another pass so a backlog drains
+/// without waiting for the next tick. Failures are logged and retried.
+pub async fn run(pool: PgPool, client: async_nats::Client, interval: Duration) {
+    let store = PgEventStore::new(pool);
+    let publisher = NatsOutboxPublisher::new(client);
+    let mut ticker = tokio::time::interval(interval);
+    loop {
+        ticker.tick().await;
+        loop {
+            match relay_once(&store, &publisher, DEFAULT_RELAY_BATCH).await {
+                Ok(0) => break,
+                Ok(count) => {
+                    tracing::debug!(events = count, "Relayed outbox events");
+                    if count < DEFAULT_RELAY_BATCH {
+                        break;
+                    }
+                }
+                Err(e) => {
+                    tracing::error!(error = %e, "Outbox relay pass failed");
+                    break;
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,275 @@
+//! Quality profile recomputation job
+//!
+//! Periodically rebuilds per-user quality profiles from annotation outcomes,
+//! including consistency against the other annotators' majority and accuracy on
+//! gold tasks. Annotators who miss too many gold tasks raise a quality alert,
+//! queued for the notification consumer.
+
+use std::collections::HashMap;
+use std::time::Duration;
+
+use glyph_db::{
+    enqueue_notification, ConsensusComparison, GoldSubmission, PgQualityProfileRepository,
+    QualityProfileRepository,
+};
+use glyph_domain::{Notification, NotificationKind, TaskId, UserId};
+use glyph_quality::{compute_consistency, score_gold};
+use sqlx::PgPool;
+
+/// Default interval between profile recomputations
+pub const DEFAULT_QUALITY_INTERVAL: Duration = Duration::from_secs(300);
+
+/// When to raise a quality alert from gold-task results
+#[derive(Debug, Clone, Copy)]
+pub struct GoldAlertConfig {
+    /// Fraction of gold tasks a user may miss before alerting
+    pub max_mismatch_rate: f64,
+    /// Gold tasks a user must have submitted before alerts apply
+    pub min_gold_tasks: u32,
+}
+
+impl Default for GoldAlertConfig {
+    fn default() -> Self {
+        Self {
+            max_mismatch_rate: 0.2,
+            min_gold_tasks: 5,
+        }
+    }
+}
+
+impl GoldAlertConfig {
+    /// Read from `GOLD_MAX_MISMATCH_RATE` and `GOLD_MIN_TASKS`, falling back to defaults
+    pub fn from_env() -> Self {
+        let defaults = Self::default();
+        Self {
+            max_mismatch_rate: std::env::var("GOLD_MAX_MISMATCH_RATE")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(defaults.max_mismatch_rate),
+            min_gold_tasks: std::env::var("GOLD_MIN_TASKS")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(defaults.min_gold_tasks),
+        }
+    }
+
+    /// Whether a user's gold results warrant an alert
+    fn should_alert(&self, outcome: &GoldOutcome) -> bool {
+        outcome.gold_tasks >= self.min_gold_tasks && outcome.mismatch_rate > self.max_mismatch_rate
+    }
+}
+
+/// A user's aggregate results on gold tasks
+#[derive(Debug, Clone, PartialEq)]
+struct GoldOutcome {
+    user_id: UserId,
+    accuracy: f64,
+    mismatch_rate: f64,
+    gold_tasks: u32,
+}
+
+/// Recompute all quality profiles once
+pub async fn run_once(pool: &PgPool, gold: GoldAlertConfig) -> Result<u64, sqlx::Error> {
+    let repo = PgQualityProfileRepository::new(pool.clone());
+    let written = repo.recompute_all().await?;
+
+    let comparisons = repo.list_consensus_comparisons().await?;
+    for (user_id, score) in consistency_by_user(&comparisons) {
+        repo.set_consistency(&user_id, score).await?;
+    }
+
+    let submissions = repo.list_gold_submissions().await?;
+    for outcome in gold_outcomes(&submissions) {
+        repo.set_gold_accuracy(&outcome.user_id, outcome.accuracy)
+            .await?;
+
+        if repo
+            .set_gold_alert(&outcome.user_id, gold.should_alert(&outcome))
+            .await?
+        {
+            let notification = Notification {
+                kind: NotificationKind::QualityAlert {
+                    user_id: outcome.user_id,
+                    mismatch_rate: outcome.mismatch_rate,
+                    gold_tasks: outcome.gold_tasks,
+                },
+                recipients: repo.alert_recipients(&outcome.user_id).await?,
+            };
+            enqueue_notification(pool, *outcome.user_id.as_uuid(), &notification).await?;
+        }
+    }
+
+    Ok(written)
+}
+
+/// Gold accuracy (mean score) and mismatch rate (share of inexact answers) per user
+fn gold_outcomes(submissions: &[GoldSubmission]) -> Vec<GoldOutcome> {
+    let mut per_user: HashMap<UserId, Vec<f64>> = HashMap::new();
+    for s in submissions {
+        per_user
+            .entry(s.user_id)
+            .or_default()
+            .push(score_gold(&s.data, &s.expected));
+    }
+
+    per_user
+        .into_iter()
+        .map(|(user_id, scores)| {
+            #[allow(clippy::cast_possible_truncation)]
+            let gold_tasks = scores.len() as u32;
+            let total = f64::from(gold_tasks);
+            let mismatches = scores.iter().filter(|s| **s < 1.0).count();
+            #[allow(clippy::cast_precision_loss)]
+            let mismatch_rate = mismatches as f64 / total;
+            GoldOutcome {
+                user_id,
+                accuracy: scores.iter().sum::<f64>() / total,
+                mismatch_rate,
+                gold_tasks,
+            }
+        })
+        .collect()
+}
+
+/// Consistency score for every user with at least one label on a task step
+/// where the other annotators reached consensus
+fn consistency_by_user(comparisons: &[ConsensusComparison]) -> Vec<(UserId, f64)> {
+    type TaskKey<'a> = (TaskId, &'a str);
+    let mut per_user: HashMap<
+        UserId,
+        (
+            Vec<(TaskKey<'_>, &serde_json::Value)>,
+            HashMap<TaskKey<'_>, &serde_json::Value>,
+        ),
+    > = HashMap::new();
+    for c in comparisons {
+        let key = (c.task_id, c.step_id.as_str());
+        let (annotations, consensus) = per_user.entry(c.user_id).or_default();
+        annotations.push((key, &c.data));
+        if let Some(agreed) = &c.consensus {
+            consensus.insert(key, agreed);
+        }
+    }
+
+    per_user
+        .into_iter()
+        .filter(|(_, (_, consensus))| !consensus.is_empty())
+        .map(|(user_id, (annotations, consensus))| {
+            (user_id, compute_consistency(&annotations, &consensus))
+        })
+        .collect()
+}
+
+/// Run the quality job forever at the given interval.
+///
+/// Failures are logged and retried on the next tick.
+pub async fn run(pool: PgPool, interval: Duration, gold: GoldAlertConfig) {
+    let mut ticker = tokio::time::interval(interval);
+    loop {
+        ticker.tick().await;
+        match run_once(&pool, gold).await {
+            Ok(count) => tracing::info!(profiles = count, "Recomputed quality profiles"),
+            Err(e) => tracing::error!(error = %e, "Quality profile recomputation failed"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn comparison(
+        task_id: TaskId,
+        user_id: UserId,
+        value: &str,
+        consensus: Option<&str>,
+    ) -> ConsensusComparison {
+        ConsensusComparison {
+            task_id,
+            step_id: "annotate".to_string(),
+            user_id,
+            data: json!({ "label": value }),
+            consensus: consensus.map(|c| json!({ "label": c })),
+        }
+    }
+
+    #[test]
+    fn test_consistency_by_user() {
+        let (alice, carol) = (UserId::new(), UserId::new());
+        let (t1, t2, tied) = (TaskId::new(), TaskId::new(), TaskId::new());
+        let comparisons = vec![
+            comparison(t1, alice, "cat", Some("cat")),
+            comparison(t1, carol, "dog", Some("cat")),
+            comparison(t2, alice, "dog", Some("dog")),
+            comparison(t2, carol, "dog", Some("dog")),
+            // No consensus among the others: not counted
+            comparison(tied, carol, "dog", None),
+        ];
+
+        let scores: HashMap<UserId, f64> = consistency_by_user(&comparisons).into_iter().collect();
+        assert!((scores[&alice] - 1.0).abs() < f64::EPSILON);
+        assert!((scores[&carol] - 0.5).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn test_users_without_consensus_tasks_are_skipped() {
+        let comparisons = vec![comparison(TaskId::new(), UserId::new(), "cat", None)];
+        assert!(consistency_by_user(&comparisons).is_empty());
+    }
+
+    fn gold(user_id: UserId, answer: &str) -> GoldSubmission {
+        GoldSubmission {
+            task_id: TaskId::new(),
+            user_id,
+            data: json!({ "label": answer, "confidence": "high" }),
+            expected: json!({ "label": "cat", "confidence": "high" }),
+        }
+    }
+
+    #[test]
+    fn test_gold_outcomes() {
+        let user = UserId::new();
+        let submissions = vec![
+            gold(user, "cat"),
+            gold(user, "cat"),
+            gold(user, "cat"),
+            gold(user, "dog"),
+        ];
+
+        let outcomes = gold_outcomes(&submissions);
+        assert_eq!(outcomes.len(), 1);
+        let outcome = &outcomes[0];
+        assert_eq!(outcome.gold_tasks, 4);
+        assert!((outcome.mismatch_rate - 0.25).abs() < f64::EPSILON);
+        // Three exact answers plus one half-right answer
+        assert!((outcome.accuracy - 3.5 / 4.0).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn test_gold_alert_threshold() {
+        let config = GoldAlertConfig {
+            max_mismatch_rate: 0.2,
+            min_gold_tasks: 4,
+        };
+        let outcome = GoldOutcome {
+            user_id: UserId::new(),
+            accuracy: 0.8,
+            mismatch_rate: 0.25,
+            gold_tasks: 4,
+        };
+        assert!(config.should_alert(&outcome));
+
+        let within_rate = GoldOutcome {
+            mismatch_rate: 0.2,
+            ..outcome.clone()
+        };
+        assert!(!config.should_alert(&within_rate));
+
+        let too_few = GoldOutcome {
+            gold_tasks: 3,
+            ..outcome
+        };
+        assert!(!config.should_alert(&too_few));
+    }
+}
@@ -0,0 +1,196 @@
+//! Skill auto-promotion job
+//!
+//! Promotes annotators one proficiency level on a skill when their recent
+//! accuracy on work requiring it stays above a threshold over enough samples.
+//! Only work done since the user reached their current level counts, so each
+//! promotion has to be earned afresh, and expired certifications are skipped.
+//! Promotions stop at expert and are recorded in the audit log.
+
+use std::time::Duration;
+
+use chrono::Utc;
+use glyph_db::{
+    next_proficiency_level, AuditAction, AuditActorType, AuditEvent, AuditWriter,
+    PgSkillRepository, SkillAccuracy, SYSTEM_ACTOR_ID,
+};
+use sqlx::PgPool;
+
+/// Default interval between promotion checks
+pub const DEFAULT_SKILL_PROMOTION_INTERVAL: Duration = Duration::from_secs(3600);
+
+/// Audit `entity_type` for skill certifications
+const USER_SKILL_ENTITY: &str = "user_skill";
+
+/// When a user's skill history earns a promotion
+#[derive(Debug, Clone, Copy)]
+pub struct SkillPromotionConfig {
+    /// Mean quality score a user must exceed
+    pub min_accuracy: f64,
+    /// Scored annotations a user must have in the window
+    pub min_samples: i64,
+    /// How far back annotations count
+    pub window_days: i64,
+}
+
+impl Default for SkillPromotionConfig {
+    fn default() -> Self {
+        Self {
+            min_accuracy: 0.9,
+            min_samples: 50,
+            window_days: 90,
+        }
+    }
+}
+
+impl SkillPromotionConfig {
+    /// Read from `SKILL_PROMOTION_MIN_ACCURACY`, `SKILL_PROMOTION_MIN_SAMPLES`
+    /// and `SKILL_PROMOTION_WINDOW_DAYS`, falling back to defaults
+    pub fn from_env() -> Self {
+        let defaults = Self::default();
+        Self {
+            min_accuracy: std::env::var("SKILL_PROMOTION_MIN_ACCURACY")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(defaults.min_accuracy),
+            min_samples: std::env::var("SKILL_PROMOTION_MIN_SAMPLES")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(defaults.min_samples),
+            window_days: std::env::var("SKILL_PROMOTION_WINDOW_DAYS")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(defaults.window_days),
+        }
+    }
+
+    /// The level a user should be promoted to, if their history qualifies.
+    ///
+    /// Users without a built-in level, at expert, or whose next level the
+    /// skill type doesn't allow are never promoted.
+    fn promotion(&self, history: &SkillAccuracy) -> Option<&'static str> {
+        if history.samples < self.min_samples || history.accuracy <= self.min_accuracy {
+            return None;
+        }
+        let next = next_proficiency_level(history.proficiency_level.as_deref()?)?;
+        match &history.allowed_levels {
+            Some(levels) if !levels.iter().any(|l| l == next) => None,
+            _ => Some(next),
+        }
+    }
+}
+
+/// Run one promotion pass, returning the number of promotions
+pub async fn run_once(pool: &PgPool, config: SkillPromotionConfig) -> Result<usize, sqlx::Error> {
+    let repo = PgSkillRepository::new(pool.clone());
+    let audit = AuditWriter::new(pool.clone());
+    let since = Utc::now() - chrono::Duration::days(config.window_days);
+    let mut promoted = 0;
+
+    for history in repo.list_skill_accuracy(since).await? {
+        let Some(next) = config.promotion(&history) else {
+            continue;
+        };
+        let Some(current) = history.proficiency_level.as_deref() else {
+            continue;
+        };
+        if !repo
+            .set_proficiency_level(&history.user_id, &history.skill_id, current, next)
+            .await?
+        {
+            continue;
+        }
+
+        tracing::info!(
+            user_id = %history.user_id,
+            skill_id = %history.skill_id,
+            from = current,
+            to = next,
+            accuracy = history.accuracy,
+            samples = history.samples,
+            "Promoted skill proficiency"
+        );
+        audit
+            .record_best_effort(AuditEvent {
+                entity_type: USER_SKILL_ENTITY,
+                entity_id: history.certification_id.to_string(),
+                action: AuditAction::Update,
+                actor_id: SYSTEM_ACTOR_ID.to_string(),
+                actor_type: AuditActorType::System,
+                data_snapshot: serde_json::json!({
+                    "user_id": history.user_id.to_string(),
+                    "skill_id": history.skill_id,
+                    "proficiency_level": next,
+                    "accuracy": history.accuracy,
+                    "samples": history.samples,
+                }),
+                changes: Some(serde_json::json!({
+                    "proficiency_level": { "old": current, "new": next },
+                })),
+                request_id: None,
+            })
+            .await;
+        promoted += 1;
+    }
+
+    Ok(promoted)
+}
+
+/// Run the promotion job forever at the given interval.
+///
+/// Failures are logged and retried on the next tick.
+pub async fn run(pool: PgPool, interval: Duration, config: SkillPromotionConfig) {
+    let mut ticker = tokio::time::interval(interval);
+    loop {
+        ticker.tick().await;
+        match run_once(&pool, config).await {
+            Ok(0) => {}
+            Ok(count) => tracing::info!(promotions = count, "Promoted skill proficiencies"),
+            Err(e) => tracing::error!(error = %e, "Skill promotion pass failed"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use glyph_domain::UserId;
+    use sqlx::types::Uuid;
+
+    use super::*;
+
+    fn history(level: Option<&str>, samples: i64, accuracy: f64) -> SkillAccuracy {
+        SkillAccuracy {
+            certification_id: Uuid::new_v4(),
+            user_id: UserId::new(),
+            skill_id: "medical-ner".to_string(),
+            proficiency_level: level.map(str::to_string),
+            allowed_levels: None,
+            samples,
+            accuracy,
+        }
+    }
+
+    #[test]
+    fn test_qualifying_history_is_promoted_one_level() {
+        let config = SkillPromotionConfig {
+            min_accuracy: 0.9,
+            min_samples: 20,
+            window_days: 90,
+        };
+
+        assert_eq!(
+            config.promotion(&history(Some("intermediate"), 25, 0.95)),
+            Some("advanced")
+        );
+        // Too few samples, or not accurate enough
+        assert_eq!(config.promotion(&history(Some("novice"), 19, 0.99)), None);
+        assert_eq!(config.promotion(&history(Some("novice"), 25, 0.9)), None);
+        // Capped at expert; no level to promote from
+        assert_eq!(config.promotion(&history(Some("expert"), 25, 0.99)), None);
+        assert_eq!(config.promotion(&history(None, 25, 0.99)), None);
+
+        // The skill type must allow the next level
+        let mut restricted = history(Some("novice"), 25, 0.99);
+        restricted.allowed_levels = Some(vec!["novice".to_string(), "expert".to_string()]);
+        assert_eq!(config.promotion(&restricted), None);
+    }
+}
@@ -0,0 +1,72 @@
+//! Workflow step SLA scan
+//!
+//! Flags steps that are still active past the SLA deadline recorded when
+//! they were activated, by appending a `StepOverdue` event to the task's
+//! workflow stream. Each activation is flagged at most once.
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use glyph_workflow_engine::events::{EventEmitter, OverdueCandidate, ReplayError, StateRebuilder};
+use glyph_workflow_engine::{Clock, EventStore, PgEventStore, SystemClock};
+use sqlx::PgPool;
+
+/// Default interval between SLA scans
+pub const DEFAULT_SLA_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Run one SLA scan as of `clock`'s current time, returning the number of
+/// steps flagged overdue
+pub async fn run_once(pool: &PgPool, clock: &dyn Clock) -> Result<usize, ReplayError> {
+    let store = Arc::new(PgEventStore::new(pool.clone()));
+    let rebuilder = StateRebuilder::new(store.clone());
+    let now = clock.now();
+    let mut flagged = 0;
+
+    for candidate in store.list_overdue_candidates(now).await? {
+        // Replay the stream so only steps that are genuinely still active get flagged
+        let events = store.load_events(candidate.stream_id, 0).await?;
+        let overdue = rebuilder.replay_events(&events)?.is_some_and(|state| {
+            state
+                .all_step_states()
+                .get(&candidate.step_id)
+                .is_some_and(|s| s.is_overdue(candidate.deadline, now))
+        });
+        if !overdue {
+            continue;
+        }
+
+        EventEmitter::new(store.clone(), candidate.stream_id, "workflow")
+            .step_overdue(&candidate.step_id, candidate.deadline)
+            .await?;
+        emit_overdue_metric(&candidate, now);
+        flagged += 1;
+    }
+
+    Ok(flagged)
+}
+
+/// Run the SLA job forever at the given interval.
+///
+/// Failures are logged and retried on the next tick.
+pub async fn run(pool: PgPool, interval: Duration) {
+    let mut ticker = tokio::time::interval(interval);
+    loop {
+        ticker.tick().await;
+        match run_once(&pool, &SystemClock).await {
+            Ok(0) => {}
+            Ok(count) => tracing::info!(steps = count, "Flagged overdue workflow steps"),
+            Err(e) => tracing::error!(error = %e, "Workflow SLA scan failed"),
+        }
+    }
+}
+
+fn emit_overdue_metric(candidate: &OverdueCandidate, now: DateTime<Utc>) {
+    tracing::warn!(
+        task_id = %candidate.stream_id,
+        step_id = %candidate.step_id,
+        deadline = %candidate.deadline.to_rfc3339(),
+        overdue_secs = (now - candidate.deadline).num_seconds(),
+        "Workflow step breached its SLA"
+    );
+}
@@ -0,0 +1,262 @@
+//! Webhook delivery job
+//!
+//! Sends queued webhook deliveries, signing each body with the project's
+//! secret. Non-2xx responses and network errors are retried with exponential
+//! backoff until [`MAX_WEBHOOK_ATTEMPTS`] is reached; every attempt is
+//! recorded on the delivery row. Deliveries that run out of attempts are also
+//! recorded in the dead-letter queue.
+//!
+//! Targets are re-checked before every attempt and requests only connect to
+//! public addresses, so a webhook can't be aimed at the internal network
+//! after it was saved. Such deliveries fail without retrying.
+
+use std::fmt::Write as _;
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use glyph_common::{check_public_url, public_only_client, RetryPolicy};
+use glyph_db::{
+    PendingDelivery, PgDeadLetterRepository, PgWebhookRepository, DEAD_LETTER_WEBHOOK_DELIVERY,
+};
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+use sqlx::PgPool;
+
+use super::dead_letters::DeadLetterSink;
+
+/// Default interval between delivery polls
+pub const DEFAULT_WEBHOOK_INTERVAL: Duration = Duration::from_secs(10);
+
+/// Attempts before a delivery is marked failed
+pub const MAX_WEBHOOK_ATTEMPTS: i32 = 8;
+
+/// Header carrying `sha256=<hex HMAC of the body>`
+pub const SIGNATURE_HEADER: &str = "X-Glyph-Signature";
+
+/// Deliveries claimed per poll
+const BATCH_SIZE: i64 = 50;
+
+/// How long a claimed delivery is hidden from other workers
+const CLAIM_LEASE_MINUTES: i64 = 5;
+
+/// Per-request timeout for webhook endpoints
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Signature header value for a request body
+pub fn sign(secret: &str, body: &[u8]) -> Result<String, hmac::digest::InvalidLength> {
+    let mut mac = Hmac::<Sha256>::new_from_slice(secret.as_bytes())?;
+    mac.update(body);
+
+    let mut signature = String::from("sha256=");
+    for byte in mac.finalize().into_bytes() {
+        let _ = write!(signature, "{byte:02x}");
+    }
+    Ok(signature)
+}
+
+/// Retry schedule for deliveries: 30s doubling, capped at 1h
+#[must_use]
+pub const fn retry_policy() -> RetryPolicy {
+    RetryPolicy {
+        max_attempts: MAX_WEBHOOK_ATTEMPTS.unsigned_abs(),
+        base_delay: Duration::from_secs(30),
+        factor: 2.0,
+        max_delay: Duration::from_secs(3600),
+        jitter: 0.0,
+    }
+}
+
+/// Delay before retrying after `attempts` failed attempts
+pub fn retry_delay(attempts: i32) -> chrono::Duration {
+    let policy = retry_policy();
+    let retry = attempts.saturating_sub(1).max(0).unsigned_abs();
+    chrono::Duration::from_std(policy.backoff(retry)).unwrap_or(chrono::Duration::MAX)
+}
+
+/// When to retry after `attempts` failed attempts, or `None` to give up
+pub fn next_retry(attempts: i32, now: DateTime<Utc>) -> Option<DateTime<Utc>> {
+    (attempts < MAX_WEBHOOK_ATTEMPTS).then(|| now + retry_delay(attempts))
+}
+
+/// A failed delivery attempt
+struct AttemptFailure {
+    status: Option<u16>,
+    error: String,
+    /// Retrying can't help, e.g. the target isn't a public address
+    permanent: bool,
+}
+
+/// Run one delivery pass, returning the number of successful deliveries
+pub async fn run_once(pool: &PgPool, client: &reqwest::Client) -> Result<usize, sqlx::Error> {
+    let repo = PgWebhookRepository::new(pool.clone());
+    let dead_letters = PgDeadLetterRepository::new(pool.clone());
+    let lease = chrono::Duration::minutes(CLAIM_LEASE_MINUTES);
+    let mut delivered = 0;
+
+    for delivery in repo.claim_due(BATCH_SIZE, lease).await? {
+        match attempt(client, &delivery).await {
+            Ok(status) => {
+                repo.record_delivered(delivery.delivery_id, status).await?;
+                delivered += 1;
+            }
+            Err(failure) => {
+                let attempts = delivery.attempts + 1;
+                let retry_at = if failure.permanent {
+                    None
+                } else {
+                    next_retry(attempts, Utc::now())
+                };
+                tracing::warn!(
+                    delivery_id = %delivery.delivery_id,
+                    project_id = %delivery.project_id,
+                    attempts,
+                    status = ?failure.status,
+                    error = %failure.error,
+                    giving_up = retry_at.is_none(),
+                    "Webhook delivery failed"
+                );
+                repo.record_failure(
+                    delivery.delivery_id,
+                    failure.status,
+                    &failure.error,
+                    retry_at,
+                )
+                .await?;
+                if retry_at.is_none() {
+                    dead_letters
+                        .dead_letter(
+                            DEAD_LETTER_WEBHOOK_DELIVERY,
+                            &serde_json::json!({ "delivery_id": delivery.delivery_id }),
+                            &failure.error,
+                            attempts,
+                        )
+                        .await?;
+                }
+            }
+        }
+    }
+
+    Ok(delivered)
+}
+
+/// Run the webhook job forever at the given interval.
+///
+/// Failures are logged and retried on the next tick.
+pub async fn run(pool: PgPool, interval: Duration) {
+    let client = public_only_client();
+    let mut ticker = tokio::time::interval(interval);
+    loop {
+        ticker.tick().await;
+        match run_once(&pool, &client).await {
+            Ok(0) => {}
+            Ok(count) => tracing::info!(deliveries = count, "Delivered webhooks"),
+            Err(e) => tracing::error!(error = %e, "Webhook delivery pass failed"),
+        }
+    }
+}
+
+async fn attempt(
+    client: &reqwest::Client,
+    delivery: &PendingDelivery,
+) -> Result<u16, AttemptFailure> {
+    let failure = |status, error: String| AttemptFailure {
+        status,
+        error,
+        permanent: false,
+    };
+
+    // IP-literal hosts never reach the client's resolver
+    if let Err(e) = check_public_url(&delivery.url) {
+        return Err(AttemptFailure {
+            status: None,
+            error: format!("webhook URL is not allowed: {e}"),
+            permanent: true,
+        });
+    }
+
+    let body = serde_json::to_vec(&delivery.payload).map_err(|e| failure(None, e.to_string()))?;
+    let signature = sign(&delivery.secret, &body).map_err(|e| failure(None, e.to_string()))?;
+
+    let response = client
+        .post(&delivery.url)
+        .timeout(REQUEST_TIMEOUT)
+        .header(reqwest::header::CONTENT_TYPE, "application/json")
+        .header(SIGNATURE_HEADER, signature)
+        .header("X-Glyph-Event", &delivery.event_type)
+        .header("X-Glyph-Delivery", delivery.delivery_id.to_string())
+        .body(body)
+        .send()
+        .await
+        .map_err(|e| failure(None, e.to_string()))?;
+
+    let status = response.status();
+    if status.is_success() {
+        return Ok(status.as_u16());
+    }
+    Err(failure(
+        Some(status.as_u16()),
+        format!("endpoint responded with {status}"),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_signature_matches_hmac_sha256() {
+        // RFC 4231 test case 2
+        let signature = sign("Jefe", b"what do ya want for nothing?").unwrap();
+        assert_eq!(
+            signature,
+            "sha256=5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843"
+        );
+    }
+
+    #[test]
+    fn test_signature_depends_on_secret_and_body() {
+        let body = br#"{"event":"workflow.completed"}"#;
+        let signature = sign("secret", body).unwrap();
+        assert_ne!(signature, sign("other", body).unwrap());
+        assert_ne!(signature, sign("secret", b"{}").unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_internal_targets_fail_permanently() {
+        for url in [
+            "https://169.254.169.254/latest",
+            "http://hooks.example.com/",
+        ] {
+            let delivery = PendingDelivery {
+                delivery_id: sqlx::types::Uuid::new_v4(),
+                project_id: glyph_domain::ProjectId::new(),
+                task_id: glyph_domain::TaskId::new(),
+                event_type: "workflow.completed".to_string(),
+                payload: serde_json::json!({}),
+                attempts: 0,
+                url: url.to_string(),
+                secret: "secret".to_string(),
+            };
+            let Err(failure) = attempt(&public_only_client(), &delivery).await else {
+                panic!("{url} should not be delivered to");
+            };
+            assert!(failure.permanent);
+            assert_eq!(failure.status, None);
+        }
+    }
+
+    #[test]
+    fn test_retry_backs_off_until_limit() {
+        assert_eq!(retry_delay(1), chrono::Duration::seconds(30));
+        assert_eq!(retry_delay(2), chrono::Duration::seconds(60));
+        assert_eq!(retry_delay(7), chrono::Duration::seconds(1920));
+        assert_eq!(retry_delay(20), chrono::Duration::seconds(3600));
+
+        let now = Utc::now();
+        assert_eq!(
+            next_retry(1, now),
+            Some(now + chrono::Duration::seconds(30))
+        );
+        assert_eq!(next_retry(MAX_WEBHOOK_ATTEMPTS, now), None);
+    }
+}
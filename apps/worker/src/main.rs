@@ -2,16 +2,108 @@
 //!
 //! Processes async jobs: assignments, quality evaluation, exports, notifications.
 
+mod jobs;
+mod notifier;
+
+use std::sync::Arc;
+
 use glyph_common::init_tracing;
+use glyph_db::{create_pool, DatabaseConfig, PgDeadLetterRepository};
 
 #[tokio::main]
 async fn main() {
     init_tracing();
     tracing::info!("Starting Glyph Worker...");
 
-    // TODO: Initialize job processor
-    // TODO: Connect to message queue
-    // TODO: Start job loop
+    let config = DatabaseConfig {
+        url: std::env::var("DATABASE_URL").unwrap_or_else(|_| DatabaseConfig::default().url),
+        ..Default::default()
+    };
+    let pool = create_pool(&config)
+        .await
+        .expect("Failed to connect to database");
+
+    let notifier = notifier::NotifierConfig::from_env()
+        .and_then(|config| config.build(pool.clone()))
+        .expect("Invalid notifier configuration");
+
+    let nats_jobs = match std::env::var("NATS_URL") {
+        Ok(url) => {
+            let client = async_nats::connect(&url)
+                .await
+                .expect("Failed to connect to NATS");
+            let notifier = Arc::clone(&notifier);
+            let dead_letters = Arc::new(PgDeadLetterRepository::new(pool.clone()));
+            let notification_client = client.clone();
+            let notification_dead_letters = Arc::clone(&dead_letters);
+            let notification_job = tokio::spawn(async move {
+                if let Err(e) = jobs::notifications::run(
+                    notification_client,
+                    notifier,
+                    notification_dead_letters,
+                )
+                .await
+                {
+                    tracing::error!(error = %e, "Notification consumer stopped");
+                }
+            });
+            let assignment_client = client.clone();
+            let assignment_pool = pool.clone();
+            let assignment_job = tokio::spawn(async move {
+                if let Err(e) =
+                    jobs::assignments::run(assignment_client, assignment_pool, dead_letters).await
+                {
+                    tracing::error!(error = %e, "Assignment consumer stopped");
+                }
+            });
+            let outbox_job = tokio::spawn(jobs::outbox::run(
+                pool.clone(),
+                client,
+                jobs::outbox::DEFAULT_OUTBOX_INTERVAL,
+            ));
+            vec![notification_job, assignment_job, outbox_job]
+        }
+        Err(_) => {
+            tracing::warn!(
+                "NATS_URL not set; queued notifications and assignment requests \
+                 will not be consumed and workflow events will not be relayed"
+            );
+            Vec::new()
+        }
+    };
+
+    let quality_job = tokio::spawn(jobs::quality::run(
+        pool.clone(),
+        jobs::quality::DEFAULT_QUALITY_INTERVAL,
+        jobs::quality::GoldAlertConfig::from_env(),
+    ));
+
+    let deadline_job = tokio::spawn(jobs::deadlines::run(
+        pool.clone(),
+        jobs::deadlines::DEFAULT_DEADLINE_INTERVAL,
+    ));
+
+    let sla_job = tokio::spawn(jobs::sla::run(
+        pool.clone(),
+        jobs::sla::DEFAULT_SLA_INTERVAL,
+    ));
+
+    let skill_job = tokio::spawn(jobs::skills::run(
+        pool.clone(),
+        jobs::skills::DEFAULT_SKILL_PROMOTION_INTERVAL,
+        jobs::skills::SkillPromotionConfig::from_env(),
+    ));
+
+    let webhook_job = tokio::spawn(jobs::webhooks::run(
+        pool.clone(),
+        jobs::webhooks::DEFAULT_WEBHOOK_INTERVAL,
+    ));
+
+    let dead_letter_job = tokio::spawn(jobs::dead_letters::run(
+        pool.clone(),
+        Arc::clone(&notifier),
+        jobs::dead_letters::DEFAULT_DEAD_LETTER_INTERVAL,
+    ));
 
     tracing::info!("Worker started. Waiting for jobs...");
 
@@ -20,4 +112,13 @@ async fn main() {
         .await
         .expect("Failed to listen for ctrl-c");
     tracing::info!("Shutting down worker...");
+    quality_job.abort();
+    deadline_job.abort();
+    sla_job.abort();
+    skill_job.abort();
+    webhook_job.abort();
+    dead_letter_job.abort();
+    for job in nats_jobs {
+        job.abort();
+    }
 }
@@ -0,0 +1,125 @@
+//! SMTP email notifier
+
+use async_trait::async_trait;
+use glyph_db::{PgUserRepository, UserRepository};
+use glyph_domain::{NotificationKind, UserId};
+use lettre::message::Mailbox;
+use lettre::transport::smtp::authentication::Credentials;
+use lettre::{AsyncSmtpTransport, AsyncTransport, Message, Tokio1Executor};
+use sqlx::PgPool;
+
+use super::{Notifier, NotifierConfigError, NotifyError};
+
+/// SMTP connection settings
+#[derive(Debug, Clone)]
+pub struct SmtpConfig {
+    pub host: String,
+    pub port: u16,
+    pub username: Option<String>,
+    pub password: Option<String>,
+    pub from: String,
+}
+
+impl SmtpConfig {
+    /// Default submission port (STARTTLS)
+    pub const DEFAULT_PORT: u16 = 587;
+}
+
+/// Sends one email per recipient, resolving addresses from the users table.
+///
+/// A failure for one recipient doesn't stop the others; the ones reached are
+/// reported so retries skip them.
+pub struct EmailNotifier {
+    transport: AsyncSmtpTransport<Tokio1Executor>,
+    from: Mailbox,
+    users: PgUserRepository,
+}
+
+impl EmailNotifier {
+    /// Create an email notifier from SMTP settings
+    pub fn new(config: SmtpConfig, pool: PgPool) -> Result<Self, NotifierConfigError> {
+        let from = config
+            .from
+            .parse()
+            .map_err(|e| NotifierConfigError::Smtp(format!("SMTP_FROM: {e}")))?;
+
+        let mut builder = AsyncSmtpTransport::<Tokio1Executor>::starttls_relay(&config.host)
+            .map_err(|e| NotifierConfigError::Smtp(e.to_string()))?
+            .port(config.port);
+        if let (Some(username), Some(password)) = (config.username, config.password) {
+            builder = builder.credentials(Credentials::new(username, password));
+        }
+
+        Ok(Self {
+            transport: builder.build(),
+            from,
+            users: PgUserRepository::new(pool),
+        })
+    }
+}
+
+#[async_trait]
+impl Notifier for EmailNotifier {
+    async fn send(
+        &self,
+        kind: &NotificationKind,
+        recipients: &[UserId],
+    ) -> Result<(), NotifyError> {
+        let mut delivered = Vec::with_capacity(recipients.len());
+        let mut failure: Option<NotifyError> = None;
+        for user_id in recipients {
+            match self.send_one(kind, user_id).await {
+                Ok(()) => delivered.push(*user_id),
+                Err(e) => {
+                    tracing::warn!(user_id = %user_id, error = %e, "Failed to email notification");
+                    // Report a transient failure over a permanent one so the
+                    // recipients it affects are retried
+                    if failure.as_ref().is_none_or(|f| !f.is_transient()) {
+                        failure = Some(e);
+                    }
+                }
+            }
+        }
+
+        match failure {
+            None => Ok(()),
+            Some(e) if delivered.is_empty() => Err(e),
+            Some(e) => Err(NotifyError::Partial {
+                delivered,
+                source: Box::new(e),
+            }),
+        }
+    }
+}
+
+impl EmailNotifier {
+    /// Email one recipient. Unknown users are skipped, not failed.
+    async fn send_one(&self, kind: &NotificationKind, user_id: &UserId) -> Result<(), NotifyError> {
+        let Some(user) = self
+            .users
+            .find_by_id(user_id)
+            .await
+            .map_err(|e| NotifyError::RecipientLookup(e.to_string()))?
+        else {
+            tracing::warn!(user_id = %user_id, "Skipping notification for unknown user");
+            return Ok(());
+        };
+
+        let to: Mailbox = user
+            .email
+            .parse()
+            .map_err(|e| NotifyError::InvalidAddress(format!("{user_id}: {e}")))?;
+        let message = Message::builder()
+            .from(self.from.clone())
+            .to(to)
+            .subject(kind.title())
+            .body(kind.body())
+            .map_err(|e| NotifyError::InvalidAddress(e.to_string()))?;
+
+        self.transport
+            .send(message)
+            .await
+            .map_err(|e| NotifyError::Delivery(e.to_string()))?;
+        Ok(())
+    }
+}
@@ -0,0 +1,434 @@
+//! Notification delivery
+//!
+//! A [`Notifier`] delivers a notification to a set of users. The concrete
+//! sink (no-op, SMTP email, or Slack webhook) is chosen by [`NotifierConfig`].
+
+mod email;
+mod slack;
+
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use async_trait::async_trait;
+use glyph_common::{with_backoff_if, RetryPolicy};
+use glyph_domain::{Notification, NotificationKind, UserId};
+use sqlx::PgPool;
+use thiserror::Error;
+
+pub use email::{EmailNotifier, SmtpConfig};
+pub use slack::SlackNotifier;
+
+/// Errors from delivering a notification
+#[derive(Debug, Error)]
+pub enum NotifyError {
+    #[error("failed to look up recipient: {0}")]
+    RecipientLookup(String),
+
+    #[error("invalid address: {0}")]
+    InvalidAddress(String),
+
+    #[error("delivery failed: {0}")]
+    Delivery(String),
+
+    #[error("rejected by sink with status {status}: {message}")]
+    Rejected { status: u16, message: String },
+
+    /// Some recipients were reached before `source` stopped the rest
+    #[error("{source} ({} recipients already notified)", delivered.len())]
+    Partial {
+        delivered: Vec<UserId>,
+        source: Box<NotifyError>,
+    },
+}
+
+impl NotifyError {
+    /// Whether retrying the same delivery could succeed
+    #[must_use]
+    pub const fn is_transient(&self) -> bool {
+        match self {
+            Self::RecipientLookup(_) | Self::Delivery(_) => true,
+            Self::InvalidAddress(_) => false,
+            Self::Rejected { status, .. } => *status == 429 || *status >= 500,
+            Self::Partial { source, .. } => source.is_transient(),
+        }
+    }
+}
+
+/// Delivers notifications to users
+#[async_trait]
+pub trait Notifier: Send + Sync {
+    /// Send a notification to the given recipients.
+    ///
+    /// Sinks that deliver to each recipient separately report the ones they
+    /// reached with [`NotifyError::Partial`], so a retry only covers the rest.
+    async fn send(&self, kind: &NotificationKind, recipients: &[UserId])
+        -> Result<(), NotifyError>;
+}
+
+/// Notifier that drops everything (default when nothing is configured)
+pub struct NoopNotifier;
+
+#[async_trait]
+impl Notifier for NoopNotifier {
+    async fn send(
+        &self,
+        kind: &NotificationKind,
+        recipients: &[UserId],
+    ) -> Result<(), NotifyError> {
+        tracing::debug!(
+            title = %kind.title(),
+            recipients = recipients.len(),
+            "Dropping notification (no notifier configured)"
+        );
+        Ok(())
+    }
+}
+
+/// Errors from reading notifier configuration
+#[derive(Debug, Error)]
+pub enum NotifierConfigError {
+    #[error("unknown notifier kind: {0} (expected noop, email, or slack)")]
+    UnknownKind(String),
+
+    #[error("missing required environment variable {0}")]
+    MissingVar(&'static str),
+
+    #[error("invalid SMTP_PORT: {0}")]
+    InvalidPort(String),
+
+    #[error("invalid SMTP configuration: {0}")]
+    Smtp(String),
+}
+
+/// Which notifier sink to use
+#[derive(Debug, Clone)]
+pub enum NotifierConfig {
+    Noop,
+    Email(SmtpConfig),
+    Slack { webhook_url: String },
+}
+
+impl NotifierConfig {
+    /// Read configuration from the environment.
+    ///
+    /// `NOTIFIER` selects the sink (`noop` when unset). Email requires
+    /// `SMTP_HOST` and `SMTP_FROM`; Slack requires `SLACK_WEBHOOK_URL`.
+    pub fn from_env() -> Result<Self, NotifierConfigError> {
+        Self::from_lookup(|key| std::env::var(key).ok())
+    }
+
+    fn from_lookup(
+        lookup: impl Fn(&'static str) -> Option<String>,
+    ) -> Result<Self, NotifierConfigError> {
+        let require = |key| lookup(key).ok_or(NotifierConfigError::MissingVar(key));
+
+        match lookup("NOTIFIER").as_deref().unwrap_or("noop") {
+            "noop" => Ok(Self::Noop),
+            "email" => {
+                let port = match lookup("SMTP_PORT") {
+                    Some(p) => p.parse().map_err(|_| NotifierConfigError::InvalidPort(p))?,
+                    None => SmtpConfig::DEFAULT_PORT,
+                };
+                Ok(Self::Email(SmtpConfig {
+                    host: require("SMTP_HOST")?,
+                    port,
+                    username: lookup("SMTP_USERNAME"),
+                    password: lookup("SMTP_PASSWORD"),
+                    from: require("SMTP_FROM")?,
+                }))
+            }
+            "slack" => Ok(Self::Slack {
+                webhook_url: require("SLACK_WEBHOOK_URL")?,
+            }),
+            other => Err(NotifierConfigError::UnknownKind(other.to_string())),
+        }
+    }
+
+    /// Build the configured notifier
+    pub fn build(self, pool: PgPool) -> Result<Arc<dyn Notifier>, NotifierConfigError> {
+        Ok(match self {
+            Self::Noop => Arc::new(NoopNotifier),
+            Self::Email(config) => Arc::new(EmailNotifier::new(config, pool)?),
+            Self::Slack { webhook_url } => Arc::new(SlackNotifier::new(webhook_url)),
+        })
+    }
+}
+
+/// Default retry policy for notification delivery
+#[must_use]
+pub const fn default_retry_policy() -> RetryPolicy {
+    RetryPolicy {
+        max_attempts: 10,
+        base_delay: Duration::from_millis(500),
+        factor: 2.0,
+        max_delay: Duration::from_secs(30),
+        jitter: 0.5,
+    }
+}
+
+/// A notification that could not be delivered to everyone
+#[derive(Debug)]
+pub struct DeliveryFailure {
+    /// The error that ended the retries
+    pub error: NotifyError,
+    /// Attempts made, including the first
+    pub attempts: u32,
+    /// The original notification, narrowed to the recipients not yet reached
+    pub undelivered: Notification,
+}
+
+/// Deliver a notification, retrying transient failures with backoff
+#[cfg(test)]
+pub async fn send_with_retry(
+    notifier: &dyn Notifier,
+    notification: &Notification,
+    policy: &RetryPolicy,
+) -> Result<(), NotifyError> {
+    send_with_retry_counted(notifier, notification, policy)
+        .await
+        .map_err(|failure| failure.error)
+}
+
+/// Like [`send_with_retry`], but a failure also reports how many attempts
+/// were made and who is still waiting for the notification.
+///
+/// Recipients a sink reports as reached are dropped from later attempts.
+pub async fn send_with_retry_counted(
+    notifier: &dyn Notifier,
+    notification: &Notification,
+    policy: &RetryPolicy,
+) -> Result<(), DeliveryFailure> {
+    let attempts = AtomicU32::new(0);
+    let pending = Mutex::new(notification.recipients.clone());
+    let result = with_backoff_if(policy, NotifyError::is_transient, || async {
+        attempts.fetch_add(1, Ordering::Relaxed);
+        let recipients = pending.lock().unwrap_or_else(|e| e.into_inner()).clone();
+        notifier
+            .send(&notification.kind, &recipients)
+            .await
+            .map_err(|e| {
+                if let NotifyError::Partial { delivered, .. } = &e {
+                    pending
+                        .lock()
+                        .unwrap_or_else(|e| e.into_inner())
+                        .retain(|user_id| !delivered.contains(user_id));
+                }
+                if e.is_transient() {
+                    tracing::warn!(error = %e, "Notification delivery failed, retrying");
+                }
+                e
+            })
+    })
+    .await;
+    result.map_err(|error| DeliveryFailure {
+        error,
+        attempts: attempts.into_inner(),
+        undelivered: Notification {
+            kind: notification.kind.clone(),
+            recipients: pending.into_inner().unwrap_or_else(|e| e.into_inner()),
+        },
+    })
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use std::collections::HashMap;
+    use std::sync::Mutex;
+
+    /// In-memory notifier that records deliveries and can fail on demand
+    #[derive(Default)]
+    pub struct CapturingNotifier {
+        pub sent: Mutex<Vec<(NotificationKind, Vec<UserId>)>>,
+        failures: Mutex<Vec<NotifyError>>,
+    }
+
+    impl CapturingNotifier {
+        pub fn failing_with(failures: Vec<NotifyError>) -> Self {
+            Self {
+                sent: Mutex::default(),
+                failures: Mutex::new(failures),
+            }
+        }
+    }
+
+    #[async_trait]
+    impl Notifier for CapturingNotifier {
+        async fn send(
+            &self,
+            kind: &NotificationKind,
+            recipients: &[UserId],
+        ) -> Result<(), NotifyError> {
+            if let Some(err) = self.failures.lock().unwrap().pop() {
+                return Err(err);
+            }
+            self.sent
+                .lock()
+                .unwrap()
+                .push((kind.clone(), recipients.to_vec()));
+            Ok(())
+        }
+    }
+
+    pub fn fast_policy() -> RetryPolicy {
+        RetryPolicy {
+            max_attempts: 5,
+            base_delay: Duration::from_millis(1),
+            factor: 2.0,
+            max_delay: Duration::from_millis(5),
+            jitter: 0.0,
+        }
+    }
+
+    pub fn rejection_notification() -> Notification {
+        Notification {
+            kind: NotificationKind::AnnotationRejected {
+                annotation_id: glyph_domain::AnnotationId::new(),
+                task_id: glyph_domain::TaskId::new(),
+                reason: None,
+            },
+            recipients: vec![UserId::new()],
+        }
+    }
+
+    #[tokio::test]
+    async fn test_capturing_notifier_records_delivery() {
+        let notifier = CapturingNotifier::default();
+        let notification = rejection_notification();
+
+        send_with_retry(&notifier, &notification, &fast_policy())
+            .await
+            .unwrap();
+
+        let sent = notifier.sent.lock().unwrap();
+        assert_eq!(sent.len(), 1);
+        assert_eq!(sent[0].0, notification.kind);
+        assert_eq!(sent[0].1, notification.recipients);
+    }
+
+    #[tokio::test]
+    async fn test_transient_failures_are_retried() {
+        let notifier = CapturingNotifier::failing_with(vec![
+            NotifyError::Delivery("connection reset".to_string()),
+            NotifyError::Rejected {
+                status: 503,
+                message: "unavailable".to_string(),
+            },
+        ]);
+
+        send_with_retry(&notifier, &rejection_notification(), &fast_policy())
+            .await
+            .unwrap();
+
+        assert_eq!(notifier.sent.lock().unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_permanent_failure_is_not_retried() {
+        let notifier = CapturingNotifier::failing_with(vec![NotifyError::Rejected {
+            status: 400,
+            message: "bad payload".to_string(),
+        }]);
+
+        let result = send_with_retry(&notifier, &rejection_notification(), &fast_policy()).await;
+
+        assert!(matches!(
+            result,
+            Err(NotifyError::Rejected { status: 400, .. })
+        ));
+        assert!(notifier.sent.lock().unwrap().is_empty());
+    }
+
+    /// Reaches every recipient except those listed, once each
+    struct FlakyRecipients {
+        failing: Mutex<Vec<UserId>>,
+        sent: Mutex<Vec<UserId>>,
+    }
+
+    #[async_trait]
+    impl Notifier for FlakyRecipients {
+        async fn send(
+            &self,
+            _kind: &NotificationKind,
+            recipients: &[UserId],
+        ) -> Result<(), NotifyError> {
+            let mut failing = self.failing.lock().unwrap();
+            let delivered: Vec<UserId> = recipients
+                .iter()
+                .filter(|r| !failing.contains(r))
+                .copied()
+                .collect();
+            self.sent.lock().unwrap().extend(&delivered);
+            if delivered.len() == recipients.len() {
+                return Ok(());
+            }
+            failing.clear();
+            Err(NotifyError::Partial {
+                delivered,
+                source: Box::new(NotifyError::Delivery("mailbox busy".to_string())),
+            })
+        }
+    }
+
+    #[tokio::test]
+    async fn test_retry_only_resends_to_failed_recipients() {
+        let (a, b, c) = (UserId::new(), UserId::new(), UserId::new());
+        let notifier = FlakyRecipients {
+            failing: Mutex::new(vec![b]),
+            sent: Mutex::default(),
+        };
+        let notification = Notification {
+            recipients: vec![a, b, c],
+            ..rejection_notification()
+        };
+
+        send_with_retry(&notifier, &notification, &fast_policy())
+            .await
+            .unwrap();
+
+        assert_eq!(*notifier.sent.lock().unwrap(), vec![a, c, b]);
+    }
+
+    #[tokio::test]
+    async fn test_failure_reports_only_undelivered_recipients() {
+        let (a, b) = (UserId::new(), UserId::new());
+        let notifier = CapturingNotifier::failing_with(vec![NotifyError::Partial {
+            delivered: vec![a],
+            source: Box::new(NotifyError::InvalidAddress(b.to_string())),
+        }]);
+        let notification = Notification {
+            recipients: vec![a, b],
+            ..rejection_notification()
+        };
+
+        let failure = send_with_retry_counted(&notifier, &notification, &fast_policy())
+            .await
+            .unwrap_err();
+
+        assert_eq!(failure.attempts, 1);
+        assert_eq!(failure.undelivered.recipients, vec![b]);
+        assert_eq!(failure.undelivered.kind, notification.kind);
+    }
+
+    #[test]
+    fn test_config_defaults_to_noop() {
+        let config = NotifierConfig::from_lookup(|_| None).unwrap();
+        assert!(matches!(config, NotifierConfig::Noop));
+    }
+
+    #[test]
+    fn test_config_selects_sink() {
+        let env: HashMap<&str, &str> = [
+            ("NOTIFIER", "slack"),
+            ("SLACK_WEBHOOK_URL", "https://hooks.slack.test/abc"),
+        ]
+        .into();
+        let config = NotifierConfig::from_lookup(|k| env.get(k).map(ToString::to_string)).unwrap();
+        assert!(matches!(config, NotifierConfig::Slack { .. }));
+
+        let env: HashMap<&str, &str> = [("NOTIFIER", "email"), ("SMTP_HOST", "smtp.test")].into();
+        let err = NotifierConfig::from_lookup(|k| env.get(k).map(ToString::to_string)).unwrap_err();
+        assert!(matches!(err, NotifierConfigError::MissingVar("SMTP_FROM")));
+    }
+}
@@ -0,0 +1,63 @@
+//! Slack incoming-webhook notifier
+
+use async_trait::async_trait;
+use glyph_domain::{NotificationKind, UserId};
+use serde_json::json;
+
+use super::{Notifier, NotifyError};
+
+/// Posts notifications to a Slack incoming webhook
+pub struct SlackNotifier {
+    client: reqwest::Client,
+    webhook_url: String,
+}
+
+impl SlackNotifier {
+    /// Create a Slack notifier for the given webhook URL
+    #[must_use]
+    pub fn new(webhook_url: String) -> Self {
+        Self {
+            client: reqwest::Client::new(),
+            webhook_url,
+        }
+    }
+}
+
+/// Build the webhook payload for a notification
+fn payload(kind: &NotificationKind, recipients: &[UserId]) -> serde_json::Value {
+    let recipients: Vec<String> = recipients.iter().map(ToString::to_string).collect();
+    json!({
+        "text": format!(
+            "*{}*\n{}\nRecipients: {}",
+            kind.title(),
+            kind.body(),
+            recipients.join(", ")
+        ),
+    })
+}
+
+#[async_trait]
+impl Notifier for SlackNotifier {
+    async fn send(
+        &self,
+        kind: &NotificationKind,
+        recipients: &[UserId],
+    ) -> Result<(), NotifyError> {
+        let response = self
+            .client
+            .post(&self.webhook_url)
+            .json(&payload(kind, recipients))
+            .send()
+            .await
+            .map_err(|e| NotifyError::Delivery(e.to_string()))?;
+
+        let status = response.status();
+        if status.is_success() {
+            return Ok(());
+        }
+        Err(NotifyError::Rejected {
+            status: status.as_u16(),
+            message: response.text().await.unwrap_or_default(),
+        })
+    }
+}
@@ -4,6 +4,14 @@
 
 use std::env;
 
+use crate::jwks::TrustedIssuer;
+
+/// Claim Auth0 puts roles in when `AUTH0_ROLES_CLAIM` is unset.
+pub const DEFAULT_ROLES_CLAIM: &str = "https://glyph.app/roles";
+
+/// Claim holding the organization a user belongs to.
+pub const ORG_CLAIM: &str = "https://glyph.app/org_id";
+
 /// Configuration error types.
 #[derive(Debug, thiserror::Error)]
 pub enum ConfigError {
@@ -27,6 +35,14 @@ pub struct Auth0Config {
     pub callback_url: String,
     /// URL to redirect after logout
     pub logout_redirect_url: String,
+    /// Access token claim holding the user's roles
+    pub roles_claim: String,
+    /// Other tenant domains whose tokens are also accepted (e.g. staging
+    /// during a migration). They must issue tokens for the same audience.
+    pub additional_domains: Vec<String>,
+    /// Whether tokens without an organization claim belong to the default
+    /// organization; otherwise they are rejected
+    pub single_tenant: bool,
 }
 
 impl Auth0Config {
@@ -40,6 +56,12 @@ impl Auth0Config {
     /// - `AUTH0_CALLBACK_URL`
     /// - `AUTH0_LOGOUT_REDIRECT_URL`
     ///
+    /// Optional variables:
+    /// - `AUTH0_ROLES_CLAIM` (defaults to [`DEFAULT_ROLES_CLAIM`])
+    /// - `AUTH0_ADDITIONAL_DOMAINS` (comma-separated tenant domains)
+    /// - `GLYPH_SINGLE_TENANT` (`true` to put users without an organization
+    ///   claim in the default organization)
+    ///
     /// # Errors
     ///
     /// Returns `ConfigError::MissingEnvVar` if any required variable is missing.
@@ -51,6 +73,13 @@ impl Auth0Config {
             api_identifier: get_required_env("AUTH0_API_IDENTIFIER")?,
             callback_url: get_required_env("AUTH0_CALLBACK_URL")?,
             logout_redirect_url: get_required_env("AUTH0_LOGOUT_REDIRECT_URL")?,
+            roles_claim: env::var("AUTH0_ROLES_CLAIM")
+                .unwrap_or_else(|_| DEFAULT_ROLES_CLAIM.to_string()),
+            additional_domains: env::var("AUTH0_ADDITIONAL_DOMAINS")
+                .map(|v| parse_domain_list(&v))
+                .unwrap_or_default(),
+            single_tenant: env::var("GLYPH_SINGLE_TENANT")
+                .is_ok_and(|v| matches!(v.trim(), "1" | "true")),
         })
     }
 
@@ -69,6 +98,31 @@ impl Auth0Config {
     pub fn jwks_url(&self) -> String {
         format!("https://{}/.well-known/jwks.json", self.domain)
     }
+
+    /// Issuers whose access tokens are accepted: the primary domain first,
+    /// then each additional domain.
+    #[must_use]
+    pub fn trusted_issuers(&self) -> Vec<TrustedIssuer> {
+        std::iter::once(&self.domain)
+            .chain(&self.additional_domains)
+            .map(|domain| {
+                TrustedIssuer::new(
+                    format!("https://{domain}/"),
+                    format!("https://{domain}/.well-known/jwks.json"),
+                )
+            })
+            .collect()
+    }
+}
+
+/// Split a comma-separated domain list, dropping blanks.
+fn parse_domain_list(value: &str) -> Vec<String> {
+    value
+        .split(',')
+        .map(str::trim)
+        .filter(|d| !d.is_empty())
+        .map(str::to_string)
+        .collect()
 }
 
 /// Get a required environment variable, returning an error if missing.
@@ -89,6 +143,9 @@ mod tests {
             api_identifier: "api".to_string(),
             callback_url: "http://localhost/callback".to_string(),
             logout_redirect_url: "http://localhost".to_string(),
+            roles_claim: DEFAULT_ROLES_CLAIM.to_string(),
+            additional_domains: vec![],
+            single_tenant: false,
         };
         assert_eq!(config.issuer(), "https://test.auth0.com/");
     }
@@ -102,10 +159,41 @@ mod tests {
             api_identifier: "api".to_string(),
             callback_url: "http://localhost/callback".to_string(),
             logout_redirect_url: "http://localhost".to_string(),
+            roles_claim: DEFAULT_ROLES_CLAIM.to_string(),
+            additional_domains: vec![],
+            single_tenant: false,
         };
         assert_eq!(
             config.jwks_url(),
             "https://test.auth0.com/.well-known/jwks.json"
         );
     }
+
+    #[test]
+    fn trusted_issuers_include_additional_domains() {
+        let config = Auth0Config {
+            domain: "prod.auth0.com".to_string(),
+            client_id: "client".to_string(),
+            client_secret: "secret".to_string(),
+            api_identifier: "api".to_string(),
+            callback_url: "http://localhost/callback".to_string(),
+            logout_redirect_url: "http://localhost".to_string(),
+            roles_claim: DEFAULT_ROLES_CLAIM.to_string(),
+            additional_domains: parse_domain_list(" staging.auth0.com, ,"),
+            single_tenant: false,
+        };
+        assert_eq!(
+            config.trusted_issuers(),
+            vec![
+                TrustedIssuer::new(
+                    "https://prod.auth0.com/",
+                    "https://prod.auth0.com/.well-known/jwks.json"
+                ),
+                TrustedIssuer::new(
+                    "https://staging.auth0.com/",
+                    "https://staging.auth0.com/.well-known/jwks.json"
+                ),
+            ]
+        );
+    }
 }
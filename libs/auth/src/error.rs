@@ -18,6 +18,10 @@ pub enum AuthError {
     #[error("token has expired")]
     TokenExpired,
 
+    /// JWT token was revoked (e.g. on logout) before it expired.
+    #[error("token has been revoked")]
+    TokenRevoked,
+
     /// No authentication token provided in request.
     #[error("authentication token missing")]
     MissingToken,
@@ -1,9 +1,13 @@
 //! JWKS (JSON Web Key Set) caching.
 //!
-//! Fetches and caches public keys from Auth0 for JWT validation.
+//! Fetches and caches public keys from Auth0 for JWT validation. A cache can
+//! trust several issuers (e.g. staging and prod tenants during a migration),
+//! each with its own key set; the token's `iss` picks which one is used.
 
-use std::sync::Arc;
+use std::collections::HashMap;
+use std::time::Duration;
 
+use glyph_common::{CircuitBreaker, CircuitError};
 use jsonwebtoken::jwk::{AlgorithmParameters, JwkSet};
 use jsonwebtoken::DecodingKey;
 use reqwest::Client;
@@ -12,46 +16,142 @@ use tracing::{info, warn};
 
 use crate::error::{AuthError, AuthResult};
 
+/// Consecutive JWKS fetch failures before an issuer's endpoint is short-circuited
+const JWKS_FAILURE_THRESHOLD: u32 = 3;
+
+/// How long a failing JWKS endpoint is short-circuited before it is probed again
+const JWKS_COOLDOWN: Duration = Duration::from_secs(30);
+
+/// An issuer whose tokens are accepted, and where its keys are published.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TrustedIssuer {
+    /// Expected `iss` claim, e.g. `https://tenant.auth0.com/`
+    pub issuer: String,
+    /// JWKS endpoint for this issuer
+    pub jwks_url: String,
+}
+
+impl TrustedIssuer {
+    /// Create a trusted issuer entry.
+    #[must_use]
+    pub fn new(issuer: impl Into<String>, jwks_url: impl Into<String>) -> Self {
+        Self {
+            issuer: issuer.into(),
+            jwks_url: jwks_url.into(),
+        }
+    }
+}
+
+/// Cached key set for one issuer.
+struct IssuerKeys {
+    jwks_url: String,
+    keys: RwLock<JwkSet>,
+    /// Stops unknown-`kid` lookups hammering an endpoint that is down
+    breaker: CircuitBreaker,
+}
+
 /// Cache for JWKS keys from Auth0.
 ///
-/// Stores JWK set and provides key lookup by key ID (kid).
-/// Supports automatic refresh when keys are not found.
+/// Stores a JWK set per trusted issuer and provides key lookup by issuer and
+/// key ID (kid). Supports automatic refresh when keys are not found.
 pub struct JwksCache {
-    keys: Arc<RwLock<JwkSet>>,
-    jwks_url: String,
+    issuers: HashMap<String, IssuerKeys>,
     http_client: Client,
 }
 
 impl JwksCache {
-    /// Create a new JWKS cache for the given URL.
+    /// Create a JWKS cache trusting a single issuer.
     ///
     /// The cache starts empty; call [`refresh`](Self::refresh) to fetch keys.
     #[must_use]
-    pub fn new(jwks_url: impl Into<String>) -> Self {
+    pub fn new(issuer: impl Into<String>, jwks_url: impl Into<String>) -> Self {
+        Self::with_issuers([TrustedIssuer::new(issuer, jwks_url)])
+    }
+
+    /// Create a JWKS cache trusting each of the given issuers.
+    ///
+    /// Tokens whose `iss` is not listed are rejected.
+    #[must_use]
+    pub fn with_issuers(issuers: impl IntoIterator<Item = TrustedIssuer>) -> Self {
         let http_client = Client::builder()
             .timeout(std::time::Duration::from_secs(10))
             .redirect(reqwest::redirect::Policy::none())
             .build()
             .expect("failed to create HTTP client");
 
+        let issuers = issuers
+            .into_iter()
+            .map(|trusted| {
+                let keys = IssuerKeys {
+                    jwks_url: trusted.jwks_url,
+                    keys: RwLock::new(JwkSet { keys: vec![] }),
+                    breaker: CircuitBreaker::new(JWKS_FAILURE_THRESHOLD, JWKS_COOLDOWN),
+                };
+                (trusted.issuer, keys)
+            })
+            .collect();
+
         Self {
-            keys: Arc::new(RwLock::new(JwkSet { keys: vec![] })),
-            jwks_url: jwks_url.into(),
+            issuers,
             http_client,
         }
     }
 
-    /// Fetch JWKS from the configured URL and update the cache.
+    /// Whether tokens from `issuer` are accepted.
+    #[must_use]
+    pub fn trusts(&self, issuer: &str) -> bool {
+        self.issuers.contains_key(issuer)
+    }
+
+    /// Fetch JWKS for every trusted issuer.
     ///
     /// # Errors
     ///
-    /// Returns `JwksFetchError` if the request fails or response is invalid.
+    /// Returns the first `JwksFetchError`; other issuers are still refreshed.
     pub async fn refresh(&self) -> AuthResult<()> {
-        info!(url = %self.jwks_url, "refreshing JWKS cache");
+        let mut result = Ok(());
+        for issuer in self.issuers.keys() {
+            if let Err(e) = self.refresh_issuer(issuer).await {
+                warn!(issuer = %issuer, error = %e, "JWKS refresh failed");
+                if result.is_ok() {
+                    result = Err(e);
+                }
+            }
+        }
+        result
+    }
+
+    /// Fetch JWKS for one issuer and update its cached keys.
+    ///
+    /// # Errors
+    ///
+    /// Returns `InvalidToken` if the issuer isn't trusted, or `JwksFetchError`
+    /// if the request fails, the response is invalid, or recent failures have
+    /// opened the issuer's circuit breaker.
+    pub async fn refresh_issuer(&self, issuer: &str) -> AuthResult<()> {
+        let entry = self.entry(issuer)?;
+        info!(issuer = %issuer, url = %entry.jwks_url, "refreshing JWKS cache");
 
+        let jwks = entry
+            .breaker
+            .call(|| self.fetch(&entry.jwks_url))
+            .await
+            .map_err(|e| match e {
+                CircuitError::Open { retry_in } => AuthError::JwksFetchError(format!(
+                    "endpoint failing, retry in {}s",
+                    retry_in.as_secs().max(1)
+                )),
+                CircuitError::Inner(e) => e,
+            })?;
+
+        info!(issuer = %issuer, key_count = jwks.keys.len(), "JWKS cache updated");
+        self.set_keys(issuer, jwks).await
+    }
+
+    async fn fetch(&self, jwks_url: &str) -> AuthResult<JwkSet> {
         let response = self
             .http_client
-            .get(&self.jwks_url)
+            .get(jwks_url)
             .send()
             .await
             .map_err(|e| AuthError::JwksFetchError(format!("request failed: {e}")))?;
@@ -63,26 +163,30 @@ impl JwksCache {
             )));
         }
 
-        let jwks: JwkSet = response
+        response
             .json()
             .await
-            .map_err(|e| AuthError::JwksFetchError(format!("invalid JSON: {e}")))?;
-
-        info!(key_count = jwks.keys.len(), "JWKS cache updated");
-
-        let mut keys = self.keys.write().await;
-        *keys = jwks;
+            .map_err(|e| AuthError::JwksFetchError(format!("invalid JSON: {e}")))
+    }
 
+    /// Replace the cached key set for a trusted issuer.
+    ///
+    /// # Errors
+    ///
+    /// Returns `InvalidToken` if the issuer isn't trusted.
+    pub async fn set_keys(&self, issuer: &str, jwks: JwkSet) -> AuthResult<()> {
+        *self.entry(issuer)?.keys.write().await = jwks;
         Ok(())
     }
 
-    /// Get a decoding key by key ID.
+    /// Get an issuer's decoding key by key ID.
     ///
     /// # Errors
     ///
-    /// Returns `KeyNotFound` if no key matches the given kid.
-    pub async fn get_key(&self, kid: &str) -> AuthResult<DecodingKey> {
-        let keys = self.keys.read().await;
+    /// Returns `InvalidToken` if the issuer isn't trusted, or `KeyNotFound`
+    /// if no key matches the given kid.
+    pub async fn get_key(&self, issuer: &str, kid: &str) -> AuthResult<DecodingKey> {
+        let keys = self.entry(issuer)?.keys.read().await;
 
         let jwk = keys
             .keys
@@ -105,17 +209,23 @@ impl JwksCache {
     /// # Errors
     ///
     /// Returns `KeyNotFound` if key is still not found after refresh.
-    pub async fn get_or_refresh_key(&self, kid: &str) -> AuthResult<DecodingKey> {
-        match self.get_key(kid).await {
+    pub async fn get_or_refresh_key(&self, issuer: &str, kid: &str) -> AuthResult<DecodingKey> {
+        match self.get_key(issuer, kid).await {
             Ok(key) => Ok(key),
             Err(AuthError::KeyNotFound { .. }) => {
-                warn!(kid = %kid, "key not found, refreshing JWKS");
-                self.refresh().await?;
-                self.get_key(kid).await
+                warn!(issuer = %issuer, kid = %kid, "key not found, refreshing JWKS");
+                self.refresh_issuer(issuer).await?;
+                self.get_key(issuer, kid).await
             }
             Err(e) => Err(e),
         }
     }
+
+    fn entry(&self, issuer: &str) -> AuthResult<&IssuerKeys> {
+        self.issuers
+            .get(issuer)
+            .ok_or_else(|| AuthError::invalid_token(format!("untrusted issuer: {issuer}")))
+    }
 }
 
 #[cfg(test)]
@@ -124,7 +234,38 @@ mod tests {
 
     #[test]
     fn new_creates_empty_cache() {
-        let cache = JwksCache::new("https://example.auth0.com/.well-known/jwks.json");
-        assert!(!cache.jwks_url.is_empty());
+        let cache = JwksCache::new(
+            "https://example.auth0.com/",
+            "https://example.auth0.com/.well-known/jwks.json",
+        );
+        let entry = cache.entry("https://example.auth0.com/").unwrap();
+        assert!(!entry.jwks_url.is_empty());
+    }
+
+    #[tokio::test]
+    async fn unknown_issuer_is_rejected() {
+        let cache = JwksCache::new(
+            "https://example.auth0.com/",
+            "https://example.auth0.com/.well-known/jwks.json",
+        );
+        assert!(cache.trusts("https://example.auth0.com/"));
+        assert!(!cache.trusts("https://evil.auth0.com/"));
+        assert!(matches!(
+            cache.get_key("https://evil.auth0.com/", "kid").await,
+            Err(AuthError::InvalidToken { .. })
+        ));
+    }
+
+    #[tokio::test]
+    async fn failing_endpoint_opens_circuit() {
+        // Nothing listens on port 1, so every fetch is refused quickly
+        let cache = JwksCache::new("https://example.auth0.com/", "http://127.0.0.1:1/jwks.json");
+        for _ in 0..JWKS_FAILURE_THRESHOLD {
+            let err = cache.refresh().await.unwrap_err();
+            assert!(err.to_string().contains("request failed"), "{err}");
+        }
+
+        let err = cache.refresh().await.unwrap_err();
+        assert!(err.to_string().contains("retry in"), "{err}");
     }
 }
@@ -2,12 +2,15 @@
 //!
 //! Validates access tokens against Auth0's public keys.
 
+use std::collections::HashMap;
+
 use jsonwebtoken::{decode, decode_header, Algorithm, Validation};
 use serde::{Deserialize, Serialize};
 
 use crate::config::Auth0Config;
 use crate::error::{AuthError, AuthResult};
 use crate::jwks::JwksCache;
+use crate::revocation::RevocationStore;
 
 /// JWT claims extracted from a validated token.
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -22,6 +25,9 @@ pub struct Claims {
     pub exp: i64,
     /// Issued at timestamp (Unix epoch)
     pub iat: i64,
+    /// Token ID, used for revocation
+    #[serde(default)]
+    pub jti: Option<String>,
     /// User's email address
     pub email: Option<String>,
     /// Whether email is verified
@@ -34,6 +40,9 @@ pub struct Claims {
     #[serde(default)]
     #[serde(alias = "https://glyph.app/roles")]
     pub roles: Option<Vec<String>>,
+    /// Remaining claims, including custom namespaced ones
+    #[serde(flatten)]
+    pub extra: HashMap<String, serde_json::Value>,
 }
 
 /// Audience claim that can be either a single string or array.
@@ -59,26 +68,30 @@ impl Audience {
 ///
 /// Performs the following validations:
 /// - RS256 algorithm (explicit, prevents algorithm confusion attacks)
-/// - Issuer matches Auth0 domain
+/// - Issuer is one the JWKS cache trusts; its key set verifies the signature
 /// - Audience matches API identifier
 /// - Token not expired (with 60-second leeway for clock skew)
+/// - Token ID (`jti`) not revoked
 ///
 /// # Arguments
 ///
 /// * `token` - The JWT access token to validate
 /// * `jwks` - JWKS cache for key lookup
-/// * `config` - Auth0 configuration for issuer/audience validation
+/// * `config` - Auth0 configuration for audience validation
+/// * `revocations` - Store of revoked token IDs
 ///
 /// # Errors
 ///
 /// Returns `AuthError` variants:
-/// - `InvalidToken` - Malformed token or invalid signature
+/// - `InvalidToken` - Malformed token, untrusted issuer, or invalid signature
 /// - `TokenExpired` - Token has expired
+/// - `TokenRevoked` - Token's `jti` has been revoked
 /// - `KeyNotFound` - Signing key not in JWKS
 pub async fn validate_jwt(
     token: &str,
     jwks: &JwksCache,
     config: &Auth0Config,
+    revocations: &dyn RevocationStore,
 ) -> AuthResult<Claims> {
     // Decode header to get the key ID
     let header = decode_header(token)
@@ -88,12 +101,20 @@ pub async fn validate_jwt(
         .kid
         .ok_or_else(|| AuthError::invalid_token("missing kid in token header"))?;
 
+    // Pick the issuer's key set; the signature check below confirms the claim
+    let issuer = unverified_issuer(token)?;
+    if !jwks.trusts(&issuer) {
+        return Err(AuthError::invalid_token(format!(
+            "untrusted issuer: {issuer}"
+        )));
+    }
+
     // Get decoding key (with auto-refresh on miss)
-    let key = jwks.get_or_refresh_key(&kid).await?;
+    let key = jwks.get_or_refresh_key(&issuer, &kid).await?;
 
     // Build validation rules
     let mut validation = Validation::new(Algorithm::RS256);
-    validation.set_issuer(&[config.issuer()]);
+    validation.set_issuer(&[&issuer]);
     validation.set_audience(&[&config.api_identifier]);
     validation.leeway = 60; // 60-second clock skew tolerance
 
@@ -103,7 +124,7 @@ pub async fn validate_jwt(
         match e.kind() {
             ErrorKind::ExpiredSignature => AuthError::TokenExpired,
             ErrorKind::InvalidIssuer => {
-                AuthError::invalid_token(format!("issuer mismatch: expected {}", config.issuer()))
+                AuthError::invalid_token(format!("issuer mismatch: expected {issuer}"))
             }
             ErrorKind::InvalidAudience => AuthError::invalid_token(format!(
                 "audience mismatch: expected {}",
@@ -114,12 +135,169 @@ pub async fn validate_jwt(
         }
     })?;
 
+    if let Some(jti) = &token_data.claims.jti {
+        if revocations.is_revoked(jti).await? {
+            return Err(AuthError::TokenRevoked);
+        }
+    }
+
     Ok(token_data.claims)
 }
 
+/// Read the `iss` claim without verifying the token.
+///
+/// Only used to choose a key set; the claim is checked again once the
+/// signature has been verified with that issuer's key.
+fn unverified_issuer(token: &str) -> AuthResult<String> {
+    use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+
+    #[derive(Deserialize)]
+    struct Issuer {
+        iss: String,
+    }
+
+    let payload = token
+        .split('.')
+        .nth(1)
+        .ok_or_else(|| AuthError::invalid_token("malformed token"))?;
+    let bytes = URL_SAFE_NO_PAD
+        .decode(payload.trim_end_matches('='))
+        .map_err(|e| AuthError::invalid_token(format!("invalid payload: {e}")))?;
+    let claims: Issuer = serde_json::from_slice(&bytes)
+        .map_err(|e| AuthError::invalid_token(format!("invalid claims: {e}")))?;
+    Ok(claims.iss)
+}
+
 #[cfg(test)]
 mod tests {
+    use jsonwebtoken::{encode, jwk::JwkSet, EncodingKey, Header};
+
     use super::*;
+    use crate::config::DEFAULT_ROLES_CLAIM;
+    use crate::jwks::TrustedIssuer;
+    use crate::revocation::InMemoryRevocationStore;
+
+    const STAGING: &str = "https://staging.auth0.com/";
+    const PROD: &str = "https://prod.auth0.com/";
+    const STAGING_KEY: &str = include_str!("testdata/staging_key.pem");
+    const PROD_KEY: &str = include_str!("testdata/prod_key.pem");
+
+    fn test_config() -> Auth0Config {
+        Auth0Config {
+            domain: "prod.auth0.com".to_string(),
+            client_id: "client".to_string(),
+            client_secret: "secret".to_string(),
+            api_identifier: "api://glyph".to_string(),
+            callback_url: "http://localhost/callback".to_string(),
+            logout_redirect_url: "http://localhost".to_string(),
+            roles_claim: DEFAULT_ROLES_CLAIM.to_string(),
+            additional_domains: vec!["staging.auth0.com".to_string()],
+            single_tenant: false,
+        }
+    }
+
+    async fn two_issuer_cache() -> JwksCache {
+        let cache = JwksCache::with_issuers(test_config().trusted_issuers());
+        let staging: JwkSet =
+            serde_json::from_str(include_str!("testdata/staging_jwks.json")).unwrap();
+        let prod: JwkSet = serde_json::from_str(include_str!("testdata/prod_jwks.json")).unwrap();
+        cache.set_keys(STAGING, staging).await.unwrap();
+        cache.set_keys(PROD, prod).await.unwrap();
+        cache
+    }
+
+    fn sign(issuer: &str, kid: &str, private_key_pem: &str) -> String {
+        sign_with_jti(
+            issuer,
+            kid,
+            private_key_pem,
+            &uuid::Uuid::new_v4().to_string(),
+        )
+    }
+
+    fn sign_with_jti(issuer: &str, kid: &str, private_key_pem: &str, jti: &str) -> String {
+        let now = chrono::Utc::now().timestamp();
+        let claims = serde_json::json!({
+            "sub": format!("auth0|{kid}"),
+            "iss": issuer,
+            "aud": "api://glyph",
+            "exp": now + 300,
+            "iat": now,
+            "jti": jti,
+        });
+        let mut header = Header::new(Algorithm::RS256);
+        header.kid = Some(kid.to_string());
+        let key = EncodingKey::from_rsa_pem(private_key_pem.as_bytes()).unwrap();
+        encode(&header, &claims, &key).unwrap()
+    }
+
+    #[tokio::test]
+    async fn tokens_from_each_configured_issuer_validate() {
+        let cache = two_issuer_cache().await;
+        let config = test_config();
+
+        let staging = sign(STAGING, "staging-key", STAGING_KEY);
+        let claims = validate_jwt(&staging, &cache, &config, &InMemoryRevocationStore::new())
+            .await
+            .unwrap();
+        assert_eq!(claims.iss, STAGING);
+        assert_eq!(claims.sub, "auth0|staging-key");
+
+        let prod = sign(PROD, "prod-key", PROD_KEY);
+        let claims = validate_jwt(&prod, &cache, &config, &InMemoryRevocationStore::new())
+            .await
+            .unwrap();
+        assert_eq!(claims.iss, PROD);
+    }
+
+    #[tokio::test]
+    async fn token_from_unlisted_issuer_is_rejected() {
+        let cache = two_issuer_cache().await;
+        let token = sign("https://other.auth0.com/", "prod-key", PROD_KEY);
+        let err = validate_jwt(
+            &token,
+            &cache,
+            &test_config(),
+            &InMemoryRevocationStore::new(),
+        )
+        .await
+        .unwrap_err();
+        assert!(
+            matches!(err, AuthError::InvalidToken { reason } if reason.contains("untrusted issuer"))
+        );
+    }
+
+    #[tokio::test]
+    async fn key_from_other_issuer_does_not_verify() {
+        // Claims to be prod but is signed with the staging key
+        let cache = JwksCache::with_issuers([TrustedIssuer::new(PROD, "http://127.0.0.1:9/jwks")]);
+        cache
+            .set_keys(
+                PROD,
+                serde_json::from_str(include_str!("testdata/prod_jwks.json")).unwrap(),
+            )
+            .await
+            .unwrap();
+        let mut forged = sign(PROD, "prod-key", STAGING_KEY);
+        assert!(validate_jwt(
+            &forged,
+            &cache,
+            &test_config(),
+            &InMemoryRevocationStore::new()
+        )
+        .await
+        .is_err());
+
+        forged = sign(PROD, "staging-key", STAGING_KEY);
+        assert!(validate_jwt(
+            &forged,
+            &cache,
+            &test_config(),
+            &InMemoryRevocationStore::new()
+        )
+        .await
+        .is_err());
+    }
 
     #[test]
     fn audience_single_contains() {
@@ -135,4 +313,27 @@ mod tests {
         assert!(aud.contains("other"));
         assert!(!aud.contains("unknown"));
     }
+
+    #[tokio::test]
+    async fn revoked_jti_is_rejected_while_others_pass() {
+        let cache = two_issuer_cache().await;
+        let config = test_config();
+        let revocations = InMemoryRevocationStore::new();
+        revocations
+            .revoke("revoked-jti", std::time::Duration::from_secs(300))
+            .await
+            .unwrap();
+
+        let revoked = sign_with_jti(PROD, "prod-key", PROD_KEY, "revoked-jti");
+        assert!(matches!(
+            validate_jwt(&revoked, &cache, &config, &revocations).await,
+            Err(AuthError::TokenRevoked)
+        ));
+
+        let live = sign_with_jti(PROD, "prod-key", PROD_KEY, "live-jti");
+        let claims = validate_jwt(&live, &cache, &config, &revocations)
+            .await
+            .unwrap();
+        assert_eq!(claims.jti.as_deref(), Some("live-jti"));
+    }
 }
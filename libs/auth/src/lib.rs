@@ -6,6 +6,7 @@
 //!
 //! - [`config`]: Auth0 configuration loaded from environment
 //! - [`error`]: Comprehensive authentication error types
+//! - [`roles`]: Mapping from token role claims to domain roles
 //!
 //! # Example
 //!
@@ -22,14 +23,22 @@ pub mod error;
 pub mod jwks;
 pub mod jwt;
 pub mod oidc;
+pub mod revocation;
+pub mod roles;
 pub mod tokens;
 
 // Re-exports for convenience
-pub use config::{Auth0Config, ConfigError};
+pub use config::{Auth0Config, ConfigError, DEFAULT_ROLES_CLAIM, ORG_CLAIM};
 pub use error::{AuthError, AuthResult};
-pub use jwks::JwksCache;
+pub use jwks::{JwksCache, TrustedIssuer};
 pub use jwt::{validate_jwt, Audience, Claims};
-pub use oidc::{Auth0Client, AuthorizationData, OidcTokenResponse};
+pub use oidc::{
+    verify_id_token_nonce, verify_state, Auth0Client, AuthorizationData, OidcTokenResponse,
+};
+pub use revocation::{
+    InMemoryRevocationStore, RedisRevocationStore, RevocationStore, REVOKED_KEY_PREFIX,
+};
+pub use roles::{effective_roles, global_role, Role};
 pub use tokens::{
     clear_auth_cookies, clear_pkce_cookie, parse_pkce_cookie, set_auth_cookies, set_pkce_cookie,
     ACCESS_TOKEN_COOKIE, PKCE_STATE_COOKIE, REFRESH_TOKEN_COOKIE,
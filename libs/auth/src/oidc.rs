@@ -131,16 +131,17 @@ impl Auth0Client {
     ///
     /// * `code` - Authorization code from callback
     /// * `pkce_verifier` - PKCE verifier from authorization request
-    /// * `_expected_nonce` - Nonce from authorization request (validated via ID token claims)
+    /// * `expected_nonce` - Nonce from authorization request (checked against the ID token)
     ///
     /// # Errors
     ///
-    /// Returns `TokenExchangeError` if code exchange fails.
+    /// Returns `TokenExchangeError` if code exchange fails, or `InvalidNonce`
+    /// if the ID token's nonce doesn't match the authorization request.
     pub async fn exchange_code(
         &self,
         code: &str,
         pkce_verifier: &str,
-        _expected_nonce: &str,
+        expected_nonce: &str,
     ) -> AuthResult<OidcTokenResponse> {
         let params = [
             ("grant_type", "authorization_code"),
@@ -177,6 +178,8 @@ impl Auth0Client {
             .id_token
             .ok_or_else(|| AuthError::TokenExchangeError("no ID token in response".to_string()))?;
 
+        verify_id_token_nonce(&id_token, expected_nonce)?;
+
         info!("token exchange successful");
 
         Ok(OidcTokenResponse {
@@ -251,6 +254,58 @@ impl Auth0Client {
     }
 }
 
+/// Check the `state` returned on callback against the one stored at login.
+///
+/// # Errors
+///
+/// Returns `InvalidState` if they differ.
+pub fn verify_state(expected: &str, returned: &str) -> AuthResult<()> {
+    if constant_time_eq(expected.as_bytes(), returned.as_bytes()) {
+        Ok(())
+    } else {
+        Err(AuthError::InvalidState)
+    }
+}
+
+/// Check the `nonce` claim of an ID token against the one sent at login.
+///
+/// The token came straight from the token endpoint over TLS, so only its
+/// claims are read here; the signature is not re-verified.
+///
+/// # Errors
+///
+/// Returns `InvalidToken` if the token can't be decoded, or `InvalidNonce` if
+/// the nonce is missing or differs.
+pub fn verify_id_token_nonce(id_token: &str, expected_nonce: &str) -> AuthResult<()> {
+    use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+
+    let payload = id_token
+        .split('.')
+        .nth(1)
+        .ok_or_else(|| AuthError::invalid_token("ID token is not a JWT"))?;
+    let bytes = URL_SAFE_NO_PAD
+        .decode(payload.trim_end_matches('='))
+        .map_err(|e| AuthError::invalid_token(format!("ID token payload: {e}")))?;
+    let claims: IdTokenNonce = serde_json::from_slice(&bytes)
+        .map_err(|e| AuthError::invalid_token(format!("ID token claims: {e}")))?;
+
+    match claims.nonce {
+        Some(nonce) if constant_time_eq(nonce.as_bytes(), expected_nonce.as_bytes()) => Ok(()),
+        _ => Err(AuthError::InvalidNonce),
+    }
+}
+
+/// The only ID token claim checked during code exchange.
+#[derive(Debug, Deserialize)]
+struct IdTokenNonce {
+    nonce: Option<String>,
+}
+
+/// Compare two byte strings without short-circuiting on the first difference.
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).fold(0u8, |acc, (x, y)| acc | (x ^ y)) == 0
+}
+
 /// Generate a random URL-safe string of the given length.
 fn generate_random_string(len: usize) -> String {
     use rand::Rng;
@@ -291,6 +346,9 @@ mod tests {
             api_identifier: "api://glyph".to_string(),
             callback_url: "http://localhost:3000/api/auth/callback".to_string(),
             logout_redirect_url: "http://localhost:3000".to_string(),
+            roles_claim: crate::config::DEFAULT_ROLES_CLAIM.to_string(),
+            additional_domains: vec![],
+            single_tenant: false,
         }
     }
 
@@ -318,6 +376,78 @@ mod tests {
         assert!(!challenge.contains('/'));
     }
 
+    fn id_token_with(claims: &serde_json::Value) -> String {
+        let header = base64_url_encode(br#"{"alg":"RS256","typ":"JWT"}"#);
+        let payload = base64_url_encode(claims.to_string().as_bytes());
+        format!("{header}.{payload}.signature")
+    }
+
+    #[test]
+    fn matching_state_accepted() {
+        let data = Auth0Client {
+            http_client: reqwest::Client::new(),
+            authorization_endpoint: "https://test.auth0.com/authorize".to_string(),
+            token_endpoint: "https://test.auth0.com/oauth/token".to_string(),
+            config: test_config(),
+        }
+        .authorize_url();
+        assert!(data
+            .url
+            .contains(&format!("state={}", urlencoding::encode(&data.csrf_token))));
+        assert!(verify_state(&data.csrf_token, &data.csrf_token).is_ok());
+    }
+
+    #[test]
+    fn mismatched_state_rejected() {
+        let stored = generate_random_string(32);
+        let returned = generate_random_string(32);
+        assert!(matches!(
+            verify_state(&stored, &returned),
+            Err(AuthError::InvalidState)
+        ));
+        assert!(matches!(
+            verify_state(&stored, &stored[..31]),
+            Err(AuthError::InvalidState)
+        ));
+        assert!(matches!(
+            verify_state(&stored, ""),
+            Err(AuthError::InvalidState)
+        ));
+    }
+
+    #[test]
+    fn matching_nonce_accepted() {
+        let token = id_token_with(&serde_json::json!({ "sub": "auth0|1", "nonce": "n-123" }));
+        assert!(verify_id_token_nonce(&token, "n-123").is_ok());
+    }
+
+    #[test]
+    fn mismatched_or_missing_nonce_rejected() {
+        let token = id_token_with(&serde_json::json!({ "sub": "auth0|1", "nonce": "n-123" }));
+        assert!(matches!(
+            verify_id_token_nonce(&token, "n-456"),
+            Err(AuthError::InvalidNonce)
+        ));
+
+        let token = id_token_with(&serde_json::json!({ "sub": "auth0|1" }));
+        assert!(matches!(
+            verify_id_token_nonce(&token, "n-123"),
+            Err(AuthError::InvalidNonce)
+        ));
+    }
+
+    #[test]
+    fn malformed_id_token_rejected() {
+        assert!(matches!(
+            verify_id_token_nonce("not-a-jwt", "n-123"),
+            Err(AuthError::InvalidToken { .. })
+        ));
+        assert!(matches!(
+            verify_id_token_nonce("a.!!!.c", "n-123"),
+            Err(AuthError::InvalidToken { .. })
+        ));
+    }
+
     #[test]
     fn random_string_generation() {
         let s1 = generate_random_string(32);
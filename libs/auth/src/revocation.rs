@@ -0,0 +1,139 @@
+//! Access token revocation.
+//!
+//! Logging out clears cookies, but a copied access token would stay valid
+//! until it expires. Logout records the token's `jti` here, and
+//! [`validate_jwt`](crate::validate_jwt) rejects any token whose `jti` has
+//! been revoked. Entries expire with the token, so the store never grows past
+//! the set of still-live revoked tokens.
+
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+use async_trait::async_trait;
+use deadpool_redis::{redis::AsyncCommands, Pool};
+use tokio::sync::RwLock;
+
+use crate::error::{AuthError, AuthResult};
+
+/// Redis key prefix for revoked token IDs
+pub const REVOKED_KEY_PREFIX: &str = "glyph:auth:revoked:";
+
+/// Store of revoked access token IDs.
+#[async_trait]
+pub trait RevocationStore: Send + Sync {
+    /// Revoke `jti` for `ttl`, normally the token's remaining lifetime.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Internal` if the store is unavailable.
+    async fn revoke(&self, jti: &str, ttl: Duration) -> AuthResult<()>;
+
+    /// Whether `jti` has been revoked.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Internal` if the store is unavailable.
+    async fn is_revoked(&self, jti: &str) -> AuthResult<bool>;
+}
+
+/// Redis-backed revocation store, shared by every API instance.
+///
+/// Each revoked `jti` is its own key so Redis can expire it independently.
+pub struct RedisRevocationStore {
+    pool: Pool,
+}
+
+impl RedisRevocationStore {
+    /// Create a revocation store on the given Redis pool.
+    #[must_use]
+    pub const fn new(pool: Pool) -> Self {
+        Self { pool }
+    }
+
+    fn key(jti: &str) -> String {
+        format!("{REVOKED_KEY_PREFIX}{jti}")
+    }
+}
+
+#[async_trait]
+impl RevocationStore for RedisRevocationStore {
+    async fn revoke(&self, jti: &str, ttl: Duration) -> AuthResult<()> {
+        let mut conn = self
+            .pool
+            .get()
+            .await
+            .map_err(|e| AuthError::internal(format!("redis pool: {e}")))?;
+        // Redis rejects a zero expiry; a token that close to expiring is
+        // about to be rejected anyway.
+        let seconds = ttl.as_secs().max(1);
+        conn.set_ex::<_, _, ()>(Self::key(jti), 1, seconds)
+            .await
+            .map_err(|e| AuthError::internal(format!("redis revoke: {e}")))
+    }
+
+    async fn is_revoked(&self, jti: &str) -> AuthResult<bool> {
+        let mut conn = self
+            .pool
+            .get()
+            .await
+            .map_err(|e| AuthError::internal(format!("redis pool: {e}")))?;
+        conn.exists(Self::key(jti))
+            .await
+            .map_err(|e| AuthError::internal(format!("redis lookup: {e}")))
+    }
+}
+
+/// In-process revocation store.
+///
+/// Only suitable for a single API instance, e.g. local development.
+#[derive(Default)]
+pub struct InMemoryRevocationStore {
+    revoked: RwLock<HashMap<String, Instant>>,
+}
+
+impl InMemoryRevocationStore {
+    /// Create an empty store.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+#[async_trait]
+impl RevocationStore for InMemoryRevocationStore {
+    async fn revoke(&self, jti: &str, ttl: Duration) -> AuthResult<()> {
+        let now = Instant::now();
+        let mut revoked = self.revoked.write().await;
+        revoked.retain(|_, expires_at| *expires_at > now);
+        revoked.insert(jti.to_string(), now + ttl);
+        Ok(())
+    }
+
+    async fn is_revoked(&self, jti: &str) -> AuthResult<bool> {
+        let revoked = self.revoked.read().await;
+        Ok(revoked
+            .get(jti)
+            .is_some_and(|expires_at| *expires_at > Instant::now()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn in_memory_revocation_expires() {
+        let store = InMemoryRevocationStore::new();
+        store.revoke("a", Duration::from_secs(60)).await.unwrap();
+        store.revoke("b", Duration::ZERO).await.unwrap();
+
+        assert!(store.is_revoked("a").await.unwrap());
+        assert!(!store.is_revoked("b").await.unwrap());
+        assert!(!store.is_revoked("c").await.unwrap());
+    }
+
+    #[test]
+    fn redis_keys_are_namespaced() {
+        assert_eq!(RedisRevocationStore::key("abc"), "glyph:auth:revoked:abc");
+    }
+}
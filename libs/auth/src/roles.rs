@@ -0,0 +1,208 @@
+//! Mapping from Auth0 role claims to domain roles.
+//!
+//! Auth0 puts roles in a namespaced custom claim whose name is configured by
+//! `AUTH0_ROLES_CLAIM`. Role strings are matched case-insensitively against
+//! the roles Glyph knows about; anything else is ignored so that roles used by
+//! other applications on the same tenant don't lock users out.
+
+use glyph_domain::{GlobalRole, TeamRole};
+use serde_json::Value;
+
+use crate::jwt::Claims;
+
+/// A role Glyph recognises in an access token.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Role {
+    /// Platform administrator ([`GlobalRole::Admin`])
+    Admin,
+    /// Regular platform user ([`GlobalRole::User`])
+    User,
+    /// Leads one or more teams ([`TeamRole::Leader`])
+    TeamLeader,
+    /// Belongs to one or more teams ([`TeamRole::Member`])
+    TeamMember,
+    /// Works annotation tasks
+    Annotator,
+    /// Reviews submitted annotations
+    Reviewer,
+    /// May certify other users' skills
+    SkillCertifier,
+}
+
+impl Role {
+    /// Canonical name, as checked by `has_role`.
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Admin => "admin",
+            Self::User => "user",
+            Self::TeamLeader => "team_leader",
+            Self::TeamMember => "team_member",
+            Self::Annotator => "annotator",
+            Self::Reviewer => "reviewer",
+            Self::SkillCertifier => "skill:certifier",
+        }
+    }
+
+    /// Parse a role string from a token, ignoring case and `-`/`_` spelling.
+    ///
+    /// Returns `None` for roles Glyph doesn't know.
+    #[must_use]
+    pub fn from_claim(raw: &str) -> Option<Self> {
+        let normalized = raw.trim().to_ascii_lowercase().replace('-', "_");
+        match normalized.as_str() {
+            "admin" => Some(Self::Admin),
+            "user" => Some(Self::User),
+            "team_leader" | "team_lead" => Some(Self::TeamLeader),
+            "team_member" => Some(Self::TeamMember),
+            "annotator" => Some(Self::Annotator),
+            "reviewer" => Some(Self::Reviewer),
+            "skill:certifier" => Some(Self::SkillCertifier),
+            _ => None,
+        }
+    }
+
+    /// The global role this grants, if any.
+    #[must_use]
+    pub const fn global_role(self) -> Option<GlobalRole> {
+        match self {
+            Self::Admin => Some(GlobalRole::Admin),
+            Self::User => Some(GlobalRole::User),
+            _ => None,
+        }
+    }
+
+    /// The team role this grants, if any.
+    #[must_use]
+    pub const fn team_role(self) -> Option<TeamRole> {
+        match self {
+            Self::TeamLeader => Some(TeamRole::Leader),
+            Self::TeamMember => Some(TeamRole::Member),
+            _ => None,
+        }
+    }
+}
+
+/// Map a token's roles claim to the user's effective roles.
+///
+/// Reads `claim_name` (a string or array of strings), falling back to the
+/// standard `roles` claim. Order is preserved, duplicates are dropped, and
+/// unknown role strings are skipped.
+#[must_use]
+pub fn effective_roles(claims: &Claims, claim_name: &str) -> Vec<Role> {
+    let raw: Vec<&str> = match claims.extra.get(claim_name) {
+        Some(Value::Array(values)) => values.iter().filter_map(Value::as_str).collect(),
+        Some(Value::String(value)) => vec![value.as_str()],
+        _ => claims.roles.iter().flatten().map(String::as_str).collect(),
+    };
+
+    let mut roles = Vec::with_capacity(raw.len());
+    for value in raw {
+        match Role::from_claim(value) {
+            Some(role) if !roles.contains(&role) => roles.push(role),
+            Some(_) => {}
+            None => tracing::debug!(role = value, "ignoring unknown role claim"),
+        }
+    }
+    roles
+}
+
+/// The user's global role: admin if any role grants it, otherwise user.
+#[must_use]
+pub fn global_role(roles: &[Role]) -> GlobalRole {
+    if roles
+        .iter()
+        .any(|r| r.global_role() == Some(GlobalRole::Admin))
+    {
+        GlobalRole::Admin
+    } else {
+        GlobalRole::User
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::*;
+    use crate::config::DEFAULT_ROLES_CLAIM;
+    use crate::jwt::Audience;
+
+    fn claims_from(value: serde_json::Value) -> Claims {
+        let mut claims = serde_json::json!({
+            "sub": "auth0|123",
+            "iss": "https://test.auth0.com/",
+            "aud": "api://glyph",
+            "exp": 9_999_999_999_i64,
+            "iat": 0,
+        });
+        claims
+            .as_object_mut()
+            .unwrap()
+            .extend(value.as_object().unwrap().clone());
+        serde_json::from_value(claims).unwrap()
+    }
+
+    #[test]
+    fn namespaced_claim_maps_to_domain_roles() {
+        let claims = claims_from(serde_json::json!({
+            "https://glyph.app/roles": ["Admin", "team-lead", "billing-viewer", "annotator"],
+        }));
+
+        let roles = effective_roles(&claims, DEFAULT_ROLES_CLAIM);
+        assert_eq!(roles, vec![Role::Admin, Role::TeamLeader, Role::Annotator]);
+        assert_eq!(global_role(&roles), GlobalRole::Admin);
+        assert_eq!(Role::TeamLeader.team_role(), Some(TeamRole::Leader));
+    }
+
+    #[test]
+    fn custom_claim_name_is_read() {
+        let claims = claims_from(serde_json::json!({
+            "https://example.org/claims/roles": "reviewer",
+            "https://glyph.app/roles": ["admin"],
+        }));
+
+        let roles = effective_roles(&claims, "https://example.org/claims/roles");
+        assert_eq!(roles, vec![Role::Reviewer]);
+        assert_eq!(global_role(&roles), GlobalRole::User);
+    }
+
+    #[test]
+    fn falls_back_to_plain_roles_claim() {
+        let claims = Claims {
+            sub: "auth0|123".to_string(),
+            iss: "https://test.auth0.com/".to_string(),
+            aud: Audience::Single("api://glyph".to_string()),
+            exp: 9_999_999_999,
+            iat: 0,
+            jti: None,
+            email: None,
+            email_verified: None,
+            name: None,
+            picture: None,
+            roles: Some(vec!["annotator".to_string(), "annotator".to_string()]),
+            extra: HashMap::new(),
+        };
+
+        assert_eq!(
+            effective_roles(&claims, DEFAULT_ROLES_CLAIM),
+            vec![Role::Annotator]
+        );
+    }
+
+    #[test]
+    fn role_names_roundtrip() {
+        for role in [
+            Role::Admin,
+            Role::User,
+            Role::TeamLeader,
+            Role::TeamMember,
+            Role::Annotator,
+            Role::Reviewer,
+            Role::SkillCertifier,
+        ] {
+            assert_eq!(Role::from_claim(role.as_str()), Some(role));
+        }
+        assert_eq!(Role::from_claim("superuser"), None);
+    }
+}
@@ -0,0 +1,228 @@
+//! Circuit breaker for calls to external services
+//!
+//! When S3 or Auth0 is down, every request waiting on a timeout piles up
+//! latency. A [`CircuitBreaker`] counts consecutive failures and, once
+//! `failure_threshold` is reached, opens: calls fail fast with
+//! [`CircuitError::Open`] until `cooldown` has passed. The next call is then
+//! let through as a probe (half-open); success closes the circuit, failure
+//! opens it for another cooldown.
+
+use std::future::Future;
+use std::sync::{Mutex, PoisonError};
+use std::time::{Duration, Instant};
+
+/// Where a breaker is in its cycle
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CircuitState {
+    /// Calls pass through
+    Closed,
+    /// Calls are short-circuited until the cooldown ends
+    Open,
+    /// One probe call is in flight; others are short-circuited
+    HalfOpen,
+}
+
+/// Error from a call made through a [`CircuitBreaker`]
+#[derive(Debug, thiserror::Error)]
+pub enum CircuitError<E> {
+    /// The circuit is open and the call was not attempted
+    #[error("circuit open; retry in {retry_in:?}")]
+    Open { retry_in: Duration },
+
+    /// The call was attempted and failed
+    #[error(transparent)]
+    Inner(E),
+}
+
+#[derive(Debug)]
+struct Breaker {
+    state: CircuitState,
+    consecutive_failures: u32,
+    /// When the circuit last opened, or when the half-open probe started
+    since: Instant,
+}
+
+/// Opens after repeated failures and short-circuits calls until a cooldown
+#[derive(Debug)]
+pub struct CircuitBreaker {
+    failure_threshold: u32,
+    cooldown: Duration,
+    breaker: Mutex<Breaker>,
+}
+
+impl CircuitBreaker {
+    /// Open after `failure_threshold` consecutive failures (at least 1) and
+    /// stay open for `cooldown`
+    #[must_use]
+    pub fn new(failure_threshold: u32, cooldown: Duration) -> Self {
+        Self {
+            failure_threshold: failure_threshold.max(1),
+            cooldown,
+            breaker: Mutex::new(Breaker {
+                state: CircuitState::Closed,
+                consecutive_failures: 0,
+                since: Instant::now(),
+            }),
+        }
+    }
+
+    /// Current state
+    pub fn state(&self) -> CircuitState {
+        self.lock().state
+    }
+
+    /// Run `f` unless the circuit is open, counting every error as a failure
+    pub async fn call<T, E, F, Fut>(&self, f: F) -> Result<T, CircuitError<E>>
+    where
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = Result<T, E>>,
+    {
+        self.call_if(|_| true, f).await
+    }
+
+    /// Run `f` unless the circuit is open. Errors `is_failure` rejects (e.g.
+    /// "not found") show the service is reachable and count as successes.
+    pub async fn call_if<T, E, F, Fut, P>(&self, is_failure: P, f: F) -> Result<T, CircuitError<E>>
+    where
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = Result<T, E>>,
+        P: Fn(&E) -> bool,
+    {
+        self.acquire(Instant::now())
+            .map_err(|retry_in| CircuitError::Open { retry_in })?;
+        match f().await {
+            Ok(value) => {
+                self.on_success();
+                Ok(value)
+            }
+            Err(e) => {
+                if is_failure(&e) {
+                    self.on_failure(Instant::now());
+                } else {
+                    self.on_success();
+                }
+                Err(CircuitError::Inner(e))
+            }
+        }
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, Breaker> {
+        self.breaker.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Whether a call may go ahead at `now`, or how long until it may
+    fn acquire(&self, now: Instant) -> Result<(), Duration> {
+        let mut breaker = self.lock();
+        let ready_at = breaker.since + self.cooldown;
+        match breaker.state {
+            CircuitState::Closed => Ok(()),
+            // A probe that never reported back (e.g. its future was dropped)
+            // stops blocking others after a cooldown
+            CircuitState::Open | CircuitState::HalfOpen if now >= ready_at => {
+                breaker.state = CircuitState::HalfOpen;
+                breaker.since = now;
+                Ok(())
+            }
+            CircuitState::Open | CircuitState::HalfOpen => Err(ready_at - now),
+        }
+    }
+
+    fn on_success(&self) {
+        let mut breaker = self.lock();
+        breaker.state = CircuitState::Closed;
+        breaker.consecutive_failures = 0;
+    }
+
+    fn on_failure(&self, now: Instant) {
+        let mut breaker = self.lock();
+        breaker.consecutive_failures = breaker.consecutive_failures.saturating_add(1);
+        let should_open = breaker.state == CircuitState::HalfOpen
+            || breaker.consecutive_failures >= self.failure_threshold;
+        if should_open {
+            if breaker.state != CircuitState::Open {
+                tracing::warn!(
+                    failures = breaker.consecutive_failures,
+                    cooldown_ms = self.cooldown.as_millis(),
+                    "Circuit opened"
+                );
+            }
+            breaker.state = CircuitState::Open;
+            breaker.since = now;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicBool, Ordering};
+
+    #[test]
+    fn test_opens_after_threshold_then_recovers_through_half_open() {
+        let breaker = CircuitBreaker::new(3, Duration::from_secs(30));
+        let start = Instant::now();
+
+        // Two failures stay under the threshold
+        for _ in 0..2 {
+            assert_eq!(breaker.acquire(start), Ok(()));
+            breaker.on_failure(start);
+        }
+        assert_eq!(breaker.state(), CircuitState::Closed);
+
+        // The third opens the circuit and calls are short-circuited
+        breaker.on_failure(start);
+        assert_eq!(breaker.state(), CircuitState::Open);
+        assert_eq!(
+            breaker.acquire(start + Duration::from_secs(10)),
+            Err(Duration::from_secs(20))
+        );
+
+        // After the cooldown one probe goes through; others wait on it
+        let probe = start + Duration::from_secs(30);
+        assert_eq!(breaker.acquire(probe), Ok(()));
+        assert_eq!(breaker.state(), CircuitState::HalfOpen);
+        assert!(breaker.acquire(probe + Duration::from_secs(1)).is_err());
+
+        // A failed probe reopens for another full cooldown
+        breaker.on_failure(probe);
+        assert_eq!(breaker.state(), CircuitState::Open);
+        assert!(breaker.acquire(probe + Duration::from_secs(29)).is_err());
+
+        // A successful probe closes it again
+        let probe = probe + Duration::from_secs(30);
+        assert_eq!(breaker.acquire(probe), Ok(()));
+        breaker.on_success();
+        assert_eq!(breaker.state(), CircuitState::Closed);
+        assert_eq!(breaker.acquire(probe), Ok(()));
+    }
+
+    #[tokio::test]
+    async fn test_call_short_circuits_while_open() {
+        let breaker = CircuitBreaker::new(1, Duration::from_secs(60));
+
+        let result: Result<(), _> = breaker.call(|| async { Err("down") }).await;
+        assert!(matches!(result, Err(CircuitError::Inner("down"))));
+
+        let attempted = AtomicBool::new(false);
+        let result: Result<(), CircuitError<&str>> = breaker
+            .call(|| async {
+                attempted.store(true, Ordering::SeqCst);
+                Ok(())
+            })
+            .await;
+        assert!(matches!(result, Err(CircuitError::Open { .. })));
+        assert!(!attempted.load(Ordering::SeqCst));
+    }
+
+    #[tokio::test]
+    async fn test_errors_not_classified_as_failures_keep_circuit_closed() {
+        let breaker = CircuitBreaker::new(1, Duration::from_secs(60));
+        for _ in 0..3 {
+            let result: Result<(), _> = breaker
+                .call_if(|e: &&str| *e != "not found", || async { Err("not found") })
+                .await;
+            assert!(matches!(result, Err(CircuitError::Inner("not found"))));
+        }
+        assert_eq!(breaker.state(), CircuitState::Closed);
+    }
+}
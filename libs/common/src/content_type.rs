@@ -0,0 +1,87 @@
+//! Content-type detection for stored and uploaded files
+//!
+//! A file's extension is trusted first, since text formats like JSON and CSV
+//! have no magic bytes. Files with an unknown extension are sniffed from
+//! their leading bytes; anything still unrecognised is
+//! [`OCTET_STREAM`].
+
+/// Content type for files that can't be identified
+pub const OCTET_STREAM: &str = "application/octet-stream";
+
+/// Leading bytes that are enough to sniff any type [`sniff`] recognises
+pub const SNIFF_BYTES: u64 = 8 * 1024;
+
+/// Content type implied by a path's extension
+pub fn from_extension(path: &str) -> Option<&'static str> {
+    let name = path.rsplit('/').next().unwrap_or(path);
+    let (_, ext) = name.rsplit_once('.')?;
+    let content_type = match ext.to_ascii_lowercase().as_str() {
+        "json" => "application/json",
+        "jsonl" | "ndjson" => "application/x-ndjson",
+        "csv" => "text/csv",
+        "tsv" => "text/tab-separated-values",
+        "txt" => "text/plain",
+        "xml" => "application/xml",
+        "html" | "htm" => "text/html",
+        "parquet" => "application/vnd.apache.parquet",
+        "png" => "image/png",
+        "jpg" | "jpeg" => "image/jpeg",
+        "gif" => "image/gif",
+        "webp" => "image/webp",
+        "svg" => "image/svg+xml",
+        "tif" | "tiff" => "image/tiff",
+        "pdf" => "application/pdf",
+        "mp3" => "audio/mpeg",
+        "wav" => "audio/wav",
+        "mp4" => "video/mp4",
+        "zip" => "application/zip",
+        "gz" => "application/gzip",
+        _ => return None,
+    };
+    Some(content_type)
+}
+
+/// Content type recognised from a file's leading bytes
+pub fn sniff(head: &[u8]) -> Option<&'static str> {
+    infer::get(head).map(|kind| kind.mime_type())
+}
+
+/// Content type of a file from its path, falling back to its leading bytes
+pub fn detect_content_type(path: &str, head: &[u8]) -> &'static str {
+    from_extension(path)
+        .or_else(|| sniff(head))
+        .unwrap_or(OCTET_STREAM)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PNG_HEADER: &[u8] = &[
+        0x89, b'P', b'N', b'G', 0x0D, 0x0A, 0x1A, 0x0A, 0, 0, 0, 0x0D,
+    ];
+
+    #[test]
+    fn test_png_is_detected_by_magic_bytes() {
+        assert_eq!(detect_content_type("uploads/scan", PNG_HEADER), "image/png");
+        assert_eq!(
+            detect_content_type("uploads/scan.bin", PNG_HEADER),
+            "image/png"
+        );
+    }
+
+    #[test]
+    fn test_json_is_detected_by_extension() {
+        assert_eq!(
+            detect_content_type("exports/records.JSON", b"{\"a\": 1}"),
+            "application/json"
+        );
+        assert_eq!(detect_content_type("data.csv", b""), "text/csv");
+    }
+
+    #[test]
+    fn test_unknown_files_are_octet_stream() {
+        assert_eq!(detect_content_type("blob", b"\x00\x01\x02"), OCTET_STREAM);
+        assert_eq!(detect_content_type("archive.v2", b""), OCTET_STREAM);
+    }
+}
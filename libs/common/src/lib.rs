@@ -2,6 +2,22 @@
 //!
 //! Provides shared configuration, error handling, and telemetry.
 
+pub mod circuit_breaker;
+pub mod content_type;
+pub mod outbound;
+pub mod redact;
+pub mod retry;
+pub mod storage;
 pub mod telemetry;
 
+pub use circuit_breaker::{CircuitBreaker, CircuitError, CircuitState};
+pub use content_type::detect_content_type;
+pub use outbound::{
+    check_public_url, is_public_ip, public_only_client, resolve_public_url, OutboundUrlError,
+};
+pub use retry::{with_backoff, with_backoff_if, RetryPolicy};
+pub use storage::{
+    CircuitBreakingStorage, LocalStorage, ObjectPage, ObjectWriter, S3Credentials, S3Storage,
+    StorageError, StorageService, StoredObject,
+};
 pub use telemetry::init_tracing;
@@ -0,0 +1,212 @@
+//! Guards for outbound requests to user-supplied URLs
+//!
+//! Webhook targets are chosen by project admins, so the server must not be
+//! talked into calling itself or anything else on its private network. URLs
+//! are checked when they are saved, and [`public_only_client`] re-checks
+//! every resolved address at connection time, so a name that is later
+//! re-pointed at an internal address still can't be reached.
+
+use std::net::{IpAddr, Ipv4Addr, SocketAddr};
+
+use reqwest::dns::{Addrs, Name, Resolve, Resolving};
+use thiserror::Error;
+use url::{Host, Url};
+
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum OutboundUrlError {
+    #[error("not a valid URL: {0}")]
+    Invalid(String),
+
+    #[error("URL must use https")]
+    InsecureScheme,
+
+    #[error("{0} is not a public address")]
+    PrivateAddress(String),
+
+    #[error("{0} could not be resolved")]
+    Unresolvable(String),
+}
+
+/// Whether an address is reachable on the public internet.
+///
+/// Loopback, private, link-local (including cloud metadata endpoints),
+/// carrier-grade NAT, multicast, documentation and reserved ranges are not.
+pub fn is_public_ip(ip: IpAddr) -> bool {
+    match ip {
+        IpAddr::V4(ip) => is_public_ipv4(ip),
+        IpAddr::V6(ip) => match ip.to_ipv4_mapped() {
+            Some(mapped) => is_public_ipv4(mapped),
+            None => {
+                !(ip.is_loopback()
+                    || ip.is_unspecified()
+                    || ip.is_multicast()
+                    || ip.is_unique_local()
+                    || ip.is_unicast_link_local())
+            }
+        },
+    }
+}
+
+fn is_public_ipv4(ip: Ipv4Addr) -> bool {
+    let [a, b, ..] = ip.octets();
+    !(ip.is_loopback()
+        || ip.is_private()
+        || ip.is_link_local()
+        || ip.is_unspecified()
+        || ip.is_broadcast()
+        || ip.is_multicast()
+        || ip.is_documentation()
+        // "This network", carrier-grade NAT, benchmarking and reserved ranges
+        || a == 0
+        || (a == 100 && (64..128).contains(&b))
+        || (a == 198 && (18..20).contains(&b))
+        || a >= 240)
+}
+
+/// Parse an https URL whose host isn't a literal non-public address.
+///
+/// Host names are not resolved; see [`resolve_public_url`].
+pub fn check_public_url(url: &str) -> Result<Url, OutboundUrlError> {
+    let parsed = Url::parse(url).map_err(|e| OutboundUrlError::Invalid(e.to_string()))?;
+    if parsed.scheme() != "https" {
+        return Err(OutboundUrlError::InsecureScheme);
+    }
+    match parsed.host() {
+        None => return Err(OutboundUrlError::Invalid("URL has no host".to_string())),
+        Some(Host::Ipv4(ip)) if !is_public_ip(ip.into()) => {
+            return Err(OutboundUrlError::PrivateAddress(ip.to_string()));
+        }
+        Some(Host::Ipv6(ip)) if !is_public_ip(ip.into()) => {
+            return Err(OutboundUrlError::PrivateAddress(ip.to_string()));
+        }
+        Some(Host::Domain(domain)) if domain.eq_ignore_ascii_case("localhost") => {
+            return Err(OutboundUrlError::PrivateAddress(domain.to_string()));
+        }
+        Some(_) => {}
+    }
+    Ok(parsed)
+}
+
+/// [`check_public_url`], then resolve the host and require every address
+/// it resolves to be public
+pub async fn resolve_public_url(url: &str) -> Result<Url, OutboundUrlError> {
+    let parsed = check_public_url(url)?;
+    let Some(Host::Domain(domain)) = parsed.host() else {
+        return Ok(parsed);
+    };
+
+    let port = parsed.port_or_known_default().unwrap_or(443);
+    let addrs: Vec<SocketAddr> = tokio::net::lookup_host((domain, port))
+        .await
+        .map_err(|_| OutboundUrlError::Unresolvable(domain.to_string()))?
+        .collect();
+    if addrs.is_empty() {
+        return Err(OutboundUrlError::Unresolvable(domain.to_string()));
+    }
+    if let Some(private) = addrs.iter().find(|addr| !is_public_ip(addr.ip())) {
+        return Err(OutboundUrlError::PrivateAddress(format!(
+            "{domain} ({})",
+            private.ip()
+        )));
+    }
+    Ok(parsed)
+}
+
+/// Resolves host names to their public addresses only
+#[derive(Debug, Clone, Copy, Default)]
+pub struct PublicOnlyResolver;
+
+impl Resolve for PublicOnlyResolver {
+    fn resolve(&self, name: Name) -> Resolving {
+        Box::pin(async move {
+            let host = name.as_str();
+            let public: Vec<SocketAddr> = tokio::net::lookup_host((host, 0))
+                .await?
+                .filter(|addr| is_public_ip(addr.ip()))
+                .collect();
+            if public.is_empty() {
+                return Err(OutboundUrlError::PrivateAddress(host.to_string()).into());
+            }
+            let addrs: Addrs = Box::new(public.into_iter());
+            Ok(addrs)
+        })
+    }
+}
+
+/// An HTTP client that only connects to public addresses and never follows
+/// redirects. Callers still need [`check_public_url`] for IP-literal hosts,
+/// which bypass the resolver.
+pub fn public_only_client() -> reqwest::Client {
+    reqwest::Client::builder()
+        .dns_resolver(std::sync::Arc::new(PublicOnlyResolver))
+        .redirect(reqwest::redirect::Policy::none())
+        .build()
+        .expect("client with a custom resolver always builds")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_internal_addresses_are_not_public() {
+        for ip in [
+            "127.0.0.1",
+            "10.1.2.3",
+            "172.16.0.1",
+            "192.168.1.1",
+            "169.254.169.254",
+            "100.64.0.1",
+            "0.0.0.0",
+            "::1",
+            "fd00::1",
+            "fe80::1",
+            "::ffff:10.0.0.1",
+        ] {
+            assert!(
+                !is_public_ip(ip.parse().unwrap()),
+                "{ip} should be internal"
+            );
+        }
+        for ip in ["8.8.8.8", "1.1.1.1", "2606:4700::1111"] {
+            assert!(is_public_ip(ip.parse().unwrap()), "{ip} should be public");
+        }
+    }
+
+    #[test]
+    fn test_check_public_url() {
+        assert!(check_public_url("https://hooks.example.com/glyph").is_ok());
+        assert_eq!(
+            check_public_url("http://hooks.example.com/glyph"),
+            Err(OutboundUrlError::InsecureScheme)
+        );
+        assert!(matches!(
+            check_public_url("https://169.254.169.254/latest/meta-data"),
+            Err(OutboundUrlError::PrivateAddress(_))
+        ));
+        assert!(matches!(
+            check_public_url("https://[::1]:8443/"),
+            Err(OutboundUrlError::PrivateAddress(_))
+        ));
+        assert!(matches!(
+            check_public_url("https://LOCALHOST/hook"),
+            Err(OutboundUrlError::PrivateAddress(_))
+        ));
+        assert!(matches!(
+            check_public_url("not a url"),
+            Err(OutboundUrlError::Invalid(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_resolve_rejects_names_pointing_inward() {
+        assert!(matches!(
+            resolve_public_url("https://localhost.localdomain./hook").await,
+            Err(OutboundUrlError::PrivateAddress(_) | OutboundUrlError::Unresolvable(_))
+        ));
+        assert!(matches!(
+            resolve_public_url("https://127.0.0.1/hook").await,
+            Err(OutboundUrlError::PrivateAddress(_))
+        ));
+    }
+}
@@ -0,0 +1,443 @@
+//! PII redaction for log output
+//!
+//! Sensitive values are masked before they reach the subscriber, either
+//! explicitly at the call site via [`Email`] / [`Secret`] wrappers, or by
+//! field name through [`RedactingFields`] when formatting text or JSON logs.
+
+use std::fmt::{self, Write as _};
+
+use serde_json::{Map, Value};
+use tracing::field::{Field, Visit};
+use tracing::span::Record;
+use tracing::{Event, Subscriber};
+use tracing_subscriber::field::RecordFields;
+use tracing_subscriber::fmt::{
+    format::Writer, FmtContext, FormatEvent, FormatFields, FormattedFields,
+};
+use tracing_subscriber::registry::LookupSpan;
+
+/// Replacement text for masked secrets
+const MASK: &str = "***";
+
+/// Mask an email address, keeping the first character and the domain.
+///
+/// `alice@example.com` becomes `a***@example.com`. Values that aren't
+/// email-shaped are fully masked.
+#[must_use]
+pub fn mask_email(email: &str) -> String {
+    match email.split_once('@') {
+        Some((local, domain)) if !domain.is_empty() => {
+            let first = local.chars().next().map(String::from).unwrap_or_default();
+            format!("{first}{MASK}@{domain}")
+        }
+        _ => MASK.to_string(),
+    }
+}
+
+/// Mask a token or other secret entirely
+#[must_use]
+pub fn mask_token(_token: &str) -> &'static str {
+    MASK
+}
+
+/// Display wrapper that logs an email in masked form
+pub struct Email<'a>(pub &'a str);
+
+impl fmt::Display for Email<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&mask_email(self.0))
+    }
+}
+
+impl fmt::Debug for Email<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self, f)
+    }
+}
+
+/// Display wrapper that never reveals the wrapped secret
+pub struct Secret<'a>(pub &'a str);
+
+impl fmt::Display for Secret<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(mask_token(self.0))
+    }
+}
+
+impl fmt::Debug for Secret<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self, f)
+    }
+}
+
+/// Field names treated as sensitive when formatting log output
+#[derive(Debug, Clone)]
+pub struct RedactionConfig {
+    /// Fields masked as emails (`u***@domain`)
+    pub email_fields: Vec<&'static str>,
+    /// Fields masked entirely (`***`); names ending in `_token` always match
+    pub secret_fields: Vec<&'static str>,
+}
+
+impl Default for RedactionConfig {
+    fn default() -> Self {
+        Self {
+            email_fields: vec!["email", "user_email"],
+            secret_fields: vec![
+                "token",
+                "password",
+                "secret",
+                "client_secret",
+                "authorization",
+                "cookie",
+                "code",
+                "nonce",
+                "pkce_verifier",
+            ],
+        }
+    }
+}
+
+impl RedactionConfig {
+    /// Redact a rendered field value according to its name
+    fn redact(&self, name: &str, value: &str) -> Option<String> {
+        if self.email_fields.contains(&name) {
+            Some(mask_email(value))
+        } else if self.secret_fields.contains(&name) || name.ends_with("_token") {
+            Some(MASK.to_string())
+        } else {
+            None
+        }
+    }
+}
+
+/// Field formatter that masks sensitive span and event fields.
+///
+/// Output matches the default `key=value` text format, or a JSON object
+/// when built with [`RedactingFields::json`].
+#[derive(Debug, Clone, Default)]
+pub struct RedactingFields {
+    config: RedactionConfig,
+    json: bool,
+}
+
+impl RedactingFields {
+    /// Create a field formatter with a custom redaction config
+    #[must_use]
+    pub fn new(config: RedactionConfig) -> Self {
+        Self {
+            config,
+            json: false,
+        }
+    }
+
+    /// Format fields as a JSON object, for the JSON log format.
+    ///
+    /// The JSON event format serializes event fields itself, so pair this
+    /// with [`RedactedJson`].
+    #[must_use]
+    pub const fn json(mut self) -> Self {
+        self.json = true;
+        self
+    }
+}
+
+impl<'writer> FormatFields<'writer> for RedactingFields {
+    fn format_fields<R: RecordFields>(
+        &self,
+        mut writer: Writer<'writer>,
+        fields: R,
+    ) -> fmt::Result {
+        if self.json {
+            let mut visitor = JsonRedactingVisitor {
+                config: &self.config,
+                values: Map::new(),
+            };
+            fields.record(&mut visitor);
+            return write!(writer, "{}", Value::Object(visitor.values));
+        }
+
+        let mut visitor = RedactingVisitor {
+            writer,
+            config: &self.config,
+            first: true,
+            result: Ok(()),
+        };
+        fields.record(&mut visitor);
+        visitor.result
+    }
+
+    fn add_fields(
+        &self,
+        current: &'writer mut FormattedFields<Self>,
+        fields: &Record<'_>,
+    ) -> fmt::Result {
+        if !self.json || current.is_empty() {
+            if !current.is_empty() {
+                current.fields.push(' ');
+            }
+            return self.format_fields(current.as_writer(), fields);
+        }
+
+        // Merge into the span's existing JSON object
+        let mut visitor = JsonRedactingVisitor {
+            config: &self.config,
+            values: serde_json::from_str(current).map_err(|_| fmt::Error)?,
+        };
+        fields.record(&mut visitor);
+        current.fields = Value::Object(visitor.values).to_string();
+        Ok(())
+    }
+}
+
+struct JsonRedactingVisitor<'a> {
+    config: &'a RedactionConfig,
+    values: Map<String, Value>,
+}
+
+impl JsonRedactingVisitor<'_> {
+    fn insert(&mut self, field: &Field, value: Value) {
+        let name = field.name();
+        let value = if name == "message" {
+            value
+        } else {
+            let rendered = match &value {
+                Value::String(s) => s.clone(),
+                other => other.to_string(),
+            };
+            self.config
+                .redact(name, &rendered)
+                .map_or(value, Value::String)
+        };
+        self.values.insert(name.to_string(), value);
+    }
+}
+
+impl Visit for JsonRedactingVisitor<'_> {
+    fn record_i64(&mut self, field: &Field, value: i64) {
+        self.insert(field, value.into());
+    }
+
+    fn record_u64(&mut self, field: &Field, value: u64) {
+        self.insert(field, value.into());
+    }
+
+    fn record_f64(&mut self, field: &Field, value: f64) {
+        self.insert(field, value.into());
+    }
+
+    fn record_bool(&mut self, field: &Field, value: bool) {
+        self.insert(field, value.into());
+    }
+
+    fn record_str(&mut self, field: &Field, value: &str) {
+        self.insert(field, value.into());
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+        self.insert(field, Value::String(format!("{value:?}")));
+    }
+}
+
+/// JSON event format whose event fields come from the layer's field
+/// formatter rather than being serialized directly.
+///
+/// Use with [`RedactingFields::json`] so event fields are masked the same way
+/// as span fields. Expects the default, non-flattened event layout.
+#[derive(Debug, Clone)]
+pub struct RedactedJson<E>(E);
+
+impl<E> RedactedJson<E> {
+    /// Wrap a JSON event format
+    #[must_use]
+    pub const fn new(inner: E) -> Self {
+        Self(inner)
+    }
+}
+
+impl<S, N, E> FormatEvent<S, N> for RedactedJson<E>
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+    N: for<'a> FormatFields<'a> + 'static,
+    E: FormatEvent<S, N>,
+{
+    fn format_event(
+        &self,
+        ctx: &FmtContext<'_, S, N>,
+        mut writer: Writer<'_>,
+        event: &Event<'_>,
+    ) -> fmt::Result {
+        let mut line = String::new();
+        self.0.format_event(ctx, Writer::new(&mut line), event)?;
+        let mut fields = String::new();
+        ctx.format_fields(Writer::new(&mut fields), event)?;
+
+        // Never fall back to the line with unmasked fields
+        let (Ok(Value::Object(mut line)), Ok(fields)) = (
+            serde_json::from_str::<Value>(&line),
+            serde_json::from_str::<Value>(&fields),
+        ) else {
+            return Err(fmt::Error);
+        };
+        line.insert("fields".to_string(), fields);
+        writeln!(writer, "{}", Value::Object(line))
+    }
+}
+
+struct RedactingVisitor<'a, 'writer> {
+    writer: Writer<'writer>,
+    config: &'a RedactionConfig,
+    first: bool,
+    result: fmt::Result,
+}
+
+impl RedactingVisitor<'_, '_> {
+    fn write_field(&mut self, field: &Field, rendered: &str, quoted: bool) {
+        if self.result.is_err() {
+            return;
+        }
+        let separator = if self.first { "" } else { " " };
+        self.first = false;
+
+        self.result = if field.name() == "message" {
+            write!(self.writer, "{separator}{rendered}")
+        } else if let Some(masked) = self.config.redact(field.name(), rendered) {
+            write!(self.writer, "{separator}{}={masked}", field.name())
+        } else if quoted {
+            write!(self.writer, "{separator}{}={rendered:?}", field.name())
+        } else {
+            write!(self.writer, "{separator}{}={rendered}", field.name())
+        };
+    }
+}
+
+impl Visit for RedactingVisitor<'_, '_> {
+    fn record_str(&mut self, field: &Field, value: &str) {
+        self.write_field(field, value, true);
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+        let rendered = format!("{value:?}");
+        self.write_field(field, &rendered, false);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io;
+    use std::sync::{Arc, Mutex};
+    use tracing_subscriber::fmt::MakeWriter;
+
+    #[derive(Clone, Default)]
+    struct Capture(Arc<Mutex<Vec<u8>>>);
+
+    impl io::Write for Capture {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.0.lock().unwrap().extend_from_slice(buf);
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    impl<'a> MakeWriter<'a> for Capture {
+        type Writer = Self;
+
+        fn make_writer(&'a self) -> Self::Writer {
+            self.clone()
+        }
+    }
+
+    impl Capture {
+        fn output(&self) -> String {
+            String::from_utf8(self.0.lock().unwrap().clone()).unwrap()
+        }
+    }
+
+    #[test]
+    fn masks_emails() {
+        assert_eq!(mask_email("alice@example.com"), "a***@example.com");
+        assert_eq!(mask_email("not-an-email"), "***");
+        assert_eq!(mask_email("@example.com"), "***@example.com");
+    }
+
+    #[test]
+    fn wrappers_mask_on_display() {
+        assert_eq!(
+            format!("{}", Email("bob@glyph.dev")),
+            "b***@glyph.dev".to_string()
+        );
+        assert_eq!(format!("{:?}", Secret("eyJhbGciOi")), "***");
+    }
+
+    #[test]
+    fn sensitive_span_fields_are_masked_in_output() {
+        let capture = Capture::default();
+        let subscriber = tracing_subscriber::fmt()
+            .with_writer(capture.clone())
+            .with_ansi(false)
+            .fmt_fields(RedactingFields::default())
+            .finish();
+
+        tracing::subscriber::with_default(subscriber, || {
+            let span = tracing::info_span!(
+                "login",
+                email = "alice@example.com",
+                access_token = "eyJhbGciOiJSUzI1NiJ9.payload.sig",
+                user_id = "user_123"
+            );
+            let _guard = span.enter();
+            tracing::info!(code = "auth-code-xyz", "callback received");
+        });
+
+        let output = capture.output();
+        assert!(output.contains("email=a***@example.com"), "{output}");
+        assert!(output.contains("access_token=***"), "{output}");
+        assert!(output.contains("code=***"), "{output}");
+        assert!(output.contains("user_id=\"user_123\""), "{output}");
+        assert!(output.contains("callback received"), "{output}");
+        assert!(!output.contains("alice@"), "{output}");
+        assert!(!output.contains("eyJhbGci"), "{output}");
+        assert!(!output.contains("auth-code-xyz"), "{output}");
+    }
+
+    #[test]
+    fn sensitive_fields_are_masked_in_json_output() {
+        let capture = Capture::default();
+        let subscriber = tracing_subscriber::fmt()
+            .json()
+            .with_writer(capture.clone())
+            .fmt_fields(RedactingFields::default().json())
+            .map_event_format(RedactedJson::new)
+            .finish();
+
+        tracing::subscriber::with_default(subscriber, || {
+            let span =
+                tracing::info_span!("login", user_id = "user_123", email = tracing::field::Empty);
+            span.record("email", "alice@example.com");
+            let _guard = span.enter();
+            tracing::info!(
+                code = "auth-code-xyz",
+                attempt = 2,
+                refresh_token = "eyJhbGciOiJSUzI1NiJ9.payload.sig",
+                "callback received"
+            );
+        });
+
+        let output = capture.output();
+        let line: Value = serde_json::from_str(output.trim()).unwrap();
+        assert_eq!(line["fields"]["message"], "callback received");
+        assert_eq!(line["fields"]["code"], MASK);
+        assert_eq!(line["fields"]["refresh_token"], MASK);
+        assert_eq!(line["fields"]["attempt"], 2);
+        assert_eq!(line["span"]["email"], "a***@example.com");
+        assert_eq!(line["span"]["user_id"], "user_123");
+        assert_eq!(line["spans"][0]["email"], "a***@example.com");
+        assert!(!output.contains("alice@"), "{output}");
+        assert!(!output.contains("eyJhbGci"), "{output}");
+        assert!(!output.contains("auth-code-xyz"), "{output}");
+    }
+}
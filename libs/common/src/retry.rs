@@ -0,0 +1,173 @@
+//! Retry with exponential backoff and jitter for outbound calls
+//!
+//! Auth0, JWKS, storage and webhook calls share the same shape: try, and on a
+//! transient failure wait a little longer than last time before trying again.
+//! [`with_backoff_if`] retries only errors the caller classifies as retryable;
+//! [`with_backoff`] retries every error.
+
+use std::future::Future;
+use std::time::Duration;
+
+use rand::Rng;
+
+/// How often and how patiently to retry
+#[derive(Debug, Clone, PartialEq)]
+pub struct RetryPolicy {
+    /// Total attempts including the first; `0` is treated as `1`
+    pub max_attempts: u32,
+    /// Delay before the first retry
+    pub base_delay: Duration,
+    /// Multiplier applied to the delay after each retry
+    pub factor: f64,
+    /// Upper bound on any single delay
+    pub max_delay: Duration,
+    /// Fraction of each delay that is randomized, in `[0, 1]`.
+    ///
+    /// With `0.5` a 200ms delay becomes anything from 100ms to 200ms.
+    pub jitter: f64,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: 3,
+            base_delay: Duration::from_millis(200),
+            factor: 2.0,
+            max_delay: Duration::from_secs(10),
+            jitter: 0.5,
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// Delay before retry number `retry` (0-based), with a fresh jitter draw
+    #[must_use]
+    pub fn backoff(&self, retry: u32) -> Duration {
+        self.delay(retry, rand::thread_rng().gen::<f64>())
+    }
+
+    /// Delay before retry number `retry` (0-based), given a random `unit` in `[0, 1)`
+    fn delay(&self, retry: u32, unit: f64) -> Duration {
+        let exponent = i32::try_from(retry).unwrap_or(i32::MAX);
+        let backoff = self.base_delay.as_secs_f64() * self.factor.max(1.0).powi(exponent);
+        let capped = backoff.min(self.max_delay.as_secs_f64());
+        let jitter = self.jitter.clamp(0.0, 1.0);
+        Duration::from_secs_f64(capped * (1.0 - jitter * unit))
+    }
+}
+
+/// Run `f` until it succeeds or the policy's attempts are exhausted,
+/// retrying every error. Returns the last error on exhaustion.
+pub async fn with_backoff<T, E, F, Fut>(policy: &RetryPolicy, f: F) -> Result<T, E>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<T, E>>,
+{
+    with_backoff_if(policy, |_| true, f).await
+}
+
+/// Run `f` until it succeeds, fails with an error `is_retryable` rejects, or
+/// the policy's attempts are exhausted. Returns the last error otherwise.
+pub async fn with_backoff_if<T, E, F, Fut, P>(
+    policy: &RetryPolicy,
+    is_retryable: P,
+    mut f: F,
+) -> Result<T, E>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<T, E>>,
+    P: Fn(&E) -> bool,
+{
+    let attempts = policy.max_attempts.max(1);
+    let mut retry = 0;
+    loop {
+        match f().await {
+            Ok(value) => return Ok(value),
+            Err(e) if retry + 1 >= attempts || !is_retryable(&e) => return Err(e),
+            Err(_) => {
+                let delay = policy.backoff(retry);
+                tracing::debug!(retry = retry + 1, delay_ms = delay.as_millis(), "Retrying");
+                tokio::time::sleep(delay).await;
+                retry += 1;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    fn fast_policy(max_attempts: u32) -> RetryPolicy {
+        RetryPolicy {
+            max_attempts,
+            base_delay: Duration::from_millis(1),
+            factor: 2.0,
+            max_delay: Duration::from_millis(5),
+            jitter: 0.0,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_retries_until_success() {
+        let calls = AtomicU32::new(0);
+        let result: Result<&str, &str> = with_backoff(&fast_policy(5), || async {
+            if calls.fetch_add(1, Ordering::SeqCst) < 2 {
+                Err("transient")
+            } else {
+                Ok("done")
+            }
+        })
+        .await;
+
+        assert_eq!(result, Ok("done"));
+        assert_eq!(calls.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn test_exhausts_attempts() {
+        let calls = AtomicU32::new(0);
+        let result: Result<(), u32> = with_backoff(&fast_policy(4), || async {
+            Err(calls.fetch_add(1, Ordering::SeqCst))
+        })
+        .await;
+
+        // The last attempt's error is returned
+        assert_eq!(result, Err(3));
+        assert_eq!(calls.load(Ordering::SeqCst), 4);
+    }
+
+    #[tokio::test]
+    async fn test_non_retryable_error_stops_immediately() {
+        let calls = AtomicU32::new(0);
+        let result: Result<(), &str> = with_backoff_if(
+            &fast_policy(5),
+            |e| *e != "permanent",
+            || async {
+                calls.fetch_add(1, Ordering::SeqCst);
+                Err("permanent")
+            },
+        )
+        .await;
+
+        assert_eq!(result, Err("permanent"));
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+    }
+
+    #[test]
+    fn test_delay_grows_caps_and_jitters() {
+        let policy = RetryPolicy {
+            max_attempts: 10,
+            base_delay: Duration::from_millis(100),
+            factor: 2.0,
+            max_delay: Duration::from_millis(300),
+            jitter: 0.5,
+        };
+        assert_eq!(policy.delay(0, 0.0), Duration::from_millis(100));
+        assert_eq!(policy.delay(1, 0.0), Duration::from_millis(200));
+        assert_eq!(policy.delay(2, 0.0), Duration::from_millis(300));
+        // Full jitter draw takes off half the delay
+        assert_eq!(policy.delay(1, 1.0), Duration::from_millis(100));
+    }
+}
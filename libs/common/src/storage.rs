@@ -0,0 +1,779 @@
+//! Object storage for data source files and exports
+//!
+//! [`StorageService`] is the one interface features use to read and write
+//! files, whatever holds them. [`LocalStorage`] keeps objects under a
+//! directory on disk; [`S3Storage`] keeps them in an S3 bucket. Keys are
+//! `/`-separated paths relative to the backend's root.
+//!
+//! Large objects can be written in pieces through an [`ObjectWriter`], which
+//! only makes the object visible once it is finished.
+
+use std::io::{ErrorKind, SeekFrom};
+use std::ops::Range;
+use std::path::{Component, Path, PathBuf};
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use futures::{StreamExt, TryStreamExt};
+use object_store::aws::{AmazonS3, AmazonS3Builder, AmazonS3ConfigKey};
+use object_store::path::Path as ObjectPath;
+use object_store::{GetOptions, GetRange, ObjectStore, PutPayload, WriteMultipart};
+use thiserror::Error;
+use tokio::io::{AsyncReadExt, AsyncSeekExt, AsyncWriteExt};
+
+use crate::circuit_breaker::{CircuitBreaker, CircuitError};
+
+/// Errors from a storage backend
+#[derive(Debug, Error)]
+pub enum StorageError {
+    #[error("object not found: {0}")]
+    NotFound(String),
+
+    #[error("access denied: {0}")]
+    AccessDenied(String),
+
+    #[error("storage error: {0}")]
+    Other(String),
+
+    /// The backend has been failing and calls are short-circuited for now
+    #[error("storage unavailable: {0}")]
+    Unavailable(String),
+}
+
+/// An object in storage
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StoredObject {
+    pub key: String,
+    pub size_bytes: u64,
+    pub modified_at: Option<DateTime<Utc>>,
+}
+
+/// One page of a listing
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ObjectPage {
+    pub objects: Vec<StoredObject>,
+    /// More objects follow the last one; pass its key as `after` to continue
+    pub has_more: bool,
+}
+
+impl ObjectPage {
+    /// Cut a sorted listing of up to `limit + 1` objects down to a page
+    fn from_listing(mut objects: Vec<StoredObject>, limit: usize) -> Self {
+        let has_more = objects.len() > limit;
+        objects.truncate(limit);
+        Self { objects, has_more }
+    }
+}
+
+/// Reads and writes objects by key
+#[async_trait]
+pub trait StorageService: Send + Sync {
+    /// Up to `limit` objects whose keys start with `prefix` and sort after
+    /// `after`, sorted by key
+    async fn list(
+        &self,
+        prefix: &str,
+        after: Option<&str>,
+        limit: usize,
+    ) -> Result<ObjectPage, StorageError>;
+
+    /// An object's contents
+    async fn get(&self, key: &str) -> Result<Vec<u8>, StorageError>;
+
+    /// The bytes of an object in `range`, which must lie within it
+    async fn get_range(&self, key: &str, range: Range<u64>) -> Result<Vec<u8>, StorageError>;
+
+    /// Create or replace an object
+    async fn put(&self, key: &str, data: Vec<u8>) -> Result<(), StorageError>;
+
+    /// Start writing an object in pieces; it replaces any object at `key`
+    /// when the writer is finished
+    async fn writer(&self, key: &str) -> Result<Box<dyn ObjectWriter>, StorageError>;
+
+    /// Remove an object
+    async fn delete(&self, key: &str) -> Result<(), StorageError>;
+}
+
+/// An object being written in pieces. Nothing is stored at its key until
+/// [`ObjectWriter::finish`] succeeds.
+#[async_trait]
+pub trait ObjectWriter: Send {
+    /// Append bytes to the object
+    async fn write(&mut self, chunk: &[u8]) -> Result<(), StorageError>;
+
+    /// Store the object
+    async fn finish(self: Box<Self>) -> Result<(), StorageError>;
+
+    /// Discard everything written so far
+    async fn abort(self: Box<Self>) -> Result<(), StorageError>;
+}
+
+// =============================================================================
+// Local filesystem
+// =============================================================================
+
+/// Stores objects as files under a root directory
+#[derive(Debug, Clone)]
+pub struct LocalStorage {
+    root: PathBuf,
+}
+
+impl LocalStorage {
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self { root: root.into() }
+    }
+
+    /// File path for a key; keys may not escape the root
+    fn path_for(&self, key: &str) -> Result<PathBuf, StorageError> {
+        let relative = Path::new(key);
+        if key.is_empty()
+            || !relative
+                .components()
+                .all(|c| matches!(c, Component::Normal(_)))
+        {
+            return Err(StorageError::AccessDenied(key.to_string()));
+        }
+        Ok(self.root.join(relative))
+    }
+
+    /// Key for a file path under the root
+    fn key_for(&self, path: &Path) -> Option<String> {
+        let relative = path.strip_prefix(&self.root).ok()?;
+        let parts: Option<Vec<&str>> = relative
+            .components()
+            .map(|c| c.as_os_str().to_str())
+            .collect();
+        Some(parts?.join("/"))
+    }
+}
+
+/// Writes to a hidden file beside the target and renames it into place
+struct LocalWriter {
+    key: String,
+    path: PathBuf,
+    partial: PathBuf,
+    file: tokio::fs::File,
+}
+
+#[async_trait]
+impl ObjectWriter for LocalWriter {
+    async fn write(&mut self, chunk: &[u8]) -> Result<(), StorageError> {
+        self.file
+            .write_all(chunk)
+            .await
+            .map_err(|e| io_error(&self.key, &e))
+    }
+
+    async fn finish(mut self: Box<Self>) -> Result<(), StorageError> {
+        let result = async {
+            self.file.flush().await?;
+            self.file.sync_all().await?;
+            tokio::fs::rename(&self.partial, &self.path).await
+        }
+        .await;
+        if let Err(e) = result {
+            let _ = tokio::fs::remove_file(&self.partial).await;
+            return Err(io_error(&self.key, &e));
+        }
+        Ok(())
+    }
+
+    async fn abort(self: Box<Self>) -> Result<(), StorageError> {
+        drop(self.file);
+        tokio::fs::remove_file(&self.partial)
+            .await
+            .map_err(|e| io_error(&self.key, &e))
+    }
+}
+
+fn io_error(key: &str, e: &std::io::Error) -> StorageError {
+    match e.kind() {
+        ErrorKind::NotFound => StorageError::NotFound(key.to_string()),
+        ErrorKind::PermissionDenied => StorageError::AccessDenied(key.to_string()),
+        _ => StorageError::Other(format!("{key}: {e}")),
+    }
+}
+
+#[async_trait]
+impl StorageService for LocalStorage {
+    async fn list(
+        &self,
+        prefix: &str,
+        after: Option<&str>,
+        limit: usize,
+    ) -> Result<ObjectPage, StorageError> {
+        let mut objects = Vec::new();
+        let mut dirs = vec![self.root.clone()];
+
+        while let Some(dir) = dirs.pop() {
+            let mut entries = match tokio::fs::read_dir(&dir).await {
+                Ok(entries) => entries,
+                // An empty store has no root yet
+                Err(e) if e.kind() == ErrorKind::NotFound => continue,
+                Err(e) => return Err(io_error(prefix, &e)),
+            };
+            while let Some(entry) = entries
+                .next_entry()
+                .await
+                .map_err(|e| io_error(prefix, &e))?
+            {
+                let metadata = entry.metadata().await.map_err(|e| io_error(prefix, &e))?;
+                if metadata.is_dir() {
+                    dirs.push(entry.path());
+                    continue;
+                }
+                // Hidden files are writes still in progress
+                if entry.file_name().to_string_lossy().starts_with('.') {
+                    continue;
+                }
+                let Some(key) = self.key_for(&entry.path()) else {
+                    continue;
+                };
+                if key.starts_with(prefix) && after.is_none_or(|after| key.as_str() > after) {
+                    objects.push(StoredObject {
+                        key,
+                        size_bytes: metadata.len(),
+                        modified_at: metadata.modified().ok().map(DateTime::<Utc>::from),
+                    });
+                }
+            }
+        }
+
+        objects.sort_by(|a, b| a.key.cmp(&b.key));
+        objects.truncate(limit.saturating_add(1));
+        Ok(ObjectPage::from_listing(objects, limit))
+    }
+
+    async fn get(&self, key: &str) -> Result<Vec<u8>, StorageError> {
+        tokio::fs::read(self.path_for(key)?)
+            .await
+            .map_err(|e| io_error(key, &e))
+    }
+
+    async fn get_range(&self, key: &str, range: Range<u64>) -> Result<Vec<u8>, StorageError> {
+        let mut file = tokio::fs::File::open(self.path_for(key)?)
+            .await
+            .map_err(|e| io_error(key, &e))?;
+        file.seek(SeekFrom::Start(range.start))
+            .await
+            .map_err(|e| io_error(key, &e))?;
+        let mut bytes = Vec::new();
+        file.take(range.end.saturating_sub(range.start))
+            .read_to_end(&mut bytes)
+            .await
+            .map_err(|e| io_error(key, &e))?;
+        Ok(bytes)
+    }
+
+    async fn put(&self, key: &str, data: Vec<u8>) -> Result<(), StorageError> {
+        let path = self.path_for(key)?;
+        if let Some(parent) = path.parent() {
+            tokio::fs::create_dir_all(parent)
+                .await
+                .map_err(|e| io_error(key, &e))?;
+        }
+        tokio::fs::write(path, data)
+            .await
+            .map_err(|e| io_error(key, &e))
+    }
+
+    async fn delete(&self, key: &str) -> Result<(), StorageError> {
+        tokio::fs::remove_file(self.path_for(key)?)
+            .await
+            .map_err(|e| io_error(key, &e))
+    }
+
+    async fn writer(&self, key: &str) -> Result<Box<dyn ObjectWriter>, StorageError> {
+        let path = self.path_for(key)?;
+        let (Some(parent), Some(name)) = (path.parent(), path.file_name()) else {
+            return Err(StorageError::AccessDenied(key.to_string()));
+        };
+        tokio::fs::create_dir_all(parent)
+            .await
+            .map_err(|e| io_error(key, &e))?;
+        let partial = parent.join(format!(
+            ".{}.{:016x}.part",
+            name.to_string_lossy(),
+            rand::random::<u64>()
+        ));
+        let file = tokio::fs::File::create(&partial)
+            .await
+            .map_err(|e| io_error(key, &e))?;
+        Ok(Box::new(LocalWriter {
+            key: key.to_string(),
+            path,
+            partial,
+            file,
+        }))
+    }
+}
+
+// =============================================================================
+// S3
+// =============================================================================
+
+/// Where [`S3Storage`] gets its credentials
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum S3Credentials {
+    /// The role of the instance, container or pod running the service.
+    /// Access keys in the environment are ignored.
+    IamRole,
+    /// `AWS_ACCESS_KEY_ID` and `AWS_SECRET_ACCESS_KEY` from the environment
+    AccessKeys,
+}
+
+/// Stores objects in an S3 bucket, optionally under a key prefix
+#[derive(Debug)]
+pub struct S3Storage {
+    store: AmazonS3,
+    prefix: Option<String>,
+}
+
+impl S3Storage {
+    pub fn new(
+        bucket: &str,
+        region: &str,
+        prefix: Option<&str>,
+        credentials: S3Credentials,
+    ) -> Result<Self, StorageError> {
+        let builder = match credentials {
+            S3Credentials::IamRole => {
+                // Web identity (EKS) is picked up at build time; ECS task
+                // roles need the relative URI passed through
+                let builder = AmazonS3Builder::new();
+                match std::env::var("AWS_CONTAINER_CREDENTIALS_RELATIVE_URI") {
+                    Ok(uri) => {
+                        builder.with_config(AmazonS3ConfigKey::ContainerCredentialsRelativeUri, uri)
+                    }
+                    Err(_) => builder,
+                }
+            }
+            S3Credentials::AccessKeys => {
+                for var in ["AWS_ACCESS_KEY_ID", "AWS_SECRET_ACCESS_KEY"] {
+                    if std::env::var_os(var).is_none() {
+                        return Err(StorageError::AccessDenied(format!(
+                            "s3://{bucket}: {var} is not set and the source doesn't use an IAM role"
+                        )));
+                    }
+                }
+                AmazonS3Builder::from_env()
+            }
+        };
+        let store = builder
+            .with_bucket_name(bucket)
+            .with_region(region)
+            .build()
+            .map_err(|e| StorageError::Other(e.to_string()))?;
+        Ok(Self {
+            store,
+            prefix: prefix
+                .map(|p| p.trim_matches('/').to_string())
+                .filter(|p| !p.is_empty()),
+        })
+    }
+
+    fn path_for(&self, key: &str) -> ObjectPath {
+        match &self.prefix {
+            Some(prefix) => ObjectPath::from(format!("{prefix}/{key}")),
+            None => ObjectPath::from(key),
+        }
+    }
+
+    fn key_for(&self, path: &ObjectPath) -> String {
+        let path = path.as_ref();
+        match &self.prefix {
+            Some(prefix) => path
+                .strip_prefix(prefix.as_str())
+                .map_or(path, |rest| rest.trim_start_matches('/'))
+                .to_string(),
+            None => path.to_string(),
+        }
+    }
+}
+
+fn object_error(key: &str, e: object_store::Error) -> StorageError {
+    match e {
+        object_store::Error::NotFound { .. } => StorageError::NotFound(key.to_string()),
+        object_store::Error::PermissionDenied { .. }
+        | object_store::Error::Unauthenticated { .. } => {
+            StorageError::AccessDenied(key.to_string())
+        }
+        e => StorageError::Other(format!("{key}: {e}")),
+    }
+}
+
+#[async_trait]
+impl StorageService for S3Storage {
+    async fn list(
+        &self,
+        prefix: &str,
+        after: Option<&str>,
+        limit: usize,
+    ) -> Result<ObjectPage, StorageError> {
+        // Object store prefixes match whole path segments, so list the
+        // enclosing directory and filter by the raw prefix
+        let dir = prefix
+            .rsplit_once('/')
+            .map(|(dir, _)| dir)
+            .unwrap_or_default();
+        let root = if dir.is_empty() && self.prefix.is_none() {
+            None
+        } else {
+            Some(self.path_for(dir))
+        };
+
+        // S3 lists keys in order, so only one object past the page is fetched
+        let listing = match after {
+            Some(after) => self
+                .store
+                .list_with_offset(root.as_ref(), &self.path_for(after)),
+            None => self.store.list(root.as_ref()),
+        };
+        let objects: Vec<StoredObject> = listing
+            .map_ok(|meta| StoredObject {
+                key: self.key_for(&meta.location),
+                size_bytes: u64::try_from(meta.size).unwrap_or(u64::MAX),
+                modified_at: Some(meta.last_modified),
+            })
+            .try_filter(|object| std::future::ready(object.key.starts_with(prefix)))
+            .take(limit.saturating_add(1))
+            .try_collect()
+            .await
+            .map_err(|e| object_error(prefix, e))?;
+
+        Ok(ObjectPage::from_listing(objects, limit))
+    }
+
+    async fn get(&self, key: &str) -> Result<Vec<u8>, StorageError> {
+        let result = self
+            .store
+            .get(&self.path_for(key))
+            .await
+            .map_err(|e| object_error(key, e))?;
+        let bytes = result.bytes().await.map_err(|e| object_error(key, e))?;
+        Ok(bytes.to_vec())
+    }
+
+    async fn get_range(&self, key: &str, range: Range<u64>) -> Result<Vec<u8>, StorageError> {
+        let to_usize = |n: u64| usize::try_from(n).unwrap_or(usize::MAX);
+        let options = GetOptions {
+            range: Some(GetRange::Bounded(
+                to_usize(range.start)..to_usize(range.end),
+            )),
+            ..Default::default()
+        };
+        let result = self
+            .store
+            .get_opts(&self.path_for(key), options)
+            .await
+            .map_err(|e| object_error(key, e))?;
+        let bytes = result.bytes().await.map_err(|e| object_error(key, e))?;
+        Ok(bytes.to_vec())
+    }
+
+    async fn put(&self, key: &str, data: Vec<u8>) -> Result<(), StorageError> {
+        self.store
+            .put(&self.path_for(key), PutPayload::from(data))
+            .await
+            .map(|_| ())
+            .map_err(|e| object_error(key, e))
+    }
+
+    async fn delete(&self, key: &str) -> Result<(), StorageError> {
+        self.store
+            .delete(&self.path_for(key))
+            .await
+            .map_err(|e| object_error(key, e))
+    }
+
+    async fn writer(&self, key: &str) -> Result<Box<dyn ObjectWriter>, StorageError> {
+        let upload = self
+            .store
+            .put_multipart(&self.path_for(key))
+            .await
+            .map_err(|e| object_error(key, e))?;
+        Ok(Box::new(S3Writer {
+            key: key.to_string(),
+            upload: WriteMultipart::new(upload),
+        }))
+    }
+}
+
+/// Parts uploaded at once by an [`S3Writer`]
+const S3_UPLOAD_CONCURRENCY: usize = 4;
+
+/// Writes a multipart upload, buffering one part at a time
+struct S3Writer {
+    key: String,
+    upload: WriteMultipart,
+}
+
+#[async_trait]
+impl ObjectWriter for S3Writer {
+    async fn write(&mut self, chunk: &[u8]) -> Result<(), StorageError> {
+        self.upload
+            .wait_for_capacity(S3_UPLOAD_CONCURRENCY)
+            .await
+            .map_err(|e| object_error(&self.key, e))?;
+        self.upload.write(chunk);
+        Ok(())
+    }
+
+    async fn finish(self: Box<Self>) -> Result<(), StorageError> {
+        let key = self.key;
+        self.upload
+            .finish()
+            .await
+            .map(|_| ())
+            .map_err(|e| object_error(&key, e))
+    }
+
+    async fn abort(self: Box<Self>) -> Result<(), StorageError> {
+        let key = self.key;
+        self.upload.abort().await.map_err(|e| object_error(&key, e))
+    }
+}
+
+// =============================================================================
+// Circuit breaking
+// =============================================================================
+
+/// Wraps a backend so repeated failures short-circuit with
+/// [`StorageError::Unavailable`] instead of waiting on each timeout.
+///
+/// Missing objects and denied access show the backend is up, so only
+/// [`StorageError::Other`] counts towards opening the circuit.
+pub struct CircuitBreakingStorage<S> {
+    inner: S,
+    breaker: Arc<CircuitBreaker>,
+}
+
+impl<S: StorageService> CircuitBreakingStorage<S> {
+    /// Guard `inner` with `breaker`, which may be shared across instances
+    /// talking to the same backend
+    pub fn new(inner: S, breaker: Arc<CircuitBreaker>) -> Self {
+        Self { inner, breaker }
+    }
+
+    async fn guard<T, Fut>(&self, key: &str, call: Fut) -> Result<T, StorageError>
+    where
+        Fut: std::future::Future<Output = Result<T, StorageError>>,
+    {
+        self.breaker
+            .call_if(|e| matches!(e, StorageError::Other(_)), || call)
+            .await
+            .map_err(|e| match e {
+                CircuitError::Open { retry_in } => StorageError::Unavailable(format!(
+                    "{key}: backend failing, retry in {}s",
+                    retry_in.as_secs().max(1)
+                )),
+                CircuitError::Inner(e) => e,
+            })
+    }
+}
+
+#[async_trait]
+impl<S: StorageService> StorageService for CircuitBreakingStorage<S> {
+    async fn list(
+        &self,
+        prefix: &str,
+        after: Option<&str>,
+        limit: usize,
+    ) -> Result<ObjectPage, StorageError> {
+        self.guard(prefix, self.inner.list(prefix, after, limit))
+            .await
+    }
+
+    async fn get(&self, key: &str) -> Result<Vec<u8>, StorageError> {
+        self.guard(key, self.inner.get(key)).await
+    }
+
+    async fn get_range(&self, key: &str, range: Range<u64>) -> Result<Vec<u8>, StorageError> {
+        self.guard(key, self.inner.get_range(key, range)).await
+    }
+
+    async fn put(&self, key: &str, data: Vec<u8>) -> Result<(), StorageError> {
+        self.guard(key, self.inner.put(key, data)).await
+    }
+
+    async fn delete(&self, key: &str) -> Result<(), StorageError> {
+        self.guard(key, self.inner.delete(key)).await
+    }
+
+    /// Only starting the write is guarded; a backend that fails mid-write
+    /// surfaces the error from the writer itself
+    async fn writer(&self, key: &str) -> Result<Box<dyn ObjectWriter>, StorageError> {
+        self.guard(key, self.inner.writer(key)).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A fresh directory under the system temp dir, removed on drop
+    struct TempDir(PathBuf);
+
+    impl TempDir {
+        fn new() -> Self {
+            let path = std::env::temp_dir().join(format!("glyph-storage-{}", uuid::Uuid::new_v4()));
+            Self(path)
+        }
+    }
+
+    impl Drop for TempDir {
+        fn drop(&mut self) {
+            let _ = std::fs::remove_dir_all(&self.0);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_local_put_get_list_delete() {
+        let dir = TempDir::new();
+        let storage = LocalStorage::new(&dir.0);
+
+        // Nothing stored yet
+        assert_eq!(
+            storage.list("", None, 10).await.unwrap(),
+            ObjectPage::default()
+        );
+
+        storage
+            .put("exports/a.parquet", b"abc".to_vec())
+            .await
+            .unwrap();
+        storage
+            .put("exports/b.parquet", b"de".to_vec())
+            .await
+            .unwrap();
+        storage
+            .put("uploads/c.jsonl", b"{}".to_vec())
+            .await
+            .unwrap();
+
+        assert_eq!(storage.get("exports/a.parquet").await.unwrap(), b"abc");
+        assert_eq!(
+            storage.get_range("exports/a.parquet", 1..3).await.unwrap(),
+            b"bc"
+        );
+
+        let exports = storage.list("exports/", None, 10).await.unwrap();
+        let keys: Vec<&str> = exports.objects.iter().map(|o| o.key.as_str()).collect();
+        assert_eq!(keys, ["exports/a.parquet", "exports/b.parquet"]);
+        assert!(!exports.has_more);
+        assert_eq!(exports.objects[1].size_bytes, 2);
+        assert!(exports.objects[0].modified_at.is_some());
+        assert_eq!(storage.list("", None, 10).await.unwrap().objects.len(), 3);
+
+        storage.delete("exports/a.parquet").await.unwrap();
+        assert!(matches!(
+            storage.get("exports/a.parquet").await,
+            Err(StorageError::NotFound(_))
+        ));
+        assert!(matches!(
+            storage.delete("exports/a.parquet").await,
+            Err(StorageError::NotFound(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_local_list_pages_after_key() {
+        let dir = TempDir::new();
+        let storage = LocalStorage::new(&dir.0);
+        for key in ["a.jsonl", "b.jsonl", "c.jsonl", "d.csv"] {
+            storage.put(key, b"{}".to_vec()).await.unwrap();
+        }
+
+        let first = storage.list("", None, 2).await.unwrap();
+        let keys: Vec<&str> = first.objects.iter().map(|o| o.key.as_str()).collect();
+        assert_eq!(keys, ["a.jsonl", "b.jsonl"]);
+        assert!(first.has_more);
+
+        let second = storage.list("", Some("b.jsonl"), 2).await.unwrap();
+        let keys: Vec<&str> = second.objects.iter().map(|o| o.key.as_str()).collect();
+        assert_eq!(keys, ["c.jsonl", "d.csv"]);
+        assert!(!second.has_more);
+
+        // The prefix still applies past the cursor
+        let jsonl = storage.list("c", Some("a.jsonl"), 10).await.unwrap();
+        assert_eq!(jsonl.objects.len(), 1);
+        assert!(!jsonl.has_more);
+    }
+
+    #[tokio::test]
+    async fn test_local_writer_only_stores_finished_objects() {
+        let dir = TempDir::new();
+        let storage = LocalStorage::new(&dir.0);
+        storage.put("tasks.jsonl", b"old".to_vec()).await.unwrap();
+
+        let mut writer = storage.writer("tasks.jsonl").await.unwrap();
+        writer.write(b"{\"a\": 1}\n").await.unwrap();
+        writer.write(b"{\"a\": 2}\n").await.unwrap();
+        // In-progress writes are neither listed nor visible at the key
+        assert_eq!(storage.list("", None, 10).await.unwrap().objects.len(), 1);
+        assert_eq!(storage.get("tasks.jsonl").await.unwrap(), b"old");
+        writer.finish().await.unwrap();
+        assert_eq!(
+            storage.get("tasks.jsonl").await.unwrap(),
+            b"{\"a\": 1}\n{\"a\": 2}\n"
+        );
+
+        let mut aborted = storage.writer("other.jsonl").await.unwrap();
+        aborted.write(b"partial").await.unwrap();
+        aborted.abort().await.unwrap();
+        assert!(matches!(
+            storage.get("other.jsonl").await,
+            Err(StorageError::NotFound(_))
+        ));
+        assert_eq!(std::fs::read_dir(&dir.0).unwrap().count(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_local_keys_cannot_escape_root() {
+        let dir = TempDir::new();
+        let storage = LocalStorage::new(dir.0.join("root"));
+
+        for key in ["../outside", "/etc/passwd", "a/../../b", ""] {
+            assert!(
+                matches!(
+                    storage.put(key, b"x".to_vec()).await,
+                    Err(StorageError::AccessDenied(_))
+                ),
+                "{key}"
+            );
+        }
+        assert!(!dir.0.join("outside").exists());
+    }
+
+    #[tokio::test]
+    async fn test_circuit_breaking_storage_short_circuits_failing_backend() {
+        let dir = TempDir::new();
+        let breaker = Arc::new(CircuitBreaker::new(2, std::time::Duration::from_secs(60)));
+        let healthy =
+            CircuitBreakingStorage::new(LocalStorage::new(dir.0.join("root")), breaker.clone());
+        // A file where the root directory should be makes every write fail
+        std::fs::create_dir_all(&dir.0).unwrap();
+        std::fs::write(dir.0.join("broken"), b"not a directory").unwrap();
+        let storage = CircuitBreakingStorage::new(LocalStorage::new(dir.0.join("broken")), breaker);
+
+        // Missing objects don't count as failures
+        for _ in 0..3 {
+            assert!(matches!(
+                healthy.get("missing.txt").await,
+                Err(StorageError::NotFound(_))
+            ));
+        }
+
+        for _ in 0..2 {
+            assert!(matches!(
+                storage.put("a.txt", b"x".to_vec()).await,
+                Err(StorageError::Other(_))
+            ));
+        }
+        assert!(matches!(
+            storage.put("a.txt", b"x".to_vec()).await,
+            Err(StorageError::Unavailable(_))
+        ));
+    }
+}
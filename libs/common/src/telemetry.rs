@@ -2,10 +2,13 @@
 
 use tracing_subscriber::{fmt, layer::SubscriberExt, util::SubscriberInitExt, EnvFilter, Layer};
 
+use crate::redact::{RedactedJson, RedactingFields};
+
 /// Initialize tracing/logging for the application.
 ///
 /// Uses RUST_LOG env var for filtering.
 /// Outputs JSON in production, pretty format in development.
+/// Both formats mask sensitive fields (see [`RedactingFields`]).
 pub fn init_tracing() {
     let env_filter = EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new("info"));
 
@@ -15,9 +18,15 @@ pub fn init_tracing() {
             .with_target(true)
             .with_file(true)
             .with_line_number(true)
+            .fmt_fields(RedactingFields::default().json())
+            .map_event_format(RedactedJson::new)
             .boxed()
     } else {
-        fmt::layer().pretty().with_target(true).boxed()
+        fmt::layer()
+            .pretty()
+            .fmt_fields(RedactingFields::default())
+            .with_target(true)
+            .boxed()
     };
 
     tracing_subscriber::registry()
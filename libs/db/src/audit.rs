@@ -2,13 +2,14 @@
 //!
 //! Records all mutating operations with full data snapshots and diffs.
 
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
-use sqlx::PgPool;
+use sqlx::{PgExecutor, PgPool};
 use thiserror::Error;
 
 /// Type of audit action
-#[derive(Debug, Clone, Copy, Serialize, Deserialize, sqlx::Type)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, sqlx::Type)]
 #[sqlx(type_name = "audit_action", rename_all = "snake_case")]
 #[serde(rename_all = "snake_case")]
 pub enum AuditAction {
@@ -19,7 +20,7 @@ pub enum AuditAction {
 }
 
 /// Type of actor performing the action
-#[derive(Debug, Clone, Copy, Serialize, Deserialize, sqlx::Type)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, sqlx::Type)]
 #[sqlx(type_name = "audit_actor_type", rename_all = "snake_case")]
 #[serde(rename_all = "snake_case")]
 pub enum AuditActorType {
@@ -49,6 +50,21 @@ pub struct AuditEvent {
     pub request_id: Option<String>,
 }
 
+/// A recorded audit event
+#[derive(Debug, Clone, Serialize, sqlx::FromRow)]
+pub struct AuditRecord {
+    pub audit_id: uuid::Uuid,
+    pub entity_type: String,
+    pub entity_id: String,
+    pub action: AuditAction,
+    pub actor_id: String,
+    pub actor_type: AuditActorType,
+    pub data_snapshot: Value,
+    pub changes: Option<Value>,
+    pub request_id: Option<String>,
+    pub occurred_at: DateTime<Utc>,
+}
+
 /// Errors from audit operations
 #[derive(Debug, Error)]
 pub enum AuditError {
@@ -78,6 +94,15 @@ impl AuditWriter {
     /// Note: This intentionally does not fail the operation if audit recording fails.
     /// Audit failures are logged but don't block the main operation.
     pub async fn record(&self, event: AuditEvent) -> Result<(), AuditError> {
+        Self::record_with(&self.pool, event).await
+    }
+
+    /// Record an audit event on a given connection, so it commits or rolls
+    /// back with the change it describes
+    pub async fn record_with<'e, E: PgExecutor<'e>>(
+        executor: E,
+        event: AuditEvent,
+    ) -> Result<(), AuditError> {
         sqlx::query(
             r#"
             INSERT INTO audit_events
@@ -94,7 +119,7 @@ impl AuditWriter {
         .bind(&event.data_snapshot)
         .bind(&event.changes)
         .bind(&event.request_id)
-        .execute(&self.pool)
+        .execute(executor)
         .await?;
 
         Ok(())
@@ -159,6 +184,55 @@ impl AuditWriter {
     }
 }
 
+/// Reader for recorded audit events
+#[derive(Clone)]
+pub struct AuditReader {
+    pool: PgPool,
+}
+
+impl AuditReader {
+    /// Create a new audit reader
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    /// One page of an entity's history, newest first, with the total count
+    pub async fn list_for_entity(
+        &self,
+        entity_type: &str,
+        entity_id: &str,
+        limit: i64,
+        offset: i64,
+    ) -> Result<(Vec<AuditRecord>, i64), sqlx::Error> {
+        let records = sqlx::query_as::<_, AuditRecord>(
+            r#"
+            SELECT audit_id, entity_type, entity_id, action, actor_id, actor_type,
+                   data_snapshot, changes, request_id, occurred_at
+            FROM audit_events
+            WHERE entity_type = $1 AND entity_id = $2
+            ORDER BY occurred_at DESC, audit_id DESC
+            LIMIT $3 OFFSET $4
+            "#,
+        )
+        .bind(entity_type)
+        .bind(entity_id)
+        .bind(limit)
+        .bind(offset)
+        .fetch_all(&self.pool)
+        .await?;
+
+        let total = sqlx::query_scalar::<_, i64>(
+            "SELECT COUNT(*) FROM audit_events WHERE entity_type = $1 AND entity_id = $2",
+        )
+        .bind(entity_type)
+        .bind(entity_id)
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok((records, total))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
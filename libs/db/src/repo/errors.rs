@@ -71,6 +71,21 @@ pub enum UpdateTeamError {
     Database(#[source] sqlx::Error),
 }
 
+#[derive(Debug, Error)]
+pub enum MoveTeamError {
+    #[error("team not found: {0}")]
+    NotFound(TeamId),
+    #[error("parent team not found: {0}")]
+    ParentNotFound(TeamId),
+    #[error("moving team {team_id} under {new_parent_id} would create a cycle")]
+    Cycle {
+        team_id: TeamId,
+        new_parent_id: TeamId,
+    },
+    #[error("database error")]
+    Database(#[source] sqlx::Error),
+}
+
 #[derive(Debug, Error)]
 pub enum TeamMembershipError {
     #[error("team not found: {0}")]
@@ -85,6 +100,22 @@ pub enum TeamMembershipError {
     Database(#[source] sqlx::Error),
 }
 
+#[derive(Debug, Error)]
+pub enum BulkMembershipError {
+    #[error("team not found: {0}")]
+    TeamNotFound(TeamId),
+    #[error("user not found: {0}")]
+    UserNotFound(UserId),
+    #[error("user listed more than once: {0}")]
+    DuplicateUser(UserId),
+    #[error("change would remove the team's last leader")]
+    LastLeader,
+    #[error("change would grow the team to {requested} members, above its capacity of {capacity}")]
+    CapacityExceeded { capacity: i32, requested: usize },
+    #[error("database error")]
+    Database(#[source] sqlx::Error),
+}
+
 // =============================================================================
 // Project Repository Errors
 // =============================================================================
@@ -372,3 +403,21 @@ pub enum UpdateAssignmentError {
     #[error("database error")]
     Database(#[source] sqlx::Error),
 }
+
+// =============================================================================
+// Quality Profile Repository Errors
+// =============================================================================
+
+#[derive(Debug, Error)]
+pub enum FindQualityProfileError {
+    #[error("database error")]
+    Database(#[source] sqlx::Error),
+}
+
+#[derive(Debug, Error)]
+pub enum UpsertQualityProfileError {
+    #[error("user not found: {0}")]
+    UserNotFound(UserId),
+    #[error("database error")]
+    Database(#[source] sqlx::Error),
+}
@@ -3,25 +3,43 @@
 //! Contains repository traits, error types, and PostgreSQL implementations.
 
 pub mod errors;
+pub mod pg_annotation;
 pub mod pg_assignment;
 pub mod pg_data_source;
+pub mod pg_dead_letter;
+pub mod pg_draft;
+pub mod pg_goal;
+pub mod pg_layout;
+pub mod pg_notification;
+pub mod pg_organization;
 pub mod pg_project;
 pub mod pg_project_type;
+pub mod pg_quality_profile;
 pub mod pg_skill;
 pub mod pg_stubs;
 pub mod pg_task;
 pub mod pg_team;
 pub mod pg_user;
+pub mod pg_webhook;
 pub mod traits;
 
 pub use errors::*;
+pub use pg_annotation::*;
 pub use pg_assignment::*;
 pub use pg_data_source::*;
+pub use pg_dead_letter::*;
+pub use pg_draft::*;
+pub use pg_goal::*;
+pub use pg_layout::*;
+pub use pg_notification::*;
+pub use pg_organization::*;
 pub use pg_project::*;
 pub use pg_project_type::*;
+pub use pg_quality_profile::*;
 pub use pg_skill::*;
 pub use pg_stubs::*;
 pub use pg_task::*;
 pub use pg_team::*;
 pub use pg_user::*;
+pub use pg_webhook::*;
 pub use traits::*;
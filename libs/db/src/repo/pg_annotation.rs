@@ -0,0 +1,584 @@
+//! PostgreSQL implementation of AnnotationRepository
+//!
+//! Every save of annotation data appends a row to `annotation_versions`
+//! in the same transaction that updates the annotation, so the edit
+//! history is never overwritten.
+
+use async_trait::async_trait;
+use sqlx::{PgPool, Postgres, Transaction};
+
+use glyph_domain::{
+    Annotation, AnnotationId, AnnotationStatus, AnnotationVersion, AssignmentId, IdParseError,
+    ProjectId, TaskId, UserId,
+};
+
+use crate::pagination::{Page, Pagination};
+use crate::repo::errors::{CreateAnnotationError, FindAnnotationError, UpdateAnnotationError};
+use crate::repo::traits::{
+    AnnotationRepository, AnnotationUpdate, CompletedAnnotation, NewAnnotation,
+};
+
+/// PostgreSQL annotation repository
+pub struct PgAnnotationRepository {
+    pool: PgPool,
+}
+
+impl PgAnnotationRepository {
+    /// Create a new PostgreSQL annotation repository
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl AnnotationRepository for PgAnnotationRepository {
+    async fn find_by_id(
+        &self,
+        id: &AnnotationId,
+    ) -> Result<Option<Annotation>, FindAnnotationError> {
+        let row = sqlx::query_as::<_, AnnotationRow>(
+            r#"
+            SELECT annotation_id::text, task_id::text, step_id, user_id::text,
+                   assignment_id::text, project_id::text, data, status::text, version,
+                   parent_version_id::text, created_at, updated_at, submitted_at,
+                   quality_score, quality_evaluated_at, time_spent_ms, client_metadata,
+                   schema_version
+            FROM annotations
+            WHERE annotation_id = $1
+            "#,
+        )
+        .bind(id.as_uuid())
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(FindAnnotationError::Database)?;
+
+        row.map(|r| r.try_into())
+            .transpose()
+            .map_err(|_| FindAnnotationError::NotFound(*id))
+    }
+
+    async fn create(
+        &self,
+        annotation: &NewAnnotation,
+    ) -> Result<Annotation, CreateAnnotationError> {
+        let id = AnnotationId::new();
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .map_err(CreateAnnotationError::Database)?;
+
+        let row = sqlx::query_as::<_, AnnotationRow>(
+            r#"
+            INSERT INTO annotations (annotation_id, task_id, step_id, user_id,
+                                     assignment_id, project_id, data, schema_version)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, (
+                SELECT pt.schema_version
+                FROM projects p
+                JOIN project_types pt ON pt.project_type_id = p.project_type_id
+                WHERE p.project_id = $6
+            ))
+            RETURNING annotation_id::text, task_id::text, step_id, user_id::text,
+                      assignment_id::text, project_id::text, data, status::text, version,
+                      parent_version_id::text, created_at, updated_at, submitted_at,
+                      quality_score, quality_evaluated_at, time_spent_ms, client_metadata,
+                      schema_version
+            "#,
+        )
+        .bind(id.as_uuid())
+        .bind(annotation.task_id.as_uuid())
+        .bind(&annotation.step_id)
+        .bind(annotation.user_id.as_uuid())
+        .bind(annotation.assignment_id.as_uuid())
+        .bind(annotation.project_id.as_uuid())
+        .bind(&annotation.data)
+        .fetch_one(&mut *tx)
+        .await
+        .map_err(|e| {
+            if let sqlx::Error::Database(ref db_err) = e {
+                if db_err.constraint() == Some("annotations_user_id_fkey") {
+                    return CreateAnnotationError::UserNotFound(annotation.user_id);
+                }
+            }
+            CreateAnnotationError::Database(e)
+        })?;
+
+        let result: Annotation = row
+            .try_into()
+            .map_err(|_| CreateAnnotationError::Database(sqlx::Error::RowNotFound))?;
+
+        insert_version(&mut tx, &result)
+            .await
+            .map_err(CreateAnnotationError::Database)?;
+        tx.commit().await.map_err(CreateAnnotationError::Database)?;
+
+        Ok(result)
+    }
+
+    async fn update(
+        &self,
+        id: &AnnotationId,
+        update: &AnnotationUpdate,
+    ) -> Result<Annotation, UpdateAnnotationError> {
+        let status_str = update.status.map(|s| format!("{s:?}").to_lowercase());
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .map_err(UpdateAnnotationError::Database)?;
+
+        // Only data changes bump the version; status-only updates keep it
+        let row = sqlx::query_as::<_, AnnotationRow>(
+            r#"
+            UPDATE annotations
+            SET data = COALESCE($2, data),
+                status = COALESCE($3::annotation_status, status),
+                version = CASE WHEN $2 IS NULL THEN version ELSE version + 1 END,
+                updated_at = NOW()
+            WHERE annotation_id = $1 AND status = 'draft'
+            RETURNING annotation_id::text, task_id::text, step_id, user_id::text,
+                      assignment_id::text, project_id::text, data, status::text, version,
+                      parent_version_id::text, created_at, updated_at, submitted_at,
+                      quality_score, quality_evaluated_at, time_spent_ms, client_metadata,
+                      schema_version
+            "#,
+        )
+        .bind(id.as_uuid())
+        .bind(&update.data)
+        .bind(status_str)
+        .fetch_optional(&mut *tx)
+        .await
+        .map_err(UpdateAnnotationError::Database)?;
+
+        let Some(row) = row else {
+            return Err(self.missing_or_not_draft(id).await);
+        };
+
+        let result: Annotation = row
+            .try_into()
+            .map_err(|_| UpdateAnnotationError::NotFound(*id))?;
+
+        if update.data.is_some() {
+            insert_version(&mut tx, &result)
+                .await
+                .map_err(UpdateAnnotationError::Database)?;
+        }
+        tx.commit().await.map_err(UpdateAnnotationError::Database)?;
+
+        Ok(result)
+    }
+
+    async fn list_by_task(
+        &self,
+        task_id: &TaskId,
+        pagination: Pagination,
+    ) -> Result<Page<Annotation>, sqlx::Error> {
+        let total = sqlx::query_scalar::<_, i64>(
+            "SELECT COUNT(*) FROM annotations WHERE task_id = $1 AND status != 'deleted'",
+        )
+        .bind(task_id.as_uuid())
+        .fetch_one(&self.pool)
+        .await?;
+
+        let rows = sqlx::query_as::<_, AnnotationRow>(
+            r#"
+            SELECT annotation_id::text, task_id::text, step_id, user_id::text,
+                   assignment_id::text, project_id::text, data, status::text, version,
+                   parent_version_id::text, created_at, updated_at, submitted_at,
+                   quality_score, quality_evaluated_at, time_spent_ms, client_metadata,
+                   schema_version
+            FROM annotations
+            WHERE task_id = $1 AND status != 'deleted'
+            ORDER BY created_at DESC
+            LIMIT $2 OFFSET $3
+            "#,
+        )
+        .bind(task_id.as_uuid())
+        .bind(pagination.clamped_limit())
+        .bind(pagination.offset)
+        .fetch_all(&self.pool)
+        .await?;
+
+        let annotations: Vec<Annotation> =
+            rows.into_iter().filter_map(|r| r.try_into().ok()).collect();
+
+        Ok(Page::new(annotations, total, &pagination))
+    }
+
+    async fn submit(&self, id: &AnnotationId) -> Result<Annotation, UpdateAnnotationError> {
+        let row = sqlx::query_as::<_, AnnotationRow>(
+            r#"
+            UPDATE annotations
+            SET status = 'submitted', submitted_at = NOW(), updated_at = NOW()
+            WHERE annotation_id = $1 AND status = 'draft'
+            RETURNING annotation_id::text, task_id::text, step_id, user_id::text,
+                      assignment_id::text, project_id::text, data, status::text, version,
+                      parent_version_id::text, created_at, updated_at, submitted_at,
+                      quality_score, quality_evaluated_at, time_spent_ms, client_metadata,
+                      schema_version
+            "#,
+        )
+        .bind(id.as_uuid())
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(UpdateAnnotationError::Database)?;
+
+        let Some(row) = row else {
+            return Err(self.missing_or_not_draft(id).await);
+        };
+
+        row.try_into()
+            .map_err(|_| UpdateAnnotationError::NotFound(*id))
+    }
+
+    async fn list_completed_by_project(
+        &self,
+        project_id: &ProjectId,
+        since: Option<chrono::DateTime<chrono::Utc>>,
+        until: chrono::DateTime<chrono::Utc>,
+    ) -> Result<Vec<CompletedAnnotation>, sqlx::Error> {
+        let rows = sqlx::query_as::<_, CompletedAnnotationRow>(
+            r#"
+            SELECT * FROM (
+                SELECT a.annotation_id::text, a.task_id::text, a.step_id, a.user_id::text,
+                       a.assignment_id::text, a.project_id::text, a.data, a.status::text,
+                       a.version, a.parent_version_id::text, a.created_at, a.updated_at,
+                       a.submitted_at, a.quality_score, a.quality_evaluated_at,
+                       a.time_spent_ms, a.client_metadata, a.schema_version,
+                       GREATEST(t.completed_at, a.updated_at) AS changed_at
+                FROM annotations a
+                JOIN tasks t ON t.project_id = a.project_id AND t.task_id = a.task_id
+                WHERE a.project_id = $1
+                  AND t.status = 'completed'
+                  AND a.status IN ('submitted', 'approved')
+            ) completed
+            WHERE ($2::timestamptz IS NULL OR changed_at > $2)
+              AND changed_at <= $3
+            ORDER BY changed_at, annotation_id
+            "#,
+        )
+        .bind(project_id.as_uuid())
+        .bind(since)
+        .bind(until)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(rows
+            .into_iter()
+            .filter_map(|r| {
+                let changed_at = r.changed_at;
+                Annotation::try_from(r.annotation)
+                    .ok()
+                    .map(|annotation| CompletedAnnotation {
+                        annotation,
+                        changed_at,
+                    })
+            })
+            .collect())
+    }
+
+    async fn list_versions(
+        &self,
+        task_id: &TaskId,
+        user_id: &UserId,
+    ) -> Result<Vec<AnnotationVersion>, sqlx::Error> {
+        let rows = sqlx::query_as::<_, AnnotationVersionRow>(
+            r#"
+            SELECT annotation_id::text, task_id::text, user_id::text, version, data, saved_at
+            FROM annotation_versions
+            WHERE task_id = $1 AND user_id = $2
+            ORDER BY saved_at, annotation_id, version
+            "#,
+        )
+        .bind(task_id.as_uuid())
+        .bind(user_id.as_uuid())
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(rows.into_iter().filter_map(|r| r.try_into().ok()).collect())
+    }
+
+    async fn history(&self, id: &AnnotationId) -> Result<Vec<AnnotationVersion>, sqlx::Error> {
+        let rows = sqlx::query_as::<_, AnnotationVersionRow>(
+            r#"
+            SELECT annotation_id::text, task_id::text, user_id::text, version, data, saved_at
+            FROM annotation_versions
+            WHERE annotation_id = $1
+            ORDER BY version
+            "#,
+        )
+        .bind(id.as_uuid())
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(rows.into_iter().filter_map(|r| r.try_into().ok()).collect())
+    }
+
+    async fn find_latest_submission(
+        &self,
+        task_id: &TaskId,
+        user_id: &UserId,
+    ) -> Result<Option<Annotation>, sqlx::Error> {
+        let row = sqlx::query_as::<_, AnnotationRow>(
+            r#"
+            SELECT annotation_id::text, task_id::text, step_id, user_id::text,
+                   assignment_id::text, project_id::text, data, status::text, version,
+                   parent_version_id::text, created_at, updated_at, submitted_at,
+                   quality_score, quality_evaluated_at, time_spent_ms, client_metadata,
+                   schema_version
+            FROM annotations
+            WHERE task_id = $1 AND user_id = $2
+              AND status IN ('submitted', 'approved', 'rejected')
+            ORDER BY submitted_at DESC NULLS LAST, created_at DESC
+            LIMIT 1
+            "#,
+        )
+        .bind(task_id.as_uuid())
+        .bind(user_id.as_uuid())
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(row.and_then(|r| r.try_into().ok()))
+    }
+}
+
+impl PgAnnotationRepository {
+    /// Distinguish a missing annotation from one that is no longer a draft
+    async fn missing_or_not_draft(&self, id: &AnnotationId) -> UpdateAnnotationError {
+        match self.find_by_id(id).await {
+            Ok(Some(_)) => UpdateAnnotationError::NotDraft,
+            Ok(None) | Err(FindAnnotationError::NotFound(_)) => {
+                UpdateAnnotationError::NotFound(*id)
+            }
+            Err(FindAnnotationError::Database(e)) => UpdateAnnotationError::Database(e),
+        }
+    }
+}
+
+/// Append the annotation's current data as a new version row
+async fn insert_version(
+    tx: &mut Transaction<'_, Postgres>,
+    annotation: &Annotation,
+) -> Result<(), sqlx::Error> {
+    sqlx::query(
+        r#"
+        INSERT INTO annotation_versions (annotation_id, version, task_id, user_id, data)
+        VALUES ($1, $2, $3, $4, $5)
+        "#,
+    )
+    .bind(annotation.annotation_id.as_uuid())
+    .bind(annotation.version)
+    .bind(annotation.task_id.as_uuid())
+    .bind(annotation.user_id.as_uuid())
+    .bind(&annotation.data)
+    .execute(&mut **tx)
+    .await?;
+
+    Ok(())
+}
+
+// =============================================================================
+// Internal row types for SQLx mapping
+// =============================================================================
+
+#[derive(sqlx::FromRow)]
+struct AnnotationRow {
+    annotation_id: String,
+    task_id: String,
+    step_id: String,
+    user_id: String,
+    assignment_id: String,
+    project_id: String,
+    data: serde_json::Value,
+    status: String,
+    version: i32,
+    parent_version_id: Option<String>,
+    created_at: chrono::DateTime<chrono::Utc>,
+    updated_at: chrono::DateTime<chrono::Utc>,
+    submitted_at: Option<chrono::DateTime<chrono::Utc>>,
+    quality_score: Option<f64>,
+    quality_evaluated_at: Option<chrono::DateTime<chrono::Utc>>,
+    time_spent_ms: Option<i64>,
+    client_metadata: Option<serde_json::Value>,
+    schema_version: Option<i32>,
+}
+
+impl TryFrom<AnnotationRow> for Annotation {
+    type Error = IdParseError;
+
+    fn try_from(row: AnnotationRow) -> Result<Self, Self::Error> {
+        Ok(Annotation {
+            annotation_id: AnnotationId::from_uuid(parse_uuid(&row.annotation_id)?),
+            task_id: TaskId::from_uuid(parse_uuid(&row.task_id)?),
+            step_id: row.step_id,
+            user_id: UserId::from_uuid(parse_uuid(&row.user_id)?),
+            assignment_id: AssignmentId::from_uuid(parse_uuid(&row.assignment_id)?),
+            project_id: ProjectId::from_uuid(parse_uuid(&row.project_id)?),
+            data: row.data,
+            status: parse_annotation_status(&row.status),
+            version: row.version,
+            parent_annotation_id: row
+                .parent_version_id
+                .as_deref()
+                .map(parse_uuid)
+                .transpose()?
+                .map(AnnotationId::from_uuid),
+            created_at: row.created_at,
+            updated_at: row.updated_at,
+            submitted_at: row.submitted_at,
+            quality_score: row.quality_score,
+            quality_evaluated_at: row.quality_evaluated_at,
+            time_spent_ms: row.time_spent_ms,
+            client_metadata: row.client_metadata,
+            schema_version: row.schema_version,
+        })
+    }
+}
+
+#[derive(sqlx::FromRow)]
+struct CompletedAnnotationRow {
+    #[sqlx(flatten)]
+    annotation: AnnotationRow,
+    changed_at: chrono::DateTime<chrono::Utc>,
+}
+
+#[derive(sqlx::FromRow)]
+struct AnnotationVersionRow {
+    annotation_id: String,
+    task_id: String,
+    user_id: String,
+    version: i32,
+    data: serde_json::Value,
+    saved_at: chrono::DateTime<chrono::Utc>,
+}
+
+impl TryFrom<AnnotationVersionRow> for AnnotationVersion {
+    type Error = IdParseError;
+
+    fn try_from(row: AnnotationVersionRow) -> Result<Self, Self::Error> {
+        Ok(AnnotationVersion {
+            annotation_id: AnnotationId::from_uuid(parse_uuid(&row.annotation_id)?),
+            task_id: TaskId::from_uuid(parse_uuid(&row.task_id)?),
+            user_id: UserId::from_uuid(parse_uuid(&row.user_id)?),
+            version: row.version,
+            data: row.data,
+            saved_at: row.saved_at,
+        })
+    }
+}
+
+fn parse_uuid(s: &str) -> Result<uuid::Uuid, IdParseError> {
+    s.parse()
+        .map_err(|e: uuid::Error| IdParseError::InvalidUuid(e.to_string()))
+}
+
+fn parse_annotation_status(s: &str) -> AnnotationStatus {
+    match s {
+        "submitted" => AnnotationStatus::Submitted,
+        "approved" => AnnotationStatus::Approved,
+        "rejected" => AnnotationStatus::Rejected,
+        "superseded" => AnnotationStatus::Superseded,
+        "deleted" => AnnotationStatus::Deleted,
+        _ => AnnotationStatus::Draft,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use uuid::Uuid;
+
+    #[tokio::test]
+    #[ignore = "requires DATABASE_URL pointing at a migrated database"]
+    async fn test_schema_version_is_fixed_at_creation() {
+        let url = std::env::var("DATABASE_URL").expect("DATABASE_URL must be set");
+        let pool = PgPool::connect(&url).await.unwrap();
+        let repo = PgAnnotationRepository::new(pool.clone());
+
+        let user_id = UserId::new();
+        sqlx::query(
+            r#"
+            INSERT INTO users (user_id, email, display_name, auth0_id, global_role, status)
+            VALUES ($1, $2, 'Versioned', $3, 'user', 'active')
+            "#,
+        )
+        .bind(user_id.as_uuid())
+        .bind(format!("{user_id}@versioned.test"))
+        .bind(format!("test|{user_id}"))
+        .execute(&pool)
+        .await
+        .unwrap();
+        let project_type_id: Uuid = sqlx::query_scalar(
+            "INSERT INTO project_types (name) VALUES ($1) RETURNING project_type_id",
+        )
+        .bind(format!("Versioned {user_id}"))
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+        let project_id: Uuid = sqlx::query_scalar(
+            r#"
+            INSERT INTO projects (name, project_type_id, created_by)
+            VALUES ('Versioned', $1, $2) RETURNING project_id
+            "#,
+        )
+        .bind(project_type_id)
+        .bind(user_id.as_uuid())
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+
+        let new_annotation = || async {
+            let (task_id, assignment_id): (Uuid, Uuid) = sqlx::query_as(
+                r#"
+                WITH task AS (
+                    INSERT INTO tasks (project_id, input_data) VALUES ($1, '{}')
+                    RETURNING task_id
+                )
+                INSERT INTO task_assignments (task_id, project_id, step_id, user_id)
+                SELECT task_id, $1, 'annotate', $2 FROM task
+                RETURNING task_id, assignment_id
+                "#,
+            )
+            .bind(project_id)
+            .bind(user_id.as_uuid())
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+            repo.create(&NewAnnotation {
+                task_id: TaskId::from_uuid(task_id),
+                step_id: "annotate".to_string(),
+                user_id,
+                assignment_id: AssignmentId::from_uuid(assignment_id),
+                project_id: ProjectId::from_uuid(project_id),
+                data: serde_json::json!({"label": "a"}),
+            })
+            .await
+            .unwrap()
+        };
+
+        let first = new_annotation().await;
+        assert_eq!(first.schema_version, Some(1));
+
+        sqlx::query("UPDATE project_types SET schema_version = 2 WHERE project_type_id = $1")
+            .bind(project_type_id)
+            .execute(&pool)
+            .await
+            .unwrap();
+
+        // Editing a draft keeps the version it was created against
+        let edited = repo
+            .update(
+                &first.annotation_id,
+                &AnnotationUpdate {
+                    data: Some(serde_json::json!({"label": "b"})),
+                    status: None,
+                },
+            )
+            .await
+            .unwrap();
+        assert_eq!(edited.version, 2);
+        assert_eq!(edited.schema_version, Some(1));
+
+        assert_eq!(new_annotation().await.schema_version, Some(2));
+    }
+}
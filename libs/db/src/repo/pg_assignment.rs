@@ -1,5 +1,7 @@
 //! PostgreSQL implementation of AssignmentRepository
 
+use std::collections::HashMap;
+
 use async_trait::async_trait;
 use sqlx::PgPool;
 
@@ -17,12 +19,283 @@ pub struct PgAssignmentRepository {
     audit: AuditWriter,
 }
 
+/// Reason key for rejections recorded without a reason
+pub const UNSPECIFIED_REJECT_REASON: &str = "unspecified";
+
+/// Rejection counts for a project, each list sorted by count descending
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RejectionStats {
+    pub total: i64,
+    /// Counts keyed by the reason's `type` tag (e.g. `unclear_instructions`)
+    pub by_reason: Vec<(String, i64)>,
+    /// Counts keyed by the user who rejected the assignment
+    pub by_user: Vec<(UserId, i64)>,
+}
+
+impl RejectionStats {
+    fn from_rows(rows: Vec<RejectionStatsRow>) -> Self {
+        let mut stats = Self::default();
+        for row in rows {
+            match (row.reason, row.user_id) {
+                (Some(reason), None) => stats.by_reason.push((reason, row.count)),
+                (None, Some(user_id)) => {
+                    stats.by_user.push((UserId::from_uuid(user_id), row.count));
+                }
+                (None, None) => stats.total = row.count,
+                (Some(_), Some(_)) => {}
+            }
+        }
+        stats
+            .by_reason
+            .sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+        stats
+            .by_user
+            .sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.as_uuid().cmp(b.0.as_uuid())));
+        stats
+    }
+}
+
+/// One user's submission counts per day, oldest day first
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UserDailyThroughput {
+    pub user_id: UserId,
+    pub total: i64,
+    /// One entry per day in the requested range, zero on idle days
+    pub days: Vec<(chrono::NaiveDate, i64)>,
+}
+
+impl UserDailyThroughput {
+    /// Expand sparse `(user, day, count)` rows into a full `[from, to]` series
+    /// per user, busiest users first
+    fn from_rows(
+        from: chrono::NaiveDate,
+        to: chrono::NaiveDate,
+        rows: Vec<DailyThroughputRow>,
+    ) -> Vec<Self> {
+        let span: Vec<chrono::NaiveDate> = from.iter_days().take_while(|day| *day <= to).collect();
+        let mut by_user: HashMap<uuid::Uuid, HashMap<chrono::NaiveDate, i64>> = HashMap::new();
+        for row in rows {
+            *by_user
+                .entry(row.user_id)
+                .or_default()
+                .entry(row.day)
+                .or_default() += row.count;
+        }
+
+        let mut users: Vec<Self> = by_user
+            .into_iter()
+            .map(|(user_id, counts)| {
+                let days: Vec<_> = span
+                    .iter()
+                    .map(|day| (*day, counts.get(day).copied().unwrap_or(0)))
+                    .collect();
+                Self {
+                    user_id: UserId::from_uuid(user_id),
+                    total: days.iter().map(|(_, count)| count).sum(),
+                    days,
+                }
+            })
+            .collect();
+        users.sort_by(|a, b| {
+            b.total
+                .cmp(&a.total)
+                .then_with(|| a.user_id.as_uuid().cmp(b.user_id.as_uuid()))
+        });
+        users
+    }
+}
+
+/// What happened to one assignment in a bulk reject
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BulkRejectOutcome {
+    Rejected,
+    /// Skipped: no such assignment
+    NotFound,
+    /// Skipped: assigned to someone else
+    NotOwned,
+    /// Skipped: already submitted, rejected or otherwise finished
+    NotActive,
+}
+
+impl BulkRejectOutcome {
+    /// Outcome for an assignment with the given owner and status
+    fn classify(owner: &UserId, status: &str, user_id: &UserId) -> Self {
+        if owner != user_id {
+            Self::NotOwned
+        } else if matches!(status, "assigned" | "accepted" | "in_progress") {
+            Self::Rejected
+        } else {
+            Self::NotActive
+        }
+    }
+}
+
 impl PgAssignmentRepository {
     /// Create a new PostgreSQL assignment repository
     pub fn new(pool: PgPool) -> Self {
         let audit = AuditWriter::new(pool.clone());
         Self { pool, audit }
     }
+
+    /// Count a project's rejections by reason and by user, optionally limited
+    /// to those rejected in `[from, to)`
+    pub async fn rejection_stats(
+        &self,
+        project_id: &ProjectId,
+        from: Option<chrono::DateTime<chrono::Utc>>,
+        to: Option<chrono::DateTime<chrono::Utc>>,
+    ) -> Result<RejectionStats, sqlx::Error> {
+        let rows = sqlx::query_as::<_, RejectionStatsRow>(
+            r#"
+            SELECT reason, user_id, COUNT(*) AS count
+            FROM (
+                SELECT COALESCE(reject_reason->>'type', $4) AS reason, user_id
+                FROM task_assignments
+                WHERE project_id = $1
+                  AND status = 'rejected'
+                  AND ($2::timestamptz IS NULL OR rejected_at >= $2)
+                  AND ($3::timestamptz IS NULL OR rejected_at < $3)
+            ) rejections
+            GROUP BY GROUPING SETS ((reason), (user_id), ())
+            "#,
+        )
+        .bind(project_id.as_uuid())
+        .bind(from)
+        .bind(to)
+        .bind(UNSPECIFIED_REJECT_REASON)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(RejectionStats::from_rows(rows))
+    }
+
+    /// Count each user's submissions per UTC day over `[from, to]` (both
+    /// inclusive). Only users who submitted something in the range are listed.
+    pub async fn daily_throughput(
+        &self,
+        project_id: &ProjectId,
+        from: chrono::NaiveDate,
+        to: chrono::NaiveDate,
+    ) -> Result<Vec<UserDailyThroughput>, sqlx::Error> {
+        let rows = sqlx::query_as::<_, DailyThroughputRow>(
+            r#"
+            SELECT user_id, (submitted_at AT TIME ZONE 'UTC')::date AS day, COUNT(*) AS count
+            FROM task_assignments
+            WHERE project_id = $1
+              AND status = 'submitted'
+              AND submitted_at >= $2::date::timestamp AT TIME ZONE 'UTC'
+              AND submitted_at < ($3::date + 1)::timestamp AT TIME ZONE 'UTC'
+            GROUP BY 1, 2
+            "#,
+        )
+        .bind(project_id.as_uuid())
+        .bind(from)
+        .bind(to)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(UserDailyThroughput::from_rows(from, to, rows))
+    }
+
+    /// Reject a user's active assignments in one transaction, putting each
+    /// task on cooldown until `cooldown_until`.
+    ///
+    /// Assignments that don't exist, belong to someone else, or are no longer
+    /// active are skipped. Returns an outcome per requested ID, in order.
+    pub async fn reject_many_for_user(
+        &self,
+        user_id: &UserId,
+        assignment_ids: &[AssignmentId],
+        reason: &serde_json::Value,
+        cooldown_until: chrono::DateTime<chrono::Utc>,
+    ) -> Result<Vec<(AssignmentId, BulkRejectOutcome)>, sqlx::Error> {
+        let ids: Vec<uuid::Uuid> = assignment_ids.iter().map(|id| *id.as_uuid()).collect();
+        let mut tx = self.pool.begin().await?;
+
+        let rows = sqlx::query_as::<_, BulkRejectRow>(
+            r#"
+            SELECT assignment_id, task_id, user_id, status::text AS status
+            FROM task_assignments
+            WHERE assignment_id = ANY($1)
+            FOR UPDATE
+            "#,
+        )
+        .bind(&ids)
+        .fetch_all(&mut *tx)
+        .await?;
+
+        let outcomes: Vec<(AssignmentId, BulkRejectOutcome)> = assignment_ids
+            .iter()
+            .map(|id| {
+                let outcome = rows
+                    .iter()
+                    .find(|row| row.assignment_id == *id.as_uuid())
+                    .map_or(BulkRejectOutcome::NotFound, |row| {
+                        BulkRejectOutcome::classify(
+                            &UserId::from_uuid(row.user_id),
+                            &row.status,
+                            user_id,
+                        )
+                    });
+                (*id, outcome)
+            })
+            .collect();
+
+        let rejected: Vec<uuid::Uuid> = outcomes
+            .iter()
+            .filter(|(_, outcome)| *outcome == BulkRejectOutcome::Rejected)
+            .map(|(id, _)| *id.as_uuid())
+            .collect();
+        let task_ids: Vec<uuid::Uuid> = rows
+            .iter()
+            .filter(|row| rejected.contains(&row.assignment_id))
+            .map(|row| row.task_id)
+            .collect();
+
+        sqlx::query(
+            r#"
+            UPDATE task_assignments
+            SET status = 'rejected', reject_reason = $2, rejected_at = NOW()
+            WHERE assignment_id = ANY($1)
+            "#,
+        )
+        .bind(&rejected)
+        .bind(reason)
+        .execute(&mut *tx)
+        .await?;
+
+        sqlx::query(
+            "UPDATE tasks SET cooldown_until = $2, updated_at = NOW() WHERE task_id = ANY($1)",
+        )
+        .bind(&task_ids)
+        .bind(cooldown_until)
+        .execute(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+
+        for assignment_id in &rejected {
+            self.audit
+                .record_best_effort(AuditEvent {
+                    entity_type: "assignment",
+                    entity_id: assignment_id.to_string(),
+                    action: AuditAction::Update,
+                    actor_id: user_id.to_string(),
+                    actor_type: AuditActorType::User,
+                    data_snapshot: serde_json::json!({
+                        "status": "rejected",
+                        "reject_reason": reason
+                    }),
+                    changes: Some(serde_json::json!({
+                        "status": {"old": null, "new": "rejected"}
+                    })),
+                    request_id: None,
+                })
+                .await;
+        }
+
+        Ok(outcomes)
+    }
 }
 
 #[async_trait]
@@ -208,11 +481,12 @@ impl AssignmentRepository for PgAssignmentRepository {
         let result = sqlx::query(
             r#"
             UPDATE task_assignments
-            SET status = 'rejected'
+            SET status = 'rejected', reject_reason = $2, rejected_at = NOW()
             WHERE assignment_id = $1
             "#,
         )
         .bind(reject.assignment_id.as_uuid())
+        .bind(&reject.reason)
         .execute(&self.pool)
         .await
         .map_err(UpdateAssignmentError::Database)?;
@@ -285,6 +559,22 @@ impl AssignmentRepository for PgAssignmentRepository {
     }
 }
 
+// Grouping-set row: reason set, user set, or neither for the total
+#[derive(sqlx::FromRow)]
+struct RejectionStatsRow {
+    reason: Option<String>,
+    user_id: Option<uuid::Uuid>,
+    count: i64,
+}
+
+// Submissions by one user on one day
+#[derive(sqlx::FromRow)]
+struct DailyThroughputRow {
+    user_id: uuid::Uuid,
+    day: chrono::NaiveDate,
+    count: i64,
+}
+
 // Internal row type for SQLx mapping
 #[derive(sqlx::FromRow)]
 struct AssignmentRow {
@@ -350,3 +640,116 @@ fn parse_assignment_status(s: &str) -> AssignmentStatus {
         _ => AssignmentStatus::Assigned,
     }
 }
+
+#[derive(sqlx::FromRow)]
+struct BulkRejectRow {
+    assignment_id: uuid::Uuid,
+    task_id: uuid::Uuid,
+    user_id: uuid::Uuid,
+    status: String,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bulk_reject_skips_foreign_and_finished_assignments() {
+        let (owner, other) = (UserId::new(), UserId::new());
+        for status in ["assigned", "accepted", "in_progress"] {
+            assert_eq!(
+                BulkRejectOutcome::classify(&owner, status, &owner),
+                BulkRejectOutcome::Rejected
+            );
+        }
+        assert_eq!(
+            BulkRejectOutcome::classify(&owner, "assigned", &other),
+            BulkRejectOutcome::NotOwned
+        );
+        assert_eq!(
+            BulkRejectOutcome::classify(&owner, "submitted", &owner),
+            BulkRejectOutcome::NotActive
+        );
+    }
+
+    fn row(reason: Option<&str>, user_id: Option<uuid::Uuid>, count: i64) -> RejectionStatsRow {
+        RejectionStatsRow {
+            reason: reason.map(str::to_string),
+            user_id,
+            count,
+        }
+    }
+
+    #[test]
+    fn test_rejection_stats_split_grouping_sets() {
+        let alice = uuid::Uuid::new_v4();
+        let bob = uuid::Uuid::new_v4();
+        let stats = RejectionStats::from_rows(vec![
+            row(Some("missing_context"), None, 1),
+            row(Some("unclear_instructions"), None, 3),
+            row(None, Some(alice), 1),
+            row(None, Some(bob), 3),
+            row(None, None, 4),
+        ]);
+
+        assert_eq!(stats.total, 4);
+        assert_eq!(
+            stats.by_reason,
+            vec![
+                ("unclear_instructions".to_string(), 3),
+                ("missing_context".to_string(), 1),
+            ]
+        );
+        assert_eq!(
+            stats.by_user,
+            vec![(UserId::from_uuid(bob), 3), (UserId::from_uuid(alice), 1)]
+        );
+    }
+
+    #[test]
+    fn test_daily_throughput_zero_fills_idle_days() {
+        let (alice, bob) = (uuid::Uuid::new_v4(), uuid::Uuid::new_v4());
+        let day = |d| chrono::NaiveDate::from_ymd_opt(2026, 3, d).unwrap();
+        let rows = vec![
+            DailyThroughputRow {
+                user_id: alice,
+                day: day(1),
+                count: 2,
+            },
+            DailyThroughputRow {
+                user_id: bob,
+                day: day(1),
+                count: 1,
+            },
+            DailyThroughputRow {
+                user_id: bob,
+                day: day(2),
+                count: 4,
+            },
+        ];
+
+        let throughput = UserDailyThroughput::from_rows(day(1), day(2), rows);
+
+        assert_eq!(
+            throughput,
+            vec![
+                UserDailyThroughput {
+                    user_id: UserId::from_uuid(bob),
+                    total: 5,
+                    days: vec![(day(1), 1), (day(2), 4)],
+                },
+                UserDailyThroughput {
+                    user_id: UserId::from_uuid(alice),
+                    total: 2,
+                    days: vec![(day(1), 2), (day(2), 0)],
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_rejection_stats_empty_project() {
+        let stats = RejectionStats::from_rows(vec![row(None, None, 0)]);
+        assert_eq!(stats, RejectionStats::default());
+    }
+}
@@ -6,11 +6,12 @@ use sqlx::{FromRow, PgPool};
 use uuid::Uuid;
 
 use glyph_domain::{
-    CreateDataSource, DataSource, DataSourceFilter, DataSourceId, DataSourceType, ProjectId,
+    CreateDataSource, DataSource, DataSourceFilter, DataSourceId, DataSourceType, OrgId, ProjectId,
     UpdateDataSource, ValidationMode,
 };
 
 use super::errors::*;
+use super::pg_task::{live_task_total, PgTaskRepository};
 
 // =============================================================================
 // Repository Trait
@@ -53,6 +54,20 @@ pub trait DataSourceRepository: Send + Sync {
         item_count: i32,
         error_count: i32,
     ) -> Result<(), UpdateDataSourceError>;
+
+    /// Count a project's data sources and imported tasks
+    async fn readiness(&self, project_id: &ProjectId) -> Result<DataSourceReadiness, sqlx::Error>;
+}
+
+/// What a project has loaded so far, for activation checks
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct DataSourceReadiness {
+    /// All data sources, active or not
+    pub source_count: i64,
+    /// Active sources that have synced at least one item
+    pub active_with_items: i64,
+    /// Tasks imported into the project, excluding cancelled and deleted ones
+    pub task_count: i64,
 }
 
 // =============================================================================
@@ -73,6 +88,7 @@ struct DataSourceRow {
     is_active: bool,
     created_at: DateTime<Utc>,
     updated_at: DateTime<Utc>,
+    org_id: Uuid,
 }
 
 // =============================================================================
@@ -82,11 +98,25 @@ struct DataSourceRow {
 /// PostgreSQL-backed data source repository
 pub struct PgDataSourceRepository {
     pool: PgPool,
+    /// Organization every query is limited to; `None` sees all of them
+    org_id: Option<OrgId>,
 }
 
 impl PgDataSourceRepository {
     pub fn new(pool: PgPool) -> Self {
-        Self { pool }
+        Self { pool, org_id: None }
+    }
+
+    /// Limit lookups, lists and changes to one organization's data sources.
+    /// A data source belongs to its project's organization.
+    #[must_use]
+    pub fn in_org(mut self, org_id: OrgId) -> Self {
+        self.org_id = Some(org_id);
+        self
+    }
+
+    fn org_filter(&self) -> Option<Uuid> {
+        self.org_id.map(OrgId::into_uuid)
     }
 
     fn row_to_data_source(&self, row: DataSourceRow) -> DataSource {
@@ -104,6 +134,7 @@ impl PgDataSourceRepository {
             is_active: row.is_active,
             created_at: row.created_at,
             updated_at: row.updated_at,
+            org_id: OrgId::from_uuid(row.org_id),
         }
     }
 }
@@ -121,17 +152,20 @@ impl DataSourceRepository for PgDataSourceRepository {
         let validation_mode =
             format_validation_mode(input.validation_mode.unwrap_or(ValidationMode::Strict));
 
-        let row: DataSourceRow = sqlx::query_as(
+        // The project must be visible to the caller; the source takes its org
+        let row: Option<DataSourceRow> = sqlx::query_as(
             r#"
             INSERT INTO data_sources (
                 data_source_id, project_id, name, source_type, config,
-                validation_mode, is_active
+                validation_mode, is_active, org_id
             )
-            VALUES ($1, $2, $3, $4::data_source_type, $5, $6::validation_mode, true)
+            SELECT $1, $2, $3, $4::data_source_type, $5, $6::validation_mode, true, p.org_id
+            FROM projects p
+            WHERE p.project_id = $2 AND ($7::uuid IS NULL OR p.org_id = $7)
             RETURNING
                 data_source_id, project_id, name, source_type::text, config,
                 validation_mode::text, last_sync_at, item_count, error_count,
-                is_active, created_at, updated_at
+                is_active, created_at, updated_at, org_id
             "#,
         )
         .bind(id.as_uuid())
@@ -140,7 +174,8 @@ impl DataSourceRepository for PgDataSourceRepository {
         .bind(&source_type)
         .bind(&config)
         .bind(&validation_mode)
-        .fetch_one(&self.pool)
+        .bind(self.org_filter())
+        .fetch_optional(&self.pool)
         .await
         .map_err(|e| {
             if is_unique_violation(&e) {
@@ -151,6 +186,7 @@ impl DataSourceRepository for PgDataSourceRepository {
                 CreateDataSourceError::Database(e)
             }
         })?;
+        let row = row.ok_or_else(|| CreateDataSourceError::ProjectNotFound(project_id.clone()))?;
 
         Ok(self.row_to_data_source(row))
     }
@@ -164,12 +200,13 @@ impl DataSourceRepository for PgDataSourceRepository {
             SELECT
                 data_source_id, project_id, name, source_type::text, config,
                 validation_mode::text, last_sync_at, item_count, error_count,
-                is_active, created_at, updated_at
+                is_active, created_at, updated_at, org_id
             FROM data_sources
-            WHERE data_source_id = $1
+            WHERE data_source_id = $1 AND ($2::uuid IS NULL OR org_id = $2)
             "#,
         )
         .bind(id.as_uuid())
+        .bind(self.org_filter())
         .fetch_optional(&self.pool)
         .await
         .map_err(FindDataSourceError::Database)?;
@@ -186,17 +223,19 @@ impl DataSourceRepository for PgDataSourceRepository {
             SELECT
                 data_source_id, project_id, name, source_type::text, config,
                 validation_mode::text, last_sync_at, item_count, error_count,
-                is_active, created_at, updated_at
+                is_active, created_at, updated_at, org_id
             FROM data_sources
             WHERE ($1::uuid IS NULL OR project_id = $1)
               AND ($2::text IS NULL OR source_type::text = $2)
               AND ($3::bool IS NULL OR is_active = $3)
+              AND ($4::uuid IS NULL OR org_id = $4)
             ORDER BY created_at DESC
             "#,
         )
         .bind(filter.project_id.as_ref().map(|p| *p.as_uuid()))
         .bind(filter.source_type.map(format_source_type))
         .bind(filter.is_active)
+        .bind(self.org_filter())
         .fetch_all(&self.pool)
         .await
         .map_err(FindDataSourceError::Database)?;
@@ -227,11 +266,11 @@ impl DataSourceRepository for PgDataSourceRepository {
                 validation_mode = COALESCE($4::validation_mode, validation_mode),
                 is_active = COALESCE($5, is_active),
                 updated_at = NOW()
-            WHERE data_source_id = $1
+            WHERE data_source_id = $1 AND ($6::uuid IS NULL OR org_id = $6)
             RETURNING
                 data_source_id, project_id, name, source_type::text, config,
                 validation_mode::text, last_sync_at, item_count, error_count,
-                is_active, created_at, updated_at
+                is_active, created_at, updated_at, org_id
             "#,
         )
         .bind(id.as_uuid())
@@ -239,6 +278,7 @@ impl DataSourceRepository for PgDataSourceRepository {
         .bind(&config)
         .bind(&validation_mode)
         .bind(update.is_active)
+        .bind(self.org_filter())
         .fetch_optional(&self.pool)
         .await
         .map_err(|e| {
@@ -257,11 +297,17 @@ impl DataSourceRepository for PgDataSourceRepository {
     }
 
     async fn delete(&self, id: &DataSourceId) -> Result<(), DeleteDataSourceError> {
-        let result = sqlx::query(r#"DELETE FROM data_sources WHERE data_source_id = $1"#)
-            .bind(id.as_uuid())
-            .execute(&self.pool)
-            .await
-            .map_err(DeleteDataSourceError::Database)?;
+        let result = sqlx::query(
+            r#"
+            DELETE FROM data_sources
+            WHERE data_source_id = $1 AND ($2::uuid IS NULL OR org_id = $2)
+            "#,
+        )
+        .bind(id.as_uuid())
+        .bind(self.org_filter())
+        .execute(&self.pool)
+        .await
+        .map_err(DeleteDataSourceError::Database)?;
 
         if result.rows_affected() == 0 {
             return Err(DeleteDataSourceError::NotFound(id.clone()));
@@ -284,12 +330,13 @@ impl DataSourceRepository for PgDataSourceRepository {
                 error_count = $3,
                 last_sync_at = NOW(),
                 updated_at = NOW()
-            WHERE data_source_id = $1
+            WHERE data_source_id = $1 AND ($4::uuid IS NULL OR org_id = $4)
             "#,
         )
         .bind(id.as_uuid())
         .bind(item_count)
         .bind(error_count)
+        .bind(self.org_filter())
         .execute(&self.pool)
         .await
         .map_err(UpdateDataSourceError::Database)?;
@@ -300,6 +347,30 @@ impl DataSourceRepository for PgDataSourceRepository {
 
         Ok(())
     }
+
+    async fn readiness(&self, project_id: &ProjectId) -> Result<DataSourceReadiness, sqlx::Error> {
+        let (source_count, active_with_items) = sqlx::query_as::<_, (i64, i64)>(
+            r#"
+            SELECT
+                (SELECT COUNT(*) FROM data_sources WHERE project_id = $1),
+                (SELECT COUNT(*) FROM data_sources
+                 WHERE project_id = $1 AND is_active AND COALESCE(item_count, 0) > 0)
+            "#,
+        )
+        .bind(project_id.as_uuid())
+        .fetch_one(&self.pool)
+        .await?;
+
+        let counts = PgTaskRepository::new(self.pool.clone())
+            .count_by_status(project_id)
+            .await?;
+
+        Ok(DataSourceReadiness {
+            source_count,
+            active_with_items,
+            task_count: live_task_total(&counts),
+        })
+    }
 }
 
 // =============================================================================
@@ -0,0 +1,310 @@
+//! PostgreSQL dead-letter queue for worker jobs
+//!
+//! Jobs that exhaust their retries are recorded here with their payload and
+//! last error. Requeuing a row flags it for the worker, which re-runs it and
+//! marks it `retried`, or back to `dead` if it fails again. The row stays
+//! `requeued` and locked while the retry runs, so a worker that dies mid-retry
+//! leaves it for the next one.
+
+use chrono::{DateTime, Utc};
+use sqlx::{PgPool, Postgres, Transaction};
+use uuid::Uuid;
+
+/// Job type for notifications consumed from NATS
+pub const DEAD_LETTER_NOTIFICATION: &str = "notification";
+
+/// Job type for webhook deliveries; the payload holds the `delivery_id`
+pub const DEAD_LETTER_WEBHOOK_DELIVERY: &str = "webhook_delivery";
+
+/// Job type for assignment requests consumed from NATS
+pub const DEAD_LETTER_ASSIGNMENT: &str = "assignment";
+
+/// Where a dead-lettered job stands
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DeadLetterStatus {
+    /// Failed all retries and is waiting for an operator
+    Dead,
+    /// Requeued by an operator; the worker will pick it up
+    Requeued,
+    /// Re-run successfully after being requeued
+    Retried,
+}
+
+impl DeadLetterStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Dead => "dead",
+            Self::Requeued => "requeued",
+            Self::Retried => "retried",
+        }
+    }
+
+    pub fn from_str(s: &str) -> Option<Self> {
+        match s {
+            "dead" => Some(Self::Dead),
+            "requeued" => Some(Self::Requeued),
+            "retried" => Some(Self::Retried),
+            _ => None,
+        }
+    }
+}
+
+/// A job that exhausted its retries
+#[derive(Debug, Clone)]
+pub struct DeadLetter {
+    pub dlq_id: Uuid,
+    pub job_type: String,
+    pub payload: serde_json::Value,
+    pub last_error: String,
+    /// Total attempts across the original run and any retries
+    pub attempts: i32,
+    pub status: DeadLetterStatus,
+    pub created_at: DateTime<Utc>,
+    pub requeued_at: Option<DateTime<Utc>>,
+    pub retried_at: Option<DateTime<Utc>>,
+}
+
+/// PostgreSQL dead-letter repository
+pub struct PgDeadLetterRepository {
+    pool: PgPool,
+}
+
+impl PgDeadLetterRepository {
+    /// Create a new PostgreSQL dead-letter repository
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    /// Record a job that failed all retries
+    pub async fn record(
+        &self,
+        job_type: &str,
+        payload: &serde_json::Value,
+        last_error: &str,
+        attempts: i32,
+    ) -> Result<Uuid, sqlx::Error> {
+        sqlx::query_scalar::<_, Uuid>(
+            r#"
+            INSERT INTO dead_letter_jobs (job_type, payload, last_error, attempts)
+            VALUES ($1, $2, $3, $4)
+            RETURNING dlq_id
+            "#,
+        )
+        .bind(job_type)
+        .bind(payload)
+        .bind(last_error)
+        .bind(attempts)
+        .fetch_one(&self.pool)
+        .await
+    }
+
+    /// List dead letters, newest first, optionally filtered by status
+    pub async fn list(
+        &self,
+        status: Option<DeadLetterStatus>,
+        limit: i64,
+    ) -> Result<Vec<DeadLetter>, sqlx::Error> {
+        let rows = sqlx::query_as::<_, DeadLetterRow>(
+            r#"
+            SELECT dlq_id, job_type, payload, last_error, attempts, status,
+                   created_at, requeued_at, retried_at
+            FROM dead_letter_jobs
+            WHERE $1::text IS NULL OR status = $1
+            ORDER BY created_at DESC
+            LIMIT $2
+            "#,
+        )
+        .bind(status.map(|s| s.as_str()))
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(rows.into_iter().map(Into::into).collect())
+    }
+
+    /// Find a dead letter by ID
+    pub async fn find(&self, dlq_id: Uuid) -> Result<Option<DeadLetter>, sqlx::Error> {
+        let row = sqlx::query_as::<_, DeadLetterRow>(
+            r#"
+            SELECT dlq_id, job_type, payload, last_error, attempts, status,
+                   created_at, requeued_at, retried_at
+            FROM dead_letter_jobs
+            WHERE dlq_id = $1
+            "#,
+        )
+        .bind(dlq_id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(row.map(Into::into))
+    }
+
+    /// Flag a dead job for the worker to retry.
+    ///
+    /// Returns `false` if the job doesn't exist or isn't dead.
+    pub async fn requeue(&self, dlq_id: Uuid) -> Result<bool, sqlx::Error> {
+        let result = sqlx::query(
+            r#"
+            UPDATE dead_letter_jobs
+            SET status = 'requeued', requeued_at = NOW()
+            WHERE dlq_id = $1 AND status = 'dead'
+            "#,
+        )
+        .bind(dlq_id)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Claim the oldest requeued job for retry inside the caller's
+    /// transaction.
+    ///
+    /// The row is locked rather than updated, so concurrent workers skip it
+    /// and it is only marked once the retry's outcome commits with
+    /// [`Self::mark_retried`] or [`Self::mark_dead`].
+    pub async fn claim_requeued(
+        &self,
+        tx: &mut Transaction<'_, Postgres>,
+    ) -> Result<Option<DeadLetter>, sqlx::Error> {
+        let row = sqlx::query_as::<_, DeadLetterRow>(
+            r#"
+            SELECT dlq_id, job_type, payload, last_error, attempts, status,
+                   created_at, requeued_at, retried_at
+            FROM dead_letter_jobs
+            WHERE status = 'requeued'
+            ORDER BY requeued_at
+            LIMIT 1
+            FOR UPDATE SKIP LOCKED
+            "#,
+        )
+        .fetch_optional(&mut **tx)
+        .await?;
+
+        Ok(row.map(Into::into))
+    }
+
+    /// Mark a claimed job as successfully re-run
+    pub async fn mark_retried(
+        &self,
+        tx: &mut Transaction<'_, Postgres>,
+        dlq_id: Uuid,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query(
+            r#"
+            UPDATE dead_letter_jobs
+            SET status = 'retried', retried_at = NOW()
+            WHERE dlq_id = $1
+            "#,
+        )
+        .bind(dlq_id)
+        .execute(&mut **tx)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Return a claimed job whose retry failed to the dead state, replacing
+    /// its payload when the retry finished part of the work
+    pub async fn mark_dead(
+        &self,
+        tx: &mut Transaction<'_, Postgres>,
+        dlq_id: Uuid,
+        last_error: &str,
+        attempts: i32,
+        remaining_payload: Option<&serde_json::Value>,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query(
+            r#"
+            UPDATE dead_letter_jobs
+            SET status = 'dead', last_error = $2, attempts = attempts + $3,
+                payload = COALESCE($4, payload)
+            WHERE dlq_id = $1
+            "#,
+        )
+        .bind(dlq_id)
+        .bind(last_error)
+        .bind(attempts)
+        .bind(remaining_payload)
+        .execute(&mut **tx)
+        .await?;
+
+        Ok(())
+    }
+}
+
+// =============================================================================
+// Internal row types for SQLx mapping
+// =============================================================================
+
+#[derive(sqlx::FromRow)]
+struct DeadLetterRow {
+    dlq_id: Uuid,
+    job_type: String,
+    payload: serde_json::Value,
+    last_error: String,
+    attempts: i32,
+    status: String,
+    created_at: DateTime<Utc>,
+    requeued_at: Option<DateTime<Utc>>,
+    retried_at: Option<DateTime<Utc>>,
+}
+
+impl From<DeadLetterRow> for DeadLetter {
+    fn from(row: DeadLetterRow) -> Self {
+        Self {
+            dlq_id: row.dlq_id,
+            job_type: row.job_type,
+            payload: row.payload,
+            last_error: row.last_error,
+            attempts: row.attempts,
+            status: DeadLetterStatus::from_str(&row.status).unwrap_or(DeadLetterStatus::Dead),
+            created_at: row.created_at,
+            requeued_at: row.requeued_at,
+            retried_at: row.retried_at,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    #[ignore = "requires DATABASE_URL pointing at a migrated database"]
+    async fn test_claimed_job_stays_requeued_until_the_retry_commits() {
+        let url = std::env::var("DATABASE_URL").expect("DATABASE_URL must be set");
+        let pool = PgPool::connect(&url).await.unwrap();
+        let repo = PgDeadLetterRepository::new(pool.clone());
+
+        // Drain anything other tests left requeued so this job is the oldest
+        sqlx::query("UPDATE dead_letter_jobs SET status = 'dead' WHERE status = 'requeued'")
+            .execute(&pool)
+            .await
+            .unwrap();
+        let dlq_id = repo
+            .record("test", &serde_json::json!({}), "boom", 3)
+            .await
+            .unwrap();
+        assert!(repo.requeue(dlq_id).await.unwrap());
+
+        // A worker that dies mid-retry rolls back and leaves the job requeued
+        let mut tx = pool.begin().await.unwrap();
+        let claimed = repo.claim_requeued(&mut tx).await.unwrap().unwrap();
+        assert_eq!(claimed.dlq_id, dlq_id);
+        let mut other = pool.begin().await.unwrap();
+        assert!(repo.claim_requeued(&mut other).await.unwrap().is_none());
+        other.rollback().await.unwrap();
+        tx.rollback().await.unwrap();
+        let job = repo.find(dlq_id).await.unwrap().unwrap();
+        assert_eq!(job.status, DeadLetterStatus::Requeued);
+
+        let mut tx = pool.begin().await.unwrap();
+        let claimed = repo.claim_requeued(&mut tx).await.unwrap().unwrap();
+        repo.mark_retried(&mut tx, claimed.dlq_id).await.unwrap();
+        tx.commit().await.unwrap();
+        let job = repo.find(dlq_id).await.unwrap().unwrap();
+        assert_eq!(job.status, DeadLetterStatus::Retried);
+        assert!(job.retried_at.is_some());
+    }
+}
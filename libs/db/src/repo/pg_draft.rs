@@ -0,0 +1,249 @@
+//! PostgreSQL annotation draft repository
+//!
+//! A draft belongs to an active assignment: saving needs one, and the row is
+//! replaced in place on every save so each assignment keeps a single draft.
+
+use chrono::{DateTime, Utc};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use glyph_domain::{Draft, DraftId, TaskId, UserId};
+
+/// PostgreSQL draft repository
+pub struct PgDraftRepository {
+    pool: PgPool,
+}
+
+impl PgDraftRepository {
+    /// Create a new PostgreSQL draft repository
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    /// Save progress on the user's active assignment for a task step, or
+    /// their most recent active assignment on the task when no step is given.
+    ///
+    /// Returns `None` when the user has no such active assignment.
+    pub async fn save(
+        &self,
+        task_id: &TaskId,
+        step_id: Option<&str>,
+        user_id: &UserId,
+        data: &serde_json::Value,
+    ) -> Result<Option<Draft>, sqlx::Error> {
+        let row = sqlx::query_as::<_, DraftRow>(
+            r#"
+            INSERT INTO annotation_drafts (assignment_id, task_id, step_id, user_id, data)
+            SELECT assignment_id, task_id, step_id, user_id, $4
+            FROM task_assignments
+            WHERE task_id = $1
+              AND ($2::VARCHAR IS NULL OR step_id = $2)
+              AND user_id = $3
+              AND status IN ('assigned', 'accepted', 'in_progress')
+            ORDER BY assigned_at DESC
+            LIMIT 1
+            ON CONFLICT (assignment_id) DO UPDATE
+            SET data = EXCLUDED.data,
+                version = annotation_drafts.version + 1,
+                updated_at = NOW()
+            RETURNING draft_id, task_id, step_id, user_id, data, version, created_at, updated_at
+            "#,
+        )
+        .bind(task_id.as_uuid())
+        .bind(step_id)
+        .bind(user_id.as_uuid())
+        .bind(data)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(row.map(Into::into))
+    }
+
+    /// Find the user's most recently saved draft for a task, optionally
+    /// limited to one step
+    pub async fn find(
+        &self,
+        task_id: &TaskId,
+        user_id: &UserId,
+        step_id: Option<&str>,
+    ) -> Result<Option<Draft>, sqlx::Error> {
+        let row = sqlx::query_as::<_, DraftRow>(
+            r#"
+            SELECT draft_id, task_id, step_id, user_id, data, version, created_at, updated_at
+            FROM annotation_drafts
+            WHERE task_id = $1
+              AND user_id = $2
+              AND ($3::VARCHAR IS NULL OR step_id = $3)
+            ORDER BY updated_at DESC
+            LIMIT 1
+            "#,
+        )
+        .bind(task_id.as_uuid())
+        .bind(user_id.as_uuid())
+        .bind(step_id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(row.map(Into::into))
+    }
+
+    /// Delete the user's drafts for a task, optionally limited to one step.
+    /// Returns the number of drafts removed.
+    pub async fn delete(
+        &self,
+        task_id: &TaskId,
+        user_id: &UserId,
+        step_id: Option<&str>,
+    ) -> Result<u64, sqlx::Error> {
+        let result = sqlx::query(
+            r#"
+            DELETE FROM annotation_drafts
+            WHERE task_id = $1
+              AND user_id = $2
+              AND ($3::VARCHAR IS NULL OR step_id = $3)
+            "#,
+        )
+        .bind(task_id.as_uuid())
+        .bind(user_id.as_uuid())
+        .bind(step_id)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.rows_affected())
+    }
+}
+
+#[derive(sqlx::FromRow)]
+struct DraftRow {
+    draft_id: Uuid,
+    task_id: Uuid,
+    step_id: String,
+    user_id: Uuid,
+    data: serde_json::Value,
+    version: i32,
+    created_at: DateTime<Utc>,
+    updated_at: DateTime<Utc>,
+}
+
+impl From<DraftRow> for Draft {
+    fn from(row: DraftRow) -> Self {
+        Self {
+            draft_id: DraftId::from_uuid(row.draft_id),
+            task_id: TaskId::from_uuid(row.task_id),
+            step_id: row.step_id,
+            user_id: UserId::from_uuid(row.user_id),
+            data: row.data,
+            version: row.version,
+            created_at: row.created_at,
+            updated_at: row.updated_at,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    #[ignore = "requires DATABASE_URL pointing at a migrated database"]
+    async fn test_drafts_need_an_active_assignment_and_replace_in_place() {
+        let url = std::env::var("DATABASE_URL").expect("DATABASE_URL must be set");
+        let pool = PgPool::connect(&url).await.unwrap();
+        let repo = PgDraftRepository::new(pool.clone());
+
+        let user_id = UserId::new();
+        sqlx::query(
+            r#"
+            INSERT INTO users (user_id, email, display_name, auth0_id, global_role, status)
+            VALUES ($1, $2, 'Drafter', $3, 'user', 'active')
+            "#,
+        )
+        .bind(user_id.as_uuid())
+        .bind(format!("{user_id}@drafts.test"))
+        .bind(format!("test|{user_id}"))
+        .execute(&pool)
+        .await
+        .unwrap();
+        let project_id: Uuid = sqlx::query_scalar(
+            r#"
+            WITH pt AS (
+                INSERT INTO project_types (name) VALUES ($1) RETURNING project_type_id
+            )
+            INSERT INTO projects (name, project_type_id, created_by)
+            SELECT 'Drafts', project_type_id, $2 FROM pt
+            RETURNING project_id
+            "#,
+        )
+        .bind(format!("Drafts {}", Uuid::new_v4()))
+        .bind(user_id.as_uuid())
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+        let task_id: Uuid = sqlx::query_scalar(
+            "INSERT INTO tasks (project_id, input_data) VALUES ($1, '{}') RETURNING task_id",
+        )
+        .bind(project_id)
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+        let task_id = TaskId::from_uuid(task_id);
+
+        let saved = repo
+            .save(
+                &task_id,
+                Some("annotate"),
+                &user_id,
+                &serde_json::json!({"n": 1}),
+            )
+            .await
+            .unwrap();
+        assert!(saved.is_none(), "no assignment, no draft");
+
+        sqlx::query(
+            r#"
+            INSERT INTO task_assignments (task_id, project_id, step_id, user_id)
+            VALUES ($1, $2, 'annotate', $3)
+            "#,
+        )
+        .bind(task_id.as_uuid())
+        .bind(project_id)
+        .bind(user_id.as_uuid())
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        let first = repo
+            .save(
+                &task_id,
+                Some("annotate"),
+                &user_id,
+                &serde_json::json!({"n": 1}),
+            )
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(first.version, 1);
+        let second = repo
+            .save(&task_id, None, &user_id, &serde_json::json!({"n": 2}))
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(second.draft_id, first.draft_id);
+        assert_eq!(second.version, 2);
+
+        let found = repo
+            .find(&task_id, &user_id, Some("annotate"))
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(found.data, serde_json::json!({"n": 2}));
+        assert!(repo
+            .find(&task_id, &user_id, Some("review"))
+            .await
+            .unwrap()
+            .is_none());
+
+        assert_eq!(repo.delete(&task_id, &user_id, None).await.unwrap(), 1);
+        assert!(repo.find(&task_id, &user_id, None).await.unwrap().is_none());
+    }
+}
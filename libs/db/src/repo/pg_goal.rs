@@ -0,0 +1,345 @@
+//! PostgreSQL goal progress
+//!
+//! Volume, deadline and quality goals have their `current_value` recomputed
+//! from the project's tasks and annotations by [`PgGoalRepository::refresh_progress`],
+//! which runs whenever a task completes; the first refresh that finds a goal
+//! at its target stamps `completion_action_fired_at`. Duration and composite
+//! goals keep whatever value was last written, and manual goals are completed
+//! by hand.
+
+use chrono::{DateTime, Utc};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use glyph_domain::{GoalType, ProjectId};
+
+/// A goal's stored progress
+#[derive(Debug, Clone)]
+pub struct GoalSnapshot {
+    pub goal_id: Uuid,
+    pub name: String,
+    pub goal_type: GoalType,
+    pub target_value: f64,
+    pub current_value: f64,
+    pub deadline: Option<DateTime<Utc>>,
+    /// Completion state set by hand; only meaningful for manual goals
+    pub manually_completed: bool,
+    /// When the completion action ran, if it has
+    pub completion_action_fired_at: Option<DateTime<Utc>>,
+}
+
+/// PostgreSQL goal repository
+pub struct PgGoalRepository {
+    pool: PgPool,
+}
+
+impl PgGoalRepository {
+    /// Create a new PostgreSQL goal repository
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    /// List a project's goals, oldest first
+    pub async fn list_for_project(
+        &self,
+        project_id: &ProjectId,
+    ) -> Result<Vec<GoalSnapshot>, sqlx::Error> {
+        let rows = sqlx::query_as::<_, GoalRow>(
+            r#"
+            SELECT goal_id, name, goal_type::text, target_value, current_value,
+                   deadline, manually_completed, completion_action_fired_at
+            FROM goals
+            WHERE project_id = $1
+            ORDER BY created_at, goal_id
+            "#,
+        )
+        .bind(project_id.as_uuid())
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(rows.into_iter().map(Into::into).collect())
+    }
+
+    /// Find one of a project's goals
+    pub async fn find(
+        &self,
+        project_id: &ProjectId,
+        goal_id: Uuid,
+    ) -> Result<Option<GoalSnapshot>, sqlx::Error> {
+        let row = sqlx::query_as::<_, GoalRow>(
+            r#"
+            SELECT goal_id, name, goal_type::text, target_value, current_value,
+                   deadline, manually_completed, completion_action_fired_at
+            FROM goals
+            WHERE project_id = $1 AND goal_id = $2
+            "#,
+        )
+        .bind(project_id.as_uuid())
+        .bind(goal_id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(row.map(Into::into))
+    }
+
+    /// Recompute the progress of a project's volume, deadline and quality
+    /// goals, returning the goals that reached their target for the first
+    /// time.
+    ///
+    /// Volume and deadline goals count completed tasks; quality goals average
+    /// the quality scores of the project's annotations.
+    pub async fn refresh_progress(&self, project_id: &ProjectId) -> Result<Vec<Uuid>, sqlx::Error> {
+        sqlx::query_scalar::<_, Uuid>(
+            r#"
+            WITH progress AS (
+                SELECT g.goal_id,
+                       CASE g.goal_type
+                           WHEN 'quality' THEN COALESCE((
+                               SELECT AVG(a.quality_score)
+                               FROM annotations a
+                               WHERE a.project_id = g.project_id
+                                 AND a.quality_score IS NOT NULL
+                           ), 0)
+                           ELSE (
+                               SELECT COUNT(*)
+                               FROM tasks t
+                               WHERE t.project_id = g.project_id AND t.status = 'completed'
+                           )::DOUBLE PRECISION
+                       END AS value,
+                       g.completion_action_fired_at IS NULL AS pending
+                FROM goals g
+                WHERE g.project_id = $1
+                  AND g.goal_type IN ('volume', 'deadline', 'quality')
+                FOR UPDATE OF g
+            ),
+            updated AS (
+                UPDATE goals g
+                SET current_value = p.value,
+                    completion_action_fired_at = CASE
+                        WHEN p.pending AND p.value >= g.target_value THEN NOW()
+                        ELSE g.completion_action_fired_at
+                    END
+                FROM progress p
+                WHERE g.goal_id = p.goal_id
+                RETURNING g.goal_id, p.pending AND p.value >= g.target_value AS reached
+            )
+            SELECT goal_id FROM updated WHERE reached
+            "#,
+        )
+        .bind(project_id.as_uuid())
+        .fetch_all(&self.pool)
+        .await
+    }
+
+    /// Set a goal's manual completion state, returning the updated goal.
+    ///
+    /// Returns `None` if the project has no such goal.
+    pub async fn set_manually_completed(
+        &self,
+        project_id: &ProjectId,
+        goal_id: Uuid,
+        completed: bool,
+    ) -> Result<Option<GoalSnapshot>, sqlx::Error> {
+        let row = sqlx::query_as::<_, GoalRow>(
+            r#"
+            UPDATE goals
+            SET manually_completed = $3
+            WHERE project_id = $1 AND goal_id = $2
+            RETURNING goal_id, name, goal_type::text, target_value, current_value,
+                      deadline, manually_completed, completion_action_fired_at
+            "#,
+        )
+        .bind(project_id.as_uuid())
+        .bind(goal_id)
+        .bind(completed)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(row.map(Into::into))
+    }
+}
+
+// =============================================================================
+// Internal row types for SQLx mapping
+// =============================================================================
+
+#[derive(sqlx::FromRow)]
+struct GoalRow {
+    goal_id: Uuid,
+    name: String,
+    goal_type: String,
+    target_value: f64,
+    current_value: f64,
+    deadline: Option<DateTime<Utc>>,
+    manually_completed: bool,
+    completion_action_fired_at: Option<DateTime<Utc>>,
+}
+
+impl From<GoalRow> for GoalSnapshot {
+    fn from(row: GoalRow) -> Self {
+        Self {
+            goal_id: row.goal_id,
+            name: row.name,
+            goal_type: parse_goal_type(&row.goal_type),
+            target_value: row.target_value,
+            current_value: row.current_value,
+            deadline: row.deadline,
+            manually_completed: row.manually_completed,
+            completion_action_fired_at: row.completion_action_fired_at,
+        }
+    }
+}
+
+fn parse_goal_type(s: &str) -> GoalType {
+    match s {
+        "quality" => GoalType::Quality,
+        "deadline" => GoalType::Deadline,
+        "duration" => GoalType::Duration,
+        "composite" => GoalType::Composite,
+        "manual" => GoalType::Manual,
+        _ => GoalType::Volume,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use glyph_domain::UserId;
+
+    use super::*;
+    use crate::PgProjectRepository;
+
+    /// Create a project with one manual goal, returning both IDs
+    async fn seed_manual_goal(pool: &PgPool) -> (ProjectId, Uuid) {
+        let user_id = UserId::new();
+        sqlx::query(
+            r#"
+            INSERT INTO users (user_id, email, display_name, auth0_id, global_role, status)
+            VALUES ($1, $2, 'Goal Test', $3, 'user', 'active')
+            "#,
+        )
+        .bind(user_id.as_uuid())
+        .bind(format!("{user_id}@goal.test"))
+        .bind(format!("test|{user_id}"))
+        .execute(pool)
+        .await
+        .unwrap();
+
+        let project = PgProjectRepository::new(pool.clone())
+            .create_minimal("Goals", None, &user_id)
+            .await
+            .unwrap();
+        let goal_id: Uuid = sqlx::query_scalar(
+            r#"
+            INSERT INTO goals (project_id, name, goal_type, target_value)
+            VALUES ($1, 'Sign-off', 'manual', 1)
+            RETURNING goal_id
+            "#,
+        )
+        .bind(project.project_id.as_uuid())
+        .fetch_one(pool)
+        .await
+        .unwrap();
+
+        (project.project_id, goal_id)
+    }
+
+    #[tokio::test]
+    #[ignore = "requires DATABASE_URL pointing at a migrated database"]
+    async fn test_completing_tasks_advances_volume_goals() {
+        use crate::{NewTask, PgTaskRepository, TaskRepository, TaskUpdate};
+        use glyph_domain::TaskStatus;
+
+        let url = std::env::var("DATABASE_URL").expect("DATABASE_URL must be set");
+        let pool = PgPool::connect(&url).await.unwrap();
+        let (project_id, _) = seed_manual_goal(&pool).await;
+        let goal_id: Uuid = sqlx::query_scalar(
+            r#"
+            INSERT INTO goals (project_id, name, goal_type, target_value)
+            VALUES ($1, 'Two tasks', 'volume', 2)
+            RETURNING goal_id
+            "#,
+        )
+        .bind(project_id.as_uuid())
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+
+        let tasks = PgTaskRepository::new(pool.clone());
+        let goals = PgGoalRepository::new(pool);
+        for expected in [1.0, 2.0] {
+            let task = tasks
+                .create(&NewTask {
+                    project_id,
+                    input_data: serde_json::json!({}),
+                    priority: None,
+                    metadata: None,
+                    gold_output: None,
+                })
+                .await
+                .unwrap();
+            tasks
+                .update(
+                    &task.task_id,
+                    &TaskUpdate {
+                        status: Some(TaskStatus::Completed),
+                        ..TaskUpdate::default()
+                    },
+                )
+                .await
+                .unwrap();
+
+            let goal = goals.find(&project_id, goal_id).await.unwrap().unwrap();
+            assert!((goal.current_value - expected).abs() < f64::EPSILON);
+            assert_eq!(goal.completion_action_fired_at.is_some(), expected >= 2.0);
+        }
+
+        // Reaching the target is only reported once
+        assert!(goals
+            .refresh_progress(&project_id)
+            .await
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test]
+    fn test_goal_type_parsing() {
+        assert_eq!(parse_goal_type("manual"), GoalType::Manual);
+        assert_eq!(parse_goal_type("composite"), GoalType::Composite);
+        assert_eq!(parse_goal_type("unknown"), GoalType::Volume);
+    }
+
+    #[tokio::test]
+    #[ignore = "requires DATABASE_URL pointing at a migrated database"]
+    async fn test_manual_goal_complete_then_uncomplete() {
+        let url = std::env::var("DATABASE_URL").expect("DATABASE_URL must be set");
+        let pool = PgPool::connect(&url).await.unwrap();
+        let (project_id, goal_id) = seed_manual_goal(&pool).await;
+        let repo = PgGoalRepository::new(pool);
+
+        let goal = repo.find(&project_id, goal_id).await.unwrap().unwrap();
+        assert_eq!(goal.goal_type, GoalType::Manual);
+        assert!(!goal.manually_completed);
+
+        let goal = repo
+            .set_manually_completed(&project_id, goal_id, true)
+            .await
+            .unwrap()
+            .unwrap();
+        assert!(goal.manually_completed);
+
+        let goal = repo
+            .set_manually_completed(&project_id, goal_id, false)
+            .await
+            .unwrap()
+            .unwrap();
+        assert!(!goal.manually_completed);
+
+        // Goals are scoped to their project
+        let other = ProjectId::new();
+        assert!(repo
+            .set_manually_completed(&other, goal_id, true)
+            .await
+            .unwrap()
+            .is_none());
+    }
+}
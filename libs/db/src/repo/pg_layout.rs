@@ -0,0 +1,122 @@
+//! PostgreSQL access to annotation layouts
+//!
+//! A layout has any number of versions; only what's needed to copy a layout's
+//! current version between instances lives here.
+
+use serde::{Deserialize, Serialize};
+use sqlx::{PgPool, Postgres, Transaction};
+use uuid::Uuid;
+
+use glyph_domain::{ProjectTypeId, UserId};
+
+/// A layout and the content of one of its versions, without IDs
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, sqlx::FromRow)]
+pub struct LayoutDefinition {
+    pub name: String,
+    pub description: Option<String>,
+    pub version: String,
+    /// `nunjucks`, `mdx` or `tsx`
+    pub template_format: String,
+    pub content: String,
+    pub input_schema: Option<serde_json::Value>,
+    pub output_schema: Option<serde_json::Value>,
+    pub settings: serde_json::Value,
+    pub allowed_components: Vec<String>,
+    pub shortcuts: serde_json::Value,
+}
+
+/// PostgreSQL layout repository
+pub struct PgLayoutRepository {
+    pool: PgPool,
+}
+
+impl PgLayoutRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    /// A layout with its current version: the latest published one, or the
+    /// latest of any status if none is published. `None` if the layout doesn't
+    /// exist or has no versions.
+    pub async fn find_current_definition(
+        &self,
+        layout_id: Uuid,
+    ) -> Result<Option<LayoutDefinition>, sqlx::Error> {
+        sqlx::query_as::<_, LayoutDefinition>(
+            r#"
+            SELECT l.name, l.description, v.version, v.template_format::text AS template_format,
+                   v.content, v.input_schema, v.output_schema, v.settings,
+                   COALESCE(v.allowed_components, '{}') AS allowed_components, v.shortcuts
+            FROM layouts l
+            JOIN layout_versions v ON v.layout_id = l.id
+            WHERE l.id = $1
+            ORDER BY (v.status = 'published') DESC, v.created_at DESC
+            LIMIT 1
+            "#,
+        )
+        .bind(layout_id)
+        .fetch_optional(&self.pool)
+        .await
+    }
+
+    /// Create a layout with a single draft version, returning the layout's ID
+    pub async fn create_from_definition(
+        &self,
+        definition: &LayoutDefinition,
+        project_type_id: Option<&ProjectTypeId>,
+        created_by: &UserId,
+    ) -> Result<Uuid, sqlx::Error> {
+        let mut tx = self.pool.begin().await?;
+        let layout_id = self
+            .create_from_definition_in(&mut tx, definition, project_type_id, created_by)
+            .await?;
+        tx.commit().await?;
+        Ok(layout_id)
+    }
+
+    /// [`Self::create_from_definition`] inside a caller's transaction
+    pub async fn create_from_definition_in(
+        &self,
+        tx: &mut Transaction<'_, Postgres>,
+        definition: &LayoutDefinition,
+        project_type_id: Option<&ProjectTypeId>,
+        created_by: &UserId,
+    ) -> Result<Uuid, sqlx::Error> {
+        let layout_id: Uuid = sqlx::query_scalar(
+            r#"
+            INSERT INTO layouts (name, description, project_type_id)
+            VALUES ($1, $2, $3)
+            RETURNING id
+            "#,
+        )
+        .bind(&definition.name)
+        .bind(&definition.description)
+        .bind(project_type_id.map(ProjectTypeId::as_uuid))
+        .fetch_one(&mut **tx)
+        .await?;
+
+        sqlx::query(
+            r#"
+            INSERT INTO layout_versions (
+                layout_id, version, template_format, content, input_schema, output_schema,
+                settings, allowed_components, shortcuts, created_by
+            )
+            VALUES ($1, $2, $3::template_format, $4, $5, $6, $7, $8, $9, $10)
+            "#,
+        )
+        .bind(layout_id)
+        .bind(&definition.version)
+        .bind(&definition.template_format)
+        .bind(&definition.content)
+        .bind(&definition.input_schema)
+        .bind(&definition.output_schema)
+        .bind(&definition.settings)
+        .bind(&definition.allowed_components)
+        .bind(&definition.shortcuts)
+        .bind(created_by.as_uuid())
+        .execute(&mut **tx)
+        .await?;
+
+        Ok(layout_id)
+    }
+}
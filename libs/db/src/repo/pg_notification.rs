@@ -0,0 +1,85 @@
+//! Notification producer
+//!
+//! Notifications are queued in the event outbox, inside the caller's
+//! transaction, and the worker's outbox relay publishes them on
+//! [`NOTIFICATION_SUBJECT`]. A notification is only sent if the change that
+//! caused it commits.
+
+use sqlx::types::Json;
+use sqlx::PgExecutor;
+use uuid::Uuid;
+
+use glyph_domain::{Notification, NOTIFICATION_SUBJECT};
+
+/// Queue a notification for publishing.
+///
+/// `source_id` identifies what the notification is about (a project, task or
+/// user) and becomes the outbox row's stream id.
+pub async fn enqueue_notification<'e, E: PgExecutor<'e>>(
+    executor: E,
+    source_id: Uuid,
+    notification: &Notification,
+) -> Result<(), sqlx::Error> {
+    sqlx::query(
+        r#"
+        INSERT INTO event_outbox (stream_id, version, subject, payload)
+        VALUES ($1, 0, $2, $3)
+        "#,
+    )
+    .bind(source_id)
+    .bind(NOTIFICATION_SUBJECT)
+    .bind(Json(notification))
+    .execute(executor)
+    .await?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use glyph_domain::{NotificationKind, ProjectId, UserId};
+    use sqlx::PgPool;
+
+    #[tokio::test]
+    #[ignore = "requires DATABASE_URL pointing at a migrated database"]
+    async fn test_notification_is_only_queued_when_the_transaction_commits() {
+        let url = std::env::var("DATABASE_URL").expect("DATABASE_URL must be set");
+        let pool = PgPool::connect(&url).await.unwrap();
+        let project_id = ProjectId::new();
+        let notification = Notification {
+            kind: NotificationKind::DeadlineReached {
+                project_id,
+                project_name: "Queued".to_string(),
+                deadline: chrono::Utc::now(),
+            },
+            recipients: vec![UserId::new()],
+        };
+        let queued = || async {
+            sqlx::query_scalar::<_, serde_json::Value>(
+                "SELECT payload FROM event_outbox WHERE stream_id = $1 AND subject = $2",
+            )
+            .bind(project_id.as_uuid())
+            .bind(NOTIFICATION_SUBJECT)
+            .fetch_all(&pool)
+            .await
+            .unwrap()
+        };
+
+        let mut tx = pool.begin().await.unwrap();
+        enqueue_notification(&mut *tx, *project_id.as_uuid(), &notification)
+            .await
+            .unwrap();
+        tx.rollback().await.unwrap();
+        assert!(queued().await.is_empty());
+
+        let mut tx = pool.begin().await.unwrap();
+        enqueue_notification(&mut *tx, *project_id.as_uuid(), &notification)
+            .await
+            .unwrap();
+        tx.commit().await.unwrap();
+        let payloads = queued().await;
+        assert_eq!(payloads.len(), 1);
+        let published: Notification = serde_json::from_value(payloads[0].clone()).unwrap();
+        assert_eq!(published, notification);
+    }
+}
@@ -0,0 +1,39 @@
+//! PostgreSQL organization lookups
+
+use sqlx::PgPool;
+
+use glyph_domain::OrgId;
+
+/// PostgreSQL organization repository
+pub struct PgOrganizationRepository {
+    pool: PgPool,
+}
+
+impl PgOrganizationRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    /// Whether an organization with this ID exists
+    pub async fn exists(&self, org_id: OrgId) -> Result<bool, sqlx::Error> {
+        sqlx::query_scalar("SELECT EXISTS (SELECT 1 FROM organizations WHERE org_id = $1)")
+            .bind(org_id.as_uuid())
+            .fetch_one(&self.pool)
+            .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    #[ignore = "requires DATABASE_URL pointing at a migrated database"]
+    async fn test_default_org_exists_and_unknown_does_not() {
+        let url = std::env::var("DATABASE_URL").expect("DATABASE_URL must be set");
+        let repo = PgOrganizationRepository::new(PgPool::connect(&url).await.unwrap());
+
+        assert!(repo.exists(OrgId::DEFAULT).await.unwrap());
+        assert!(!repo.exists(OrgId::new()).await.unwrap());
+    }
+}
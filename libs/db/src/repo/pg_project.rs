@@ -3,11 +3,15 @@
 //! Full implementation with audit trail integration.
 
 use async_trait::async_trait;
-use sqlx::PgPool;
+use sqlx::{PgExecutor, PgPool, Postgres, Transaction};
 
-use glyph_domain::{DeadlineAction, Project, ProjectId, ProjectSettings, ProjectStatus, UserId};
+use glyph_domain::{
+    DeadlineAction, OrgId, Project, ProjectId, ProjectSettings, ProjectStatus, UserId,
+};
 
-use crate::audit::{AuditAction, AuditActorType, AuditEvent, AuditWriter, SYSTEM_ACTOR_ID};
+use crate::audit::{
+    AuditAction, AuditActorType, AuditError, AuditEvent, AuditWriter, SYSTEM_ACTOR_ID,
+};
 use crate::pagination::{Page, Pagination};
 use crate::repo::errors::{CreateProjectError, FindProjectError, UpdateProjectError};
 use crate::repo::traits::{NewProject, ProjectRepository, ProjectUpdate};
@@ -16,13 +20,35 @@ use crate::repo::traits::{NewProject, ProjectRepository, ProjectUpdate};
 pub struct PgProjectRepository {
     pool: PgPool,
     audit: AuditWriter,
+    /// Organization every query is limited to; `None` sees all of them
+    org_id: Option<OrgId>,
 }
 
 impl PgProjectRepository {
     /// Create a new PostgreSQL project repository
     pub fn new(pool: PgPool) -> Self {
         let audit = AuditWriter::new(pool.clone());
-        Self { pool, audit }
+        Self {
+            pool,
+            audit,
+            org_id: None,
+        }
+    }
+
+    /// Limit lookups, lists and changes to one organization's projects.
+    /// Projects created through the repository belong to it.
+    #[must_use]
+    pub fn in_org(mut self, org_id: OrgId) -> Self {
+        self.org_id = Some(org_id);
+        self
+    }
+
+    fn org_filter(&self) -> Option<uuid::Uuid> {
+        self.org_id.map(OrgId::into_uuid)
+    }
+
+    fn org_for_insert(&self) -> uuid::Uuid {
+        self.org_id.unwrap_or(OrgId::DEFAULT).into_uuid()
     }
 }
 
@@ -35,12 +61,14 @@ impl ProjectRepository for PgProjectRepository {
                    project_type_id::text, workflow_id::text, layout_id,
                    team_id::text, settings, tags, documentation,
                    deadline, deadline_action,
-                   created_at, updated_at, created_by::text
+                   created_at, updated_at, created_by::text, org_id::text
             FROM projects
             WHERE project_id = $1 AND status != 'deleted'
+              AND ($2::uuid IS NULL OR org_id = $2)
             "#,
         )
         .bind(id.as_uuid())
+        .bind(self.org_filter())
         .fetch_optional(&self.pool)
         .await
         .map_err(FindProjectError::Database)?;
@@ -56,14 +84,14 @@ impl ProjectRepository for PgProjectRepository {
         let row = sqlx::query_as::<_, ProjectRow>(
             r#"
             INSERT INTO projects (
-                project_id, name, description, workflow_id, layout_id, created_by
+                project_id, name, description, workflow_id, layout_id, created_by, org_id
             )
-            VALUES ($1, $2, $3, $4, $5, $6)
+            VALUES ($1, $2, $3, $4, $5, $6, $7)
             RETURNING project_id::text, name, description, status::text,
                       project_type_id::text, workflow_id::text, layout_id,
                       team_id::text, settings, tags, documentation,
                       deadline, deadline_action,
-                      created_at, updated_at, created_by::text
+                      created_at, updated_at, created_by::text, org_id::text
             "#,
         )
         .bind(id.as_uuid())
@@ -72,6 +100,7 @@ impl ProjectRepository for PgProjectRepository {
         .bind(new_project.workflow_id.as_uuid())
         .bind(&new_project.layout_id)
         .bind(new_project.created_by.as_uuid())
+        .bind(self.org_for_insert())
         .fetch_one(&self.pool)
         .await
         .map_err(CreateProjectError::Database)?;
@@ -122,17 +151,19 @@ impl ProjectRepository for PgProjectRepository {
                 status = COALESCE($4, status),
                 updated_at = NOW()
             WHERE project_id = $1 AND status != 'deleted'
+              AND ($5::uuid IS NULL OR org_id = $5)
             RETURNING project_id::text, name, description, status::text,
                       project_type_id::text, workflow_id::text, layout_id,
                       team_id::text, settings, tags, documentation,
                       deadline, deadline_action,
-                      created_at, updated_at, created_by::text
+                      created_at, updated_at, created_by::text, org_id::text
             "#,
         )
         .bind(id.as_uuid())
         .bind(&update.name)
         .bind(&update.description)
         .bind(update.status.map(|s| format!("{s:?}").to_lowercase()))
+        .bind(self.org_filter())
         .fetch_optional(&self.pool)
         .await
         .map_err(UpdateProjectError::Database)?
@@ -163,10 +194,12 @@ impl ProjectRepository for PgProjectRepository {
     }
 
     async fn list(&self, pagination: Pagination) -> Result<Page<Project>, sqlx::Error> {
-        let total =
-            sqlx::query_scalar::<_, i64>("SELECT COUNT(*) FROM projects WHERE status != 'deleted'")
-                .fetch_one(&self.pool)
-                .await?;
+        let total = sqlx::query_scalar::<_, i64>(
+            "SELECT COUNT(*) FROM projects WHERE status != 'deleted' AND ($1::uuid IS NULL OR org_id = $1)",
+        )
+        .bind(self.org_filter())
+        .fetch_one(&self.pool)
+        .await?;
 
         let rows = sqlx::query_as::<_, ProjectRow>(
             r#"
@@ -174,15 +207,16 @@ impl ProjectRepository for PgProjectRepository {
                    project_type_id::text, workflow_id::text, layout_id,
                    team_id::text, settings, tags, documentation,
                    deadline, deadline_action,
-                   created_at, updated_at, created_by::text
+                   created_at, updated_at, created_by::text, org_id::text
             FROM projects
-            WHERE status != 'deleted'
+            WHERE status != 'deleted' AND ($3::uuid IS NULL OR org_id = $3)
             ORDER BY created_at DESC
             LIMIT $1 OFFSET $2
             "#,
         )
         .bind(pagination.clamped_limit())
         .bind(pagination.offset)
+        .bind(self.org_filter())
         .fetch_all(&self.pool)
         .await?;
 
@@ -193,9 +227,13 @@ impl ProjectRepository for PgProjectRepository {
 
     async fn soft_delete(&self, id: &ProjectId) -> Result<(), UpdateProjectError> {
         let result = sqlx::query(
-            "UPDATE projects SET status = 'deleted', updated_at = NOW() WHERE project_id = $1",
+            r#"
+            UPDATE projects SET status = 'deleted', updated_at = NOW()
+            WHERE project_id = $1 AND ($2::uuid IS NULL OR org_id = $2)
+            "#,
         )
         .bind(id.as_uuid())
+        .bind(self.org_filter())
         .execute(&self.pool)
         .await
         .map_err(UpdateProjectError::Database)?;
@@ -233,47 +271,73 @@ impl PgProjectRepository {
         name: &str,
         description: Option<&str>,
         created_by: &UserId,
+    ) -> Result<Project, CreateProjectError> {
+        let project = self
+            .insert_minimal(&self.pool, name, description, created_by)
+            .await?;
+
+        // Record audit event
+        self.audit
+            .record_best_effort(create_event(&project, created_by))
+            .await;
+
+        Ok(project)
+    }
+
+    /// [`Self::create_minimal`] inside a caller's transaction, with its audit
+    /// event, so both are discarded if the transaction rolls back
+    pub async fn create_minimal_in(
+        &self,
+        tx: &mut Transaction<'_, Postgres>,
+        name: &str,
+        description: Option<&str>,
+        created_by: &UserId,
+    ) -> Result<Project, CreateProjectError> {
+        let project = self
+            .insert_minimal(&mut **tx, name, description, created_by)
+            .await?;
+        AuditWriter::record_with(&mut **tx, create_event(&project, created_by))
+            .await
+            .map_err(|e| match e {
+                AuditError::Database(e) => CreateProjectError::Database(e),
+                AuditError::Serialization(e) => {
+                    CreateProjectError::Database(sqlx::Error::Decode(Box::new(e)))
+                }
+            })?;
+        Ok(project)
+    }
+
+    async fn insert_minimal<'e, E: PgExecutor<'e>>(
+        &self,
+        executor: E,
+        name: &str,
+        description: Option<&str>,
+        created_by: &UserId,
     ) -> Result<Project, CreateProjectError> {
         let id = ProjectId::new();
 
         let row = sqlx::query_as::<_, ProjectRow>(
             r#"
-            INSERT INTO projects (project_id, name, description, created_by)
-            VALUES ($1, $2, $3, $4)
+            INSERT INTO projects (project_id, name, description, created_by, org_id)
+            VALUES ($1, $2, $3, $4, $5)
             RETURNING project_id::text, name, description, status::text,
                       project_type_id::text, workflow_id::text, layout_id,
                       team_id::text, settings, tags, documentation,
                       deadline, deadline_action,
-                      created_at, updated_at, created_by::text
+                      created_at, updated_at, created_by::text, org_id::text
             "#,
         )
         .bind(id.as_uuid())
         .bind(name)
         .bind(description)
         .bind(created_by.as_uuid())
-        .fetch_one(&self.pool)
+        .bind(self.org_for_insert())
+        .fetch_one(executor)
         .await
         .map_err(CreateProjectError::Database)?;
 
-        let project: Project = row
-            .try_into()
-            .map_err(|_| CreateProjectError::Database(sqlx::Error::RowNotFound))?;
-
-        // Record audit event
-        self.audit
-            .record_best_effort(AuditEvent {
-                entity_type: "project",
-                entity_id: project.project_id.to_string(),
-                action: AuditAction::Create,
-                actor_id: created_by.to_string(),
-                actor_type: AuditActorType::User,
-                data_snapshot: serde_json::to_value(&project).unwrap_or_default(),
-                changes: None,
-                request_id: None,
-            })
-            .await;
-
-        Ok(project)
+        row.try_into()
+            .map_err(|_| CreateProjectError::Database(sqlx::Error::RowNotFound))
     }
 
     /// Update project with extended fields
@@ -281,13 +345,32 @@ impl PgProjectRepository {
         &self,
         id: &ProjectId,
         update: &ExtendedProjectUpdate,
+    ) -> Result<Project, UpdateProjectError> {
+        self.apply_extended(&self.pool, id, update).await
+    }
+
+    /// [`Self::update_extended`] inside a caller's transaction
+    pub async fn update_extended_in(
+        &self,
+        tx: &mut Transaction<'_, Postgres>,
+        id: &ProjectId,
+        update: &ExtendedProjectUpdate,
+    ) -> Result<Project, UpdateProjectError> {
+        self.apply_extended(&mut **tx, id, update).await
+    }
+
+    async fn apply_extended<'e, E: PgExecutor<'e>>(
+        &self,
+        executor: E,
+        id: &ProjectId,
+        update: &ExtendedProjectUpdate,
     ) -> Result<Project, UpdateProjectError> {
         let row = sqlx::query_as::<_, ProjectRow>(
             r#"
             UPDATE projects
             SET name = COALESCE($2, name),
                 description = COALESCE($3, description),
-                status = COALESCE($4, status),
+                status = COALESCE($4::project_status, status),
                 project_type_id = COALESCE($5, project_type_id),
                 team_id = COALESCE($6, team_id),
                 tags = COALESCE($7, tags),
@@ -295,13 +378,16 @@ impl PgProjectRepository {
                 deadline = COALESCE($9, deadline),
                 deadline_action = COALESCE($10, deadline_action),
                 settings = COALESCE($11, settings),
+                workflow_id = COALESCE($12, workflow_id),
+                layout_id = COALESCE($13, layout_id),
                 updated_at = NOW()
             WHERE project_id = $1 AND status != 'deleted'
+              AND ($14::uuid IS NULL OR org_id = $14)
             RETURNING project_id::text, name, description, status::text,
                       project_type_id::text, workflow_id::text, layout_id,
                       team_id::text, settings, tags, documentation,
                       deadline, deadline_action,
-                      created_at, updated_at, created_by::text
+                      created_at, updated_at, created_by::text, org_id::text
             "#,
         )
         .bind(id.as_uuid())
@@ -329,7 +415,10 @@ impl PgProjectRepository {
                 .as_ref()
                 .map(|s| serde_json::to_value(s).unwrap_or_default()),
         )
-        .fetch_optional(&self.pool)
+        .bind(update.workflow_id.as_ref().map(|id| id.as_uuid()))
+        .bind(&update.layout_id)
+        .bind(self.org_filter())
+        .fetch_optional(executor)
         .await
         .map_err(UpdateProjectError::Database)?
         .ok_or_else(|| UpdateProjectError::NotFound(id.clone()))?;
@@ -342,6 +431,120 @@ impl PgProjectRepository {
     }
 }
 
+fn create_event(project: &Project, created_by: &UserId) -> AuditEvent {
+    AuditEvent {
+        entity_type: "project",
+        entity_id: project.project_id.to_string(),
+        action: AuditAction::Create,
+        actor_id: created_by.to_string(),
+        actor_type: AuditActorType::User,
+        data_snapshot: serde_json::to_value(project).unwrap_or_default(),
+        changes: None,
+        request_id: None,
+    }
+}
+
+impl PgProjectRepository {
+    /// List active projects whose deadline has passed and whose deadline
+    /// action has not fired for the current deadline
+    pub async fn list_deadline_candidates(
+        &self,
+        now: chrono::DateTime<chrono::Utc>,
+    ) -> Result<Vec<DeadlineCandidate>, sqlx::Error> {
+        let rows = sqlx::query_as::<_, DeadlineCandidateRow>(
+            r#"
+            SELECT project_id::text, name, status::text, team_id::text,
+                   deadline, deadline_action, deadline_action_fired_at, created_by::text
+            FROM projects
+            WHERE status = 'active'
+              AND deadline IS NOT NULL
+              AND deadline <= $1
+              AND deadline_action IS NOT NULL
+              AND (deadline_action_fired_at IS NULL OR deadline_action_fired_at < deadline)
+            ORDER BY deadline
+            "#,
+        )
+        .bind(now)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(rows
+            .into_iter()
+            .filter_map(|row| row.try_into().ok())
+            .collect())
+    }
+
+    /// Claim the deadline action for a project inside the caller's
+    /// transaction, so the claim only sticks if the action commits with it.
+    ///
+    /// Returns `false` if it already fired for the current deadline, so
+    /// concurrent workers act at most once.
+    pub async fn claim_deadline_action(
+        &self,
+        tx: &mut Transaction<'_, Postgres>,
+        id: &ProjectId,
+        now: chrono::DateTime<chrono::Utc>,
+    ) -> Result<bool, sqlx::Error> {
+        let result = sqlx::query(
+            r#"
+            UPDATE projects
+            SET deadline_action_fired_at = $2
+            WHERE project_id = $1
+              AND deadline IS NOT NULL
+              AND (deadline_action_fired_at IS NULL OR deadline_action_fired_at < deadline)
+            "#,
+        )
+        .bind(id.as_uuid())
+        .bind(now)
+        .execute(&mut **tx)
+        .await?;
+
+        Ok(result.rows_affected() == 1)
+    }
+
+    /// Users to escalate to for a project: leaders of its team, or the
+    /// project creator when there is no team or the team has no leader
+    pub async fn escalation_recipients(
+        &self,
+        project_id: &ProjectId,
+    ) -> Result<Vec<UserId>, sqlx::Error> {
+        let ids: Vec<uuid::Uuid> = sqlx::query_scalar(
+            r#"
+            SELECT tm.user_id
+            FROM projects p
+            JOIN team_memberships tm ON tm.team_id = p.team_id AND tm.role = 'leader'
+            WHERE p.project_id = $1
+            UNION
+            SELECT p.created_by
+            FROM projects p
+            WHERE p.project_id = $1
+              AND NOT EXISTS (
+                  SELECT 1 FROM team_memberships tm
+                  WHERE tm.team_id = p.team_id AND tm.role = 'leader'
+              )
+            "#,
+        )
+        .bind(project_id.as_uuid())
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(ids.into_iter().map(UserId::from_uuid).collect())
+    }
+}
+
+/// Project past its deadline, as seen by the deadline job
+#[derive(Debug, Clone)]
+pub struct DeadlineCandidate {
+    pub project_id: ProjectId,
+    pub name: String,
+    pub status: ProjectStatus,
+    pub team_id: Option<glyph_domain::TeamId>,
+    pub deadline: chrono::DateTime<chrono::Utc>,
+    pub deadline_action: Option<DeadlineAction>,
+    pub deadline_action_fired_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub created_by: UserId,
+}
+
 /// Extended update input with all project fields
 #[derive(Debug, Clone, Default)]
 pub struct ExtendedProjectUpdate {
@@ -355,6 +558,8 @@ pub struct ExtendedProjectUpdate {
     pub deadline: Option<chrono::DateTime<chrono::Utc>>,
     pub deadline_action: Option<DeadlineAction>,
     pub settings: Option<ProjectSettings>,
+    pub workflow_id: Option<glyph_domain::WorkflowId>,
+    pub layout_id: Option<String>,
 }
 
 // =============================================================================
@@ -379,6 +584,7 @@ struct ProjectRow {
     created_at: chrono::DateTime<chrono::Utc>,
     updated_at: chrono::DateTime<chrono::Utc>,
     created_by: String,
+    org_id: String,
 }
 
 impl TryFrom<ProjectRow> for Project {
@@ -399,6 +605,11 @@ impl TryFrom<ProjectRow> for Project {
             .parse()
             .map_err(|e: uuid::Error| IdParseError::InvalidUuid(e.to_string()))?;
 
+        let org_uuid: uuid::Uuid = row
+            .org_id
+            .parse()
+            .map_err(|e: uuid::Error| IdParseError::InvalidUuid(e.to_string()))?;
+
         // Parse optional IDs
         let project_type_id = row
             .project_type_id
@@ -441,6 +652,39 @@ impl TryFrom<ProjectRow> for Project {
             created_at: row.created_at,
             updated_at: row.updated_at,
             created_by: UserId::from_uuid(created_by_uuid),
+            org_id: OrgId::from_uuid(org_uuid),
+        })
+    }
+}
+
+#[derive(sqlx::FromRow)]
+struct DeadlineCandidateRow {
+    project_id: String,
+    name: String,
+    status: String,
+    team_id: Option<String>,
+    deadline: chrono::DateTime<chrono::Utc>,
+    deadline_action: Option<String>,
+    deadline_action_fired_at: Option<chrono::DateTime<chrono::Utc>>,
+    created_by: String,
+}
+
+impl TryFrom<DeadlineCandidateRow> for DeadlineCandidate {
+    type Error = uuid::Error;
+
+    fn try_from(row: DeadlineCandidateRow) -> Result<Self, Self::Error> {
+        Ok(Self {
+            project_id: ProjectId::from_uuid(row.project_id.parse()?),
+            name: row.name,
+            status: parse_project_status(&row.status),
+            team_id: row
+                .team_id
+                .map(|s| s.parse().map(glyph_domain::TeamId::from_uuid))
+                .transpose()?,
+            deadline: row.deadline,
+            deadline_action: row.deadline_action.as_deref().map(parse_deadline_action),
+            deadline_action_fired_at: row.deadline_action_fired_at,
+            created_by: UserId::from_uuid(row.created_by.parse()?),
         })
     }
 }
@@ -465,3 +709,69 @@ fn parse_deadline_action(s: &str) -> DeadlineAction {
         _ => DeadlineAction::Notify,
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    async fn create_org(pool: &PgPool) -> OrgId {
+        let org_id = OrgId::new();
+        sqlx::query("INSERT INTO organizations (org_id, name, slug) VALUES ($1, $2, $2)")
+            .bind(org_id.as_uuid())
+            .bind(org_id.to_string())
+            .execute(pool)
+            .await
+            .unwrap();
+        org_id
+    }
+
+    #[tokio::test]
+    #[ignore = "requires DATABASE_URL pointing at a migrated database"]
+    async fn test_org_cannot_read_another_orgs_project() {
+        let url = std::env::var("DATABASE_URL").expect("DATABASE_URL must be set");
+        let pool = PgPool::connect(&url).await.unwrap();
+
+        let user_id = UserId::new();
+        sqlx::query(
+            r#"
+            INSERT INTO users (user_id, email, display_name, auth0_id, global_role, status)
+            VALUES ($1, $2, 'Org Scope', $3, 'user', 'active')
+            "#,
+        )
+        .bind(user_id.as_uuid())
+        .bind(format!("{user_id}@orgs.test"))
+        .bind(format!("test|{user_id}"))
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        let org_a = create_org(&pool).await;
+        let org_b = create_org(&pool).await;
+        let repo_a = PgProjectRepository::new(pool.clone()).in_org(org_a);
+        let repo_b = PgProjectRepository::new(pool.clone()).in_org(org_b);
+
+        let project = repo_b
+            .create_minimal("Org B Project", None, &user_id)
+            .await
+            .unwrap();
+        assert_eq!(project.org_id, org_b);
+
+        assert!(repo_a
+            .find_by_id(&project.project_id)
+            .await
+            .unwrap()
+            .is_none());
+        let listed = repo_a.list(Pagination::default()).await.unwrap();
+        assert!(listed
+            .items
+            .iter()
+            .all(|p| p.project_id != project.project_id));
+        assert!(matches!(
+            repo_a.soft_delete(&project.project_id).await,
+            Err(UpdateProjectError::NotFound(_))
+        ));
+
+        let found = repo_b.find_by_id(&project.project_id).await.unwrap();
+        assert_eq!(found.map(|p| p.org_id), Some(org_b));
+    }
+}
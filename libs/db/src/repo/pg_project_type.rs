@@ -2,12 +2,12 @@
 
 use async_trait::async_trait;
 use chrono::{DateTime, Utc};
-use sqlx::{FromRow, PgPool};
+use sqlx::{FromRow, PgPool, Postgres, Transaction};
 use uuid::Uuid;
 
 use glyph_domain::{
-    CreateProjectType, DifficultyLevel, ProficiencyLevel, ProjectType, ProjectTypeFilter,
-    ProjectTypeId, SkillRequirement, UpdateProjectType, UserId,
+    CreateProjectType, DifficultyLevel, OrgId, ProficiencyLevel, ProjectType, ProjectTypeFilter,
+    ProjectTypeId, ProjectTypeSchemaVersion, SkillRequirement, TaskId, UpdateProjectType, UserId,
 };
 
 use super::errors::*;
@@ -35,7 +35,17 @@ pub trait ProjectTypeRepository: Send + Sync {
     /// List project types with filtering
     async fn list(&self, filter: &ProjectTypeFilter) -> Result<Vec<ProjectType>, sqlx::Error>;
 
-    /// Update a project type
+    /// Find the schemas a project type had at a given version
+    async fn find_schema_version(
+        &self,
+        id: &ProjectTypeId,
+        version: i32,
+    ) -> Result<Option<ProjectTypeSchemaVersion>, sqlx::Error>;
+
+    /// Update a project type.
+    ///
+    /// Changing either schema increments `schema_version`; earlier versions
+    /// stay available through [`Self::find_schema_version`].
     async fn update(
         &self,
         id: &ProjectTypeId,
@@ -71,12 +81,35 @@ struct ProjectTypeRow {
     description: Option<String>,
     input_schema: serde_json::Value,
     output_schema: serde_json::Value,
+    schema_version: i32,
     estimated_duration_seconds: Option<i32>,
     difficulty_level: Option<String>,
+    allow_overlapping_spans: bool,
     is_system: bool,
     created_by: Option<Uuid>,
     created_at: DateTime<Utc>,
     updated_at: DateTime<Utc>,
+    org_id: Uuid,
+}
+
+/// What a task's submissions are validated against
+#[derive(Debug, Clone, FromRow)]
+pub struct TaskOutputSchema {
+    pub output_schema: serde_json::Value,
+    pub allow_overlapping_spans: bool,
+    /// The project type's input schema, locating the document spans index
+    pub input_schema: serde_json::Value,
+    /// The task's input data
+    pub input_data: serde_json::Value,
+}
+
+#[derive(FromRow)]
+struct SchemaVersionRow {
+    project_type_id: Uuid,
+    version: i32,
+    input_schema: serde_json::Value,
+    output_schema: serde_json::Value,
+    created_at: DateTime<Utc>,
 }
 
 #[derive(FromRow)]
@@ -94,11 +127,48 @@ struct SkillRequirementRow {
 /// PostgreSQL-backed project type repository
 pub struct PgProjectTypeRepository {
     pool: PgPool,
+    /// Organization every query is limited to; `None` sees all of them
+    org_id: Option<OrgId>,
 }
 
 impl PgProjectTypeRepository {
     pub fn new(pool: PgPool) -> Self {
-        Self { pool }
+        Self { pool, org_id: None }
+    }
+
+    /// Limit lookups and lists to one organization's project types plus the
+    /// system types, and changes to that organization's own types. Types
+    /// created through the repository belong to it.
+    #[must_use]
+    pub fn in_org(mut self, org_id: OrgId) -> Self {
+        self.org_id = Some(org_id);
+        self
+    }
+
+    fn org_filter(&self) -> Option<Uuid> {
+        self.org_id.map(OrgId::into_uuid)
+    }
+
+    /// Output schema and span policy of the project type a task's project
+    /// uses, along with the task's input.
+    ///
+    /// Returns `None` if the task doesn't exist or its project has no type.
+    pub async fn find_output_schema_for_task(
+        &self,
+        task_id: &TaskId,
+    ) -> Result<Option<TaskOutputSchema>, sqlx::Error> {
+        sqlx::query_as(
+            r#"
+            SELECT pt.output_schema, pt.allow_overlapping_spans, pt.input_schema, t.input_data
+            FROM tasks t
+            JOIN projects p ON p.project_id = t.project_id
+            JOIN project_types pt ON pt.project_type_id = p.project_type_id
+            WHERE t.task_id = $1
+            "#,
+        )
+        .bind(task_id.as_uuid())
+        .fetch_optional(&self.pool)
+        .await
     }
 
     /// Load skill requirements for a project type
@@ -143,21 +213,51 @@ impl PgProjectTypeRepository {
             description: row.description,
             input_schema: row.input_schema,
             output_schema: row.output_schema,
+            schema_version: row.schema_version,
             estimated_duration_seconds: row.estimated_duration_seconds,
             difficulty_level: row.difficulty_level.and_then(|d| parse_difficulty(&d)),
             skill_requirements,
+            allow_overlapping_spans: row.allow_overlapping_spans,
             is_system: row.is_system,
             created_by: row.created_by.map(UserId::from_uuid),
             created_at: row.created_at,
             updated_at: row.updated_at,
+            org_id: OrgId::from_uuid(row.org_id),
         }
     }
 }
 
-#[async_trait]
-impl ProjectTypeRepository for PgProjectTypeRepository {
-    async fn create(
+/// Store a row's schemas under its current version; a no-op if that version
+/// is already stored
+async fn record_schema_version<'e, E>(executor: E, row: &ProjectTypeRow) -> Result<(), sqlx::Error>
+where
+    E: sqlx::PgExecutor<'e>,
+{
+    sqlx::query(
+        r#"
+        INSERT INTO project_type_schema_versions
+            (project_type_id, version, input_schema, output_schema)
+        VALUES ($1, $2, $3, $4)
+        ON CONFLICT (project_type_id, version) DO NOTHING
+        "#,
+    )
+    .bind(row.project_type_id)
+    .bind(row.schema_version)
+    .bind(&row.input_schema)
+    .bind(&row.output_schema)
+    .execute(executor)
+    .await?;
+    Ok(())
+}
+
+impl PgProjectTypeRepository {
+    /// Create a project type inside a caller's transaction.
+    ///
+    /// A name clash fails the transaction; run this under a savepoint to
+    /// retry with another name.
+    pub async fn create_in(
         &self,
+        tx: &mut Transaction<'_, Postgres>,
         input: &CreateProjectType,
         created_by: Option<&UserId>,
     ) -> Result<ProjectType, CreateProjectTypeError> {
@@ -177,13 +277,14 @@ impl ProjectTypeRepository for PgProjectTypeRepository {
             r#"
             INSERT INTO project_types (
                 project_type_id, name, description, input_schema, output_schema,
-                estimated_duration_seconds, difficulty_level, is_system, created_by
+                estimated_duration_seconds, difficulty_level, allow_overlapping_spans,
+                is_system, created_by, org_id
             )
-            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11)
             RETURNING
-                project_type_id, name, description, input_schema, output_schema,
-                estimated_duration_seconds, difficulty_level, is_system, created_by,
-                created_at, updated_at
+                project_type_id, name, description, input_schema, output_schema, schema_version,
+                estimated_duration_seconds, difficulty_level, allow_overlapping_spans, is_system,
+                created_by, created_at, updated_at, org_id
             "#,
         )
         .bind(id.as_uuid())
@@ -193,9 +294,11 @@ impl ProjectTypeRepository for PgProjectTypeRepository {
         .bind(&output_schema)
         .bind(input.estimated_duration_seconds)
         .bind(&difficulty)
+        .bind(input.allow_overlapping_spans.unwrap_or(true))
         .bind(is_system)
         .bind(created_by.map(|u| *u.as_uuid()))
-        .fetch_one(&self.pool)
+        .bind(self.org_id.unwrap_or(OrgId::DEFAULT).into_uuid())
+        .fetch_one(&mut **tx)
         .await
         .map_err(|e| {
             if is_unique_violation(&e) {
@@ -205,6 +308,10 @@ impl ProjectTypeRepository for PgProjectTypeRepository {
             }
         })?;
 
+        record_schema_version(&mut **tx, &row)
+            .await
+            .map_err(CreateProjectTypeError::Database)?;
+
         // Insert skill requirements if provided
         let skill_requirements = if let Some(requirements) = &input.skill_requirements {
             for req in requirements {
@@ -221,7 +328,7 @@ impl ProjectTypeRepository for PgProjectTypeRepository {
                 .bind(format_proficiency(req.min_proficiency))
                 .bind(req.is_required)
                 .bind(req.weight)
-                .execute(&self.pool)
+                .execute(&mut **tx)
                 .await
                 .map_err(CreateProjectTypeError::Database)?;
             }
@@ -232,6 +339,26 @@ impl ProjectTypeRepository for PgProjectTypeRepository {
 
         Ok(self.row_to_project_type(row, skill_requirements))
     }
+}
+
+#[async_trait]
+impl ProjectTypeRepository for PgProjectTypeRepository {
+    async fn create(
+        &self,
+        input: &CreateProjectType,
+        created_by: Option<&UserId>,
+    ) -> Result<ProjectType, CreateProjectTypeError> {
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .map_err(CreateProjectTypeError::Database)?;
+        let project_type = self.create_in(&mut tx, input, created_by).await?;
+        tx.commit()
+            .await
+            .map_err(CreateProjectTypeError::Database)?;
+        Ok(project_type)
+    }
 
     async fn find_by_id(
         &self,
@@ -240,14 +367,16 @@ impl ProjectTypeRepository for PgProjectTypeRepository {
         let row: Option<ProjectTypeRow> = sqlx::query_as(
             r#"
             SELECT
-                project_type_id, name, description, input_schema, output_schema,
-                estimated_duration_seconds, difficulty_level, is_system, created_by,
-                created_at, updated_at
+                project_type_id, name, description, input_schema, output_schema, schema_version,
+                estimated_duration_seconds, difficulty_level, allow_overlapping_spans, is_system,
+                created_by, created_at, updated_at, org_id
             FROM project_types
             WHERE project_type_id = $1
+              AND ($2::uuid IS NULL OR org_id = $2 OR is_system)
             "#,
         )
         .bind(id.as_uuid())
+        .bind(self.org_filter())
         .fetch_optional(&self.pool)
         .await
         .map_err(FindProjectTypeError::Database)?;
@@ -272,13 +401,14 @@ impl ProjectTypeRepository for PgProjectTypeRepository {
         let rows: Vec<ProjectTypeRow> = sqlx::query_as(
             r#"
             SELECT
-                project_type_id, name, description, input_schema, output_schema,
-                estimated_duration_seconds, difficulty_level, is_system, created_by,
-                created_at, updated_at
+                project_type_id, name, description, input_schema, output_schema, schema_version,
+                estimated_duration_seconds, difficulty_level, allow_overlapping_spans, is_system,
+                created_by, created_at, updated_at, org_id
             FROM project_types
             WHERE ($1::bool IS NULL OR is_system = $1)
               AND ($2::uuid IS NULL OR created_by = $2)
               AND ($3::text IS NULL OR name ILIKE '%' || $3 || '%' OR description ILIKE '%' || $3 || '%')
+              AND ($6::uuid IS NULL OR org_id = $6 OR is_system)
             ORDER BY created_at DESC
             LIMIT $4
             OFFSET $5
@@ -289,6 +419,7 @@ impl ProjectTypeRepository for PgProjectTypeRepository {
         .bind(&filter.search)
         .bind(limit)
         .bind(offset)
+        .bind(self.org_filter())
         .fetch_all(&self.pool)
         .await?;
 
@@ -302,11 +433,45 @@ impl ProjectTypeRepository for PgProjectTypeRepository {
         Ok(result)
     }
 
+    async fn find_schema_version(
+        &self,
+        id: &ProjectTypeId,
+        version: i32,
+    ) -> Result<Option<ProjectTypeSchemaVersion>, sqlx::Error> {
+        let row: Option<SchemaVersionRow> = sqlx::query_as(
+            r#"
+            SELECT project_type_id, version, input_schema, output_schema, created_at
+            FROM project_type_schema_versions
+            WHERE project_type_id = $1 AND version = $2
+            "#,
+        )
+        .bind(id.as_uuid())
+        .bind(version)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(row.map(|row| ProjectTypeSchemaVersion {
+            project_type_id: ProjectTypeId::from_uuid(row.project_type_id),
+            version: row.version,
+            input_schema: row.input_schema,
+            output_schema: row.output_schema,
+            created_at: row.created_at,
+        }))
+    }
+
     async fn update(
         &self,
         id: &ProjectTypeId,
         update: &UpdateProjectType,
     ) -> Result<ProjectType, UpdateProjectTypeError> {
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .map_err(UpdateProjectTypeError::Database)?;
+
+        // SET expressions see the old row, so the version only moves when a
+        // schema actually changes
         let row: Option<ProjectTypeRow> = sqlx::query_as(
             r#"
             UPDATE project_types
@@ -315,14 +480,20 @@ impl ProjectTypeRepository for PgProjectTypeRepository {
                 description = COALESCE($3, description),
                 input_schema = COALESCE($4, input_schema),
                 output_schema = COALESCE($5, output_schema),
+                schema_version = schema_version + CASE
+                    WHEN COALESCE($4, input_schema) IS DISTINCT FROM input_schema
+                      OR COALESCE($5, output_schema) IS DISTINCT FROM output_schema
+                    THEN 1 ELSE 0
+                END,
                 estimated_duration_seconds = COALESCE($6, estimated_duration_seconds),
                 difficulty_level = COALESCE($7, difficulty_level),
+                allow_overlapping_spans = COALESCE($8, allow_overlapping_spans),
                 updated_at = NOW()
-            WHERE project_type_id = $1
+            WHERE project_type_id = $1 AND ($9::uuid IS NULL OR org_id = $9)
             RETURNING
-                project_type_id, name, description, input_schema, output_schema,
-                estimated_duration_seconds, difficulty_level, is_system, created_by,
-                created_at, updated_at
+                project_type_id, name, description, input_schema, output_schema, schema_version,
+                estimated_duration_seconds, difficulty_level, allow_overlapping_spans, is_system,
+                created_by, created_at, updated_at, org_id
             "#,
         )
         .bind(id.as_uuid())
@@ -332,12 +503,21 @@ impl ProjectTypeRepository for PgProjectTypeRepository {
         .bind(&update.output_schema)
         .bind(update.estimated_duration_seconds)
         .bind(update.difficulty_level.map(format_difficulty))
-        .fetch_optional(&self.pool)
+        .bind(update.allow_overlapping_spans)
+        .bind(self.org_filter())
+        .fetch_optional(&mut *tx)
         .await
         .map_err(UpdateProjectTypeError::Database)?;
 
         let row = row.ok_or_else(|| UpdateProjectTypeError::NotFound(id.clone()))?;
 
+        record_schema_version(&mut *tx, &row)
+            .await
+            .map_err(UpdateProjectTypeError::Database)?;
+        tx.commit()
+            .await
+            .map_err(UpdateProjectTypeError::Database)?;
+
         let skill_requirements = self
             .load_skill_requirements(id)
             .await
@@ -347,11 +527,17 @@ impl ProjectTypeRepository for PgProjectTypeRepository {
     }
 
     async fn delete(&self, id: &ProjectTypeId) -> Result<(), DeleteProjectTypeError> {
-        let result = sqlx::query(r#"DELETE FROM project_types WHERE project_type_id = $1"#)
-            .bind(id.as_uuid())
-            .execute(&self.pool)
-            .await
-            .map_err(DeleteProjectTypeError::Database)?;
+        let result = sqlx::query(
+            r#"
+            DELETE FROM project_types
+            WHERE project_type_id = $1 AND ($2::uuid IS NULL OR org_id = $2)
+            "#,
+        )
+        .bind(id.as_uuid())
+        .bind(self.org_filter())
+        .execute(&self.pool)
+        .await
+        .map_err(DeleteProjectTypeError::Database)?;
 
         if result.rows_affected() == 0 {
             return Err(DeleteProjectTypeError::NotFound(id.clone()));
@@ -462,3 +648,84 @@ fn parse_proficiency(s: &str) -> ProficiencyLevel {
         _ => ProficiencyLevel::Intermediate,
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    async fn test_repo() -> PgProjectTypeRepository {
+        let url = std::env::var("DATABASE_URL").expect("DATABASE_URL must be set");
+        PgProjectTypeRepository::new(PgPool::connect(&url).await.unwrap())
+    }
+
+    #[tokio::test]
+    #[ignore = "requires DATABASE_URL pointing at a migrated database"]
+    async fn test_schema_edit_increments_version_and_keeps_prior() {
+        let repo = test_repo().await;
+        let original = serde_json::json!({"type": "object", "required": ["label"]});
+        let created = repo
+            .create(
+                &CreateProjectType {
+                    name: format!("Versioned {}", Uuid::new_v4()),
+                    description: None,
+                    input_schema: None,
+                    output_schema: Some(original.clone()),
+                    estimated_duration_seconds: None,
+                    difficulty_level: None,
+                    skill_requirements: None,
+                    allow_overlapping_spans: None,
+                    is_system: None,
+                },
+                None,
+            )
+            .await
+            .unwrap();
+        assert_eq!(created.schema_version, 1);
+
+        // Non-schema edits, and re-saving the same schema, keep the version
+        let renamed = repo
+            .update(
+                &created.project_type_id,
+                &UpdateProjectType {
+                    description: Some("renamed".to_string()),
+                    output_schema: Some(original.clone()),
+                    ..Default::default()
+                },
+            )
+            .await
+            .unwrap();
+        assert_eq!(renamed.schema_version, 1);
+
+        let edited = serde_json::json!({"type": "object", "required": ["label", "confidence"]});
+        let updated = repo
+            .update(
+                &created.project_type_id,
+                &UpdateProjectType {
+                    output_schema: Some(edited.clone()),
+                    ..Default::default()
+                },
+            )
+            .await
+            .unwrap();
+        assert_eq!(updated.schema_version, 2);
+        assert_eq!(updated.output_schema, edited);
+
+        let v1 = repo
+            .find_schema_version(&created.project_type_id, 1)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(v1.output_schema, original);
+        let v2 = repo
+            .find_schema_version(&created.project_type_id, 2)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(v2.output_schema, edited);
+        assert!(repo
+            .find_schema_version(&created.project_type_id, 3)
+            .await
+            .unwrap()
+            .is_none());
+    }
+}
@@ -0,0 +1,506 @@
+//! PostgreSQL implementation of QualityProfileRepository
+
+use async_trait::async_trait;
+use sqlx::PgPool;
+
+use glyph_domain::{QualityProfile, TaskId, UserId};
+
+use crate::repo::errors::{FindQualityProfileError, UpsertQualityProfileError};
+use crate::repo::traits::{ConsensusComparison, GoldSubmission, QualityProfileRepository};
+
+/// PostgreSQL quality profile repository
+pub struct PgQualityProfileRepository {
+    pool: PgPool,
+}
+
+impl PgQualityProfileRepository {
+    /// Create a new PostgreSQL quality profile repository
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl QualityProfileRepository for PgQualityProfileRepository {
+    async fn find_by_user(
+        &self,
+        user_id: &UserId,
+    ) -> Result<Option<QualityProfile>, FindQualityProfileError> {
+        let row = sqlx::query_as::<_, QualityProfileRow>(
+            r#"
+            SELECT overall_score, accuracy_score, gold_accuracy_score, consistency_score,
+                   speed_percentile, total_annotations, approved_annotations,
+                   rejected_annotations
+            FROM quality_profiles
+            WHERE user_id = $1
+            "#,
+        )
+        .bind(user_id.as_uuid())
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(FindQualityProfileError::Database)?;
+
+        Ok(row.map(Into::into))
+    }
+
+    async fn upsert(
+        &self,
+        user_id: &UserId,
+        profile: &QualityProfile,
+    ) -> Result<QualityProfile, UpsertQualityProfileError> {
+        let row = sqlx::query_as::<_, QualityProfileRow>(
+            r#"
+            INSERT INTO quality_profiles (user_id, overall_score, accuracy_score,
+                                          consistency_score, speed_percentile,
+                                          total_annotations, approved_annotations,
+                                          rejected_annotations, computed_at)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, NOW())
+            ON CONFLICT (user_id) DO UPDATE SET
+                overall_score = EXCLUDED.overall_score,
+                accuracy_score = EXCLUDED.accuracy_score,
+                consistency_score = EXCLUDED.consistency_score,
+                speed_percentile = EXCLUDED.speed_percentile,
+                total_annotations = EXCLUDED.total_annotations,
+                approved_annotations = EXCLUDED.approved_annotations,
+                rejected_annotations = EXCLUDED.rejected_annotations,
+                computed_at = EXCLUDED.computed_at
+            RETURNING overall_score, accuracy_score, gold_accuracy_score, consistency_score,
+                      speed_percentile, total_annotations, approved_annotations,
+                      rejected_annotations
+            "#,
+        )
+        .bind(user_id.as_uuid())
+        .bind(profile.overall_score)
+        .bind(profile.accuracy_score)
+        .bind(profile.consistency_score)
+        .bind(profile.speed_percentile)
+        .bind(profile.total_annotations)
+        .bind(profile.approved_annotations)
+        .bind(profile.rejected_annotations)
+        .fetch_one(&self.pool)
+        .await
+        .map_err(|e| {
+            if let sqlx::Error::Database(ref db_err) = e {
+                if db_err.constraint() == Some("quality_profiles_user_id_fkey") {
+                    return UpsertQualityProfileError::UserNotFound(user_id.clone());
+                }
+            }
+            UpsertQualityProfileError::Database(e)
+        })?;
+
+        Ok(row.into())
+    }
+
+    async fn recompute_all(&self) -> Result<u64, sqlx::Error> {
+        // Accuracy is the approval rate among reviewed annotations; speed is the
+        // percentile of average time spent, inverted so faster annotators rank higher.
+        // Consistency is computed separately (see `set_consistency`) and left as is.
+        let result = sqlx::query(
+            r#"
+            WITH stats AS (
+                SELECT user_id,
+                       COUNT(*) AS total,
+                       COUNT(*) FILTER (WHERE status = 'approved') AS approved,
+                       COUNT(*) FILTER (WHERE status = 'rejected') AS rejected,
+                       AVG(quality_score) AS overall,
+                       AVG(time_spent_ms) AS avg_time_ms
+                FROM annotations
+                WHERE status IN ('submitted', 'approved', 'rejected')
+                GROUP BY user_id
+            ),
+            speed AS (
+                SELECT user_id, 1.0 - PERCENT_RANK() OVER (ORDER BY avg_time_ms) AS speed_percentile
+                FROM stats
+                WHERE avg_time_ms IS NOT NULL
+            )
+            INSERT INTO quality_profiles (user_id, overall_score, accuracy_score,
+                                          consistency_score, speed_percentile,
+                                          total_annotations, approved_annotations,
+                                          rejected_annotations, computed_at)
+            SELECT s.user_id,
+                   s.overall,
+                   CASE WHEN s.approved + s.rejected > 0
+                        THEN s.approved::float8 / (s.approved + s.rejected)
+                   END,
+                   NULL::float8,
+                   sp.speed_percentile,
+                   s.total, s.approved, s.rejected, NOW()
+            FROM stats s
+            LEFT JOIN speed sp ON sp.user_id = s.user_id
+            ON CONFLICT (user_id) DO UPDATE SET
+                overall_score = EXCLUDED.overall_score,
+                accuracy_score = EXCLUDED.accuracy_score,
+                speed_percentile = EXCLUDED.speed_percentile,
+                total_annotations = EXCLUDED.total_annotations,
+                approved_annotations = EXCLUDED.approved_annotations,
+                rejected_annotations = EXCLUDED.rejected_annotations,
+                computed_at = EXCLUDED.computed_at
+            "#,
+        )
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.rows_affected())
+    }
+
+    async fn list_consensus_comparisons(&self) -> Result<Vec<ConsensusComparison>, sqlx::Error> {
+        // Each label is compared with the strict-majority label of the other
+        // annotators on its task step, so nobody's vote counts toward the
+        // consensus they are measured against. Steps with fewer than two other
+        // annotators, or where they tie, have no consensus.
+        let rows: Vec<(
+            uuid::Uuid,
+            String,
+            uuid::Uuid,
+            serde_json::Value,
+            Option<serde_json::Value>,
+        )> = sqlx::query_as(
+            r#"
+            WITH latest AS (
+                SELECT DISTINCT ON (task_id, step_id, user_id) task_id, step_id, user_id, data
+                FROM annotations
+                WHERE status IN ('submitted', 'approved', 'rejected')
+                ORDER BY task_id, step_id, user_id, version DESC
+            ),
+            votes AS (
+                SELECT task_id, step_id, data, COUNT(*) AS votes,
+                       SUM(COUNT(*)) OVER (PARTITION BY task_id, step_id)::bigint AS total
+                FROM latest
+                GROUP BY task_id, step_id, data
+            )
+            SELECT l.task_id, l.step_id, l.user_id, l.data, consensus.data
+            FROM latest l
+            LEFT JOIN LATERAL (
+                SELECT v.data
+                FROM votes v
+                WHERE v.task_id = l.task_id AND v.step_id = l.step_id
+                  AND v.total - 1 >= 2
+                  AND (v.votes - (v.data = l.data)::int) * 2 > v.total - 1
+            ) consensus ON TRUE
+            "#,
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(
+                |(task_id, step_id, user_id, data, consensus)| ConsensusComparison {
+                    task_id: TaskId::from_uuid(task_id),
+                    step_id,
+                    user_id: UserId::from_uuid(user_id),
+                    data,
+                    consensus,
+                },
+            )
+            .collect())
+    }
+
+    async fn set_consistency(&self, user_id: &UserId, score: f64) -> Result<(), sqlx::Error> {
+        sqlx::query("UPDATE quality_profiles SET consistency_score = $2 WHERE user_id = $1")
+            .bind(user_id.as_uuid())
+            .bind(score)
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+
+    async fn list_gold_submissions(&self) -> Result<Vec<GoldSubmission>, sqlx::Error> {
+        let rows: Vec<(uuid::Uuid, uuid::Uuid, serde_json::Value, serde_json::Value)> =
+            sqlx::query_as(
+                r#"
+                SELECT DISTINCT ON (a.task_id, a.user_id) a.task_id, a.user_id, a.data, t.gold_output
+                FROM annotations a
+                JOIN tasks t ON t.project_id = a.project_id AND t.task_id = a.task_id
+                WHERE t.gold_output IS NOT NULL
+                  AND a.status IN ('submitted', 'approved', 'rejected')
+                ORDER BY a.task_id, a.user_id, a.version DESC
+                "#,
+            )
+            .fetch_all(&self.pool)
+            .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|(task_id, user_id, data, expected)| GoldSubmission {
+                task_id: TaskId::from_uuid(task_id),
+                user_id: UserId::from_uuid(user_id),
+                data,
+                expected,
+            })
+            .collect())
+    }
+
+    async fn set_gold_accuracy(&self, user_id: &UserId, accuracy: f64) -> Result<(), sqlx::Error> {
+        sqlx::query("UPDATE quality_profiles SET gold_accuracy_score = $2 WHERE user_id = $1")
+            .bind(user_id.as_uuid())
+            .bind(accuracy)
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+
+    async fn set_gold_alert(&self, user_id: &UserId, alerting: bool) -> Result<bool, sqlx::Error> {
+        let query = if alerting {
+            "UPDATE quality_profiles SET gold_alerted_at = NOW() \
+             WHERE user_id = $1 AND gold_alerted_at IS NULL"
+        } else {
+            "UPDATE quality_profiles SET gold_alerted_at = NULL \
+             WHERE user_id = $1 AND gold_alerted_at IS NOT NULL"
+        };
+        let result = sqlx::query(query)
+            .bind(user_id.as_uuid())
+            .execute(&self.pool)
+            .await?;
+
+        Ok(alerting && result.rows_affected() == 1)
+    }
+
+    async fn alert_recipients(&self, user_id: &UserId) -> Result<Vec<UserId>, sqlx::Error> {
+        let ids: Vec<uuid::Uuid> = sqlx::query_scalar(
+            r#"
+            WITH leaders AS (
+                SELECT DISTINCT leader.user_id
+                FROM team_memberships member
+                JOIN team_memberships leader
+                  ON leader.team_id = member.team_id AND leader.role = 'leader'
+                WHERE member.user_id = $1 AND leader.user_id != $1
+            )
+            SELECT user_id FROM leaders
+            UNION
+            SELECT user_id FROM users
+            WHERE global_role = 'admin' AND status != 'deleted'
+              AND NOT EXISTS (SELECT 1 FROM leaders)
+            "#,
+        )
+        .bind(user_id.as_uuid())
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(ids.into_iter().map(UserId::from_uuid).collect())
+    }
+}
+
+// Internal row type for SQLx mapping
+#[derive(sqlx::FromRow)]
+struct QualityProfileRow {
+    overall_score: Option<f64>,
+    accuracy_score: Option<f64>,
+    gold_accuracy_score: Option<f64>,
+    consistency_score: Option<f64>,
+    speed_percentile: Option<f64>,
+    total_annotations: i64,
+    approved_annotations: i64,
+    rejected_annotations: i64,
+}
+
+impl From<QualityProfileRow> for QualityProfile {
+    fn from(row: QualityProfileRow) -> Self {
+        Self {
+            overall_score: row.overall_score,
+            accuracy_score: row.accuracy_score,
+            gold_accuracy_score: row.gold_accuracy_score,
+            consistency_score: row.consistency_score,
+            speed_percentile: row.speed_percentile,
+            total_annotations: row.total_annotations,
+            approved_annotations: row.approved_annotations,
+            rejected_annotations: row.rejected_annotations,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_row_conversion_preserves_nulls() {
+        let row = QualityProfileRow {
+            overall_score: None,
+            accuracy_score: Some(0.9),
+            gold_accuracy_score: None,
+            consistency_score: None,
+            speed_percentile: None,
+            total_annotations: 10,
+            approved_annotations: 9,
+            rejected_annotations: 1,
+        };
+        let profile: QualityProfile = row.into();
+        assert_eq!(profile.overall_score, None);
+        assert_eq!(profile.accuracy_score, Some(0.9));
+        assert_eq!(profile.total_annotations, 10);
+        assert_eq!(profile.rejected_annotations, 1);
+    }
+
+    #[tokio::test]
+    #[ignore = "requires DATABASE_URL pointing at a migrated database"]
+    async fn test_gold_accuracy_is_kept_apart_from_approval_accuracy() {
+        let url = std::env::var("DATABASE_URL").expect("DATABASE_URL must be set");
+        let pool = PgPool::connect(&url).await.unwrap();
+        let repo = PgQualityProfileRepository::new(pool.clone());
+
+        let user_id = UserId::new();
+        sqlx::query(
+            r#"
+            INSERT INTO users (user_id, email, display_name, auth0_id, global_role, status)
+            VALUES ($1, $2, 'Gold', $3, 'user', 'active')
+            "#,
+        )
+        .bind(user_id.as_uuid())
+        .bind(format!("{user_id}@quality.test"))
+        .bind(format!("test|{user_id}"))
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        let approval = QualityProfile {
+            accuracy_score: Some(0.9),
+            ..Default::default()
+        };
+        repo.upsert(&user_id, &approval).await.unwrap();
+        repo.set_gold_accuracy(&user_id, 0.5).await.unwrap();
+
+        // Recomputing the approval rate leaves the gold score alone
+        let recomputed = QualityProfile {
+            accuracy_score: Some(0.8),
+            ..Default::default()
+        };
+        let profile = repo.upsert(&user_id, &recomputed).await.unwrap();
+        assert_eq!(profile.accuracy_score, Some(0.8));
+        assert_eq!(profile.gold_accuracy_score, Some(0.5));
+    }
+
+    #[tokio::test]
+    #[ignore = "requires DATABASE_URL pointing at a migrated database"]
+    async fn test_consensus_comparisons_leave_out_the_annotators_own_vote() {
+        let url = std::env::var("DATABASE_URL").expect("DATABASE_URL must be set");
+        let pool = PgPool::connect(&url).await.unwrap();
+        let repo = PgQualityProfileRepository::new(pool.clone());
+
+        let mut users = Vec::new();
+        for name in ["Ann", "Ben", "Cat", "Dan"] {
+            let user_id = UserId::new();
+            sqlx::query(
+                r#"
+                INSERT INTO users (user_id, email, display_name, auth0_id, global_role, status)
+                VALUES ($1, $2, $3, $4, 'user', 'active')
+                "#,
+            )
+            .bind(user_id.as_uuid())
+            .bind(format!("{user_id}@consistency.test"))
+            .bind(name)
+            .bind(format!("test|{user_id}"))
+            .execute(&pool)
+            .await
+            .unwrap();
+            users.push(user_id);
+        }
+        let project_id: uuid::Uuid = sqlx::query_scalar(
+            r#"
+            WITH pt AS (
+                INSERT INTO project_types (name) VALUES ($1) RETURNING project_type_id
+            )
+            INSERT INTO projects (name, project_type_id, created_by)
+            SELECT 'Consistency', project_type_id, $2 FROM pt
+            RETURNING project_id
+            "#,
+        )
+        .bind(format!("Consistency {}", uuid::Uuid::new_v4()))
+        .bind(users[0].as_uuid())
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+
+        async fn submit(
+            pool: &PgPool,
+            project_id: uuid::Uuid,
+            task_id: uuid::Uuid,
+            user_id: &UserId,
+            label: &str,
+        ) {
+            sqlx::query(
+                r#"
+                WITH assignment AS (
+                    INSERT INTO task_assignments (task_id, project_id, step_id, user_id)
+                    VALUES ($1, $2, 'annotate', $3)
+                    RETURNING assignment_id
+                )
+                INSERT INTO annotations (task_id, step_id, user_id, assignment_id, project_id,
+                                         data, status)
+                SELECT $1, 'annotate', $3, assignment_id, $2, jsonb_build_object('label', $4::text),
+                       'submitted'
+                FROM assignment
+                "#,
+            )
+            .bind(task_id)
+            .bind(project_id)
+            .bind(user_id.as_uuid())
+            .bind(label)
+            .execute(pool)
+            .await
+            .unwrap();
+        }
+        let new_task = || async {
+            sqlx::query_scalar::<_, uuid::Uuid>(
+                "INSERT INTO tasks (project_id, input_data) VALUES ($1, '{}') RETURNING task_id",
+            )
+            .bind(project_id)
+            .fetch_one(&pool)
+            .await
+            .unwrap()
+        };
+
+        // Task one: three say cat, one says dog. Task two: two against two.
+        let tasks = [["cat", "cat", "cat", "dog"], ["cat", "cat", "dog", "dog"]];
+        let mut task_ids = Vec::new();
+        for labels in tasks {
+            let task_id = new_task().await;
+            for (user_id, label) in users.iter().zip(labels) {
+                submit(&pool, project_id, task_id, user_id, label).await;
+            }
+            task_ids.push(task_id);
+        }
+
+        let comparisons = repo.list_consensus_comparisons().await.unwrap();
+        let consensus_for = |user_id: &UserId, task_id: &uuid::Uuid| {
+            comparisons
+                .iter()
+                .find(|c| c.user_id == *user_id && c.task_id.as_uuid() == task_id)
+                .unwrap()
+                .consensus
+                .clone()
+        };
+        let cat = Some(serde_json::json!({ "label": "cat" }));
+        let dog = Some(serde_json::json!({ "label": "dog" }));
+        // On task one every annotator sees a cat majority among the others,
+        // even the cat voters once their own vote is left out. On task two
+        // leaving out the annotator's own vote hands the other label the
+        // majority.
+        for (user_id, expected) in users.iter().zip([&cat, &cat, &cat, &cat]) {
+            assert_eq!(&consensus_for(user_id, &task_ids[0]), expected);
+        }
+        for (user_id, expected) in users.iter().zip([&dog, &dog, &cat, &cat]) {
+            assert_eq!(&consensus_for(user_id, &task_ids[1]), expected);
+        }
+
+        // A step with only two annotators has no consensus for either
+        let task_id = new_task().await;
+        for user_id in &users[..2] {
+            submit(&pool, project_id, task_id, user_id, "cat").await;
+        }
+        let comparisons = repo.list_consensus_comparisons().await.unwrap();
+        assert!(comparisons
+            .iter()
+            .filter(|c| *c.task_id.as_uuid() == task_id)
+            .all(|c| c.consensus.is_none()));
+
+        repo.set_consistency(&users[0], 0.5).await.unwrap();
+        assert!(repo.find_by_user(&users[0]).await.unwrap().is_none());
+        repo.upsert(&users[0], &QualityProfile::default())
+            .await
+            .unwrap();
+        repo.set_consistency(&users[0], 0.5).await.unwrap();
+        let profile = repo.find_by_user(&users[0]).await.unwrap().unwrap();
+        assert_eq!(profile.consistency_score, Some(0.5));
+    }
+}
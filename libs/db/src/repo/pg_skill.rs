@@ -1,7 +1,7 @@
 //! PostgreSQL implementation of SkillRepository
 
 use async_trait::async_trait;
-use chrono::{Duration, Utc};
+use chrono::{DateTime, Duration, Utc};
 use sqlx::PgPool;
 use uuid::Uuid;
 
@@ -20,6 +20,180 @@ impl PgSkillRepository {
     pub fn new(pool: PgPool) -> Self {
         Self { pool }
     }
+
+    /// Per-skill accuracy of every user with an unexpired certification.
+    ///
+    /// Accuracy is the mean quality score of the user's scored annotations on
+    /// projects whose type requires the skill, counting only work submitted
+    /// since `since` and since the user reached their current level.
+    pub async fn list_skill_accuracy(
+        &self,
+        since: DateTime<Utc>,
+    ) -> Result<Vec<SkillAccuracy>, sqlx::Error> {
+        let rows = sqlx::query_as::<_, SkillAccuracyRow>(
+            r#"
+            SELECT us.certification_id, us.user_id, us.skill_id, us.proficiency_level,
+                   st.proficiency_levels,
+                   COUNT(a.quality_score) AS samples,
+                   AVG(a.quality_score) AS accuracy
+            FROM user_skills us
+            JOIN skill_types st ON st.skill_id = us.skill_id
+            JOIN project_type_skill_requirements r ON r.skill_id = us.skill_id
+            JOIN projects p ON p.project_type_id = r.project_type_id
+            JOIN annotations a ON a.project_id = p.project_id AND a.user_id = us.user_id
+            WHERE a.quality_score IS NOT NULL
+              AND a.submitted_at >= GREATEST($1, us.certified_at, us.level_changed_at)
+              AND (us.expires_at IS NULL OR us.expires_at > NOW())
+            GROUP BY us.certification_id, us.user_id, us.skill_id, us.proficiency_level,
+                     st.proficiency_levels
+            "#,
+        )
+        .bind(since)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(rows.into_iter().map(Into::into).collect())
+    }
+
+    /// Change the proficiency level on a user's certification.
+    ///
+    /// Only applies while the level is still `from`, so a concurrent manual
+    /// change wins; returns whether the level was changed.
+    pub async fn set_proficiency_level(
+        &self,
+        user_id: &UserId,
+        skill_id: &str,
+        from: &str,
+        to: &str,
+    ) -> Result<bool, sqlx::Error> {
+        let result = sqlx::query(
+            r#"
+            UPDATE user_skills SET proficiency_level = $4, level_changed_at = NOW()
+            WHERE user_id = $1 AND skill_id = $2 AND proficiency_level = $3
+            "#,
+        )
+        .bind(user_id.as_uuid())
+        .bind(skill_id)
+        .bind(from)
+        .bind(to)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+}
+
+// =============================================================================
+// Proficiency Decay
+// =============================================================================
+
+/// Default time for unused proficiency to halve
+pub const DEFAULT_PROFICIENCY_HALF_LIFE_DAYS: i64 = 90;
+
+/// Exponential decay of skill proficiency over inactivity.
+///
+/// Proficiency halves every `half_life` without relevant work. Decayed values
+/// only rank candidates; they never make a certified user ineligible.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ProficiencyDecay {
+    half_life: Duration,
+}
+
+impl Default for ProficiencyDecay {
+    fn default() -> Self {
+        Self::new(Duration::days(DEFAULT_PROFICIENCY_HALF_LIFE_DAYS))
+    }
+}
+
+impl ProficiencyDecay {
+    /// Decay with the given half-life; a non-positive half-life disables decay
+    pub fn new(half_life: Duration) -> Self {
+        Self { half_life }
+    }
+
+    pub fn half_life(&self) -> Duration {
+        self.half_life
+    }
+
+    /// Fraction of proficiency left after inactivity since `last_active`
+    pub fn factor(&self, last_active: DateTime<Utc>, now: DateTime<Utc>) -> f64 {
+        let half_life = self.half_life.num_seconds();
+        let idle = (now - last_active).num_seconds();
+        if half_life <= 0 || idle <= 0 {
+            return 1.0;
+        }
+        #[allow(clippy::cast_precision_loss)]
+        let half_lives = idle as f64 / half_life as f64;
+        0.5_f64.powf(half_lives)
+    }
+
+    /// Decay a proficiency score for inactivity since `last_active`
+    pub fn apply(&self, proficiency: f64, last_active: DateTime<Utc>, now: DateTime<Utc>) -> f64 {
+        proficiency * self.factor(last_active, now)
+    }
+
+    /// SQL computing [`Self::factor`] as of `NOW()`, given an SQL timestamp
+    /// expression for when the user was last active.
+    ///
+    /// Evaluates to 1 when `last_active` is NULL. Uses numeric arithmetic,
+    /// which unlike float8 doesn't fail on underflow after long inactivity.
+    pub fn sql_factor(&self, last_active: &str) -> String {
+        let half_life = self.half_life.num_seconds();
+        if half_life <= 0 {
+            return "1.0".to_string();
+        }
+        format!(
+            "COALESCE(POWER(0.5, GREATEST(0, EXTRACT(EPOCH FROM (NOW() - ({last_active})))) \
+             / {half_life}), 1.0)"
+        )
+    }
+}
+
+/// Built-in proficiency levels, lowest first
+pub const PROFICIENCY_LADDER: [&str; 4] = ["novice", "intermediate", "advanced", "expert"];
+
+/// The level above `level` on the built-in ladder.
+///
+/// `None` for expert, which is the cap, and for custom levels.
+pub fn next_proficiency_level(level: &str) -> Option<&'static str> {
+    let index = PROFICIENCY_LADDER.iter().position(|l| *l == level)?;
+    PROFICIENCY_LADDER.get(index + 1).copied()
+}
+
+/// A certified user's recent accuracy on work requiring a skill
+#[derive(Debug, Clone, PartialEq)]
+pub struct SkillAccuracy {
+    pub certification_id: Uuid,
+    pub user_id: UserId,
+    pub skill_id: String,
+    pub proficiency_level: Option<String>,
+    /// Levels the skill type allows, if it restricts them
+    pub allowed_levels: Option<Vec<String>>,
+    /// Scored annotations the accuracy is based on
+    pub samples: i64,
+    /// Mean quality score over those annotations
+    pub accuracy: f64,
+}
+
+/// Numeric proficiency for ranking, from 0.25 (novice) to 1.0 (expert).
+///
+/// Skills certified without a level, or with a custom level, count as full
+/// proficiency.
+pub fn proficiency_score(level: Option<&str>) -> f64 {
+    match level {
+        Some("novice") => 0.25,
+        Some("intermediate") => 0.5,
+        Some("advanced") => 0.75,
+        _ => 1.0,
+    }
+}
+
+/// SQL for [`proficiency_score`] of a proficiency level column
+pub fn proficiency_score_sql(level: &str) -> String {
+    format!(
+        "CASE {level} WHEN 'novice' THEN 0.25 WHEN 'intermediate' THEN 0.5 \
+         WHEN 'advanced' THEN 0.75 ELSE 1.0 END"
+    )
 }
 
 #[async_trait]
@@ -315,6 +489,33 @@ impl TryFrom<UserSkillRow> for UserSkillWithStatus {
     }
 }
 
+#[derive(sqlx::FromRow)]
+struct SkillAccuracyRow {
+    certification_id: Uuid,
+    user_id: Uuid,
+    skill_id: String,
+    proficiency_level: Option<String>,
+    proficiency_levels: Option<serde_json::Value>,
+    samples: i64,
+    accuracy: Option<f64>,
+}
+
+impl From<SkillAccuracyRow> for SkillAccuracy {
+    fn from(r: SkillAccuracyRow) -> Self {
+        Self {
+            certification_id: r.certification_id,
+            user_id: UserId::from_uuid(r.user_id),
+            skill_id: r.skill_id,
+            proficiency_level: r.proficiency_level,
+            allowed_levels: r
+                .proficiency_levels
+                .and_then(|v| serde_json::from_value(v).ok()),
+            samples: r.samples,
+            accuracy: r.accuracy.unwrap_or(0.0),
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -329,4 +530,209 @@ mod tests {
         let diff = (expires_at - Utc::now()).num_days();
         assert!(diff >= expected_days - 1 && diff <= expected_days + 1);
     }
+
+    #[test]
+    fn test_proficiency_decays_over_inactivity() {
+        let decay = ProficiencyDecay::new(Duration::days(30));
+        let last_active = Utc::now();
+        let expert = proficiency_score(Some("expert"));
+
+        let at = |days| decay.apply(expert, last_active, last_active + Duration::days(days));
+        assert!((at(0) - 1.0).abs() < 1e-9);
+        assert!((at(30) - 0.5).abs() < 1e-9);
+        assert!((at(60) - 0.25).abs() < 1e-9);
+        assert!(at(10) < at(0) && at(45) < at(10));
+
+        // A recently active novice can outrank a long-idle expert
+        let novice = decay.apply(proficiency_score(Some("novice")), last_active, last_active);
+        assert!(novice > at(90));
+    }
+
+    #[test]
+    fn test_non_positive_half_life_disables_decay() {
+        let decay = ProficiencyDecay::new(Duration::zero());
+        let last_active = Utc::now();
+        assert!((decay.factor(last_active, last_active + Duration::days(365)) - 1.0).abs() < 1e-9);
+        assert_eq!(decay.sql_factor("us.certified_at"), "1.0");
+    }
+
+    #[test]
+    fn test_next_proficiency_level_caps_at_expert() {
+        assert_eq!(next_proficiency_level("novice"), Some("intermediate"));
+        assert_eq!(next_proficiency_level("advanced"), Some("expert"));
+        assert_eq!(next_proficiency_level("expert"), None);
+        assert_eq!(next_proficiency_level("guru"), None);
+    }
+
+    #[tokio::test]
+    #[ignore = "requires DATABASE_URL pointing at a migrated database"]
+    async fn test_sql_scores_match_rust() {
+        let url = std::env::var("DATABASE_URL").expect("DATABASE_URL must be set");
+        let pool = PgPool::connect(&url).await.unwrap();
+        let decay = ProficiencyDecay::new(Duration::days(30));
+
+        for days in [0, 30, 60, 3650] {
+            let factor: f64 = sqlx::query_scalar(&format!(
+                "SELECT ({})::float8",
+                decay.sql_factor("NOW() - make_interval(days => $1)")
+            ))
+            .bind(days)
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+            let now = Utc::now();
+            let expected = decay.factor(now - Duration::days(i64::from(days)), now);
+            assert!((factor - expected).abs() < 1e-6, "{days} days: {factor}");
+        }
+        let never: f64 = sqlx::query_scalar(&format!(
+            "SELECT ({})::float8",
+            decay.sql_factor("NULL::timestamptz")
+        ))
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+        assert!((never - 1.0).abs() < 1e-9);
+
+        for level in [Some("novice"), Some("advanced"), Some("custom"), None] {
+            let score: f64 = sqlx::query_scalar(&format!(
+                "SELECT ({})::float8",
+                proficiency_score_sql("$1::text")
+            ))
+            .bind(level)
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+            assert!((score - proficiency_score(level)).abs() < 1e-9, "{level:?}");
+        }
+    }
+
+    #[tokio::test]
+    #[ignore = "requires DATABASE_URL pointing at a migrated database"]
+    async fn test_skill_accuracy_counts_work_at_the_current_level_only() {
+        let url = std::env::var("DATABASE_URL").expect("DATABASE_URL must be set");
+        let pool = PgPool::connect(&url).await.unwrap();
+        let repo = PgSkillRepository::new(pool.clone());
+
+        let user_id = UserId::new();
+        let skill_id = format!("acc-{}", Uuid::new_v4().simple());
+        sqlx::query(
+            r#"
+            INSERT INTO users (user_id, email, display_name, auth0_id, global_role, status)
+            VALUES ($1, $2, 'Accuracy', $3, 'user', 'active')
+            "#,
+        )
+        .bind(user_id.as_uuid())
+        .bind(format!("{user_id}@accuracy.test"))
+        .bind(format!("test|{user_id}"))
+        .execute(&pool)
+        .await
+        .unwrap();
+        sqlx::query("INSERT INTO skill_types (skill_id, name) VALUES ($1, $1)")
+            .bind(&skill_id)
+            .execute(&pool)
+            .await
+            .unwrap();
+        let project_type_id: Uuid = sqlx::query_scalar(
+            "INSERT INTO project_types (name) VALUES ($1) RETURNING project_type_id",
+        )
+        .bind(&skill_id)
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+        sqlx::query(
+            "INSERT INTO project_type_skill_requirements (project_type_id, skill_id) VALUES ($1, $2)",
+        )
+        .bind(project_type_id)
+        .bind(&skill_id)
+        .execute(&pool)
+        .await
+        .unwrap();
+        let project_id: Uuid = sqlx::query_scalar(
+            r#"
+            INSERT INTO projects (name, project_type_id, created_by)
+            VALUES ('Accuracy', $1, $2) RETURNING project_id
+            "#,
+        )
+        .bind(project_type_id)
+        .bind(user_id.as_uuid())
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+
+        // Certified ten days ago; one annotation before that and two after
+        sqlx::query(
+            r#"
+            INSERT INTO user_skills (user_id, skill_id, proficiency_level, certified_at)
+            VALUES ($1, $2, 'novice', NOW() - INTERVAL '10 days')
+            "#,
+        )
+        .bind(user_id.as_uuid())
+        .bind(&skill_id)
+        .execute(&pool)
+        .await
+        .unwrap();
+        for days_ago in [20, 5, 1] {
+            let task_id: Uuid = sqlx::query_scalar(
+                "INSERT INTO tasks (project_id, input_data) VALUES ($1, '{}') RETURNING task_id",
+            )
+            .bind(project_id)
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+            sqlx::query(
+                r#"
+                WITH assignment AS (
+                    INSERT INTO task_assignments (task_id, project_id, step_id, user_id)
+                    VALUES ($1, $2, 'annotate', $3)
+                    RETURNING assignment_id
+                )
+                INSERT INTO annotations (task_id, step_id, user_id, assignment_id, project_id,
+                                         data, status, submitted_at, quality_score)
+                SELECT $1, 'annotate', $3, assignment_id, $2, '{}', 'submitted',
+                       NOW() - make_interval(days => $4), 0.95
+                FROM assignment
+                "#,
+            )
+            .bind(task_id)
+            .bind(project_id)
+            .bind(user_id.as_uuid())
+            .bind(days_ago)
+            .execute(&pool)
+            .await
+            .unwrap();
+        }
+
+        let samples = || async {
+            repo.list_skill_accuracy(Utc::now() - Duration::days(90))
+                .await
+                .unwrap()
+                .into_iter()
+                .find(|a| a.user_id == user_id && a.skill_id == skill_id)
+                .map(|a| a.samples)
+        };
+        assert_eq!(samples().await, Some(2));
+
+        // Three days ago the user was promoted; only later work counts
+        repo.set_proficiency_level(&user_id, &skill_id, "novice", "intermediate")
+            .await
+            .unwrap();
+        sqlx::query(
+            "UPDATE user_skills SET level_changed_at = NOW() - INTERVAL '3 days' WHERE user_id = $1",
+        )
+        .bind(user_id.as_uuid())
+        .execute(&pool)
+        .await
+        .unwrap();
+        assert_eq!(samples().await, Some(1));
+
+        // Expired certifications are never promoted
+        sqlx::query(
+            "UPDATE user_skills SET expires_at = NOW() - INTERVAL '1 day' WHERE user_id = $1",
+        )
+        .bind(user_id.as_uuid())
+        .execute(&pool)
+        .await
+        .unwrap();
+        assert_eq!(samples().await, None);
+    }
 }
@@ -4,80 +4,162 @@
 //! They currently return `todo!()` to allow compilation.
 
 use async_trait::async_trait;
-use sqlx::PgPool;
+use serde::{Deserialize, Serialize};
+use sqlx::{PgExecutor, PgPool, Postgres, Transaction};
 
-use glyph_domain::{Annotation, Workflow};
-use glyph_domain::{AnnotationId, TaskId, WorkflowId};
+use glyph_domain::{OrgId, Workflow, WorkflowId};
 
 use crate::pagination::{Page, Pagination};
 use crate::repo::errors::*;
 use crate::repo::traits::*;
 
 // =============================================================================
-// Annotation Repository Stub
+// Workflow Repository Stub
 // =============================================================================
 
-pub struct PgAnnotationRepository {
-    #[allow(dead_code)]
+pub struct PgWorkflowRepository {
     pool: PgPool,
 }
 
-impl PgAnnotationRepository {
+/// A workflow row without its ID, for copying a workflow between instances.
+///
+/// The JSON columns are kept as-is so nothing is lost in transit.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, sqlx::FromRow)]
+pub struct WorkflowDefinition {
+    pub name: String,
+    pub workflow_type: String,
+    pub entry_step_id: String,
+    pub exit_step_ids: serde_json::Value,
+    pub steps: serde_json::Value,
+    pub transitions: serde_json::Value,
+    pub settings: serde_json::Value,
+    pub hooks: serde_json::Value,
+}
+
+impl PgWorkflowRepository {
     pub fn new(pool: PgPool) -> Self {
         Self { pool }
     }
-}
 
-#[async_trait]
-impl AnnotationRepository for PgAnnotationRepository {
-    async fn find_by_id(
-        &self,
-        _id: &AnnotationId,
-    ) -> Result<Option<Annotation>, FindAnnotationError> {
-        todo!("Implement in Phase 9")
+    /// Find a workflow's ID by name; the oldest wins if names repeat
+    pub async fn find_id_by_name(&self, name: &str) -> Result<Option<WorkflowId>, sqlx::Error> {
+        let id = sqlx::query_scalar::<_, uuid::Uuid>(
+            "SELECT workflow_id FROM workflows WHERE name = $1 ORDER BY created_at LIMIT 1",
+        )
+        .bind(name)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(id.map(WorkflowId::from_uuid))
     }
 
-    async fn create(
-        &self,
-        _annotation: &NewAnnotation,
-    ) -> Result<Annotation, CreateAnnotationError> {
-        todo!("Implement in Phase 9")
+    /// The workflow assigned on activation to projects without one, as its
+    /// ID and name
+    pub async fn find_default(&self) -> Result<Option<(WorkflowId, String)>, sqlx::Error> {
+        let row = sqlx::query_as::<_, (uuid::Uuid, String)>(
+            "SELECT workflow_id, name FROM workflows WHERE is_default",
+        )
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(row.map(|(id, name)| (WorkflowId::from_uuid(id), name)))
     }
 
-    async fn update(
-        &self,
-        _id: &AnnotationId,
-        _update: &AnnotationUpdate,
-    ) -> Result<Annotation, UpdateAnnotationError> {
-        todo!("Implement in Phase 9")
+    /// Make a workflow the default, replacing any previous one.
+    ///
+    /// Returns `false` if the workflow doesn't exist.
+    pub async fn set_default(&self, id: &WorkflowId) -> Result<bool, sqlx::Error> {
+        let mut tx = self.pool.begin().await?;
+        sqlx::query("UPDATE workflows SET is_default = FALSE WHERE is_default")
+            .execute(&mut *tx)
+            .await?;
+        let result = sqlx::query("UPDATE workflows SET is_default = TRUE WHERE workflow_id = $1")
+            .bind(id.as_uuid())
+            .execute(&mut *tx)
+            .await?;
+        if result.rows_affected() == 0 {
+            return Ok(false);
+        }
+        tx.commit().await?;
+        Ok(true)
     }
 
-    async fn list_by_task(
+    /// Whether any of an organization's projects runs the workflow
+    pub async fn is_used_in_org(
         &self,
-        _task_id: &TaskId,
-        _pagination: Pagination,
-    ) -> Result<Page<Annotation>, sqlx::Error> {
-        todo!("Implement in Phase 9")
+        id: &WorkflowId,
+        org_id: OrgId,
+    ) -> Result<bool, sqlx::Error> {
+        sqlx::query_scalar(
+            "SELECT EXISTS (SELECT 1 FROM projects WHERE workflow_id = $1 AND org_id = $2)",
+        )
+        .bind(id.as_uuid())
+        .bind(org_id.as_uuid())
+        .fetch_one(&self.pool)
+        .await
     }
 
-    async fn submit(&self, _id: &AnnotationId) -> Result<Annotation, UpdateAnnotationError> {
-        todo!("Implement in Phase 9")
+    /// A workflow's definition, or `None` if it doesn't exist
+    pub async fn find_definition(
+        &self,
+        id: &WorkflowId,
+    ) -> Result<Option<WorkflowDefinition>, sqlx::Error> {
+        sqlx::query_as::<_, WorkflowDefinition>(
+            r#"
+            SELECT name, workflow_type::text AS workflow_type, entry_step_id, exit_step_ids,
+                   steps, transitions, settings, hooks
+            FROM workflows
+            WHERE workflow_id = $1
+            "#,
+        )
+        .bind(id.as_uuid())
+        .fetch_optional(&self.pool)
+        .await
     }
-}
 
-// =============================================================================
-// Workflow Repository Stub
-// =============================================================================
+    /// Create a workflow from a definition, returning its new ID
+    pub async fn create_from_definition(
+        &self,
+        definition: &WorkflowDefinition,
+    ) -> Result<WorkflowId, sqlx::Error> {
+        insert_definition(&self.pool, definition).await
+    }
 
-pub struct PgWorkflowRepository {
-    #[allow(dead_code)]
-    pool: PgPool,
+    /// [`Self::create_from_definition`] inside a caller's transaction
+    pub async fn create_from_definition_in(
+        &self,
+        tx: &mut Transaction<'_, Postgres>,
+        definition: &WorkflowDefinition,
+    ) -> Result<WorkflowId, sqlx::Error> {
+        insert_definition(&mut **tx, definition).await
+    }
 }
 
-impl PgWorkflowRepository {
-    pub fn new(pool: PgPool) -> Self {
-        Self { pool }
-    }
+async fn insert_definition<'e, E: PgExecutor<'e>>(
+    executor: E,
+    definition: &WorkflowDefinition,
+) -> Result<WorkflowId, sqlx::Error> {
+    let id = WorkflowId::new();
+    sqlx::query(
+        r#"
+        INSERT INTO workflows (workflow_id, name, workflow_type, entry_step_id, exit_step_ids,
+                               steps, transitions, settings, hooks)
+        VALUES ($1, $2, $3::workflow_type, $4, $5, $6, $7, $8, $9)
+        "#,
+    )
+    .bind(id.as_uuid())
+    .bind(&definition.name)
+    .bind(&definition.workflow_type)
+    .bind(&definition.entry_step_id)
+    .bind(&definition.exit_step_ids)
+    .bind(&definition.steps)
+    .bind(&definition.transitions)
+    .bind(&definition.settings)
+    .bind(&definition.hooks)
+    .execute(executor)
+    .await?;
+
+    Ok(id)
 }
 
 #[async_trait]
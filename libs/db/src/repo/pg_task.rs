@@ -2,14 +2,19 @@
 //!
 //! Full implementation with audit trail integration.
 
+use std::collections::HashMap;
+
 use async_trait::async_trait;
-use sqlx::PgPool;
+use sqlx::{PgExecutor, PgPool, Postgres, Transaction};
 
 use glyph_domain::{ProjectId, Task, TaskId, TaskStatus, WorkflowState};
 
-use crate::audit::{AuditAction, AuditActorType, AuditEvent, AuditWriter, SYSTEM_ACTOR_ID};
+use crate::audit::{
+    AuditAction, AuditActorType, AuditError, AuditEvent, AuditWriter, SYSTEM_ACTOR_ID,
+};
 use crate::pagination::{Page, Pagination};
 use crate::repo::errors::{CreateTaskError, FindTaskError, UpdateTaskError};
+use crate::repo::pg_goal::PgGoalRepository;
 use crate::repo::traits::{NewTask, TaskRepository, TaskUpdate};
 
 /// PostgreSQL task repository
@@ -18,12 +23,174 @@ pub struct PgTaskRepository {
     audit: AuditWriter,
 }
 
+/// Every task status, so counts can include statuses with no tasks
+pub const TASK_STATUSES: [TaskStatus; 9] = [
+    TaskStatus::Pending,
+    TaskStatus::Assigned,
+    TaskStatus::InProgress,
+    TaskStatus::Review,
+    TaskStatus::Adjudication,
+    TaskStatus::Completed,
+    TaskStatus::Failed,
+    TaskStatus::Cancelled,
+    TaskStatus::Deleted,
+];
+
 impl PgTaskRepository {
     /// Create a new PostgreSQL task repository
     pub fn new(pool: PgPool) -> Self {
         let audit = AuditWriter::new(pool.clone());
         Self { pool, audit }
     }
+
+    /// Count a project's tasks by status.
+    ///
+    /// Every status is present, with zero for statuses that have no tasks.
+    pub async fn count_by_status(
+        &self,
+        project_id: &ProjectId,
+    ) -> Result<HashMap<TaskStatus, i64>, sqlx::Error> {
+        let rows = sqlx::query_as::<_, (String, i64)>(
+            r#"
+            SELECT status::text, COUNT(*)
+            FROM tasks
+            WHERE project_id = $1
+            GROUP BY status
+            "#,
+        )
+        .bind(project_id.as_uuid())
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(status_counts(rows))
+    }
+
+    /// Count the tasks of several projects by status in one query.
+    ///
+    /// Every requested project is present, and every status within it, with
+    /// zero where there are no tasks.
+    pub async fn count_by_status_for_projects(
+        &self,
+        project_ids: &[ProjectId],
+    ) -> Result<HashMap<ProjectId, HashMap<TaskStatus, i64>>, sqlx::Error> {
+        let ids: Vec<uuid::Uuid> = project_ids.iter().map(|id| *id.as_uuid()).collect();
+        let rows = sqlx::query_as::<_, (uuid::Uuid, String, i64)>(
+            r#"
+            SELECT project_id, status::text, COUNT(*)
+            FROM tasks
+            WHERE project_id = ANY($1)
+            GROUP BY project_id, status
+            "#,
+        )
+        .bind(&ids)
+        .fetch_all(&self.pool)
+        .await?;
+
+        let mut grouped: HashMap<ProjectId, Vec<(String, i64)>> =
+            project_ids.iter().map(|id| (*id, Vec::new())).collect();
+        for (project_id, status, count) in rows {
+            grouped
+                .entry(ProjectId::from_uuid(project_id))
+                .or_default()
+                .push((status, count));
+        }
+        Ok(grouped
+            .into_iter()
+            .map(|(project_id, rows)| (project_id, status_counts(rows)))
+            .collect())
+    }
+}
+
+impl PgTaskRepository {
+    /// Create a task inside a caller's transaction, with its audit event, so
+    /// both are discarded if the transaction rolls back
+    pub async fn create_in(
+        &self,
+        tx: &mut Transaction<'_, Postgres>,
+        new_task: &NewTask,
+    ) -> Result<Task, CreateTaskError> {
+        let task = insert_task(&mut **tx, new_task).await?;
+        AuditWriter::record_with(&mut **tx, create_event(&task))
+            .await
+            .map_err(|e| match e {
+                AuditError::Database(e) => CreateTaskError::Database(e),
+                AuditError::Serialization(e) => {
+                    CreateTaskError::Database(sqlx::Error::Decode(Box::new(e)))
+                }
+            })?;
+        Ok(task)
+    }
+}
+
+async fn insert_task<'e, E: PgExecutor<'e>>(
+    executor: E,
+    new_task: &NewTask,
+) -> Result<Task, CreateTaskError> {
+    let id = TaskId::new();
+
+    let row = sqlx::query_as::<_, TaskRow>(
+        r#"
+        INSERT INTO tasks (
+            task_id, project_id, input_data, priority, metadata, gold_output
+        )
+        VALUES ($1, $2, $3, COALESCE($4, 0), COALESCE($5, '{}'), $6)
+        RETURNING task_id::text, project_id::text, status::text, priority,
+                  input_data, workflow_state, metadata, gold_output,
+                  created_at, updated_at, completed_at
+        "#,
+    )
+    .bind(id.as_uuid())
+    .bind(new_task.project_id.as_uuid())
+    .bind(&new_task.input_data)
+    .bind(new_task.priority)
+    .bind(&new_task.metadata)
+    .bind(&new_task.gold_output)
+    .fetch_one(executor)
+    .await
+    .map_err(|e| {
+        // Check for foreign key violation (project not found)
+        if let Some(db_err) = e.as_database_error() {
+            if db_err.constraint() == Some("tasks_project_id_fkey") {
+                return CreateTaskError::ProjectNotFound(new_task.project_id.clone());
+            }
+        }
+        CreateTaskError::Database(e)
+    })?;
+
+    row.try_into()
+        .map_err(|_| CreateTaskError::Database(sqlx::Error::RowNotFound))
+}
+
+fn create_event(task: &Task) -> AuditEvent {
+    AuditEvent {
+        entity_type: "task",
+        entity_id: task.task_id.to_string(),
+        action: AuditAction::Create,
+        actor_id: SYSTEM_ACTOR_ID.to_string(),
+        actor_type: AuditActorType::System,
+        data_snapshot: serde_json::to_value(task).unwrap_or_default(),
+        changes: None,
+        request_id: None,
+    }
+}
+
+/// Total of per-status counts, excluding cancelled and deleted tasks
+pub fn live_task_total(counts: &HashMap<TaskStatus, i64>) -> i64 {
+    counts
+        .iter()
+        .filter(|(status, _)| !matches!(status, TaskStatus::Cancelled | TaskStatus::Deleted))
+        .map(|(_, count)| count)
+        .sum()
+}
+
+/// Counts per status from `(status, count)` rows, zero-filled
+fn status_counts(rows: Vec<(String, i64)>) -> HashMap<TaskStatus, i64> {
+    let mut counts: HashMap<TaskStatus, i64> =
+        TASK_STATUSES.iter().map(|status| (*status, 0)).collect();
+    for (status, count) in rows {
+        *counts.entry(parse_task_status(&status)).or_default() += count;
+    }
+    counts
 }
 
 #[async_trait]
@@ -32,7 +199,7 @@ impl TaskRepository for PgTaskRepository {
         let row = sqlx::query_as::<_, TaskRow>(
             r#"
             SELECT task_id::text, project_id::text, status::text, priority,
-                   input_data, workflow_state, metadata,
+                   input_data, workflow_state, metadata, gold_output,
                    created_at, updated_at, completed_at
             FROM tasks
             WHERE task_id = $1 AND status != 'deleted'
@@ -49,53 +216,10 @@ impl TaskRepository for PgTaskRepository {
     }
 
     async fn create(&self, new_task: &NewTask) -> Result<Task, CreateTaskError> {
-        let id = TaskId::new();
-
-        let row = sqlx::query_as::<_, TaskRow>(
-            r#"
-            INSERT INTO tasks (
-                task_id, project_id, input_data, priority, metadata
-            )
-            VALUES ($1, $2, $3, COALESCE($4, 0), COALESCE($5, '{}'))
-            RETURNING task_id::text, project_id::text, status::text, priority,
-                      input_data, workflow_state, metadata,
-                      created_at, updated_at, completed_at
-            "#,
-        )
-        .bind(id.as_uuid())
-        .bind(new_task.project_id.as_uuid())
-        .bind(&new_task.input_data)
-        .bind(new_task.priority)
-        .bind(&new_task.metadata)
-        .fetch_one(&self.pool)
-        .await
-        .map_err(|e| {
-            // Check for foreign key violation (project not found)
-            if let Some(db_err) = e.as_database_error() {
-                if db_err.constraint() == Some("tasks_project_id_fkey") {
-                    return CreateTaskError::ProjectNotFound(new_task.project_id.clone());
-                }
-            }
-            CreateTaskError::Database(e)
-        })?;
-
-        let task: Task = row
-            .try_into()
-            .map_err(|_| CreateTaskError::Database(sqlx::Error::RowNotFound))?;
+        let task = insert_task(&self.pool, new_task).await?;
 
         // Record audit event
-        self.audit
-            .record_best_effort(AuditEvent {
-                entity_type: "task",
-                entity_id: task.task_id.to_string(),
-                action: AuditAction::Create,
-                actor_id: SYSTEM_ACTOR_ID.to_string(),
-                actor_type: AuditActorType::System,
-                data_snapshot: serde_json::to_value(&task).unwrap_or_default(),
-                changes: None,
-                request_id: None,
-            })
-            .await;
+        self.audit.record_best_effort(create_event(&task)).await;
 
         Ok(task)
     }
@@ -120,7 +244,7 @@ impl TaskRepository for PgTaskRepository {
         let row = sqlx::query_as::<_, TaskRow>(
             r#"
             UPDATE tasks
-            SET status = COALESCE($2, status),
+            SET status = COALESCE($2::task_status, status),
                 priority = COALESCE($3, priority),
                 metadata = COALESCE($4, metadata),
                 updated_at = NOW(),
@@ -130,7 +254,7 @@ impl TaskRepository for PgTaskRepository {
                 END
             WHERE task_id = $1 AND status != 'deleted'
             RETURNING task_id::text, project_id::text, status::text, priority,
-                      input_data, workflow_state, metadata,
+                      input_data, workflow_state, metadata, gold_output,
                       created_at, updated_at, completed_at
             "#,
         )
@@ -151,6 +275,22 @@ impl TaskRepository for PgTaskRepository {
         let new_snapshot = serde_json::to_value(&task).unwrap_or_default();
         let changes = AuditWriter::compute_changes(&old_snapshot, &new_snapshot);
 
+        if set_completed {
+            // Like the audit trail, goal progress never fails the update; the
+            // next completion recomputes it from scratch
+            match PgGoalRepository::new(self.pool.clone())
+                .refresh_progress(&task.project_id)
+                .await
+            {
+                Ok(reached) => {
+                    for goal_id in reached {
+                        tracing::info!(%goal_id, project_id = %task.project_id, "Goal reached its target");
+                    }
+                }
+                Err(e) => tracing::warn!("Failed to refresh goal progress: {}", e),
+            }
+        }
+
         // Record audit event
         self.audit
             .record_best_effort(AuditEvent {
@@ -183,7 +323,7 @@ impl TaskRepository for PgTaskRepository {
         let rows = sqlx::query_as::<_, TaskRow>(
             r#"
             SELECT task_id::text, project_id::text, status::text, priority,
-                   input_data, workflow_state, metadata,
+                   input_data, workflow_state, metadata, gold_output,
                    created_at, updated_at, completed_at
             FROM tasks
             WHERE project_id = $1 AND status != 'deleted'
@@ -252,6 +392,34 @@ impl TaskRepository for PgTaskRepository {
 
         Ok(())
     }
+
+    async fn get_cooldown(
+        &self,
+        id: &TaskId,
+    ) -> Result<Option<chrono::DateTime<chrono::Utc>>, FindTaskError> {
+        sqlx::query_scalar("SELECT cooldown_until FROM tasks WHERE task_id = $1")
+            .bind(id.as_uuid())
+            .fetch_optional(&self.pool)
+            .await
+            .map_err(FindTaskError::Database)?
+            .ok_or_else(|| FindTaskError::NotFound(*id))
+    }
+
+    async fn clear_cooldown(&self, id: &TaskId) -> Result<(), UpdateTaskError> {
+        let result = sqlx::query(
+            "UPDATE tasks SET cooldown_until = NULL, updated_at = NOW() WHERE task_id = $1",
+        )
+        .bind(id.as_uuid())
+        .execute(&self.pool)
+        .await
+        .map_err(UpdateTaskError::Database)?;
+
+        if result.rows_affected() == 0 {
+            return Err(UpdateTaskError::NotFound(*id));
+        }
+
+        Ok(())
+    }
 }
 
 // =============================================================================
@@ -268,7 +436,7 @@ impl PgTaskRepository {
         let row = sqlx::query_as::<_, TaskRow>(
             r#"
             SELECT task_id::text, project_id::text, status::text, priority,
-                   input_data, workflow_state, metadata,
+                   input_data, workflow_state, metadata, gold_output,
                    created_at, updated_at, completed_at
             FROM tasks
             WHERE task_id = $1 AND project_id = $2 AND status != 'deleted'
@@ -298,7 +466,7 @@ impl PgTaskRepository {
                 updated_at = NOW()
             WHERE task_id = $1 AND status != 'deleted'
             RETURNING task_id::text, project_id::text, status::text, priority,
-                      input_data, workflow_state, metadata,
+                      input_data, workflow_state, metadata, gold_output,
                       created_at, updated_at, completed_at
             "#,
         )
@@ -333,7 +501,7 @@ impl PgTaskRepository {
         let rows = sqlx::query_as::<_, TaskRow>(
             r#"
             SELECT task_id::text, project_id::text, status::text, priority,
-                   input_data, workflow_state, metadata,
+                   input_data, workflow_state, metadata, gold_output,
                    created_at, updated_at, completed_at
             FROM tasks
             WHERE project_id = $1 AND status = $2::task_status
@@ -367,6 +535,7 @@ struct TaskRow {
     input_data: serde_json::Value,
     workflow_state: serde_json::Value,
     metadata: serde_json::Value,
+    gold_output: Option<serde_json::Value>,
     created_at: chrono::DateTime<chrono::Utc>,
     updated_at: chrono::DateTime<chrono::Utc>,
     completed_at: Option<chrono::DateTime<chrono::Utc>>,
@@ -398,6 +567,7 @@ impl TryFrom<TaskRow> for Task {
             input_data: row.input_data,
             workflow_state: serde_json::from_value(row.workflow_state).unwrap_or_default(),
             metadata: row.metadata,
+            gold_output: row.gold_output,
             created_at: row.created_at,
             updated_at: row.updated_at,
             completed_at: row.completed_at,
@@ -419,3 +589,150 @@ fn parse_task_status(s: &str) -> TaskStatus {
         _ => TaskStatus::Pending,
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use glyph_domain::UserId;
+
+    use super::*;
+    use crate::PgProjectRepository;
+
+    #[test]
+    fn test_status_counts_include_zero_entries() {
+        let counts = status_counts(vec![
+            ("pending".to_string(), 3),
+            ("completed".to_string(), 2),
+        ]);
+        assert_eq!(counts.len(), TASK_STATUSES.len());
+        assert_eq!(counts[&TaskStatus::Pending], 3);
+        assert_eq!(counts[&TaskStatus::Completed], 2);
+        assert_eq!(counts[&TaskStatus::Review], 0);
+    }
+
+    #[test]
+    fn test_live_task_total_skips_cancelled_and_deleted() {
+        let counts = status_counts(vec![
+            ("pending".to_string(), 3),
+            ("completed".to_string(), 2),
+            ("cancelled".to_string(), 4),
+            ("deleted".to_string(), 5),
+        ]);
+        assert_eq!(live_task_total(&counts), 5);
+    }
+
+    #[tokio::test]
+    #[ignore = "requires DATABASE_URL pointing at a migrated database"]
+    async fn test_count_by_status_over_seeded_mix() {
+        let url = std::env::var("DATABASE_URL").expect("DATABASE_URL must be set");
+        let pool = PgPool::connect(&url).await.unwrap();
+
+        let user_id = UserId::new();
+        sqlx::query(
+            r#"
+            INSERT INTO users (user_id, email, display_name, auth0_id, global_role, status)
+            VALUES ($1, $2, 'Task Counts', $3, 'user', 'active')
+            "#,
+        )
+        .bind(user_id.as_uuid())
+        .bind(format!("{user_id}@tasks.test"))
+        .bind(format!("test|{user_id}"))
+        .execute(&pool)
+        .await
+        .unwrap();
+        let project = PgProjectRepository::new(pool.clone())
+            .create_minimal("Task Counts", None, &user_id)
+            .await
+            .unwrap();
+
+        for (status, count) in [("pending", 3), ("in_progress", 1), ("completed", 2)] {
+            for _ in 0..count {
+                sqlx::query(
+                    r#"
+                    INSERT INTO tasks (project_id, input_data, status)
+                    VALUES ($1, '{}', $2::task_status)
+                    "#,
+                )
+                .bind(project.project_id.as_uuid())
+                .bind(status)
+                .execute(&pool)
+                .await
+                .unwrap();
+            }
+        }
+
+        let repo = PgTaskRepository::new(pool.clone());
+        let counts = repo.count_by_status(&project.project_id).await.unwrap();
+        assert_eq!(counts[&TaskStatus::Pending], 3);
+        assert_eq!(counts[&TaskStatus::InProgress], 1);
+        assert_eq!(counts[&TaskStatus::Completed], 2);
+        assert_eq!(counts[&TaskStatus::Failed], 0);
+        assert_eq!(counts.values().sum::<i64>(), 6);
+
+        let empty = PgProjectRepository::new(pool)
+            .create_minimal("Task Counts Empty", None, &user_id)
+            .await
+            .unwrap();
+        let by_project = repo
+            .count_by_status_for_projects(&[project.project_id, empty.project_id])
+            .await
+            .unwrap();
+        assert_eq!(by_project.len(), 2);
+        assert_eq!(by_project[&project.project_id], counts);
+        assert_eq!(by_project[&empty.project_id][&TaskStatus::Pending], 0);
+        assert_eq!(by_project[&empty.project_id].values().sum::<i64>(), 0);
+    }
+
+    #[tokio::test]
+    #[ignore = "requires DATABASE_URL pointing at a migrated database"]
+    async fn test_set_and_clear_cooldown() {
+        let url = std::env::var("DATABASE_URL").expect("DATABASE_URL must be set");
+        let pool = PgPool::connect(&url).await.unwrap();
+
+        let user_id = UserId::new();
+        sqlx::query(
+            r#"
+            INSERT INTO users (user_id, email, display_name, auth0_id, global_role, status)
+            VALUES ($1, $2, 'Task Cooldown', $3, 'user', 'active')
+            "#,
+        )
+        .bind(user_id.as_uuid())
+        .bind(format!("{user_id}@tasks.test"))
+        .bind(format!("test|{user_id}"))
+        .execute(&pool)
+        .await
+        .unwrap();
+        let project = PgProjectRepository::new(pool.clone())
+            .create_minimal("Task Cooldown", None, &user_id)
+            .await
+            .unwrap();
+        let task_id: uuid::Uuid = sqlx::query_scalar(
+            "INSERT INTO tasks (project_id, input_data) VALUES ($1, '{}') RETURNING task_id",
+        )
+        .bind(project.project_id.as_uuid())
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+        let task_id = TaskId::from_uuid(task_id);
+
+        let repo = PgTaskRepository::new(pool);
+        assert_eq!(repo.get_cooldown(&task_id).await.unwrap(), None);
+
+        let until = chrono::Utc::now() + chrono::Duration::minutes(5);
+        repo.set_cooldown(&task_id, until).await.unwrap();
+        let stored = repo.get_cooldown(&task_id).await.unwrap().unwrap();
+        assert!((stored - until).num_milliseconds().abs() < 1);
+
+        repo.clear_cooldown(&task_id).await.unwrap();
+        assert_eq!(repo.get_cooldown(&task_id).await.unwrap(), None);
+
+        let missing = TaskId::new();
+        assert!(matches!(
+            repo.get_cooldown(&missing).await,
+            Err(FindTaskError::NotFound(_))
+        ));
+        assert!(matches!(
+            repo.clear_cooldown(&missing).await,
+            Err(UpdateTaskError::NotFound(_))
+        ));
+    }
+}
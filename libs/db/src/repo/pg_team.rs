@@ -3,7 +3,7 @@
 use async_trait::async_trait;
 use sqlx::PgPool;
 
-use glyph_domain::{Team, TeamId, TeamMembership, TeamRole, TeamStatus, UserId};
+use glyph_domain::{OrgId, Team, TeamId, TeamMembership, TeamRole, TeamStatus, UserId};
 
 use crate::pagination::{Page, Pagination};
 use crate::repo::errors::*;
@@ -12,12 +12,74 @@ use crate::repo::traits::*;
 /// PostgreSQL team repository with hierarchy support
 pub struct PgTeamRepository {
     pool: PgPool,
+    /// Organization every query is limited to; `None` sees all of them
+    org_id: Option<OrgId>,
 }
 
 impl PgTeamRepository {
     /// Create a new team repository
     pub fn new(pool: PgPool) -> Self {
-        Self { pool }
+        Self { pool, org_id: None }
+    }
+
+    /// Limit lookups, lists and changes to one organization's teams.
+    /// Teams created through the repository belong to it.
+    #[must_use]
+    pub fn in_org(mut self, org_id: OrgId) -> Self {
+        self.org_id = Some(org_id);
+        self
+    }
+
+    fn org_filter(&self) -> Option<uuid::Uuid> {
+        self.org_id.map(OrgId::into_uuid)
+    }
+
+    /// Fail membership changes on teams outside the organization
+    async fn ensure_in_org(&self, team_id: &TeamId) -> Result<(), TeamMembershipError> {
+        let Some(org_id) = self.org_filter() else {
+            return Ok(());
+        };
+        let in_org = sqlx::query_scalar::<_, bool>(
+            "SELECT EXISTS (SELECT 1 FROM teams WHERE team_id = $1 AND org_id = $2)",
+        )
+        .bind(team_id.as_uuid())
+        .bind(org_id)
+        .fetch_one(&self.pool)
+        .await
+        .map_err(TeamMembershipError::Database)?;
+        if in_org {
+            Ok(())
+        } else {
+            Err(TeamMembershipError::TeamNotFound(team_id.clone()))
+        }
+    }
+
+    /// A live team and its ancestors in the organization, ordered by id
+    async fn ancestry(
+        conn: &mut sqlx::PgConnection,
+        team_id: &TeamId,
+        org_id: uuid::Uuid,
+    ) -> Result<Vec<uuid::Uuid>, sqlx::Error> {
+        sqlx::query_scalar::<_, uuid::Uuid>(
+            r#"
+            WITH RECURSIVE ancestors AS (
+                SELECT team_id, parent_team_id
+                FROM teams
+                WHERE team_id = $1 AND status != 'deleted' AND org_id = $2
+
+                UNION
+
+                SELECT t.team_id, t.parent_team_id
+                FROM teams t
+                JOIN ancestors a ON t.team_id = a.parent_team_id
+            )
+            SELECT team_id FROM ancestors ORDER BY team_id
+            "#,
+        )
+        .bind(team_id.as_uuid())
+        .bind(org_id)
+        .fetch_all(conn)
+        .await
     }
 }
 
@@ -27,12 +89,14 @@ impl TeamRepository for PgTeamRepository {
         let row = sqlx::query_as::<_, TeamRow>(
             r#"
             SELECT team_id, parent_team_id, name, description, status::text,
-                   capacity, specializations, created_at, updated_at
+                   capacity, specializations, created_at, updated_at, org_id
             FROM teams
             WHERE team_id = $1 AND status != 'deleted'
+              AND ($2::uuid IS NULL OR org_id = $2)
             "#,
         )
         .bind(id.as_uuid())
+        .bind(self.org_filter())
         .fetch_optional(&self.pool)
         .await
         .map_err(FindTeamError::Database)?;
@@ -46,10 +110,15 @@ impl TeamRepository for PgTeamRepository {
         let id = TeamId::new();
         let row = sqlx::query_as::<_, TeamRow>(
             r#"
-            INSERT INTO teams (team_id, parent_team_id, name, description, capacity, specializations)
-            VALUES ($1, $2, $3, $4, $5, $6)
+            INSERT INTO teams (
+                team_id, parent_team_id, name, description, capacity, specializations, org_id
+            )
+            VALUES (
+                $1, $2, $3, $4, $5, $6,
+                COALESCE($7, (SELECT org_id FROM teams WHERE team_id = $2), $8)
+            )
             RETURNING team_id, parent_team_id, name, description, status::text,
-                      capacity, specializations, created_at, updated_at
+                      capacity, specializations, created_at, updated_at, org_id
             "#,
         )
         .bind(id.as_uuid())
@@ -58,6 +127,8 @@ impl TeamRepository for PgTeamRepository {
         .bind(&team.description)
         .bind(team.capacity)
         .bind(serde_json::to_value(&team.specializations).unwrap_or_default())
+        .bind(self.org_filter())
+        .bind(OrgId::DEFAULT.into_uuid())
         .fetch_one(&mut *tx)
         .await
         .map_err(CreateTeamError::Database)?;
@@ -90,8 +161,9 @@ impl TeamRepository for PgTeamRepository {
                 specializations = COALESCE($6, specializations),
                 updated_at = NOW()
             WHERE team_id = $1 AND status != 'deleted'
+              AND ($7::uuid IS NULL OR org_id = $7)
             RETURNING team_id, parent_team_id, name, description, status::text,
-                      capacity, specializations, created_at, updated_at
+                      capacity, specializations, created_at, updated_at, org_id
             "#,
         )
         .bind(id.as_uuid())
@@ -105,6 +177,7 @@ impl TeamRepository for PgTeamRepository {
                 .as_ref()
                 .and_then(|s| serde_json::to_value(s).ok()),
         )
+        .bind(self.org_filter())
         .fetch_optional(&self.pool)
         .await
         .map_err(UpdateTeamError::Database)?
@@ -114,23 +187,26 @@ impl TeamRepository for PgTeamRepository {
     }
 
     async fn list(&self, pagination: Pagination) -> Result<Page<Team>, sqlx::Error> {
-        let total =
-            sqlx::query_scalar::<_, i64>("SELECT COUNT(*) FROM teams WHERE status != 'deleted'")
-                .fetch_one(&self.pool)
-                .await?;
+        let total = sqlx::query_scalar::<_, i64>(
+            "SELECT COUNT(*) FROM teams WHERE status != 'deleted' AND ($1::uuid IS NULL OR org_id = $1)",
+        )
+        .bind(self.org_filter())
+        .fetch_one(&self.pool)
+        .await?;
 
         let rows = sqlx::query_as::<_, TeamRow>(
             r#"
             SELECT team_id, parent_team_id, name, description, status::text,
-                   capacity, specializations, created_at, updated_at
+                   capacity, specializations, created_at, updated_at, org_id
             FROM teams
-            WHERE status != 'deleted'
+            WHERE status != 'deleted' AND ($3::uuid IS NULL OR org_id = $3)
             ORDER BY name
             LIMIT $1 OFFSET $2
             "#,
         )
         .bind(pagination.clamped_limit())
         .bind(pagination.offset)
+        .bind(self.org_filter())
         .fetch_all(&self.pool)
         .await?;
 
@@ -140,23 +216,30 @@ impl TeamRepository for PgTeamRepository {
 
     async fn list_root_teams(&self, pagination: Pagination) -> Result<Page<Team>, sqlx::Error> {
         let total = sqlx::query_scalar::<_, i64>(
-            "SELECT COUNT(*) FROM teams WHERE parent_team_id IS NULL AND status != 'deleted'",
+            r#"
+            SELECT COUNT(*) FROM teams
+            WHERE parent_team_id IS NULL AND status != 'deleted'
+              AND ($1::uuid IS NULL OR org_id = $1)
+            "#,
         )
+        .bind(self.org_filter())
         .fetch_one(&self.pool)
         .await?;
 
         let rows = sqlx::query_as::<_, TeamRow>(
             r#"
             SELECT team_id, parent_team_id, name, description, status::text,
-                   capacity, specializations, created_at, updated_at
+                   capacity, specializations, created_at, updated_at, org_id
             FROM teams
             WHERE parent_team_id IS NULL AND status != 'deleted'
+              AND ($3::uuid IS NULL OR org_id = $3)
             ORDER BY name
             LIMIT $1 OFFSET $2
             "#,
         )
         .bind(pagination.clamped_limit())
         .bind(pagination.offset)
+        .bind(self.org_filter())
         .fetch_all(&self.pool)
         .await?;
 
@@ -168,13 +251,15 @@ impl TeamRepository for PgTeamRepository {
         let rows = sqlx::query_as::<_, TeamRow>(
             r#"
             SELECT team_id, parent_team_id, name, description, status::text,
-                   capacity, specializations, created_at, updated_at
+                   capacity, specializations, created_at, updated_at, org_id
             FROM teams
             WHERE parent_team_id = $1 AND status != 'deleted'
+              AND ($2::uuid IS NULL OR org_id = $2)
             ORDER BY name
             "#,
         )
         .bind(team_id.as_uuid())
+        .bind(self.org_filter())
         .fetch_all(&self.pool)
         .await
         .map_err(FindTeamError::Database)?;
@@ -188,14 +273,17 @@ impl TeamRepository for PgTeamRepository {
             WITH RECURSIVE team_tree AS (
                 SELECT t.team_id, t.parent_team_id, t.name, t.description,
                        t.status::text, t.capacity, t.specializations, t.created_at, t.updated_at,
+                       t.org_id,
                        0 as depth
                 FROM teams t
                 WHERE t.team_id = $1 AND t.status != 'deleted'
+                  AND ($2::uuid IS NULL OR t.org_id = $2)
 
                 UNION ALL
 
                 SELECT t.team_id, t.parent_team_id, t.name, t.description,
                        t.status::text, t.capacity, t.specializations, t.created_at, t.updated_at,
+                       t.org_id,
                        tt.depth + 1
                 FROM teams t
                 JOIN team_tree tt ON t.parent_team_id = tt.team_id
@@ -210,6 +298,7 @@ impl TeamRepository for PgTeamRepository {
             "#,
         )
         .bind(team_id.as_uuid())
+        .bind(self.org_filter())
         .fetch_all(&self.pool)
         .await
         .map_err(FindTeamError::Database)?;
@@ -217,6 +306,116 @@ impl TeamRepository for PgTeamRepository {
         Ok(rows.into_iter().map(|r| r.into()).collect())
     }
 
+    async fn get_effective_members(&self, team_id: &TeamId) -> Result<Vec<UserId>, FindTeamError> {
+        let rows = sqlx::query_scalar::<_, uuid::Uuid>(
+            r#"
+            WITH RECURSIVE team_tree AS (
+                SELECT team_id
+                FROM teams
+                WHERE team_id = $1 AND status != 'deleted'
+                  AND ($2::uuid IS NULL OR org_id = $2)
+
+                UNION
+
+                SELECT t.team_id
+                FROM teams t
+                JOIN team_tree tt ON t.parent_team_id = tt.team_id
+                WHERE t.status != 'deleted'
+            )
+            SELECT DISTINCT tm.user_id
+            FROM team_memberships tm
+            JOIN team_tree tt ON tm.team_id = tt.team_id
+            ORDER BY tm.user_id
+            "#,
+        )
+        .bind(team_id.as_uuid())
+        .bind(self.org_filter())
+        .fetch_all(&self.pool)
+        .await
+        .map_err(FindTeamError::Database)?;
+
+        Ok(rows.into_iter().map(UserId::from_uuid).collect())
+    }
+
+    async fn move_team(
+        &self,
+        id: &TeamId,
+        new_parent_id: Option<&TeamId>,
+    ) -> Result<Team, MoveTeamError> {
+        let mut tx = self.pool.begin().await.map_err(MoveTeamError::Database)?;
+
+        let org_id = sqlx::query_scalar::<_, uuid::Uuid>(
+            r#"
+            SELECT org_id FROM teams
+            WHERE team_id = $1 AND status != 'deleted' AND ($2::uuid IS NULL OR org_id = $2)
+            FOR UPDATE
+            "#,
+        )
+        .bind(id.as_uuid())
+        .bind(self.org_filter())
+        .fetch_optional(&mut *tx)
+        .await
+        .map_err(MoveTeamError::Database)?;
+        let Some(org_id) = org_id else {
+            return Err(MoveTeamError::NotFound(id.clone()));
+        };
+
+        if let Some(parent_id) = new_parent_id {
+            // Walk up from the new parent; meeting the moved team means the
+            // parent sits inside its subtree. The chain is locked so a
+            // concurrent move can't re-parent one of its teams before this
+            // update commits; if it changed before the lock was taken, the
+            // walk is repeated over the new chain.
+            let mut ancestry = Self::ancestry(&mut tx, parent_id, org_id)
+                .await
+                .map_err(MoveTeamError::Database)?;
+            while !ancestry.is_empty() {
+                sqlx::query(
+                    "SELECT 1 FROM teams WHERE team_id = ANY($1) ORDER BY team_id FOR UPDATE",
+                )
+                .bind(&ancestry)
+                .execute(&mut *tx)
+                .await
+                .map_err(MoveTeamError::Database)?;
+                let locked = Self::ancestry(&mut tx, parent_id, org_id)
+                    .await
+                    .map_err(MoveTeamError::Database)?;
+                if locked == ancestry {
+                    break;
+                }
+                ancestry = locked;
+            }
+
+            if ancestry.is_empty() {
+                return Err(MoveTeamError::ParentNotFound(parent_id.clone()));
+            }
+            if ancestry.contains(id.as_uuid()) {
+                return Err(MoveTeamError::Cycle {
+                    team_id: id.clone(),
+                    new_parent_id: parent_id.clone(),
+                });
+            }
+        }
+
+        let row = sqlx::query_as::<_, TeamRow>(
+            r#"
+            UPDATE teams SET parent_team_id = $2, updated_at = NOW()
+            WHERE team_id = $1
+            RETURNING team_id, parent_team_id, name, description, status::text,
+                      capacity, specializations, created_at, updated_at, org_id
+            "#,
+        )
+        .bind(id.as_uuid())
+        .bind(new_parent_id.map(|p| *p.as_uuid()))
+        .fetch_one(&mut *tx)
+        .await
+        .map_err(MoveTeamError::Database)?;
+
+        tx.commit().await.map_err(MoveTeamError::Database)?;
+
+        Ok(row.into())
+    }
+
     async fn add_member(
         &self,
         team_id: &TeamId,
@@ -224,10 +423,11 @@ impl TeamRepository for PgTeamRepository {
         role: TeamRole,
         allocation: Option<i32>,
     ) -> Result<TeamMembership, TeamMembershipError> {
+        self.ensure_in_org(team_id).await?;
         let row = sqlx::query_as::<_, TeamMembershipRow>(
             r#"
             INSERT INTO team_memberships (team_id, user_id, role, allocation_percentage)
-            VALUES ($1, $2, $3, $4)
+            VALUES ($1, $2, $3::team_role, $4)
             RETURNING team_id, user_id, role::text, allocation_percentage, joined_at
             "#,
         )
@@ -254,6 +454,7 @@ impl TeamRepository for PgTeamRepository {
         team_id: &TeamId,
         user_id: &UserId,
     ) -> Result<(), TeamMembershipError> {
+        self.ensure_in_org(team_id).await?;
         let result =
             sqlx::query("DELETE FROM team_memberships WHERE team_id = $1 AND user_id = $2")
                 .bind(team_id.as_uuid())
@@ -269,6 +470,100 @@ impl TeamRepository for PgTeamRepository {
         Ok(())
     }
 
+    async fn bulk_update_members(
+        &self,
+        team_id: &TeamId,
+        add: &[NewTeamMember],
+        remove: &[UserId],
+    ) -> Result<Vec<BulkMemberResult>, BulkMembershipError> {
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .map_err(BulkMembershipError::Database)?;
+
+        // Lock the team so concurrent bulk changes see each other's result
+        let capacity = sqlx::query_scalar::<_, Option<i32>>(
+            r#"
+            SELECT capacity FROM teams
+            WHERE team_id = $1 AND status != 'deleted' AND ($2::uuid IS NULL OR org_id = $2)
+            FOR UPDATE
+            "#,
+        )
+        .bind(team_id.as_uuid())
+        .bind(self.org_filter())
+        .fetch_optional(&mut *tx)
+        .await
+        .map_err(BulkMembershipError::Database)?
+        .ok_or_else(|| BulkMembershipError::TeamNotFound(team_id.clone()))?;
+
+        let current: Vec<(UserId, TeamRole)> = sqlx::query_as::<_, (uuid::Uuid, String)>(
+            "SELECT user_id, role::text FROM team_memberships WHERE team_id = $1",
+        )
+        .bind(team_id.as_uuid())
+        .fetch_all(&mut *tx)
+        .await
+        .map_err(BulkMembershipError::Database)?
+        .into_iter()
+        .map(|(user_id, role)| (UserId::from_uuid(user_id), parse_team_role(&role)))
+        .collect();
+
+        let results = plan_bulk_membership(&current, capacity, add, remove)?;
+
+        let added: Vec<uuid::Uuid> = results
+            .iter()
+            .filter(|r| r.outcome == BulkMemberOutcome::Added)
+            .map(|r| *r.user_id.as_uuid())
+            .collect();
+        let known = sqlx::query_scalar::<_, uuid::Uuid>(
+            "SELECT user_id FROM users WHERE user_id = ANY($1) AND status != 'deleted'",
+        )
+        .bind(&added)
+        .fetch_all(&mut *tx)
+        .await
+        .map_err(BulkMembershipError::Database)?;
+        if let Some(missing) = added.iter().find(|id| !known.contains(id)) {
+            return Err(BulkMembershipError::UserNotFound(UserId::from_uuid(
+                *missing,
+            )));
+        }
+
+        for member in add {
+            if !added.contains(member.user_id.as_uuid()) {
+                continue;
+            }
+            sqlx::query(
+                r#"
+                INSERT INTO team_memberships (team_id, user_id, role, allocation_percentage)
+                VALUES ($1, $2, $3::team_role, $4)
+                "#,
+            )
+            .bind(team_id.as_uuid())
+            .bind(member.user_id.as_uuid())
+            .bind(format!("{:?}", member.role).to_lowercase())
+            .bind(member.allocation)
+            .execute(&mut *tx)
+            .await
+            .map_err(BulkMembershipError::Database)?;
+        }
+
+        let removed: Vec<uuid::Uuid> = results
+            .iter()
+            .filter(|r| r.outcome == BulkMemberOutcome::Removed)
+            .map(|r| *r.user_id.as_uuid())
+            .collect();
+        sqlx::query("DELETE FROM team_memberships WHERE team_id = $1 AND user_id = ANY($2)")
+            .bind(team_id.as_uuid())
+            .bind(&removed)
+            .execute(&mut *tx)
+            .await
+            .map_err(BulkMembershipError::Database)?;
+
+        tx.commit().await.map_err(BulkMembershipError::Database)?;
+
+        Ok(results)
+    }
+
     async fn update_member(
         &self,
         team_id: &TeamId,
@@ -276,10 +571,11 @@ impl TeamRepository for PgTeamRepository {
         role: Option<TeamRole>,
         allocation: Option<i32>,
     ) -> Result<TeamMembership, TeamMembershipError> {
+        self.ensure_in_org(team_id).await?;
         let row = sqlx::query_as::<_, TeamMembershipRow>(
             r#"
             UPDATE team_memberships SET
-                role = COALESCE($3, role),
+                role = COALESCE($3::team_role, role),
                 allocation_percentage = COALESCE($4, allocation_percentage)
             WHERE team_id = $1 AND user_id = $2
             RETURNING team_id, user_id, role::text, allocation_percentage, joined_at
@@ -341,9 +637,13 @@ impl TeamRepository for PgTeamRepository {
 
     async fn soft_delete(&self, id: &TeamId) -> Result<(), UpdateTeamError> {
         let result = sqlx::query(
-            "UPDATE teams SET status = 'deleted', updated_at = NOW() WHERE team_id = $1",
+            r#"
+            UPDATE teams SET status = 'deleted', updated_at = NOW()
+            WHERE team_id = $1 AND ($2::uuid IS NULL OR org_id = $2)
+            "#,
         )
         .bind(id.as_uuid())
+        .bind(self.org_filter())
         .execute(&self.pool)
         .await
         .map_err(UpdateTeamError::Database)?;
@@ -371,6 +671,7 @@ struct TeamRow {
     specializations: serde_json::Value,
     created_at: chrono::DateTime<chrono::Utc>,
     updated_at: chrono::DateTime<chrono::Utc>,
+    org_id: uuid::Uuid,
 }
 
 impl From<TeamRow> for Team {
@@ -385,6 +686,7 @@ impl From<TeamRow> for Team {
             specializations: serde_json::from_value(r.specializations).unwrap_or_default(),
             created_at: r.created_at,
             updated_at: r.updated_at,
+            org_id: OrgId::from_uuid(r.org_id),
         }
     }
 }
@@ -400,6 +702,7 @@ struct TeamTreeRow {
     specializations: serde_json::Value,
     created_at: chrono::DateTime<chrono::Utc>,
     updated_at: chrono::DateTime<chrono::Utc>,
+    org_id: uuid::Uuid,
     depth: i32,
     member_count: i64,
     sub_team_count: i64,
@@ -418,6 +721,7 @@ impl From<TeamTreeRow> for TeamTreeNode {
                 specializations: serde_json::from_value(r.specializations).unwrap_or_default(),
                 created_at: r.created_at,
                 updated_at: r.updated_at,
+                org_id: OrgId::from_uuid(r.org_id),
             },
             depth: r.depth,
             member_count: r.member_count,
@@ -472,6 +776,73 @@ impl From<TeamMemberWithUserRow> for TeamMembershipWithUser {
     }
 }
 
+/// Work out the outcome of a bulk membership change against the current
+/// members, enforcing the last-leader and capacity rules on the result.
+fn plan_bulk_membership(
+    current: &[(UserId, TeamRole)],
+    capacity: Option<i32>,
+    add: &[NewTeamMember],
+    remove: &[UserId],
+) -> Result<Vec<BulkMemberResult>, BulkMembershipError> {
+    let mut seen: Vec<&UserId> = Vec::with_capacity(add.len() + remove.len());
+    for user_id in add.iter().map(|m| &m.user_id).chain(remove) {
+        if seen.contains(&user_id) {
+            return Err(BulkMembershipError::DuplicateUser(user_id.clone()));
+        }
+        seen.push(user_id);
+    }
+
+    let mut resulting: Vec<(&UserId, TeamRole)> =
+        current.iter().map(|(id, role)| (id, *role)).collect();
+    let mut results = Vec::with_capacity(seen.len());
+
+    for member in add {
+        let outcome = if resulting.iter().any(|(id, _)| *id == &member.user_id) {
+            BulkMemberOutcome::AlreadyMember
+        } else {
+            resulting.push((&member.user_id, member.role));
+            BulkMemberOutcome::Added
+        };
+        results.push(BulkMemberResult {
+            user_id: member.user_id.clone(),
+            outcome,
+        });
+    }
+
+    for user_id in remove {
+        let before = resulting.len();
+        resulting.retain(|(id, _)| *id != user_id);
+        let outcome = if resulting.len() < before {
+            BulkMemberOutcome::Removed
+        } else {
+            BulkMemberOutcome::NotAMember
+        };
+        results.push(BulkMemberResult {
+            user_id: user_id.clone(),
+            outcome,
+        });
+    }
+
+    let had_leader = current.iter().any(|(_, role)| *role == TeamRole::Leader);
+    let has_leader = resulting.iter().any(|(_, role)| *role == TeamRole::Leader);
+    if had_leader && !has_leader {
+        return Err(BulkMembershipError::LastLeader);
+    }
+
+    // Teams already over capacity may still shrink
+    if let Some(capacity) = capacity {
+        let requested = resulting.len();
+        if requested > usize::try_from(capacity).unwrap_or(0) && requested > current.len() {
+            return Err(BulkMembershipError::CapacityExceeded {
+                capacity,
+                requested,
+            });
+        }
+    }
+
+    Ok(results)
+}
+
 fn parse_team_status(s: &str) -> TeamStatus {
     match s {
         "active" => TeamStatus::Active,
@@ -507,4 +878,287 @@ mod tests {
         assert_eq!(parse_team_role("member"), TeamRole::Member);
         assert_eq!(parse_team_role("unknown"), TeamRole::Member);
     }
+
+    fn member(role: TeamRole) -> NewTeamMember {
+        NewTeamMember {
+            user_id: UserId::new(),
+            role,
+            allocation: None,
+        }
+    }
+
+    fn outcomes(results: &[BulkMemberResult]) -> Vec<BulkMemberOutcome> {
+        results.iter().map(|r| r.outcome).collect()
+    }
+
+    #[test]
+    fn test_bulk_plan_adds_and_removes() {
+        let leader = UserId::new();
+        let leaving = UserId::new();
+        let current = vec![
+            (leader.clone(), TeamRole::Leader),
+            (leaving.clone(), TeamRole::Member),
+        ];
+        let add = vec![
+            member(TeamRole::Member),
+            member(TeamRole::Member),
+            member(TeamRole::Member),
+        ];
+
+        let results = plan_bulk_membership(&current, Some(4), &add, &[leaving.clone()]).unwrap();
+        assert_eq!(
+            outcomes(&results),
+            vec![
+                BulkMemberOutcome::Added,
+                BulkMemberOutcome::Added,
+                BulkMemberOutcome::Added,
+                BulkMemberOutcome::Removed,
+            ]
+        );
+        assert_eq!(results[3].user_id, leaving);
+    }
+
+    #[test]
+    fn test_bulk_plan_reports_no_ops() {
+        let existing = UserId::new();
+        let current = vec![(existing.clone(), TeamRole::Member)];
+        let add = vec![NewTeamMember {
+            user_id: existing,
+            role: TeamRole::Member,
+            allocation: None,
+        }];
+
+        let results = plan_bulk_membership(&current, None, &add, &[UserId::new()]).unwrap();
+        assert_eq!(
+            outcomes(&results),
+            vec![
+                BulkMemberOutcome::AlreadyMember,
+                BulkMemberOutcome::NotAMember
+            ]
+        );
+    }
+
+    #[test]
+    fn test_bulk_plan_keeps_a_leader() {
+        let leader = UserId::new();
+        let current = vec![(leader.clone(), TeamRole::Leader)];
+
+        let err = plan_bulk_membership(&current, None, &[], &[leader.clone()]).unwrap_err();
+        assert!(matches!(err, BulkMembershipError::LastLeader));
+
+        // Promoting a replacement in the same change is fine
+        let add = vec![member(TeamRole::Leader)];
+        assert!(plan_bulk_membership(&current, None, &add, &[leader]).is_ok());
+    }
+
+    #[test]
+    fn test_bulk_plan_enforces_capacity() {
+        let current = vec![(UserId::new(), TeamRole::Leader)];
+        let add = vec![member(TeamRole::Member), member(TeamRole::Member)];
+
+        let err = plan_bulk_membership(&current, Some(2), &add, &[]).unwrap_err();
+        assert!(matches!(
+            err,
+            BulkMembershipError::CapacityExceeded {
+                capacity: 2,
+                requested: 3
+            }
+        ));
+        assert!(plan_bulk_membership(&current, Some(3), &add, &[]).is_ok());
+    }
+
+    #[test]
+    fn test_bulk_plan_rejects_duplicates() {
+        let add = member(TeamRole::Member);
+        let user_id = add.user_id.clone();
+        let err = plan_bulk_membership(&[], None, &[add], &[user_id]).unwrap_err();
+        assert!(matches!(err, BulkMembershipError::DuplicateUser(_)));
+    }
+
+    async fn create_team(repo: &PgTeamRepository, name: &str, parent: Option<&Team>) -> Team {
+        repo.create(&NewTeam {
+            name: name.to_string(),
+            description: None,
+            parent_team_id: parent.map(|p| p.team_id.clone()),
+            capacity: None,
+            specializations: vec![],
+            initial_leader_id: None,
+        })
+        .await
+        .unwrap()
+    }
+
+    async fn test_repo() -> PgTeamRepository {
+        let url = std::env::var("DATABASE_URL").expect("DATABASE_URL must be set");
+        PgTeamRepository::new(PgPool::connect(&url).await.unwrap())
+    }
+
+    async fn create_user(repo: &PgTeamRepository) -> UserId {
+        let user_id = UserId::new();
+        sqlx::query(
+            r#"
+            INSERT INTO users (user_id, email, display_name, auth0_id, global_role, status)
+            VALUES ($1, $2, 'Team Test', $3, 'user', 'active')
+            "#,
+        )
+        .bind(user_id.as_uuid())
+        .bind(format!("{user_id}@team.test"))
+        .bind(format!("test|{user_id}"))
+        .execute(&repo.pool)
+        .await
+        .unwrap();
+        user_id
+    }
+
+    #[tokio::test]
+    #[ignore = "requires DATABASE_URL pointing at a migrated database"]
+    async fn test_bulk_update_adds_three_and_removes_one() {
+        let repo = test_repo().await;
+        let team = create_team(&repo, "Bulk Team", None).await;
+        let leaving = create_user(&repo).await;
+        repo.add_member(&team.team_id, &leaving, TeamRole::Member, None)
+            .await
+            .unwrap();
+
+        let mut add = Vec::new();
+        for _ in 0..3 {
+            add.push(NewTeamMember {
+                user_id: create_user(&repo).await,
+                role: TeamRole::Member,
+                allocation: Some(50),
+            });
+        }
+
+        let results = repo
+            .bulk_update_members(&team.team_id, &add, &[leaving.clone()])
+            .await
+            .unwrap();
+        assert_eq!(results.len(), 4);
+
+        let members = repo
+            .list_members(&team.team_id, Pagination::default())
+            .await
+            .unwrap();
+        assert_eq!(members.items.len(), 3);
+        assert!(members.items.iter().all(|m| m.user_id != leaving));
+
+        // An unknown user aborts the whole change
+        let err = repo
+            .bulk_update_members(
+                &team.team_id,
+                &[NewTeamMember {
+                    user_id: UserId::new(),
+                    role: TeamRole::Member,
+                    allocation: None,
+                }],
+                &[add[0].user_id.clone()],
+            )
+            .await
+            .unwrap_err();
+        assert!(matches!(err, BulkMembershipError::UserNotFound(_)));
+        assert_eq!(repo.get_member_count(&team.team_id).await.unwrap(), 3);
+    }
+
+    #[tokio::test]
+    #[ignore = "requires DATABASE_URL pointing at a migrated database"]
+    async fn test_effective_members_include_sub_teams_once() {
+        let repo = test_repo().await;
+        let parent = create_team(&repo, "Effective Parent", None).await;
+        let child = create_team(&repo, "Effective Child", Some(&parent)).await;
+
+        let shared = create_user(&repo).await;
+        let parent_only = create_user(&repo).await;
+        let child_only = create_user(&repo).await;
+        for (team, user) in [
+            (&parent, &shared),
+            (&parent, &parent_only),
+            (&child, &shared),
+            (&child, &child_only),
+        ] {
+            repo.add_member(&team.team_id, user, TeamRole::Member, None)
+                .await
+                .unwrap();
+        }
+
+        let mut expected = vec![shared.clone(), parent_only, child_only.clone()];
+        expected.sort_by_key(|id| *id.as_uuid());
+        assert_eq!(
+            repo.get_effective_members(&parent.team_id).await.unwrap(),
+            expected
+        );
+
+        let mut expected = vec![shared, child_only];
+        expected.sort_by_key(|id| *id.as_uuid());
+        assert_eq!(
+            repo.get_effective_members(&child.team_id).await.unwrap(),
+            expected
+        );
+    }
+
+    #[tokio::test]
+    #[ignore = "requires DATABASE_URL pointing at a migrated database"]
+    async fn test_move_team_carries_subtree() {
+        let repo = test_repo().await;
+        let root_a = create_team(&repo, "Move A", None).await;
+        let root_b = create_team(&repo, "Move B", None).await;
+        let child = create_team(&repo, "Move Child", Some(&root_a)).await;
+        let grandchild = create_team(&repo, "Move Grandchild", Some(&child)).await;
+
+        let moved = repo
+            .move_team(&child.team_id, Some(&root_b.team_id))
+            .await
+            .unwrap();
+        assert_eq!(moved.parent_team_id, Some(root_b.team_id.clone()));
+
+        let tree = repo.get_team_tree(&root_b.team_id).await.unwrap();
+        let ids: Vec<_> = tree.iter().map(|n| n.team.team_id.clone()).collect();
+        assert!(ids.contains(&child.team_id));
+        assert!(ids.contains(&grandchild.team_id));
+
+        let moved = repo.move_team(&child.team_id, None).await.unwrap();
+        assert_eq!(moved.parent_team_id, None);
+    }
+
+    #[tokio::test]
+    #[ignore = "requires DATABASE_URL pointing at a migrated database"]
+    async fn test_concurrent_opposite_moves_cannot_form_a_cycle() {
+        let repo = test_repo().await;
+        for _ in 0..10 {
+            let a = create_team(&repo, "Race A", None).await;
+            let b = create_team(&repo, "Race B", None).await;
+
+            let (a_under_b, b_under_a) = tokio::join!(
+                repo.move_team(&a.team_id, Some(&b.team_id)),
+                repo.move_team(&b.team_id, Some(&a.team_id)),
+            );
+            assert!(
+                a_under_b.is_ok() != b_under_a.is_ok(),
+                "exactly one move wins: {a_under_b:?} / {b_under_a:?}"
+            );
+        }
+    }
+
+    #[tokio::test]
+    #[ignore = "requires DATABASE_URL pointing at a migrated database"]
+    async fn test_move_team_under_descendant_rejected() {
+        let repo = test_repo().await;
+        let root = create_team(&repo, "Cycle Root", None).await;
+        let child = create_team(&repo, "Cycle Child", Some(&root)).await;
+        let grandchild = create_team(&repo, "Cycle Grandchild", Some(&child)).await;
+
+        let err = repo
+            .move_team(&root.team_id, Some(&grandchild.team_id))
+            .await
+            .unwrap_err();
+        assert!(matches!(err, MoveTeamError::Cycle { .. }));
+
+        let err = repo
+            .move_team(&root.team_id, Some(&root.team_id))
+            .await
+            .unwrap_err();
+        assert!(matches!(err, MoveTeamError::Cycle { .. }));
+
+        let unchanged = repo.find_by_id(&root.team_id).await.unwrap().unwrap();
+        assert_eq!(unchanged.parent_team_id, None);
+    }
 }
@@ -0,0 +1,293 @@
+//! PostgreSQL webhook configuration and delivery queue
+//!
+//! `webhook_deliveries` doubles as the outbound queue and the delivery log:
+//! rows start `pending`, and end `delivered` or `failed` once retries run out.
+
+use chrono::{DateTime, Utc};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use glyph_domain::{ProjectId, TaskId};
+
+/// A project's webhook endpoint
+#[derive(Debug, Clone)]
+pub struct WebhookConfig {
+    pub project_id: ProjectId,
+    pub url: String,
+    /// Shared secret used to sign payloads
+    pub secret: String,
+    pub is_active: bool,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// A queued delivery claimed by the worker, with its destination
+#[derive(Debug, Clone)]
+pub struct PendingDelivery {
+    pub delivery_id: Uuid,
+    pub project_id: ProjectId,
+    pub task_id: TaskId,
+    pub event_type: String,
+    pub payload: serde_json::Value,
+    /// Attempts made before this one
+    pub attempts: i32,
+    pub url: String,
+    pub secret: String,
+}
+
+/// PostgreSQL webhook repository
+pub struct PgWebhookRepository {
+    pool: PgPool,
+}
+
+impl PgWebhookRepository {
+    /// Create a new PostgreSQL webhook repository
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    /// Find a project's webhook configuration
+    pub async fn find_config(
+        &self,
+        project_id: &ProjectId,
+    ) -> Result<Option<WebhookConfig>, sqlx::Error> {
+        let row = sqlx::query_as::<_, WebhookConfigRow>(
+            r#"
+            SELECT project_id, url, secret, is_active, created_at, updated_at
+            FROM project_webhooks
+            WHERE project_id = $1
+            "#,
+        )
+        .bind(project_id.as_uuid())
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(row.map(Into::into))
+    }
+
+    /// Create or replace a project's webhook configuration
+    pub async fn upsert_config(
+        &self,
+        project_id: &ProjectId,
+        url: &str,
+        secret: &str,
+        is_active: bool,
+    ) -> Result<WebhookConfig, sqlx::Error> {
+        let row = sqlx::query_as::<_, WebhookConfigRow>(
+            r#"
+            INSERT INTO project_webhooks (project_id, url, secret, is_active)
+            VALUES ($1, $2, $3, $4)
+            ON CONFLICT (project_id) DO UPDATE
+            SET url = EXCLUDED.url,
+                secret = EXCLUDED.secret,
+                is_active = EXCLUDED.is_active,
+                updated_at = NOW()
+            RETURNING project_id, url, secret, is_active, created_at, updated_at
+            "#,
+        )
+        .bind(project_id.as_uuid())
+        .bind(url)
+        .bind(secret)
+        .bind(is_active)
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(row.into())
+    }
+
+    /// Remove a project's webhook configuration. Returns whether one existed.
+    pub async fn delete_config(&self, project_id: &ProjectId) -> Result<bool, sqlx::Error> {
+        let result = sqlx::query("DELETE FROM project_webhooks WHERE project_id = $1")
+            .bind(project_id.as_uuid())
+            .execute(&self.pool)
+            .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Queue a delivery for the task's project, if it has an active webhook.
+    ///
+    /// Returns the delivery ID, or `None` when no webhook is configured.
+    pub async fn enqueue_for_task(
+        &self,
+        task_id: &TaskId,
+        event_type: &str,
+        payload: &serde_json::Value,
+    ) -> Result<Option<Uuid>, sqlx::Error> {
+        sqlx::query_scalar::<_, Uuid>(
+            r#"
+            INSERT INTO webhook_deliveries (project_id, task_id, event_type, payload)
+            SELECT t.project_id, t.task_id, $2, $3
+            FROM tasks t
+            JOIN project_webhooks w ON w.project_id = t.project_id AND w.is_active
+            WHERE t.task_id = $1
+            RETURNING delivery_id
+            "#,
+        )
+        .bind(task_id.as_uuid())
+        .bind(event_type)
+        .bind(payload)
+        .fetch_optional(&self.pool)
+        .await
+    }
+
+    /// Claim up to `limit` due deliveries.
+    ///
+    /// Claimed rows are pushed back by `lease` so a concurrent worker skips
+    /// them; a worker that dies mid-delivery leaves them to be retried.
+    pub async fn claim_due(
+        &self,
+        limit: i64,
+        lease: chrono::Duration,
+    ) -> Result<Vec<PendingDelivery>, sqlx::Error> {
+        let rows = sqlx::query_as::<_, PendingDeliveryRow>(
+            r#"
+            UPDATE webhook_deliveries d
+            SET next_attempt_at = NOW() + $2
+            FROM project_webhooks w
+            WHERE w.project_id = d.project_id
+              AND d.delivery_id IN (
+                  SELECT delivery_id FROM webhook_deliveries
+                  WHERE status = 'pending' AND next_attempt_at <= NOW()
+                  ORDER BY next_attempt_at
+                  LIMIT $1
+                  FOR UPDATE SKIP LOCKED
+              )
+            RETURNING d.delivery_id, d.project_id, d.task_id, d.event_type, d.payload,
+                      d.attempts, w.url, w.secret
+            "#,
+        )
+        .bind(limit)
+        .bind(lease)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(rows.into_iter().map(Into::into).collect())
+    }
+
+    /// Put a failed delivery back in the queue with a fresh set of attempts.
+    ///
+    /// Returns `false` if the delivery doesn't exist or hasn't failed.
+    pub async fn requeue(&self, delivery_id: Uuid) -> Result<bool, sqlx::Error> {
+        let result = sqlx::query(
+            r#"
+            UPDATE webhook_deliveries
+            SET status = 'pending', attempts = 0, next_attempt_at = NOW()
+            WHERE delivery_id = $1 AND status = 'failed'
+            "#,
+        )
+        .bind(delivery_id)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Log a successful delivery
+    pub async fn record_delivered(
+        &self,
+        delivery_id: Uuid,
+        status_code: u16,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query(
+            r#"
+            UPDATE webhook_deliveries
+            SET status = 'delivered', attempts = attempts + 1, last_status_code = $2,
+                last_error = NULL, delivered_at = NOW()
+            WHERE delivery_id = $1
+            "#,
+        )
+        .bind(delivery_id)
+        .bind(i32::from(status_code))
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Log a failed attempt.
+    ///
+    /// With `retry_at` the delivery stays pending until then; without it the
+    /// delivery is marked failed for good.
+    pub async fn record_failure(
+        &self,
+        delivery_id: Uuid,
+        status_code: Option<u16>,
+        error: &str,
+        retry_at: Option<DateTime<Utc>>,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query(
+            r#"
+            UPDATE webhook_deliveries
+            SET status = CASE WHEN $4::timestamptz IS NULL THEN 'failed' ELSE 'pending' END,
+                attempts = attempts + 1,
+                last_status_code = $2,
+                last_error = $3,
+                next_attempt_at = COALESCE($4, next_attempt_at)
+            WHERE delivery_id = $1
+            "#,
+        )
+        .bind(delivery_id)
+        .bind(status_code.map(i32::from))
+        .bind(error)
+        .bind(retry_at)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+}
+
+// =============================================================================
+// Internal row types for SQLx mapping
+// =============================================================================
+
+#[derive(sqlx::FromRow)]
+struct WebhookConfigRow {
+    project_id: Uuid,
+    url: String,
+    secret: String,
+    is_active: bool,
+    created_at: DateTime<Utc>,
+    updated_at: DateTime<Utc>,
+}
+
+impl From<WebhookConfigRow> for WebhookConfig {
+    fn from(row: WebhookConfigRow) -> Self {
+        Self {
+            project_id: ProjectId::from_uuid(row.project_id),
+            url: row.url,
+            secret: row.secret,
+            is_active: row.is_active,
+            created_at: row.created_at,
+            updated_at: row.updated_at,
+        }
+    }
+}
+
+#[derive(sqlx::FromRow)]
+struct PendingDeliveryRow {
+    delivery_id: Uuid,
+    project_id: Uuid,
+    task_id: Uuid,
+    event_type: String,
+    payload: serde_json::Value,
+    attempts: i32,
+    url: String,
+    secret: String,
+}
+
+impl From<PendingDeliveryRow> for PendingDelivery {
+    fn from(row: PendingDeliveryRow) -> Self {
+        Self {
+            delivery_id: row.delivery_id,
+            project_id: ProjectId::from_uuid(row.project_id),
+            task_id: TaskId::from_uuid(row.task_id),
+            event_type: row.event_type,
+            payload: row.payload,
+            attempts: row.attempts,
+            url: row.url,
+            secret: row.secret,
+        }
+    }
+}
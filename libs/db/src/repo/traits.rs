@@ -7,8 +7,8 @@ use async_trait::async_trait;
 use uuid::Uuid;
 
 use glyph_domain::{
-    Annotation, AnnotationStatus, Project, ProjectStatus, Task, TaskStatus, Team, TeamMembership,
-    TeamRole, TeamStatus, User, UserStatus, Workflow,
+    Annotation, AnnotationStatus, AnnotationVersion, Project, ProjectStatus, Task, TaskStatus,
+    Team, TeamMembership, TeamRole, TeamStatus, User, UserStatus, Workflow,
 };
 use glyph_domain::{AnnotationId, AssignmentId, ProjectId, TaskId, TeamId, UserId, WorkflowId};
 
@@ -85,6 +85,43 @@ pub struct TeamMembershipWithUser {
     pub email: String,
 }
 
+/// A member to add in a bulk membership change
+#[derive(Debug, Clone)]
+pub struct NewTeamMember {
+    pub user_id: UserId,
+    pub role: TeamRole,
+    pub allocation: Option<i32>,
+}
+
+/// What a bulk membership change did for one user
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BulkMemberOutcome {
+    Added,
+    Removed,
+    /// Listed in `add` but already a member; left unchanged
+    AlreadyMember,
+    /// Listed in `remove` but not a member; nothing to do
+    NotAMember,
+}
+
+impl BulkMemberOutcome {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Added => "added",
+            Self::Removed => "removed",
+            Self::AlreadyMember => "already_member",
+            Self::NotAMember => "not_a_member",
+        }
+    }
+}
+
+/// Per-user result of a bulk membership change
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BulkMemberResult {
+    pub user_id: UserId,
+    pub outcome: BulkMemberOutcome,
+}
+
 /// Input for creating a new project
 #[derive(Debug, Clone)]
 pub struct NewProject {
@@ -110,6 +147,8 @@ pub struct NewTask {
     pub input_data: serde_json::Value,
     pub priority: Option<i32>,
     pub metadata: Option<serde_json::Value>,
+    /// Expected output, marking this as a gold task
+    pub gold_output: Option<serde_json::Value>,
 }
 
 /// Input for updating a task
@@ -138,6 +177,14 @@ pub struct AnnotationUpdate {
     pub status: Option<AnnotationStatus>,
 }
 
+/// An annotation on a completed task, with when it last changed for export
+#[derive(Debug, Clone)]
+pub struct CompletedAnnotation {
+    pub annotation: Annotation,
+    /// Later of the task's completion and the annotation's last update
+    pub changed_at: chrono::DateTime<chrono::Utc>,
+}
+
 // =============================================================================
 // Repository Traits
 // =============================================================================
@@ -191,6 +238,19 @@ pub trait TeamRepository: Send + Sync {
     /// Get team hierarchy tree (recursive)
     async fn get_team_tree(&self, team_id: &TeamId) -> Result<Vec<TeamTreeNode>, FindTeamError>;
 
+    /// Users who belong to a team or any of its sub-teams, each listed once
+    async fn get_effective_members(&self, team_id: &TeamId) -> Result<Vec<UserId>, FindTeamError>;
+
+    /// Re-parent a team, carrying its sub-teams along; `None` makes it a root team.
+    ///
+    /// Fails with [`MoveTeamError::Cycle`] if the new parent is the team itself
+    /// or one of its descendants.
+    async fn move_team(
+        &self,
+        id: &TeamId,
+        new_parent_id: Option<&TeamId>,
+    ) -> Result<Team, MoveTeamError>;
+
     /// Add a member to a team
     async fn add_member(
         &self,
@@ -207,6 +267,17 @@ pub trait TeamRepository: Send + Sync {
         user_id: &UserId,
     ) -> Result<(), TeamMembershipError>;
 
+    /// Add and remove several members in one transaction.
+    ///
+    /// The last-leader and capacity rules are checked against the resulting
+    /// membership, so either every change applies or none does.
+    async fn bulk_update_members(
+        &self,
+        team_id: &TeamId,
+        add: &[NewTeamMember],
+        remove: &[UserId],
+    ) -> Result<Vec<BulkMemberResult>, BulkMembershipError>;
+
     /// Update a team member's role or allocation
     async fn update_member(
         &self,
@@ -281,6 +352,15 @@ pub trait TaskRepository: Send + Sync {
         id: &TaskId,
         until: chrono::DateTime<chrono::Utc>,
     ) -> Result<(), UpdateTaskError>;
+
+    /// When a task's cooldown ends, or `None` if it has none
+    async fn get_cooldown(
+        &self,
+        id: &TaskId,
+    ) -> Result<Option<chrono::DateTime<chrono::Utc>>, FindTaskError>;
+
+    /// Remove a task's cooldown so it can be claimed immediately
+    async fn clear_cooldown(&self, id: &TaskId) -> Result<(), UpdateTaskError>;
 }
 
 /// Repository for annotation operations
@@ -292,11 +372,13 @@ pub trait AnnotationRepository: Send + Sync {
         id: &AnnotationId,
     ) -> Result<Option<Annotation>, FindAnnotationError>;
 
-    /// Create a new annotation
+    /// Create a new annotation, recording its first version
     async fn create(&self, annotation: &NewAnnotation)
         -> Result<Annotation, CreateAnnotationError>;
 
-    /// Update an existing annotation (draft only)
+    /// Update an existing annotation (draft only).
+    ///
+    /// Saving new data appends a version instead of overwriting history.
     async fn update(
         &self,
         id: &AnnotationId,
@@ -312,6 +394,35 @@ pub trait AnnotationRepository: Send + Sync {
 
     /// Submit an annotation (changes status from Draft to Submitted)
     async fn submit(&self, id: &AnnotationId) -> Result<Annotation, UpdateAnnotationError>;
+
+    /// List submitted or approved annotations on a project's completed tasks
+    /// whose task completed or that were last updated no later than `until`.
+    ///
+    /// With `since`, only those that changed after that time are returned.
+    async fn list_completed_by_project(
+        &self,
+        project_id: &ProjectId,
+        since: Option<chrono::DateTime<chrono::Utc>>,
+        until: chrono::DateTime<chrono::Utc>,
+    ) -> Result<Vec<CompletedAnnotation>, sqlx::Error>;
+
+    /// List every saved version of a user's annotations on a task, oldest first
+    async fn list_versions(
+        &self,
+        task_id: &TaskId,
+        user_id: &UserId,
+    ) -> Result<Vec<AnnotationVersion>, sqlx::Error>;
+
+    /// List the saved versions of a single annotation, oldest first
+    async fn history(&self, id: &AnnotationId) -> Result<Vec<AnnotationVersion>, sqlx::Error>;
+
+    /// Find a user's most recent submission on a task, ignoring drafts and
+    /// superseded annotations
+    async fn find_latest_submission(
+        &self,
+        task_id: &TaskId,
+        user_id: &UserId,
+    ) -> Result<Option<Annotation>, sqlx::Error>;
 }
 
 /// Repository for workflow operations
@@ -494,3 +605,69 @@ pub trait AssignmentRepository: Send + Sync {
     /// Count active assignments for a user (for load balancing)
     async fn count_active_by_user(&self, user_id: &UserId) -> Result<i64, sqlx::Error>;
 }
+
+// =============================================================================
+// Quality Profile Repository
+// =============================================================================
+
+/// A user's latest label on a task step, with the strict-majority label of
+/// the step's other annotators when they reached one
+#[derive(Debug, Clone)]
+pub struct ConsensusComparison {
+    pub task_id: TaskId,
+    pub step_id: String,
+    pub user_id: UserId,
+    pub data: serde_json::Value,
+    pub consensus: Option<serde_json::Value>,
+}
+
+/// A user's latest submission on a gold task, with the expected output
+#[derive(Debug, Clone)]
+pub struct GoldSubmission {
+    pub task_id: TaskId,
+    pub user_id: UserId,
+    pub data: serde_json::Value,
+    pub expected: serde_json::Value,
+}
+
+/// Repository for per-user quality profiles
+#[async_trait]
+pub trait QualityProfileRepository: Send + Sync {
+    /// Find the quality profile for a user (None if never computed)
+    async fn find_by_user(
+        &self,
+        user_id: &UserId,
+    ) -> Result<Option<glyph_domain::QualityProfile>, FindQualityProfileError>;
+
+    /// Insert or replace the quality profile for a user
+    async fn upsert(
+        &self,
+        user_id: &UserId,
+        profile: &glyph_domain::QualityProfile,
+    ) -> Result<glyph_domain::QualityProfile, UpsertQualityProfileError>;
+
+    /// Recompute profiles for every user with reviewed or submitted annotations.
+    /// Returns the number of profiles written.
+    async fn recompute_all(&self) -> Result<u64, sqlx::Error>;
+
+    /// List every user's latest submitted, approved, or rejected label per
+    /// task step alongside the other annotators' consensus
+    async fn list_consensus_comparisons(&self) -> Result<Vec<ConsensusComparison>, sqlx::Error>;
+
+    /// Set the consistency score on an existing profile
+    async fn set_consistency(&self, user_id: &UserId, score: f64) -> Result<(), sqlx::Error>;
+
+    /// List every user's latest submission on each gold task
+    async fn list_gold_submissions(&self) -> Result<Vec<GoldSubmission>, sqlx::Error>;
+
+    /// Set the gold accuracy score from gold-task results
+    async fn set_gold_accuracy(&self, user_id: &UserId, accuracy: f64) -> Result<(), sqlx::Error>;
+
+    /// Record whether a user is above the gold mismatch threshold.
+    /// Returns true only when the user newly crossed it.
+    async fn set_gold_alert(&self, user_id: &UserId, alerting: bool) -> Result<bool, sqlx::Error>;
+
+    /// Users to notify about a quality alert: leaders of the user's teams,
+    /// or global admins when the user has no team leader
+    async fn alert_recipients(&self, user_id: &UserId) -> Result<Vec<UserId>, sqlx::Error>;
+}
@@ -1,7 +1,10 @@
 //! Annotation domain models
 
+use std::collections::{HashMap, HashSet};
+
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
+use thiserror::Error;
 use typeshare::typeshare;
 
 use crate::enums::{ActorType, AnnotationStatus};
@@ -28,6 +31,9 @@ pub struct Annotation {
     pub quality_evaluated_at: Option<DateTime<Utc>>,
     pub time_spent_ms: Option<i64>,
     pub client_metadata: Option<serde_json::Value>,
+    /// Project type schema version the annotation was created against,
+    /// `None` if the project had no type
+    pub schema_version: Option<i32>,
 }
 
 /// An event in the annotation's history (for event sourcing)
@@ -59,3 +65,557 @@ pub enum AnnotationEventType {
     Rejected,
     Superseded,
 }
+
+/// An immutable snapshot of an annotation's data, recorded on every save.
+///
+/// Versions are append-only: saving an annotation produces a new version
+/// rather than overwriting the previous one.
+#[typeshare]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AnnotationVersion {
+    pub annotation_id: AnnotationId,
+    pub task_id: TaskId,
+    pub user_id: UserId,
+    pub version: i32,
+    pub data: serde_json::Value,
+    pub saved_at: DateTime<Utc>,
+}
+
+impl AnnotationVersion {
+    /// The first version of a newly created annotation
+    pub fn initial(
+        annotation_id: AnnotationId,
+        task_id: TaskId,
+        user_id: UserId,
+        data: serde_json::Value,
+    ) -> Self {
+        Self {
+            annotation_id,
+            task_id,
+            user_id,
+            version: 1,
+            data,
+            saved_at: Utc::now(),
+        }
+    }
+
+    /// The version recorded when `data` is saved on top of this one
+    #[must_use]
+    pub fn next(&self, data: serde_json::Value) -> Self {
+        Self {
+            annotation_id: self.annotation_id,
+            task_id: self.task_id,
+            user_id: self.user_id,
+            version: self.version + 1,
+            data,
+            saved_at: Utc::now(),
+        }
+    }
+}
+
+/// An axis-aligned bounding box annotation value, in image pixels.
+///
+/// `(x, y)` is the top-left corner; `w` and `h` extend right and down.
+#[typeshare]
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct BoundingBox {
+    pub x: f64,
+    pub y: f64,
+    pub w: f64,
+    pub h: f64,
+}
+
+/// Why a bounding box is invalid for an image
+#[derive(Debug, Clone, PartialEq, Error)]
+pub enum BoundingBoxError {
+    #[error("bounding box has negative dimensions ({w} x {h})")]
+    NegativeDimensions { w: f64, h: f64 },
+    #[error("bounding box extends outside the {image_width} x {image_height} image")]
+    OutOfBounds { image_width: f64, image_height: f64 },
+}
+
+impl BoundingBox {
+    /// Check the box has non-negative dimensions and lies within the image
+    pub fn validate(&self, image_width: f64, image_height: f64) -> Result<(), BoundingBoxError> {
+        if self.w < 0.0 || self.h < 0.0 {
+            return Err(BoundingBoxError::NegativeDimensions {
+                w: self.w,
+                h: self.h,
+            });
+        }
+        let within = self.x >= 0.0
+            && self.y >= 0.0
+            && self.x + self.w <= image_width
+            && self.y + self.h <= image_height;
+        if !within {
+            return Err(BoundingBoxError::OutOfBounds {
+                image_width,
+                image_height,
+            });
+        }
+        Ok(())
+    }
+}
+
+/// A polygon annotation value, as a closed ring of `(x, y)` vertices.
+///
+/// The last point connects back to the first; it should not be repeated.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Polygon {
+    pub points: Vec<(f64, f64)>,
+}
+
+/// Why a polygon is invalid
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum PolygonError {
+    #[error("polygon needs at least 3 points, got {0}")]
+    TooFewPoints(usize),
+    #[error("polygon is self-intersecting: edge {first} crosses edge {second}")]
+    SelfIntersecting { first: usize, second: usize },
+}
+
+impl Polygon {
+    /// Check the polygon has at least 3 points and does not cross itself.
+    ///
+    /// Edge `i` runs from point `i` to point `i + 1` (wrapping to 0).
+    pub fn validate(&self) -> Result<(), PolygonError> {
+        let n = self.points.len();
+        if n < 3 {
+            return Err(PolygonError::TooFewPoints(n));
+        }
+
+        let edge = |i: usize| (self.points[i], self.points[(i + 1) % n]);
+        for first in 0..n {
+            // Adjacent edges share a vertex, so only compare non-neighbours
+            for second in (first + 2)..n {
+                if first == 0 && second == n - 1 {
+                    continue;
+                }
+                let (p1, p2) = edge(first);
+                let (q1, q2) = edge(second);
+                if segments_intersect(p1, p2, q1, q2) {
+                    return Err(PolygonError::SelfIntersecting { first, second });
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Whether segments `p1-p2` and `q1-q2` touch, including collinear overlap
+fn segments_intersect(p1: (f64, f64), p2: (f64, f64), q1: (f64, f64), q2: (f64, f64)) -> bool {
+    let d1 = orientation(q1, q2, p1);
+    let d2 = orientation(q1, q2, p2);
+    let d3 = orientation(p1, p2, q1);
+    let d4 = orientation(p1, p2, q2);
+
+    if d1 * d2 < 0.0 && d3 * d4 < 0.0 {
+        return true;
+    }
+    (d1 == 0.0 && on_segment(q1, q2, p1))
+        || (d2 == 0.0 && on_segment(q1, q2, p2))
+        || (d3 == 0.0 && on_segment(p1, p2, q1))
+        || (d4 == 0.0 && on_segment(p1, p2, q2))
+}
+
+/// Cross product sign: > 0 if `r` is left of `a -> b`, < 0 if right, 0 if collinear
+fn orientation(a: (f64, f64), b: (f64, f64), r: (f64, f64)) -> f64 {
+    (b.0 - a.0) * (r.1 - a.1) - (b.1 - a.1) * (r.0 - a.0)
+}
+
+/// Whether collinear point `r` lies within the bounding rectangle of `a-b`
+fn on_segment(a: (f64, f64), b: (f64, f64), r: (f64, f64)) -> bool {
+    r.0 >= a.0.min(b.0) && r.0 <= a.0.max(b.0) && r.1 >= a.1.min(b.1) && r.1 <= a.1.max(b.1)
+}
+
+/// A text span annotation value, as character offsets into the document.
+///
+/// `start` is inclusive and `end` exclusive. Offsets are signed so malformed
+/// submissions deserialize and can be reported rather than rejected opaquely.
+#[typeshare]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Span {
+    pub start: i64,
+    pub end: i64,
+    /// Identifies the span for [`Relation`]s to refer to; unique within an
+    /// annotation
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+}
+
+/// Why a span is invalid for a document
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum SpanError {
+    #[error("span has negative offsets ({start}..{end})")]
+    Negative { start: i64, end: i64 },
+    #[error("span start {start} is after its end {end}")]
+    Inverted { start: i64, end: i64 },
+    #[error("span end {end} is past the end of the {doc_len}-character document")]
+    OutOfBounds { end: i64, doc_len: usize },
+}
+
+impl Span {
+    /// A span without an id
+    #[must_use]
+    pub const fn new(start: i64, end: i64) -> Self {
+        Self {
+            start,
+            end,
+            id: None,
+        }
+    }
+
+    /// Check the span's offsets are non-negative, ordered, and within a
+    /// document of `doc_len` characters
+    pub fn validate(&self, doc_len: usize) -> Result<(), SpanError> {
+        if self.start < 0 || self.end < 0 {
+            return Err(SpanError::Negative {
+                start: self.start,
+                end: self.end,
+            });
+        }
+        if self.start > self.end {
+            return Err(SpanError::Inverted {
+                start: self.start,
+                end: self.end,
+            });
+        }
+        if !usize::try_from(self.end).is_ok_and(|end| end <= doc_len) {
+            return Err(SpanError::OutOfBounds {
+                end: self.end,
+                doc_len,
+            });
+        }
+        Ok(())
+    }
+}
+
+/// Two spans in one annotation that overlap where overlap isn't allowed
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+#[error("span {first} ({}..{}) overlaps span {second} ({}..{})", first_span.start, first_span.end, second_span.start, second_span.end)]
+pub struct SpanOverlapError {
+    /// Index of the earlier span in the annotation
+    pub first: usize,
+    pub first_span: Span,
+    /// Index of the later span in the annotation
+    pub second: usize,
+    pub second_span: Span,
+}
+
+impl Span {
+    /// Whether the spans share at least one character; empty spans never do
+    #[must_use]
+    pub fn overlaps(&self, other: &Span) -> bool {
+        self.start < self.end
+            && other.start < other.end
+            && self.start < other.end
+            && other.start < self.end
+    }
+}
+
+/// Check no two spans overlap, reporting the first conflicting pair by index
+pub fn check_span_overlap(spans: &[Span]) -> Result<(), SpanOverlapError> {
+    let mut order: Vec<usize> = (0..spans.len()).collect();
+    order.sort_by_key(|&i| (spans[i].start, spans[i].end));
+
+    // Sorted by start, a span can only overlap the one reaching furthest before it
+    let mut furthest: Option<usize> = None;
+    for i in order {
+        if let Some(j) = furthest {
+            if spans[j].overlaps(&spans[i]) {
+                let (first, second) = (i.min(j), i.max(j));
+                return Err(SpanOverlapError {
+                    first,
+                    first_span: spans[first].clone(),
+                    second,
+                    second_span: spans[second].clone(),
+                });
+            }
+        }
+        if furthest.is_none_or(|j| spans[i].end > spans[j].end) {
+            furthest = Some(i);
+        }
+    }
+    Ok(())
+}
+
+/// Two spans in one annotation sharing an id
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+#[error("span {second} reuses id \"{id}\" of span {first}")]
+pub struct DuplicateSpanIdError {
+    pub id: String,
+    /// Index of the span that first used the id
+    pub first: usize,
+    /// Index of the span reusing it
+    pub second: usize,
+}
+
+/// The ids of `spans`, which must not repeat; spans without an id are skipped
+pub fn unique_span_ids(spans: &[Span]) -> Result<HashSet<String>, DuplicateSpanIdError> {
+    let mut seen: HashMap<&str, usize> = HashMap::new();
+    for (i, span) in spans.iter().enumerate() {
+        let Some(id) = span.id.as_deref() else {
+            continue;
+        };
+        if let Some(&first) = seen.get(id) {
+            return Err(DuplicateSpanIdError {
+                id: id.to_string(),
+                first,
+                second: i,
+            });
+        }
+        seen.insert(id, i);
+    }
+    Ok(seen.into_keys().map(str::to_string).collect())
+}
+
+/// A labeled, directed link between two spans in the same annotation.
+///
+/// Spans are referenced by the `id` field of their span values.
+#[typeshare]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Relation {
+    pub from_span_id: String,
+    pub to_span_id: String,
+    pub label: String,
+}
+
+/// Why a relation is invalid for an annotation
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum RelationError {
+    #[error("relation references span \"{0}\", which is not in the annotation")]
+    DanglingReference(String),
+}
+
+impl Relation {
+    /// Check both ends refer to spans among `span_ids`
+    pub fn validate(&self, span_ids: &HashSet<String>) -> Result<(), RelationError> {
+        for id in [&self.from_span_id, &self.to_span_id] {
+            if !span_ids.contains(id) {
+                return Err(RelationError::DanglingReference(id.clone()));
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_two_saves_produce_two_versions() {
+        let first = AnnotationVersion::initial(
+            AnnotationId::new(),
+            TaskId::new(),
+            UserId::new(),
+            serde_json::json!({"label": "cat"}),
+        );
+        let second = first.next(serde_json::json!({"label": "dog"}));
+
+        assert_eq!(first.version, 1);
+        assert_eq!(second.version, 2);
+        assert_eq!(second.annotation_id, first.annotation_id);
+        assert_eq!(first.data["label"], "cat");
+        assert_eq!(second.data["label"], "dog");
+    }
+
+    #[test]
+    fn test_bounding_box_within_image_is_valid() {
+        let bbox = BoundingBox {
+            x: 10.0,
+            y: 20.0,
+            w: 90.0,
+            h: 80.0,
+        };
+        assert_eq!(bbox.validate(100.0, 100.0), Ok(()));
+    }
+
+    #[test]
+    fn test_bounding_box_out_of_bounds() {
+        let bbox = BoundingBox {
+            x: 50.0,
+            y: 0.0,
+            w: 60.0,
+            h: 10.0,
+        };
+        assert_eq!(
+            bbox.validate(100.0, 100.0),
+            Err(BoundingBoxError::OutOfBounds {
+                image_width: 100.0,
+                image_height: 100.0
+            })
+        );
+
+        let negative_origin = BoundingBox { x: -1.0, ..bbox };
+        assert!(matches!(
+            negative_origin.validate(200.0, 200.0),
+            Err(BoundingBoxError::OutOfBounds { .. })
+        ));
+    }
+
+    #[test]
+    fn test_bounding_box_negative_dimensions() {
+        let bbox = BoundingBox {
+            x: 10.0,
+            y: 10.0,
+            w: -5.0,
+            h: 5.0,
+        };
+        assert!(matches!(
+            bbox.validate(100.0, 100.0),
+            Err(BoundingBoxError::NegativeDimensions { .. })
+        ));
+    }
+
+    #[test]
+    fn test_span_within_document_is_valid() {
+        assert_eq!(Span::new(0, 5).validate(11), Ok(()));
+        assert_eq!(Span::new(6, 11).validate(11), Ok(()));
+        // Empty spans mark a position
+        assert_eq!(Span::new(3, 3).validate(11), Ok(()));
+    }
+
+    #[test]
+    fn test_span_inverted() {
+        assert_eq!(
+            Span::new(8, 2).validate(11),
+            Err(SpanError::Inverted { start: 8, end: 2 })
+        );
+    }
+
+    #[test]
+    fn test_span_out_of_bounds() {
+        assert_eq!(
+            Span::new(6, 12).validate(11),
+            Err(SpanError::OutOfBounds {
+                end: 12,
+                doc_len: 11
+            })
+        );
+    }
+
+    #[test]
+    fn test_span_negative() {
+        assert!(matches!(
+            Span::new(-1, 4).validate(11),
+            Err(SpanError::Negative { .. })
+        ));
+    }
+
+    #[test]
+    fn test_overlapping_spans_report_the_pair() {
+        let spans = [Span::new(20, 30), Span::new(0, 5), Span::new(3, 8)];
+        let err = check_span_overlap(&spans).unwrap_err();
+        assert_eq!((err.first, err.second), (1, 2));
+        assert_eq!(err.first_span, Span::new(0, 5));
+        assert_eq!(err.second_span, Span::new(3, 8));
+    }
+
+    #[test]
+    fn test_nested_span_overlaps() {
+        let spans = [Span::new(0, 50), Span::new(10, 12), Span::new(40, 45)];
+        let err = check_span_overlap(&spans).unwrap_err();
+        assert_eq!((err.first, err.second), (0, 1));
+    }
+
+    #[test]
+    fn test_adjacent_and_empty_spans_do_not_overlap() {
+        let spans = [Span::new(0, 5), Span::new(5, 9), Span::new(7, 7)];
+        assert_eq!(check_span_overlap(&spans), Ok(()));
+    }
+
+    #[test]
+    fn test_span_ids_must_be_unique() {
+        let span = |start, end, id: &str| Span {
+            id: Some(id.to_string()),
+            ..Span::new(start, end)
+        };
+        let ids = unique_span_ids(&[span(0, 4, "a"), Span::new(5, 6), span(7, 9, "b")]).unwrap();
+        assert_eq!(ids, HashSet::from(["a".to_string(), "b".to_string()]));
+
+        let err =
+            unique_span_ids(&[span(0, 4, "a"), span(5, 6, "b"), span(7, 9, "a")]).unwrap_err();
+        assert_eq!(
+            err,
+            DuplicateSpanIdError {
+                id: "a".to_string(),
+                first: 0,
+                second: 2
+            }
+        );
+    }
+
+    #[test]
+    fn test_span_id_round_trips_and_is_optional() {
+        let span: Span = serde_json::from_value(serde_json::json!({"start": 1, "end": 3})).unwrap();
+        assert_eq!(span, Span::new(1, 3));
+        assert_eq!(
+            serde_json::to_value(&span).unwrap(),
+            serde_json::json!({"start": 1, "end": 3})
+        );
+
+        let span: Span =
+            serde_json::from_value(serde_json::json!({"start": 1, "end": 3, "id": "e1"})).unwrap();
+        assert_eq!(span.id.as_deref(), Some("e1"));
+    }
+
+    fn relation(from: &str, to: &str) -> Relation {
+        Relation {
+            from_span_id: from.to_string(),
+            to_span_id: to.to_string(),
+            label: "works_for".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_relation_between_existing_spans_is_valid() {
+        let ids: HashSet<String> = ["e1".to_string(), "e2".to_string()].into();
+        assert_eq!(relation("e1", "e2").validate(&ids), Ok(()));
+    }
+
+    #[test]
+    fn test_relation_with_dangling_reference() {
+        let ids: HashSet<String> = ["e1".to_string()].into();
+        assert_eq!(
+            relation("e1", "e9").validate(&ids),
+            Err(RelationError::DanglingReference("e9".to_string()))
+        );
+        assert_eq!(
+            relation("e0", "e1").validate(&ids),
+            Err(RelationError::DanglingReference("e0".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_polygon_triangle_is_valid() {
+        let triangle = Polygon {
+            points: vec![(0.0, 0.0), (10.0, 0.0), (5.0, 8.0)],
+        };
+        assert_eq!(triangle.validate(), Ok(()));
+    }
+
+    #[test]
+    fn test_polygon_with_two_points_is_invalid() {
+        let line = Polygon {
+            points: vec![(0.0, 0.0), (10.0, 0.0)],
+        };
+        assert_eq!(line.validate(), Err(PolygonError::TooFewPoints(2)));
+    }
+
+    #[test]
+    fn test_polygon_bowtie_is_self_intersecting() {
+        let bowtie = Polygon {
+            points: vec![(0.0, 0.0), (10.0, 10.0), (10.0, 0.0), (0.0, 10.0)],
+        };
+        let err = bowtie.validate().unwrap_err();
+        assert_eq!(
+            err,
+            PolygonError::SelfIntersecting {
+                first: 0,
+                second: 2
+            }
+        );
+        assert!(err.to_string().contains("self-intersecting"));
+    }
+}
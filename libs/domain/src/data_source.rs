@@ -9,7 +9,7 @@ use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use typeshare::typeshare;
 
-use crate::ids::{DataSourceId, ProjectId};
+use crate::ids::{DataSourceId, OrgId, ProjectId};
 
 /// Type of data source
 #[typeshare]
@@ -84,6 +84,12 @@ pub enum DataSourceConfig {
     FileUpload {
         allowed_extensions: Vec<String>,
         max_file_size_mb: i32,
+        /// Single-character field separator for CSV files
+        #[serde(default = "default_csv_delimiter")]
+        csv_delimiter: String,
+        /// Turn CSV numbers and booleans into JSON numbers and booleans
+        #[serde(default)]
+        csv_coerce_types: bool,
     },
     /// AWS S3 configuration
     S3 {
@@ -114,11 +120,20 @@ pub enum DataSourceConfig {
     },
 }
 
+/// CSV field separator used when a file upload source doesn't set one
+pub const DEFAULT_CSV_DELIMITER: &str = ",";
+
+fn default_csv_delimiter() -> String {
+    DEFAULT_CSV_DELIMITER.to_string()
+}
+
 impl Default for DataSourceConfig {
     fn default() -> Self {
         Self::FileUpload {
             allowed_extensions: vec!["json".to_string(), "jsonl".to_string(), "csv".to_string()],
             max_file_size_mb: 100,
+            csv_delimiter: default_csv_delimiter(),
+            csv_coerce_types: false,
         }
     }
 }
@@ -156,6 +171,7 @@ pub struct DataSource {
     pub is_active: bool,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
+    pub org_id: OrgId,
 }
 
 /// DTO for creating a new data source
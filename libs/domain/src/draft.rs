@@ -9,11 +9,13 @@ use serde::{Deserialize, Serialize};
 use crate::ids::{DraftId, TaskId, UserId};
 
 /// Auto-saved annotation work in progress.
-/// Only one draft per (task_id, user_id) pair.
+/// Only one draft per (task_id, step_id, user_id).
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Draft {
     pub draft_id: DraftId,
     pub task_id: TaskId,
+    /// Workflow step the work is for
+    pub step_id: String,
     pub user_id: UserId,
     /// Annotation data in progress
     pub data: serde_json::Value,
@@ -25,11 +27,17 @@ pub struct Draft {
 
 impl Draft {
     /// Create a new draft with initial data.
-    pub fn new(task_id: TaskId, user_id: UserId, data: serde_json::Value) -> Self {
+    pub fn new(
+        task_id: TaskId,
+        step_id: impl Into<String>,
+        user_id: UserId,
+        data: serde_json::Value,
+    ) -> Self {
         let now = Utc::now();
         Self {
             draft_id: DraftId::new(),
             task_id,
+            step_id: step_id.into(),
             user_id,
             data,
             version: 1,
@@ -62,9 +70,10 @@ mod tests {
         let user_id = UserId::new();
         let data = serde_json::json!({"field": "value"});
 
-        let draft = Draft::new(task_id, user_id, data.clone());
+        let draft = Draft::new(task_id, "annotate", user_id, data.clone());
 
         assert_eq!(draft.task_id, task_id);
+        assert_eq!(draft.step_id, "annotate");
         assert_eq!(draft.user_id, user_id);
         assert_eq!(draft.data, data);
         assert_eq!(draft.version, 1);
@@ -75,7 +84,7 @@ mod tests {
         let task_id = TaskId::new();
         let user_id = UserId::new();
         let initial_data = serde_json::json!({"field": "initial"});
-        let mut draft = Draft::new(task_id, user_id, initial_data);
+        let mut draft = Draft::new(task_id, "annotate", user_id, initial_data);
 
         let updated_data = serde_json::json!({"field": "updated"});
         draft.update(updated_data.clone());
@@ -53,6 +53,7 @@ pub enum AnnotationStatus {
 /// Status of a task assignment
 #[typeshare]
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
 #[serde(rename_all = "snake_case")]
 pub enum AssignmentStatus {
     Assigned,
@@ -200,6 +201,10 @@ pub enum LoadBalancingStrategy {
     RoundRobin,
     LeastLoaded,
     QualityWeighted,
+    /// Best weighted skill match, then least loaded
+    SkillWeighted,
+    /// Fewest assignments in a recent window, then least recently assigned
+    FairShare,
 }
 
 /// Type of contribution to a goal
@@ -246,9 +251,9 @@ pub enum TimeoutAction {
     Escalate,
 }
 
-/// Proficiency level of a user
+/// Proficiency level of a user, ordered from least to most proficient
 #[typeshare]
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
 pub enum ProficiencyLevel {
     Novice,
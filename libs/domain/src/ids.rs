@@ -136,6 +136,13 @@ define_prefixed_id!(SkipReasonId, "skip");
 define_prefixed_id!(ReviewId, "review");
 define_prefixed_id!(ReviewCommentId, "rcmt");
 define_prefixed_id!(TaskSkipId, "tskip");
+define_prefixed_id!(OrgId, "org");
+
+impl OrgId {
+    /// Organization that single-tenant installs, development and data
+    /// created before organizations existed belong to
+    pub const DEFAULT: Self = Self::from_uuid(Uuid::from_u128(1));
+}
 
 #[cfg(test)]
 mod tests {
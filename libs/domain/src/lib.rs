@@ -10,6 +10,7 @@ pub mod enums;
 pub mod goal;
 pub mod ids;
 pub mod layout;
+pub mod notification;
 pub mod project;
 pub mod project_type;
 pub mod quality;
@@ -28,6 +29,7 @@ pub use enums::*;
 pub use goal::*;
 pub use ids::*;
 pub use layout::*;
+pub use notification::*;
 pub use project::*;
 pub use project_type::*;
 pub use quality::*;
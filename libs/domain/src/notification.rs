@@ -0,0 +1,129 @@
+//! Notification domain models
+//!
+//! Notifications are published to NATS and delivered by the worker through
+//! the configured notifier (email, Slack, or none).
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use typeshare::typeshare;
+
+use crate::ids::{AnnotationId, ProjectId, TaskId, UserId};
+
+/// NATS subject that notification messages are published on
+pub const NOTIFICATION_SUBJECT: &str = "glyph.notifications";
+
+/// What a notification is about
+#[typeshare]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", content = "content", rename_all = "snake_case")]
+pub enum NotificationKind {
+    /// A project reached its deadline
+    DeadlineReached {
+        project_id: ProjectId,
+        project_name: String,
+        deadline: DateTime<Utc>,
+    },
+    /// A project passed its deadline and was escalated to team leads
+    DeadlineEscalated {
+        project_id: ProjectId,
+        project_name: String,
+        deadline: DateTime<Utc>,
+    },
+    /// An annotator's gold-task mismatch rate crossed the alert threshold
+    QualityAlert {
+        user_id: UserId,
+        mismatch_rate: f64,
+        gold_tasks: u32,
+    },
+    /// An annotation was rejected in review
+    AnnotationRejected {
+        annotation_id: AnnotationId,
+        task_id: TaskId,
+        reason: Option<String>,
+    },
+}
+
+impl NotificationKind {
+    /// Short, human-readable subject line
+    #[must_use]
+    pub fn title(&self) -> String {
+        match self {
+            Self::DeadlineReached { project_name, .. } => {
+                format!("Project \"{project_name}\" reached its deadline")
+            }
+            Self::DeadlineEscalated { project_name, .. } => {
+                format!("Escalation: project \"{project_name}\" is past its deadline")
+            }
+            Self::QualityAlert { user_id, .. } => {
+                format!("Quality alert: {user_id} is missing gold tasks")
+            }
+            Self::AnnotationRejected { .. } => "Your annotation was rejected".to_string(),
+        }
+    }
+
+    /// Plain-text message body
+    #[must_use]
+    pub fn body(&self) -> String {
+        match self {
+            Self::DeadlineReached {
+                project_id,
+                deadline,
+                ..
+            }
+            | Self::DeadlineEscalated {
+                project_id,
+                deadline,
+                ..
+            } => format!(
+                "Project {project_id} had a deadline of {}.",
+                deadline.to_rfc3339()
+            ),
+            Self::QualityAlert {
+                user_id,
+                mismatch_rate,
+                gold_tasks,
+            } => format!(
+                "{user_id} mismatched {:.0}% of {gold_tasks} gold tasks.",
+                mismatch_rate * 100.0
+            ),
+            Self::AnnotationRejected {
+                annotation_id,
+                task_id,
+                reason,
+            } => {
+                let reason = reason.as_deref().unwrap_or("no reason given");
+                format!("Annotation {annotation_id} on task {task_id} was rejected: {reason}")
+            }
+        }
+    }
+}
+
+/// A notification message addressed to one or more users
+#[typeshare]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Notification {
+    pub kind: NotificationKind,
+    pub recipients: Vec<UserId>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_notification_round_trips_through_json() {
+        let notification = Notification {
+            kind: NotificationKind::AnnotationRejected {
+                annotation_id: AnnotationId::new(),
+                task_id: TaskId::new(),
+                reason: Some("wrong label".to_string()),
+            },
+            recipients: vec![UserId::new()],
+        };
+
+        let json = serde_json::to_string(&notification).unwrap();
+        assert!(json.contains("\"type\":\"annotation_rejected\""));
+        let parsed: Notification = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed, notification);
+    }
+}
@@ -5,7 +5,7 @@ use serde::{Deserialize, Serialize};
 use typeshare::typeshare;
 
 use crate::enums::ProjectStatus;
-use crate::ids::{ProjectId, ProjectTypeId, TeamId, UserId, WorkflowId};
+use crate::ids::{OrgId, ProjectId, ProjectTypeId, TeamId, UserId, WorkflowId};
 
 /// Action to take when project deadline is reached
 #[typeshare]
@@ -40,13 +40,14 @@ pub struct Project {
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
     pub created_by: UserId,
+    pub org_id: OrgId,
 }
 
 // Note: ProjectType is defined in project_type.rs with full schema support
 
 /// Project-level settings
 #[typeshare]
-#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
 pub struct ProjectSettings {
     pub allow_self_review: bool,
     pub require_all_fields: bool,
@@ -8,7 +8,7 @@ use serde::{Deserialize, Serialize};
 use typeshare::typeshare;
 
 use crate::enums::ProficiencyLevel;
-use crate::ids::{ProjectTypeId, UserId};
+use crate::ids::{OrgId, ProjectTypeId, UserId};
 
 /// Difficulty level for project types
 #[typeshare]
@@ -21,6 +21,46 @@ pub enum DifficultyLevel {
     Expert,
 }
 
+impl DifficultyLevel {
+    /// Typical time to complete one task at this difficulty, in seconds
+    pub const fn default_duration_seconds(self) -> i32 {
+        match self {
+            Self::Easy => 30,
+            Self::Medium => 120,
+            Self::Hard => 300,
+            Self::Expert => 600,
+        }
+    }
+}
+
+/// Estimated time to complete one task
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EstimatedDuration {
+    pub seconds: i32,
+    /// Whether `seconds` was inferred from difficulty rather than set explicitly
+    pub inferred: bool,
+}
+
+impl EstimatedDuration {
+    /// The explicit duration if set, otherwise the default for the
+    /// difficulty level, or `None` if neither is set
+    pub fn resolve(
+        estimated_duration_seconds: Option<i32>,
+        difficulty_level: Option<DifficultyLevel>,
+    ) -> Option<Self> {
+        if let Some(seconds) = estimated_duration_seconds {
+            return Some(Self {
+                seconds,
+                inferred: false,
+            });
+        }
+        difficulty_level.map(|level| Self {
+            seconds: level.default_duration_seconds(),
+            inferred: true,
+        })
+    }
+}
+
 /// A skill requirement for a project type
 #[typeshare]
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -51,18 +91,43 @@ pub struct ProjectType {
     pub input_schema: serde_json::Value,
     /// JSON Schema for validating annotation output data
     pub output_schema: serde_json::Value,
+    /// Version of the schemas, incremented whenever either changes
+    pub schema_version: i32,
     /// Estimated time to complete one task (seconds)
     pub estimated_duration_seconds: Option<i32>,
     /// Difficulty level for task assignment
     pub difficulty_level: Option<DifficultyLevel>,
     /// Skills required for this project type
     pub skill_requirements: Vec<SkillRequirement>,
+    /// Whether spans within one annotation field may overlap
+    pub allow_overlapping_spans: bool,
     /// Whether this is a system-provided template (vs user-created)
     pub is_system: bool,
     /// User who created this project type (null for system types)
     pub created_by: Option<UserId>,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
+    /// Owning organization; system types are visible to every organization
+    pub org_id: OrgId,
+}
+
+/// The input and output schemas of a project type at one version
+#[typeshare]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProjectTypeSchemaVersion {
+    pub project_type_id: ProjectTypeId,
+    pub version: i32,
+    pub input_schema: serde_json::Value,
+    pub output_schema: serde_json::Value,
+    pub created_at: DateTime<Utc>,
+}
+
+impl ProjectType {
+    /// Estimated task duration: the explicit value if set, otherwise the
+    /// default for the difficulty level, or `None` if neither is set
+    pub fn effective_duration(&self) -> Option<EstimatedDuration> {
+        EstimatedDuration::resolve(self.estimated_duration_seconds, self.difficulty_level)
+    }
 }
 
 /// DTO for creating a new project type
@@ -76,6 +141,8 @@ pub struct CreateProjectType {
     pub estimated_duration_seconds: Option<i32>,
     pub difficulty_level: Option<DifficultyLevel>,
     pub skill_requirements: Option<Vec<SkillRequirement>>,
+    /// Defaults to allowing overlap
+    pub allow_overlapping_spans: Option<bool>,
     pub is_system: Option<bool>,
 }
 
@@ -89,6 +156,7 @@ pub struct UpdateProjectType {
     pub output_schema: Option<serde_json::Value>,
     pub estimated_duration_seconds: Option<i32>,
     pub difficulty_level: Option<DifficultyLevel>,
+    pub allow_overlapping_spans: Option<bool>,
 }
 
 /// Filter options for listing project types
@@ -113,3 +181,61 @@ pub struct ProjectTypeSummary {
     pub skill_count: i32,
     pub created_at: DateTime<Utc>,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn project_type(
+        estimated_duration_seconds: Option<i32>,
+        difficulty_level: Option<DifficultyLevel>,
+    ) -> ProjectType {
+        ProjectType {
+            project_type_id: ProjectTypeId::new(),
+            name: "Sentiment".to_string(),
+            description: None,
+            input_schema: serde_json::json!({}),
+            output_schema: serde_json::json!({}),
+            schema_version: 1,
+            estimated_duration_seconds,
+            difficulty_level,
+            skill_requirements: Vec::new(),
+            allow_overlapping_spans: true,
+            is_system: false,
+            created_by: None,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+            org_id: OrgId::DEFAULT,
+        }
+    }
+
+    #[test]
+    fn test_duration_defaults_per_difficulty() {
+        for (level, seconds) in [
+            (DifficultyLevel::Easy, 30),
+            (DifficultyLevel::Medium, 120),
+            (DifficultyLevel::Hard, 300),
+            (DifficultyLevel::Expert, 600),
+        ] {
+            assert_eq!(
+                project_type(None, Some(level)).effective_duration(),
+                Some(EstimatedDuration {
+                    seconds,
+                    inferred: true
+                })
+            );
+        }
+    }
+
+    #[test]
+    fn test_explicit_duration_wins() {
+        assert_eq!(
+            project_type(Some(45), Some(DifficultyLevel::Expert)).effective_duration(),
+            Some(EstimatedDuration {
+                seconds: 45,
+                inferred: false
+            })
+        );
+        assert_eq!(project_type(None, None).effective_duration(), None);
+    }
+}
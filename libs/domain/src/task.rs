@@ -18,11 +18,21 @@ pub struct Task {
     pub input_data: serde_json::Value,
     pub workflow_state: WorkflowState,
     pub metadata: serde_json::Value,
+    /// Known-correct output for gold (honeypot) tasks; `None` for regular tasks
+    pub gold_output: Option<serde_json::Value>,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
     pub completed_at: Option<DateTime<Utc>>,
 }
 
+impl Task {
+    /// Whether this is a gold task with a known answer
+    #[must_use]
+    pub const fn is_gold(&self) -> bool {
+        self.gold_output.is_some()
+    }
+}
+
 /// Current state of the task in the workflow
 #[typeshare]
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
@@ -70,6 +80,19 @@ pub struct TaskAssignment {
     pub metadata: serde_json::Value,
 }
 
+/// NATS subject that assignment requests are published on
+pub const ASSIGNMENT_SUBJECT: &str = "glyph.assignments";
+
+/// A request for the worker to assign a task step to a user
+#[typeshare]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AssignmentRequest {
+    pub task_id: TaskId,
+    pub project_id: ProjectId,
+    pub step_id: String,
+    pub user_id: UserId,
+}
+
 /// Reason for rejecting a task assignment
 #[typeshare]
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -4,7 +4,7 @@ use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use typeshare::typeshare;
 
-use crate::ids::{TeamId, UserId};
+use crate::ids::{OrgId, TeamId, UserId};
 
 /// Status of a team
 #[typeshare]
@@ -38,6 +38,7 @@ pub struct Team {
     pub specializations: Vec<String>,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
+    pub org_id: OrgId,
 }
 
 /// A membership record linking a user to a team
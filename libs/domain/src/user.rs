@@ -66,6 +66,7 @@ pub struct UserSkill {
 pub struct QualityProfile {
     pub overall_score: Option<f64>,
     pub accuracy_score: Option<f64>,
+    pub gold_accuracy_score: Option<f64>,
     pub consistency_score: Option<f64>,
     pub speed_percentile: Option<f64>,
     pub total_annotations: i64,
@@ -0,0 +1,156 @@
+//! Result caching for deterministic plugins
+//!
+//! A deterministic plugin (a normalizer, a tokenizer) always produces the same
+//! output for the same module, export and input, so
+//! [`WasmPool`](super::WasmPool) can skip re-running it. Results are keyed by
+//! a SHA-256 of the module's bytes, the export called and the input, so
+//! re-registering a name with a new build never serves the old build's
+//! results, and two exports of one module never share them.
+
+use std::collections::HashMap;
+use std::fmt::Write;
+use std::time::{Duration, Instant};
+
+use async_trait::async_trait;
+use deadpool_redis::{redis::AsyncCommands, Pool};
+use sha2::{Digest, Sha256};
+use tokio::sync::RwLock;
+
+use super::runtime::WasmError;
+
+/// Redis key prefix for cached plugin results
+pub const RESULT_KEY_PREFIX: &str = "glyph:plugins:result:";
+
+/// Store of plugin results, keyed by [`result_key`]
+#[async_trait]
+pub trait ResultCache: Send + Sync {
+    /// The cached result for `key`, if present and not expired
+    async fn get(&self, key: &str) -> Result<Option<Vec<u8>>, WasmError>;
+
+    /// Cache `value` under `key` for `ttl`
+    async fn put(&self, key: &str, value: &[u8], ttl: Duration) -> Result<(), WasmError>;
+}
+
+/// Cache key for calling `export` of the module with SHA-256 `module_digest`
+/// on `input`
+pub fn result_key(module_digest: &[u8; 32], export: &str, input: &[u8]) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(module_digest);
+    // Length-prefixed so the export name and input can't run together
+    hasher.update((export.len() as u64).to_le_bytes());
+    hasher.update(export);
+    hasher.update(input);
+
+    let mut key = String::from(RESULT_KEY_PREFIX);
+    for byte in hasher.finalize() {
+        let _ = write!(key, "{byte:02x}");
+    }
+    key
+}
+
+/// SHA-256 of a module's bytes, identifying the build in cache keys
+pub fn module_digest(wasm_bytes: &[u8]) -> [u8; 32] {
+    Sha256::digest(wasm_bytes).into()
+}
+
+/// Redis-backed result cache, shared by every instance
+pub struct RedisResultCache {
+    pool: Pool,
+}
+
+impl RedisResultCache {
+    /// Create a result cache on the given Redis pool
+    pub const fn new(pool: Pool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl ResultCache for RedisResultCache {
+    async fn get(&self, key: &str) -> Result<Option<Vec<u8>>, WasmError> {
+        let mut conn = self
+            .pool
+            .get()
+            .await
+            .map_err(|e| WasmError::CacheError(format!("redis pool: {e}")))?;
+        conn.get(key)
+            .await
+            .map_err(|e| WasmError::CacheError(format!("redis get: {e}")))
+    }
+
+    async fn put(&self, key: &str, value: &[u8], ttl: Duration) -> Result<(), WasmError> {
+        let mut conn = self
+            .pool
+            .get()
+            .await
+            .map_err(|e| WasmError::CacheError(format!("redis pool: {e}")))?;
+        // Redis rejects a zero expiry
+        let seconds = ttl.as_secs().max(1);
+        conn.set_ex::<_, _, ()>(key, value, seconds)
+            .await
+            .map_err(|e| WasmError::CacheError(format!("redis set: {e}")))
+    }
+}
+
+/// In-process result cache, for a single instance or tests
+#[derive(Default)]
+pub struct InMemoryResultCache {
+    entries: RwLock<HashMap<String, (Vec<u8>, Instant)>>,
+}
+
+impl InMemoryResultCache {
+    /// Create an empty cache
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+#[async_trait]
+impl ResultCache for InMemoryResultCache {
+    async fn get(&self, key: &str) -> Result<Option<Vec<u8>>, WasmError> {
+        let entries = self.entries.read().await;
+        Ok(entries
+            .get(key)
+            .filter(|(_, expires_at)| *expires_at > Instant::now())
+            .map(|(value, _)| value.clone()))
+    }
+
+    async fn put(&self, key: &str, value: &[u8], ttl: Duration) -> Result<(), WasmError> {
+        let now = Instant::now();
+        let mut entries = self.entries.write().await;
+        entries.retain(|_, (_, expires_at)| *expires_at > now);
+        entries.insert(key.to_string(), (value.to_vec(), now + ttl));
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_result_key_depends_on_module_export_and_input() {
+        let a = module_digest(b"module a");
+        let b = module_digest(b"module b");
+
+        let key = result_key(&a, "run", b"input");
+        assert!(key.starts_with(RESULT_KEY_PREFIX));
+        assert_eq!(key.len(), RESULT_KEY_PREFIX.len() + 64);
+        assert_eq!(key, result_key(&a, "run", b"input"));
+        assert_ne!(key, result_key(&b, "run", b"input"));
+        assert_ne!(key, result_key(&a, "run", b"other input"));
+        assert_ne!(key, result_key(&a, "validate", b"input"));
+        assert_ne!(result_key(&a, "ab", b"c"), result_key(&a, "a", b"bc"));
+    }
+
+    #[tokio::test]
+    async fn test_in_memory_entries_expire() {
+        let cache = InMemoryResultCache::new();
+        cache.put("a", b"1", Duration::from_secs(60)).await.unwrap();
+        cache.put("b", b"2", Duration::ZERO).await.unwrap();
+
+        assert_eq!(cache.get("a").await.unwrap(), Some(b"1".to_vec()));
+        assert_eq!(cache.get("b").await.unwrap(), None);
+        assert_eq!(cache.get("c").await.unwrap(), None);
+    }
+}
@@ -1,5 +1,9 @@
 //! WASM plugin runtime
 
+mod cache;
+mod pool;
 mod runtime;
 
+pub use cache::*;
+pub use pool::*;
 pub use runtime::*;
@@ -0,0 +1,472 @@
+//! Bounded pool for concurrent WASM executions
+//!
+//! Modules are compiled and linked once, when registered, so each execution
+//! only pays for a fresh store and instance. A semaphore caps how many
+//! executions run at once; what happens beyond the cap is set by
+//! [`BusyPolicy`].
+//!
+//! With a [`ResultCache`] attached, results of
+//! [`execute_with_input`](WasmPool::execute_with_input) are cached per module
+//! build, export and input, except for modules registered as
+//! non-deterministic.
+
+use std::collections::HashMap;
+use std::sync::{Arc, PoisonError, RwLock};
+use std::time::Duration;
+
+use tokio::sync::{OwnedSemaphorePermit, Semaphore, TryAcquireError};
+use wasmtime::{Func, Instance, InstancePre, Linker, Store, StoreLimits, StoreLimitsBuilder};
+
+use super::cache::{module_digest, result_key, ResultCache};
+use super::runtime::{BusyPolicy, WasmError, WasmRuntime};
+
+/// Whether a call may be answered from the result cache
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum CacheMode {
+    /// Serve a cached result if there is one
+    #[default]
+    Use,
+    /// Always execute, then refresh the cached result
+    Bypass,
+}
+
+/// A registered, pre-instantiated module
+#[derive(Clone)]
+struct PooledModule {
+    pre: InstancePre<StoreLimits>,
+    digest: [u8; 32],
+    deterministic: bool,
+}
+
+/// A pool of pre-instantiated modules with a cap on concurrent executions
+pub struct WasmPool {
+    runtime: WasmRuntime,
+    linker: Linker<StoreLimits>,
+    modules: RwLock<HashMap<String, PooledModule>>,
+    permits: Arc<Semaphore>,
+    result_cache: Option<(Arc<dyn ResultCache>, Duration)>,
+}
+
+impl WasmPool {
+    /// Create a pool sized by the runtime's `max_concurrent_executions`
+    pub fn new(runtime: WasmRuntime) -> Self {
+        let linker = Linker::new(runtime.engine());
+        let permits = Arc::new(Semaphore::new(runtime.config().max_concurrent_executions));
+        Self {
+            runtime,
+            linker,
+            modules: RwLock::new(HashMap::new()),
+            permits,
+            result_cache: None,
+        }
+    }
+
+    /// Cache results of deterministic modules in `cache` for `ttl`
+    #[must_use]
+    pub fn with_result_cache(mut self, cache: Arc<dyn ResultCache>, ttl: Duration) -> Self {
+        self.result_cache = Some((cache, ttl));
+        self
+    }
+
+    /// Compile and link a deterministic module under `name`, replacing any
+    /// module already registered under it
+    pub fn register(&self, name: impl Into<String>, wasm_bytes: &[u8]) -> Result<(), WasmError> {
+        self.insert(name.into(), wasm_bytes, true)
+    }
+
+    /// Like [`register`](Self::register), for a module whose output may vary
+    /// for the same input; its results are never cached
+    pub fn register_non_deterministic(
+        &self,
+        name: impl Into<String>,
+        wasm_bytes: &[u8],
+    ) -> Result<(), WasmError> {
+        self.insert(name.into(), wasm_bytes, false)
+    }
+
+    fn insert(
+        &self,
+        name: String,
+        wasm_bytes: &[u8],
+        deterministic: bool,
+    ) -> Result<(), WasmError> {
+        let module = self.runtime.load_module(wasm_bytes)?;
+        let pre = self
+            .linker
+            .instantiate_pre(module.module())
+            .map_err(|e| WasmError::ModuleLoadError(e.to_string()))?;
+        let pooled = PooledModule {
+            pre,
+            digest: module_digest(wasm_bytes),
+            deterministic,
+        };
+        self.modules
+            .write()
+            .unwrap_or_else(PoisonError::into_inner)
+            .insert(name, pooled);
+        Ok(())
+    }
+
+    /// Whether a module is registered under `name`
+    pub fn contains(&self, name: &str) -> bool {
+        self.modules
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .contains_key(name)
+    }
+
+    /// Executions that can start right now without waiting
+    pub fn available_permits(&self) -> usize {
+        self.permits.available_permits()
+    }
+
+    /// Run `run` against a fresh instance of the module registered under
+    /// `name`.
+    ///
+    /// The call runs on the blocking thread pool and holds a permit until it
+    /// returns. With every permit taken, [`BusyPolicy::Wait`] queues for one
+    /// and [`BusyPolicy::Reject`] fails with [`WasmError::Busy`].
+    pub async fn execute<T, F>(&self, name: &str, run: F) -> Result<T, WasmError>
+    where
+        F: FnOnce(&mut Store<StoreLimits>, Instance) -> wasmtime::Result<T> + Send + 'static,
+        T: Send + 'static,
+    {
+        let pre = self.module(name)?.pre;
+        self.run(pre, run).await
+    }
+
+    /// Call the export `export` of a fresh instance of the module registered
+    /// under `name` on `input`, returning its output.
+    ///
+    /// `run` receives the resolved export and does the marshalling; its
+    /// output must depend only on calling that export with `input`, since
+    /// that is what results are cached by. For a deterministic module in a
+    /// pool with a result cache, an identical earlier call's output is
+    /// returned without executing, unless `mode` is [`CacheMode::Bypass`].
+    /// Cache failures are logged and fall back to executing.
+    pub async fn execute_with_input<F>(
+        &self,
+        name: &str,
+        export: &str,
+        input: Vec<u8>,
+        mode: CacheMode,
+        run: F,
+    ) -> Result<Vec<u8>, WasmError>
+    where
+        F: FnOnce(&mut Store<StoreLimits>, Instance, Func, &[u8]) -> wasmtime::Result<Vec<u8>>
+            + Send
+            + 'static,
+    {
+        let module = self.module(name)?;
+        let cache = self
+            .result_cache
+            .as_ref()
+            .filter(|_| module.deterministic)
+            .map(|(cache, ttl)| (cache, *ttl, result_key(&module.digest, export, &input)));
+
+        if let Some((cache, _, key)) = &cache {
+            if mode == CacheMode::Use {
+                match cache.get(key).await {
+                    Ok(Some(output)) => return Ok(output),
+                    Ok(None) => {}
+                    Err(e) => {
+                        tracing::warn!(plugin = name, error = %e, "Result cache lookup failed");
+                    }
+                }
+            }
+        }
+
+        let export_name = export.to_string();
+        let output = self
+            .run(module.pre, move |store, instance| {
+                let func = instance
+                    .get_func(&mut *store, &export_name)
+                    .ok_or_else(|| {
+                        wasmtime::Error::msg(format!("no export named '{export_name}'"))
+                    })?;
+                run(store, instance, func, &input)
+            })
+            .await?;
+
+        if let Some((cache, ttl, key)) = &cache {
+            if let Err(e) = cache.put(key, &output, *ttl).await {
+                tracing::warn!(plugin = name, error = %e, "Result cache store failed");
+            }
+        }
+        Ok(output)
+    }
+
+    fn module(&self, name: &str) -> Result<PooledModule, WasmError> {
+        self.modules
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .get(name)
+            .cloned()
+            .ok_or_else(|| WasmError::ModuleLoadError(format!("no module named '{name}'")))
+    }
+
+    async fn run<T, F>(&self, pre: InstancePre<StoreLimits>, run: F) -> Result<T, WasmError>
+    where
+        F: FnOnce(&mut Store<StoreLimits>, Instance) -> wasmtime::Result<T> + Send + 'static,
+        T: Send + 'static,
+    {
+        let permit = self.acquire().await?;
+
+        let max_memory =
+            usize::try_from(self.runtime.config().max_memory_bytes).unwrap_or(usize::MAX);
+        let engine = self.runtime.engine().clone();
+        tokio::task::spawn_blocking(move || {
+            let _permit = permit;
+            let limits = StoreLimitsBuilder::new().memory_size(max_memory).build();
+            let mut store = Store::new(&engine, limits);
+            store.limiter(|limits| limits);
+            let instance = pre
+                .instantiate(&mut store)
+                .map_err(|e| WasmError::ExecutionError(e.to_string()))?;
+            run(&mut store, instance).map_err(|e| WasmError::ExecutionError(e.to_string()))
+        })
+        .await
+        .map_err(|e| WasmError::ExecutionError(e.to_string()))?
+    }
+
+    async fn acquire(&self) -> Result<OwnedSemaphorePermit, WasmError> {
+        let busy = || WasmError::Busy {
+            limit: self.runtime.config().max_concurrent_executions,
+        };
+        match self.runtime.config().when_busy {
+            BusyPolicy::Wait => Arc::clone(&self.permits)
+                .acquire_owned()
+                .await
+                .map_err(|_| busy()),
+            BusyPolicy::Reject => match Arc::clone(&self.permits).try_acquire_owned() {
+                Ok(permit) => Ok(permit),
+                Err(TryAcquireError::NoPermits | TryAcquireError::Closed) => Err(busy()),
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::mpsc;
+    use std::time::Duration;
+
+    use super::*;
+    use crate::wasm::{InMemoryResultCache, WasmRuntimeConfig};
+
+    const ANSWER_WAT: &str = r#"(module
+        (func (export "answer") (result i32) i32.const 42)
+        (func (export "double") (result i32) i32.const 84))"#;
+
+    fn pool(limit: usize, when_busy: BusyPolicy) -> Arc<WasmPool> {
+        let runtime = WasmRuntime::new(WasmRuntimeConfig {
+            max_concurrent_executions: limit,
+            when_busy,
+            ..Default::default()
+        })
+        .unwrap();
+        let pool = WasmPool::new(runtime);
+        pool.register("answer", ANSWER_WAT.as_bytes()).unwrap();
+        Arc::new(pool)
+    }
+
+    fn call_answer(store: &mut Store<StoreLimits>, instance: Instance) -> wasmtime::Result<i32> {
+        let answer = instance.get_typed_func::<(), i32>(&mut *store, "answer")?;
+        answer.call(store, ())
+    }
+
+    /// Start an execution that holds its permit until `release` is sent
+    async fn hold_permit(pool: &Arc<WasmPool>) -> mpsc::Sender<()> {
+        let (release, held) = mpsc::channel::<()>();
+        let before = pool.available_permits();
+        let holder = Arc::clone(pool);
+        tokio::spawn(async move {
+            holder
+                .execute("answer", move |store, instance| {
+                    held.recv().ok();
+                    call_answer(store, instance)
+                })
+                .await
+        });
+        while pool.available_permits() == before {
+            tokio::task::yield_now().await;
+        }
+        release
+    }
+
+    #[tokio::test]
+    async fn test_executes_registered_module() {
+        let pool = pool(2, BusyPolicy::Wait);
+        assert_eq!(pool.execute("answer", call_answer).await.unwrap(), 42);
+        assert_eq!(pool.execute("answer", call_answer).await.unwrap(), 42);
+        assert_eq!(pool.available_permits(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_unknown_module_is_an_error() {
+        let pool = pool(1, BusyPolicy::Wait);
+        assert!(!pool.contains("missing"));
+        assert!(matches!(
+            pool.execute("missing", call_answer).await,
+            Err(WasmError::ModuleLoadError(_))
+        ));
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+    async fn test_execution_beyond_limit_is_rejected() {
+        let pool = pool(2, BusyPolicy::Reject);
+        let first = hold_permit(&pool).await;
+        let second = hold_permit(&pool).await;
+
+        assert!(matches!(
+            pool.execute("answer", call_answer).await,
+            Err(WasmError::Busy { limit: 2 })
+        ));
+
+        first.send(()).unwrap();
+        second.send(()).unwrap();
+        while pool.available_permits() < 2 {
+            tokio::task::yield_now().await;
+        }
+        assert_eq!(pool.execute("answer", call_answer).await.unwrap(), 42);
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+    async fn test_execution_beyond_limit_waits_for_a_permit() {
+        let pool = pool(1, BusyPolicy::Wait);
+        let release = hold_permit(&pool).await;
+
+        let waiter = Arc::clone(&pool);
+        let mut queued = tokio::spawn(async move { waiter.execute("answer", call_answer).await });
+        assert!(
+            tokio::time::timeout(Duration::from_millis(50), &mut queued)
+                .await
+                .is_err(),
+            "execution should wait while the pool is full"
+        );
+
+        release.send(()).unwrap();
+        assert_eq!(queued.await.unwrap().unwrap(), 42);
+    }
+
+    fn cached_pool() -> (WasmPool, Arc<AtomicUsize>) {
+        let runtime = WasmRuntime::new(WasmRuntimeConfig::default()).unwrap();
+        let pool = WasmPool::new(runtime).with_result_cache(
+            Arc::new(InMemoryResultCache::new()),
+            Duration::from_secs(60),
+        );
+        pool.register("normalize", ANSWER_WAT.as_bytes()).unwrap();
+        pool.register_non_deterministic("sample", ANSWER_WAT.as_bytes())
+            .unwrap();
+        (pool, Arc::new(AtomicUsize::new(0)))
+    }
+
+    /// Appends the export's result to the upper-cased input, counting how
+    /// often it actually runs
+    fn counted(
+        runs: &Arc<AtomicUsize>,
+    ) -> impl FnOnce(&mut Store<StoreLimits>, Instance, Func, &[u8]) -> wasmtime::Result<Vec<u8>>
+           + Send
+           + 'static {
+        let runs = Arc::clone(runs);
+        move |store, _instance, func, input| {
+            runs.fetch_add(1, Ordering::SeqCst);
+            let value = func.typed::<(), i32>(&*store)?.call(&mut *store, ())?;
+            let mut output = input.to_ascii_uppercase();
+            output.extend_from_slice(value.to_string().as_bytes());
+            Ok(output)
+        }
+    }
+
+    #[tokio::test]
+    async fn test_identical_call_is_served_from_cache() {
+        let (pool, runs) = cached_pool();
+        for _ in 0..2 {
+            let output = pool
+                .execute_with_input(
+                    "normalize",
+                    "answer",
+                    b"hello".to_vec(),
+                    CacheMode::Use,
+                    counted(&runs),
+                )
+                .await
+                .unwrap();
+            assert_eq!(output, b"HELLO42");
+        }
+        assert_eq!(runs.load(Ordering::SeqCst), 1);
+
+        pool.execute_with_input(
+            "normalize",
+            "answer",
+            b"other".to_vec(),
+            CacheMode::Use,
+            counted(&runs),
+        )
+        .await
+        .unwrap();
+        assert_eq!(runs.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn test_exports_of_one_module_are_cached_separately() {
+        let (pool, runs) = cached_pool();
+        for (export, expected) in [("answer", b"HELLO42"), ("double", b"HELLO84")] {
+            let output = pool
+                .execute_with_input(
+                    "normalize",
+                    export,
+                    b"hello".to_vec(),
+                    CacheMode::Use,
+                    counted(&runs),
+                )
+                .await
+                .unwrap();
+            assert_eq!(output, expected);
+        }
+        assert_eq!(runs.load(Ordering::SeqCst), 2);
+
+        assert!(matches!(
+            pool.execute_with_input(
+                "normalize",
+                "missing",
+                b"hello".to_vec(),
+                CacheMode::Use,
+                counted(&runs),
+            )
+            .await,
+            Err(WasmError::ExecutionError(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_bypass_and_non_deterministic_modules_always_execute() {
+        let (pool, runs) = cached_pool();
+        for mode in [CacheMode::Use, CacheMode::Bypass] {
+            pool.execute_with_input(
+                "normalize",
+                "answer",
+                b"hello".to_vec(),
+                mode,
+                counted(&runs),
+            )
+            .await
+            .unwrap();
+        }
+        assert_eq!(runs.load(Ordering::SeqCst), 2);
+
+        for _ in 0..2 {
+            pool.execute_with_input(
+                "sample",
+                "answer",
+                b"hello".to_vec(),
+                CacheMode::Use,
+                counted(&runs),
+            )
+            .await
+            .unwrap();
+        }
+        assert_eq!(runs.load(Ordering::SeqCst), 4);
+    }
+}
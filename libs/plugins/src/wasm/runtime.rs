@@ -13,6 +13,22 @@ pub enum WasmError {
 
     #[error("Plugin execution failed: {0}")]
     ExecutionError(String),
+
+    #[error("All {limit} plugin execution slots are in use")]
+    Busy { limit: usize },
+
+    #[error("Plugin result cache failed: {0}")]
+    CacheError(String),
+}
+
+/// What to do with an execution when every slot is in use
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum BusyPolicy {
+    /// Queue until a slot frees up
+    #[default]
+    Wait,
+    /// Fail immediately with [`WasmError::Busy`]
+    Reject,
 }
 
 /// Configuration for the WASM runtime
@@ -21,6 +37,9 @@ pub struct WasmRuntimeConfig {
     pub max_memory_bytes: u64,
     pub max_execution_time_ms: u64,
     pub enable_bulk_memory: bool,
+    /// Executions allowed to run at once in a [`WasmPool`](super::WasmPool)
+    pub max_concurrent_executions: usize,
+    pub when_busy: BusyPolicy,
 }
 
 impl Default for WasmRuntimeConfig {
@@ -29,6 +48,8 @@ impl Default for WasmRuntimeConfig {
             max_memory_bytes: 64 * 1024 * 1024, // 64 MB
             max_execution_time_ms: 5000,        // 5 seconds
             enable_bulk_memory: true,
+            max_concurrent_executions: 8,
+            when_busy: BusyPolicy::Wait,
         }
     }
 }
@@ -36,7 +57,6 @@ impl Default for WasmRuntimeConfig {
 /// WASM plugin runtime
 pub struct WasmRuntime {
     engine: Engine,
-    #[allow(dead_code)]
     config: WasmRuntimeConfig,
 }
 
@@ -54,6 +74,14 @@ impl WasmRuntime {
         })
     }
 
+    pub const fn engine(&self) -> &Engine {
+        &self.engine
+    }
+
+    pub const fn config(&self) -> &WasmRuntimeConfig {
+        &self.config
+    }
+
     /// Load a WASM module from bytes
     pub fn load_module(&self, wasm_bytes: &[u8]) -> Result<WasmModule, WasmError> {
         let module = Module::new(&self.engine, wasm_bytes)?;
@@ -68,11 +96,15 @@ impl WasmRuntime {
 pub struct WasmModule {
     #[allow(dead_code)]
     engine: Engine,
-    #[allow(dead_code)]
     module: Module,
 }
 
 impl WasmModule {
+    /// The compiled module
+    pub const fn module(&self) -> &Module {
+        &self.module
+    }
+
     /// Create a new instance of the module
     pub fn instantiate(&self) -> Result<WasmInstance, WasmError> {
         let store = Store::new(&self.engine, ());
@@ -95,6 +127,8 @@ mod tests {
         let config = WasmRuntimeConfig::default();
         assert_eq!(config.max_memory_bytes, 64 * 1024 * 1024);
         assert_eq!(config.max_execution_time_ms, 5000);
+        assert_eq!(config.max_concurrent_executions, 8);
+        assert_eq!(config.when_busy, BusyPolicy::Wait);
     }
 
     #[test]
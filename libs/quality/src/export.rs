@@ -1,6 +1,24 @@
 //! Data export service
 
+use std::io::Write;
+use std::sync::Arc;
+
+use arrow::array::{
+    ArrayRef, BooleanArray, Float64Array, Int32Array, Int64Array, StringArray, StructArray,
+    TimestampMicrosecondArray,
+};
+use arrow::buffer::NullBuffer;
+use arrow::datatypes::{DataType, Field, Fields, Schema, TimeUnit};
+use arrow::record_batch::RecordBatch;
 use async_trait::async_trait;
+use chrono::{DateTime, Duration, Utc};
+use glyph_common::StorageService;
+use glyph_db::{
+    AnnotationRepository, CompletedAnnotation, ProjectRepository, ProjectTypeRepository,
+};
+use glyph_domain::ProjectId;
+use parquet::arrow::ArrowWriter;
+use serde_json::Value;
 use thiserror::Error;
 use uuid::Uuid;
 
@@ -15,6 +33,13 @@ pub enum ExportError {
     #[error("Export failed: {0}")]
     ExportFailed(String),
 
+    #[error("Annotation {annotation_id} has a non-{expected} value in column {column}")]
+    TypeMismatch {
+        annotation_id: Uuid,
+        column: String,
+        expected: &'static str,
+    },
+
     #[error("Database error: {0}")]
     DatabaseError(String),
 }
@@ -70,3 +95,621 @@ pub trait ExportService: Send + Sync {
     /// Get export progress for a running export job
     async fn get_export_progress(&self, job_id: Uuid) -> Result<f64, ExportError>;
 }
+
+// =============================================================================
+// Parquet Export
+// =============================================================================
+
+/// Outcome of an export run
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ExportSummary {
+    /// Number of rows written
+    pub rows: usize,
+    /// Upper bound of the exported change window, to pass as `since` next time.
+    ///
+    /// Carries the previous `since` forward if it was later.
+    pub checkpoint: Option<DateTime<Utc>>,
+}
+
+/// How far behind the current time an export's change window ends.
+///
+/// A row's change time is stamped when its transaction starts, so a slow
+/// transaction can commit a row older than rows already exported. Stopping
+/// the window this far back leaves such rows to the next export instead of
+/// skipping them past its checkpoint.
+pub const EXPORT_SAFETY_LAG_SECONDS: i64 = 300;
+
+/// Exports completed annotations as Parquet files that Hugging Face
+/// `datasets` can load directly.
+pub struct AnnotationExportService {
+    annotations: Arc<dyn AnnotationRepository>,
+    projects: Arc<dyn ProjectRepository>,
+    project_types: Arc<dyn ProjectTypeRepository>,
+}
+
+impl AnnotationExportService {
+    pub fn new(
+        annotations: Arc<dyn AnnotationRepository>,
+        projects: Arc<dyn ProjectRepository>,
+        project_types: Arc<dyn ProjectTypeRepository>,
+    ) -> Self {
+        Self {
+            annotations,
+            projects,
+            project_types,
+        }
+    }
+
+    /// Write a project's completed annotations to `writer` as Parquet.
+    ///
+    /// Columns are derived from the project type's output schema; projects
+    /// without a project type get a single JSON `data` column. With `since`,
+    /// only annotations whose task completed or that changed after it are
+    /// written; `None` exports everything. Changes from the last
+    /// [`EXPORT_SAFETY_LAG_SECONDS`] are left for the next export.
+    pub async fn export_parquet<W: Write + Send>(
+        &self,
+        project_id: Uuid,
+        since: Option<DateTime<Utc>>,
+        writer: W,
+    ) -> Result<ExportSummary, ExportError> {
+        let project = self
+            .projects
+            .find_by_id(&ProjectId::from_uuid(project_id))
+            .await
+            .map_err(|e| ExportError::DatabaseError(e.to_string()))?
+            .ok_or(ExportError::ProjectNotFound(project_id))?;
+
+        let output_schema = match &project.project_type_id {
+            Some(id) => self
+                .project_types
+                .find_by_id(id)
+                .await
+                .map_err(|e| ExportError::DatabaseError(e.to_string()))?
+                .map(|pt| pt.output_schema)
+                .unwrap_or(Value::Null),
+            None => Value::Null,
+        };
+
+        let until = Utc::now() - Duration::seconds(EXPORT_SAFETY_LAG_SECONDS);
+        let annotations = self
+            .annotations
+            .list_completed_by_project(&project.project_id, since, until)
+            .await
+            .map_err(|e| ExportError::DatabaseError(e.to_string()))?;
+
+        write_annotations_parquet(&output_schema, &annotations, since, until, writer)
+    }
+
+    /// [`Self::export_parquet`] into `key` in `storage`, replacing any object there
+    pub async fn export_parquet_to_storage(
+        &self,
+        project_id: Uuid,
+        since: Option<DateTime<Utc>>,
+        storage: &dyn StorageService,
+        key: &str,
+    ) -> Result<ExportSummary, ExportError> {
+        let mut buffer = Vec::new();
+        let summary = self.export_parquet(project_id, since, &mut buffer).await?;
+        storage
+            .put(key, buffer)
+            .await
+            .map_err(|e| ExportError::ExportFailed(e.to_string()))?;
+        Ok(summary)
+    }
+}
+
+/// How a JSON value is laid out in a Parquet column
+#[derive(Debug, Clone)]
+enum ColumnKind {
+    String,
+    Integer,
+    Number,
+    Boolean,
+    Struct(Vec<(String, ColumnKind)>),
+    /// Anything without a fixed shape, serialized as a JSON string
+    Json,
+}
+
+impl ColumnKind {
+    /// Map a JSON Schema to a column layout, falling back to JSON strings
+    fn from_schema(schema: &Value) -> Self {
+        match schema.get("type").and_then(Value::as_str) {
+            Some("string") => Self::String,
+            Some("integer") => Self::Integer,
+            Some("number") => Self::Number,
+            Some("boolean") => Self::Boolean,
+            Some("object") => match object_properties(schema) {
+                Some(properties) if !properties.is_empty() => Self::Struct(properties),
+                _ => Self::Json,
+            },
+            _ => Self::Json,
+        }
+    }
+
+    fn data_type(&self) -> DataType {
+        match self {
+            Self::String | Self::Json => DataType::Utf8,
+            Self::Integer => DataType::Int64,
+            Self::Number => DataType::Float64,
+            Self::Boolean => DataType::Boolean,
+            Self::Struct(children) => DataType::Struct(struct_fields(children)),
+        }
+    }
+
+    /// JSON Schema type name of the values this column holds
+    fn expected(&self) -> &'static str {
+        match self {
+            Self::String => "string",
+            Self::Integer => "integer",
+            Self::Number => "number",
+            Self::Boolean => "boolean",
+            Self::Struct(_) => "object",
+            Self::Json => "JSON",
+        }
+    }
+
+    /// Build the column at `column` from one optional JSON value per row.
+    ///
+    /// Missing and null values become nulls; any other value that doesn't
+    /// fit the column is reported rather than dropped.
+    fn build_array(
+        &self,
+        column: &str,
+        values: &[Option<&Value>],
+    ) -> Result<ArrayRef, ColumnMismatch> {
+        let array: ArrayRef = match self {
+            Self::String => Arc::new(StringArray::from(self.convert(
+                column,
+                values,
+                Value::as_str,
+            )?)),
+            Self::Integer => Arc::new(Int64Array::from(self.convert(
+                column,
+                values,
+                Value::as_i64,
+            )?)),
+            Self::Number => Arc::new(Float64Array::from(self.convert(
+                column,
+                values,
+                Value::as_f64,
+            )?)),
+            Self::Boolean => Arc::new(BooleanArray::from(self.convert(
+                column,
+                values,
+                Value::as_bool,
+            )?)),
+            Self::Json => Arc::new(StringArray::from(
+                values
+                    .iter()
+                    .map(|v| v.filter(|v| !v.is_null()).map(Value::to_string))
+                    .collect::<Vec<_>>(),
+            )),
+            Self::Struct(children) => {
+                let objects = self.convert(column, values, Value::as_object)?;
+                let columns = children
+                    .iter()
+                    .map(|(name, kind)| {
+                        let child_values: Vec<Option<&Value>> = objects
+                            .iter()
+                            .map(|o| o.and_then(|o| o.get(name)))
+                            .collect();
+                        kind.build_array(&format!("{column}.{name}"), &child_values)
+                    })
+                    .collect::<Result<Vec<_>, _>>()?;
+                let nulls =
+                    NullBuffer::from(objects.iter().map(Option::is_some).collect::<Vec<_>>());
+                Arc::new(StructArray::new(
+                    struct_fields(children),
+                    columns,
+                    Some(nulls),
+                ))
+            }
+        };
+        Ok(array)
+    }
+
+    /// Convert every present, non-null value, failing on the first that
+    /// `convert` rejects
+    fn convert<'a, T>(
+        &self,
+        column: &str,
+        values: &[Option<&'a Value>],
+        convert: impl Fn(&'a Value) -> Option<T>,
+    ) -> Result<Vec<Option<T>>, ColumnMismatch> {
+        values
+            .iter()
+            .enumerate()
+            .map(|(row, value)| match value {
+                None | Some(Value::Null) => Ok(None),
+                Some(value) => convert(value).map(Some).ok_or_else(|| ColumnMismatch {
+                    row,
+                    column: column.to_string(),
+                    expected: self.expected(),
+                }),
+            })
+            .collect()
+    }
+}
+
+/// A value that doesn't fit its column's type
+#[derive(Debug)]
+struct ColumnMismatch {
+    row: usize,
+    column: String,
+    expected: &'static str,
+}
+
+/// Property name and layout for each property of an object schema
+fn object_properties(schema: &Value) -> Option<Vec<(String, ColumnKind)>> {
+    schema
+        .get("properties")
+        .and_then(Value::as_object)
+        .map(|properties| {
+            properties
+                .iter()
+                .map(|(name, property)| (name.clone(), ColumnKind::from_schema(property)))
+                .collect()
+        })
+}
+
+fn struct_fields(children: &[(String, ColumnKind)]) -> Fields {
+    children
+        .iter()
+        .map(|(name, kind)| Field::new(name, kind.data_type(), true))
+        .collect()
+}
+
+/// Write annotations to `writer` as a single Parquet row group.
+///
+/// Each row carries the annotation, task, and user IDs, submission time and
+/// the schema version the annotation was made against, followed by a `data`
+/// column holding the annotation itself: a struct with one field per
+/// property of `output_schema`, or a JSON string without one. A value that
+/// doesn't match its property's type fails the export. Only rows that
+/// changed after `since` and no later than `until` are written.
+pub fn write_annotations_parquet<W: Write + Send>(
+    output_schema: &Value,
+    completed: &[CompletedAnnotation],
+    since: Option<DateTime<Utc>>,
+    until: DateTime<Utc>,
+    writer: W,
+) -> Result<ExportSummary, ExportError> {
+    let rows: Vec<&CompletedAnnotation> = completed
+        .iter()
+        .filter(|c| since.is_none_or(|since| c.changed_at > since) && c.changed_at <= until)
+        .collect();
+    let checkpoint = Some(since.map_or(until, |since| since.max(until)));
+    let annotations: Vec<_> = rows.iter().map(|c| &c.annotation).collect();
+
+    // Properties live under `data` so they can't collide with the row's own
+    // columns; without typed properties the annotation is kept as JSON
+    let data_kind =
+        match object_properties(output_schema).filter(|properties| !properties.is_empty()) {
+            Some(properties) => ColumnKind::Struct(properties),
+            None => ColumnKind::Json,
+        };
+
+    let fields = vec![
+        Field::new("annotation_id", DataType::Utf8, false),
+        Field::new("task_id", DataType::Utf8, false),
+        Field::new("user_id", DataType::Utf8, false),
+        Field::new(
+            "submitted_at",
+            DataType::Timestamp(TimeUnit::Microsecond, Some("UTC".into())),
+            true,
+        ),
+        Field::new("schema_version", DataType::Int32, true),
+        Field::new("data", data_kind.data_type(), true),
+    ];
+    let schema = Arc::new(Schema::new(fields));
+
+    let mut columns: Vec<ArrayRef> = vec![
+        Arc::new(StringArray::from_iter_values(
+            annotations.iter().map(|a| a.annotation_id.to_string()),
+        )),
+        Arc::new(StringArray::from_iter_values(
+            annotations.iter().map(|a| a.task_id.to_string()),
+        )),
+        Arc::new(StringArray::from_iter_values(
+            annotations.iter().map(|a| a.user_id.to_string()),
+        )),
+        Arc::new(
+            TimestampMicrosecondArray::from(
+                annotations
+                    .iter()
+                    .map(|a| a.submitted_at.map(|t| t.timestamp_micros()))
+                    .collect::<Vec<_>>(),
+            )
+            .with_timezone("UTC"),
+        ),
+        Arc::new(Int32Array::from(
+            annotations
+                .iter()
+                .map(|a| a.schema_version)
+                .collect::<Vec<_>>(),
+        )),
+    ];
+    let data: Vec<Option<&Value>> = annotations.iter().map(|a| Some(&a.data)).collect();
+    columns.push(data_kind.build_array("data", &data).map_err(|mismatch| {
+        ExportError::TypeMismatch {
+            annotation_id: *annotations[mismatch.row].annotation_id.as_uuid(),
+            column: mismatch.column,
+            expected: mismatch.expected,
+        }
+    })?);
+
+    let batch = RecordBatch::try_new(schema.clone(), columns)
+        .map_err(|e| ExportError::ExportFailed(e.to_string()))?;
+    let mut parquet_writer = ArrowWriter::try_new(writer, schema, None)
+        .map_err(|e| ExportError::ExportFailed(e.to_string()))?;
+    parquet_writer
+        .write(&batch)
+        .map_err(|e| ExportError::ExportFailed(e.to_string()))?;
+    parquet_writer
+        .close()
+        .map_err(|e| ExportError::ExportFailed(e.to_string()))?;
+
+    Ok(ExportSummary {
+        rows: annotations.len(),
+        checkpoint,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use arrow::array::Array;
+    use chrono::Duration;
+    use chrono::Utc;
+    use glyph_domain::{Annotation, AnnotationId, AnnotationStatus, AssignmentId, TaskId, UserId};
+    use parquet::arrow::arrow_reader::ParquetRecordBatchReaderBuilder;
+
+    fn completed(data: Value, changed_at: DateTime<Utc>) -> CompletedAnnotation {
+        let annotation = Annotation {
+            annotation_id: AnnotationId::new(),
+            task_id: TaskId::new(),
+            step_id: "annotate".to_string(),
+            user_id: UserId::new(),
+            assignment_id: AssignmentId::new(),
+            project_id: ProjectId::new(),
+            data,
+            status: AnnotationStatus::Approved,
+            version: 1,
+            parent_annotation_id: None,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+            submitted_at: Some(Utc::now()),
+            quality_score: None,
+            quality_evaluated_at: None,
+            time_spent_ms: None,
+            client_metadata: None,
+            schema_version: Some(2),
+        };
+        CompletedAnnotation {
+            annotation,
+            changed_at,
+        }
+    }
+
+    #[test]
+    fn test_parquet_round_trip() {
+        let output_schema = serde_json::json!({
+            "type": "object",
+            "properties": {
+                "label": {"type": "string"},
+                "confidence": {"type": "number"},
+                "bbox": {
+                    "type": "object",
+                    "properties": {"x": {"type": "number"}, "y": {"type": "number"}}
+                },
+                "tags": {"type": "array", "items": {"type": "string"}}
+            }
+        });
+        let now = Utc::now();
+        let annotations = vec![
+            completed(
+                serde_json::json!({
+                    "label": "cat", "confidence": 0.9, "bbox": {"x": 1.0, "y": 2.0}, "tags": ["a"]
+                }),
+                now,
+            ),
+            completed(serde_json::json!({"label": "dog", "confidence": 0.5}), now),
+        ];
+
+        let mut buffer = Vec::new();
+        let summary =
+            write_annotations_parquet(&output_schema, &annotations, None, now, &mut buffer)
+                .unwrap();
+        assert_eq!(summary.rows, 2);
+
+        let reader = ParquetRecordBatchReaderBuilder::try_new(bytes::Bytes::from(buffer))
+            .unwrap()
+            .build()
+            .unwrap();
+        let batches: Vec<RecordBatch> = reader.map(Result::unwrap).collect();
+        assert_eq!(batches.iter().map(RecordBatch::num_rows).sum::<usize>(), 2);
+
+        let batch = &batches[0];
+        let data = batch
+            .column_by_name("data")
+            .unwrap()
+            .as_any()
+            .downcast_ref::<StructArray>()
+            .unwrap();
+        let labels = data
+            .column_by_name("label")
+            .unwrap()
+            .as_any()
+            .downcast_ref::<StringArray>()
+            .unwrap();
+        assert_eq!(labels.value(0), "cat");
+        assert_eq!(labels.value(1), "dog");
+        let versions = batch
+            .column_by_name("schema_version")
+            .unwrap()
+            .as_any()
+            .downcast_ref::<Int32Array>()
+            .unwrap();
+        assert_eq!(versions.value(0), 2);
+
+        // Nested objects become struct columns; arrays fall back to JSON strings
+        let bbox = data.column_by_name("bbox").unwrap();
+        assert!(matches!(bbox.data_type(), DataType::Struct(_)));
+        assert!(bbox.is_null(1));
+        let tags = data
+            .column_by_name("tags")
+            .unwrap()
+            .as_any()
+            .downcast_ref::<StringArray>()
+            .unwrap();
+        assert_eq!(tags.value(0), r#"["a"]"#);
+    }
+
+    #[test]
+    fn test_properties_named_like_row_columns_do_not_collide() {
+        let output_schema = serde_json::json!({
+            "type": "object",
+            "properties": {"task_id": {"type": "integer"}}
+        });
+        let now = Utc::now();
+        let annotation = completed(serde_json::json!({"task_id": 7}), now);
+        let task_id = annotation.annotation.task_id.to_string();
+
+        let mut buffer = Vec::new();
+        write_annotations_parquet(&output_schema, &[annotation], None, now, &mut buffer).unwrap();
+
+        let batch = ParquetRecordBatchReaderBuilder::try_new(bytes::Bytes::from(buffer))
+            .unwrap()
+            .build()
+            .unwrap()
+            .next()
+            .unwrap()
+            .unwrap();
+        let row_task_ids = batch
+            .column_by_name("task_id")
+            .unwrap()
+            .as_any()
+            .downcast_ref::<StringArray>()
+            .unwrap();
+        assert_eq!(row_task_ids.value(0), task_id);
+        let data = batch
+            .column_by_name("data")
+            .unwrap()
+            .as_any()
+            .downcast_ref::<StructArray>()
+            .unwrap();
+        let property = data
+            .column_by_name("task_id")
+            .unwrap()
+            .as_any()
+            .downcast_ref::<Int64Array>()
+            .unwrap();
+        assert_eq!(property.value(0), 7);
+    }
+
+    #[test]
+    fn test_type_mismatch_fails_the_export() {
+        let output_schema = serde_json::json!({
+            "type": "object",
+            "properties": {
+                "bbox": {
+                    "type": "object",
+                    "properties": {"x": {"type": "number"}}
+                }
+            }
+        });
+        let now = Utc::now();
+        let annotations = vec![
+            completed(serde_json::json!({"bbox": {"x": 1.0}}), now),
+            completed(serde_json::json!({"bbox": {"x": "left"}}), now),
+        ];
+        let bad_id = *annotations[1].annotation.annotation_id.as_uuid();
+
+        let err = write_annotations_parquet(&output_schema, &annotations, None, now, Vec::new())
+            .unwrap_err();
+        match err {
+            ExportError::TypeMismatch {
+                annotation_id,
+                column,
+                expected,
+            } => {
+                assert_eq!(annotation_id, bad_id);
+                assert_eq!(column, "data.bbox.x");
+                assert_eq!(expected, "number");
+            }
+            other => panic!("expected a type mismatch, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_delta_export_from_checkpoint_is_empty() {
+        let now = Utc::now();
+        let annotations = vec![
+            completed(
+                serde_json::json!({"label": "cat"}),
+                now - Duration::minutes(5),
+            ),
+            completed(serde_json::json!({"label": "dog"}), now),
+        ];
+
+        let full =
+            write_annotations_parquet(&Value::Null, &annotations, None, now, Vec::new()).unwrap();
+        assert_eq!(full.rows, 2);
+        assert_eq!(full.checkpoint, Some(now));
+
+        let delta =
+            write_annotations_parquet(&Value::Null, &annotations, full.checkpoint, now, Vec::new())
+                .unwrap();
+        assert_eq!(delta.rows, 0);
+        assert_eq!(delta.checkpoint, full.checkpoint);
+
+        let partial = write_annotations_parquet(
+            &Value::Null,
+            &annotations,
+            Some(now - Duration::minutes(1)),
+            now,
+            Vec::new(),
+        )
+        .unwrap();
+        assert_eq!(partial.rows, 1);
+    }
+
+    #[test]
+    fn test_changes_inside_the_lag_are_left_for_the_next_export() {
+        let now = Utc::now();
+        let first_until = now - Duration::seconds(EXPORT_SAFETY_LAG_SECONDS);
+        let settled = completed(
+            serde_json::json!({"label": "cat"}),
+            now - Duration::hours(1),
+        );
+        // Stamped before the first export ran but not yet committed then
+        let late = completed(
+            serde_json::json!({"label": "dog"}),
+            now - Duration::seconds(1),
+        );
+
+        let first = write_annotations_parquet(
+            &Value::Null,
+            std::slice::from_ref(&settled),
+            None,
+            first_until,
+            Vec::new(),
+        )
+        .unwrap();
+        assert_eq!(first.rows, 1);
+        assert_eq!(first.checkpoint, Some(first_until));
+
+        let later = now + Duration::seconds(EXPORT_SAFETY_LAG_SECONDS);
+        let next = write_annotations_parquet(
+            &Value::Null,
+            &[settled, late],
+            first.checkpoint,
+            later,
+            Vec::new(),
+        )
+        .unwrap();
+        assert_eq!(next.rows, 1);
+        assert_eq!(next.checkpoint, Some(later));
+    }
+}
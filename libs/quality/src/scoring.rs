@@ -1,5 +1,8 @@
 //! Quality evaluation service
 
+use std::collections::HashMap;
+use std::hash::Hash;
+
 use async_trait::async_trait;
 use glyph_domain::{Annotation, QualityEntityType, QualityScore};
 use thiserror::Error;
@@ -43,3 +46,133 @@ pub trait QualityService: Send + Sync {
     /// Update user quality profile based on recent annotations
     async fn update_user_profile(&self, user_id: Uuid) -> Result<(), QualityError>;
 }
+
+/// Fraction of a user's labels that matched the consensus label.
+///
+/// `user_annotations` pairs each task key the user annotated with their
+/// label; `consensus_labels` maps task keys to the agreed label. Tasks without
+/// a consensus are excluded. Returns 0.0 when no task overlaps.
+pub fn compute_consistency<K, L>(
+    user_annotations: &[(K, L)],
+    consensus_labels: &HashMap<K, L>,
+) -> f64
+where
+    K: Eq + Hash,
+    L: PartialEq,
+{
+    let mut compared = 0u32;
+    let mut matched = 0u32;
+    for (key, label) in user_annotations {
+        if let Some(consensus) = consensus_labels.get(key) {
+            compared += 1;
+            if consensus == label {
+                matched += 1;
+            }
+        }
+    }
+
+    if compared == 0 {
+        0.0
+    } else {
+        f64::from(matched) / f64::from(compared)
+    }
+}
+
+/// Score a submission against a gold task's expected output, from 0.0 to 1.0.
+///
+/// Objects score the mean over the expected keys (extra submitted keys are
+/// ignored), arrays score position by position over the longer length, and
+/// scalars score 1.0 only on an exact match.
+pub fn score_gold(submission: &serde_json::Value, expected: &serde_json::Value) -> f64 {
+    use serde_json::Value;
+
+    if submission == expected {
+        return 1.0;
+    }
+    match (submission, expected) {
+        (Value::Object(_), Value::Object(expected)) if expected.is_empty() => 0.0,
+        (Value::Object(submitted), Value::Object(expected)) => {
+            let total: f64 = expected
+                .iter()
+                .map(|(key, want)| submitted.get(key).map_or(0.0, |got| score_gold(got, want)))
+                .sum();
+            #[allow(clippy::cast_precision_loss)]
+            let len = expected.len() as f64;
+            total / len
+        }
+        (Value::Array(submitted), Value::Array(expected)) => {
+            let total: f64 = submitted
+                .iter()
+                .zip(expected)
+                .map(|(got, want)| score_gold(got, want))
+                .sum();
+            #[allow(clippy::cast_precision_loss)]
+            let len = submitted.len().max(expected.len()) as f64;
+            total / len
+        }
+        _ => 0.0,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn consensus(pairs: &[(u32, &'static str)]) -> HashMap<u32, &'static str> {
+        pairs.iter().copied().collect()
+    }
+
+    #[test]
+    fn test_consistency_full_match() {
+        let user = [(1, "cat"), (2, "dog"), (3, "cat")];
+        let agreed = consensus(&[(1, "cat"), (2, "dog"), (3, "cat")]);
+        assert!((compute_consistency(&user, &agreed) - 1.0).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn test_consistency_partial_match() {
+        let user = [(1, "cat"), (2, "cat"), (3, "cat"), (4, "dog")];
+        let agreed = consensus(&[(1, "cat"), (2, "dog"), (3, "cat"), (4, "cat")]);
+        assert!((compute_consistency(&user, &agreed) - 0.5).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn test_consistency_excludes_tasks_without_consensus() {
+        // Task 2 has no consensus, so only task 1 counts
+        let user = [(1, "cat"), (2, "dog")];
+        let agreed = consensus(&[(1, "cat")]);
+        assert!((compute_consistency(&user, &agreed) - 1.0).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn test_consistency_no_overlap() {
+        let user = [(1, "cat")];
+        let agreed = consensus(&[(2, "cat")]);
+        assert!(compute_consistency(&user, &agreed).abs() < f64::EPSILON);
+        assert!(compute_consistency::<u32, &str>(&[], &agreed).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn test_gold_exact_match() {
+        let expected = serde_json::json!({ "label": "cat", "boxes": [[0, 0, 10, 10]] });
+        assert!((score_gold(&expected, &expected) - 1.0).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn test_gold_partial_match() {
+        let expected = serde_json::json!({ "label": "cat", "occluded": false });
+        let submission = serde_json::json!({ "label": "cat", "occluded": true, "note": "x" });
+        assert!((score_gold(&submission, &expected) - 0.5).abs() < f64::EPSILON);
+
+        let expected = serde_json::json!({ "tags": ["a", "b", "c", "d"] });
+        let submission = serde_json::json!({ "tags": ["a", "b", "x"] });
+        assert!((score_gold(&submission, &expected) - 0.5).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn test_gold_mismatch() {
+        let expected = serde_json::json!({ "label": "cat" });
+        assert!(score_gold(&serde_json::json!("cat"), &expected).abs() < f64::EPSILON);
+        assert!(score_gold(&serde_json::json!({}), &expected).abs() < f64::EPSILON);
+    }
+}
@@ -3,18 +3,23 @@
 //! Provides skill-based, load-balanced task assignment with duplicate prevention
 //! and cross-step exclusion support.
 
+use std::collections::{HashMap, VecDeque};
 use std::sync::Arc;
 
 use async_trait::async_trait;
+use chrono::{DateTime, Utc};
 use glyph_domain::{
-    AssignmentMode, AssignmentStatus, LoadBalancingStrategy, ProjectId, Task, TaskAssignment,
-    TaskId, User, UserId, UserStatus,
+    AssignmentMode, AssignmentStatus, LoadBalancingStrategy, ProficiencyLevel, ProjectId,
+    SkillRequirement, Task, TaskAssignment, TaskId, User, UserId, UserStatus,
 };
 use thiserror::Error;
 use uuid::Uuid;
 
 use glyph_db::{AssignmentRepository, NewAssignment, UserRepository};
 
+use crate::clock::{system_clock, Clock};
+use crate::rng::{pick_weighted, thread_rng, Rng};
+
 #[derive(Debug, Error)]
 pub enum AssignmentError {
     #[error("No eligible users found for assignment")]
@@ -42,16 +47,57 @@ pub enum AssignmentError {
     DatabaseError(String),
 }
 
+/// What a job runner should do with a message whose assignment failed
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AssignmentDisposition {
+    /// Transient failure; retry with backoff
+    Retry,
+    /// Can't be assigned yet; try again after a delay
+    RequeueLater,
+    /// Nothing left to do; drop the request
+    Drop,
+    /// Shouldn't happen; record it for an operator
+    DeadLetter,
+}
+
+impl AssignmentError {
+    /// Whether retrying the same assignment could succeed
+    #[must_use]
+    pub fn is_retryable(&self) -> bool {
+        self.disposition() == AssignmentDisposition::Retry
+    }
+
+    /// How a job runner should handle this failure
+    #[must_use]
+    pub fn disposition(&self) -> AssignmentDisposition {
+        match self {
+            Self::DatabaseError(_) => AssignmentDisposition::Retry,
+            // Users may free up capacity or come online
+            Self::NoEligibleUsers | Self::AssignmentLimitReached(_) => {
+                AssignmentDisposition::RequeueLater
+            }
+            Self::DuplicateAssignment
+            | Self::UserNotEligible(_)
+            | Self::TaskNotAvailable(_)
+            | Self::NotFound(_) => AssignmentDisposition::Drop,
+            Self::InvalidStatusTransition => AssignmentDisposition::DeadLetter,
+        }
+    }
+}
+
 /// Service for assigning tasks to users
 #[async_trait]
 pub trait AssignmentService: Send + Sync {
-    /// Find the best user to assign a task to
+    /// Find the best user to assign a task to.
+    ///
+    /// Users missing a required skill in `requirements` are never chosen.
     async fn find_best_assignee(
         &self,
         task: &Task,
         step_id: &str,
         mode: AssignmentMode,
         strategy: LoadBalancingStrategy,
+        requirements: &[SkillRequirement],
     ) -> Result<User, AssignmentError>;
 
     /// Assign a task to a specific user
@@ -87,6 +133,8 @@ pub struct AssignmentConfig {
     pub cooldown_minutes: u32,
     /// Default load balancing strategy
     pub default_strategy: LoadBalancingStrategy,
+    /// How far back assignments count toward fair-share rotation
+    pub fair_share_window_minutes: u32,
 }
 
 impl Default for AssignmentConfig {
@@ -99,8 +147,129 @@ impl Default for AssignmentConfig {
             ],
             cooldown_minutes: 5,
             default_strategy: LoadBalancingStrategy::LeastLoaded,
+            fair_share_window_minutes: 60,
+        }
+    }
+}
+
+impl AssignmentConfig {
+    /// Cooldown applied to tasks rejected under this configuration
+    #[must_use]
+    pub fn cooldown(&self) -> TaskCooldown {
+        TaskCooldown::new(chrono::Duration::minutes(i64::from(self.cooldown_minutes)))
+    }
+
+    /// Fair-share ledger counting assignments over this configuration's window
+    #[must_use]
+    pub fn fair_share(&self) -> FairShareLedger {
+        FairShareLedger::new(chrono::Duration::minutes(i64::from(
+            self.fair_share_window_minutes,
+        )))
+    }
+}
+
+// =============================================================================
+// Cooldowns
+// =============================================================================
+
+/// Period after a rejection during which a task isn't reassigned
+#[derive(Debug, Clone)]
+pub struct TaskCooldown {
+    duration: chrono::Duration,
+    clock: Arc<dyn Clock>,
+}
+
+impl TaskCooldown {
+    /// Create a cooldown of `duration` on the system clock
+    #[must_use]
+    pub fn new(duration: chrono::Duration) -> Self {
+        Self {
+            duration,
+            clock: system_clock(),
+        }
+    }
+
+    /// Use a different time source
+    #[must_use]
+    pub fn with_clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    /// When a task rejected now becomes assignable again
+    #[must_use]
+    pub fn until(&self) -> DateTime<Utc> {
+        self.clock.now() + self.duration
+    }
+
+    /// Whether a task's `cooldown_until` still blocks reassignment.
+    ///
+    /// Matches the queue query, which treats a task as available once
+    /// `cooldown_until < NOW()`.
+    #[must_use]
+    pub fn is_active(&self, cooldown_until: Option<DateTime<Utc>>) -> bool {
+        cooldown_until.is_some_and(|until| self.clock.now() <= until)
+    }
+}
+
+// =============================================================================
+// Fair Share
+// =============================================================================
+
+/// Assignments each user actually received within a sliding window.
+///
+/// Unlike a rotating index, the ledger only moves when work is handed out,
+/// so a user who rejects tasks still counts what they were given and a user
+/// who was away simply has fewer entries to catch up on.
+#[derive(Debug, Clone)]
+pub struct FairShareLedger {
+    window: chrono::Duration,
+    assigned: HashMap<UserId, VecDeque<DateTime<Utc>>>,
+}
+
+impl FairShareLedger {
+    #[must_use]
+    pub fn new(window: chrono::Duration) -> Self {
+        Self {
+            window,
+            assigned: HashMap::new(),
         }
     }
+
+    /// Note an assignment handed to `user_id` at `at`
+    pub fn record(&mut self, user_id: UserId, at: DateTime<Utc>) {
+        self.assigned.entry(user_id).or_default().push_back(at);
+    }
+
+    /// Forget assignments that have left the window
+    fn prune(&mut self, now: DateTime<Utc>) {
+        let cutoff = now - self.window;
+        self.assigned.retain(|_, times| {
+            while times.front().is_some_and(|t| *t < cutoff) {
+                times.pop_front();
+            }
+            !times.is_empty()
+        });
+    }
+
+    /// Assignments `user_id` received in the window
+    #[must_use]
+    pub fn count(&self, user_id: &UserId) -> usize {
+        self.assigned.get(user_id).map_or(0, VecDeque::len)
+    }
+
+    /// The user with the fewest assignments in the window, breaking ties by
+    /// who was assigned least recently, then by order in `users`
+    pub fn pick<'a>(&mut self, users: &'a [User], now: DateTime<Utc>) -> Option<&'a User> {
+        self.prune(now);
+        users.iter().min_by_key(|user| {
+            let last = self
+                .assigned
+                .get(&user.user_id)
+                .and_then(|times| times.back().copied());
+            (self.count(&user.user_id), last)
+        })
+    }
 }
 
 // =============================================================================
@@ -118,6 +287,12 @@ where
     config: AssignmentConfig,
     /// Track last assigned user index per step for round-robin
     round_robin_index: std::sync::atomic::AtomicUsize,
+    /// Randomness for quality-weighted selection
+    rng: Arc<dyn Rng>,
+    /// Recent assignments per user for fair-share selection
+    fair_share: std::sync::Mutex<FairShareLedger>,
+    /// Time source for the fair-share window
+    clock: Arc<dyn Clock>,
 }
 
 impl<A, U> AssignmentEngine<A, U>
@@ -130,11 +305,35 @@ where
         Self {
             assignment_repo,
             user_repo,
+            fair_share: std::sync::Mutex::new(config.fair_share()),
             config,
             round_robin_index: std::sync::atomic::AtomicUsize::new(0),
+            rng: thread_rng(),
+            clock: system_clock(),
         }
     }
 
+    /// Use a different time source
+    #[must_use]
+    pub fn with_clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    /// The fair-share ledger, even if a panic poisoned its lock
+    fn fair_share(&self) -> std::sync::MutexGuard<'_, FairShareLedger> {
+        self.fair_share
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+    }
+
+    /// Use a different source of randomness, e.g. a seeded one in tests
+    #[must_use]
+    pub fn with_rng(mut self, rng: Arc<dyn Rng>) -> Self {
+        self.rng = rng;
+        self
+    }
+
     /// Get excluded steps for a given step (steps where the same user cannot work)
     fn get_excluded_steps(&self, step_id: &str) -> Vec<String> {
         let mut excluded = Vec::new();
@@ -229,19 +428,99 @@ where
         Ok(selected.cloned())
     }
 
+    /// Select the user with the fewest recent assignments
+    fn select_fair_share<'a>(&self, eligible_users: &'a [User]) -> Option<&'a User> {
+        self.fair_share().pick(eligible_users, self.clock.now())
+    }
+
     /// Select user based on quality-weighted strategy
-    /// Users with higher quality scores get higher priority
-    async fn select_quality_weighted(
+    fn select_quality_weighted<'a>(&self, eligible_users: &'a [User]) -> Option<&'a User> {
+        select_by_quality(self.rng.as_ref(), eligible_users)
+    }
+
+    /// Select the least loaded of the users best matching `requirements`
+    async fn select_skill_weighted(
         &self,
         eligible_users: &[User],
+        requirements: &[SkillRequirement],
     ) -> Result<Option<User>, AssignmentError> {
-        // For now, fall back to least-loaded since quality scores
-        // require integration with the quality_scores table
-        // TODO: Integrate with quality scores when available
-        self.select_least_loaded(eligible_users).await
+        let best = best_skill_matches(eligible_users, requirements);
+        self.select_least_loaded(&best).await
     }
 }
 
+/// Tolerance when comparing skill scores, so float noise doesn't break ties
+const SKILL_SCORE_EPSILON: f64 = 1e-9;
+
+/// How well a proficiency level meets a requirement's minimum, in `0.0..=1.0`.
+///
+/// Meeting the minimum is a full match; lower levels earn partial credit.
+fn proficiency_match(level: ProficiencyLevel, min: ProficiencyLevel) -> f64 {
+    let rank = |level: ProficiencyLevel| f64::from(level as u8 + 1);
+    (rank(level) / rank(min)).min(1.0)
+}
+
+/// The user's level in a skill, if they have it
+fn proficiency_in(user: &User, skill_id: &str) -> Option<ProficiencyLevel> {
+    user.skills
+        .iter()
+        .find(|s| s.skill_id == skill_id)
+        .map(|s| s.proficiency)
+}
+
+/// Whether the user meets every required skill at its minimum level
+fn meets_required_skills(user: &User, requirements: &[SkillRequirement]) -> bool {
+    requirements
+        .iter()
+        .filter(|r| r.is_required)
+        .all(|r| proficiency_in(user, &r.skill_id).is_some_and(|level| level >= r.min_proficiency))
+}
+
+/// Sum of each requirement's weight times how well the user matches it
+fn skill_score(user: &User, requirements: &[SkillRequirement]) -> f64 {
+    requirements
+        .iter()
+        .map(|r| {
+            proficiency_in(user, &r.skill_id).map_or(0.0, |level| {
+                f64::from(r.weight) * proficiency_match(level, r.min_proficiency)
+            })
+        })
+        .sum()
+}
+
+/// The users sharing the highest skill score
+fn best_skill_matches(users: &[User], requirements: &[SkillRequirement]) -> Vec<User> {
+    let scores: Vec<f64> = users.iter().map(|u| skill_score(u, requirements)).collect();
+    let best = scores.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+    users
+        .iter()
+        .zip(scores)
+        .filter(|(_, score)| best - score <= SKILL_SCORE_EPSILON)
+        .map(|(user, _)| user.clone())
+        .collect()
+}
+
+/// Weight for users without an overall quality score
+const UNSCORED_QUALITY_WEIGHT: f64 = 0.5;
+
+/// Lowest weight a user can have, so low scorers still get occasional work
+const MIN_QUALITY_WEIGHT: f64 = 0.05;
+
+/// A user's weight in quality-weighted selection: their overall score
+fn quality_weight(user: &User) -> f64 {
+    user.quality_profile
+        .overall_score
+        .filter(|score| score.is_finite())
+        .map_or(UNSCORED_QUALITY_WEIGHT, |score| score.clamp(0.0, 1.0))
+        .max(MIN_QUALITY_WEIGHT)
+}
+
+/// Pick a user at random, with odds proportional to their quality score
+fn select_by_quality<'a>(rng: &dyn Rng, users: &'a [User]) -> Option<&'a User> {
+    let weights: Vec<f64> = users.iter().map(quality_weight).collect();
+    pick_weighted(rng, &weights).map(|index| &users[index])
+}
+
 #[async_trait]
 impl<A, U> AssignmentService for AssignmentEngine<A, U>
 where
@@ -254,6 +533,7 @@ where
         step_id: &str,
         mode: AssignmentMode,
         strategy: LoadBalancingStrategy,
+        requirements: &[SkillRequirement],
     ) -> Result<User, AssignmentError> {
         // For manual mode, this shouldn't be called - return error
         if mode == AssignmentMode::Manual {
@@ -278,7 +558,9 @@ where
         // Filter to eligible users
         let mut eligible_users = Vec::new();
         for user in users.items {
-            if self.is_user_eligible(&user, task, step_id).await? {
+            if meets_required_skills(&user, requirements)
+                && self.is_user_eligible(&user, task, step_id).await?
+            {
                 eligible_users.push(user);
             }
         }
@@ -292,8 +574,13 @@ where
             LoadBalancingStrategy::RoundRobin => self.select_round_robin(&eligible_users).cloned(),
             LoadBalancingStrategy::LeastLoaded => self.select_least_loaded(&eligible_users).await?,
             LoadBalancingStrategy::QualityWeighted => {
-                self.select_quality_weighted(&eligible_users).await?
+                self.select_quality_weighted(&eligible_users).cloned()
             }
+            LoadBalancingStrategy::SkillWeighted => {
+                self.select_skill_weighted(&eligible_users, requirements)
+                    .await?
+            }
+            LoadBalancingStrategy::FairShare => self.select_fair_share(&eligible_users).cloned(),
         };
 
         selected.ok_or(AssignmentError::NoEligibleUsers)
@@ -358,6 +645,8 @@ where
                     }
                 })?;
 
+        self.fair_share()
+            .record(assignment.user_id, self.clock.now());
         Ok(assignment)
     }
 
@@ -481,6 +770,8 @@ where
                     }
                 })?;
 
+        self.fair_share()
+            .record(assignment.user_id, self.clock.now());
         Ok(assignment)
     }
 
@@ -579,6 +870,51 @@ where
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_error_dispositions() {
+        let id = Uuid::nil();
+        let cases = [
+            (
+                AssignmentError::DatabaseError("timeout".into()),
+                AssignmentDisposition::Retry,
+            ),
+            (
+                AssignmentError::NoEligibleUsers,
+                AssignmentDisposition::RequeueLater,
+            ),
+            (
+                AssignmentError::AssignmentLimitReached(id),
+                AssignmentDisposition::RequeueLater,
+            ),
+            (
+                AssignmentError::DuplicateAssignment,
+                AssignmentDisposition::Drop,
+            ),
+            (
+                AssignmentError::UserNotEligible(id),
+                AssignmentDisposition::Drop,
+            ),
+            (
+                AssignmentError::TaskNotAvailable(id),
+                AssignmentDisposition::Drop,
+            ),
+            (AssignmentError::NotFound(id), AssignmentDisposition::Drop),
+            (
+                AssignmentError::InvalidStatusTransition,
+                AssignmentDisposition::DeadLetter,
+            ),
+        ];
+
+        for (error, expected) in cases {
+            assert_eq!(error.disposition(), expected, "{error}");
+            assert_eq!(
+                error.is_retryable(),
+                expected == AssignmentDisposition::Retry,
+                "{error}"
+            );
+        }
+    }
+
     #[test]
     fn test_default_config() {
         let config = AssignmentConfig::default();
@@ -587,6 +923,219 @@ mod tests {
         assert!(!config.cross_step_exclusion_pairs.is_empty());
     }
 
+    #[test]
+    fn test_cooldown_expires_when_clock_advances() {
+        let clock = crate::clock::MockClock::default();
+        let cooldown = AssignmentConfig::default()
+            .cooldown()
+            .with_clock(Arc::new(clock.clone()));
+
+        let until = cooldown.until();
+        assert!(cooldown.is_active(Some(until)));
+
+        clock.advance(chrono::Duration::minutes(4));
+        assert!(cooldown.is_active(Some(until)));
+
+        clock.advance(chrono::Duration::minutes(1));
+        assert!(cooldown.is_active(Some(until)), "expiry is exclusive");
+
+        clock.advance(chrono::Duration::seconds(1));
+        assert!(!cooldown.is_active(Some(until)));
+        assert!(!cooldown.is_active(None));
+    }
+
+    fn user_with_score(score: Option<f64>) -> User {
+        User {
+            user_id: UserId::new(),
+            auth0_id: None,
+            email: "annotator@example.com".to_string(),
+            display_name: "Annotator".to_string(),
+            status: UserStatus::Active,
+            timezone: None,
+            department: None,
+            bio: None,
+            avatar_url: None,
+            contact_info: glyph_domain::ContactInfo::default(),
+            global_role: glyph_domain::GlobalRole::User,
+            skills: Vec::new(),
+            roles: Vec::new(),
+            quality_profile: glyph_domain::QualityProfile {
+                overall_score: score,
+                ..Default::default()
+            },
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_quality_weights() {
+        assert!((quality_weight(&user_with_score(Some(0.9))) - 0.9).abs() < f64::EPSILON);
+        assert!(
+            (quality_weight(&user_with_score(None)) - UNSCORED_QUALITY_WEIGHT).abs() < f64::EPSILON
+        );
+        assert!(
+            (quality_weight(&user_with_score(Some(0.0))) - MIN_QUALITY_WEIGHT).abs() < f64::EPSILON
+        );
+        assert!((quality_weight(&user_with_score(Some(7.0))) - 1.0).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn test_fixed_seed_yields_fixed_selection_sequence() {
+        let users = vec![
+            user_with_score(Some(0.95)),
+            user_with_score(Some(0.6)),
+            user_with_score(None),
+            user_with_score(Some(0.2)),
+        ];
+        let sequence = |seed| {
+            let rng = crate::rng::SeededRng::new(seed);
+            (0..20)
+                .map(|_| select_by_quality(&rng, &users).unwrap().user_id.clone())
+                .collect::<Vec<_>>()
+        };
+
+        let first = sequence(42);
+        assert_eq!(first, sequence(42));
+        // Every pick comes from the pool, and a run this long isn't all one user
+        assert!(first
+            .iter()
+            .all(|id| users.iter().any(|u| &u.user_id == id)));
+        assert!(first.iter().any(|id| id != &first[0]));
+    }
+
+    fn requirement(skill_id: &str, weight: f32, is_required: bool) -> SkillRequirement {
+        SkillRequirement {
+            skill_id: skill_id.to_string(),
+            min_proficiency: ProficiencyLevel::Advanced,
+            is_required,
+            weight,
+        }
+    }
+
+    fn user_with_skills(skills: &[(&str, ProficiencyLevel)]) -> User {
+        let mut user = user_with_score(None);
+        user.skills = skills
+            .iter()
+            .map(|(skill_id, proficiency)| glyph_domain::UserSkill {
+                skill_id: (*skill_id).to_string(),
+                proficiency: *proficiency,
+                verified: true,
+                verified_at: None,
+            })
+            .collect();
+        user
+    }
+
+    #[test]
+    fn test_higher_weighted_skill_match_wins() {
+        let requirements = [
+            requirement("medical-ner", 3.0, false),
+            requirement("ocr", 1.0, false),
+        ];
+        let ner = user_with_skills(&[("medical-ner", ProficiencyLevel::Expert)]);
+        let ocr = user_with_skills(&[("ocr", ProficiencyLevel::Expert)]);
+        let novice_ner = user_with_skills(&[("medical-ner", ProficiencyLevel::Novice)]);
+        let users = vec![ocr, ner.clone(), novice_ner];
+
+        let best = best_skill_matches(&users, &requirements);
+        assert_eq!(best.len(), 1);
+        assert_eq!(best[0].user_id, ner.user_id);
+        // Novice against an advanced minimum earns a third of the weight
+        assert!((skill_score(&users[2], &requirements) - 1.0).abs() < SKILL_SCORE_EPSILON);
+    }
+
+    #[test]
+    fn test_equal_skill_scores_tie() {
+        let requirements = [requirement("ocr", 2.0, false)];
+        let users = vec![
+            user_with_skills(&[("ocr", ProficiencyLevel::Advanced)]),
+            user_with_skills(&[("ocr", ProficiencyLevel::Expert)]),
+        ];
+        // Exceeding the minimum is no better than meeting it; load breaks the tie
+        assert_eq!(best_skill_matches(&users, &requirements).len(), 2);
+    }
+
+    #[test]
+    fn test_unmet_required_skill_excludes() {
+        let requirements = [requirement("medical-ner", 1.0, true)];
+        assert!(meets_required_skills(
+            &user_with_skills(&[("medical-ner", ProficiencyLevel::Expert)]),
+            &requirements
+        ));
+        assert!(!meets_required_skills(
+            &user_with_skills(&[("medical-ner", ProficiencyLevel::Intermediate)]),
+            &requirements
+        ));
+        assert!(!meets_required_skills(
+            &user_with_skills(&[]),
+            &requirements
+        ));
+        // Nice-to-have skills never exclude
+        assert!(meets_required_skills(
+            &user_with_skills(&[]),
+            &[requirement("ocr", 1.0, false)]
+        ));
+    }
+
+    #[test]
+    fn test_fair_share_does_not_over_serve_a_rejecting_user() {
+        let clock = crate::clock::MockClock::default();
+        let mut ledger = FairShareLedger::new(chrono::Duration::minutes(60));
+        let users = vec![
+            user_with_score(None),
+            user_with_score(None),
+            user_with_score(None),
+        ];
+        let rejecter = users[0].user_id;
+
+        for _ in 0..12 {
+            let picked = ledger.pick(&users, clock.now()).unwrap().user_id;
+            ledger.record(picked, clock.now());
+            if picked == rejecter {
+                // The rejected task goes to someone who hasn't seen it
+                let others: Vec<User> = users
+                    .iter()
+                    .filter(|u| u.user_id != rejecter)
+                    .cloned()
+                    .collect();
+                let next = ledger.pick(&others, clock.now()).unwrap().user_id;
+                ledger.record(next, clock.now());
+            }
+            clock.advance(chrono::Duration::minutes(1));
+        }
+
+        let counts: Vec<usize> = users.iter().map(|u| ledger.count(&u.user_id)).collect();
+        let (min, max) = (*counts.iter().min().unwrap(), *counts.iter().max().unwrap());
+        assert!(max - min <= 1, "{counts:?}");
+        assert_eq!(ledger.count(&rejecter), min, "{counts:?}");
+    }
+
+    #[test]
+    fn test_fair_share_forgets_assignments_outside_window() {
+        let clock = crate::clock::MockClock::default();
+        let mut ledger = FairShareLedger::new(chrono::Duration::minutes(30));
+        let users = vec![user_with_score(None), user_with_score(None)];
+
+        // The first user was busy earlier; the second worked more recently
+        ledger.record(users[0].user_id, clock.now());
+        ledger.record(users[0].user_id, clock.now());
+        clock.advance(chrono::Duration::minutes(20));
+        ledger.record(users[1].user_id, clock.now());
+        assert_eq!(
+            ledger.pick(&users, clock.now()).unwrap().user_id,
+            users[1].user_id
+        );
+
+        // Once the old assignments age out, the least recent user is next
+        clock.advance(chrono::Duration::minutes(15));
+        assert_eq!(
+            ledger.pick(&users, clock.now()).unwrap().user_id,
+            users[0].user_id
+        );
+        assert_eq!(ledger.count(&users[0].user_id), 0);
+    }
+
     #[test]
     fn test_get_excluded_steps() {
         // Would need mock repos for full test
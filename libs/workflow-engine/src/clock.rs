@@ -0,0 +1,118 @@
+//! Injectable time source
+//!
+//! Time-dependent components (goal debouncing, cooldowns, presence, SLA
+//! checks) read the time through a [`Clock`] so tests can drive it with a
+//! [`MockClock`] instead of sleeping. Production code uses [`SystemClock`].
+
+use std::fmt::Debug;
+use std::sync::{Arc, Mutex};
+use std::time::Instant;
+
+use chrono::{DateTime, Duration, Utc};
+
+/// Source of the current time
+pub trait Clock: Debug + Send + Sync {
+    /// Current wall-clock time
+    fn now(&self) -> DateTime<Utc>;
+
+    /// Current monotonic time, for measuring intervals
+    fn instant(&self) -> Instant;
+}
+
+/// The real system clock
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> DateTime<Utc> {
+        Utc::now()
+    }
+
+    fn instant(&self) -> Instant {
+        Instant::now()
+    }
+}
+
+/// Shared handle to the system clock, the default for components taking a clock
+#[must_use]
+pub fn system_clock() -> Arc<dyn Clock> {
+    Arc::new(SystemClock)
+}
+
+/// A clock that only moves when told to.
+///
+/// Clones share the same time, so a test can keep one handle and pass another
+/// to the component under test.
+#[derive(Debug, Clone)]
+pub struct MockClock {
+    state: Arc<Mutex<MockState>>,
+}
+
+#[derive(Debug)]
+struct MockState {
+    now: DateTime<Utc>,
+    instant: Instant,
+}
+
+impl Default for MockClock {
+    fn default() -> Self {
+        Self::new(Utc::now())
+    }
+}
+
+impl MockClock {
+    /// Create a mock clock frozen at `now`
+    #[must_use]
+    pub fn new(now: DateTime<Utc>) -> Self {
+        Self {
+            state: Arc::new(Mutex::new(MockState {
+                now,
+                instant: Instant::now(),
+            })),
+        }
+    }
+
+    /// Move time forward by `by`; negative durations are ignored
+    pub fn advance(&self, by: Duration) {
+        let Ok(step) = by.to_std() else {
+            return;
+        };
+        let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+        state.now += by;
+        state.instant += step;
+    }
+}
+
+impl Clock for MockClock {
+    fn now(&self) -> DateTime<Utc> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner()).now
+    }
+
+    fn instant(&self) -> Instant {
+        self.state.lock().unwrap_or_else(|e| e.into_inner()).instant
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mock_clock_only_moves_when_advanced() {
+        let start = Utc::now();
+        let clock = MockClock::new(start);
+        let instant = clock.instant();
+        assert_eq!(clock.now(), start);
+
+        let handle = clock.clone();
+        handle.advance(Duration::minutes(3));
+        assert_eq!(clock.now(), start + Duration::minutes(3));
+        assert_eq!(
+            clock.instant().duration_since(instant),
+            std::time::Duration::from_secs(180)
+        );
+
+        clock.advance(Duration::seconds(-10));
+        assert_eq!(clock.now(), start + Duration::minutes(3));
+    }
+}
@@ -267,6 +267,9 @@ impl StepLibrary {
                 "threshold" => {
                     settings.threshold = value.as_f64();
                 }
+                "review_sample_rate" => {
+                    settings.review_sample_rate = value.as_f64();
+                }
                 "handler" => {
                     settings.handler = value.as_str().map(String::from);
                 }
@@ -28,6 +28,10 @@ pub struct WorkflowConfig {
     #[serde(default)]
     pub settings: WorkflowSettingsConfig,
 
+    /// Step to start at; the first step when unset
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub entry_step: Option<String>,
+
     /// Step definitions
     pub steps: Vec<StepConfig>,
 
@@ -39,6 +43,17 @@ pub struct WorkflowConfig {
     pub step_library: Vec<StepLibraryRef>,
 }
 
+impl WorkflowConfig {
+    /// The step a new run starts at: `entry_step` if set, otherwise the
+    /// first step
+    #[must_use]
+    pub fn entry_step_id(&self) -> Option<&str> {
+        self.entry_step
+            .as_deref()
+            .or_else(|| self.steps.first().map(|s| s.id.as_str()))
+    }
+}
+
 // =============================================================================
 // Step Configuration
 // =============================================================================
@@ -116,6 +131,11 @@ pub struct StepSettingsConfig {
     /// Required skills for this step
     #[serde(default)]
     pub required_skills: Option<Vec<String>>,
+
+    /// Fraction of annotations routed to this review step (0.0 to 1.0);
+    /// the rest are auto-approved. Overrides the workflow default.
+    #[serde(default)]
+    pub review_sample_rate: Option<f64>,
 }
 
 // =============================================================================
@@ -185,6 +205,10 @@ pub struct WorkflowSettingsConfig {
     /// Maximum retries for failed steps
     #[serde(default)]
     pub max_retries: Option<u32>,
+
+    /// Default review sample rate for review steps (0.0 to 1.0, default 1.0)
+    #[serde(default)]
+    pub review_sample_rate: Option<f64>,
 }
 
 // =============================================================================
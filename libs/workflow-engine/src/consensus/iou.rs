@@ -1,8 +1,9 @@
-//! Intersection over Union (IoU) for spans and bounding boxes
+//! Intersection over Union (IoU) for spans, bounding boxes, and polygons
 //!
 //! Used for measuring agreement on spatial annotations like
-//! text spans, image regions, and bounding boxes.
+//! text spans, image regions, bounding boxes, and segmentation polygons.
 
+use glyph_domain::Polygon;
 use serde::{Deserialize, Serialize};
 
 // =============================================================================
@@ -261,6 +262,234 @@ pub fn average_iou_boxes(boxes_a: &[BoundingBox], boxes_b: &[BoundingBox]) -> f6
     total_iou / total_boxes as f64
 }
 
+/// Pairwise mean IoU between annotators' bounding boxes
+///
+/// Each pair of annotators is matched optimally (Hungarian algorithm) so
+/// the total IoU of matched boxes is maximised. The pair's score is that
+/// total divided by the larger box count, so unmatched boxes count as 0.
+///
+/// # Returns
+/// A symmetric N x N matrix where entry `[i][j]` is the mean IoU between
+/// annotators `i` and `j`
+#[must_use]
+pub fn iou_matrix(annotations: &[Vec<BoundingBox>]) -> Vec<Vec<f64>> {
+    let n = annotations.len();
+    let mut matrix = vec![vec![0.0; n]; n];
+    for (i, boxes_a) in annotations.iter().enumerate() {
+        for (j, boxes_b) in annotations.iter().enumerate().skip(i) {
+            let score = matched_mean_iou(boxes_a, boxes_b);
+            matrix[i][j] = score;
+            matrix[j][i] = score;
+        }
+    }
+    matrix
+}
+
+/// Mean IoU of the optimal one-to-one matching between two box sets
+fn matched_mean_iou(boxes_a: &[BoundingBox], boxes_b: &[BoundingBox]) -> f64 {
+    let size = boxes_a.len().max(boxes_b.len());
+    if size == 0 {
+        return 0.0;
+    }
+
+    // Pad to a square matrix; dummy rows/columns have zero IoU
+    let weights: Vec<Vec<f64>> = (0..size)
+        .map(|i| {
+            (0..size)
+                .map(|j| match (boxes_a.get(i), boxes_b.get(j)) {
+                    (Some(a), Some(b)) => iou_box(a, b),
+                    _ => 0.0,
+                })
+                .collect()
+        })
+        .collect();
+
+    max_weight_assignment(&weights) / size as f64
+}
+
+/// Total weight of the maximum-weight perfect matching in a square matrix
+///
+/// Kuhn-Munkres with potentials, minimising `1 - weight`; O(n^3).
+#[allow(clippy::needless_range_loop)]
+fn max_weight_assignment(weights: &[Vec<f64>]) -> f64 {
+    let n = weights.len();
+    // 1-indexed; column 0 and row 0 are sentinels
+    let mut u = vec![0.0; n + 1];
+    let mut v = vec![0.0; n + 1];
+    let mut row_of = vec![0usize; n + 1];
+    let mut way = vec![0usize; n + 1];
+
+    for row in 1..=n {
+        row_of[0] = row;
+        let mut col = 0;
+        let mut min_slack = vec![f64::INFINITY; n + 1];
+        let mut used = vec![false; n + 1];
+        loop {
+            used[col] = true;
+            let current_row = row_of[col];
+            let mut delta = f64::INFINITY;
+            let mut next_col = 0;
+            for j in 1..=n {
+                if used[j] {
+                    continue;
+                }
+                let cost = 1.0 - weights[current_row - 1][j - 1];
+                let slack = cost - u[current_row] - v[j];
+                if slack < min_slack[j] {
+                    min_slack[j] = slack;
+                    way[j] = col;
+                }
+                if min_slack[j] < delta {
+                    delta = min_slack[j];
+                    next_col = j;
+                }
+            }
+            for j in 0..=n {
+                if used[j] {
+                    u[row_of[j]] += delta;
+                    v[j] -= delta;
+                } else {
+                    min_slack[j] -= delta;
+                }
+            }
+            col = next_col;
+            if row_of[col] == 0 {
+                break;
+            }
+        }
+        // Augment along the alternating path
+        while col != 0 {
+            let prev = way[col];
+            row_of[col] = row_of[prev];
+            col = prev;
+        }
+    }
+
+    (1..=n).map(|j| weights[row_of[j] - 1][j - 1]).sum()
+}
+
+// =============================================================================
+// Polygon (2D)
+// =============================================================================
+
+type Point = (f64, f64);
+
+/// Calculate IoU between two simple polygons
+///
+/// Each polygon is fan-triangulated into signed triangles, so the
+/// intersection area is the signed sum of triangle-pair intersections,
+/// each clipped exactly with Sutherland-Hodgman. Works for concave
+/// polygons in either winding order.
+///
+/// # Returns
+/// IoU score in range [0.0, 1.0]; 0.0 if either polygon has zero area
+#[must_use]
+pub fn iou_polygon(a: &Polygon, b: &Polygon) -> f64 {
+    let area_a = signed_area(&a.points).abs();
+    let area_b = signed_area(&b.points).abs();
+    if area_a <= 0.0 || area_b <= 0.0 {
+        return 0.0;
+    }
+
+    let triangles_a = fan_triangles(&a.points);
+    let triangles_b = fan_triangles(&b.points);
+    let mut intersection_area = 0.0;
+    for (ta, sign_a) in &triangles_a {
+        for (tb, sign_b) in &triangles_b {
+            let clipped = clip_convex(ta, tb);
+            intersection_area += sign_a * sign_b * signed_area(&clipped).abs();
+        }
+    }
+
+    let union_area = area_a + area_b - intersection_area;
+    if union_area <= 0.0 {
+        return 0.0;
+    }
+
+    (intersection_area / union_area).clamp(0.0, 1.0)
+}
+
+/// Shoelace area; positive for counter-clockwise rings
+fn signed_area(points: &[Point]) -> f64 {
+    let n = points.len();
+    if n < 3 {
+        return 0.0;
+    }
+    let twice: f64 = (0..n)
+        .map(|i| {
+            let (x1, y1) = points[i];
+            let (x2, y2) = points[(i + 1) % n];
+            x1 * y2 - x2 * y1
+        })
+        .sum();
+    twice / 2.0
+}
+
+/// Split a polygon into counter-clockwise fan triangles with +1/-1 weights.
+///
+/// Summing the weighted triangle indicators reproduces the polygon's
+/// indicator, regardless of the polygon's own winding order.
+fn fan_triangles(points: &[Point]) -> Vec<([Point; 3], f64)> {
+    let winding = signed_area(points).signum();
+    (1..points.len().saturating_sub(1))
+        .filter_map(|i| {
+            let tri = [points[0], points[i], points[i + 1]];
+            let area = signed_area(&tri);
+            if area == 0.0 {
+                return None;
+            }
+            let ccw = if area > 0.0 {
+                tri
+            } else {
+                [tri[0], tri[2], tri[1]]
+            };
+            Some((ccw, area.signum() * winding))
+        })
+        .collect()
+}
+
+/// Sutherland-Hodgman clip of a convex subject by a convex CCW clip polygon
+fn clip_convex(subject: &[Point], clip: &[Point]) -> Vec<Point> {
+    let mut output: Vec<Point> = subject.to_vec();
+    for (&edge_start, &edge_end) in clip.iter().zip(clip.iter().cycle().skip(1)) {
+        let Some(&last) = output.last() else {
+            break;
+        };
+        let inside = |p: Point| cross(edge_start, edge_end, p) >= 0.0;
+
+        let input = std::mem::take(&mut output);
+        let mut previous = last;
+        for &current in &input {
+            match (inside(previous), inside(current)) {
+                (true, true) => output.push(current),
+                (true, false) => {
+                    output.push(line_intersection(previous, current, edge_start, edge_end));
+                }
+                (false, true) => {
+                    output.push(line_intersection(previous, current, edge_start, edge_end));
+                    output.push(current);
+                }
+                (false, false) => {}
+            }
+            previous = current;
+        }
+    }
+    output
+}
+
+/// Cross product of `a -> b` and `a -> p`; positive when `p` is left of the edge
+fn cross(a: Point, b: Point, p: Point) -> f64 {
+    (b.0 - a.0) * (p.1 - a.1) - (b.1 - a.1) * (p.0 - a.0)
+}
+
+/// Intersection of segment `p1-p2` with the infinite line through `a-b`
+fn line_intersection(p1: Point, p2: Point, a: Point, b: Point) -> Point {
+    let d1 = cross(a, b, p1);
+    let d2 = cross(a, b, p2);
+    let t = d1 / (d1 - d2);
+    (p1.0 + t * (p2.0 - p1.0), p1.1 + t * (p2.1 - p1.1))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -344,4 +573,126 @@ mod tests {
         let avg = average_iou_spans(&[], &[Span::new(0, 10)]);
         assert!((avg).abs() < 0.001);
     }
+
+    fn square(x: f64, y: f64, size: f64) -> Polygon {
+        Polygon {
+            points: vec![(x, y), (x + size, y), (x + size, y + size), (x, y + size)],
+        }
+    }
+
+    #[test]
+    fn test_polygon_iou_identical() {
+        let a = square(0.0, 0.0, 10.0);
+
+        assert!((iou_polygon(&a, &a) - 1.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_polygon_iou_overlap() {
+        let a = square(0.0, 0.0, 10.0);
+        let b = square(5.0, 5.0, 10.0);
+
+        // Same as the box case: 25 / 175
+        assert!((iou_polygon(&a, &b) - 25.0 / 175.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_polygon_iou_containment() {
+        let outer = square(0.0, 0.0, 10.0);
+        let inner = square(2.0, 2.0, 5.0);
+
+        // Intersection 25, union 100
+        assert!((iou_polygon(&outer, &inner) - 0.25).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_polygon_iou_disjoint() {
+        let a = square(0.0, 0.0, 10.0);
+        let b = square(20.0, 20.0, 10.0);
+
+        assert!(iou_polygon(&a, &b).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_polygon_iou_concave_and_clockwise() {
+        // L-shape (area 75) covering a 10x10 square minus its top-right quarter,
+        // wound clockwise
+        let l_shape = Polygon {
+            points: vec![
+                (0.0, 0.0),
+                (0.0, 10.0),
+                (5.0, 10.0),
+                (5.0, 5.0),
+                (10.0, 5.0),
+                (10.0, 0.0),
+            ],
+        };
+        let full = square(0.0, 0.0, 10.0);
+
+        assert!((iou_polygon(&l_shape, &full) - 0.75).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_polygon_iou_degenerate_is_zero() {
+        let line = Polygon {
+            points: vec![(0.0, 0.0), (5.0, 5.0), (10.0, 10.0)],
+        };
+        let a = square(0.0, 0.0, 10.0);
+
+        let iou = iou_polygon(&line, &a);
+        assert!(!iou.is_nan());
+        assert!(iou.abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_iou_matrix_three_annotators() {
+        let annotations = vec![
+            vec![
+                BoundingBox::new(0.0, 0.0, 10.0, 10.0),
+                BoundingBox::new(20.0, 20.0, 10.0, 10.0),
+            ],
+            vec![BoundingBox::new(0.0, 0.0, 10.0, 10.0)],
+            vec![
+                BoundingBox::new(20.0, 20.0, 10.0, 10.0),
+                BoundingBox::new(5.0, 0.0, 10.0, 10.0),
+            ],
+        ];
+
+        let matrix = iou_matrix(&annotations);
+
+        // A-B: one perfect match, one unmatched box -> 1.0 / 2
+        // A-C: perfect match + half-offset box (50 / 150) -> (1 + 1/3) / 2
+        // B-C: half-offset box only -> (1/3) / 2
+        let expected = [
+            [1.0, 0.5, 2.0 / 3.0],
+            [0.5, 1.0, 1.0 / 6.0],
+            [2.0 / 3.0, 1.0 / 6.0, 1.0],
+        ];
+        for (row, expected_row) in matrix.iter().zip(expected) {
+            for (value, expected_value) in row.iter().zip(expected_row) {
+                assert!((value - expected_value).abs() < 1e-9);
+            }
+        }
+    }
+
+    #[test]
+    fn test_iou_matrix_uses_optimal_matching() {
+        // Greedy takes the single best pair (70/130) and strands the rest at 0;
+        // the optimal matching pairs the two 60/140 overlaps instead
+        let annotations = vec![
+            vec![
+                BoundingBox::new(0.0, 0.0, 10.0, 10.0),
+                BoundingBox::new(7.0, 0.0, 10.0, 10.0),
+            ],
+            vec![
+                BoundingBox::new(3.0, 0.0, 10.0, 10.0),
+                BoundingBox::new(-4.0, 0.0, 10.0, 10.0),
+            ],
+        ];
+
+        let matrix = iou_matrix(&annotations);
+
+        let expected = (60.0 / 140.0 + 60.0 / 140.0) / 2.0;
+        assert!((matrix[0][1] - expected).abs() < 1e-9);
+    }
 }
@@ -3,7 +3,8 @@
 //! Provides implementations of:
 //! - Cohen's Kappa (2 annotators)
 //! - Krippendorff's Alpha (multiple annotators, missing data)
-//! - IoU (Intersection over Union) for spans and bounding boxes
+//! - IoU (Intersection over Union) for spans, bounding boxes, and polygons
+//! - Pairwise IoU agreement matrices across annotators
 
 pub mod alpha;
 pub mod iou;
@@ -3,25 +3,49 @@
 //! Integrates all workflow engine components: parser, state machine,
 //! executors, transitions, goals, and event sourcing.
 
+use std::collections::HashMap;
 use std::sync::Arc;
 
 use async_trait::async_trait;
-use chrono::Utc;
+use chrono::{DateTime, Utc};
 use glyph_domain::enums::StepType;
 use thiserror::Error;
-use tokio::sync::Mutex;
+use tokio::sync::{Mutex, OwnedMutexGuard, Semaphore};
 use uuid::Uuid;
 
 use crate::config::{StepLibrary, WorkflowConfig};
 use crate::events::{EventEmitter, EventStore, EventStoreError, PgEventStore, StateRebuilder};
 use crate::executor::{
     create_executor, AnnotationData, ExecutionContext, ExecutionResult, ExecutorError,
-    HandlerRegistry,
+    HandlerRegistry, SubmissionKind,
 };
 use crate::goals::GoalTracker;
+use crate::hooks::{CompletionHook, DraftStore, SubmissionValidator, WebhookCompletionHook};
 use crate::parser::{parse_workflow_with_library, ParseError, ValidationError};
-use crate::state::{StateTransitionError, WorkflowStateManager};
-use crate::transition::{ConditionError, TransitionEvaluator};
+use crate::state::{StateTransitionError, StepResult, WorkflowStateManager};
+use crate::transition::{
+    in_review_sample, review_sample_rate, ConditionError, TransitionEvaluator,
+};
+
+/// Attempts at a submission before a concurrency conflict is returned
+const MAX_SUBMISSION_ATTEMPTS: u32 = 3;
+
+/// Waiting reason returned after saving progress
+pub const PROGRESS_SAVED: &str = "Progress saved";
+
+/// Skip reason recorded for review steps that fall outside the sample
+const OUTSIDE_REVIEW_SAMPLE: &str = "outside_review_sample";
+
+/// SLA deadline for a step activated now, from its `timeout_minutes`
+fn sla_deadline(config: &WorkflowConfig, step_id: &str) -> Option<DateTime<Utc>> {
+    let minutes = config
+        .steps
+        .iter()
+        .find(|s| s.id == step_id)?
+        .settings
+        .timeout_minutes?;
+    Some(Utc::now() + chrono::Duration::minutes(i64::from(minutes)))
+}
 
 // =============================================================================
 // Errors
@@ -81,6 +105,12 @@ pub enum OrchestrationError {
     /// No steps defined
     #[error("Workflow has no steps defined")]
     NoStepsDefined,
+
+    /// The submission doesn't match the project type's output schema
+    #[error("Submission failed validation with {} violation(s)", violations.len())]
+    InvalidSubmission {
+        violations: Vec<glyph_domain::ValidationError>,
+    },
 }
 
 // =============================================================================
@@ -172,6 +202,57 @@ impl WorkflowConfigStore for InMemoryConfigStore {
     }
 }
 
+// =============================================================================
+// Task Locks
+// =============================================================================
+
+/// One lock per task with submissions in flight
+#[derive(Default)]
+struct TaskLocks(std::sync::Mutex<HashMap<Uuid, Arc<Mutex<()>>>>);
+
+impl TaskLocks {
+    /// Wait until no other submission for `task_id` is being processed
+    async fn lock(&self, task_id: Uuid) -> TaskLockGuard<'_> {
+        let lock = {
+            let mut locks = self
+                .0
+                .lock()
+                .unwrap_or_else(std::sync::PoisonError::into_inner);
+            Arc::clone(locks.entry(task_id).or_default())
+        };
+        TaskLockGuard {
+            locks: self,
+            task_id,
+            guard: Some(lock.lock_owned().await),
+        }
+    }
+}
+
+/// Holds a task's lock, dropping the task's entry once nobody else wants it
+struct TaskLockGuard<'a> {
+    locks: &'a TaskLocks,
+    task_id: Uuid,
+    guard: Option<OwnedMutexGuard<()>>,
+}
+
+impl Drop for TaskLockGuard<'_> {
+    fn drop(&mut self) {
+        let mut locks = self
+            .locks
+            .0
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner);
+        // Only the map and this guard hold the lock when no one is waiting
+        if locks
+            .get(&self.task_id)
+            .is_some_and(|lock| Arc::strong_count(lock) <= 2)
+        {
+            locks.remove(&self.task_id);
+        }
+        self.guard.take();
+    }
+}
+
 // =============================================================================
 // Workflow Orchestrator
 // =============================================================================
@@ -196,6 +277,21 @@ pub struct WorkflowOrchestrator {
 
     /// State rebuilder for event replay
     state_rebuilder: StateRebuilder,
+
+    /// Hooks run after a workflow completion is committed
+    completion_hooks: Vec<Arc<dyn CompletionHook>>,
+
+    /// Checks annotation payloads before they are executed
+    submission_validator: Option<Arc<dyn SubmissionValidator>>,
+
+    /// Keeps saved progress on annotation steps
+    draft_store: Option<Arc<dyn DraftStore>>,
+
+    /// Serializes submissions for the same task
+    task_locks: TaskLocks,
+
+    /// Caps submissions processed at once across all tasks
+    submission_permits: Option<Arc<Semaphore>>,
 }
 
 impl WorkflowOrchestrator {
@@ -224,22 +320,65 @@ impl WorkflowOrchestrator {
             goal_tracker,
             step_library,
             state_rebuilder,
+            completion_hooks: Vec::new(),
+            submission_validator: None,
+            draft_store: None,
+            task_locks: TaskLocks::default(),
+            submission_permits: None,
         }
     }
 
-    /// Create orchestrator with PostgreSQL event store
+    /// Create orchestrator with PostgreSQL event store.
+    ///
+    /// Completed workflows queue a webhook delivery for their project.
     #[must_use]
     pub fn with_pg(config_store: Arc<dyn WorkflowConfigStore>, pool: sqlx::PgPool) -> Self {
-        let event_store = Arc::new(PgEventStore::new(pool));
+        let event_store = Arc::new(PgEventStore::new(pool.clone()));
         Self::new(config_store, event_store)
+            .with_completion_hook(Arc::new(WebhookCompletionHook::new(pool)))
+    }
+
+    /// Validate annotation submissions before executing them
+    #[must_use]
+    pub fn with_submission_validator(mut self, validator: Arc<dyn SubmissionValidator>) -> Self {
+        self.submission_validator = Some(validator);
+        self
+    }
+
+    /// Store saved progress, enabling [`Self::save_progress`]
+    #[must_use]
+    pub fn with_draft_store(mut self, store: Arc<dyn DraftStore>) -> Self {
+        self.draft_store = Some(store);
+        self
+    }
+
+    /// Use a custom goal debounce window instead of [`crate::goals::DEBOUNCE_DURATION`]
+    #[must_use]
+    pub fn with_goal_debounce(mut self, debounce: std::time::Duration) -> Self {
+        self.goal_tracker = Arc::new(Mutex::new(GoalTracker::with_debounce(debounce)));
+        self
+    }
+
+    /// Process at most `limit` submissions at once; others wait their turn.
+    ///
+    /// Submissions for the same task are always processed one at a time.
+    #[must_use]
+    pub fn with_max_concurrent_submissions(mut self, limit: usize) -> Self {
+        self.submission_permits = Some(Arc::new(Semaphore::new(limit.max(1))));
+        self
+    }
+
+    /// Add a hook to run after each committed workflow completion
+    #[must_use]
+    pub fn with_completion_hook(mut self, hook: Arc<dyn CompletionHook>) -> Self {
+        self.completion_hooks.push(hook);
+        self
     }
 
     /// Get the entry step ID (first step in the workflow)
     fn get_entry_step(config: &WorkflowConfig) -> Result<&str, OrchestrationError> {
         config
-            .steps
-            .first()
-            .map(|s| s.id.as_str())
+            .entry_step_id()
             .ok_or(OrchestrationError::NoStepsDefined)
     }
 
@@ -268,11 +407,24 @@ impl WorkflowOrchestrator {
     // =========================================================================
 
     /// Start a workflow for a task
+    ///
+    /// Fails with [`OrchestrationError::AlreadyStarted`] if the task already
+    /// has workflow events, including when a concurrent start wins the race,
+    /// so a task is never started twice.
     pub async fn start_task(
         &self,
         task_id: Uuid,
         workflow_id: Uuid,
     ) -> Result<WorkflowStateManager, OrchestrationError> {
+        if self
+            .event_store
+            .get_stream_version(task_id)
+            .await?
+            .is_some()
+        {
+            return Err(OrchestrationError::AlreadyStarted(task_id));
+        }
+
         // Load workflow config
         let config = self.config_store.load(workflow_id).await?;
 
@@ -285,8 +437,9 @@ impl WorkflowOrchestrator {
         // Create initial state
         let mut state = WorkflowStateManager::new(entry_step, &step_ids);
 
-        // Create event emitter
-        let emitter = EventEmitter::new(Arc::clone(&self.event_store), task_id, "workflow");
+        // Both start events land together, and only on an empty stream
+        let emitter =
+            EventEmitter::new(Arc::clone(&self.event_store), task_id, "workflow").buffered(0);
 
         // Emit workflow started event
         emitter
@@ -297,12 +450,28 @@ impl WorkflowOrchestrator {
         state.activate_step(entry_step, vec![])?;
 
         // Emit step activated event
-        emitter.step_activated(entry_step, vec![]).await?;
+        emitter
+            .step_activated(entry_step, vec![], sla_deadline(&config, entry_step))
+            .await?;
 
-        Ok(state)
+        match emitter.commit().await {
+            Err(EventStoreError::ConcurrencyConflict { .. }) => {
+                Err(OrchestrationError::AlreadyStarted(task_id))
+            }
+            result => {
+                result?;
+                Ok(state)
+            }
+        }
     }
 
     /// Process an annotation submission for a task
+    ///
+    /// Submissions for one task are processed one at a time, while different
+    /// tasks proceed concurrently. All events from one submission are
+    /// appended atomically. If another process's submission for the same task
+    /// lands first, the submission is replayed against the new state, so a
+    /// step is never advanced twice.
     pub async fn process_submission(
         &self,
         task_id: Uuid,
@@ -310,10 +479,133 @@ impl WorkflowOrchestrator {
         step_id: &str,
         submission: serde_json::Value,
         user_id: Uuid,
+    ) -> Result<ProcessResult, OrchestrationError> {
+        let _task_lock = self.task_locks.lock(task_id).await;
+        let _permit = match &self.submission_permits {
+            Some(permits) => Some(
+                Arc::clone(permits)
+                    .acquire_owned()
+                    .await
+                    .map_err(|e| OrchestrationError::InvalidState(e.to_string()))?,
+            ),
+            None => None,
+        };
+
+        let mut attempt = 1;
+        loop {
+            let result = self
+                .try_process_submission(task_id, workflow_id, step_id, &submission, user_id)
+                .await;
+            match result {
+                Err(OrchestrationError::EventStoreError(
+                    EventStoreError::ConcurrencyConflict { expected, actual },
+                )) if attempt < MAX_SUBMISSION_ATTEMPTS => {
+                    tracing::debug!(
+                        %task_id,
+                        expected,
+                        actual,
+                        attempt,
+                        "Concurrent submission detected; retrying"
+                    );
+                    attempt += 1;
+                }
+                Ok(ProcessResult::Completed { final_output }) => {
+                    self.discard_draft(task_id, step_id, user_id).await;
+                    for hook in &self.completion_hooks {
+                        hook.on_completed(task_id, &final_output).await;
+                    }
+                    return Ok(ProcessResult::Completed { final_output });
+                }
+                Ok(result) => {
+                    self.discard_draft(task_id, step_id, user_id).await;
+                    return Ok(result);
+                }
+                other => return other,
+            }
+        }
+    }
+
+    /// Save a partial annotation without submitting it.
+    ///
+    /// The data is stored as the user's draft for the step; nothing is
+    /// validated or executed and no workflow events are recorded, so the step
+    /// never advances. Submit the finished annotation with
+    /// [`Self::process_submission`].
+    pub async fn save_progress(
+        &self,
+        task_id: Uuid,
+        workflow_id: Uuid,
+        step_id: &str,
+        data: serde_json::Value,
+        user_id: Uuid,
+    ) -> Result<ProcessResult, OrchestrationError> {
+        let store = self.draft_store.as_ref().ok_or_else(|| {
+            OrchestrationError::InvalidState("Saving progress is not enabled".to_string())
+        })?;
+
+        let config = self.config_store.load(workflow_id).await?;
+        let step_config = config
+            .steps
+            .iter()
+            .find(|s| s.id == step_id)
+            .ok_or_else(|| OrchestrationError::StepNotFound(step_id.to_string()))?;
+        if step_config.step_type != StepType::Annotation {
+            return Err(OrchestrationError::InvalidState(format!(
+                "Progress can only be saved on annotation steps; {step_id} is {:?}",
+                step_config.step_type
+            )));
+        }
+
+        let state = self.get_task_state(task_id, workflow_id).await?;
+        if state.current_step() != Some(step_id) {
+            return Err(OrchestrationError::InvalidState(format!(
+                "Expected step {}, but current step is {:?}",
+                step_id,
+                state.current_step()
+            )));
+        }
+
+        store
+            .save_draft(task_id, step_id, user_id, &data)
+            .await
+            .map_err(OrchestrationError::StorageError)?;
+
+        Ok(ProcessResult::Waiting {
+            step_id: step_id.to_string(),
+            reason: PROGRESS_SAVED.to_string(),
+        })
+    }
+
+    /// Drop a user's draft after a final submission; failures are only logged
+    async fn discard_draft(&self, task_id: Uuid, step_id: &str, user_id: Uuid) {
+        let Some(store) = &self.draft_store else {
+            return;
+        };
+        if let Err(error) = store.discard_draft(task_id, step_id, user_id).await {
+            tracing::warn!(%task_id, step_id, %error, "Failed to discard draft after submission");
+        }
+    }
+
+    /// One attempt at processing a submission against the current stream version
+    async fn try_process_submission(
+        &self,
+        task_id: Uuid,
+        workflow_id: Uuid,
+        step_id: &str,
+        submission: &serde_json::Value,
+        user_id: Uuid,
     ) -> Result<ProcessResult, OrchestrationError> {
         // Load workflow config
         let config = self.config_store.load(workflow_id).await?;
 
+        // Read the version before rebuilding: if anything lands after this,
+        // the commit below conflicts and the submission is retried
+        let expected_version = self
+            .event_store
+            .get_stream_version(task_id)
+            .await?
+            .unwrap_or(0);
+
         // Rebuild state from events
         let step_ids: Vec<&str> = config.steps.iter().map(|s| s.id.as_str()).collect();
         let mut state = self
@@ -338,13 +630,28 @@ impl WorkflowOrchestrator {
             .find(|s| s.id == step_id)
             .ok_or_else(|| OrchestrationError::StepNotFound(step_id.to_string()))?;
 
+        // Reject malformed annotations before they enter the workflow
+        if step_config.step_type == StepType::Annotation {
+            if let Some(validator) = &self.submission_validator {
+                let violations = validator
+                    .validate(task_id, step_id, submission)
+                    .await
+                    .map_err(OrchestrationError::StorageError)?;
+                if !violations.is_empty() {
+                    return Err(OrchestrationError::InvalidSubmission { violations });
+                }
+            }
+        }
+
         // Create annotation data from submission
+        let annotation_id = Uuid::new_v4();
         let annotation = AnnotationData {
-            annotation_id: Uuid::new_v4(),
+            annotation_id,
             user_id,
             data: submission.clone(),
             submitted_at: Utc::now(),
             decision: None,
+            kind: SubmissionKind::Final,
         };
 
         // Create execution context
@@ -357,10 +664,14 @@ impl WorkflowOrchestrator {
 
         let result = executor.execute(&ctx).await?;
 
-        // Create event emitter
-        let emitter = EventEmitter::new(Arc::clone(&self.event_store), task_id, "workflow");
+        // Create event emitter; the nil user is the system (auto-process steps)
+        let mut emitter = EventEmitter::new(Arc::clone(&self.event_store), task_id, "workflow")
+            .buffered(expected_version);
+        if !user_id.is_nil() {
+            emitter = emitter.with_actor(user_id);
+        }
 
-        match result {
+        let outcome = match result {
             ExecutionResult::Complete {
                 result: step_result,
             } => {
@@ -370,56 +681,31 @@ impl WorkflowOrchestrator {
                 // Emit step completed event
                 emitter.step_completed(step_id, step_result.clone()).await?;
 
-                // Evaluate transitions using TransitionEvaluator
-                let evaluator = TransitionEvaluator::new(&config);
-                let next_step = evaluator.evaluate_next_step(
+                Self::advance_from(
+                    &config,
+                    &mut state,
+                    &emitter,
                     step_id,
-                    &state,
-                    Some(&step_result),
-                    None, // No consensus score
-                );
-
-                // Handle transition result
-                match next_step {
-                    Ok(Some(next)) => {
-                        // Emit transition event
-                        emitter.transition_occurred(step_id, &next, None).await?;
-
-                        // Activate next step
-                        state.activate_step(&next, vec![])?;
-                        state.transition_to(&next, "condition_met")?;
-
-                        // Emit step activated event
-                        emitter.step_activated(&next, vec![]).await?;
-
-                        Ok(ProcessResult::Advanced {
-                            from_step: step_id.to_string(),
-                            to_step: next,
-                        })
-                    }
-                    Ok(None) => {
-                        // Workflow complete (terminal state reached)
-                        state.complete_workflow("all_steps_complete");
-
-                        let output = serde_json::json!({"status": "completed"});
-                        emitter.workflow_completed(output.clone()).await?;
-
-                        Ok(ProcessResult::Completed {
-                            final_output: output,
-                        })
-                    }
-                    Err(_) => {
-                        // No matching transition - workflow complete
-                        state.complete_workflow("no_matching_transition");
+                    step_result,
+                    annotation_id,
+                )
+                .await
+            }
 
-                        let output = serde_json::json!({"status": "completed"});
-                        emitter.workflow_completed(output.clone()).await?;
+            ExecutionResult::Skipped { reason } => {
+                state.skip_step(step_id, &reason)?;
+                emitter.step_skipped(step_id, &reason).await?;
 
-                        Ok(ProcessResult::Completed {
-                            final_output: output,
-                        })
-                    }
-                }
+                // Transitions see a skipped step as passed
+                Self::advance_from(
+                    &config,
+                    &mut state,
+                    &emitter,
+                    step_id,
+                    StepResult::approved(),
+                    annotation_id,
+                )
+                .await
             }
 
             ExecutionResult::Waiting { reason } => {
@@ -443,6 +729,79 @@ impl WorkflowOrchestrator {
                     recoverable: retryable,
                 })
             }
+        }?;
+
+        emitter.commit().await?;
+        Ok(outcome)
+    }
+
+    /// Follow transitions out of a completed or skipped step.
+    ///
+    /// Review steps whose sample excludes this annotation are skipped and
+    /// passed through, so the task lands on the next step that needs work.
+    async fn advance_from(
+        config: &WorkflowConfig,
+        state: &mut WorkflowStateManager,
+        emitter: &EventEmitter,
+        step_id: &str,
+        step_result: StepResult,
+        annotation_id: Uuid,
+    ) -> Result<ProcessResult, OrchestrationError> {
+        let evaluator = TransitionEvaluator::new(config);
+        let mut from = step_id.to_string();
+        let mut result = step_result;
+
+        loop {
+            let next_step = evaluator.evaluate_next_step(
+                &from,
+                state,
+                Some(&result),
+                None, // No consensus score
+            );
+
+            // Handle transition result
+            let reason = match next_step {
+                Ok(Some(next)) => {
+                    // Emit transition event
+                    emitter.transition_occurred(&from, &next, None).await?;
+
+                    // Activate next step
+                    state.activate_step(&next, vec![])?;
+                    state.transition_to(&next, "condition_met")?;
+
+                    // Emit step activated event
+                    emitter
+                        .step_activated(&next, vec![], sla_deadline(config, &next))
+                        .await?;
+
+                    if in_review_sample(annotation_id, review_sample_rate(config, &next)) {
+                        return Ok(ProcessResult::Advanced {
+                            from_step: step_id.to_string(),
+                            to_step: next,
+                        });
+                    }
+
+                    // Not sampled for review: skip it and keep going
+                    state.skip_step(&next, OUTSIDE_REVIEW_SAMPLE)?;
+                    emitter.step_skipped(&next, OUTSIDE_REVIEW_SAMPLE).await?;
+                    result = StepResult::approved();
+                    from = next;
+                    continue;
+                }
+                // Workflow complete (terminal state reached)
+                Ok(None) => "all_steps_complete",
+                // No matching transition - workflow complete
+                Err(_) => "no_matching_transition",
+            };
+
+            state.complete_workflow(reason);
+
+            let output = serde_json::json!({"status": "completed"});
+            emitter.workflow_completed(output.clone()).await?;
+
+            return Ok(ProcessResult::Completed {
+                final_output: output,
+            });
         }
     }
 
@@ -523,6 +882,54 @@ impl WorkflowOrchestrator {
 mod tests {
     use super::*;
 
+    const SINGLE_STEP: &str = r#"
+version: "1.0"
+name: "Single Step"
+workflow_type: single
+steps:
+  - id: annotate
+    name: Annotation
+    step_type: annotation
+transitions:
+  - from: annotate
+    to: _complete
+"#;
+
+    const TWO_STEP: &str = r#"
+version: "1.0"
+name: "Two Step"
+workflow_type: single
+steps:
+  - id: annotate
+    name: Annotation
+    step_type: annotation
+  - id: review
+    name: Review
+    step_type: review
+transitions:
+  - from: annotate
+    to: review
+  - from: review
+    to: _complete
+"#;
+
+    /// An orchestrator over `event_store` with `yaml` saved to a fresh
+    /// config store, and the saved workflow's id
+    async fn orchestrator_with(
+        yaml: &str,
+        event_store: Arc<dyn EventStore>,
+    ) -> (WorkflowOrchestrator, Uuid) {
+        let config_store = Arc::new(InMemoryConfigStore::new());
+        let workflow_id = config_store
+            .save(&crate::parser::parse_workflow(yaml).unwrap())
+            .await
+            .unwrap();
+        (
+            WorkflowOrchestrator::new(config_store, event_store),
+            workflow_id,
+        )
+    }
+
     #[test]
     fn test_process_result_variants() {
         let waiting = ProcessResult::Waiting {
@@ -544,6 +951,652 @@ mod tests {
         assert!(store.configs.try_lock().is_ok());
     }
 
+    #[test]
+    fn test_execution_result_skipped() {
+        let skipped = ExecutionResult::skipped("trusted annotator");
+        assert!(skipped.is_skipped());
+        assert!(!skipped.is_complete());
+    }
+
+    #[tokio::test]
+    async fn test_skipped_review_advances_workflow() {
+        let yaml = r#"
+version: "1.0"
+name: "Sampled Review"
+workflow_type: single
+steps:
+  - id: annotate
+    name: Annotation
+    step_type: annotation
+  - id: review
+    name: Review
+    step_type: review
+    settings:
+      review_sample_rate: 0.0
+  - id: finalize
+    name: Finalize
+    step_type: annotation
+transitions:
+  - from: annotate
+    to: review
+  - from: review
+    to: finalize
+    condition:
+      type: on_approved
+  - from: finalize
+    to: _complete
+"#;
+        let event_store = Arc::new(crate::events::InMemoryEventStore::new());
+        let (orchestrator, workflow_id) = orchestrator_with(yaml, event_store.clone()).await;
+
+        let task_id = Uuid::new_v4();
+        orchestrator.start_task(task_id, workflow_id).await.unwrap();
+        let result = orchestrator
+            .process_submission(
+                task_id,
+                workflow_id,
+                "annotate",
+                serde_json::json!({"label": "cat"}),
+                Uuid::new_v4(),
+            )
+            .await
+            .unwrap();
+
+        assert!(matches!(
+            result,
+            ProcessResult::Advanced { ref to_step, .. } if to_step == "finalize"
+        ));
+
+        let events = event_store.load_events(task_id, 0).await.unwrap();
+        assert!(events.iter().any(|e| matches!(
+            &e.event,
+            crate::events::WorkflowEvent::StepSkipped { step_id, .. } if step_id == "review"
+        )));
+
+        // Replaying the stream lands on the same state
+        let state = StateRebuilder::new(event_store)
+            .rebuild_state(task_id, &["annotate", "review", "finalize"])
+            .await
+            .unwrap();
+        assert_eq!(state.current_step(), Some("finalize"));
+        assert!(matches!(
+            state.all_step_states().get("review"),
+            Some(crate::state::StepState::Skipped { .. })
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_two_step_workflow_runs_to_completion_in_memory() {
+        let yaml = r#"
+version: "1.0"
+name: "Annotate Then Check"
+workflow_type: single
+steps:
+  - id: annotate
+    name: Annotation
+    step_type: annotation
+  - id: check
+    name: Check
+    step_type: annotation
+transitions:
+  - from: annotate
+    to: check
+  - from: check
+    to: _complete
+"#;
+        let event_store = Arc::new(crate::events::InMemoryEventStore::new());
+        let (orchestrator, workflow_id) = orchestrator_with(yaml, event_store.clone()).await;
+
+        let task_id = Uuid::new_v4();
+        orchestrator.start_task(task_id, workflow_id).await.unwrap();
+
+        let first = orchestrator
+            .process_submission(
+                task_id,
+                workflow_id,
+                "annotate",
+                serde_json::json!({"label": "cat"}),
+                Uuid::new_v4(),
+            )
+            .await
+            .unwrap();
+        assert!(matches!(
+            first,
+            ProcessResult::Advanced { ref to_step, .. } if to_step == "check"
+        ));
+
+        let second = orchestrator
+            .process_submission(
+                task_id,
+                workflow_id,
+                "check",
+                serde_json::json!({"label": "cat"}),
+                Uuid::new_v4(),
+            )
+            .await
+            .unwrap();
+        assert!(matches!(second, ProcessResult::Completed { .. }));
+
+        // The stream opens and closes the workflow, with contiguous versions
+        let events = event_store.load_events(task_id, 0).await.unwrap();
+        assert!(matches!(
+            events.first().map(|e| &e.event),
+            Some(crate::events::WorkflowEvent::WorkflowStarted { .. })
+        ));
+        assert!(matches!(
+            events.last().map(|e| &e.event),
+            Some(crate::events::WorkflowEvent::WorkflowCompleted { .. })
+        ));
+        assert!(events
+            .iter()
+            .zip(1_u64..)
+            .all(|(event, version)| event.version == version));
+
+        let state = StateRebuilder::new(event_store)
+            .rebuild_state(task_id, &["annotate", "check"])
+            .await
+            .unwrap();
+        assert!(state.is_complete());
+        assert!(state.all_steps_terminal());
+    }
+
+    #[tokio::test]
+    async fn test_start_activates_explicit_entry_step() {
+        let yaml = r#"
+version: "1.0"
+name: "Out Of Order"
+workflow_type: single
+entry_step: annotate
+steps:
+  - id: review
+    name: Review
+    step_type: review
+  - id: annotate
+    name: Annotation
+    step_type: annotation
+transitions:
+  - from: annotate
+    to: review
+  - from: review
+    to: _complete
+"#;
+        let (orchestrator, workflow_id) =
+            orchestrator_with(yaml, Arc::new(crate::events::InMemoryEventStore::new())).await;
+
+        let state = orchestrator
+            .start_task(Uuid::new_v4(), workflow_id)
+            .await
+            .unwrap();
+        assert_eq!(state.current_step(), Some("annotate"));
+    }
+
+    #[tokio::test]
+    async fn test_second_start_errors_without_new_events() {
+        let event_store = Arc::new(crate::events::InMemoryEventStore::new());
+        let (orchestrator, workflow_id) = orchestrator_with(SINGLE_STEP, event_store.clone()).await;
+
+        let task_id = Uuid::new_v4();
+        orchestrator.start_task(task_id, workflow_id).await.unwrap();
+        let version = event_store.get_stream_version(task_id).await.unwrap();
+        assert_eq!(version, Some(2));
+
+        assert!(matches!(
+            orchestrator.start_task(task_id, workflow_id).await,
+            Err(OrchestrationError::AlreadyStarted(id)) if id == task_id
+        ));
+        assert_eq!(
+            event_store.get_stream_version(task_id).await.unwrap(),
+            version
+        );
+    }
+
+    #[tokio::test]
+    async fn test_concurrent_starts_start_once() {
+        let event_store = Arc::new(crate::events::InMemoryEventStore::new());
+        let (orchestrator, workflow_id) = orchestrator_with(SINGLE_STEP, event_store.clone()).await;
+
+        let task_id = Uuid::new_v4();
+        let (first, second) = tokio::join!(
+            orchestrator.start_task(task_id, workflow_id),
+            orchestrator.start_task(task_id, workflow_id),
+        );
+
+        assert_eq!(u8::from(first.is_ok()) + u8::from(second.is_ok()), 1);
+        assert_eq!(
+            event_store.get_stream_version(task_id).await.unwrap(),
+            Some(2)
+        );
+    }
+
+    /// Hook that records the tasks it was called for
+    #[derive(Default)]
+    struct RecordingHook(Mutex<Vec<Uuid>>);
+
+    #[async_trait]
+    impl CompletionHook for RecordingHook {
+        async fn on_completed(&self, task_id: Uuid, _final_output: &serde_json::Value) {
+            self.0.lock().await.push(task_id);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_completion_runs_hooks() {
+        let event_store = Arc::new(crate::events::InMemoryEventStore::new());
+        let hook = Arc::new(RecordingHook::default());
+        let (orchestrator, workflow_id) = orchestrator_with(SINGLE_STEP, event_store).await;
+        let orchestrator = orchestrator.with_completion_hook(hook.clone());
+
+        let task_id = Uuid::new_v4();
+        orchestrator.start_task(task_id, workflow_id).await.unwrap();
+        let result = orchestrator
+            .process_submission(
+                task_id,
+                workflow_id,
+                "annotate",
+                serde_json::json!({"label": "cat"}),
+                Uuid::new_v4(),
+            )
+            .await
+            .unwrap();
+
+        assert!(matches!(result, ProcessResult::Completed { .. }));
+        assert_eq!(*hook.0.lock().await, vec![task_id]);
+    }
+
+    /// Validator requiring a top-level `label` field
+    struct RequiresLabel;
+
+    #[async_trait]
+    impl SubmissionValidator for RequiresLabel {
+        async fn validate(
+            &self,
+            _task_id: Uuid,
+            _step_id: &str,
+            submission: &serde_json::Value,
+        ) -> Result<Vec<glyph_domain::ValidationError>, String> {
+            if submission.get("label").is_some() {
+                return Ok(Vec::new());
+            }
+            Ok(vec![glyph_domain::ValidationError {
+                path: String::new(),
+                message: "\"label\" is a required property".to_string(),
+                keyword: Some("required".to_string()),
+            }])
+        }
+    }
+
+    #[tokio::test]
+    async fn test_submission_missing_required_field_is_rejected() {
+        let event_store = Arc::new(crate::events::InMemoryEventStore::new());
+        let (orchestrator, workflow_id) = orchestrator_with(SINGLE_STEP, event_store.clone()).await;
+        let orchestrator = orchestrator.with_submission_validator(Arc::new(RequiresLabel));
+
+        let task_id = Uuid::new_v4();
+        orchestrator.start_task(task_id, workflow_id).await.unwrap();
+        let version = event_store.get_stream_version(task_id).await.unwrap();
+
+        let result = orchestrator
+            .process_submission(
+                task_id,
+                workflow_id,
+                "annotate",
+                serde_json::json!({"confidence": 0.9}),
+                Uuid::new_v4(),
+            )
+            .await;
+        let Err(OrchestrationError::InvalidSubmission { violations }) = result else {
+            panic!("expected InvalidSubmission, got {result:?}");
+        };
+        assert_eq!(violations.len(), 1);
+        assert_eq!(violations[0].keyword.as_deref(), Some("required"));
+        // Nothing was recorded for the rejected submission
+        assert_eq!(
+            event_store.get_stream_version(task_id).await.unwrap(),
+            version
+        );
+
+        // A valid submission still goes through
+        let result = orchestrator
+            .process_submission(
+                task_id,
+                workflow_id,
+                "annotate",
+                serde_json::json!({"label": "cat"}),
+                Uuid::new_v4(),
+            )
+            .await
+            .unwrap();
+        assert!(matches!(result, ProcessResult::Completed { .. }));
+    }
+
+    /// Draft store keyed by (task, step, user)
+    #[derive(Default)]
+    struct MemoryDrafts(Mutex<std::collections::HashMap<(Uuid, String, Uuid), serde_json::Value>>);
+
+    #[async_trait]
+    impl DraftStore for MemoryDrafts {
+        async fn save_draft(
+            &self,
+            task_id: Uuid,
+            step_id: &str,
+            user_id: Uuid,
+            data: &serde_json::Value,
+        ) -> Result<(), String> {
+            self.0
+                .lock()
+                .await
+                .insert((task_id, step_id.to_string(), user_id), data.clone());
+            Ok(())
+        }
+
+        async fn discard_draft(
+            &self,
+            task_id: Uuid,
+            step_id: &str,
+            user_id: Uuid,
+        ) -> Result<(), String> {
+            self.0
+                .lock()
+                .await
+                .remove(&(task_id, step_id.to_string(), user_id));
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_partial_save_does_not_advance_but_submit_does() {
+        let event_store = Arc::new(crate::events::InMemoryEventStore::new());
+        let drafts = Arc::new(MemoryDrafts::default());
+        let (orchestrator, workflow_id) = orchestrator_with(SINGLE_STEP, event_store.clone()).await;
+        let orchestrator = orchestrator.with_draft_store(drafts.clone());
+
+        let task_id = Uuid::new_v4();
+        let user_id = Uuid::new_v4();
+        orchestrator.start_task(task_id, workflow_id).await.unwrap();
+        let version = event_store.get_stream_version(task_id).await.unwrap();
+
+        let result = orchestrator
+            .save_progress(
+                task_id,
+                workflow_id,
+                "annotate",
+                serde_json::json!({"label": "ca"}),
+                user_id,
+            )
+            .await
+            .unwrap();
+        assert!(matches!(
+            result,
+            ProcessResult::Waiting { ref reason, .. } if reason == PROGRESS_SAVED
+        ));
+        assert_eq!(
+            event_store.get_stream_version(task_id).await.unwrap(),
+            version
+        );
+        let state = orchestrator
+            .get_task_state(task_id, workflow_id)
+            .await
+            .unwrap();
+        assert_eq!(state.current_step(), Some("annotate"));
+        assert_eq!(drafts.0.lock().await.len(), 1);
+
+        let result = orchestrator
+            .process_submission(
+                task_id,
+                workflow_id,
+                "annotate",
+                serde_json::json!({"label": "cat"}),
+                user_id,
+            )
+            .await
+            .unwrap();
+        assert!(matches!(result, ProcessResult::Completed { .. }));
+        // The draft is gone once the final version is submitted
+        assert!(drafts.0.lock().await.is_empty());
+    }
+
+    /// Event store that yields before every call so concurrent tasks interleave
+    struct YieldingStore(crate::events::InMemoryEventStore);
+
+    #[async_trait]
+    impl EventStore for YieldingStore {
+        async fn append(
+            &self,
+            stream_id: Uuid,
+            stream_type: &str,
+            expected_version: Option<u64>,
+            events: Vec<crate::events::WorkflowEvent>,
+            metadata: serde_json::Value,
+        ) -> Result<u64, EventStoreError> {
+            tokio::task::yield_now().await;
+            self.0
+                .append(stream_id, stream_type, expected_version, events, metadata)
+                .await
+        }
+
+        async fn load_events(
+            &self,
+            stream_id: Uuid,
+            from_version: u64,
+        ) -> Result<Vec<crate::events::StoredEvent>, EventStoreError> {
+            tokio::task::yield_now().await;
+            self.0.load_events(stream_id, from_version).await
+        }
+
+        async fn get_latest_snapshot(
+            &self,
+            stream_id: Uuid,
+        ) -> Result<Option<crate::state::WorkflowSnapshot>, EventStoreError> {
+            tokio::task::yield_now().await;
+            self.0.get_latest_snapshot(stream_id).await
+        }
+
+        async fn save_snapshot(
+            &self,
+            stream_id: Uuid,
+            stream_type: &str,
+            snapshot: &crate::state::WorkflowSnapshot,
+        ) -> Result<(), EventStoreError> {
+            self.0.save_snapshot(stream_id, stream_type, snapshot).await
+        }
+
+        async fn get_stream_version(
+            &self,
+            stream_id: Uuid,
+        ) -> Result<Option<u64>, EventStoreError> {
+            tokio::task::yield_now().await;
+            self.0.get_stream_version(stream_id).await
+        }
+    }
+
+    #[tokio::test]
+    async fn test_concurrent_submissions_advance_once() {
+        let event_store = Arc::new(YieldingStore(crate::events::InMemoryEventStore::new()));
+        let (orchestrator, workflow_id) = orchestrator_with(TWO_STEP, event_store.clone()).await;
+
+        let task_id = Uuid::new_v4();
+        orchestrator.start_task(task_id, workflow_id).await.unwrap();
+
+        let submit = |label: &'static str| {
+            orchestrator.process_submission(
+                task_id,
+                workflow_id,
+                "annotate",
+                serde_json::json!({ "label": label }),
+                Uuid::new_v4(),
+            )
+        };
+        let (first, second) = tokio::join!(submit("cat"), submit("dog"));
+
+        // Exactly one submission advances; the other sees the step already done
+        let advanced = [&first, &second]
+            .iter()
+            .filter(|r| matches!(r, Ok(ProcessResult::Advanced { .. })))
+            .count();
+        assert_eq!(advanced, 1);
+        assert!([&first, &second]
+            .iter()
+            .any(|r| matches!(r, Err(OrchestrationError::InvalidState(_)))));
+
+        let events = event_store.load_events(task_id, 0).await.unwrap();
+        let transitions = events
+            .iter()
+            .filter(|e| e.event.event_type() == "transition_occurred")
+            .count();
+        assert_eq!(transitions, 1);
+    }
+
+    /// Event store recording how many calls overlap, overall and per stream
+    #[derive(Default)]
+    struct OverlapProbe {
+        store: crate::events::InMemoryEventStore,
+        in_flight: std::sync::Mutex<HashMap<Uuid, usize>>,
+        max_per_stream: std::sync::atomic::AtomicUsize,
+        max_overall: std::sync::atomic::AtomicUsize,
+    }
+
+    impl OverlapProbe {
+        /// Count a call on `stream_id` as in flight while it sleeps
+        async fn enter(&self, stream_id: Uuid) {
+            use std::sync::atomic::Ordering;
+            {
+                let mut in_flight = self.in_flight.lock().unwrap();
+                let stream = in_flight.entry(stream_id).or_default();
+                *stream += 1;
+                self.max_per_stream.fetch_max(*stream, Ordering::SeqCst);
+                let overall = in_flight.values().sum();
+                self.max_overall.fetch_max(overall, Ordering::SeqCst);
+            }
+            tokio::time::sleep(std::time::Duration::from_millis(5)).await;
+            *self.in_flight.lock().unwrap().get_mut(&stream_id).unwrap() -= 1;
+        }
+    }
+
+    #[async_trait]
+    impl EventStore for OverlapProbe {
+        async fn append(
+            &self,
+            stream_id: Uuid,
+            stream_type: &str,
+            expected_version: Option<u64>,
+            events: Vec<crate::events::WorkflowEvent>,
+            metadata: serde_json::Value,
+        ) -> Result<u64, EventStoreError> {
+            self.enter(stream_id).await;
+            self.store
+                .append(stream_id, stream_type, expected_version, events, metadata)
+                .await
+        }
+
+        async fn load_events(
+            &self,
+            stream_id: Uuid,
+            from_version: u64,
+        ) -> Result<Vec<crate::events::StoredEvent>, EventStoreError> {
+            self.enter(stream_id).await;
+            self.store.load_events(stream_id, from_version).await
+        }
+
+        async fn get_latest_snapshot(
+            &self,
+            stream_id: Uuid,
+        ) -> Result<Option<crate::state::WorkflowSnapshot>, EventStoreError> {
+            self.enter(stream_id).await;
+            self.store.get_latest_snapshot(stream_id).await
+        }
+
+        async fn save_snapshot(
+            &self,
+            stream_id: Uuid,
+            stream_type: &str,
+            snapshot: &crate::state::WorkflowSnapshot,
+        ) -> Result<(), EventStoreError> {
+            self.store
+                .save_snapshot(stream_id, stream_type, snapshot)
+                .await
+        }
+
+        async fn get_stream_version(
+            &self,
+            stream_id: Uuid,
+        ) -> Result<Option<u64>, EventStoreError> {
+            self.enter(stream_id).await;
+            self.store.get_stream_version(stream_id).await
+        }
+    }
+
+    #[tokio::test]
+    async fn test_submissions_serialize_per_task_but_not_across_tasks() {
+        use std::sync::atomic::Ordering;
+
+        let event_store = Arc::new(OverlapProbe::default());
+        let (orchestrator, workflow_id) = orchestrator_with(TWO_STEP, event_store.clone()).await;
+
+        let (first_task, second_task) = (Uuid::new_v4(), Uuid::new_v4());
+        orchestrator
+            .start_task(first_task, workflow_id)
+            .await
+            .unwrap();
+        orchestrator
+            .start_task(second_task, workflow_id)
+            .await
+            .unwrap();
+        event_store.max_per_stream.store(0, Ordering::SeqCst);
+        event_store.max_overall.store(0, Ordering::SeqCst);
+
+        let submit = |task_id: Uuid, label: &'static str| {
+            orchestrator.process_submission(
+                task_id,
+                workflow_id,
+                "annotate",
+                serde_json::json!({ "label": label }),
+                Uuid::new_v4(),
+            )
+        };
+        let (a, b, c) = tokio::join!(
+            submit(first_task, "cat"),
+            submit(first_task, "dog"),
+            submit(second_task, "bird"),
+        );
+
+        // The second submission for the first task waited and saw the step done
+        assert!(matches!(a, Ok(ProcessResult::Advanced { .. })));
+        assert!(matches!(b, Err(OrchestrationError::InvalidState(_))));
+        assert!(matches!(c, Ok(ProcessResult::Advanced { .. })));
+
+        // One task's calls never overlapped; the two tasks' calls did
+        assert_eq!(event_store.max_per_stream.load(Ordering::SeqCst), 1);
+        assert_eq!(event_store.max_overall.load(Ordering::SeqCst), 2);
+        // Locks are released once nobody is waiting on them
+        assert!(orchestrator.task_locks.0.lock().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_buffered_emitter_conflicts_on_stale_version() {
+        let store: Arc<dyn EventStore> = Arc::new(crate::events::InMemoryEventStore::new());
+        let stream_id = Uuid::new_v4();
+
+        let first = EventEmitter::new(Arc::clone(&store), stream_id, "workflow").buffered(0);
+        let second = EventEmitter::new(Arc::clone(&store), stream_id, "workflow").buffered(0);
+        first
+            .step_activated("annotate", vec![], None)
+            .await
+            .unwrap();
+        second
+            .step_activated("annotate", vec![], None)
+            .await
+            .unwrap();
+
+        assert_eq!(first.commit().await.unwrap(), Some(1));
+        assert!(matches!(
+            second.commit().await,
+            Err(EventStoreError::ConcurrencyConflict {
+                expected: 0,
+                actual: 1
+            })
+        ));
+    }
+
     #[test]
     fn test_orchestration_error_display() {
         let err = OrchestrationError::ConfigNotFound(Uuid::nil());
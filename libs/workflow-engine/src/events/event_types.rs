@@ -30,6 +30,9 @@ pub enum WorkflowEvent {
     StepActivated {
         step_id: String,
         assigned_to: Vec<Uuid>,
+        /// When the step breaches its timeout, if it has one
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        sla_deadline: Option<DateTime<Utc>>,
         activated_at: DateTime<Utc>,
     },
 
@@ -55,6 +58,13 @@ pub enum WorkflowEvent {
         skipped_at: DateTime<Utc>,
     },
 
+    /// Step is still active past its SLA deadline
+    StepOverdue {
+        step_id: String,
+        deadline: DateTime<Utc>,
+        detected_at: DateTime<Utc>,
+    },
+
     /// Transition from one step to another
     TransitionOccurred {
         from_step: String,
@@ -103,6 +113,7 @@ impl WorkflowEvent {
             Self::StepCompleted { .. } => "step_completed",
             Self::StepFailed { .. } => "step_failed",
             Self::StepSkipped { .. } => "step_skipped",
+            Self::StepOverdue { .. } => "step_overdue",
             Self::TransitionOccurred { .. } => "transition_occurred",
             Self::ConsensusCalculated { .. } => "consensus_calculated",
             Self::ContextUpdated { .. } => "context_updated",
@@ -111,6 +122,26 @@ impl WorkflowEvent {
         }
     }
 
+    /// Get the step this event concerns, if any
+    ///
+    /// Transitions report their destination step.
+    #[must_use]
+    pub fn step_id(&self) -> Option<&str> {
+        match self {
+            Self::StepActivated { step_id, .. }
+            | Self::StepCompleted { step_id, .. }
+            | Self::StepFailed { step_id, .. }
+            | Self::StepSkipped { step_id, .. }
+            | Self::StepOverdue { step_id, .. }
+            | Self::ConsensusCalculated { step_id, .. } => Some(step_id),
+            Self::TransitionOccurred { to_step, .. } => Some(to_step),
+            Self::WorkflowStarted { .. }
+            | Self::ContextUpdated { .. }
+            | Self::WorkflowCompleted { .. }
+            | Self::WorkflowFailed { .. } => None,
+        }
+    }
+
     /// Get the timestamp when the event occurred
     #[must_use]
     pub fn occurred_at(&self) -> DateTime<Utc> {
@@ -120,6 +151,7 @@ impl WorkflowEvent {
             Self::StepCompleted { completed_at, .. } => *completed_at,
             Self::StepFailed { failed_at, .. } => *failed_at,
             Self::StepSkipped { skipped_at, .. } => *skipped_at,
+            Self::StepOverdue { detected_at, .. } => *detected_at,
             Self::TransitionOccurred { occurred_at, .. } => *occurred_at,
             Self::ConsensusCalculated { calculated_at, .. } => *calculated_at,
             Self::ContextUpdated { updated_at, .. } => *updated_at,
@@ -214,6 +246,7 @@ mod tests {
             WorkflowEvent::StepActivated {
                 step_id: "step1".to_string(),
                 assigned_to: vec![],
+                sla_deadline: None,
                 activated_at: Utc::now(),
             },
             WorkflowEvent::WorkflowCompleted {
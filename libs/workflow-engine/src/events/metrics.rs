@@ -0,0 +1,216 @@
+//! Step timing derived from the event stream
+//!
+//! A step's duration runs from its `StepActivated` event to the next
+//! `StepCompleted` event for the same step in the same stream. Activations
+//! that haven't completed yet, or that ended by failing or being skipped,
+//! have no duration and are left out.
+
+use std::collections::{BTreeMap, HashMap};
+
+use chrono::{DateTime, Duration, Utc};
+use uuid::Uuid;
+
+use super::event_types::{StoredEvent, WorkflowEvent};
+
+/// How long one activation of a step took to complete
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StepDuration {
+    pub stream_id: Uuid,
+    pub step_id: String,
+    pub duration: Duration,
+}
+
+/// Duration percentiles for one step across many activations
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StepDurationStats {
+    pub step_id: String,
+    /// Completed activations measured
+    pub count: usize,
+    pub p50_ms: i64,
+    pub p95_ms: i64,
+}
+
+/// Pair each step activation with its completion.
+///
+/// `events` may mix streams but must be ordered by version within each one.
+pub fn step_durations(events: &[StoredEvent]) -> Vec<StepDuration> {
+    let mut activated: HashMap<(Uuid, &str), DateTime<Utc>> = HashMap::new();
+    let mut durations = Vec::new();
+
+    for stored in events {
+        match &stored.event {
+            WorkflowEvent::StepActivated {
+                step_id,
+                activated_at,
+                ..
+            } => {
+                activated.insert((stored.stream_id, step_id.as_str()), *activated_at);
+            }
+            WorkflowEvent::StepCompleted {
+                step_id,
+                completed_at,
+                ..
+            } => {
+                if let Some(activated_at) = activated.remove(&(stored.stream_id, step_id.as_str()))
+                {
+                    durations.push(StepDuration {
+                        stream_id: stored.stream_id,
+                        step_id: step_id.clone(),
+                        duration: *completed_at - activated_at,
+                    });
+                }
+            }
+            WorkflowEvent::StepFailed { step_id, .. }
+            | WorkflowEvent::StepSkipped { step_id, .. } => {
+                activated.remove(&(stored.stream_id, step_id.as_str()));
+            }
+            _ => {}
+        }
+    }
+
+    durations
+}
+
+/// p50 and p95 of each step's durations, ordered by step ID
+pub fn summarize_step_durations(durations: &[StepDuration]) -> Vec<StepDurationStats> {
+    let mut by_step: BTreeMap<&str, Vec<i64>> = BTreeMap::new();
+    for d in durations {
+        by_step
+            .entry(&d.step_id)
+            .or_default()
+            .push(d.duration.num_milliseconds());
+    }
+
+    by_step
+        .into_iter()
+        .map(|(step_id, mut millis)| {
+            millis.sort_unstable();
+            StepDurationStats {
+                step_id: step_id.to_string(),
+                count: millis.len(),
+                p50_ms: percentile(&millis, 50),
+                p95_ms: percentile(&millis, 95),
+            }
+        })
+        .collect()
+}
+
+/// Nearest-rank percentile of sorted, non-empty `values`
+fn percentile(values: &[i64], pct: usize) -> i64 {
+    let rank = (pct * values.len()).div_ceil(100).max(1);
+    values[rank - 1]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state::StepResult;
+
+    fn at(seconds: i64) -> DateTime<Utc> {
+        DateTime::from_timestamp(1_700_000_000 + seconds, 0).unwrap()
+    }
+
+    fn stored(stream_id: Uuid, version: u64, event: WorkflowEvent) -> StoredEvent {
+        StoredEvent::new(stream_id, "workflow", version, event, serde_json::json!({}))
+    }
+
+    fn activated(stream_id: Uuid, version: u64, step_id: &str, seconds: i64) -> StoredEvent {
+        stored(
+            stream_id,
+            version,
+            WorkflowEvent::StepActivated {
+                step_id: step_id.to_string(),
+                assigned_to: vec![],
+                sla_deadline: None,
+                activated_at: at(seconds),
+            },
+        )
+    }
+
+    fn completed(stream_id: Uuid, version: u64, step_id: &str, seconds: i64) -> StoredEvent {
+        stored(
+            stream_id,
+            version,
+            WorkflowEvent::StepCompleted {
+                step_id: step_id.to_string(),
+                result: StepResult::approved(),
+                completed_at: at(seconds),
+            },
+        )
+    }
+
+    #[test]
+    fn test_duration_from_activation_to_completion() {
+        let task = Uuid::new_v4();
+        let events = [
+            activated(task, 1, "annotate", 0),
+            completed(task, 2, "annotate", 90),
+        ];
+
+        assert_eq!(
+            step_durations(&events),
+            vec![StepDuration {
+                stream_id: task,
+                step_id: "annotate".to_string(),
+                duration: Duration::seconds(90),
+            }]
+        );
+    }
+
+    #[test]
+    fn test_active_and_failed_steps_are_excluded() {
+        let task = Uuid::new_v4();
+        let other = Uuid::new_v4();
+        let events = [
+            activated(task, 1, "annotate", 0),
+            activated(other, 1, "annotate", 5),
+            stored(
+                other,
+                2,
+                WorkflowEvent::StepFailed {
+                    step_id: "annotate".to_string(),
+                    error: "boom".to_string(),
+                    retries: 0,
+                    failed_at: at(6),
+                },
+            ),
+            completed(other, 3, "annotate", 50),
+        ];
+
+        assert!(step_durations(&events).is_empty());
+    }
+
+    #[test]
+    fn test_percentiles_per_step() {
+        let durations: Vec<StepDuration> = (1..=20)
+            .map(|seconds| StepDuration {
+                stream_id: Uuid::new_v4(),
+                step_id: "annotate".to_string(),
+                duration: Duration::seconds(seconds),
+            })
+            .chain(std::iter::once(StepDuration {
+                stream_id: Uuid::new_v4(),
+                step_id: "review".to_string(),
+                duration: Duration::milliseconds(250),
+            }))
+            .collect();
+
+        assert_eq!(
+            summarize_step_durations(&durations),
+            vec![
+                StepDurationStats {
+                    step_id: "annotate".to_string(),
+                    count: 20,
+                    p50_ms: 10_000,
+                    p95_ms: 19_000,
+                },
+                StepDurationStats {
+                    step_id: "review".to_string(),
+                    count: 1,
+                    p50_ms: 250,
+                    p95_ms: 250,
+                },
+            ]
+        );
+    }
+}
@@ -2,11 +2,16 @@
 //!
 //! Persists all workflow state changes as events for audit trail
 //! and state reconstruction. Snapshots every 50 events for replay performance.
+//! Appends also write to a transactional outbox that the worker relays to NATS.
 
 pub mod event_types;
+pub mod metrics;
+pub mod outbox;
 pub mod replay;
 pub mod store;
 
 pub use event_types::*;
+pub use metrics::*;
+pub use outbox::*;
 pub use replay::*;
 pub use store::*;
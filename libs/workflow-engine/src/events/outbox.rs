@@ -0,0 +1,376 @@
+//! Transactional outbox for workflow events
+//!
+//! Event stores write an outbox row in the same transaction as each appended
+//! event. A relay later publishes unsent rows and marks them sent, so an event
+//! is never lost between commit and publish. Delivery is at-least-once: a
+//! crash after publishing but before marking sent republishes the row, so
+//! consumers should deduplicate on `event_id`.
+
+use async_trait::async_trait;
+use chrono::{DateTime, Duration, Utc};
+use thiserror::Error;
+use uuid::Uuid;
+
+use super::event_types::StoredEvent;
+use super::store::{EventStoreError, InMemoryEventStore, PgEventStore};
+
+// =============================================================================
+// Constants
+// =============================================================================
+
+/// Subject prefix for relayed workflow events; the event type is appended
+pub const OUTBOX_SUBJECT_PREFIX: &str = "glyph.workflow.events";
+
+/// Default number of rows relayed per pass
+pub const DEFAULT_RELAY_BATCH: usize = 100;
+
+/// How long a claimed row is hidden from other relays
+pub const DEFAULT_CLAIM_LEASE_SECS: i64 = 60;
+
+/// NATS subject an event is relayed on
+#[must_use]
+pub fn outbox_subject(event: &StoredEvent) -> String {
+    format!("{OUTBOX_SUBJECT_PREFIX}.{}", event.event.event_type())
+}
+
+// =============================================================================
+// Types
+// =============================================================================
+
+/// An outbox row awaiting relay
+#[derive(Debug, Clone)]
+pub struct OutboxMessage {
+    pub outbox_id: Uuid,
+    pub stream_id: Uuid,
+    pub version: u64,
+    pub subject: String,
+    /// The serialized [`StoredEvent`]
+    pub payload: serde_json::Value,
+    pub created_at: DateTime<Utc>,
+    /// Claimed rows are skipped by other relays until then
+    pub claimed_until: DateTime<Utc>,
+    pub sent_at: Option<DateTime<Utc>>,
+}
+
+impl OutboxMessage {
+    /// Build the outbox row for a stored event
+    pub fn for_event(event: &StoredEvent) -> Result<Self, EventStoreError> {
+        let now = Utc::now();
+        Ok(Self {
+            outbox_id: Uuid::new_v4(),
+            stream_id: event.stream_id,
+            version: event.version,
+            subject: outbox_subject(event),
+            payload: serde_json::to_value(event)?,
+            created_at: now,
+            claimed_until: now,
+            sent_at: None,
+        })
+    }
+}
+
+/// Outbox relay errors
+#[derive(Debug, Error)]
+pub enum OutboxError {
+    /// Reading or updating the outbox failed
+    #[error(transparent)]
+    Store(#[from] EventStoreError),
+
+    /// The message broker rejected a publish
+    #[error("Publish error: {0}")]
+    Publish(String),
+}
+
+// =============================================================================
+// Traits
+// =============================================================================
+
+/// Storage side of the outbox
+#[async_trait]
+pub trait OutboxStore: Send + Sync {
+    /// Claim up to `limit` unsent rows, oldest first.
+    ///
+    /// Claimed rows are hidden from other relays for `lease`; rows that are
+    /// never marked sent become claimable again once it expires.
+    async fn claim_unsent(
+        &self,
+        limit: usize,
+        lease: Duration,
+    ) -> Result<Vec<OutboxMessage>, EventStoreError>;
+
+    /// Mark rows as sent
+    async fn mark_sent(&self, outbox_ids: &[Uuid]) -> Result<(), EventStoreError>;
+}
+
+/// Broker side of the outbox
+#[async_trait]
+pub trait OutboxPublisher: Send + Sync {
+    /// Publish a payload and wait until the broker has it
+    async fn publish(&self, subject: &str, payload: Vec<u8>) -> Result<(), OutboxError>;
+}
+
+// =============================================================================
+// Relay
+// =============================================================================
+
+/// Relay one batch of unsent rows, returning how many were published.
+///
+/// Publishing stops at the first failure; rows published before it are still
+/// marked sent and the rest are retried once their lease expires.
+pub async fn relay_once(
+    store: &dyn OutboxStore,
+    publisher: &dyn OutboxPublisher,
+    limit: usize,
+) -> Result<usize, OutboxError> {
+    let lease = Duration::seconds(DEFAULT_CLAIM_LEASE_SECS);
+    let messages = store.claim_unsent(limit, lease).await?;
+
+    let mut sent = Vec::with_capacity(messages.len());
+    let mut failure = None;
+    for message in &messages {
+        let payload = serde_json::to_vec(&message.payload).map_err(EventStoreError::from)?;
+        if let Err(e) = publisher.publish(&message.subject, payload).await {
+            failure = Some(e);
+            break;
+        }
+        sent.push(message.outbox_id);
+    }
+
+    if !sent.is_empty() {
+        store.mark_sent(&sent).await?;
+    }
+
+    match failure {
+        Some(e) => Err(e),
+        None => Ok(sent.len()),
+    }
+}
+
+// =============================================================================
+// Store Implementations
+// =============================================================================
+
+#[async_trait]
+impl OutboxStore for PgEventStore {
+    async fn claim_unsent(
+        &self,
+        limit: usize,
+        lease: Duration,
+    ) -> Result<Vec<OutboxMessage>, EventStoreError> {
+        let rows: Vec<OutboxRow> = sqlx::query_as(
+            r#"
+            UPDATE event_outbox
+            SET claimed_until = NOW() + $2
+            WHERE outbox_id IN (
+                SELECT outbox_id FROM event_outbox
+                WHERE sent_at IS NULL AND claimed_until <= NOW()
+                ORDER BY created_at
+                LIMIT $1
+                FOR UPDATE SKIP LOCKED
+            )
+            RETURNING outbox_id, stream_id, version, subject, payload,
+                      created_at, claimed_until, sent_at
+            "#,
+        )
+        .bind(i64::try_from(limit).unwrap_or(i64::MAX))
+        .bind(lease)
+        .fetch_all(self.pool())
+        .await?;
+
+        let mut messages: Vec<OutboxMessage> = rows.into_iter().map(Into::into).collect();
+        messages.sort_by_key(|m| m.created_at);
+        Ok(messages)
+    }
+
+    async fn mark_sent(&self, outbox_ids: &[Uuid]) -> Result<(), EventStoreError> {
+        sqlx::query("UPDATE event_outbox SET sent_at = NOW() WHERE outbox_id = ANY($1)")
+            .bind(outbox_ids)
+            .execute(self.pool())
+            .await?;
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl OutboxStore for InMemoryEventStore {
+    async fn claim_unsent(
+        &self,
+        limit: usize,
+        lease: Duration,
+    ) -> Result<Vec<OutboxMessage>, EventStoreError> {
+        let now = Utc::now();
+        let mut outbox = self.outbox().write().await;
+        Ok(outbox
+            .iter_mut()
+            .filter(|m| m.sent_at.is_none() && m.claimed_until <= now)
+            .take(limit)
+            .map(|m| {
+                m.claimed_until = now + lease;
+                m.clone()
+            })
+            .collect())
+    }
+
+    async fn mark_sent(&self, outbox_ids: &[Uuid]) -> Result<(), EventStoreError> {
+        let now = Utc::now();
+        let mut outbox = self.outbox().write().await;
+        for message in outbox.iter_mut() {
+            if outbox_ids.contains(&message.outbox_id) {
+                message.sent_at = Some(now);
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Insert an outbox row inside an event append transaction
+pub(crate) async fn insert_outbox(
+    tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+    message: &OutboxMessage,
+) -> Result<(), EventStoreError> {
+    sqlx::query(
+        r#"
+        INSERT INTO event_outbox (outbox_id, stream_id, version, subject, payload)
+        VALUES ($1, $2, $3, $4, $5)
+        "#,
+    )
+    .bind(message.outbox_id)
+    .bind(message.stream_id)
+    .bind(message.version as i64)
+    .bind(&message.subject)
+    .bind(&message.payload)
+    .execute(&mut **tx)
+    .await?;
+    Ok(())
+}
+
+// =============================================================================
+// Database Row Types
+// =============================================================================
+
+#[derive(Debug, sqlx::FromRow)]
+struct OutboxRow {
+    outbox_id: Uuid,
+    stream_id: Uuid,
+    version: i64,
+    subject: String,
+    payload: serde_json::Value,
+    created_at: DateTime<Utc>,
+    claimed_until: DateTime<Utc>,
+    sent_at: Option<DateTime<Utc>>,
+}
+
+impl From<OutboxRow> for OutboxMessage {
+    fn from(row: OutboxRow) -> Self {
+        Self {
+            outbox_id: row.outbox_id,
+            stream_id: row.stream_id,
+            version: row.version as u64,
+            subject: row.subject,
+            payload: row.payload,
+            created_at: row.created_at,
+            claimed_until: row.claimed_until,
+            sent_at: row.sent_at,
+        }
+    }
+}
+
+// =============================================================================
+// Tests
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Mutex;
+
+    use super::*;
+    use crate::events::{EventStore, WorkflowEvent};
+
+    #[derive(Default)]
+    struct CapturingPublisher {
+        published: Mutex<Vec<(String, serde_json::Value)>>,
+    }
+
+    #[async_trait]
+    impl OutboxPublisher for CapturingPublisher {
+        async fn publish(&self, subject: &str, payload: Vec<u8>) -> Result<(), OutboxError> {
+            let value = serde_json::from_slice(&payload).map_err(EventStoreError::from)?;
+            self.published
+                .lock()
+                .unwrap()
+                .push((subject.to_string(), value));
+            Ok(())
+        }
+    }
+
+    struct FailingPublisher;
+
+    #[async_trait]
+    impl OutboxPublisher for FailingPublisher {
+        async fn publish(&self, _subject: &str, _payload: Vec<u8>) -> Result<(), OutboxError> {
+            Err(OutboxError::Publish("broker unavailable".to_string()))
+        }
+    }
+
+    async fn append_started(store: &InMemoryEventStore) -> Uuid {
+        let stream_id = Uuid::new_v4();
+        store
+            .append(
+                stream_id,
+                "workflow",
+                None,
+                vec![WorkflowEvent::WorkflowStarted {
+                    workflow_id: stream_id,
+                    config_version: "1.0".to_string(),
+                    started_at: Utc::now(),
+                }],
+                serde_json::json!({}),
+            )
+            .await
+            .unwrap();
+        stream_id
+    }
+
+    #[tokio::test]
+    async fn test_unrelayed_row_is_published_and_marked_sent() {
+        let store = InMemoryEventStore::new();
+        let stream_id = append_started(&store).await;
+        let publisher = CapturingPublisher::default();
+
+        let relayed = relay_once(&store, &publisher, DEFAULT_RELAY_BATCH)
+            .await
+            .unwrap();
+        assert_eq!(relayed, 1);
+
+        let published = publisher.published.lock().unwrap().clone();
+        assert_eq!(published.len(), 1);
+        assert_eq!(published[0].0, "glyph.workflow.events.workflow_started");
+        let event: StoredEvent = serde_json::from_value(published[0].1.clone()).unwrap();
+        assert_eq!(event.stream_id, stream_id);
+        assert_eq!(event.version, 1);
+
+        let outbox = store.outbox().read().await;
+        assert!(outbox.iter().all(|m| m.sent_at.is_some()));
+        drop(outbox);
+
+        // Sent rows are not relayed again
+        let relayed = relay_once(&store, &publisher, DEFAULT_RELAY_BATCH)
+            .await
+            .unwrap();
+        assert_eq!(relayed, 0);
+        assert_eq!(publisher.published.lock().unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_failed_publish_leaves_row_unsent() {
+        let store = InMemoryEventStore::new();
+        append_started(&store).await;
+
+        let result = relay_once(&store, &FailingPublisher, DEFAULT_RELAY_BATCH).await;
+        assert!(matches!(result, Err(OutboxError::Publish(_))));
+
+        let outbox = store.outbox().read().await;
+        assert_eq!(outbox.len(), 1);
+        assert!(outbox[0].sent_at.is_none());
+    }
+}
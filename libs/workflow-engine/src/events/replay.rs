@@ -5,13 +5,14 @@
 
 use std::sync::Arc;
 
-use chrono::Utc;
+use chrono::{DateTime, Utc};
 use thiserror::Error;
+use tokio::sync::Mutex;
 use uuid::Uuid;
 
-use super::event_types::WorkflowEvent;
+use super::event_types::{StoredEvent, WorkflowEvent};
 use super::store::{EventStore, EventStoreError};
-use crate::state::{StepResult, WorkflowStateManager};
+use crate::state::{StepResult, StepState, WorkflowStateManager};
 
 // =============================================================================
 // Errors
@@ -37,6 +38,42 @@ pub enum ReplayError {
     StateTransitionFailed(String),
 }
 
+// =============================================================================
+// Replay Verification
+// =============================================================================
+
+/// One field that differs between the two replay paths
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReplayMismatch {
+    /// What differs, e.g. `current_step` or `step:review`
+    pub field: String,
+    /// Value rebuilt from the latest snapshot plus the events after it
+    pub from_snapshot: serde_json::Value,
+    /// Value rebuilt from the full event log
+    pub from_events: serde_json::Value,
+}
+
+/// Result of checking a stream's snapshot against its full event log
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReplayVerification {
+    /// Stream that was checked
+    pub stream_id: Uuid,
+    /// Version of the snapshot checked, if the stream has one
+    pub snapshot_version: Option<u64>,
+    /// Number of events replayed for the full-log path
+    pub events_replayed: usize,
+    /// Differences found; empty when both paths agree
+    pub mismatches: Vec<ReplayMismatch>,
+}
+
+impl ReplayVerification {
+    /// Check if both replay paths produced the same state
+    #[must_use]
+    pub fn is_consistent(&self) -> bool {
+        self.mismatches.is_empty()
+    }
+}
+
 // =============================================================================
 // State Rebuilder
 // =============================================================================
@@ -88,6 +125,27 @@ impl StateRebuilder {
         Ok(state)
     }
 
+    /// Replay an already-loaded stream from its first event
+    ///
+    /// Step IDs are taken from the stream itself in activation order, so no
+    /// workflow config is needed. Returns `None` for an empty stream.
+    pub fn replay_events(
+        &self,
+        events: &[StoredEvent],
+    ) -> Result<Option<WorkflowStateManager>, ReplayError> {
+        let step_ids = activated_steps(events);
+        let Some(entry_step) = step_ids.first().copied() else {
+            return Ok(None);
+        };
+
+        let mut state = WorkflowStateManager::new(entry_step, &step_ids);
+        for stored_event in events {
+            self.apply_event(&mut state, &stored_event.event)?;
+        }
+
+        Ok(Some(state))
+    }
+
     /// Apply a single event to the state
     fn apply_event(
         &self,
@@ -136,6 +194,11 @@ impl StateRebuilder {
                 Ok(())
             }
 
+            WorkflowEvent::StepOverdue { .. } => {
+                // SLA breaches are informational - the step stays active
+                Ok(())
+            }
+
             WorkflowEvent::TransitionOccurred {
                 to_step,
                 condition_met,
@@ -170,6 +233,41 @@ impl StateRebuilder {
         }
     }
 
+    /// Check that snapshot+tail replay agrees with a full replay of the stream.
+    ///
+    /// Rebuilds the stream the way production does (latest snapshot, then the
+    /// events after it) and again from the first event, then compares current
+    /// step, context, and every step's state. Timestamps are ignored because
+    /// replay stamps states as it applies them. A stream without a snapshot
+    /// is trivially consistent.
+    pub async fn verify(&self, stream_id: Uuid) -> Result<ReplayVerification, ReplayError> {
+        let events = self.event_store.load_events(stream_id, 0).await?;
+        let snapshot_version = self
+            .event_store
+            .get_latest_snapshot(stream_id)
+            .await?
+            .map(|snap| snap.version);
+
+        let mut report = ReplayVerification {
+            stream_id,
+            snapshot_version,
+            events_replayed: events.len(),
+            mismatches: Vec::new(),
+        };
+        if snapshot_version.is_none() {
+            return Ok(report);
+        }
+
+        let full = self.replay_events(&events)?.ok_or_else(|| {
+            ReplayError::InvalidEventSequence("snapshot exists but stream has no events".into())
+        })?;
+        let step_ids = activated_steps(&events);
+        let from_snapshot = self.rebuild_state(stream_id, &step_ids).await?;
+
+        report.mismatches = compare_states(&from_snapshot, &full);
+        Ok(report)
+    }
+
     /// Create a snapshot of current state if needed
     pub async fn maybe_snapshot(
         &self,
@@ -179,8 +277,8 @@ impl StateRebuilder {
     ) -> Result<bool, ReplayError> {
         let version = state.version();
 
-        // Check if we should snapshot (every 50 events)
-        if version > 0 && version % 50 == 0 {
+        // Check if the store's policy wants a snapshot at this version
+        if self.event_store.snapshot_policy().should_snapshot(version) {
             let snapshot = state.to_snapshot();
             self.event_store
                 .save_snapshot(stream_id, stream_type, &snapshot)
@@ -192,6 +290,75 @@ impl StateRebuilder {
     }
 }
 
+/// Differences between two rebuilt states, ignoring timestamps
+fn compare_states(
+    from_snapshot: &WorkflowStateManager,
+    from_events: &WorkflowStateManager,
+) -> Vec<ReplayMismatch> {
+    let mut mismatches = Vec::new();
+    let mut check = |field: String, a: serde_json::Value, b: serde_json::Value| {
+        if a != b {
+            mismatches.push(ReplayMismatch {
+                field,
+                from_snapshot: a,
+                from_events: b,
+            });
+        }
+    };
+
+    check(
+        "current_step".to_string(),
+        serde_json::json!(from_snapshot.current_step()),
+        serde_json::json!(from_events.current_step()),
+    );
+    check(
+        "context".to_string(),
+        from_snapshot.get_context().clone(),
+        from_events.get_context().clone(),
+    );
+
+    // A step the full replay never saw is pending on both sides
+    let mut step_ids: Vec<&String> = from_snapshot
+        .all_step_states()
+        .keys()
+        .chain(from_events.all_step_states().keys())
+        .collect();
+    step_ids.sort();
+    step_ids.dedup();
+    for step_id in step_ids {
+        check(
+            format!("step:{step_id}"),
+            step_fingerprint(from_snapshot.all_step_states().get(step_id)),
+            step_fingerprint(from_events.all_step_states().get(step_id)),
+        );
+    }
+
+    mismatches
+}
+
+/// A step state with its timestamps removed
+fn step_fingerprint(state: Option<&StepState>) -> serde_json::Value {
+    let mut value = serde_json::to_value(state.unwrap_or(&StepState::Pending))
+        .unwrap_or(serde_json::Value::Null);
+    if let serde_json::Value::Object(map) = &mut value {
+        map.retain(|key, _| !key.ends_with("_at") && key != "last_activity");
+    }
+    value
+}
+
+/// Step IDs in activation order, entry step first
+fn activated_steps(events: &[StoredEvent]) -> Vec<&str> {
+    let mut ids: Vec<&str> = Vec::new();
+    for stored in events {
+        if let WorkflowEvent::StepActivated { step_id, .. } = &stored.event {
+            if !ids.contains(&step_id.as_str()) {
+                ids.push(step_id);
+            }
+        }
+    }
+    ids
+}
+
 // =============================================================================
 // Event Emitter Helper
 // =============================================================================
@@ -201,6 +368,9 @@ pub struct EventEmitter {
     event_store: Arc<dyn EventStore>,
     stream_id: Uuid,
     stream_type: String,
+    metadata: serde_json::Value,
+    /// Events held for an atomic `commit` at the expected stream version
+    buffer: Option<(u64, Mutex<Vec<WorkflowEvent>>)>,
 }
 
 impl EventEmitter {
@@ -215,12 +385,66 @@ impl EventEmitter {
             event_store,
             stream_id,
             stream_type: stream_type.into(),
+            metadata: serde_json::json!({}),
+            buffer: None,
         }
     }
 
+    /// Hold events until [`commit`](Self::commit) instead of appending each one.
+    ///
+    /// The commit appends everything in one batch and fails with
+    /// [`EventStoreError::ConcurrencyConflict`] if the stream has moved past
+    /// `expected_version` in the meantime. Buffered events share the
+    /// emitter's metadata.
+    #[must_use]
+    pub fn buffered(mut self, expected_version: u64) -> Self {
+        self.buffer = Some((expected_version, Mutex::new(Vec::new())));
+        self
+    }
+
+    /// Append buffered events, returning the new stream version.
+    ///
+    /// A no-op for unbuffered emitters or an empty buffer.
+    pub async fn commit(&self) -> Result<Option<u64>, EventStoreError> {
+        let Some((expected_version, buffer)) = &self.buffer else {
+            return Ok(None);
+        };
+        let events = std::mem::take(&mut *buffer.lock().await);
+        if events.is_empty() {
+            return Ok(None);
+        }
+
+        let version = self
+            .event_store
+            .append(
+                self.stream_id,
+                &self.stream_type,
+                Some(*expected_version),
+                events,
+                self.metadata.clone(),
+            )
+            .await?;
+        Ok(Some(version))
+    }
+
+    /// Queue events on a buffered emitter, returning the version they will occupy
+    async fn push_buffered(&self, events: Vec<WorkflowEvent>) -> Option<u64> {
+        let (expected_version, buffer) = self.buffer.as_ref()?;
+        let mut buffer = buffer.lock().await;
+        buffer.extend(events);
+        Some(expected_version + buffer.len() as u64)
+    }
+
+    /// Record `user_id` as the actor on every event this emitter writes
+    #[must_use]
+    pub fn with_actor(mut self, user_id: Uuid) -> Self {
+        self.metadata = serde_json::json!({ "user_id": user_id });
+        self
+    }
+
     /// Emit a single event
     pub async fn emit(&self, event: WorkflowEvent) -> Result<u64, EventStoreError> {
-        self.emit_with_metadata(event, serde_json::json!({})).await
+        self.emit_with_metadata(event, self.metadata.clone()).await
     }
 
     /// Emit a single event with metadata
@@ -229,6 +453,9 @@ impl EventEmitter {
         event: WorkflowEvent,
         metadata: serde_json::Value,
     ) -> Result<u64, EventStoreError> {
+        if self.buffer.is_some() {
+            return Ok(self.push_buffered(vec![event]).await.unwrap_or_default());
+        }
         self.event_store
             .append(
                 self.stream_id,
@@ -242,13 +469,16 @@ impl EventEmitter {
 
     /// Emit multiple events atomically
     pub async fn emit_batch(&self, events: Vec<WorkflowEvent>) -> Result<u64, EventStoreError> {
+        if self.buffer.is_some() {
+            return Ok(self.push_buffered(events).await.unwrap_or_default());
+        }
         self.event_store
             .append(
                 self.stream_id,
                 &self.stream_type,
                 None,
                 events,
-                serde_json::json!({}),
+                self.metadata.clone(),
             )
             .await
     }
@@ -276,10 +506,12 @@ impl EventEmitter {
         &self,
         step_id: impl Into<String>,
         assigned_to: Vec<Uuid>,
+        sla_deadline: Option<DateTime<Utc>>,
     ) -> Result<u64, EventStoreError> {
         self.emit(WorkflowEvent::StepActivated {
             step_id: step_id.into(),
             assigned_to,
+            sla_deadline,
             activated_at: Utc::now(),
         })
         .await
@@ -315,6 +547,34 @@ impl EventEmitter {
         .await
     }
 
+    /// Emit step skipped event
+    pub async fn step_skipped(
+        &self,
+        step_id: impl Into<String>,
+        reason: impl Into<String>,
+    ) -> Result<u64, EventStoreError> {
+        self.emit(WorkflowEvent::StepSkipped {
+            step_id: step_id.into(),
+            reason: reason.into(),
+            skipped_at: Utc::now(),
+        })
+        .await
+    }
+
+    /// Emit step overdue event
+    pub async fn step_overdue(
+        &self,
+        step_id: impl Into<String>,
+        deadline: DateTime<Utc>,
+    ) -> Result<u64, EventStoreError> {
+        self.emit(WorkflowEvent::StepOverdue {
+            step_id: step_id.into(),
+            deadline,
+            detected_at: Utc::now(),
+        })
+        .await
+    }
+
     /// Emit transition occurred event
     pub async fn transition_occurred(
         &self,
@@ -371,4 +631,133 @@ mod tests {
         let err = ReplayError::InvalidEventSequence("missing start event".to_string());
         assert!(err.to_string().contains("missing start event"));
     }
+
+    fn stored(version: u64, event: WorkflowEvent) -> StoredEvent {
+        StoredEvent::new(
+            Uuid::nil(),
+            "workflow",
+            version,
+            event,
+            serde_json::json!({}),
+        )
+    }
+
+    fn activated(version: u64, step_id: &str) -> StoredEvent {
+        stored(
+            version,
+            WorkflowEvent::StepActivated {
+                step_id: step_id.to_string(),
+                assigned_to: vec![],
+                sla_deadline: None,
+                activated_at: Utc::now(),
+            },
+        )
+    }
+
+    #[test]
+    fn test_activated_steps_dedupes_in_order() {
+        let events = vec![
+            activated(1, "annotate"),
+            activated(2, "review"),
+            activated(3, "annotate"),
+        ];
+        assert_eq!(activated_steps(&events), vec!["annotate", "review"]);
+    }
+
+    async fn seeded_store() -> (Arc<crate::events::InMemoryEventStore>, Uuid) {
+        let store = Arc::new(crate::events::InMemoryEventStore::new());
+        let stream_id = Uuid::new_v4();
+        let emitter = EventEmitter::new(store.clone(), stream_id, "workflow");
+        emitter
+            .step_activated("annotate", vec![], None)
+            .await
+            .unwrap();
+        emitter
+            .step_completed("annotate", StepResult::approved())
+            .await
+            .unwrap();
+        emitter
+            .transition_occurred("annotate", "review", None)
+            .await
+            .unwrap();
+        emitter
+            .step_activated("review", vec![], None)
+            .await
+            .unwrap();
+        (store, stream_id)
+    }
+
+    #[tokio::test]
+    async fn test_verify_accepts_faithful_snapshot() {
+        let (store, stream_id) = seeded_store().await;
+        let rebuilder = StateRebuilder::new(store.clone());
+
+        // No snapshot yet: nothing to compare
+        assert!(rebuilder.verify(stream_id).await.unwrap().is_consistent());
+
+        let events = store.load_events(stream_id, 0).await.unwrap();
+        let state = rebuilder.replay_events(&events).unwrap().unwrap();
+        store
+            .save_snapshot(stream_id, "workflow", &state.to_snapshot())
+            .await
+            .unwrap();
+
+        let report = rebuilder.verify(stream_id).await.unwrap();
+        assert_eq!(report.snapshot_version, Some(4));
+        assert!(report.is_consistent(), "{:?}", report.mismatches);
+    }
+
+    #[tokio::test]
+    async fn test_verify_detects_corrupt_snapshot() {
+        let (store, stream_id) = seeded_store().await;
+        let rebuilder = StateRebuilder::new(store.clone());
+
+        // Snapshot claims the workflow is still on "annotate"
+        let mut corrupt = WorkflowStateManager::new("annotate", &["annotate", "review"]);
+        corrupt.activate_step("annotate", vec![]).unwrap();
+        let mut snapshot = corrupt.to_snapshot();
+        snapshot.version = 4;
+        store
+            .save_snapshot(stream_id, "workflow", &snapshot)
+            .await
+            .unwrap();
+
+        let report = rebuilder.verify(stream_id).await.unwrap();
+        assert!(!report.is_consistent());
+        let fields: Vec<&str> = report.mismatches.iter().map(|m| m.field.as_str()).collect();
+        assert!(fields.contains(&"current_step"));
+        assert!(fields.contains(&"step:annotate"));
+        assert!(fields.contains(&"step:review"));
+    }
+
+    #[test]
+    fn test_replay_events_without_config() {
+        let rebuilder = StateRebuilder::new(Arc::new(crate::events::InMemoryEventStore::new()));
+        assert!(rebuilder.replay_events(&[]).unwrap().is_none());
+
+        let events = vec![
+            activated(1, "annotate"),
+            stored(
+                2,
+                WorkflowEvent::StepCompleted {
+                    step_id: "annotate".to_string(),
+                    result: StepResult::approved(),
+                    completed_at: Utc::now(),
+                },
+            ),
+            stored(
+                3,
+                WorkflowEvent::TransitionOccurred {
+                    from_step: "annotate".to_string(),
+                    to_step: "review".to_string(),
+                    condition_met: None,
+                    occurred_at: Utc::now(),
+                },
+            ),
+            activated(4, "review"),
+        ];
+        let state = rebuilder.replay_events(&events).unwrap().unwrap();
+        assert_eq!(state.current_step(), Some("review"));
+        assert!(state.all_step_states()["annotate"].is_terminal());
+    }
 }
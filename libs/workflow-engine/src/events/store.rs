@@ -1,7 +1,7 @@
 //! Event store for persisting workflow events
 //!
 //! Provides append-only storage with optimistic concurrency control
-//! and automatic snapshotting every 50 events.
+//! and automatic snapshotting (every 50 events by default).
 
 use std::collections::HashMap;
 use std::sync::Arc;
@@ -14,6 +14,7 @@ use tokio::sync::RwLock;
 use uuid::Uuid;
 
 use super::event_types::{StoredEvent, WorkflowEvent};
+use super::outbox::{insert_outbox, OutboxMessage};
 use crate::state::WorkflowSnapshot;
 
 // =============================================================================
@@ -23,6 +24,36 @@ use crate::state::WorkflowSnapshot;
 /// Snapshot interval per RESEARCH.md: snapshot every 50 events
 pub const SNAPSHOT_INTERVAL: u64 = 50;
 
+// =============================================================================
+// Snapshot Policy
+// =============================================================================
+
+/// When a stream should be snapshotted
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SnapshotPolicy {
+    /// Snapshot every `n` events; `Every(0)` behaves like `Never`
+    Every(u64),
+    /// Never snapshot, so every rebuild replays the full stream (useful for debugging)
+    Never,
+}
+
+impl SnapshotPolicy {
+    /// Check if a snapshot is due at the given stream version
+    #[must_use]
+    pub const fn should_snapshot(self, version: u64) -> bool {
+        match self {
+            Self::Every(interval) if interval > 0 => version > 0 && version % interval == 0,
+            Self::Every(_) | Self::Never => false,
+        }
+    }
+}
+
+impl Default for SnapshotPolicy {
+    fn default() -> Self {
+        Self::Every(SNAPSHOT_INTERVAL)
+    }
+}
+
 // =============================================================================
 // Errors
 // =============================================================================
@@ -102,6 +133,11 @@ pub trait EventStore: Send + Sync {
 
     /// Get the current version of a stream
     async fn get_stream_version(&self, stream_id: Uuid) -> Result<Option<u64>, EventStoreError>;
+
+    /// How often streams in this store are snapshotted
+    fn snapshot_policy(&self) -> SnapshotPolicy {
+        SnapshotPolicy::default()
+    }
 }
 
 // =============================================================================
@@ -113,18 +149,32 @@ pub struct PgEventStore {
     pool: PgPool,
     /// Cache of stream versions for optimistic concurrency
     version_cache: Arc<RwLock<HashMap<Uuid, u64>>>,
+    /// When streams are snapshotted
+    snapshot_policy: SnapshotPolicy,
 }
 
 impl PgEventStore {
-    /// Create a new PostgreSQL event store
+    /// Create a new PostgreSQL event store with the default snapshot policy
     #[must_use]
     pub fn new(pool: PgPool) -> Self {
+        Self::new_with_config(pool, SnapshotPolicy::default())
+    }
+
+    /// Create a new PostgreSQL event store with a custom snapshot policy
+    #[must_use]
+    pub fn new_with_config(pool: PgPool, snapshot_policy: SnapshotPolicy) -> Self {
         Self {
             pool,
             version_cache: Arc::new(RwLock::new(HashMap::new())),
+            snapshot_policy,
         }
     }
 
+    /// Connection pool backing this store
+    pub(crate) fn pool(&self) -> &PgPool {
+        &self.pool
+    }
+
     /// Get cached version or fetch from database
     async fn get_or_fetch_version(&self, stream_id: Uuid) -> Result<u64, EventStoreError> {
         // Check cache first
@@ -153,11 +203,6 @@ impl PgEventStore {
 
         Ok(version)
     }
-
-    /// Check if a snapshot should be created
-    fn should_snapshot(version: u64) -> bool {
-        version > 0 && version % SNAPSHOT_INTERVAL == 0
-    }
 }
 
 #[async_trait]
@@ -174,7 +219,21 @@ impl EventStore for PgEventStore {
             return self.get_or_fetch_version(stream_id).await;
         }
 
-        let current_version = self.get_or_fetch_version(stream_id).await?;
+        let mut tx = self.pool.begin().await?;
+
+        // Serialize appends per stream so the version read below stays current
+        // until commit; the cache can't be trusted across processes here.
+        sqlx::query("SELECT pg_advisory_xact_lock(hashtextextended($1::uuid::text, 0))")
+            .bind(stream_id)
+            .execute(&mut *tx)
+            .await?;
+        let (current_version,): (i64,) = sqlx::query_as(
+            "SELECT COALESCE(MAX(version), 0) FROM workflow_events WHERE stream_id = $1",
+        )
+        .bind(stream_id)
+        .fetch_one(&mut *tx)
+        .await?;
+        let current_version = current_version as u64;
 
         // Check optimistic concurrency
         if let Some(expected) = expected_version {
@@ -186,16 +245,15 @@ impl EventStore for PgEventStore {
             }
         }
 
-        let mut tx = self.pool.begin().await?;
         let mut new_version = current_version;
 
-        for event in &events {
+        for event in events {
             new_version += 1;
 
-            let event_id = Uuid::new_v4();
-            let event_type = event.event_type();
-            let event_data = serde_json::to_value(event)?;
-            let occurred_at = event.occurred_at();
+            let stored =
+                StoredEvent::new(stream_id, stream_type, new_version, event, metadata.clone());
+            let event_type = stored.event.event_type();
+            let event_data = serde_json::to_value(&stored.event)?;
 
             sqlx::query(
                 r#"
@@ -204,16 +262,19 @@ impl EventStore for PgEventStore {
                 VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
                 "#,
             )
-            .bind(event_id)
+            .bind(stored.event_id)
             .bind(stream_id)
             .bind(stream_type)
             .bind(new_version as i64)
             .bind(event_type)
             .bind(&event_data)
             .bind(&metadata)
-            .bind(occurred_at)
+            .bind(stored.occurred_at)
             .execute(&mut *tx)
             .await?;
+
+            // Same transaction, so the event is published iff it committed
+            insert_outbox(&mut tx, &OutboxMessage::for_event(&stored)?).await?;
         }
 
         tx.commit().await?;
@@ -310,6 +371,261 @@ impl EventStore for PgEventStore {
 
         Ok(row.and_then(|(v,)| if v > 0 { Some(v as u64) } else { None }))
     }
+
+    fn snapshot_policy(&self) -> SnapshotPolicy {
+        self.snapshot_policy
+    }
+}
+
+/// A step activation whose SLA deadline has passed
+#[derive(Debug, Clone)]
+pub struct OverdueCandidate {
+    /// Workflow stream (task) the step belongs to
+    pub stream_id: Uuid,
+    /// Step that breached its deadline
+    pub step_id: String,
+    /// Deadline recorded at activation
+    pub deadline: DateTime<Utc>,
+}
+
+impl PgEventStore {
+    /// Find step activations past their SLA deadline.
+    ///
+    /// Excludes steps that have since moved on (completed, failed, skipped,
+    /// or re-activated), workflows that have ended, and steps already
+    /// flagged overdue for this activation.
+    pub async fn list_overdue_candidates(
+        &self,
+        now: DateTime<Utc>,
+    ) -> Result<Vec<OverdueCandidate>, EventStoreError> {
+        let rows: Vec<(Uuid, String, DateTime<Utc>)> = sqlx::query_as(
+            r#"
+            SELECT a.stream_id,
+                   a.event_data->>'step_id',
+                   (a.event_data->>'sla_deadline')::timestamptz
+            FROM workflow_events a
+            WHERE a.event_type = 'step_activated'
+              AND a.event_data ? 'sla_deadline'
+              AND (a.event_data->>'sla_deadline')::timestamptz < $1
+              AND NOT EXISTS (
+                  SELECT 1 FROM workflow_events l
+                  WHERE l.stream_id = a.stream_id
+                    AND l.version > a.version
+                    AND (
+                        l.event_type IN ('workflow_completed', 'workflow_failed')
+                        OR (l.event_type IN ('step_activated', 'step_completed',
+                                             'step_failed', 'step_skipped', 'step_overdue')
+                            AND l.event_data->>'step_id' = a.event_data->>'step_id')
+                    )
+              )
+            "#,
+        )
+        .bind(now)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|(stream_id, step_id, deadline)| OverdueCandidate {
+                stream_id,
+                step_id,
+                deadline,
+            })
+            .collect())
+    }
+}
+
+/// Step events of one page of task streams started on a workflow
+#[derive(Debug, Clone)]
+pub struct WorkflowStepEventPage {
+    /// Events ordered by version within each stream
+    pub events: Vec<StoredEvent>,
+    /// Stream to continue after, or `None` once every stream has been read
+    pub next_after: Option<Uuid>,
+}
+
+impl PgEventStore {
+    /// Step activation, completion, failure and skip events of up to
+    /// `stream_limit` task streams started on `workflow_id`, taking streams
+    /// in ID order after `after`. With `org_id`, only tasks of that
+    /// organization's projects are included.
+    ///
+    /// Each page holds whole streams, so activations and completions are
+    /// never split across pages.
+    pub async fn load_step_events_for_workflow(
+        &self,
+        workflow_id: Uuid,
+        org_id: Option<Uuid>,
+        after: Option<Uuid>,
+        stream_limit: i64,
+    ) -> Result<WorkflowStepEventPage, EventStoreError> {
+        let stream_ids: Vec<Uuid> = sqlx::query_scalar(
+            r#"
+            SELECT stream_id FROM workflow_events
+            WHERE event_type = 'workflow_started'
+              AND event_data->>'workflow_id' = $1::text
+              AND ($2::uuid IS NULL OR stream_id > $2)
+              AND ($4::uuid IS NULL OR stream_id IN (
+                  SELECT t.task_id
+                  FROM tasks t
+                  JOIN projects p ON p.project_id = t.project_id
+                  WHERE p.org_id = $4
+              ))
+            ORDER BY stream_id
+            LIMIT $3
+            "#,
+        )
+        .bind(workflow_id)
+        .bind(after)
+        .bind(stream_limit)
+        .bind(org_id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        let rows: Vec<EventRow> = sqlx::query_as(
+            r#"
+            SELECT event_id, stream_id, stream_type, version, event_type,
+                   event_data, metadata, occurred_at
+            FROM workflow_events
+            WHERE stream_id = ANY($1)
+              AND event_type IN ('step_activated', 'step_completed', 'step_failed', 'step_skipped')
+            ORDER BY stream_id, version
+            "#,
+        )
+        .bind(&stream_ids)
+        .fetch_all(&self.pool)
+        .await?;
+
+        let events = rows
+            .into_iter()
+            .map(StoredEvent::try_from)
+            .collect::<Result<Vec<_>, _>>()?;
+        let next_after = if stream_ids.len() as i64 == stream_limit {
+            stream_ids.last().copied()
+        } else {
+            None
+        };
+
+        Ok(WorkflowStepEventPage { events, next_after })
+    }
+}
+
+// =============================================================================
+// In-Memory Event Store
+// =============================================================================
+
+/// Simple in-memory event store for development/testing
+#[derive(Default)]
+pub struct InMemoryEventStore {
+    events: RwLock<HashMap<Uuid, Vec<StoredEvent>>>,
+    snapshots: RwLock<HashMap<Uuid, WorkflowSnapshot>>,
+    outbox: RwLock<Vec<OutboxMessage>>,
+    snapshot_policy: SnapshotPolicy,
+}
+
+impl InMemoryEventStore {
+    /// Create a new in-memory event store
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Use a custom snapshot policy
+    #[must_use]
+    pub fn with_snapshot_policy(mut self, snapshot_policy: SnapshotPolicy) -> Self {
+        self.snapshot_policy = snapshot_policy;
+        self
+    }
+
+    /// Outbox rows written by appends
+    pub(crate) fn outbox(&self) -> &RwLock<Vec<OutboxMessage>> {
+        &self.outbox
+    }
+}
+
+#[async_trait]
+impl EventStore for InMemoryEventStore {
+    async fn append(
+        &self,
+        stream_id: Uuid,
+        stream_type: &str,
+        expected_version: Option<u64>,
+        events: Vec<WorkflowEvent>,
+        metadata: serde_json::Value,
+    ) -> Result<u64, EventStoreError> {
+        let mut streams = self.events.write().await;
+        let stream = streams.entry(stream_id).or_default();
+        let current_version = stream.len() as u64;
+
+        if let Some(expected) = expected_version {
+            if current_version != expected {
+                return Err(EventStoreError::ConcurrencyConflict {
+                    expected,
+                    actual: current_version,
+                });
+            }
+        }
+
+        let mut outbox = self.outbox.write().await;
+        for event in events {
+            let version = stream.len() as u64 + 1;
+            let stored = StoredEvent::new(stream_id, stream_type, version, event, metadata.clone());
+            outbox.push(OutboxMessage::for_event(&stored)?);
+            stream.push(stored);
+        }
+
+        Ok(stream.len() as u64)
+    }
+
+    async fn load_events(
+        &self,
+        stream_id: Uuid,
+        from_version: u64,
+    ) -> Result<Vec<StoredEvent>, EventStoreError> {
+        let streams = self.events.read().await;
+        Ok(streams
+            .get(&stream_id)
+            .map(|stream| {
+                stream
+                    .iter()
+                    .filter(|e| e.version > from_version)
+                    .cloned()
+                    .collect()
+            })
+            .unwrap_or_default())
+    }
+
+    async fn get_latest_snapshot(
+        &self,
+        stream_id: Uuid,
+    ) -> Result<Option<WorkflowSnapshot>, EventStoreError> {
+        Ok(self.snapshots.read().await.get(&stream_id).cloned())
+    }
+
+    async fn save_snapshot(
+        &self,
+        stream_id: Uuid,
+        _stream_type: &str,
+        snapshot: &WorkflowSnapshot,
+    ) -> Result<(), EventStoreError> {
+        self.snapshots
+            .write()
+            .await
+            .insert(stream_id, snapshot.clone());
+        Ok(())
+    }
+
+    async fn get_stream_version(&self, stream_id: Uuid) -> Result<Option<u64>, EventStoreError> {
+        let streams = self.events.read().await;
+        Ok(streams
+            .get(&stream_id)
+            .map(|stream| stream.len() as u64)
+            .filter(|&v| v > 0))
+    }
+
+    fn snapshot_policy(&self) -> SnapshotPolicy {
+        self.snapshot_policy
+    }
 }
 
 // =============================================================================
@@ -404,7 +720,7 @@ impl<S: EventStore> EventStore for AutoSnapshotEventStore<S> {
             .await?;
 
         // Check if we should create a snapshot
-        if PgEventStore::should_snapshot(new_version) {
+        if self.inner.snapshot_policy().should_snapshot(new_version) {
             if let Some(snapshot) = (self.state_provider)(stream_id) {
                 // Fire and forget - snapshot creation shouldn't block
                 let _ = self
@@ -446,6 +762,10 @@ impl<S: EventStore> EventStore for AutoSnapshotEventStore<S> {
     async fn get_stream_version(&self, stream_id: Uuid) -> Result<Option<u64>, EventStoreError> {
         self.inner.get_stream_version(stream_id).await
     }
+
+    fn snapshot_policy(&self) -> SnapshotPolicy {
+        self.inner.snapshot_policy()
+    }
 }
 
 // =============================================================================
@@ -455,19 +775,84 @@ impl<S: EventStore> EventStore for AutoSnapshotEventStore<S> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::state::WorkflowStateManager;
 
     #[test]
     fn test_should_snapshot() {
-        assert!(!PgEventStore::should_snapshot(0));
-        assert!(!PgEventStore::should_snapshot(49));
-        assert!(PgEventStore::should_snapshot(50));
-        assert!(!PgEventStore::should_snapshot(51));
-        assert!(PgEventStore::should_snapshot(100));
-        assert!(PgEventStore::should_snapshot(150));
+        let policy = SnapshotPolicy::default();
+        assert!(!policy.should_snapshot(0));
+        assert!(!policy.should_snapshot(49));
+        assert!(policy.should_snapshot(50));
+        assert!(!policy.should_snapshot(51));
+        assert!(policy.should_snapshot(100));
+        assert!(policy.should_snapshot(150));
     }
 
     #[test]
     fn test_snapshot_interval_is_50() {
         assert_eq!(SNAPSHOT_INTERVAL, 50);
+        assert_eq!(SnapshotPolicy::default(), SnapshotPolicy::Every(50));
+    }
+
+    #[test]
+    fn test_never_policy_never_snapshots() {
+        for version in [0, 1, 50, 100] {
+            assert!(!SnapshotPolicy::Never.should_snapshot(version));
+            assert!(!SnapshotPolicy::Every(0).should_snapshot(version));
+        }
+    }
+
+    /// Versions at which the auto-snapshot wrapper saved a snapshot
+    async fn snapshot_versions(policy: SnapshotPolicy, events: u64) -> Vec<u64> {
+        let store = AutoSnapshotEventStore::new(
+            InMemoryEventStore::new().with_snapshot_policy(policy),
+            Arc::new(|_| Some(WorkflowStateManager::new("start", &["start"]).to_snapshot())),
+        );
+        let stream_id = Uuid::new_v4();
+        let mut taken = Vec::new();
+
+        for _ in 0..events {
+            let version = store
+                .append(
+                    stream_id,
+                    "workflow",
+                    None,
+                    vec![WorkflowEvent::ContextUpdated {
+                        key: "k".to_string(),
+                        value: serde_json::json!(1),
+                        updated_at: Utc::now(),
+                    }],
+                    serde_json::json!({}),
+                )
+                .await
+                .unwrap();
+            if store
+                .inner
+                .snapshots
+                .write()
+                .await
+                .remove(&stream_id)
+                .is_some()
+            {
+                taken.push(version);
+            }
+        }
+
+        taken
+    }
+
+    #[tokio::test]
+    async fn test_snapshots_follow_configured_cadence() {
+        assert_eq!(
+            snapshot_versions(SnapshotPolicy::Every(3), 10).await,
+            vec![3, 6, 9]
+        );
+        assert_eq!(
+            snapshot_versions(SnapshotPolicy::Every(50), 10).await,
+            Vec::<u64>::new()
+        );
+        assert!(snapshot_versions(SnapshotPolicy::Never, 10)
+            .await
+            .is_empty());
     }
 }
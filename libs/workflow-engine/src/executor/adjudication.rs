@@ -109,7 +109,7 @@ mod tests {
     use chrono::Utc;
     use uuid::Uuid;
 
-    use super::super::traits::AnnotationData;
+    use super::super::traits::{AnnotationData, SubmissionKind};
 
     fn create_adjudication_annotation(agreement: f64) -> AnnotationData {
         AnnotationData {
@@ -122,6 +122,7 @@ mod tests {
             }),
             submitted_at: Utc::now(),
             decision: None,
+            kind: SubmissionKind::Final,
         }
     }
 
@@ -1,7 +1,8 @@
 //! Annotation step executor
 //!
 //! Waits for the configured number of annotations to be submitted
-//! before completing the step.
+//! before completing the step. Saved progress (partial annotations) is
+//! ignored until it is submitted.
 
 use async_trait::async_trait;
 
@@ -60,7 +61,9 @@ impl AnnotationStepExecutor {
 #[async_trait]
 impl StepExecutor for AnnotationStepExecutor {
     async fn execute(&self, ctx: &ExecutionContext<'_>) -> Result<ExecutionResult, ExecutorError> {
-        let annotation_count = ctx.annotations.len() as u32;
+        let submitted: Vec<&AnnotationData> =
+            ctx.annotations.iter().filter(|a| a.is_final()).collect();
+        let annotation_count = submitted.len() as u32;
 
         if annotation_count < self.min_annotators {
             let remaining = self.min_annotators - annotation_count;
@@ -70,10 +73,12 @@ impl StepExecutor for AnnotationStepExecutor {
         }
 
         // All required annotations received
-        let annotation_ids: Vec<_> = ctx.annotations.iter().map(|a| a.annotation_id).collect();
+        let annotation_ids: Vec<_> = submitted.iter().map(|a| a.annotation_id).collect();
+        let output = submitted.last().map(|a| a.data.clone());
 
         Ok(ExecutionResult::complete(StepResult::submitted(
             annotation_ids,
+            output,
         )))
     }
 
@@ -84,6 +89,7 @@ impl StepExecutor for AnnotationStepExecutor {
 
 #[cfg(test)]
 mod tests {
+    use super::super::traits::SubmissionKind;
     use super::*;
     use crate::config::StepSettingsConfig;
     use crate::state::WorkflowStateManager;
@@ -97,6 +103,7 @@ mod tests {
             data: serde_json::json!({"label": "test"}),
             submitted_at: Utc::now(),
             decision: None,
+            kind: SubmissionKind::Final,
         }
     }
 
@@ -152,6 +159,30 @@ mod tests {
         assert!(result.is_complete());
     }
 
+    #[tokio::test]
+    async fn test_partial_annotations_do_not_count() {
+        let config = StepConfig {
+            id: "step1".to_string(),
+            name: "Annotate".to_string(),
+            step_type: StepType::Annotation,
+            settings: StepSettingsConfig::default(),
+            ref_name: None,
+            overrides: None,
+        };
+
+        let executor = AnnotationStepExecutor::new(&config).unwrap();
+        let state = WorkflowStateManager::new("step1", &["step1"]);
+        let mut ctx = ExecutionContext::new(Uuid::new_v4(), "step1".to_string(), &config, &state);
+
+        let mut partial = create_annotation(Uuid::new_v4());
+        partial.kind = SubmissionKind::Partial;
+        ctx.annotations = vec![partial];
+        assert!(executor.execute(&ctx).await.unwrap().is_waiting());
+
+        ctx.annotations.push(create_annotation(Uuid::new_v4()));
+        assert!(executor.execute(&ctx).await.unwrap().is_complete());
+    }
+
     #[test]
     fn test_blind_visibility() {
         let config = StepConfig {
@@ -164,6 +164,7 @@ mod tests {
             data: serde_json::json!({"label": "test"}),
             submitted_at: chrono::Utc::now(),
             decision: None,
+            kind: super::super::traits::SubmissionKind::Final,
         }];
 
         let result = executor.execute(&ctx).await.unwrap();
@@ -81,13 +81,29 @@ impl StepExecutor for ConditionalStepExecutor {
             threshold: None,
         };
 
+        // `annotation.*` reads the output of the step that led here
+        let previous_step = ctx
+            .workflow_state
+            .get_history()
+            .iter()
+            .rev()
+            .find(|transition| transition.to_step == ctx.step_id)
+            .and_then(|transition| transition.from_step.as_deref());
+        let annotation = previous_step.and_then(|step_id| ctx.step_output(step_id));
+
+        let workflow_context = ctx.workflow_state.get_context();
+        let agreement = workflow_context
+            .get("agreement")
+            .and_then(serde_json::Value::as_f64);
+
         // Build context for condition evaluation
         let condition_ctx = ConditionContext::new(
             None, // No step result for conditional
-            None, // Consensus from context if needed
-            ctx.workflow_state.get_context(),
+            agreement,
+            workflow_context,
             ctx.workflow_state.all_step_states(),
-        );
+        )
+        .with_annotation(annotation);
 
         // Evaluate the condition
         let result = evaluate_condition(&condition_config, &condition_ctx)
@@ -194,6 +210,112 @@ mod tests {
         }
     }
 
+    #[tokio::test]
+    async fn test_condition_reads_previous_step_output() {
+        let config = StepConfig {
+            id: "cond".to_string(),
+            name: "Conditional".to_string(),
+            step_type: StepType::Conditional,
+            settings: StepSettingsConfig {
+                condition: Some("step.classify.output.label == \"x\"".to_string()),
+                ..Default::default()
+            },
+            ref_name: None,
+            overrides: Some(serde_json::json!({
+                "true_branch": "approved",
+                "false_branch": "rejected"
+            })),
+        };
+
+        let executor = ConditionalStepExecutor::new(&config).unwrap();
+
+        let mut state =
+            WorkflowStateManager::new("classify", &["classify", "cond", "approved", "rejected"]);
+        state.activate_step("classify", vec![]).unwrap();
+        state
+            .complete_step(
+                "classify",
+                StepResult::AutoProcessed {
+                    output: serde_json::json!({ "label": "x" }),
+                },
+            )
+            .unwrap();
+
+        let ctx = ExecutionContext::new(Uuid::new_v4(), "cond".to_string(), &config, &state);
+        assert_eq!(
+            ctx.step_output("classify"),
+            Some(&serde_json::json!({ "label": "x" }))
+        );
+        assert_eq!(ctx.step_output("approved"), None);
+        assert_eq!(ctx.step_output("missing"), None);
+
+        let result = executor.execute(&ctx).await.unwrap();
+
+        if let ExecutionResult::Complete {
+            result: StepResult::ConditionMet { branch },
+        } = result
+        {
+            assert_eq!(branch, "approved");
+        } else {
+            panic!("Expected ConditionMet result");
+        }
+    }
+
+    #[tokio::test]
+    async fn test_condition_reads_submitted_annotation() {
+        let config = StepConfig {
+            id: "cond".to_string(),
+            name: "Conditional".to_string(),
+            step_type: StepType::Conditional,
+            settings: StepSettingsConfig {
+                condition: Some("annotation.label == \"x\"".to_string()),
+                ..Default::default()
+            },
+            ref_name: None,
+            overrides: Some(serde_json::json!({
+                "true_branch": "approved",
+                "false_branch": "rejected"
+            })),
+        };
+
+        let executor = ConditionalStepExecutor::new(&config).unwrap();
+
+        let branch_for = |label: &str| {
+            let mut state = WorkflowStateManager::new(
+                "annotate",
+                &["annotate", "cond", "approved", "rejected"],
+            );
+            state.activate_step("annotate", vec![]).unwrap();
+            state
+                .complete_step(
+                    "annotate",
+                    StepResult::submitted(
+                        vec![Uuid::new_v4()],
+                        Some(serde_json::json!({ "label": label })),
+                    ),
+                )
+                .unwrap();
+            state.transition_to("cond", "annotation submitted").unwrap();
+            state
+        };
+
+        for (label, expected) in [("x", "approved"), ("y", "rejected")] {
+            let state = branch_for(label);
+            let ctx = ExecutionContext::new(Uuid::new_v4(), "cond".to_string(), &config, &state);
+            assert_eq!(
+                ctx.step_output("annotate"),
+                Some(&serde_json::json!({ "label": label }))
+            );
+
+            match executor.execute(&ctx).await.unwrap() {
+                ExecutionResult::Complete {
+                    result: StepResult::ConditionMet { branch },
+                } => assert_eq!(branch, expected),
+                other => panic!("Expected ConditionMet result, got {other:?}"),
+            }
+        }
+    }
+
     #[test]
     fn test_missing_condition() {
         let config = StepConfig {
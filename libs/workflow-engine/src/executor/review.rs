@@ -82,7 +82,7 @@ mod tests {
     use chrono::Utc;
     use uuid::Uuid;
 
-    use super::super::traits::AnnotationData;
+    use super::super::traits::{AnnotationData, SubmissionKind};
 
     fn create_review_annotation(decision: ReviewDecision, reason: Option<&str>) -> AnnotationData {
         let mut data = serde_json::Map::new();
@@ -96,6 +96,7 @@ mod tests {
             data: serde_json::Value::Object(data),
             submitted_at: Utc::now(),
             decision: Some(decision),
+            kind: SubmissionKind::Final,
         }
     }
 
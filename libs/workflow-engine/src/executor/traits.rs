@@ -67,6 +67,30 @@ pub struct AnnotationData {
 
     /// Optional decision for review steps
     pub decision: Option<ReviewDecision>,
+
+    /// Whether this is a final submission or saved progress
+    #[serde(default)]
+    pub kind: SubmissionKind,
+}
+
+impl AnnotationData {
+    /// Whether this annotation is a final submission that counts toward
+    /// completing its step
+    #[must_use]
+    pub fn is_final(&self) -> bool {
+        self.kind == SubmissionKind::Final
+    }
+}
+
+/// Whether an annotation payload is finished
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SubmissionKind {
+    /// Final submission: schema-validated and can advance the step
+    #[default]
+    Final,
+    /// Saved progress: stored as a draft and never advances the step
+    Partial,
 }
 
 /// Decision made during review
@@ -145,6 +169,18 @@ impl<'a> ExecutionContext<'a> {
         self.current_user_roles = roles;
         self
     }
+
+    /// Output of a completed earlier step
+    ///
+    /// Returns `None` if the step doesn't exist, hasn't completed, or
+    /// completed with a result that carries no output.
+    #[must_use]
+    pub fn step_output(&self, step_id: &str) -> Option<&serde_json::Value> {
+        self.workflow_state
+            .get_step_state(step_id)?
+            .result()?
+            .output()
+    }
 }
 
 // =============================================================================
@@ -174,6 +210,12 @@ pub enum ExecutionResult {
         /// Whether the step can be retried
         retryable: bool,
     },
+
+    /// Step was skipped; the workflow advances as if it had completed
+    Skipped {
+        /// Why the step was skipped
+        reason: String,
+    },
 }
 
 impl ExecutionResult {
@@ -200,6 +242,14 @@ impl ExecutionResult {
         }
     }
 
+    /// Create a skipped result
+    #[must_use]
+    pub fn skipped(reason: impl Into<String>) -> Self {
+        Self::Skipped {
+            reason: reason.into(),
+        }
+    }
+
     /// Check if waiting
     #[must_use]
     pub fn is_waiting(&self) -> bool {
@@ -217,6 +267,12 @@ impl ExecutionResult {
     pub fn is_failed(&self) -> bool {
         matches!(self, Self::Failed { .. })
     }
+
+    /// Check if skipped
+    #[must_use]
+    pub fn is_skipped(&self) -> bool {
+        matches!(self, Self::Skipped { .. })
+    }
 }
 
 // =============================================================================
@@ -3,6 +3,8 @@
 //! Evaluates different goal types (volume, quality, deadline, composite)
 //! and calculates progress, projections, and alerts.
 
+use std::collections::HashMap;
+
 use chrono::{DateTime, Duration, Utc};
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
@@ -87,6 +89,58 @@ impl EvaluationResult {
     }
 }
 
+// =============================================================================
+// Composite Goals
+// =============================================================================
+
+/// Identifier of a tracked goal
+pub type GoalId = Uuid;
+
+/// How a composite goal combines its children
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum CompositeMode {
+    /// Complete once every child is complete
+    All,
+
+    /// Complete once any child is complete
+    Any,
+
+    /// Complete once the weighted average of child progress reaches `threshold`
+    Weighted {
+        /// Weight per child; children not listed weigh 1.0
+        #[serde(default)]
+        weights: HashMap<GoalId, f64>,
+
+        /// Weighted progress (0.0 to 1.0) required for completion
+        threshold: f64,
+    },
+}
+
+/// A goal whose progress is derived from other goals
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CompositeGoal {
+    /// Goal ID of the composite itself
+    pub goal_id: GoalId,
+
+    /// Child goals feeding this goal
+    pub children: Vec<GoalId>,
+
+    /// How child progress is combined
+    pub mode: CompositeMode,
+}
+
+impl CompositeGoal {
+    /// Weight of a child in [`CompositeMode::Weighted`] mode
+    #[must_use]
+    pub fn weight_of(&self, child: GoalId) -> f64 {
+        match &self.mode {
+            CompositeMode::Weighted { weights, .. } => weights.get(&child).copied().unwrap_or(1.0),
+            CompositeMode::All | CompositeMode::Any => 1.0,
+        }
+    }
+}
+
 // =============================================================================
 // Alert Conditions
 // =============================================================================
@@ -261,6 +315,45 @@ impl GoalEvaluator {
         result
     }
 
+    /// Evaluate a [`CompositeGoal`] from its children's latest results.
+    ///
+    /// Children without a result count as having made no progress.
+    #[must_use]
+    pub fn evaluate_composite_goal(
+        &self,
+        goal: &CompositeGoal,
+        child_results: &HashMap<GoalId, EvaluationResult>,
+    ) -> EvaluationResult {
+        let sub_results: Vec<EvaluationResult> = goal
+            .children
+            .iter()
+            .map(|child| {
+                child_results
+                    .get(child)
+                    .cloned()
+                    .unwrap_or_else(|| EvaluationResult::new(*child, 0.0, 1.0))
+            })
+            .collect();
+
+        match &goal.mode {
+            CompositeMode::All => self.evaluate_composite(goal.goal_id, &sub_results, true),
+            CompositeMode::Any => self.evaluate_composite(goal.goal_id, &sub_results, false),
+            CompositeMode::Weighted { threshold, .. } => {
+                let total_weight: f64 = goal.children.iter().map(|c| goal.weight_of(*c)).sum();
+                let progress = if total_weight > 0.0 {
+                    sub_results
+                        .iter()
+                        .map(|r| goal.weight_of(r.goal_id) * r.percentage.min(1.0))
+                        .sum::<f64>()
+                        / total_weight
+                } else {
+                    0.0
+                };
+                EvaluationResult::new(goal.goal_id, progress, *threshold)
+            }
+        }
+    }
+
     /// Project completion time based on velocity
     #[must_use]
     pub fn project_completion(current: f64, target: f64, velocity: f64) -> Option<DateTime<Utc>> {
@@ -399,6 +492,59 @@ mod tests {
         assert!(result.is_complete); // At least one complete
     }
 
+    fn composite(children: Vec<GoalId>, mode: CompositeMode) -> CompositeGoal {
+        CompositeGoal {
+            goal_id: Uuid::new_v4(),
+            children,
+            mode,
+        }
+    }
+
+    #[test]
+    fn test_composite_goal_all_mode() {
+        let evaluator = GoalEvaluator::new();
+        let (a, b) = (Uuid::new_v4(), Uuid::new_v4());
+        let goal = composite(vec![a, b], CompositeMode::All);
+
+        let mut children = HashMap::from([(a, EvaluationResult::new(a, 100.0, 100.0))]);
+        let result = evaluator.evaluate_composite_goal(&goal, &children);
+        assert_eq!(result.goal_id, goal.goal_id);
+        assert!(!result.is_complete); // b has no result yet
+        assert!((result.percentage - 0.5).abs() < 0.001);
+
+        children.insert(b, EvaluationResult::new(b, 10.0, 10.0));
+        let result = evaluator.evaluate_composite_goal(&goal, &children);
+        assert!(result.is_complete);
+    }
+
+    #[test]
+    fn test_composite_goal_weighted_mode() {
+        let evaluator = GoalEvaluator::new();
+        let (a, b) = (Uuid::new_v4(), Uuid::new_v4());
+        let goal = composite(
+            vec![a, b],
+            CompositeMode::Weighted {
+                weights: HashMap::from([(a, 3.0)]),
+                threshold: 0.8,
+            },
+        );
+
+        // a is done (weight 3), b is untouched (weight 1): 0.75 < 0.8
+        let mut children = HashMap::from([
+            (a, EvaluationResult::new(a, 150.0, 100.0)),
+            (b, EvaluationResult::new(b, 0.0, 100.0)),
+        ]);
+        let result = evaluator.evaluate_composite_goal(&goal, &children);
+        assert!((result.current_value - 0.75).abs() < 0.001);
+        assert!(!result.is_complete);
+
+        // b at 20% adds 0.05: 0.8 reaches the threshold
+        children.insert(b, EvaluationResult::new(b, 20.0, 100.0));
+        let result = evaluator.evaluate_composite_goal(&goal, &children);
+        assert!((result.current_value - 0.8).abs() < 0.001);
+        assert!(result.is_complete);
+    }
+
     #[test]
     fn test_threshold_alert() {
         let evaluator = GoalEvaluator::new();
@@ -1,7 +1,8 @@
 //! Goal tracker with debounced updates
 //!
 //! Tracks goal progress with debouncing (5-10 seconds per CONTEXT.md)
-//! and configurable completion actions.
+//! and configurable completion actions. Flushed updates to a child goal queue
+//! its composite parents, which are re-evaluated after their own debounce.
 
 use std::collections::HashMap;
 use std::sync::Arc;
@@ -13,7 +14,11 @@ use tokio::sync::Mutex;
 use tokio::task::JoinHandle;
 use uuid::Uuid;
 
-use super::goal_evaluator::{AlertCondition, EvaluationResult, GoalEvaluator};
+use crate::clock::{system_clock, Clock};
+
+use super::goal_evaluator::{
+    AlertCondition, CompositeGoal, EvaluationResult, GoalEvaluator, GoalId,
+};
 
 // =============================================================================
 // Constants
@@ -126,6 +131,9 @@ pub struct GoalUpdate {
     /// New value
     pub new_value: f64,
 
+    /// Progress toward the target after the update (0.0 to 1.0+)
+    pub percentage: f64,
+
     /// When update was applied
     pub evaluated_at: DateTime<Utc>,
 
@@ -154,8 +162,17 @@ pub struct GoalTracker {
     /// Previous evaluation results (for alert comparison)
     previous_results: HashMap<Uuid, EvaluationResult>,
 
+    /// Composite goals derived from other goals
+    composites: HashMap<Uuid, CompositeGoal>,
+
+    /// Composite goals awaiting re-evaluation, with when they were first queued
+    pending_composites: HashMap<Uuid, Instant>,
+
     /// Debounce duration
     debounce_duration: Duration,
+
+    /// Time source for debouncing and update timestamps
+    clock: Arc<dyn Clock>,
 }
 
 impl Default for GoalTracker {
@@ -174,11 +191,17 @@ impl GoalTracker {
             evaluator: GoalEvaluator::new(),
             completion_actions: HashMap::new(),
             previous_results: HashMap::new(),
+            composites: HashMap::new(),
+            pending_composites: HashMap::new(),
             debounce_duration: DEBOUNCE_DURATION,
+            clock: system_clock(),
         }
     }
 
-    /// Create with custom debounce duration
+    /// Create with custom debounce duration.
+    ///
+    /// Contributions to a goal within one window collapse into a single
+    /// evaluation when the window closes.
     #[must_use]
     pub fn with_debounce(debounce: Duration) -> Self {
         Self {
@@ -187,6 +210,19 @@ impl GoalTracker {
         }
     }
 
+    /// Use a different time source
+    #[must_use]
+    pub fn with_clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    /// The debounce window
+    #[must_use]
+    pub fn debounce(&self) -> Duration {
+        self.debounce_duration
+    }
+
     /// Register a goal for tracking
     pub fn register_goal(&mut self, goal: TrackedGoal, actions: Vec<CompletionAction>) {
         let goal_id = goal.goal_id;
@@ -196,18 +232,34 @@ impl GoalTracker {
         }
     }
 
+    /// Register a composite goal whose progress is derived from its children
+    pub fn register_composite(&mut self, goal: CompositeGoal, actions: Vec<CompletionAction>) {
+        let goal_id = goal.goal_id;
+        self.composites.insert(goal_id, goal);
+        if !actions.is_empty() {
+            self.completion_actions.insert(goal_id, actions);
+        }
+    }
+
     /// Unregister a goal
     pub fn unregister_goal(&mut self, goal_id: Uuid) {
         self.goals.remove(&goal_id);
         self.pending_updates.remove(&goal_id);
         self.completion_actions.remove(&goal_id);
         self.previous_results.remove(&goal_id);
+        self.composites.remove(&goal_id);
+        self.pending_composites.remove(&goal_id);
     }
 
     /// Record a contribution to a goal (debounced)
     pub fn record_contribution(&mut self, goal_id: Uuid, increment: f64) {
-        let now = Instant::now();
+        let now = self.clock.instant();
+        self.record_contribution_at(goal_id, increment, now);
+    }
 
+    /// Record a contribution as of `now`; the window starts at the first
+    /// contribution since the last flush
+    pub fn record_contribution_at(&mut self, goal_id: Uuid, increment: f64, now: Instant) {
         // Queue or accumulate update
         self.pending_updates
             .entry(goal_id)
@@ -224,7 +276,12 @@ impl GoalTracker {
 
     /// Flush pending updates that have been debounced long enough
     pub fn flush_pending(&mut self) -> Vec<GoalUpdate> {
-        let now = Instant::now();
+        let now = self.clock.instant();
+        self.flush_pending_at(now)
+    }
+
+    /// Flush pending updates whose window has closed as of `now`
+    pub fn flush_pending_at(&mut self, now: Instant) -> Vec<GoalUpdate> {
         let debounce = self.debounce_duration;
 
         // Find updates ready to flush
@@ -259,6 +316,8 @@ impl GoalTracker {
                         &goal.alert_thresholds,
                     );
 
+                    let percentage = result.percentage;
+
                     // Store result for next comparison
                     self.previous_results.insert(goal_id, result);
 
@@ -266,13 +325,26 @@ impl GoalTracker {
                         goal_id,
                         old_value,
                         new_value,
-                        evaluated_at: Utc::now(),
+                        percentage,
+                        evaluated_at: self.clock.now(),
                         alerts,
                     });
                 }
             }
         }
 
+        for update in &updates {
+            self.queue_parents(update.goal_id, now);
+        }
+
+        let ready_composites: Vec<Uuid> = self
+            .pending_composites
+            .iter()
+            .filter(|(_, queued_at)| now.duration_since(**queued_at) >= debounce)
+            .map(|(id, _)| *id)
+            .collect();
+        updates.extend(self.evaluate_composites(&ready_composites, now));
+
         updates
     }
 
@@ -302,19 +374,100 @@ impl GoalTracker {
                         &goal.alert_thresholds,
                     );
 
+                    let percentage = result.percentage;
                     self.previous_results.insert(goal_id, result);
 
                     updates.push(GoalUpdate {
                         goal_id,
                         old_value,
                         new_value,
-                        evaluated_at: Utc::now(),
+                        percentage,
+                        evaluated_at: self.clock.now(),
                         alerts,
                     });
                 }
             }
         }
 
+        let now = self.clock.instant();
+        for update in &updates {
+            self.queue_parents(update.goal_id, now);
+        }
+
+        // Nested composites queue their own parents; each pass climbs one
+        // level, and the bound stops a cyclic configuration from looping
+        for _ in 0..=self.composites.len() {
+            if self.pending_composites.is_empty() {
+                break;
+            }
+            let queued: Vec<Uuid> = self.pending_composites.keys().copied().collect();
+            updates.extend(self.evaluate_composites(&queued, now));
+        }
+
+        updates
+    }
+
+    /// Queue every composite that has `child_id` as a child
+    fn queue_parents(&mut self, child_id: GoalId, now: Instant) {
+        for (parent_id, composite) in &self.composites {
+            if composite.children.contains(&child_id) {
+                // Keep the original time so a busy child can't starve the parent
+                self.pending_composites.entry(*parent_id).or_insert(now);
+            }
+        }
+    }
+
+    /// Latest result for a goal, evaluating tracked goals never flushed
+    fn current_result(&self, goal_id: GoalId) -> Option<EvaluationResult> {
+        self.previous_results.get(&goal_id).cloned().or_else(|| {
+            self.goals.get(&goal_id).map(|goal| {
+                self.evaluator
+                    .evaluate_volume(goal_id, goal.current as u64, goal.target as u64)
+            })
+        })
+    }
+
+    /// Re-evaluate queued composites and queue their own parents.
+    ///
+    /// A composite's value is its combined child progress; `percentage`
+    /// measures that against the composite's threshold.
+    fn evaluate_composites(&mut self, goal_ids: &[Uuid], now: Instant) -> Vec<GoalUpdate> {
+        let mut updates = Vec::new();
+
+        for &goal_id in goal_ids {
+            self.pending_composites.remove(&goal_id);
+            let Some(composite) = self.composites.get(&goal_id) else {
+                continue;
+            };
+
+            let child_results = composite
+                .children
+                .iter()
+                .filter_map(|child| self.current_result(*child).map(|r| (*child, r)))
+                .collect();
+            let result = self
+                .evaluator
+                .evaluate_composite_goal(composite, &child_results);
+
+            let previous = self.previous_results.get(&goal_id);
+            let old_value = previous.map_or(0.0, |r| r.current_value);
+            let alerts = self.evaluator.check_alerts(&result, previous, None, &[]);
+            let new_value = result.current_value;
+            let percentage = result.percentage;
+
+            self.previous_results.insert(goal_id, result);
+            self.queue_parents(goal_id, now);
+
+            updates.push(GoalUpdate {
+                goal_id,
+                old_value,
+                new_value,
+                percentage,
+                evaluated_at: self.clock.now(),
+                alerts,
+            });
+        }
+
         updates
     }
 
@@ -353,9 +506,21 @@ impl GoalTracker {
         &self.goals
     }
 
+    /// Get a composite goal by ID
+    #[must_use]
+    pub fn get_composite(&self, goal_id: Uuid) -> Option<&CompositeGoal> {
+        self.composites.get(&goal_id)
+    }
+
     /// Check if a goal is complete
     #[must_use]
     pub fn is_goal_complete(&self, goal_id: Uuid) -> bool {
+        if self.composites.contains_key(&goal_id) {
+            return self
+                .previous_results
+                .get(&goal_id)
+                .is_some_and(|r| r.is_complete);
+        }
         self.goals
             .get(&goal_id)
             .map(|g| g.current >= g.target)
@@ -401,10 +566,11 @@ async fn execute_action(action: &CompletionAction) -> ActionResult {
     }
 }
 
-/// Spawn a background task that flushes pending updates periodically
+/// Spawn a background task that flushes pending updates once per debounce window
 pub fn spawn_flush_loop(tracker: Arc<Mutex<GoalTracker>>) -> JoinHandle<()> {
     tokio::spawn(async move {
-        let mut interval = tokio::time::interval(DEBOUNCE_DURATION);
+        let debounce = tracker.lock().await.debounce();
+        let mut interval = tokio::time::interval(debounce);
 
         loop {
             interval.tick().await;
@@ -437,6 +603,7 @@ pub fn spawn_flush_loop(tracker: Arc<Mutex<GoalTracker>>) -> JoinHandle<()> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::goals::CompositeMode;
 
     #[test]
     fn test_register_and_contribute() {
@@ -534,4 +701,107 @@ mod tests {
         assert_eq!(updates.len(), 1);
         assert!(!updates[0].alerts.is_empty());
     }
+
+    fn tracked(target: f64) -> TrackedGoal {
+        TrackedGoal {
+            goal_id: Uuid::new_v4(),
+            name: "Child Goal".to_string(),
+            target,
+            current: 0.0,
+            deadline: None,
+            alert_thresholds: vec![],
+        }
+    }
+
+    #[test]
+    fn test_composite_all_propagates_from_children() {
+        let mut tracker = GoalTracker::new();
+        let (a, b) = (tracked(10.0), tracked(20.0));
+        let parent = CompositeGoal {
+            goal_id: Uuid::new_v4(),
+            children: vec![a.goal_id, b.goal_id],
+            mode: CompositeMode::All,
+        };
+        let parent_id = parent.goal_id;
+        let (a_id, b_id) = (a.goal_id, b.goal_id);
+        tracker.register_goal(a, vec![]);
+        tracker.register_goal(b, vec![]);
+        tracker.register_composite(parent, vec![]);
+
+        tracker.record_contribution(a_id, 10.0);
+        let updates = tracker.flush_all();
+        let parent_update = updates.iter().find(|u| u.goal_id == parent_id).unwrap();
+        assert!((parent_update.new_value - 0.5).abs() < 0.001);
+        assert!((parent_update.percentage - 0.5).abs() < 0.001);
+        assert!(!tracker.is_goal_complete(parent_id));
+
+        tracker.record_contribution(b_id, 20.0);
+        let updates = tracker.flush_all();
+        let parent_update = updates.iter().find(|u| u.goal_id == parent_id).unwrap();
+        assert!(parent_update
+            .alerts
+            .iter()
+            .any(|a| matches!(a, AlertCondition::GoalCompleted { .. })));
+        assert!(tracker.is_goal_complete(parent_id));
+    }
+
+    #[test]
+    fn test_composite_weighted_is_debounced() {
+        let mut tracker = GoalTracker::with_debounce(Duration::from_secs(60));
+        let (a, b) = (tracked(100.0), tracked(100.0));
+        let parent = CompositeGoal {
+            goal_id: Uuid::new_v4(),
+            children: vec![a.goal_id, b.goal_id],
+            mode: CompositeMode::Weighted {
+                weights: HashMap::from([(a.goal_id, 3.0)]),
+                threshold: 0.7,
+            },
+        };
+        let parent_id = parent.goal_id;
+        let a_id = a.goal_id;
+        tracker.register_goal(a, vec![]);
+        tracker.register_goal(b, vec![]);
+        tracker.register_composite(parent, vec![CompletionAction::Pause]);
+
+        tracker.record_contribution(a_id, 100.0);
+
+        // Neither the child nor the parent has waited out the debounce
+        assert!(tracker.flush_pending().is_empty());
+        assert!(tracker.pending_composites.is_empty());
+
+        let updates = tracker.flush_all();
+        assert_eq!(updates.len(), 2);
+        let parent_update = updates.iter().find(|u| u.goal_id == parent_id).unwrap();
+        assert!((parent_update.new_value - 0.75).abs() < 0.001);
+        assert!((parent_update.percentage - 0.75 / 0.7).abs() < 0.001);
+        assert!(tracker.is_goal_complete(parent_id));
+    }
+
+    #[test]
+    fn test_updates_within_window_collapse_into_one_evaluation() {
+        let mut tracker = GoalTracker::with_debounce(Duration::from_secs(5));
+        assert_eq!(tracker.debounce(), Duration::from_secs(5));
+
+        let goal = tracked(100.0);
+        let goal_id = goal.goal_id;
+        tracker.register_goal(goal, vec![]);
+
+        let start = Instant::now();
+        for (offset_ms, increment) in [(0, 10.0), (1_000, 5.0), (4_000, 20.0)] {
+            let at = start + Duration::from_millis(offset_ms);
+            tracker.record_contribution_at(goal_id, increment, at);
+            // Still inside the window: nothing is evaluated yet
+            assert!(tracker.flush_pending_at(at).is_empty());
+        }
+
+        let updates = tracker.flush_pending_at(start + Duration::from_secs(5));
+        assert_eq!(updates.len(), 1);
+        assert_eq!(updates[0].old_value, 0.0);
+        assert_eq!(updates[0].new_value, 35.0);
+
+        // The window is consumed; later flushes have nothing to evaluate
+        assert!(tracker
+            .flush_pending_at(start + Duration::from_secs(20))
+            .is_empty());
+    }
 }
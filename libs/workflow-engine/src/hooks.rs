@@ -0,0 +1,105 @@
+//! Orchestrator hooks
+//!
+//! Completion hooks run after a task's workflow completion has been committed
+//! to the event store. They must not fail the submission, so errors are logged
+//! rather than returned. Submission validators run before an annotation step
+//! executes and reject payloads that don't match the expected output. Draft
+//! stores keep saved progress on annotation steps.
+
+use async_trait::async_trait;
+use chrono::Utc;
+use glyph_db::PgWebhookRepository;
+use glyph_domain::TaskId;
+use uuid::Uuid;
+
+/// Webhook event type sent when a task's workflow completes
+pub const WORKFLOW_COMPLETED_EVENT: &str = "workflow.completed";
+
+/// Called once a task's workflow has completed
+#[async_trait]
+pub trait CompletionHook: Send + Sync {
+    /// Handle a committed workflow completion
+    async fn on_completed(&self, task_id: Uuid, final_output: &serde_json::Value);
+}
+
+/// Checks an annotation payload before its step executes
+#[async_trait]
+pub trait SubmissionValidator: Send + Sync {
+    /// Return the violations found in `submission`; an empty list means it is
+    /// valid. `Err` means validation itself couldn't run.
+    async fn validate(
+        &self,
+        task_id: Uuid,
+        step_id: &str,
+        submission: &serde_json::Value,
+    ) -> Result<Vec<glyph_domain::ValidationError>, String>;
+}
+
+/// Stores saved progress on an annotation step.
+///
+/// One draft is kept per (task, step, user); saving again replaces it.
+#[async_trait]
+pub trait DraftStore: Send + Sync {
+    /// Save a user's in-progress annotation
+    async fn save_draft(
+        &self,
+        task_id: Uuid,
+        step_id: &str,
+        user_id: Uuid,
+        data: &serde_json::Value,
+    ) -> Result<(), String>;
+
+    /// Discard a user's draft once their final submission is accepted
+    async fn discard_draft(
+        &self,
+        task_id: Uuid,
+        step_id: &str,
+        user_id: Uuid,
+    ) -> Result<(), String>;
+}
+
+/// Queues a webhook delivery for the task's project, if one is configured.
+///
+/// The worker's webhook job signs and sends the queued delivery.
+pub struct WebhookCompletionHook {
+    repo: PgWebhookRepository,
+}
+
+impl WebhookCompletionHook {
+    /// Create a hook backed by the PostgreSQL delivery queue
+    #[must_use]
+    pub fn new(pool: sqlx::PgPool) -> Self {
+        Self {
+            repo: PgWebhookRepository::new(pool),
+        }
+    }
+}
+
+#[async_trait]
+impl CompletionHook for WebhookCompletionHook {
+    async fn on_completed(&self, task_id: Uuid, final_output: &serde_json::Value) {
+        let payload = serde_json::json!({
+            "event": WORKFLOW_COMPLETED_EVENT,
+            "task_id": task_id,
+            "final_output": final_output,
+            "completed_at": Utc::now(),
+        });
+        match self
+            .repo
+            .enqueue_for_task(
+                &TaskId::from_uuid(task_id),
+                WORKFLOW_COMPLETED_EVENT,
+                &payload,
+            )
+            .await
+        {
+            Ok(Some(delivery_id)) => {
+                tracing::debug!(%task_id, %delivery_id, "Queued workflow completion webhook");
+            }
+            Ok(None) => {}
+            Err(e) => {
+                tracing::error!(%task_id, error = %e, "Failed to queue completion webhook");
+            }
+        }
+    }
+}
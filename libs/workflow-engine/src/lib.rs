@@ -11,16 +11,21 @@
 //! - [`StepExecutor`] - Trait for step execution
 //! - [`EventStore`] - Event sourcing storage
 //! - [`GoalTracker`] - Goal tracking with debouncing
+//! - [`Clock`] - Injectable time source for time-dependent components
+//! - [`Rng`] - Injectable randomness for load balancing
 
 // Module declarations
 pub mod assignment;
+pub mod clock;
 pub mod config;
 pub mod consensus;
 pub mod engine;
 pub mod events;
 pub mod executor;
 pub mod goals;
+pub mod hooks;
 pub mod parser;
+pub mod rng;
 pub mod state;
 pub mod transition;
 
@@ -45,17 +50,34 @@ pub use consensus::{cohens_kappa, iou_span, krippendorffs_alpha_nominal, Consens
 // Executors
 pub use executor::{
     create_executor, ExecutionContext, ExecutionResult, ExecutorError, HandlerRegistry,
-    StepExecutor,
+    StepExecutor, SubmissionKind,
 };
 
+// Clock
+pub use clock::{system_clock, Clock, MockClock, SystemClock};
+
+// Randomness
+pub use rng::{Rng, SeededRng, ThreadRng};
+
 // Goals
 pub use goals::{CompletionAction, GoalEvaluator, GoalTracker};
 
 // Events
-pub use events::{EventStore, PgEventStore, StateRebuilder, StoredEvent, WorkflowEvent};
+pub use events::{
+    relay_once, step_durations, summarize_step_durations, EventStore, InMemoryEventStore,
+    OutboxError, OutboxMessage, OutboxPublisher, OutboxStore, OverdueCandidate, PgEventStore,
+    SnapshotPolicy, StateRebuilder, StepDuration, StepDurationStats, StoredEvent, WorkflowEvent,
+    WorkflowStepEventPage,
+};
+
+// Hooks
+pub use hooks::{
+    CompletionHook, DraftStore, SubmissionValidator, WebhookCompletionHook,
+    WORKFLOW_COMPLETED_EVENT,
+};
 
 // Engine (orchestrator)
 pub use engine::{
     InMemoryConfigStore, OrchestrationError, ProcessResult, WorkflowConfigStore,
-    WorkflowOrchestrator,
+    WorkflowOrchestrator, PROGRESS_SAVED,
 };
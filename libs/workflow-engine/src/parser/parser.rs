@@ -152,6 +152,78 @@ transitions:
         assert_eq!(config.steps.len(), 2);
     }
 
+    #[test]
+    fn test_entry_step_defaults_to_first_step() {
+        let yaml = r#"
+version: "1.0"
+name: "Review Workflow"
+workflow_type: single
+steps:
+  - id: annotate
+    name: Annotation
+    step_type: annotation
+  - id: review
+    name: Review
+    step_type: review
+transitions:
+  - from: annotate
+    to: review
+  - from: review
+    to: _complete
+"#;
+
+        let config = parse_workflow(yaml).unwrap();
+        assert_eq!(config.entry_step, None);
+        assert_eq!(config.entry_step_id(), Some("annotate"));
+    }
+
+    #[test]
+    fn test_explicit_entry_step_overrides_step_order() {
+        let yaml = r#"
+version: "1.0"
+name: "Review Workflow"
+workflow_type: single
+entry_step: annotate
+steps:
+  - id: review
+    name: Review
+    step_type: review
+  - id: annotate
+    name: Annotation
+    step_type: annotation
+transitions:
+  - from: annotate
+    to: review
+  - from: review
+    to: _complete
+"#;
+
+        let config = parse_workflow(yaml).unwrap();
+        assert_eq!(config.entry_step_id(), Some("annotate"));
+    }
+
+    #[test]
+    fn test_unknown_entry_step_is_rejected() {
+        let yaml = r#"
+version: "1.0"
+name: "Simple Workflow"
+workflow_type: single
+entry_step: missing
+steps:
+  - id: annotate
+    name: Annotation
+    step_type: annotation
+transitions:
+  - from: annotate
+    to: _complete
+"#;
+
+        assert!(matches!(
+            parse_workflow(yaml),
+            Err(ParseError::ValidationError(e)) if e.message.contains("Unknown entry step")
+        ));
+    }
+
     #[test]
     fn test_parse_invalid_yaml() {
         let yaml = "invalid: [yaml: {";
@@ -85,11 +85,26 @@ pub fn validate_workflow(config: &WorkflowConfig) -> Result<(), ValidationError>
     Ok(())
 }
 
-/// Validate that all transition step references exist
+/// Validate that the entry step and all transition step references exist
 fn validate_step_references(config: &WorkflowConfig) -> Result<(), ValidationError> {
     // Build set of valid step IDs
     let step_ids: HashSet<&str> = config.steps.iter().map(|s| s.id.as_str()).collect();
 
+    if let Some(entry_step) = &config.entry_step {
+        if !step_ids.contains(entry_step.as_str()) {
+            let suggestion = find_similar_step(entry_step, &step_ids);
+            return Err(
+                ValidationError::new(format!("Unknown entry step '{entry_step}'"))
+                    .with_location("entry_step")
+                    .with_suggestion(
+                        suggestion
+                            .map(|s| format!("Did you mean '{s}'?"))
+                            .unwrap_or_default(),
+                    ),
+            );
+        }
+    }
+
     // Check all transitions reference valid steps
     for (idx, transition) in config.transitions.iter().enumerate() {
         // Check 'from' reference
@@ -213,9 +228,11 @@ fn validate_reachability(config: &WorkflowConfig) -> Result<(), ValidationError>
         node_indices.insert(graph[node_idx], node_idx);
     }
 
-    // Find entry step (first step in list)
-    let entry_step = &config.steps[0].id;
-    let Some(&entry_idx) = node_indices.get(entry_step.as_str()) else {
+    // Find entry step (explicit, or first step in list)
+    let Some(entry_step) = config.entry_step_id() else {
+        return Err(ValidationError::new("Workflow must have at least one step"));
+    };
+    let Some(&entry_idx) = node_indices.get(entry_step) else {
         return Err(ValidationError::new("Entry step not found in graph"));
     };
 
@@ -336,6 +353,25 @@ fn validate_step_settings(config: &WorkflowConfig) -> Result<(), ValidationError
                 .with_location(format!("steps[{idx}].settings.threshold")));
             }
         }
+
+        if let Some(rate) = step.settings.review_sample_rate {
+            if !(0.0..=1.0).contains(&rate) {
+                return Err(ValidationError::new(format!(
+                    "Step '{}' review_sample_rate {rate} is not in valid range [0.0, 1.0]",
+                    step.id
+                ))
+                .with_location(format!("steps[{idx}].settings.review_sample_rate")));
+            }
+        }
+    }
+
+    if let Some(rate) = config.settings.review_sample_rate {
+        if !(0.0..=1.0).contains(&rate) {
+            return Err(ValidationError::new(format!(
+                "Workflow review_sample_rate {rate} is not in valid range [0.0, 1.0]"
+            ))
+            .with_location("settings.review_sample_rate"));
+        }
     }
 
     Ok(())
@@ -353,6 +389,7 @@ mod tests {
             name: "Test".to_string(),
             workflow_type: WorkflowType::Single,
             settings: Default::default(),
+            entry_step: None,
             steps: vec![StepConfig {
                 id: "step1".to_string(),
                 name: "Step 1".to_string(),
@@ -387,6 +424,17 @@ mod tests {
         assert!(err.message.contains("Unknown step 'nonexistent'"));
     }
 
+    #[test]
+    fn test_unknown_entry_step() {
+        let mut config = minimal_config();
+        config.entry_step = Some("stepp1".to_string());
+
+        let err = validate_workflow(&config).unwrap_err();
+        assert!(err.message.contains("Unknown entry step 'stepp1'"));
+        assert_eq!(err.location.as_deref(), Some("entry_step"));
+        assert!(err.suggestion.as_ref().is_some_and(|s| s.contains("step1")));
+    }
+
     #[test]
     fn test_typo_suggestion() {
         let mut config = minimal_config();
@@ -466,6 +514,18 @@ mod tests {
         // Will fail due to cycle, which is fine
     }
 
+    #[test]
+    fn test_review_sample_rate_bounds() {
+        let mut config = minimal_config();
+        config.steps[0].settings.review_sample_rate = Some(1.5);
+        let err = validate_workflow(&config).unwrap_err();
+        assert!(err.message.contains("review_sample_rate"));
+
+        let mut config = minimal_config();
+        config.settings.review_sample_rate = Some(-0.1);
+        assert!(validate_workflow(&config).is_err());
+    }
+
     #[test]
     fn test_auto_process_requires_handler() {
         let mut config = minimal_config();
@@ -140,6 +140,12 @@ impl StepState {
         }
     }
 
+    /// Check if the step is still active past its SLA deadline
+    #[must_use]
+    pub fn is_overdue(&self, deadline: DateTime<Utc>, now: DateTime<Utc>) -> bool {
+        self.is_active() && now > deadline
+    }
+
     /// Get the status name as a string
     #[must_use]
     pub fn status_name(&self) -> &'static str {
@@ -168,7 +174,12 @@ impl Default for StepState {
 #[serde(tag = "type", rename_all = "snake_case")]
 pub enum StepResult {
     /// Annotations were submitted
-    Submitted { annotations: Vec<Uuid> },
+    Submitted {
+        annotations: Vec<Uuid>,
+        /// Content of the submission that completed the step
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        output: Option<serde_json::Value>,
+    },
 
     /// Review approved the annotations
     Approved,
@@ -200,8 +211,11 @@ pub enum StepResult {
 impl StepResult {
     /// Create a submitted result
     #[must_use]
-    pub fn submitted(annotations: Vec<Uuid>) -> Self {
-        Self::Submitted { annotations }
+    pub fn submitted(annotations: Vec<Uuid>, output: Option<serde_json::Value>) -> Self {
+        Self::Submitted {
+            annotations,
+            output,
+        }
     }
 
     /// Create an approved result
@@ -226,6 +240,16 @@ impl StepResult {
             resolved_by: resolved_by.into(),
         }
     }
+
+    /// Output data produced by the step, for results that carry any
+    #[must_use]
+    pub const fn output(&self) -> Option<&serde_json::Value> {
+        match self {
+            Self::AutoProcessed { output } | Self::SubWorkflowCompleted { output } => Some(output),
+            Self::Submitted { output, .. } => output.as_ref(),
+            _ => None,
+        }
+    }
 }
 
 #[cfg(test)]
@@ -277,6 +301,27 @@ mod tests {
         assert!(!StepState::Pending.is_terminal());
     }
 
+    #[test]
+    fn test_overdue_when_active_past_deadline() {
+        let now = Utc::now();
+        let deadline = now - chrono::Duration::minutes(5);
+        let active = StepState::Active {
+            started_at: now - chrono::Duration::hours(1),
+            assigned_to: vec![],
+            last_activity: now - chrono::Duration::minutes(30),
+        };
+
+        assert!(active.is_overdue(deadline, now));
+        assert!(!active.is_overdue(now + chrono::Duration::minutes(5), now));
+
+        let completed = StepState::Completed {
+            completed_at: now,
+            result: StepResult::approved(),
+        };
+        assert!(!completed.is_overdue(deadline, now));
+        assert!(!StepState::Pending.is_overdue(deadline, now));
+    }
+
     #[test]
     fn test_step_result_serialization() {
         let result = StepResult::consensus(0.85, "majority_vote");
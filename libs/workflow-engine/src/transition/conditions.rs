@@ -52,6 +52,9 @@ pub struct ConditionContext<'a> {
 
     /// All step states
     pub step_states: &'a HashMap<String, StepState>,
+
+    /// Output of the step being transitioned from, as `annotation.*`
+    pub annotation: Option<&'a serde_json::Value>,
 }
 
 impl<'a> ConditionContext<'a> {
@@ -68,8 +71,16 @@ impl<'a> ConditionContext<'a> {
             consensus_agreement,
             workflow_context,
             step_states,
+            annotation: step_result.and_then(StepResult::output),
         }
     }
+
+    /// Resolve `annotation.*` against `annotation` instead of the step result
+    #[must_use]
+    pub fn with_annotation(mut self, annotation: Option<&'a serde_json::Value>) -> Self {
+        self.annotation = annotation;
+        self
+    }
 }
 
 // =============================================================================
@@ -135,6 +146,8 @@ pub fn evaluate_condition(
 /// Supported expressions:
 /// - `agreement >= 0.8` - Compare agreement score
 /// - `step.X.completed` - Check if step X is completed
+/// - `step.X.output.field == "value"` - Compare a field of step X's output
+/// - `annotation.field == "value"` - Compare a field of the previous step's output
 /// - `context.field == "value"` - Compare context value
 fn evaluate_expression(expr: &str, ctx: &ConditionContext<'_>) -> Result<bool, ConditionError> {
     let expr = expr.trim();
@@ -228,6 +241,38 @@ fn resolve_field_value(
             Ok(json_to_field_value(value))
         }
 
+        _ if field.starts_with("annotation.") => {
+            let value = ctx
+                .annotation
+                .and_then(|annotation| get_json_path(annotation, &field["annotation.".len()..]))
+                .ok_or_else(|| ConditionError::MissingContext(field.to_string()))?;
+            Ok(json_to_field_value(value))
+        }
+
+        _ if field.starts_with("step.") => {
+            let invalid = || ConditionError::InvalidField(field.to_string());
+            let (step_id, path) = field
+                .strip_prefix("step.")
+                .and_then(|rest| rest.split_once('.'))
+                .ok_or_else(invalid)?;
+            let path = match path.strip_prefix("output").ok_or_else(invalid)? {
+                "" => None,
+                rest => Some(rest.strip_prefix('.').ok_or_else(invalid)?),
+            };
+            let output = ctx
+                .step_states
+                .get(step_id)
+                .and_then(StepState::result)
+                .and_then(StepResult::output)
+                .ok_or_else(|| ConditionError::MissingContext(field.to_string()))?;
+            let value = match path {
+                Some(path) => get_json_path(output, path)
+                    .ok_or_else(|| ConditionError::MissingContext(field.to_string()))?,
+                None => output,
+            };
+            Ok(json_to_field_value(value))
+        }
+
         _ => Err(ConditionError::InvalidField(field.to_string())),
     }
 }
@@ -341,6 +386,7 @@ mod tests {
             consensus_agreement: None,
             workflow_context: &EMPTY_CONTEXT,
             step_states: &EMPTY_STATES,
+            annotation: None,
         }
     }
 
@@ -445,8 +491,61 @@ mod tests {
             consensus_agreement: None,
             workflow_context: &workflow_ctx,
             step_states: &states,
+            annotation: None,
         };
 
         assert!(evaluate_condition(&condition, &ctx).unwrap());
     }
+
+    #[test]
+    fn test_expression_step_output_requires_completed_output() {
+        let condition = TransitionConditionConfig {
+            condition_type: "expression".to_string(),
+            expression: Some("step.annotate.output.label == \"x\"".to_string()),
+            threshold: None,
+        };
+
+        let mut states = HashMap::new();
+        states.insert("annotate".to_string(), StepState::Pending);
+
+        let workflow_ctx = serde_json::Value::Null;
+        let ctx = ConditionContext {
+            step_result: None,
+            consensus_agreement: None,
+            workflow_context: &workflow_ctx,
+            step_states: &states,
+            annotation: None,
+        };
+
+        assert!(matches!(
+            evaluate_condition(&condition, &ctx),
+            Err(ConditionError::MissingContext(_))
+        ));
+    }
+
+    #[test]
+    fn test_expression_annotation_reads_submitted_output() {
+        let condition = TransitionConditionConfig {
+            condition_type: "expression".to_string(),
+            expression: Some("annotation.label == \"x\"".to_string()),
+            threshold: None,
+        };
+
+        let submitted = StepResult::submitted(vec![], Some(serde_json::json!({ "label": "x" })));
+        let ctx = ConditionContext {
+            step_result: Some(&submitted),
+            annotation: submitted.output(),
+            ..empty_context()
+        };
+        assert!(evaluate_condition(&condition, &ctx).unwrap());
+
+        let ctx = ConditionContext {
+            step_result: None,
+            ..empty_context()
+        };
+        assert!(matches!(
+            evaluate_condition(&condition, &ctx),
+            Err(ConditionError::MissingContext(_))
+        ));
+    }
 }
@@ -143,7 +143,7 @@ impl<'a> TransitionEvaluator<'a> {
     /// Get the entry step (first step in configuration)
     #[must_use]
     pub fn entry_step(&self) -> Option<&str> {
-        self.workflow_config.steps.first().map(|s| s.id.as_str())
+        self.workflow_config.entry_step_id()
     }
 
     /// Find all steps that can reach a terminal state
@@ -194,6 +194,7 @@ mod tests {
             name: "Test".to_string(),
             workflow_type: WorkflowType::Single,
             settings: WorkflowSettingsConfig::default(),
+            entry_step: None,
             steps: vec![
                 StepConfig {
                     id: "annotate".to_string(),
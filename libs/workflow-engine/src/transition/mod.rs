@@ -5,6 +5,8 @@
 
 pub mod conditions;
 pub mod evaluator;
+pub mod sampling;
 
 pub use conditions::*;
 pub use evaluator::*;
+pub use sampling::*;
@@ -0,0 +1,136 @@
+//! Review sampling
+//!
+//! Review steps can be configured to see only a fraction of annotations.
+//! Selection hashes the annotation ID so the same annotation always gets
+//! the same decision.
+
+use uuid::Uuid;
+
+use glyph_domain::enums::StepType;
+
+use crate::config::WorkflowConfig;
+
+/// Map an ID to a stable point in `[0.0, 1.0)`
+#[must_use]
+pub fn sample_point(id: Uuid) -> f64 {
+    let (hi, lo) = id.as_u64_pair();
+    // splitmix64 finalizer: spreads time-ordered (v7) IDs evenly
+    let mut x = hi ^ lo.rotate_left(32);
+    x = (x ^ (x >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    x = (x ^ (x >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    x ^= x >> 31;
+    // Top 53 bits fit exactly in an f64 mantissa
+    #[allow(clippy::cast_precision_loss)]
+    let point = (x >> 11) as f64 / (1u64 << 53) as f64;
+    point
+}
+
+/// Whether an annotation falls inside the review sample for `rate`.
+///
+/// A rate of 1.0 (or more) reviews everything; 0.0 (or less) reviews nothing.
+#[must_use]
+pub fn in_review_sample(annotation_id: Uuid, rate: f64) -> bool {
+    if rate >= 1.0 {
+        return true;
+    }
+    if rate <= 0.0 {
+        return false;
+    }
+    sample_point(annotation_id) < rate
+}
+
+/// Effective sample rate for a step: the step's own rate, else the
+/// workflow default, else 1.0. Non-review steps always use 1.0.
+#[must_use]
+pub fn review_sample_rate(config: &WorkflowConfig, step_id: &str) -> f64 {
+    config
+        .steps
+        .iter()
+        .find(|s| s.id == step_id && s.step_type == StepType::Review)
+        .and_then(|s| {
+            s.settings
+                .review_sample_rate
+                .or(config.settings.review_sample_rate)
+        })
+        .unwrap_or(1.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::{StepConfig, StepSettingsConfig};
+    use glyph_domain::enums::WorkflowType;
+
+    fn selected_fraction(rate: f64, ids: &[Uuid]) -> f64 {
+        let selected = ids.iter().filter(|id| in_review_sample(**id, rate)).count();
+        #[allow(clippy::cast_precision_loss)]
+        let fraction = selected as f64 / ids.len() as f64;
+        fraction
+    }
+
+    #[test]
+    fn test_fraction_near_configured_rate() {
+        let random: Vec<Uuid> = (0..20_000).map(|_| Uuid::new_v4()).collect();
+        let ordered: Vec<Uuid> = (0..20_000).map(|_| Uuid::now_v7()).collect();
+
+        for rate in [0.1, 0.25, 0.5, 0.9] {
+            for ids in [&random, &ordered] {
+                let fraction = selected_fraction(rate, ids);
+                assert!(
+                    (fraction - rate).abs() < 0.02,
+                    "rate {rate} selected {fraction}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_boundary_rates() {
+        let ids: Vec<Uuid> = (0..1_000).map(|_| Uuid::new_v4()).collect();
+        assert!((selected_fraction(1.0, &ids) - 1.0).abs() < f64::EPSILON);
+        assert!(selected_fraction(0.0, &ids).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn test_selection_is_deterministic() {
+        let id = Uuid::new_v4();
+        let first = in_review_sample(id, 0.5);
+        for _ in 0..10 {
+            assert_eq!(in_review_sample(id, 0.5), first);
+        }
+    }
+
+    #[test]
+    fn test_step_rate_overrides_workflow_default() {
+        let step = |id: &str, step_type, rate| StepConfig {
+            id: id.to_string(),
+            name: id.to_string(),
+            step_type,
+            settings: StepSettingsConfig {
+                review_sample_rate: rate,
+                ..Default::default()
+            },
+            ref_name: None,
+            overrides: None,
+        };
+        let mut config = WorkflowConfig {
+            version: "1.0".to_string(),
+            name: "Sampled".to_string(),
+            workflow_type: WorkflowType::Single,
+            settings: Default::default(),
+            entry_step: None,
+            steps: vec![
+                step("annotate", StepType::Annotation, Some(0.1)),
+                step("review", StepType::Review, None),
+                step("qa", StepType::Review, Some(0.5)),
+            ],
+            transitions: vec![],
+            step_library: vec![],
+        };
+        config.settings.review_sample_rate = Some(0.2);
+
+        assert!((review_sample_rate(&config, "annotate") - 1.0).abs() < f64::EPSILON);
+        assert!((review_sample_rate(&config, "review") - 0.2).abs() < f64::EPSILON);
+        assert!((review_sample_rate(&config, "qa") - 0.5).abs() < f64::EPSILON);
+    }
+}
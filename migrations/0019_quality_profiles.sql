@@ -0,0 +1,23 @@
+-- Per-user quality profiles
+-- Recomputed periodically by the worker's quality job from annotation outcomes.
+-- A user without a row simply has no profile yet (all scores null).
+
+CREATE TABLE quality_profiles (
+    user_id UUID PRIMARY KEY REFERENCES users(user_id) ON DELETE CASCADE,
+    overall_score DOUBLE PRECISION,
+    accuracy_score DOUBLE PRECISION,
+    consistency_score DOUBLE PRECISION,
+    speed_percentile DOUBLE PRECISION,
+    total_annotations BIGINT NOT NULL DEFAULT 0,
+    approved_annotations BIGINT NOT NULL DEFAULT 0,
+    rejected_annotations BIGINT NOT NULL DEFAULT 0,
+    computed_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+
+    CONSTRAINT valid_overall_score CHECK (overall_score IS NULL OR (overall_score >= 0 AND overall_score <= 1)),
+    CONSTRAINT valid_accuracy_score CHECK (accuracy_score IS NULL OR (accuracy_score >= 0 AND accuracy_score <= 1)),
+    CONSTRAINT valid_consistency_score CHECK (consistency_score IS NULL OR (consistency_score >= 0 AND consistency_score <= 1)),
+    CONSTRAINT valid_speed_percentile CHECK (speed_percentile IS NULL OR (speed_percentile >= 0 AND speed_percentile <= 1))
+);
+
+-- Find stale profiles for recomputation
+CREATE INDEX idx_quality_profiles_computed_at ON quality_profiles (computed_at);
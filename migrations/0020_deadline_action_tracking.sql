@@ -0,0 +1,9 @@
+-- Glyph Data Annotation Platform
+-- Migration 0020: Track when a project's deadline action has fired
+-- Purpose: Let the worker's deadline job act once per deadline
+
+ALTER TABLE projects
+ADD COLUMN IF NOT EXISTS deadline_action_fired_at TIMESTAMPTZ;
+
+-- Comments
+COMMENT ON COLUMN projects.deadline_action_fired_at IS 'When the deadline action last fired; re-arms if the deadline moves past it';
@@ -0,0 +1,17 @@
+-- Glyph Data Annotation Platform
+-- Migration 0021: Gold (honeypot) tasks
+-- Purpose: Inject tasks with known answers to measure annotator accuracy
+
+ALTER TABLE tasks
+ADD COLUMN IF NOT EXISTS gold_output JSONB;
+
+CREATE INDEX IF NOT EXISTS idx_tasks_gold ON tasks (project_id) WHERE gold_output IS NOT NULL;
+
+-- Set while a user's gold mismatch rate is above the alert threshold, so the
+-- quality job alerts once per breach rather than on every run
+ALTER TABLE quality_profiles
+ADD COLUMN IF NOT EXISTS gold_alerted_at TIMESTAMPTZ;
+
+-- Comments
+COMMENT ON COLUMN tasks.gold_output IS 'Expected output for gold tasks; NULL for regular tasks';
+COMMENT ON COLUMN quality_profiles.gold_alerted_at IS 'When a gold mismatch alert was raised; cleared once the rate recovers';
@@ -0,0 +1,19 @@
+-- Glyph Data Annotation Platform
+-- Migration 0022: Annotation versions
+-- Purpose: Append-only edit history; every save records a new version
+
+CREATE TABLE annotation_versions (
+    annotation_id   UUID NOT NULL,
+    version         INT NOT NULL,
+    task_id         UUID NOT NULL,
+    user_id         UUID NOT NULL REFERENCES users(user_id),
+    data            JSONB NOT NULL,
+    saved_at        TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+
+    PRIMARY KEY (annotation_id, version),
+    CONSTRAINT valid_version CHECK (version >= 1)
+);
+
+CREATE INDEX idx_annotation_versions_task_user ON annotation_versions (task_id, user_id, saved_at);
+
+COMMENT ON TABLE annotation_versions IS 'Append-only history of annotation saves; rows are never updated';
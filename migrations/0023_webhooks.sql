@@ -0,0 +1,44 @@
+-- Glyph Data Annotation Platform
+-- Migration 0023: Project webhooks
+-- Purpose: Notify downstream systems when task workflows complete
+
+-- =============================================================================
+-- Webhook Configuration (one per project)
+-- =============================================================================
+
+CREATE TABLE project_webhooks (
+    project_id      UUID PRIMARY KEY REFERENCES projects(project_id) ON DELETE CASCADE,
+    url             TEXT NOT NULL,
+    secret          TEXT NOT NULL,
+    is_active       BOOLEAN NOT NULL DEFAULT TRUE,
+    created_at      TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at      TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+-- =============================================================================
+-- Delivery Queue and Log
+-- =============================================================================
+
+CREATE TABLE webhook_deliveries (
+    delivery_id         UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    project_id          UUID NOT NULL REFERENCES projects(project_id) ON DELETE CASCADE,
+    task_id             UUID NOT NULL,
+    event_type          VARCHAR(100) NOT NULL,
+    payload             JSONB NOT NULL,
+    status              VARCHAR(20) NOT NULL DEFAULT 'pending',
+    attempts            INT NOT NULL DEFAULT 0,
+    next_attempt_at     TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    last_status_code    INT,
+    last_error          TEXT,
+    created_at          TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    delivered_at        TIMESTAMPTZ,
+
+    CONSTRAINT valid_delivery_status CHECK (status IN ('pending', 'delivered', 'failed'))
+);
+
+CREATE INDEX idx_webhook_deliveries_due ON webhook_deliveries (next_attempt_at)
+    WHERE status = 'pending';
+CREATE INDEX idx_webhook_deliveries_project ON webhook_deliveries (project_id, created_at DESC);
+
+COMMENT ON TABLE project_webhooks IS 'Per-project webhook endpoint and HMAC signing secret';
+COMMENT ON TABLE webhook_deliveries IS 'Outbound webhook queue; rows are kept as the delivery log';
@@ -0,0 +1,24 @@
+-- Glyph Data Annotation Platform
+-- Migration 0024: Event outbox
+-- Purpose: Publish workflow events to NATS at least once, even if the
+--          process dies between committing the event and publishing it
+
+-- =============================================================================
+-- Outbox (written in the same transaction as workflow_events)
+-- =============================================================================
+
+CREATE TABLE event_outbox (
+    outbox_id       UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    stream_id       UUID NOT NULL,
+    version         BIGINT NOT NULL,
+    subject         VARCHAR(200) NOT NULL,
+    payload         JSONB NOT NULL,
+    created_at      TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    claimed_until   TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    sent_at         TIMESTAMPTZ
+);
+
+CREATE INDEX idx_event_outbox_unsent ON event_outbox (created_at)
+    WHERE sent_at IS NULL;
+
+COMMENT ON TABLE event_outbox IS 'Workflow events awaiting relay to NATS; sent rows are kept for auditing';
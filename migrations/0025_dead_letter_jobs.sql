@@ -0,0 +1,22 @@
+-- Glyph Data Annotation Platform
+-- Migration 0025: Dead-letter queue
+-- Purpose: Keep worker jobs that exhausted their retries so they can be
+--          inspected and requeued instead of silently dropped
+
+CREATE TABLE dead_letter_jobs (
+    dlq_id          UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    job_type        VARCHAR(100) NOT NULL,
+    payload         JSONB NOT NULL,
+    last_error      TEXT NOT NULL,
+    attempts        INT NOT NULL DEFAULT 0,
+    status          VARCHAR(20) NOT NULL DEFAULT 'dead',
+    created_at      TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    requeued_at     TIMESTAMPTZ,
+    retried_at      TIMESTAMPTZ,
+
+    CONSTRAINT valid_dead_letter_status CHECK (status IN ('dead', 'requeued', 'retried'))
+);
+
+CREATE INDEX idx_dead_letter_jobs_status ON dead_letter_jobs (status, created_at DESC);
+
+COMMENT ON TABLE dead_letter_jobs IS 'Worker jobs that failed all retries; requeued rows are retried by the worker';
@@ -0,0 +1,25 @@
+-- Glyph Data Annotation Platform
+-- Migration 0026: Audit events
+-- Purpose: Field-level change history written by AuditWriter
+
+CREATE TYPE audit_action AS ENUM ('create', 'read', 'update', 'delete');
+CREATE TYPE audit_actor_type AS ENUM ('user', 'system', 'api');
+
+CREATE TABLE audit_events (
+    audit_id        UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    entity_type     VARCHAR(50) NOT NULL,
+    entity_id       VARCHAR(100) NOT NULL,
+    action          audit_action NOT NULL,
+    actor_id        VARCHAR(100) NOT NULL,
+    actor_type      audit_actor_type NOT NULL,
+    data_snapshot   JSONB NOT NULL DEFAULT '{}',
+    changes         JSONB,
+    request_id      VARCHAR(100),
+    occurred_at     TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+-- Entity history, newest first
+CREATE INDEX idx_audit_events_entity ON audit_events (entity_type, entity_id, occurred_at DESC);
+CREATE INDEX idx_audit_events_actor ON audit_events (actor_id, occurred_at DESC);
+
+COMMENT ON TABLE audit_events IS 'Append-only audit trail with entity snapshots and field-level diffs';
@@ -0,0 +1,11 @@
+-- Glyph Data Annotation Platform
+-- Migration 0027: Goal progress state
+-- Purpose: Record when a goal's completion action fired and the state of
+--          manually tracked goals, for the goal progress snapshot
+
+ALTER TABLE goals
+    ADD COLUMN manually_completed         BOOLEAN NOT NULL DEFAULT FALSE,
+    ADD COLUMN completion_action_fired_at TIMESTAMPTZ;
+
+COMMENT ON COLUMN goals.manually_completed IS 'Completion state set by hand; only meaningful for manual goals';
+COMMENT ON COLUMN goals.completion_action_fired_at IS 'When the goal''s completion action ran, NULL if it has not';
@@ -0,0 +1,29 @@
+-- Glyph Data Annotation Platform
+-- Migration 0028: Project type schema versions
+-- Purpose: Keep every version of a project type's schemas so annotations can
+--          be read against the schema that was active when they were made
+
+ALTER TABLE project_types
+    ADD COLUMN schema_version INTEGER NOT NULL DEFAULT 1;
+
+CREATE TABLE project_type_schema_versions (
+    project_type_id UUID NOT NULL REFERENCES project_types(project_type_id) ON DELETE CASCADE,
+    version         INTEGER NOT NULL,
+    input_schema    JSONB NOT NULL,
+    output_schema   JSONB NOT NULL,
+    created_at      TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (project_type_id, version)
+);
+
+-- Existing schemas become version 1
+INSERT INTO project_type_schema_versions (project_type_id, version, input_schema, output_schema)
+SELECT project_type_id, 1, input_schema, output_schema
+FROM project_types;
+
+-- Schema version of the project's type when the annotation was created
+ALTER TABLE annotations
+    ADD COLUMN schema_version INTEGER;
+
+COMMENT ON COLUMN project_types.schema_version IS 'Current schema version; incremented whenever input_schema or output_schema changes';
+COMMENT ON TABLE project_type_schema_versions IS 'Every version of each project type''s input and output schemas';
+COMMENT ON COLUMN annotations.schema_version IS 'Project type schema version active at creation, NULL if the project had no type';
@@ -0,0 +1,8 @@
+-- Glyph Data Annotation Platform
+-- Migration 0029: Span overlap policy
+-- Purpose: Let strict project types reject annotations whose spans overlap
+
+ALTER TABLE project_types
+    ADD COLUMN allow_overlapping_spans BOOLEAN NOT NULL DEFAULT TRUE;
+
+COMMENT ON COLUMN project_types.allow_overlapping_spans IS 'Whether spans in one annotation field may overlap; FALSE rejects overlapping submissions';
@@ -0,0 +1,44 @@
+-- Glyph Data Annotation Platform
+-- Migration 0030: Organizations
+-- Purpose: Give projects, teams, project types and data sources an owning
+--          organization so one deployment can serve several tenants
+
+CREATE TABLE organizations (
+    org_id      UUID PRIMARY KEY,
+    name        VARCHAR(255) NOT NULL,
+    slug        VARCHAR(100) NOT NULL UNIQUE,
+    created_at  TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+-- Existing data, single-tenant installs and development use the default org
+INSERT INTO organizations (org_id, name, slug)
+VALUES ('00000000-0000-0000-0000-000000000001', 'Default', 'default');
+
+ALTER TABLE teams
+    ADD COLUMN org_id UUID NOT NULL DEFAULT '00000000-0000-0000-0000-000000000001'
+        REFERENCES organizations(org_id);
+ALTER TABLE projects
+    ADD COLUMN org_id UUID NOT NULL DEFAULT '00000000-0000-0000-0000-000000000001'
+        REFERENCES organizations(org_id);
+ALTER TABLE project_types
+    ADD COLUMN org_id UUID NOT NULL DEFAULT '00000000-0000-0000-0000-000000000001'
+        REFERENCES organizations(org_id);
+ALTER TABLE data_sources
+    ADD COLUMN org_id UUID NOT NULL DEFAULT '00000000-0000-0000-0000-000000000001'
+        REFERENCES organizations(org_id);
+
+-- Project type names only need to be unique within an organization
+ALTER TABLE project_types DROP CONSTRAINT project_types_name_key;
+ALTER TABLE project_types
+    ADD CONSTRAINT project_types_org_name_key UNIQUE (org_id, name);
+
+CREATE INDEX idx_teams_org ON teams (org_id);
+CREATE INDEX idx_projects_org ON projects (org_id);
+CREATE INDEX idx_project_types_org ON project_types (org_id);
+CREATE INDEX idx_data_sources_org ON data_sources (org_id);
+
+COMMENT ON TABLE organizations IS 'Tenants; every project, team, project type and data source belongs to one';
+COMMENT ON COLUMN teams.org_id IS 'Owning organization';
+COMMENT ON COLUMN projects.org_id IS 'Owning organization';
+COMMENT ON COLUMN project_types.org_id IS 'Owning organization; system types are visible to every organization';
+COMMENT ON COLUMN data_sources.org_id IS 'Owning organization, always that of the data source''s project';
@@ -0,0 +1,2 @@
+-- Add 'deleted' value to task_status enum for soft delete support
+ALTER TYPE task_status ADD VALUE IF NOT EXISTS 'deleted';
@@ -0,0 +1,9 @@
+-- Glyph Data Annotation Platform
+-- Migration 0032: Skill level change tracking
+-- Purpose: Record when a certification's proficiency level last changed, so
+-- auto-promotion only counts work done at the current level
+
+ALTER TABLE user_skills
+    ADD COLUMN level_changed_at TIMESTAMPTZ;
+
+COMMENT ON COLUMN user_skills.level_changed_at IS 'When proficiency_level was last changed after certification; NULL if unchanged since certified_at';
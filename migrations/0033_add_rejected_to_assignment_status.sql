@@ -0,0 +1,2 @@
+-- Add 'rejected' value to assignment_status enum for queue rejects
+ALTER TYPE assignment_status ADD VALUE IF NOT EXISTS 'rejected';
@@ -0,0 +1,21 @@
+-- Glyph Data Annotation Platform
+-- Migration 0034: Annotation drafts
+-- Purpose: Keep an annotator's saved progress on a step apart from submitted
+--          annotations, so partial work never advances the workflow
+
+CREATE TABLE annotation_drafts (
+    draft_id        UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    assignment_id   UUID NOT NULL UNIQUE REFERENCES task_assignments(assignment_id) ON DELETE CASCADE,
+    task_id         UUID NOT NULL,
+    step_id         VARCHAR(100) NOT NULL,
+    user_id         UUID NOT NULL REFERENCES users(user_id),
+    data            JSONB NOT NULL,
+    version         INTEGER NOT NULL DEFAULT 1,
+    created_at      TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at      TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_annotation_drafts_task_user ON annotation_drafts (task_id, user_id);
+
+COMMENT ON TABLE annotation_drafts IS 'Saved progress on an assignment; replaced on each save and discarded on final submission';
+COMMENT ON COLUMN annotation_drafts.version IS 'Incremented on every save';
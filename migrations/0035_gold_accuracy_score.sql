@@ -0,0 +1,14 @@
+-- Glyph Data Annotation Platform
+-- Migration 0035: Separate gold accuracy score
+-- Purpose: Keep gold-task accuracy apart from the review approval rate, which
+-- the quality job recomputes into accuracy_score on every run
+
+ALTER TABLE quality_profiles
+ADD COLUMN IF NOT EXISTS gold_accuracy_score DOUBLE PRECISION;
+
+ALTER TABLE quality_profiles
+ADD CONSTRAINT valid_gold_accuracy_score
+CHECK (gold_accuracy_score IS NULL OR (gold_accuracy_score >= 0 AND gold_accuracy_score <= 1));
+
+-- Comments
+COMMENT ON COLUMN quality_profiles.gold_accuracy_score IS 'Share of gold tasks answered correctly; NULL until the user has submitted one';
@@ -0,0 +1,12 @@
+-- Glyph Data Annotation Platform
+-- Migration 0036: Default workflow
+-- Purpose: Record which workflow activation assigns to projects without one
+
+ALTER TABLE workflows
+ADD COLUMN IF NOT EXISTS is_default BOOLEAN NOT NULL DEFAULT FALSE;
+
+-- At most one default
+CREATE UNIQUE INDEX IF NOT EXISTS idx_workflows_single_default ON workflows ((TRUE)) WHERE is_default;
+
+-- Comments
+COMMENT ON COLUMN workflows.is_default IS 'Assigned on activation to projects without a workflow';
@@ -0,0 +1,7 @@
+-- Glyph Data Annotation Platform
+-- Migration 0037: Workflow lookup on workflow events
+-- Purpose: Find the task streams started on a workflow without scanning every event
+
+CREATE INDEX IF NOT EXISTS idx_workflow_events_started_workflow
+    ON workflow_events ((event_data->>'workflow_id'), stream_id)
+    WHERE event_type = 'workflow_started';
@@ -0,0 +1,2 @@
+-- Add 'skill_weighted' value to load_balancing_strategy enum for weighted skill matching
+ALTER TYPE load_balancing_strategy ADD VALUE IF NOT EXISTS 'skill_weighted';
@@ -101,7 +101,8 @@ export type AssignmentMode = "auto" | "manual" | "pool";
 export type LoadBalancingStrategy =
   | "round_robin"
   | "least_loaded"
-  | "quality_weighted";
+  | "quality_weighted"
+  | "skill_weighted";
 
 export type ContributionType = "count" | "quality_metric" | "progress";
 
@@ -148,6 +149,7 @@ export interface UserSkill {
 export interface QualityProfile {
   overall_score?: number;
   accuracy_score?: number;
+  gold_accuracy_score?: number;
   consistency_score?: number;
   speed_percentile?: number;
   total_annotations: number;
@@ -207,6 +209,7 @@ export interface Annotation {
   quality_evaluated_at?: string;
   time_spent_ms?: number;
   client_metadata?: Record<string, unknown>;
+  schema_version?: number;
 }
 
 export interface Project {
@@ -398,11 +401,13 @@ export type ReviewCommentId = string;
 
 /**
  * Draft - Auto-saved annotation work in progress.
- * Only one draft per (task_id, user_id) pair.
+ * Only one draft per (task_id, step_id, user_id).
  */
 export interface Draft {
   draft_id: DraftId;
   task_id: TaskId;
+  /** Workflow step the work is for */
+  step_id: string;
   user_id: UserId;
   /** Annotation data in progress */
   data: Record<string, unknown>;